@@ -0,0 +1,136 @@
+//! Benchmarks del motor de simulación en tiempo virtual.
+//! 
+//! Cada caso ejecuta una línea de tres estaciones con [`Simulation::run_virtual`],
+//! de modo que el tiempo medido corresponde solo al costo del motor
+//! (planificación, canales y métricas) y no a los tiempos de procesamiento
+//! simulados. Los casos se agrupan por algoritmo (`fcfs/1000`, `rr/10000`,
+//! ...) y declaran como throughput la cantidad de eventos de la ejecución,
+//! donde un evento es una porción de procesamiento ejecutada por una
+//! estación, por lo que criterion reporta también el costo por evento.
+//! 
+//! Criterion se encarga del calentamiento, del análisis estadístico y de la
+//! comparación con la ejecución anterior o con una línea base guardada:
+//! 
+//! ```text
+//! cargo bench --features async --bench engine
+//! cargo bench --features async --bench engine -- rr/          # solo Round Robin
+//! cargo bench --features async --bench engine -- --save-baseline antes
+//! cargo bench --features async --bench engine -- --baseline antes
+//! ```
+//! 
+//! Mientras se miden las ejecuciones, el registro de eventos que la
+//! simulación imprime se descarta (en Unix) para no mezclarlo con el
+//! reporte de criterion.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+
+/// Cantidades de productos a simular en cada caso
+const PRODUCT_COUNTS: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// Tiempo de medición por caso (los casos grandes necesitan más para
+/// completar las muestras mínimas)
+fn measurement_time(products: usize) -> Duration {
+    Duration::from_secs(if products >= 100_000 { 60 } else { 10 })
+}
+
+/// Descarta la salida estándar mientras existe.
+#[cfg(unix)]
+struct SilencedStdout {
+    /// Descriptor con la salida estándar original
+    saved: std::os::raw::c_int,
+}
+
+#[cfg(unix)]
+extern "C" {
+    fn dup(fd: std::os::raw::c_int) -> std::os::raw::c_int;
+    fn dup2(old: std::os::raw::c_int, new: std::os::raw::c_int) -> std::os::raw::c_int;
+    fn close(fd: std::os::raw::c_int) -> std::os::raw::c_int;
+}
+
+#[cfg(unix)]
+impl SilencedStdout {
+    fn new() -> Self {
+        use std::io::Write;
+        use std::os::fd::AsRawFd;
+
+        std::io::stdout().flush().expect("No se pudo vaciar la salida estándar");
+        let null = std::fs::File::create("/dev/null").expect("No se pudo abrir /dev/null");
+        // SAFETY: los descriptores son válidos durante las llamadas
+        let saved = unsafe { dup(1) };
+        assert!(saved >= 0 && unsafe { dup2(null.as_raw_fd(), 1) } >= 0, "No se pudo redirigir la salida estándar");
+        Self { saved }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SilencedStdout {
+    fn drop(&mut self) {
+        use std::io::Write;
+
+        let _ = std::io::stdout().flush();
+        // SAFETY: `saved` es un duplicado de la salida estándar original
+        unsafe {
+            dup2(self.saved, 1);
+            close(self.saved);
+        }
+    }
+}
+
+/// Sin redirección fuera de Unix: el registro se mezcla con el reporte.
+#[cfg(not(unix))]
+struct SilencedStdout;
+
+#[cfg(not(unix))]
+impl SilencedStdout {
+    fn new() -> Self {
+        Self
+    }
+}
+
+fn engine(c: &mut Criterion) {
+    let stations: Vec<StationConfig> = ["Corte", "Ensamblaje", "Empaque"]
+        .into_iter()
+        .map(|name| StationConfig::new(name, Duration::from_millis(10)))
+        .collect();
+    let algorithms = [
+        ("fcfs", SchedulingAlgorithm::fcfs()),
+        ("rr", SchedulingAlgorithm::round_robin(Duration::from_millis(2))),
+    ];
+
+    for (label, algorithm) in &algorithms {
+        let mut group = c.benchmark_group(*label);
+        group.sample_size(10);
+        for products in PRODUCT_COUNTS {
+            let arrivals = (0..products).map(|i| Duration::from_millis(i as u64 * 5)).collect();
+            let simulation = Simulation::with_config(stations.clone(), algorithm.clone(), arrivals);
+
+            // Los eventos no dependen de la ejecución: el tiempo es virtual
+            let events: usize = {
+                let _silenced = SilencedStdout::new();
+                let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+                metrics.station_reports.iter().map(|report| report.slices_executed).sum()
+            };
+            group.throughput(Throughput::Elements(events as u64));
+            group.measurement_time(measurement_time(products));
+            group.bench_with_input(BenchmarkId::from_parameter(products), &simulation, |b, simulation| {
+                b.iter_custom(|iterations| {
+                    let _silenced = SilencedStdout::new();
+                    let started = Instant::now();
+                    for _ in 0..iterations {
+                        black_box(simulation.run_virtual().expect("La simulación debe completarse"));
+                    }
+                    started.elapsed()
+                });
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, engine);
+criterion_main!(benches);
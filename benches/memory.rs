@@ -0,0 +1,116 @@
+//! Benchmark de memoria de los modos de almacenamiento de visitas.
+//! 
+//! Cada caso ejecuta una línea de tres estaciones con Round Robin en tiempo
+//! virtual ([`Simulation::run_virtual`]) con [`StorageMode::Full`] y con
+//! [`StorageMode::Compact`], y reporta el pico de memoria del heap mientras
+//! las estaciones procesan los productos (hasta que el colector termina), el
+//! pico total (incluido el cálculo de las métricas finales) y la memoria
+//! que ocupan las métricas. La memoria se mide con un asignador global que
+//! envuelve al del sistema y cuenta los bytes solicitados; los resultados
+//! crecen linealmente con la cantidad de productos.
+//! 
+//! ```text
+//! cargo bench --features async --bench memory > /dev/null
+//! cargo bench --features async --bench memory -- 1000000 > /dev/null
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use assembly_line_simulator::{ProductMetrics, Simulation, SchedulingAlgorithm, Sink, StationConfig, StorageMode};
+
+/// Cantidades de productos a simular en cada caso
+const PRODUCT_COUNTS: [usize; 2] = [10_000, 100_000];
+
+/// Asignador que cuenta los bytes en uso y su máximo.
+struct CountingAllocator;
+
+static IN_USE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+/// Pico registrado cuando el colector terminó, antes de calcular las métricas
+static RUN_PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = unsafe { System.alloc(layout) };
+        if !pointer.is_null() {
+            let in_use = IN_USE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(in_use, Ordering::Relaxed);
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(pointer, layout) };
+        IN_USE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Destino que registra el pico de memoria al terminar el colector.
+struct RunPeakSink;
+
+impl Sink for RunPeakSink {
+    fn on_product(&self, _product: &ProductMetrics) {}
+
+    fn on_finish(&self) {
+        RUN_PEAK.store(PEAK.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+}
+
+/// Convierte bytes a MiB.
+fn mib(bytes: usize) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+fn main() {
+    // Filtros opcionales por nombre de caso (se ignoran las opciones de cargo)
+    let filters: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+
+    let stations: Vec<StationConfig> = ["Corte", "Ensamblaje", "Empaque"]
+        .into_iter()
+        .map(|name| StationConfig::new(name, Duration::from_millis(10)))
+        .collect();
+
+    for products in PRODUCT_COUNTS {
+        for (label, mode) in [("completo", StorageMode::Full), ("compacto", StorageMode::Compact)] {
+            let name = format!("{}/{}", label, products);
+            if !filters.is_empty() && !filters.iter().any(|filter| name.contains(filter.as_str())) {
+                continue;
+            }
+
+            let arrivals = (0..products).map(|i| Duration::from_millis(i as u64 * 5)).collect();
+            let mut simulation = Simulation::with_config(
+                stations.clone(),
+                SchedulingAlgorithm::round_robin(Duration::from_millis(4)),
+                arrivals,
+            );
+            simulation.set_storage_mode(mode).add_sink(RunPeakSink);
+
+            let baseline = IN_USE.load(Ordering::Relaxed);
+            PEAK.store(baseline, Ordering::Relaxed);
+            let started = Instant::now();
+            let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+            let elapsed = started.elapsed();
+            let run_peak = RUN_PEAK.load(Ordering::Relaxed) - baseline;
+            let peak = PEAK.load(Ordering::Relaxed) - baseline;
+            let retained = IN_USE.load(Ordering::Relaxed) - baseline;
+            drop(metrics);
+
+            eprintln!(
+                "{:<16} ejecución {:>8.1} MiB  total {:>8.1} MiB  métricas {:>8.1} MiB  {:>7.2} s",
+                name,
+                mib(run_peak),
+                mib(peak),
+                mib(retained),
+                elapsed.as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! Registra el commit de git del que se compila el simulador para incluirlo
+//! en los metadatos de cada ejecución (`RunMetadata::git_commit`).
+//! 
+//! Fuera de un repositorio (o sin `git` instalado) el commit queda sin
+//! registrar. La variable `SIMULATOR_GIT_COMMIT` permite indicarlo al
+//! compilar desde un paquete fuente.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    println!("cargo:rerun-if-env-changed=SIMULATOR_GIT_COMMIT");
+
+    if env::var("SIMULATOR_GIT_COMMIT").is_ok() {
+        // Cargo ya la expone al compilar la biblioteca
+        return;
+    }
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    if let Some(commit) = commit.map(|commit| commit.trim().to_string()).filter(|commit| !commit.is_empty()) {
+        println!("cargo:rustc-env=SIMULATOR_GIT_COMMIT={}", commit);
+    }
+}
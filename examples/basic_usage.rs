@@ -8,7 +8,7 @@ fn main() {
     // Ejecutar simulación con FCFS
     println!("1. Ejecutando simulación con FCFS...");
     let mut fcfs_simulation = Simulation::new(SchedulingAlgorithm::fcfs());
-    let fcfs_metrics = fcfs_simulation.run();
+    let fcfs_metrics = fcfs_simulation.run().expect("La simulación debe completarse");
     
     println!("\n--- Reporte FCFS ---");
     let fcfs_report = fcfs_simulation.generate_report(&fcfs_metrics);
@@ -18,7 +18,7 @@ fn main() {
     println!("\n2. Ejecutando simulación con Round Robin (300ms)...");
     let rr_algorithm = SchedulingAlgorithm::round_robin(std::time::Duration::from_millis(300));
     let mut rr_simulation = Simulation::new(rr_algorithm);
-    let rr_metrics = rr_simulation.run();
+    let rr_metrics = rr_simulation.run().expect("La simulación debe completarse");
     
     println!("\n--- Reporte Round Robin ---");
     let rr_report = rr_simulation.generate_report(&rr_metrics);
@@ -0,0 +1,33 @@
+/*
+ * Ejemplo de uso del simulador desde C.
+ *
+ *     cargo build --release --features ffi
+ *     cc examples/c/main.c -Iinclude -Ltarget/release -lassembly_line_simulator -o simulador
+ *     LD_LIBRARY_PATH=target/release ./simulador > /dev/null
+ */
+
+#include <stdio.h>
+
+#include "assembly_line_simulator.h"
+
+int main(void) {
+    SimHandle *sim = sim_new(SIM_ROUND_ROBIN, 100);
+    if (sim == NULL) {
+        return 1;
+    }
+
+    sim_add_station(sim, "Corte", 200);
+    sim_add_station(sim, "Empaque", 150);
+    for (uint64_t i = 0; i < 4; i++) {
+        sim_add_product(sim, i * 50);
+    }
+
+    int status = sim_run(sim);
+    const char *json = sim_metrics_json(sim);
+    if (json != NULL) {
+        fprintf(stderr, "%s\n", json);
+    }
+
+    sim_free(sim);
+    return status == SIM_OK ? 0 : 1;
+}
@@ -0,0 +1,54 @@
+//! Mide el costo del propio motor de simulación (sin tiempos de procesamiento).
+//! 
+//! Usa el modelo de trabajo `NoWork` y un quantum muy pequeño para que cada
+//! producto genere muchas porciones, de modo que el tiempo total refleje la
+//! sincronización entre hilos y la actualización de métricas. El resumen se
+//! imprime en stderr para poder descartar el registro de eventos:
+//! 
+//! ```text
+//! cargo run --release --example engine_overhead > /dev/null
+//! ```
+
+use std::time::{Duration, Instant};
+
+use assembly_line_simulator::{NoWork, Simulation, SchedulingAlgorithm, StationConfig};
+
+fn main() {
+    let stations: Vec<StationConfig> = ["Corte", "Ensamblaje", "Empaque"]
+        .into_iter()
+        .map(|name| StationConfig::new(name, Duration::from_millis(10)))
+        .collect();
+
+    let algorithms = [
+        SchedulingAlgorithm::round_robin(Duration::from_millis(1)),
+        SchedulingAlgorithm::earliest_due_date(),
+    ];
+    for (algorithm, products) in algorithms
+        .iter()
+        .flat_map(|algorithm| [500, 2000].map(|products| (algorithm, products)))
+    {
+        let mut simulation = Simulation::with_config(
+            stations.clone(),
+            algorithm.clone(),
+            vec![Duration::ZERO; products],
+        );
+        simulation
+            .set_work_model(NoWork)
+            .set_completion_timeout(Duration::from_secs(30))
+            .set_due_dates((0..products).map(|i| Duration::from_millis(i as u64 % 97)).collect());
+
+        let started = Instant::now();
+        let metrics = simulation.run().expect("La simulación debe completarse");
+        let elapsed = started.elapsed();
+
+        let slices: usize = metrics.station_reports.iter().map(|r| r.slices_executed).sum();
+        eprintln!(
+            "{:<28} {:>5} productos, {:>6} porciones: {:>8.1} ms ({:.2} µs/porción)",
+            algorithm.to_string(),
+            products,
+            slices,
+            elapsed.as_secs_f64() * 1000.0,
+            elapsed.as_secs_f64() * 1e6 / slices as f64
+        );
+    }
+}
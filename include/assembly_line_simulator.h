@@ -0,0 +1,57 @@
+/*
+ * Interfaz C del simulador de línea de ensamblaje.
+ *
+ * Compilar la biblioteca con la feature `ffi`:
+ *
+ *     cargo build --release --features ffi
+ *
+ * y enlazar contra target/release/libassembly_line_simulator.so (o .dylib/.dll).
+ * Debe mantenerse sincronizado con src/ffi.rs (lo verifica un test).
+ */
+
+#ifndef ASSEMBLY_LINE_SIMULATOR_H
+#define ASSEMBLY_LINE_SIMULATOR_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Algoritmos de planificación */
+#define SIM_FCFS 0
+#define SIM_ROUND_ROBIN 1
+#define SIM_EARLIEST_DUE_DATE 2
+#define SIM_CRITICAL_RATIO 3
+
+/* Códigos de retorno */
+#define SIM_OK 0
+#define SIM_SIMULATION_FAILED 1
+#define SIM_INVALID_ARGUMENT (-1)
+
+/* Simulación opaca */
+typedef struct SimHandle SimHandle;
+
+/* Crea una simulación vacía; NULL si el algoritmo o el quantum son inválidos. */
+SimHandle *sim_new(int algorithm, uint64_t quantum_ms);
+
+/* Agrega una estación al final de la línea (el nombre se copia). */
+int sim_add_station(SimHandle *sim, const char *name, uint64_t processing_time_ms);
+
+/* Agrega un producto que llega en el instante indicado. */
+int sim_add_product(SimHandle *sim, uint64_t arrival_ms);
+
+/* Ejecuta la simulación; con SIM_SIMULATION_FAILED quedan métricas parciales. */
+int sim_run(SimHandle *sim);
+
+/* Métricas de la última ejecución como JSON; válido hasta sim_run/sim_free. */
+const char *sim_metrics_json(const SimHandle *sim);
+
+/* Libera la simulación (acepta NULL). */
+void sim_free(SimHandle *sim);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ASSEMBLY_LINE_SIMULATOR_H */
@@ -0,0 +1,402 @@
+//! # Módulo de Planificación del Sistema Operativo
+//! 
+//! Este módulo (feature `affinity`, solo Linux) ofrece un modo experimental
+//! en el que cada hilo de estación se fija a un núcleo de la CPU y, si se
+//! pide, cambia su valor nice antes de atender su primer producto. Al
+//! terminar reporta cómo interactuó la planificación real del sistema
+//! operativo con la línea simulada: en qué núcleos corrió cada estación,
+//! cuántos cambios de contexto voluntarios e involuntarios sufrió y cuánto
+//! duraron realmente sus porciones frente a su duración nominal.
+//! 
+//! Se enlaza directamente con la biblioteca de C del sistema
+//! (`sched_setaffinity`, `setpriority` y `sched_getcpu`), sin dependencias
+//! adicionales; los cambios de contexto se leen de
+//! `/proc/thread-self/status`. Bajar el nice (subir la prioridad) requiere
+//! privilegios: si el sistema lo rechaza, la simulación continúa y el
+//! reporte muestra el error.
+//! 
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use assembly_line_simulator::affinity::ThreadPlacement;
+//! use assembly_line_simulator::{BusyWait, Simulation, SchedulingAlgorithm};
+//! 
+//! // Todas las estaciones compiten por el núcleo 0 y el Empaque cede CPU
+//! let mut simulation = Simulation::new(SchedulingAlgorithm::round_robin(Duration::from_millis(100)));
+//! simulation.set_work_model(BusyWait);
+//! let placement = ThreadPlacement::pinned(vec![0]).with_nice(2, 10);
+//! let (metrics, report) = simulation.run_with_placement(placement).unwrap();
+//! println!("{}", simulation.generate_report(&metrics));
+//! println!("{}", report);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ffi::{c_int, c_uint, c_ulong};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::error::SimulationError;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::product::lock_recover;
+use crate::simulation::Simulation;
+use crate::status::{StationObserver, StationStatus, StatusTransition};
+
+/// Núcleos que caben en un `cpu_set_t` de glibc.
+const CPU_SETSIZE: usize = 1024;
+
+/// Selector de `setpriority` para un proceso; en Linux, con `who = 0`
+/// afecta solo al hilo que llama.
+const PRIO_PROCESS: c_int = 0;
+
+/// Rango de valores nice admitidos por Linux.
+const NICE_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
+extern "C" {
+    fn sched_setaffinity(pid: c_int, cpusetsize: usize, mask: *const c_ulong) -> c_int;
+    fn sched_getcpu() -> c_int;
+    fn setpriority(which: c_int, who: c_uint, prio: c_int) -> c_int;
+}
+
+/// Ubicación de los hilos de las estaciones en la CPU.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::affinity::ThreadPlacement;
+/// 
+/// let placement = ThreadPlacement::pinned(vec![0, 1]).with_nice(2, 5);
+/// assert_eq!(placement.core_for(0), Some(0));
+/// assert_eq!(placement.core_for(3), Some(1));
+/// assert_eq!(placement.nice_for(2), Some(5));
+/// assert_eq!(ThreadPlacement::default().core_for(0), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ThreadPlacement {
+    /// Núcleos a los que se fijan las estaciones: la estación `i` usa
+    /// `cores[i % cores.len()]` (vacío = el sistema elige)
+    pub cores: Vec<usize>,
+    /// Valor nice de las estaciones que lo cambian, por índice
+    pub nice: BTreeMap<usize, i32>,
+}
+
+impl ThreadPlacement {
+    /// Fija las estaciones a los núcleos indicados, repartiéndolas en orden.
+    pub fn pinned(cores: Vec<usize>) -> Self {
+        Self { cores, nice: BTreeMap::new() }
+    }
+
+    /// Fija cada estación a un núcleo distinto mientras alcancen los
+    /// núcleos disponibles; a partir de ahí los reparte en orden.
+    pub fn one_core_per_station() -> Self {
+        let available = std::thread::available_parallelism().map_or(1, usize::from);
+        Self::pinned((0..available).collect())
+    }
+
+    /// Asigna el valor nice de una estación.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el valor está fuera del rango -20 a 19
+    pub fn with_nice(mut self, station: usize, nice: i32) -> Self {
+        assert!(NICE_RANGE.contains(&nice), "El valor nice debe estar entre -20 y 19");
+        self.nice.insert(station, nice);
+        self
+    }
+
+    /// Núcleo al que se fija la estación indicada.
+    pub fn core_for(&self, station: usize) -> Option<usize> {
+        (!self.cores.is_empty()).then(|| self.cores[station % self.cores.len()])
+    }
+
+    /// Valor nice de la estación indicada, si lo cambia.
+    pub fn nice_for(&self, station: usize) -> Option<i32> {
+        self.nice.get(&station).copied()
+    }
+}
+
+/// Interacción de una estación con la planificación del sistema operativo.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StationThreadReport {
+    /// Índice de la estación en la línea
+    pub station: usize,
+    /// Nombre de la estación
+    pub name: String,
+    /// Núcleo al que se pidió fijar el hilo
+    pub core: Option<usize>,
+    /// Valor nice que se pidió para el hilo
+    pub nice: Option<i32>,
+    /// Errores al aplicar la afinidad o el nice
+    pub errors: Vec<String>,
+    /// Núcleos en los que el hilo comenzó alguna porción, en orden
+    pub cpus: Vec<usize>,
+    /// Veces que una porción comenzó en un núcleo distinto del de la anterior
+    pub migrations: usize,
+    /// Cambios de contexto en que el hilo cedió la CPU (esperas, sueños)
+    pub voluntary_switches: u64,
+    /// Cambios de contexto en que el sistema expropió al hilo
+    pub involuntary_switches: u64,
+    /// Porciones ejecutadas
+    pub slices: usize,
+    /// Duración nominal total de las porciones
+    pub nominal: Duration,
+    /// Duración medida total de las porciones
+    pub measured: Duration,
+    /// Mayor exceso de una porción sobre su duración nominal
+    pub max_slice_drift: Duration,
+}
+
+impl StationThreadReport {
+    /// Cuántas veces más duraron las porciones respecto de lo nominal (1.0
+    /// si no hubo porciones).
+    pub fn stretch(&self) -> f64 {
+        if self.nominal.is_zero() {
+            1.0
+        } else {
+            self.measured.as_secs_f64() / self.nominal.as_secs_f64()
+        }
+    }
+}
+
+/// Reporte de la interacción de la línea con la planificación del sistema
+/// operativo, una fila por estación.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OsSchedulingReport {
+    /// Reporte de cada estación, en orden de la línea
+    pub stations: Vec<StationThreadReport>,
+}
+
+impl fmt::Display for OsSchedulingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        writeln!(f, "=== PLANIFICACIÓN DEL SISTEMA OPERATIVO ===")?;
+        writeln!(
+            f,
+            "{:<15} {:>6} {:>5} {:>8} {:>11} {:>10} {:>10} {:>12} {:>12} {:>8}",
+            "Estación", "Núcleo", "Nice", "CPUs", "Migraciones", "Cedidas", "Expropiado", "Nominal", "Medido", "Factor"
+        )?;
+        for station in &self.stations {
+            let cpus: Vec<String> = station.cpus.iter().map(usize::to_string).collect();
+            writeln!(
+                f,
+                "{:<15} {:>6} {:>5} {:>8} {:>11} {:>10} {:>10} {:>12} {:>12} {:>7.2}x",
+                station.name,
+                optional(station.core.map(|core| core.to_string())),
+                optional(station.nice.map(|nice| nice.to_string())),
+                cpus.join(","),
+                station.migrations,
+                station.voluntary_switches,
+                station.involuntary_switches,
+                MetricsCalculator::format_duration(station.nominal),
+                MetricsCalculator::format_duration(station.measured),
+                station.stretch()
+            )?;
+        }
+        for station in &self.stations {
+            for error in &station.errors {
+                writeln!(f, "  {}: {}", station.name, error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Simulation {
+    /// Ejecuta la simulación en hilos con la ubicación indicada y reporta
+    /// cómo los trató la planificación del sistema operativo.
+    /// 
+    /// Cada estación fija su hilo al núcleo y al nice que le corresponden
+    /// antes de atender su primer producto. Solo tiene sentido con el
+    /// backend de hilos: los backends asíncronos atienden todas las
+    /// estaciones desde un mismo hilo.
+    /// 
+    /// # Returns
+    /// 
+    /// Las métricas de la ejecución y el reporte de planificación, o el
+    /// error de la simulación
+    pub fn run_with_placement(
+        &mut self,
+        placement: ThreadPlacement,
+    ) -> Result<(SimulationMetrics, OsSchedulingReport), SimulationError> {
+        let observer = Arc::new(PlacementObserver::new(placement));
+        self.station_observers.push(Arc::clone(&observer) as Arc<dyn StationObserver>);
+        let result = self.run();
+        self.station_observers.pop();
+        let metrics = result?;
+        let report = observer.report(&metrics);
+        Ok((metrics, report))
+    }
+}
+
+/// Cambios de contexto (voluntarios, involuntarios) de un hilo.
+type ContextSwitches = (u64, u64);
+
+/// Observaciones del hilo de una estación.
+#[derive(Debug, Default)]
+struct ThreadObservations {
+    /// Errores al aplicar la ubicación
+    errors: Vec<String>,
+    /// Núcleos en los que comenzó alguna porción
+    cpus: BTreeSet<usize>,
+    /// Núcleo en el que comenzó la última porción
+    last_cpu: Option<usize>,
+    /// Cambios de núcleo entre porciones
+    migrations: usize,
+    /// Cambios de contexto al aplicar la ubicación
+    started: Option<ContextSwitches>,
+    /// Cambios de contexto al terminar la estación
+    finished: Option<ContextSwitches>,
+}
+
+/// Observador que aplica la ubicación desde el hilo de cada estación y
+/// registra cómo lo planifica el sistema operativo.
+struct PlacementObserver {
+    /// Ubicación a aplicar
+    placement: ThreadPlacement,
+    /// Observaciones por índice de estación
+    threads: Mutex<HashMap<usize, ThreadObservations>>,
+}
+
+impl PlacementObserver {
+    fn new(placement: ThreadPlacement) -> Self {
+        Self {
+            placement,
+            threads: Mutex::default(),
+        }
+    }
+
+    /// Aplica la ubicación de la estación al hilo que llama.
+    fn place_current_thread(&self, station: usize) -> ThreadObservations {
+        let mut errors = Vec::new();
+        if let Some(core) = self.placement.core_for(station) {
+            errors.extend(pin_current_thread(core).err());
+        }
+        if let Some(nice) = self.placement.nice_for(station) {
+            errors.extend(set_current_thread_nice(nice).err());
+        }
+        ThreadObservations {
+            errors,
+            started: context_switches(),
+            ..ThreadObservations::default()
+        }
+    }
+
+    /// Combina lo observado con los reportes de las estaciones.
+    fn report(&self, metrics: &SimulationMetrics) -> OsSchedulingReport {
+        let mut threads = lock_recover(&self.threads);
+        let stations = metrics.station_reports
+            .iter()
+            .map(|station| {
+                let observed = threads.remove(&station.index).unwrap_or_default();
+                let switches = observed.started.zip(observed.finished).map(|(started, finished)| {
+                    (finished.0.saturating_sub(started.0), finished.1.saturating_sub(started.1))
+                });
+                StationThreadReport {
+                    station: station.index,
+                    name: station.name.clone(),
+                    core: self.placement.core_for(station.index),
+                    nice: self.placement.nice_for(station.index),
+                    errors: observed.errors,
+                    cpus: observed.cpus.into_iter().collect(),
+                    migrations: observed.migrations,
+                    voluntary_switches: switches.map_or(0, |switches| switches.0),
+                    involuntary_switches: switches.map_or(0, |switches| switches.1),
+                    slices: station.slices_executed,
+                    nominal: station.slice_time,
+                    measured: station.measured_slice_time(),
+                    max_slice_drift: station.max_slice_drift,
+                }
+            })
+            .collect();
+        OsSchedulingReport { stations }
+    }
+}
+
+impl StationObserver for PlacementObserver {
+    fn on_transition(&self, transition: &StatusTransition) {
+        let mut threads = lock_recover(&self.threads);
+        let observed = threads
+            .entry(transition.station_index)
+            .or_insert_with(|| self.place_current_thread(transition.station_index));
+        match transition.to {
+            StationStatus::Busy => {
+                if let Some(cpu) = current_cpu() {
+                    if observed.last_cpu.is_some_and(|last| last != cpu) {
+                        observed.migrations += 1;
+                    }
+                    observed.last_cpu = Some(cpu);
+                    observed.cpus.insert(cpu);
+                }
+            }
+            StationStatus::Down => observed.finished = context_switches(),
+            StationStatus::Idle | StationStatus::Blocked => {}
+        }
+    }
+}
+
+/// Fija el hilo que llama a un núcleo.
+fn pin_current_thread(core: usize) -> Result<(), String> {
+    if core >= CPU_SETSIZE {
+        return Err(format!("el núcleo {} supera el máximo de {}", core, CPU_SETSIZE - 1));
+    }
+    let bits = c_ulong::BITS as usize;
+    let mut mask = [0 as c_ulong; CPU_SETSIZE / c_ulong::BITS as usize];
+    mask[core / bits] |= 1 << (core % bits);
+    // SAFETY: la máscara es un cpu_set_t válido del tamaño indicado y el
+    // pid 0 designa al hilo que llama
+    let result = unsafe { sched_setaffinity(0, std::mem::size_of_val(&mask), mask.as_ptr()) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(format!("no se pudo fijar al núcleo {}: {}", core, io::Error::last_os_error()))
+    }
+}
+
+/// Cambia el valor nice del hilo que llama.
+fn set_current_thread_nice(nice: i32) -> Result<(), String> {
+    // SAFETY: setpriority solo lee sus argumentos
+    let result = unsafe { setpriority(PRIO_PROCESS, 0, nice) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(format!("no se pudo asignar nice {}: {}", nice, io::Error::last_os_error()))
+    }
+}
+
+/// Núcleo en el que corre el hilo que llama.
+fn current_cpu() -> Option<usize> {
+    // SAFETY: sched_getcpu no recibe argumentos
+    usize::try_from(unsafe { sched_getcpu() }).ok()
+}
+
+/// Lee los cambios de contexto del hilo que llama.
+fn context_switches() -> Option<ContextSwitches> {
+    let status = fs::read_to_string("/proc/thread-self/status").ok()?;
+    let field = |name: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.trim().parse().ok())
+    };
+    Some((field("voluntary_ctxt_switches:")?, field("nonvoluntary_ctxt_switches:")?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_thread_runs_on_its_core() {
+        std::thread::spawn(|| {
+            assert_eq!(pin_current_thread(0), Ok(()));
+            assert_eq!(current_cpu(), Some(0));
+            assert!(pin_current_thread(CPU_SETSIZE).is_err());
+
+            let (voluntary, _) = context_switches().expect("Linux expone los cambios de contexto");
+            std::thread::sleep(Duration::from_millis(1));
+            assert!(context_switches().unwrap().0 > voluntary);
+        })
+        .join()
+        .unwrap();
+    }
+}
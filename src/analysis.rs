@@ -0,0 +1,1216 @@
+//! # Módulo de Análisis
+//! 
+//! Este módulo interpreta las métricas de una simulación terminada para
+//! identificar patrones de planificación. Trabaja solo con
+//! [`SimulationMetrics`], por lo que puede aplicarse a cualquier ejecución.
+//! 
+//! ## Efecto convoy
+//! 
+//! En una estación no expropiativa (FCFS), un producto con un servicio largo
+//! retiene la estación mientras varios productos cortos esperan detrás de
+//! él. [`ConvoyDetector`] reconstruye la cola de cada estación a partir de
+//! los tiempos de entrada y salida, encuentra esos convoyes y cuantifica la
+//! espera que el producto largo impuso a los cortos.
+//! 
+//! ## Productos atípicos
+//! 
+//! En corridas grandes los promedios esconden a los productos que sufrieron
+//! la planificación. [`OutlierDetector`] marca los productos cuya espera o
+//! turnaround supera `Q3 + k · IQR` e indica en qué estaciones acumularon
+//! el retraso.
+//! 
+//! ## Comparación entre ejecuciones
+//! 
+//! [`compare_runs`] contrasta la espera y el turnaround por producto de dos
+//! ejecuciones con pruebas de hipótesis (t y U de Mann-Whitney) y el tamaño
+//! del efecto, para que afirmaciones como "Round Robin espera menos que FCFS
+//! en esta línea" no dependan solo de comparar dos promedios.
+//! 
+//! ## Diferencia entre configuraciones
+//! 
+//! [`diff_metrics`] resume cuánto cambió cada métrica agregada entre dos
+//! ejecuciones, típicamente una simulación y una variante creada con
+//! [`Simulation::clone_with`](crate::Simulation::clone_with).
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::metrics::{DistributionMetric, MetricsCalculator, ProductMetrics, SimulationMetrics};
+use crate::scenario::ScenarioDiff;
+
+/// Detector de efecto convoy.
+/// 
+/// Un convoy es un producto líder cuyo servicio en una estación dura al
+/// menos `long_job_ratio` veces el de cada seguidor, con al menos
+/// `min_followers` productos cortos esperando en la cola durante ese
+/// servicio.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{
+///     ConvoyDetector, ProductType, Simulation, SchedulingAlgorithm, StationConfig,
+/// };
+/// 
+/// // Un producto largo llega primero y tres cortos quedan detrás de él
+/// let stations = vec![StationConfig::new("Horno", Duration::from_millis(10))];
+/// let arrivals = vec![Duration::ZERO, Duration::from_millis(5), Duration::from_millis(5), Duration::from_millis(5)];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+/// simulation
+///     .register_product_type(ProductType::new("largo").with_service_time(0, Duration::from_millis(200)))
+///     .set_product_type(1, "largo");
+/// let metrics = simulation.run().expect("La simulación falló");
+/// 
+/// let analysis = ConvoyDetector::new().analyze(&metrics);
+/// assert_eq!(analysis.convoys.len(), 1);
+/// assert_eq!(analysis.convoys[0].leader, 1);
+/// assert_eq!(analysis.convoys[0].followers, vec![2, 3, 4]);
+/// assert!(analysis.total_extra_wait > Duration::from_millis(500));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ConvoyDetector {
+    /// Cuántas veces más largo que cada seguidor debe ser el servicio del líder
+    pub long_job_ratio: f64,
+    /// Número mínimo de productos cortos esperando detrás del líder
+    pub min_followers: usize,
+}
+
+/// Convoy encontrado en una estación.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Convoy {
+    /// Índice de la estación
+    pub station_index: usize,
+    /// ID del producto largo que retuvo la estación
+    pub leader: usize,
+    /// Tiempo de servicio del líder en la estación
+    pub leader_service: Duration,
+    /// IDs de los productos cortos que esperaron detrás del líder
+    pub followers: Vec<usize>,
+    /// Espera total de los seguidores mientras el líder ocupaba la estación
+    pub extra_wait: Duration,
+}
+
+/// Resultado del análisis de efecto convoy.
+#[derive(Clone, Debug, Default)]
+pub struct ConvoyAnalysis {
+    /// Convoyes encontrados, ordenados por estación y momento de inicio
+    pub convoys: Vec<Convoy>,
+    /// Espera atribuible a todos los convoyes
+    pub total_extra_wait: Duration,
+    /// Espera en cola reconstruida de todos los productos analizados
+    pub total_wait: Duration,
+    /// Estaciones omitidas por tener expropiaciones (su intervalo de entrada
+    /// y salida no corresponde a un único servicio)
+    pub skipped_stations: Vec<usize>,
+}
+
+impl ConvoyAnalysis {
+    /// Fracción de la espera total atribuible a convoyes (entre 0 y 1).
+    pub fn convoy_wait_fraction(&self) -> f64 {
+        if self.total_wait.is_zero() {
+            0.0
+        } else {
+            self.total_extra_wait.as_secs_f64() / self.total_wait.as_secs_f64()
+        }
+    }
+}
+
+/// Visita de un producto a una estación reconstruida desde sus métricas.
+struct Visit {
+    product_id: usize,
+    /// Llegada a la cola: salida de la visita anterior de su ruta
+    queued: Duration,
+    /// Inicio del servicio
+    start: Duration,
+    /// Fin del servicio
+    end: Duration,
+}
+
+impl Visit {
+    fn service(&self) -> Duration {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+impl ConvoyDetector {
+    /// Crea un detector con los criterios por defecto: el líder debe durar
+    /// al menos el doble que cada seguidor y tener dos seguidores o más.
+    pub fn new() -> Self {
+        Self {
+            long_job_ratio: 2.0,
+            min_followers: 2,
+        }
+    }
+
+    /// Asigna la proporción mínima entre el servicio del líder y el de
+    /// cada seguidor.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `long_job_ratio` no es mayor que 1
+    pub fn with_long_job_ratio(mut self, long_job_ratio: f64) -> Self {
+        assert!(long_job_ratio > 1.0, "La proporción del líder debe ser mayor que 1");
+        self.long_job_ratio = long_job_ratio;
+        self
+    }
+
+    /// Asigna el número mínimo de seguidores de un convoy.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `min_followers` es cero
+    pub fn with_min_followers(mut self, min_followers: usize) -> Self {
+        assert!(min_followers > 0, "Un convoy requiere al menos un seguidor");
+        self.min_followers = min_followers;
+        self
+    }
+
+    /// Busca convoyes en las métricas de una simulación.
+    /// 
+    /// Solo se analizan los productos completados y las estaciones sin
+    /// expropiaciones: con Round Robin un producto largo no retiene la
+    /// estación, que es justamente lo que evita el efecto convoy.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// Los convoyes encontrados y la espera que se les atribuye
+    pub fn analyze(&self, metrics: &SimulationMetrics) -> ConvoyAnalysis {
+        let mut analysis = ConvoyAnalysis::default();
+        let station_count = metrics
+            .products
+            .iter()
+            .flat_map(|product| &product.visit_times)
+            .map(|&(station, _, _)| station + 1)
+            .max()
+            .unwrap_or(0);
+
+        for station_index in 0..station_count {
+            let preempted = metrics
+                .station_reports
+                .iter()
+                .any(|report| report.index == station_index && report.preemptions > 0);
+            if preempted {
+                analysis.skipped_stations.push(station_index);
+                continue;
+            }
+
+            let mut visits: Vec<Visit> = metrics
+                .products
+                .iter()
+                .flat_map(|product| visits_at(product, station_index))
+                .collect();
+            visits.sort_by_key(|visit| visit.start);
+            analysis.total_wait += visits
+                .iter()
+                .map(|visit| visit.start.saturating_sub(visit.queued))
+                .sum::<Duration>();
+
+            for leader in &visits {
+                if let Some(convoy) = self.convoy_behind(station_index, leader, &visits) {
+                    analysis.total_extra_wait += convoy.extra_wait;
+                    analysis.convoys.push(convoy);
+                }
+            }
+        }
+        analysis
+    }
+
+    /// Construye el convoy que forma `leader`, si cumple los criterios.
+    fn convoy_behind(&self, station_index: usize, leader: &Visit, visits: &[Visit]) -> Option<Convoy> {
+        let leader_service = leader.service();
+        let mut followers = Vec::new();
+        let mut extra_wait = Duration::ZERO;
+
+        for follower in visits {
+            let is_short = follower.service().as_secs_f64() * self.long_job_ratio
+                <= leader_service.as_secs_f64();
+            // Tiempo que el seguidor pasó en cola mientras el líder era atendido
+            let overlap = follower
+                .start
+                .min(leader.end)
+                .saturating_sub(follower.queued.max(leader.start));
+            if follower.product_id != leader.product_id && is_short && !overlap.is_zero() {
+                followers.push(follower.product_id);
+                extra_wait += overlap;
+            }
+        }
+
+        (followers.len() >= self.min_followers).then_some(Convoy {
+            station_index,
+            leader: leader.product_id,
+            leader_service,
+            followers,
+            extra_wait,
+        })
+    }
+}
+
+impl Default for ConvoyDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reconstruye las visitas completadas de un producto a una estación.
+/// 
+/// Los productos pasan directamente de una visita a la cola de la siguiente
+/// de su ruta, por lo que su llegada a la cola es la salida de la visita
+/// anterior (o su llegada a la línea). Con rutas reentrantes un producto
+/// puede visitar la misma estación más de una vez.
+fn visits_at(product: &ProductMetrics, station_index: usize) -> Vec<Visit> {
+    product
+        .visit_times
+        .iter()
+        .enumerate()
+        .filter(|&(_, &(station, _, end))| station == station_index && !end.is_zero())
+        .map(|(position, &(_, start, end))| Visit {
+            product_id: product.product_id,
+            queued: position
+                .checked_sub(1)
+                .map_or(product.arrival_time, |previous| product.visit_times[previous].2),
+            start,
+            end,
+        })
+        .collect()
+}
+
+/// Detector de productos atípicos.
+/// 
+/// Un producto es atípico en una métrica si su valor supera el límite
+/// superior de Tukey, `Q3 + k · IQR`, calculado sobre todos los productos
+/// completados. Solo se buscan valores altos: un producto que esperó mucho
+/// menos que el resto no es un problema de planificación.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{OutlierDetector, ProductType, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// // Las llegadas espaciadas no esperan, salvo las que quedan detrás del producto lento
+/// let stations = vec![StationConfig::new("Horno", Duration::from_millis(10))];
+/// let arrivals = (0..20).map(|i| Duration::from_millis(30 * i)).collect();
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+/// simulation
+///     .register_product_type(ProductType::new("lento").with_service_time(0, Duration::from_millis(100)))
+///     .set_product_type(10, "lento");
+/// let metrics = simulation.run().expect("La simulación falló");
+/// 
+/// let analysis = OutlierDetector::new().detect(&metrics);
+/// assert_eq!(analysis.suspects[0].product_id, 10);
+/// let follower = analysis.suspects.iter().find(|outlier| outlier.product_id == 11).unwrap();
+/// assert_eq!(follower.station_waits[0].0, 0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct OutlierDetector {
+    /// Múltiplo del rango intercuartílico sobre el tercer cuartil
+    pub k: f64,
+}
+
+/// Producto atípico y dónde acumuló su retraso.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outlier {
+    /// ID del producto
+    pub product_id: usize,
+    /// Métricas en las que el producto superó el límite
+    pub metrics: Vec<DistributionMetric>,
+    /// Tiempo total de espera del producto
+    pub total_wait_time: Duration,
+    /// Turnaround del producto
+    pub turnaround_time: Duration,
+    /// Espera en cola por estación `(índice, espera)`, de mayor a menor;
+    /// solo estaciones donde el producto esperó
+    pub station_waits: Vec<(usize, Duration)>,
+}
+
+/// Resultado de la búsqueda de productos atípicos.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OutlierAnalysis {
+    /// Límite de espera por sobre el cual un producto es atípico
+    pub wait_threshold: Duration,
+    /// Límite de turnaround por sobre el cual un producto es atípico
+    pub turnaround_threshold: Duration,
+    /// Productos atípicos, del mayor turnaround al menor
+    pub suspects: Vec<Outlier>,
+}
+
+impl OutlierDetector {
+    /// Número mínimo de productos para estimar los cuartiles.
+    pub const MIN_PRODUCTS: usize = 4;
+
+    /// Crea un detector con el criterio de Tukey habitual (`k = 1.5`).
+    pub fn new() -> Self {
+        Self { k: 1.5 }
+    }
+
+    /// Asigna el múltiplo del rango intercuartílico.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `k` es negativo
+    pub fn with_k(mut self, k: f64) -> Self {
+        assert!(k >= 0.0, "El múltiplo del rango intercuartílico no puede ser negativo");
+        self.k = k;
+        self
+    }
+
+    /// Busca productos atípicos en las métricas de una simulación.
+    /// 
+    /// Con menos de [`OutlierDetector::MIN_PRODUCTS`] productos completados
+    /// los cuartiles no son representativos y no se informa ningún producto.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// Los límites calculados y los productos que los superan
+    pub fn detect(&self, metrics: &SimulationMetrics) -> OutlierAnalysis {
+        if metrics.products.len() < Self::MIN_PRODUCTS {
+            return OutlierAnalysis::default();
+        }
+        let wait_threshold = self.threshold(metrics, DistributionMetric::WaitTime);
+        let turnaround_threshold = self.threshold(metrics, DistributionMetric::Turnaround);
+
+        let mut suspects: Vec<Outlier> = metrics
+            .products
+            .iter()
+            .filter_map(|product| {
+                let exceeded: Vec<DistributionMetric> = [
+                    (DistributionMetric::WaitTime, wait_threshold),
+                    (DistributionMetric::Turnaround, turnaround_threshold),
+                ]
+                .into_iter()
+                .filter(|&(metric, threshold)| metric.value(product) > threshold)
+                .map(|(metric, _)| metric)
+                .collect();
+                (!exceeded.is_empty()).then(|| Outlier {
+                    product_id: product.product_id,
+                    metrics: exceeded,
+                    total_wait_time: product.total_wait_time,
+                    turnaround_time: product.turnaround_time,
+                    station_waits: station_waits(product),
+                })
+            })
+            .collect();
+        suspects.sort_by(|a, b| b.turnaround_time.cmp(&a.turnaround_time).then(a.product_id.cmp(&b.product_id)));
+
+        OutlierAnalysis { wait_threshold, turnaround_threshold, suspects }
+    }
+
+    /// Calcula el límite superior de Tukey de una métrica.
+    fn threshold(&self, metrics: &SimulationMetrics, metric: DistributionMetric) -> Duration {
+        let mut values: Vec<f64> = metrics
+            .products
+            .iter()
+            .map(|product| metric.value(product).as_secs_f64())
+            .collect();
+        values.sort_by(f64::total_cmp);
+        let (q1, q3) = (quantile(&values, 0.25), quantile(&values, 0.75));
+        Duration::from_secs_f64(q3 + self.k * (q3 - q1))
+    }
+}
+
+impl Default for OutlierDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cuantil `q` de valores ordenados, con interpolación lineal entre los
+/// dos valores más cercanos.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Espera en cola de un producto por estación, de mayor a menor, omitiendo
+/// las estaciones donde no esperó.
+fn station_waits(product: &ProductMetrics) -> Vec<(usize, Duration)> {
+    let mut waits: Vec<(usize, Duration)> = product
+        .wait_by_station()
+        .into_iter()
+        .enumerate()
+        .filter(|(_, wait)| !wait.is_zero())
+        .collect();
+    waits.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    waits
+}
+
+/// Prueba t usada para comparar las medias de dos ejecuciones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TTest {
+    /// Prueba t pareada: ambas ejecuciones tienen los mismos productos y se
+    /// compara la diferencia de cada producto consigo mismo
+    Paired,
+    /// Prueba t de Welch para muestras independientes con varianzas distintas
+    Welch,
+}
+
+impl fmt::Display for TTest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Paired => "t pareada",
+            Self::Welch => "t de Welch",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Comparación estadística de una métrica por producto entre dos ejecuciones.
+/// 
+/// Las diferencias se expresan como `b - a` en segundos, por lo que un valor
+/// negativo indica que la ejecución `b` obtuvo tiempos menores.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricComparison {
+    /// Métrica comparada
+    pub metric: DistributionMetric,
+    /// Prueba t aplicada
+    pub test: TTest,
+    /// Productos considerados de cada ejecución (en la prueba pareada, los
+    /// dos valores son el número de pares)
+    pub samples: (usize, usize),
+    /// Media de la ejecución `a`
+    pub mean_a: Duration,
+    /// Media de la ejecución `b`
+    pub mean_b: Duration,
+    /// Diferencia de medias `b - a`, en segundos
+    pub mean_difference: f64,
+    /// Estadístico t
+    pub t_statistic: f64,
+    /// Grados de libertad de la prueba t (fraccionarios en la de Welch)
+    pub degrees_of_freedom: f64,
+    /// Valor p bilateral de la prueba t
+    pub p_value: f64,
+    /// Valor p bilateral de la prueba U de Mann-Whitney (aproximación normal
+    /// con corrección por empates), que no supone distribución normal
+    pub mann_whitney_p_value: f64,
+    /// Tamaño del efecto (d de Cohen) de `b` respecto de `a`; en la prueba
+    /// pareada se estandariza por la desviación de las diferencias
+    pub effect_size: f64,
+}
+
+impl MetricComparison {
+    /// Indica si la diferencia es significativa al nivel `alpha` según la
+    /// prueba t y la de Mann-Whitney a la vez.
+    pub fn is_significant(&self, alpha: f64) -> bool {
+        self.p_value < alpha && self.mann_whitney_p_value < alpha
+    }
+}
+
+impl fmt::Display for MetricComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} vs {} (diferencia {:+.3}s), {} p = {:.4}, Mann-Whitney p = {:.4}, d = {:.2}",
+            self.metric,
+            MetricsCalculator::format_duration(self.mean_a),
+            MetricsCalculator::format_duration(self.mean_b),
+            self.mean_difference,
+            self.test,
+            self.p_value,
+            self.mann_whitney_p_value,
+            self.effect_size
+        )
+    }
+}
+
+/// Comparación estadística entre dos ejecuciones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunComparison {
+    /// Comparación del tiempo de espera por producto
+    pub wait: MetricComparison,
+    /// Comparación del turnaround por producto
+    pub turnaround: MetricComparison,
+}
+
+impl fmt::Display for RunComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.wait)?;
+        write!(f, "{}", self.turnaround)
+    }
+}
+
+/// Compara estadísticamente la espera y el turnaround por producto de dos
+/// ejecuciones.
+/// 
+/// Si ambas ejecuciones completaron exactamente los mismos productos (por
+/// ejemplo, la misma línea y llegadas con otro algoritmo) se aplica una
+/// prueba t pareada por ID de producto; si no, una prueba t de Welch. En
+/// ambos casos se informa además la prueba U de Mann-Whitney y el tamaño del
+/// efecto. Para comparar varias réplicas de cada configuración basta con
+/// reunir sus productos en un solo [`SimulationMetrics`] por configuración.
+/// 
+/// Con menos de dos productos por ejecución no hay varianza que estimar: los
+/// valores p son 1.0 y el tamaño del efecto es 0.0.
+/// 
+/// # Arguments
+/// 
+/// * `a` - Métricas de la primera ejecución (referencia)
+/// * `b` - Métricas de la segunda ejecución
+/// 
+/// # Returns
+/// 
+/// `RunComparison` con los valores p y el tamaño del efecto de cada métrica
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{compare_runs, TTest, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let run = |delay_ms: u64| {
+///     let stations = vec![StationConfig::new("Corte", Duration::from_millis(delay_ms))];
+///     let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 6]);
+///     simulation.run().expect("La simulación falló")
+/// };
+/// 
+/// let comparison = compare_runs(&run(20), &run(5));
+/// assert_eq!(comparison.wait.test, TTest::Paired);
+/// assert!(comparison.wait.mean_difference < 0.0);
+/// assert!(comparison.turnaround.p_value < 0.05);
+/// ```
+pub fn compare_runs(a: &SimulationMetrics, b: &SimulationMetrics) -> RunComparison {
+    RunComparison {
+        wait: compare_metric(a, b, DistributionMetric::WaitTime),
+        turnaround: compare_metric(a, b, DistributionMetric::Turnaround),
+    }
+}
+
+/// Compara una métrica por producto entre dos ejecuciones.
+fn compare_metric(a: &SimulationMetrics, b: &SimulationMetrics, metric: DistributionMetric) -> MetricComparison {
+    let values = |metrics: &SimulationMetrics| {
+        let mut values: Vec<(usize, f64)> = metrics
+            .products
+            .iter()
+            .map(|product| (product.product_id, metric.value(product).as_secs_f64()))
+            .collect();
+        values.sort_by_key(|&(id, _)| id);
+        values
+    };
+    let (values_a, values_b) = (values(a), values(b));
+    let paired = values_a.len() == values_b.len()
+        && values_a.iter().zip(&values_b).all(|((id_a, _), (id_b, _))| id_a == id_b);
+    let sample_a: Vec<f64> = values_a.iter().map(|&(_, value)| value).collect();
+    let sample_b: Vec<f64> = values_b.iter().map(|&(_, value)| value).collect();
+
+    let (test, t_statistic, degrees_of_freedom, p_value, effect_size) = if paired {
+        let differences: Vec<f64> = sample_a.iter().zip(&sample_b).map(|(a, b)| b - a).collect();
+        let (t, df, p) = paired_t_test(&differences);
+        (TTest::Paired, t, df, p, standardized(mean(&differences), variance(&differences).sqrt()))
+    } else {
+        let (t, df, p) = welch_t_test(&sample_a, &sample_b);
+        let pooled = ((variance(&sample_a) + variance(&sample_b)) / 2.0).sqrt();
+        (TTest::Welch, t, df, p, standardized(mean(&sample_b) - mean(&sample_a), pooled))
+    };
+
+    MetricComparison {
+        metric,
+        test,
+        samples: (sample_a.len(), sample_b.len()),
+        mean_a: Duration::from_secs_f64(mean(&sample_a)),
+        mean_b: Duration::from_secs_f64(mean(&sample_b)),
+        mean_difference: mean(&sample_b) - mean(&sample_a),
+        t_statistic,
+        degrees_of_freedom,
+        p_value,
+        mann_whitney_p_value: mann_whitney_p_value(&sample_a, &sample_b),
+        effect_size,
+    }
+}
+
+/// Cambio de una métrica agregada entre dos ejecuciones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricDelta {
+    /// Nombre de la métrica
+    pub name: String,
+    /// Valor en la ejecución `a` (los tiempos, en segundos)
+    pub a: f64,
+    /// Valor en la ejecución `b` (los tiempos, en segundos)
+    pub b: f64,
+}
+
+impl MetricDelta {
+    /// Obtiene la diferencia `b - a`.
+    pub fn difference(&self) -> f64 {
+        self.b - self.a
+    }
+
+    /// Obtiene el cambio relativo `(b - a) / a`, o `None` si `a` es cero.
+    pub fn relative_change(&self) -> Option<f64> {
+        (self.a != 0.0).then(|| self.difference() / self.a.abs())
+    }
+}
+
+impl fmt::Display for MetricDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:.3} -> {:.3} ({:+.3}", self.name, self.a, self.b, self.difference())?;
+        match self.relative_change() {
+            Some(change) => write!(f, ", {:+.1}%)", change * 100.0),
+            None => write!(f, ")"),
+        }
+    }
+}
+
+/// Reporte de diferencias entre las métricas agregadas de dos ejecuciones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricsDiff {
+    /// Cambio de cada métrica, en el orden del reporte
+    pub deltas: Vec<MetricDelta>,
+    /// Cambios de configuración entre las ejecuciones, si se adjuntaron
+    pub scenario: Option<ScenarioDiff>,
+}
+
+impl MetricsDiff {
+    /// Adjunta los cambios de configuración que explican las diferencias;
+    /// el reporte los muestra antes de las métricas.
+    pub fn with_scenario(mut self, scenario: ScenarioDiff) -> Self {
+        self.scenario = Some(scenario);
+        self
+    }
+
+    /// Busca el cambio de una métrica por su nombre.
+    pub fn get(&self, name: &str) -> Option<&MetricDelta> {
+        self.deltas.iter().find(|delta| delta.name == name)
+    }
+}
+
+impl fmt::Display for MetricsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(scenario) = &self.scenario {
+            writeln!(f, "{}", scenario)?;
+        }
+        writeln!(f, "=== DIFERENCIAS (a -> b) ===")?;
+        for delta in &self.deltas {
+            writeln!(f, "{}", delta)?;
+        }
+        Ok(())
+    }
+}
+
+/// Calcula el cambio de las métricas agregadas entre dos ejecuciones.
+/// 
+/// Incluye la espera, el turnaround y el slowdown promedio, el throughput,
+/// la duración total, el índice de equidad, la tardanza total, los
+/// productos completados y la utilización de cada estación presente en
+/// ambas ejecuciones (por índice). A diferencia de [`compare_runs`], no
+/// evalúa si las diferencias son significativas. Para indicar en el reporte
+/// qué cambió entre ambas configuraciones, adjunte un [`ScenarioDiff`] con
+/// [`MetricsDiff::with_scenario`].
+/// 
+/// # Arguments
+/// 
+/// * `a` - Métricas de la primera ejecución (referencia)
+/// * `b` - Métricas de la segunda ejecución
+/// 
+/// # Returns
+/// 
+/// `MetricsDiff` con el valor de cada métrica en ambas ejecuciones
+pub fn diff_metrics(a: &SimulationMetrics, b: &SimulationMetrics) -> MetricsDiff {
+    let delta = |name: &str, value: fn(&SimulationMetrics) -> f64| MetricDelta {
+        name: name.to_string(),
+        a: value(a),
+        b: value(b),
+    };
+    let mut deltas = vec![
+        delta("Tiempo promedio de espera", |m| m.average_wait_time.as_secs_f64()),
+        delta("Tiempo promedio de turnaround", |m| m.average_turnaround_time.as_secs_f64()),
+        delta("Slowdown promedio", |m| m.average_slowdown),
+        delta("Throughput", |m| m.throughput),
+        delta("Tiempo total de simulación", |m| m.total_simulation_time.as_secs_f64()),
+        delta("Índice de equidad", |m| m.fairness_index),
+        delta("Tardanza total", |m| m.total_tardiness.as_secs_f64()),
+        delta("Productos completados", |m| m.products.len() as f64),
+    ];
+    deltas.extend(a.station_reports.iter().zip(&b.station_reports).map(|(report_a, report_b)| MetricDelta {
+        name: format!("Utilización {}", report_a.name),
+        a: report_a.utilization(),
+        b: report_b.utilization(),
+    }));
+    MetricsDiff { deltas, scenario: None }
+}
+
+/// Media de una muestra (0 si está vacía).
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Varianza muestral (con denominador `n - 1`; 0 con menos de dos valores).
+fn variance(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = mean(values);
+    values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+/// Divide una diferencia por una desviación; sin dispersión, cualquier
+/// diferencia es infinitamente grande.
+fn standardized(difference: f64, deviation: f64) -> f64 {
+    if deviation > 0.0 {
+        difference / deviation
+    } else if difference == 0.0 {
+        0.0
+    } else {
+        difference.signum() * f64::INFINITY
+    }
+}
+
+/// Prueba t pareada sobre las diferencias por producto.
+/// 
+/// Devuelve `(t, grados de libertad, valor p bilateral)`.
+fn paired_t_test(differences: &[f64]) -> (f64, f64, f64) {
+    let n = differences.len();
+    if n < 2 {
+        return (0.0, 0.0, 1.0);
+    }
+    let standard_error = (variance(differences) / n as f64).sqrt();
+    t_test_result(mean(differences), standard_error, (n - 1) as f64)
+}
+
+/// Prueba t de Welch para dos muestras independientes.
+/// 
+/// Devuelve `(t, grados de libertad, valor p bilateral)`; los grados de
+/// libertad siguen la aproximación de Welch-Satterthwaite.
+fn welch_t_test(a: &[f64], b: &[f64]) -> (f64, f64, f64) {
+    if a.len() < 2 || b.len() < 2 {
+        return (0.0, 0.0, 1.0);
+    }
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    let (va, vb) = (variance(a) / na, variance(b) / nb);
+    let standard_error = (va + vb).sqrt();
+    let degrees_of_freedom = if standard_error > 0.0 {
+        (va + vb).powi(2) / (va * va / (na - 1.0) + vb * vb / (nb - 1.0))
+    } else {
+        na + nb - 2.0
+    };
+    t_test_result(mean(b) - mean(a), standard_error, degrees_of_freedom)
+}
+
+/// Calcula el estadístico t y su valor p bilateral.
+fn t_test_result(difference: f64, standard_error: f64, degrees_of_freedom: f64) -> (f64, f64, f64) {
+    if standard_error == 0.0 {
+        // Sin dispersión la diferencia es exacta: o no hay ninguna o es segura
+        let p_value = if difference == 0.0 { 1.0 } else { 0.0 };
+        return (standardized(difference, 0.0), degrees_of_freedom, p_value);
+    }
+    let t = difference / standard_error;
+    let x = degrees_of_freedom / (degrees_of_freedom + t * t);
+    (t, degrees_of_freedom, regularized_incomplete_beta(degrees_of_freedom / 2.0, 0.5, x))
+}
+
+/// Valor crítico bilateral de la distribución t de Student: el `t` tal que
+/// `P(|T| > t) = 1 - confidence` con los grados de libertad indicados.
+/// 
+/// Se obtiene por bisección sobre el valor p de [`t_test_result`]; sin
+/// grados de libertad devuelve infinito.
+pub(crate) fn t_critical(confidence: f64, degrees_of_freedom: f64) -> f64 {
+    if degrees_of_freedom <= 0.0 {
+        return f64::INFINITY;
+    }
+    let alpha = 1.0 - confidence;
+    let (mut low, mut high) = (0.0, 1e4);
+    for _ in 0..200 {
+        let t = (low + high) / 2.0;
+        if t_test_result(t, 1.0, degrees_of_freedom).2 > alpha {
+            low = t;
+        } else {
+            high = t;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// Valor p bilateral de la prueba U de Mann-Whitney.
+/// 
+/// Usa la aproximación normal con corrección por continuidad y por empates,
+/// adecuada a partir de unos ocho valores por muestra.
+fn mann_whitney_p_value(a: &[f64], b: &[f64]) -> f64 {
+    let (na, nb) = (a.len() as f64, b.len() as f64);
+    if a.is_empty() || b.is_empty() {
+        return 1.0;
+    }
+
+    // Rangos promedio de la muestra combinada
+    let mut combined: Vec<(f64, bool)> = a.iter().map(|&value| (value, true))
+        .chain(b.iter().map(|&value| (value, false)))
+        .collect();
+    combined.sort_by(|x, y| x.0.total_cmp(&y.0));
+    let mut rank_sum_a = 0.0;
+    let mut tie_correction = 0.0;
+    let mut start = 0;
+    while start < combined.len() {
+        let end = combined[start..].iter().position(|&(value, _)| value != combined[start].0)
+            .map_or(combined.len(), |offset| start + offset);
+        let ties = (end - start) as f64;
+        let rank = (start + end + 1) as f64 / 2.0;
+        rank_sum_a += rank * combined[start..end].iter().filter(|&&(_, in_a)| in_a).count() as f64;
+        tie_correction += ties.powi(3) - ties;
+        start = end;
+    }
+
+    let n = na + nb;
+    let u = rank_sum_a - na * (na + 1.0) / 2.0;
+    let variance = na * nb / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)));
+    if variance <= 0.0 {
+        return 1.0;
+    }
+    let z = ((u - na * nb / 2.0).abs() - 0.5).max(0.0) / variance.sqrt();
+    complementary_error_function(z / std::f64::consts::SQRT_2)
+}
+
+/// Logaritmo de la función gamma (aproximación de Lanczos).
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let series = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .fold(1.000_000_000_190_015, |sum, (index, coefficient)| sum + coefficient / (x + 1.0 + index as f64));
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+/// Función beta incompleta regularizada `I_x(a, b)`.
+fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front = (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // La fracción continua converge rápido a un lado del punto de simetría
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * incomplete_beta_fraction(a, b, x) / a
+    } else {
+        1.0 - front * incomplete_beta_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Fracción continua de la función beta incompleta (método de Lentz).
+fn incomplete_beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 200;
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            result *= d * c;
+        }
+        if (d * c - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    result
+}
+
+/// Función de error complementaria (error relativo menor que 1.2e-7).
+fn complementary_error_function(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * x.abs());
+    let polynomial = [
+        -1.265_512_23, 1.000_023_68, 0.374_091_96, 0.096_784_18, -0.186_288_06,
+        0.278_868_07, -1.135_203_98, 1.488_515_87, -0.822_152_23, 0.170_872_77,
+    ]
+    .iter()
+    .rev()
+    .fold(0.0, |sum, coefficient| sum * t + coefficient);
+    let result = t * (-x * x + polynomial).exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::clock::SimTime;
+    use crate::product::{TimelineEvent, TimelineSpan};
+    use crate::provenance::RunMetadata;
+    use crate::station::StationReport;
+
+    fn product(id: usize, arrival_ms: u64, station_times_ms: &[(u64, u64)]) -> ProductMetrics {
+        ProductMetrics {
+            product_id: id,
+            class: None,
+            metadata: Default::default(),
+            arrival_time: Duration::from_millis(arrival_ms),
+            total_wait_time: Duration::ZERO,
+            dependency_wait_time: Duration::ZERO,
+            resource_wait_time: Duration::ZERO,
+            turnaround_time: Duration::ZERO,
+            timer_drift: Duration::ZERO,
+            service_time: Duration::ZERO,
+            slowdown: 1.0,
+            due_date: None,
+            tardiness: Duration::ZERO,
+            station_times: station_times_ms
+                .iter()
+                .map(|&(entry, exit)| (Duration::from_millis(entry), Duration::from_millis(exit)))
+                .collect(),
+            visit_times: station_times_ms
+                .iter()
+                .enumerate()
+                .map(|(station, &(entry, exit))| {
+                    (station, Duration::from_millis(entry), Duration::from_millis(exit))
+                })
+                .collect(),
+            visit_resources: Vec::new(),
+            jockeys: 0,
+            slices: Vec::new(),
+            slice_units: Vec::new(),
+            slice_counts: Vec::new(),
+            timeline: Vec::new(),
+            config_epoch: 0,
+            loss: None,
+            replaces: None,
+            replaced_by: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    fn metrics(products: Vec<ProductMetrics>) -> SimulationMetrics {
+        SimulationMetrics {
+            completion_order: products.iter().map(|product| product.product_id).collect(),
+            completion_order_divergence: 0.0,
+            products,
+            average_wait_time: Duration::ZERO,
+            average_dependency_wait_time: Duration::ZERO,
+            average_resource_wait_time: Duration::ZERO,
+            average_turnaround_time: Duration::ZERO,
+            average_slowdown: 0.0,
+            max_slowdown: 0.0,
+            total_simulation_time: Duration::from_secs(1),
+            throughput: 0.0,
+            fairness_index: 1.0,
+            total_tardiness: Duration::ZERO,
+            average_tardiness: Duration::ZERO,
+            max_lateness: None,
+            tardy_jobs: 0,
+            station_reports: Vec::new(),
+            incomplete_products: Vec::new(),
+            lost_products: Vec::new(),
+            steady_state: None,
+            synchronization: String::new(),
+            periods: Vec::new(),
+            config_epochs: Vec::new(),
+            sla_results: Vec::new(),
+            takt: None,
+            resource_units: Vec::new(),
+            jockeying: None,
+            release_plan: None,
+            by_class: HashMap::new(),
+            inter_departure_times: Vec::new(),
+            arrival_flow: Default::default(),
+            departure_flow: Default::default(),
+            run_metadata: RunMetadata::capture(),
+            tick_length: SimTime::DEFAULT_TICK_LENGTH,
+        }
+    }
+
+    #[test]
+    fn test_convoy_at_second_station() {
+        // Estación 0 sin cola; en la estación 1 el producto 1 (500 ms) retiene
+        // a los productos 2 y 3 (50 ms cada uno)
+        let metrics = metrics(vec![
+            product(1, 0, &[(0, 100), (100, 600)]),
+            product(2, 100, &[(100, 200), (600, 650)]),
+            product(3, 200, &[(200, 300), (650, 700)]),
+        ]);
+
+        let analysis = ConvoyDetector::new().analyze(&metrics);
+        assert_eq!(analysis.convoys.len(), 1);
+        let convoy = &analysis.convoys[0];
+        assert_eq!((convoy.station_index, convoy.leader), (1, 1));
+        assert_eq!(convoy.followers, vec![2, 3]);
+        // 400 ms del producto 2 y 300 ms del producto 3 detrás del líder
+        assert_eq!(convoy.extra_wait, Duration::from_millis(700));
+        // El producto 3 además esperó 50 ms detrás del producto 2
+        assert_eq!(analysis.total_wait, Duration::from_millis(750));
+        assert!((analysis.convoy_wait_fraction() - 700.0 / 750.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_convoy_without_enough_short_followers() {
+        // Servicios similares: nadie es el doble de largo que sus seguidores
+        let metrics = metrics(vec![
+            product(1, 0, &[(0, 100)]),
+            product(2, 0, &[(100, 180)]),
+            product(3, 0, &[(180, 260)]),
+        ]);
+        assert!(ConvoyDetector::new().analyze(&metrics).convoys.is_empty());
+        assert_eq!(
+            ConvoyDetector::new().with_long_job_ratio(1.2).with_min_followers(1).analyze(&metrics).convoys.len(),
+            1
+        );
+    }
+
+    fn timed(id: usize, wait_ms: u64) -> ProductMetrics {
+        ProductMetrics {
+            total_wait_time: Duration::from_millis(wait_ms),
+            turnaround_time: Duration::from_millis(wait_ms + 100),
+            ..product(id, 0, &[])
+        }
+    }
+
+    #[test]
+    fn test_t_and_normal_p_values() {
+        // Valores de tabla: t = 2.0 y t = 2.228 con 10 grados de libertad
+        assert!((t_test_result(2.0, 1.0, 10.0).2 - 0.073_39).abs() < 1e-4);
+        assert!((t_test_result(2.228, 1.0, 10.0).2 - 0.05).abs() < 5e-4);
+        assert!((complementary_error_function(1.96 / std::f64::consts::SQRT_2) - 0.05).abs() < 1e-4);
+        assert_eq!(t_test_result(0.0, 0.0, 4.0).2, 1.0);
+        assert_eq!(t_test_result(1.0, 0.0, 4.0).2, 0.0);
+        // Valores críticos de tabla al 95 %
+        assert!((t_critical(0.95, 4.0) - 2.776).abs() < 1e-3);
+        assert!((t_critical(0.95, 10.0) - 2.228).abs() < 1e-3);
+        assert!(t_critical(0.95, 0.0).is_infinite());
+    }
+
+    #[test]
+    fn test_compare_runs_paired_by_product() {
+        let a = metrics((1..=5).map(|id| timed(id, id as u64 * 1000)).collect());
+        let b = metrics(vec![timed(1, 2000), timed(2, 2500), timed(3, 4000), timed(4, 6000), timed(5, 5500)]);
+
+        let comparison = compare_runs(&a, &b).wait;
+        assert_eq!(comparison.test, TTest::Paired);
+        assert_eq!(comparison.samples, (5, 5));
+        assert!((comparison.mean_difference - 1.0).abs() < 1e-9);
+        // Diferencias 1, 0.5, 1, 2, 0.5: t = 1 / sqrt(0.375 / 5) con 4 grados de libertad
+        assert!((comparison.t_statistic - 3.6515).abs() < 1e-3);
+        assert_eq!(comparison.degrees_of_freedom, 4.0);
+        assert!(comparison.p_value > 0.02 && comparison.p_value < 0.025);
+        assert!((comparison.effect_size - 1.0 / 0.375f64.sqrt()).abs() < 1e-9);
+
+        let same = compare_runs(&a, &a);
+        assert_eq!((same.turnaround.p_value, same.turnaround.effect_size), (1.0, 0.0));
+        assert!(!same.turnaround.is_significant(0.05));
+    }
+
+    #[test]
+    fn test_compare_runs_independent_samples() {
+        let a = metrics((1..=5).map(|id| timed(id, id as u64 * 1000)).collect());
+        let b = metrics([3, 4, 6, 7, 9, 10].iter().zip(10..).map(|(&wait, id)| timed(id, wait * 1000)).collect());
+
+        let comparison = compare_runs(&a, &b).wait;
+        assert_eq!(comparison.test, TTest::Welch);
+        assert_eq!(comparison.samples, (5, 6));
+        // Medias 3 y 6.5, varianzas 2.5 y 7.5: t = 3.5 / sqrt(0.5 + 1.25)
+        assert!((comparison.t_statistic - 2.6458).abs() < 1e-3);
+        assert!((comparison.degrees_of_freedom - 8.1667).abs() < 1e-3);
+        assert!(comparison.p_value > 0.02 && comparison.p_value < 0.04);
+        // U = 4 con dos empates: z = 10.5 / sqrt(29.727)
+        assert!((comparison.mann_whitney_p_value - 0.0541).abs() < 1e-3);
+        assert!(comparison.is_significant(0.1) && !comparison.is_significant(0.05));
+    }
+
+    #[test]
+    fn test_diff_metrics_reports_changes() {
+        let a = SimulationMetrics {
+            average_wait_time: Duration::from_millis(400),
+            station_reports: vec![StationReport {
+                name: "Corte".to_string(),
+                busy_time: Duration::from_millis(500),
+                idle_time: Duration::from_millis(500),
+                ..StationReport::default()
+            }],
+            ..metrics((1..=4).map(|id| timed(id, 400)).collect())
+        };
+        let b = SimulationMetrics {
+            average_wait_time: Duration::from_millis(300),
+            station_reports: vec![StationReport {
+                name: "Corte".to_string(),
+                busy_time: Duration::from_millis(500),
+                ..StationReport::default()
+            }],
+            ..metrics((1..=3).map(|id| timed(id, 300)).collect())
+        };
+
+        let diff = diff_metrics(&a, &b);
+        let wait = diff.get("Tiempo promedio de espera").unwrap();
+        assert!((wait.difference() + 0.1).abs() < 1e-9);
+        assert!((wait.relative_change().unwrap() + 0.25).abs() < 1e-9);
+        assert_eq!(wait.to_string(), "Tiempo promedio de espera: 0.400 -> 0.300 (-0.100, -25.0%)");
+        assert_eq!(diff.get("Productos completados").unwrap().difference(), -1.0);
+        assert_eq!(diff.get("Throughput").unwrap().relative_change(), None);
+        assert_eq!(diff.get("Utilización Corte").unwrap().b, 1.0);
+        assert!(diff.to_string().starts_with("=== DIFERENCIAS (a -> b) ===\n"));
+    }
+
+    #[test]
+    fn test_outliers_beyond_tukey_fence() {
+        assert_eq!(quantile(&[1.0, 2.0, 3.0, 4.0, 5.0], 0.25), 2.0);
+        assert_eq!(quantile(&[1.0, 2.0, 3.0, 4.0], 0.75), 3.25);
+
+        let queued = |station, start_ms, end_ms| TimelineSpan {
+            station,
+            event: TimelineEvent::Queued,
+            start: Duration::from_millis(start_ms),
+            end: Duration::from_millis(end_ms),
+        };
+        let mut products: Vec<ProductMetrics> = (1..=7).map(|id| timed(id, 10 + id as u64)).collect();
+        products.push(ProductMetrics {
+            timeline: vec![queued(0, 0, 100), queued(1, 200, 700), queued(0, 800, 850)],
+            ..timed(8, 650)
+        });
+        let metrics = metrics(products);
+
+        // Esperas 11..17 y 650 ms: Q1 = 12.75, Q3 = 16.25, límite 21.5 ms
+        let analysis = OutlierDetector::new().detect(&metrics);
+        assert_eq!(analysis.wait_threshold, Duration::from_micros(21_500));
+        assert_eq!(analysis.suspects.len(), 1);
+        let outlier = &analysis.suspects[0];
+        assert_eq!(outlier.product_id, 8);
+        assert_eq!(outlier.metrics, vec![DistributionMetric::WaitTime, DistributionMetric::Turnaround]);
+        assert_eq!(
+            outlier.station_waits,
+            vec![(1, Duration::from_millis(500)), (0, Duration::from_millis(150))]
+        );
+
+        // Con k muy grande no hay atípicos; con pocos productos no se evalúa
+        assert!(OutlierDetector::new().with_k(200.0).detect(&metrics).suspects.is_empty());
+        let few = self::metrics(vec![timed(1, 0), timed(2, 0), timed(3, 1000)]);
+        assert!(OutlierDetector::new().detect(&few).suspects.is_empty());
+    }
+}
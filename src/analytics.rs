@@ -0,0 +1,258 @@
+//! # Módulo de Línea Base Analítica
+//! 
+//! Este módulo calcula las predicciones de la teoría de colas para cada
+//! estación de la línea y las contrasta con los valores simulados. Cada
+//! estación se modela como una cola M/M/1 (servicio exponencial) o M/D/1
+//! (servicio determinista, como el de la simulación) con la tasa de
+//! llegadas configurada y su tiempo de procesamiento nominal.
+//! 
+//! Las fórmulas suponen llegadas de Poisson, un solo servidor FCFS y que
+//! todos los productos visitan todas las estaciones en estado estable; en
+//! las estaciones posteriores a la primera las llegadas ya no son de
+//! Poisson, por lo que la predicción es una aproximación. Las diferencias
+//! con la simulación sirven tanto para validar el simulador como para
+//! mostrar el efecto de esos supuestos.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::arrival::ArrivalProcess;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::product::TimelineEvent;
+use crate::simulation::Simulation;
+
+/// Modelo de cola con el que se predice cada estación.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueueModel {
+    /// Llegadas de Poisson y servicio exponencial
+    MM1,
+    /// Llegadas de Poisson y servicio determinista
+    #[default]
+    MD1,
+}
+
+impl QueueModel {
+    /// Calcula la longitud media de la cola (sin el producto en servicio)
+    /// para una utilización estable.
+    fn queue_length(&self, utilization: f64) -> f64 {
+        let mm1 = utilization * utilization / (1.0 - utilization);
+        match self {
+            Self::MM1 => mm1,
+            Self::MD1 => mm1 / 2.0,
+        }
+    }
+}
+
+impl fmt::Display for QueueModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MM1 => write!(f, "M/M/1"),
+            Self::MD1 => write!(f, "M/D/1"),
+        }
+    }
+}
+
+/// Predicción y valores simulados de una estación.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StationBaseline {
+    /// Nombre de la estación
+    pub name: String,
+    /// Tiempo de servicio nominal
+    pub service_time: Duration,
+    /// Utilización predicha (λ / μ)
+    pub utilization: f64,
+    /// Espera media en cola predicha, o `None` si la estación es inestable
+    /// (utilización ≥ 1)
+    pub expected_wait: Option<Duration>,
+    /// Longitud media de la cola predicha, o `None` si la estación es inestable
+    pub expected_queue_length: Option<f64>,
+    /// Utilización simulada
+    pub simulated_utilization: f64,
+    /// Espera media en cola simulada de los productos completados
+    pub simulated_wait: Duration,
+    /// Longitud media de la cola simulada (promedio en el tiempo)
+    pub simulated_queue_length: f64,
+}
+
+impl StationBaseline {
+    /// Indica si la estación es estable según el modelo.
+    pub fn is_stable(&self) -> bool {
+        self.utilization < 1.0
+    }
+}
+
+/// Comparación entre la teoría de colas y una simulación.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{AnalyticalBaseline, QueueModel, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// // Una llegada cada 100ms a una estación de 50ms: ρ = 0.5
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(50))];
+/// let arrivals = (0..5).map(|i| Duration::from_millis(i * 100)).collect();
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+/// let metrics = simulation.run().unwrap();
+/// 
+/// let baseline = AnalyticalBaseline::compare(&simulation, &metrics, QueueModel::MD1);
+/// let station = &baseline.stations[0];
+/// assert!((station.utilization - 0.5).abs() < 1e-9);
+/// // M/D/1: Wq = ρ / (2μ(1 - ρ)) = 25ms
+/// let expected_wait = station.expected_wait.unwrap().as_secs_f64();
+/// assert!((expected_wait - 0.025).abs() < 1e-6);
+/// // Las llegadas regulares no esperan: la variabilidad explica la diferencia
+/// assert!(station.simulated_wait < Duration::from_millis(10));
+/// println!("{}", baseline);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnalyticalBaseline {
+    /// Modelo de cola utilizado
+    pub model: QueueModel,
+    /// Tasa de llegadas a la línea (productos por segundo)
+    pub arrival_rate: f64,
+    /// Predicción de cada estación, en orden de la línea
+    pub stations: Vec<StationBaseline>,
+}
+
+impl AnalyticalBaseline {
+    /// Calcula la predicción de cada estación y la contrasta con las
+    /// métricas de una ejecución de la simulación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `simulation` - Simulación de la que se toman la tasa de llegadas y
+    ///   los tiempos de servicio (ver [`AnalyticalBaseline::arrival_rate_of`])
+    /// * `metrics` - Métricas de una ejecución de esa simulación
+    /// * `model` - Modelo de cola de las estaciones
+    pub fn compare(simulation: &Simulation, metrics: &SimulationMetrics, model: QueueModel) -> Self {
+        let arrival_rate = Self::arrival_rate_of(simulation);
+        let elapsed = metrics.total_simulation_time.as_secs_f64();
+        let stations = simulation.station_configs
+            .iter()
+            .enumerate()
+            .map(|(index, config)| {
+                let service = config.processing_time.as_secs_f64();
+                let utilization = arrival_rate * service;
+                let stable = utilization < 1.0;
+                let expected_queue_length = stable.then(|| model.queue_length(utilization));
+                // Little: Wq = Lq / λ (sin llegadas no hay espera)
+                let expected_wait = expected_queue_length.map(|length| {
+                    if arrival_rate > 0.0 {
+                        Duration::from_secs_f64(length / arrival_rate)
+                    } else {
+                        Duration::ZERO
+                    }
+                });
+
+                let waits: Vec<Duration> = metrics.products
+                    .iter()
+                    .map(|product| product.wait_by_station().get(index).copied().unwrap_or_default())
+                    .collect();
+                let simulated_wait = if waits.is_empty() {
+                    Duration::ZERO
+                } else {
+                    waits.iter().sum::<Duration>() / waits.len() as u32
+                };
+                let queued: Duration = metrics.products
+                    .iter()
+                    .chain(&metrics.incomplete_products)
+                    .chain(&metrics.lost_products)
+                    .flat_map(|product| &product.timeline)
+                    .filter(|span| span.station == index && span.event == TimelineEvent::Queued)
+                    .map(|span| span.duration())
+                    .sum();
+                let simulated_queue_length = if elapsed > 0.0 { queued.as_secs_f64() / elapsed } else { 0.0 };
+
+                StationBaseline {
+                    name: config.name.clone(),
+                    service_time: config.processing_time,
+                    utilization,
+                    expected_wait,
+                    expected_queue_length,
+                    simulated_utilization: metrics.station_reports
+                        .get(index)
+                        .map_or(0.0, |report| report.utilization()),
+                    simulated_wait,
+                    simulated_queue_length,
+                }
+            })
+            .collect();
+
+        Self {
+            model,
+            arrival_rate,
+            stations,
+        }
+    }
+
+    /// Calcula la tasa de llegadas configurada en una simulación.
+    /// 
+    /// Se combinan las llegadas de la fuente principal y de las fuentes
+    /// finitas: `n` llegadas entre la primera y la última equivalen a una
+    /// tasa de `(n - 1) / (última - primera)`. Las fuentes exponenciales
+    /// infinitas suman su tasa `1 / media`.
+    /// 
+    /// # Returns
+    /// 
+    /// Productos por segundo (0 si hay menos de dos llegadas o todas llegan
+    /// en el mismo instante y no hay fuentes infinitas)
+    pub fn arrival_rate_of(simulation: &Simulation) -> f64 {
+        let mut offsets = simulation.arrival_times.clone();
+        let mut open_rate = 0.0;
+        for source in &simulation.sources {
+            match &source.process {
+                ArrivalProcess::Exponential { mean_interarrival, count: None, .. } => {
+                    open_rate += 1.0 / mean_interarrival.as_secs_f64();
+                }
+                process => offsets.extend(process.offsets()),
+            }
+        }
+        offsets.sort();
+        let finite_rate = match (offsets.first(), offsets.last()) {
+            (Some(first), Some(last)) if last > first => (offsets.len() - 1) as f64 / (*last - *first).as_secs_f64(),
+            _ => 0.0,
+        };
+        finite_rate + open_rate
+    }
+}
+
+impl fmt::Display for AnalyticalBaseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = MetricsCalculator::format_duration;
+        writeln!(
+            f,
+            "=== LÍNEA BASE ANALÍTICA ({}, λ = {:.3}/s) ===",
+            self.model,
+            self.arrival_rate
+        )?;
+        let name_width = self.stations
+            .iter()
+            .map(|station| station.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Estación".chars().count());
+        writeln!(
+            f,
+            "{:<name_width$}  {:>9}  {:>9}  {:>12}  {:>12}  {:>8}  {:>8}",
+            "Estación", "ρ teórica", "ρ simul.", "Wq teórica", "Wq simul.", "Lq teór.", "Lq simul."
+        )?;
+        for station in &self.stations {
+            let expected_wait = station.expected_wait.map_or_else(|| "inestable".to_string(), time);
+            let expected_length = station.expected_queue_length
+                .map_or_else(|| "∞".to_string(), |length| format!("{:.3}", length));
+            writeln!(
+                f,
+                "{:<name_width$}  {:>9.3}  {:>9.3}  {:>12}  {:>12}  {:>8}  {:>8.3}",
+                station.name,
+                station.utilization,
+                station.simulated_utilization,
+                expected_wait,
+                time(station.simulated_wait),
+                expected_length,
+                station.simulated_queue_length
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,455 @@
+//! # Módulo de Fuentes de Llegada
+//! 
+//! Este módulo define los procesos de llegada de productos y las fuentes
+//! que alimentan la primera estación de la línea. Cada fuente se ejecuta
+//! en su propio hilo generador, lo que permite combinar, por ejemplo, la
+//! alimentación normal de la línea con inserciones manuales de retrabajo.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::product::ProductSpec;
+use crate::rng::SimRng;
+
+/// Proceso que determina los tiempos de llegada de una fuente.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ArrivalProcess {
+    /// Llegadas en tiempos fijos relativos al inicio de la simulación.
+    Fixed(Vec<Duration>),
+    /// Llegadas periódicas con intervalo constante.
+    Periodic {
+        /// Momento de la primera llegada
+        start: Duration,
+        /// Tiempo entre llegadas consecutivas
+        interval: Duration,
+        /// Número total de llegadas
+        count: usize,
+    },
+    /// Llegadas en ráfagas: varios productos llegan juntos y las ráfagas se
+    /// repiten con un intervalo constante.
+    Burst {
+        /// Momento de la primera ráfaga
+        start: Duration,
+        /// Productos que llegan en cada ráfaga
+        size: usize,
+        /// Tiempo entre ráfagas consecutivas
+        gap: Duration,
+        /// Número total de ráfagas
+        bursts: usize,
+    },
+    /// Llegadas con una tasa que varía por período (turnos, horas pico).
+    /// 
+    /// Dentro de cada período las llegadas se separan por su tiempo medio
+    /// entre llegadas: de forma exacta sin semilla, o con tiempos
+    /// exponenciales (Poisson) si se indica una semilla. Los períodos se
+    /// usan también para segmentar el reporte.
+    Piecewise {
+        /// Períodos de llegada, ordenados y sin solaparse
+        periods: Vec<ArrivalPeriod>,
+        /// Semilla del generador aleatorio (`None` = llegadas regulares)
+        seed: Option<u64>,
+    },
+    /// Llegadas aleatorias con tiempos entre llegadas exponenciales (Poisson).
+    /// 
+    /// Con `count: None` la fuente es de ciclo abierto: genera productos
+    /// indefinidamente y la simulación requiere una condición de parada.
+    Exponential {
+        /// Tiempo medio entre llegadas
+        mean_interarrival: Duration,
+        /// Semilla del generador aleatorio
+        seed: u64,
+        /// Número total de llegadas (`None` para llegadas infinitas)
+        count: Option<usize>,
+    },
+}
+
+/// Período de tiempo con una tasa de llegadas constante.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{ArrivalPeriod, ArrivalProcess};
+/// 
+/// // La tasa se duplica entre t=5s y t=10s
+/// let process = ArrivalProcess::Piecewise {
+///     periods: vec![
+///         ArrivalPeriod::new("normal", Duration::ZERO, Duration::from_secs(5), Duration::from_secs(1)),
+///         ArrivalPeriod::new("pico", Duration::from_secs(5), Duration::from_secs(10), Duration::from_millis(500)),
+///     ],
+///     seed: None,
+/// };
+/// assert_eq!(process.expected_count(), Some(15));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrivalPeriod {
+    /// Nombre del período (usado en el reporte)
+    pub name: String,
+    /// Inicio del período, relativo al inicio de la simulación
+    pub start: Duration,
+    /// Fin del período (exclusivo)
+    pub end: Duration,
+    /// Tiempo medio entre llegadas durante el período
+    pub mean_interarrival: Duration,
+}
+
+impl ArrivalPeriod {
+    /// Crea un período de llegadas.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Nombre del período
+    /// * `start` - Inicio del período
+    /// * `end` - Fin del período (exclusivo)
+    /// * `mean_interarrival` - Tiempo medio entre llegadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el período está vacío o el tiempo entre llegadas es cero
+    pub fn new(name: impl Into<String>, start: Duration, end: Duration, mean_interarrival: Duration) -> Self {
+        assert!(start < end, "El período debe terminar después de comenzar");
+        assert!(!mean_interarrival.is_zero(), "El tiempo entre llegadas debe ser mayor que cero");
+        Self {
+            name: name.into(),
+            start,
+            end,
+            mean_interarrival,
+        }
+    }
+
+    /// Indica si un instante pertenece al período.
+    pub fn contains(&self, instant: Duration) -> bool {
+        self.start <= instant && instant < self.end
+    }
+
+    /// Duración del período.
+    pub fn length(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// Iterador sobre los tiempos de llegada de un proceso.
+pub type ArrivalIter = Box<dyn Iterator<Item = Duration> + Send>;
+
+impl ArrivalProcess {
+    /// Indica si el proceso genera llegadas indefinidamente.
+    pub fn is_infinite(&self) -> bool {
+        matches!(self, Self::Exponential { count: None, .. })
+    }
+
+    /// Obtiene el número de llegadas del proceso, si es finito.
+    pub fn expected_count(&self) -> Option<usize> {
+        match self {
+            Self::Fixed(offsets) => Some(offsets.len()),
+            Self::Periodic { count, .. } => Some(*count),
+            Self::Burst { size, bursts, .. } => Some(size * bursts),
+            Self::Piecewise { periods, seed } => Some(Self::piecewise_offsets(periods, *seed).len()),
+            Self::Exponential { count, .. } => *count,
+        }
+    }
+
+    /// Obtiene los períodos del proceso, si tiene una tasa por período.
+    pub fn periods(&self) -> Option<&[ArrivalPeriod]> {
+        match self {
+            Self::Piecewise { periods, .. } => Some(periods),
+            _ => None,
+        }
+    }
+
+    /// Genera las llegadas de cada período en orden.
+    fn piecewise_offsets(periods: &[ArrivalPeriod], seed: Option<u64>) -> Vec<Duration> {
+        let mut rng = seed.map(SimRng::new);
+        let mut offsets = Vec::new();
+        for period in periods {
+            let mean = period.mean_interarrival;
+            let mut current = match &mut rng {
+                Some(rng) => period.start + Duration::from_secs_f64(rng.next_exponential(mean.as_secs_f64())),
+                None => period.start,
+            };
+            while period.contains(current) {
+                offsets.push(current);
+                current += match &mut rng {
+                    Some(rng) => Duration::from_secs_f64(rng.next_exponential(mean.as_secs_f64())),
+                    None => mean,
+                };
+            }
+        }
+        offsets
+    }
+
+    /// Crea un iterador sobre los tiempos de llegada del proceso.
+    /// 
+    /// Para procesos infinitos el iterador nunca termina.
+    /// 
+    /// # Returns
+    /// 
+    /// Iterador con los tiempos de llegada relativos al inicio de la simulación
+    pub fn iter(&self) -> ArrivalIter {
+        match self.clone() {
+            Self::Fixed(offsets) => Box::new(offsets.into_iter()),
+            Self::Periodic { start, interval, count } => {
+                Box::new((0..count).map(move |i| start + interval * i as u32))
+            }
+            Self::Burst { start, size, gap, bursts } => Box::new(
+                (0..bursts).flat_map(move |burst| std::iter::repeat_n(start + gap * burst as u32, size)),
+            ),
+            Self::Piecewise { periods, seed } => Box::new(Self::piecewise_offsets(&periods, seed).into_iter()),
+            Self::Exponential { mean_interarrival, seed, count } => {
+                let mut rng = SimRng::new(seed);
+                let mean = mean_interarrival.as_secs_f64();
+                let mut current = Duration::ZERO;
+                let arrivals = std::iter::from_fn(move || {
+                    current += Duration::from_secs_f64(rng.next_exponential(mean));
+                    Some(current)
+                });
+                match count {
+                    Some(count) => Box::new(arrivals.take(count)),
+                    None => Box::new(arrivals),
+                }
+            }
+        }
+    }
+
+    /// Calcula los tiempos de llegada del proceso.
+    /// 
+    /// # Returns
+    /// 
+    /// Vector con los tiempos de llegada relativos al inicio de la simulación
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el proceso es infinito
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::ArrivalProcess;
+    /// 
+    /// let process = ArrivalProcess::Periodic {
+    ///     start: Duration::from_millis(100),
+    ///     interval: Duration::from_millis(50),
+    ///     count: 3,
+    /// };
+    /// assert_eq!(
+    ///     process.offsets(),
+    ///     vec![Duration::from_millis(100), Duration::from_millis(150), Duration::from_millis(200)]
+    /// );
+    /// ```
+    pub fn offsets(&self) -> Vec<Duration> {
+        assert!(!self.is_infinite(), "Un proceso de llegadas infinito no tiene lista de llegadas");
+        self.iter().collect()
+    }
+}
+
+/// Orden en que los productos de la fuente principal ocupan sus tiempos de
+/// llegada.
+/// 
+/// Los tiempos de llegada no cambian: se reparten entre los productos en
+/// otro orden, de modo que cada producto conserva su ID, su fecha de
+/// entrega, su tipo y sus metadatos pero llega en otro momento. Con
+/// distintas semillas se obtienen réplicas para evaluar la equidad o la
+/// robustez de un algoritmo frente al orden de llegada.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::ArrivalOrder;
+/// 
+/// let order = ArrivalOrder::shuffled(7).permutation(5);
+/// assert_eq!(order, ArrivalOrder::shuffled(7).permutation(5));
+/// let mut sorted = order.clone();
+/// sorted.sort();
+/// assert_eq!(sorted, vec![0, 1, 2, 3, 4]);
+/// 
+/// // Cada producto se desplaza a lo sumo una posición
+/// let order = ArrivalOrder::perturbed(7, 1).permutation(50);
+/// assert!(order.iter().enumerate().all(|(slot, &product)| slot.abs_diff(product) <= 1));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArrivalOrder {
+    /// El producto i-ésimo ocupa el i-ésimo tiempo de llegada
+    #[default]
+    AsGiven,
+    /// Permutación aleatoria uniforme de los productos
+    Shuffled {
+        /// Semilla del generador aleatorio
+        seed: u64,
+    },
+    /// Perturbación local: cada producto se desplaza a lo sumo
+    /// `max_displacement` posiciones respecto del orden dado
+    Perturbed {
+        /// Semilla del generador aleatorio
+        seed: u64,
+        /// Máximo desplazamiento de un producto
+        max_displacement: usize,
+    },
+    /// Planificador global: el orden se decide mirando los tiempos de
+    /// servicio de todas las estaciones para minimizar el makespan (ver
+    /// [`ReleasePlan`](crate::ReleasePlan))
+    Lookahead,
+}
+
+impl ArrivalOrder {
+    /// Crea un orden aleatorio uniforme.
+    pub fn shuffled(seed: u64) -> Self {
+        Self::Shuffled { seed }
+    }
+
+    /// Crea una perturbación local del orden dado.
+    pub fn perturbed(seed: u64, max_displacement: usize) -> Self {
+        Self::Perturbed { seed, max_displacement }
+    }
+
+    /// Crea el orden del planificador global.
+    pub fn lookahead() -> Self {
+        Self::Lookahead
+    }
+
+    /// Calcula qué producto ocupa cada tiempo de llegada.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `count` - Número de productos
+    /// 
+    /// # Returns
+    /// 
+    /// Vector cuya posición `k` contiene el índice (desde 0) del producto
+    /// que ocupa el `k`-ésimo tiempo de llegada. El orden del planificador
+    /// global depende de los tiempos de servicio, por lo que aquí se
+    /// devuelve el orden dado (ver [`Simulation::release_plan`](crate::Simulation::release_plan))
+    pub fn permutation(&self, count: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..count).collect();
+        match *self {
+            Self::AsGiven | Self::Lookahead => {}
+            Self::Shuffled { seed } => {
+                // Fisher-Yates
+                let mut rng = SimRng::new(seed);
+                for i in (1..count).rev() {
+                    let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+                    order.swap(i, j);
+                }
+            }
+            Self::Perturbed { seed, max_displacement } => {
+                // Ordenar por la posición más un ruido en [0, d) acota el
+                // desplazamiento: dos productos a más de d posiciones
+                // conservan su orden relativo
+                let mut rng = SimRng::new(seed);
+                let keys: Vec<f64> = (0..count)
+                    .map(|i| i as f64 + rng.next_f64() * max_displacement as f64)
+                    .collect();
+                order.sort_by(|&a, &b| keys[a].total_cmp(&keys[b]));
+            }
+        }
+        order
+    }
+}
+
+impl fmt::Display for ArrivalOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AsGiven => write!(f, "orden dado"),
+            Self::Shuffled { seed } => write!(f, "aleatorio (semilla {})", seed),
+            Self::Perturbed { seed, max_displacement } => {
+                write!(f, "perturbado ±{} posiciones (semilla {})", max_displacement, seed)
+            }
+            Self::Lookahead => write!(f, "planificador global (regla de Johnson)"),
+        }
+    }
+}
+
+/// Fuente independiente de productos que alimenta la primera estación.
+/// 
+/// Cada fuente tiene su propio proceso de llegada y su propia clase de
+/// producto, descrita por la especificación que se aplica a todos los
+/// productos que genera.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrivalSource {
+    /// Nombre de la fuente (usado en los registros del generador)
+    pub name: String,
+    /// Proceso de llegada de la fuente
+    pub process: ArrivalProcess,
+    /// Especificación aplicada a los productos generados (clase, prioridad, etc.)
+    pub spec: ProductSpec,
+}
+
+impl ArrivalSource {
+    /// Crea una nueva fuente de llegadas.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Nombre de la fuente
+    /// * `process` - Proceso de llegada
+    /// * `spec` - Especificación de los productos generados
+    pub fn new(name: impl Into<String>, process: ArrivalProcess, spec: ProductSpec) -> Self {
+        Self {
+            name: name.into(),
+            process,
+            spec,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_process_is_reproducible() {
+        let process = ArrivalProcess::Exponential {
+            mean_interarrival: Duration::from_millis(100),
+            seed: 3,
+            count: Some(50),
+        };
+        let offsets = process.offsets();
+        assert_eq!(offsets.len(), 50);
+        assert_eq!(offsets, process.offsets());
+        assert!(offsets.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_burst_process() {
+        let process = ArrivalProcess::Burst {
+            start: Duration::from_millis(100),
+            size: 5,
+            gap: Duration::from_secs(2),
+            bursts: 3,
+        };
+        let offsets = process.offsets();
+        assert_eq!(process.expected_count(), Some(15));
+        assert_eq!(offsets.len(), 15);
+        assert!(offsets[..5].iter().all(|&offset| offset == Duration::from_millis(100)));
+        assert!(offsets[10..].iter().all(|&offset| offset == Duration::from_millis(4100)));
+    }
+
+    #[test]
+    fn test_piecewise_process_follows_each_period_rate() {
+        let secs = Duration::from_secs;
+        let periods = vec![
+            ArrivalPeriod::new("normal", secs(0), secs(5), secs(1)),
+            ArrivalPeriod::new("pico", secs(5), secs(10), Duration::from_millis(500)),
+        ];
+        let regular = ArrivalProcess::Piecewise { periods: periods.clone(), seed: None };
+        let offsets = regular.offsets();
+        assert_eq!(offsets.iter().filter(|&&t| periods[0].contains(t)).count(), 5);
+        assert_eq!(offsets.iter().filter(|&&t| periods[1].contains(t)).count(), 10);
+
+        let random = ArrivalProcess::Piecewise { periods, seed: Some(7) };
+        let offsets = random.offsets();
+        assert_eq!(random.expected_count(), Some(offsets.len()));
+        assert!(offsets.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(offsets.iter().all(|&t| t < secs(10)));
+    }
+
+    #[test]
+    fn test_infinite_process() {
+        let process = ArrivalProcess::Exponential {
+            mean_interarrival: Duration::from_millis(10),
+            seed: 1,
+            count: None,
+        };
+        assert!(process.is_infinite());
+        assert_eq!(process.expected_count(), None);
+        assert_eq!(process.iter().take(1000).count(), 1000);
+    }
+}
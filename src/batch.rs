@@ -0,0 +1,139 @@
+//! # Módulo de Ejecución en Lote
+//! 
+//! Este módulo permite ejecutar muchas simulaciones independientes en
+//! paralelo, por ejemplo para barridos de parámetros o estudios de Monte
+//! Carlo. Como cada simulación ya crea un hilo por estación, el número de
+//! simulaciones simultáneas se limita con un grado máximo de paralelismo.
+
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+use crate::error::SimulationError;
+use crate::metrics::SimulationMetrics;
+use crate::product::lock_recover;
+use crate::simulation::Simulation;
+
+/// Ejecutor de lotes de simulaciones independientes.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{BatchRunner, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+/// let scenarios: Vec<Simulation> = [1, 2, 3]
+///     .into_iter()
+///     .map(|products| {
+///         Simulation::with_config(stations.clone(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; products])
+///     })
+///     .collect();
+/// 
+/// let results = BatchRunner::new().with_max_parallelism(2).run(scenarios);
+/// let completed: Vec<usize> = results
+///     .iter()
+///     .map(|result| result.as_ref().expect("La simulación falló").products.len())
+///     .collect();
+/// assert_eq!(completed, vec![1, 2, 3]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct BatchRunner {
+    /// Número máximo de simulaciones ejecutándose al mismo tiempo
+    pub max_parallelism: usize,
+}
+
+impl BatchRunner {
+    /// Crea un ejecutor que usa tantas simulaciones simultáneas como
+    /// núcleos disponibles tenga la máquina.
+    pub fn new() -> Self {
+        Self {
+            max_parallelism: thread::available_parallelism().map_or(1, usize::from),
+        }
+    }
+
+    /// Asigna el número máximo de simulaciones simultáneas.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `max_parallelism` - Simulaciones simultáneas (al menos 1)
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `max_parallelism` es cero
+    pub fn with_max_parallelism(mut self, max_parallelism: usize) -> Self {
+        assert!(max_parallelism > 0, "El paralelismo máximo debe ser al menos 1");
+        self.max_parallelism = max_parallelism;
+        self
+    }
+
+    /// Ejecuta todas las simulaciones del lote.
+    /// 
+    /// Las simulaciones se reparten entre un grupo de hilos de trabajo que
+    /// toman el siguiente escenario pendiente al terminar el anterior.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `scenarios` - Simulaciones configuradas a ejecutar
+    /// 
+    /// # Returns
+    /// 
+    /// El resultado de cada simulación, en el mismo orden que `scenarios`.
+    /// Una simulación con una configuración inválida no detiene el lote: su
+    /// resultado es `Err(SimulationError::InvalidConfig)` con el
+    /// [`ConfigError`](crate::ConfigError) correspondiente
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si alguna simulación hace panic (ver [`Simulation::run`])
+    pub fn run(&self, scenarios: Vec<Simulation>) -> Vec<Result<SimulationMetrics, SimulationError>> {
+        let total = scenarios.len();
+        let workers = self.max_parallelism.min(total);
+        let pending = Mutex::new(scenarios.into_iter().enumerate());
+        let (result_tx, result_rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                let result_tx = result_tx.clone();
+                let pending = &pending;
+                scope.spawn(move || loop {
+                    let next = lock_recover(pending).next();
+                    let Some((index, mut simulation)) = next else {
+                        break;
+                    };
+                    let _ = result_tx.send((index, simulation.run()));
+                });
+            }
+        });
+        drop(result_tx);
+
+        let mut results: Vec<_> = (0..total).map(|_| None).collect();
+        for (index, result) in result_rx {
+            results[index] = Some(result);
+        }
+        results
+            .into_iter()
+            .map(|result| result.expect("Cada escenario del lote debe producir un resultado"))
+            .collect()
+    }
+}
+
+impl Default for BatchRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ejecuta un lote de simulaciones con el paralelismo por defecto.
+/// 
+/// Equivale a `BatchRunner::new().run(scenarios)`.
+/// 
+/// # Arguments
+/// 
+/// * `scenarios` - Simulaciones configuradas a ejecutar
+/// 
+/// # Returns
+/// 
+/// El resultado de cada simulación, en el mismo orden que `scenarios`
+pub fn run_batch(scenarios: Vec<Simulation>) -> Vec<Result<SimulationMetrics, SimulationError>> {
+    BatchRunner::new().run(scenarios)
+}
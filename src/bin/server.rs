@@ -0,0 +1,398 @@
+//! # Servidor HTTP del Simulador
+//! 
+//! Expone el simulador a través de una API REST mínima (feature `server`),
+//! para que un frontend web o un corrector automático ejecuten experimentos
+//! sin enlazar Rust. Cada simulación se inicia con el handle no bloqueante
+//! (`Simulation::start`) y se consulta mientras avanza.
+//! 
+//! ## Endpoints
+//! 
+//! - `POST /simulations`: recibe un escenario JSON y retorna `{"id": N}`
+//! - `GET /simulations/{id}`: estado y progreso de la simulación
+//! - `GET /simulations/{id}/results`: métricas finales en JSON
+//! - `GET /schema`: JSON Schema del formato de escenarios; un escenario
+//!   inválido se rechaza con `400` y la lista de problemas por campo y línea
+//! - `GET /health`: verificación de disponibilidad
+//! 
+//! ## Uso
+//! 
+//! ```bash
+//! cargo run --features server --bin simulador-server -- 127.0.0.1:8080
+//! curl -X POST localhost:8080/simulations -d '{"algorithm": "rr", "quantum_ms": 100}'
+//! curl localhost:8080/simulations/1
+//! curl localhost:8080/simulations/1/results
+//! ```
+
+use std::collections::HashMap;
+use std::env;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+use assembly_line_simulator::json::Json;
+use assembly_line_simulator::{schema, LiveMetrics, MetricsCalculator, Scenario, ScenarioFormat, SchemaError, SimulationHandle};
+
+/// Dirección por defecto del servidor
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+
+/// Tamaño máximo aceptado para el cuerpo de una solicitud
+const MAX_BODY_BYTES: usize = 1 << 20;
+
+/// Simulación registrada en el servidor.
+enum Job {
+    /// En ejecución; el handle se consume al terminar
+    Running {
+        handle: Box<SimulationHandle>,
+        total_products: usize,
+    },
+    /// Terminada, con su progreso final y sus métricas en JSON
+    Finished {
+        progress: LiveMetrics,
+        total_products: usize,
+        error: Option<String>,
+        metrics_json: String,
+    },
+}
+
+impl Job {
+    /// Si la simulación ya terminó, recoge sus métricas sin bloquear.
+    fn refresh(self) -> Job {
+        match self {
+            Job::Running { handle, total_products } if handle.is_finished() => {
+                let progress = handle.progress();
+                let calculator = MetricsCalculator::new();
+                let (error, metrics_json) = match handle.wait() {
+                    Ok(metrics) => (None, calculator.generate_json_report(&metrics)),
+                    Err(error) => (
+                        Some(error.to_string()),
+                        calculator.generate_json_report(error.partial_metrics()),
+                    ),
+                };
+                Job::Finished { progress, total_products, error, metrics_json }
+            }
+            job => job,
+        }
+    }
+}
+
+/// Estado compartido entre las conexiones.
+#[derive(Default)]
+struct Server {
+    jobs: Mutex<HashMap<usize, Job>>,
+}
+
+/// Respuesta HTTP con cuerpo JSON.
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, format!("{{\"error\":{}}}", Json::String(message.to_string())))
+    }
+
+    /// Rechaza un escenario con los problemas encontrados al validarlo.
+    fn invalid_scenario(errors: &[SchemaError]) -> Self {
+        let messages: Vec<String> = errors.iter().map(SchemaError::to_string).collect();
+        let details: Vec<String> = errors
+            .iter()
+            .map(|error| {
+                format!(
+                    "{{\"path\":{},\"line\":{},\"message\":{}}}",
+                    Json::String(error.path.clone()),
+                    error.line.map_or(Json::Null, |line| Json::Number(line as f64)),
+                    Json::String(error.message.clone())
+                )
+            })
+            .collect();
+        Self::json(
+            400,
+            format!("{{\"error\":{},\"errors\":[{}]}}", Json::String(messages.join("\n")), details.join(",")),
+        )
+    }
+}
+
+impl Server {
+    /// Atiende una solicitud ya leída.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `method` - Método HTTP
+    /// * `path` - Ruta solicitada (sin parámetros de consulta)
+    /// * `body` - Cuerpo de la solicitud
+    fn route(&self, method: &str, path: &str, body: &str) -> Response {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            ("GET", ["health"]) => Response::json(200, "{\"status\":\"ok\"}".to_string()),
+            ("GET", ["schema"]) => Response::json(200, schema::scenario_schema().to_string()),
+            ("POST", ["simulations"]) => self.submit(body),
+            ("GET", ["simulations", id]) => self.with_job(id, |id, job| Response::json(200, status_json(id, job))),
+            ("GET", ["simulations", id, "results"]) => self.with_job(id, |_, job| match job {
+                Job::Running { .. } => Response::error(409, "La simulación sigue en ejecución"),
+                Job::Finished { error, metrics_json, .. } => Response::json(
+                    200,
+                    format!(
+                        "{{\"status\":\"{}\",\"error\":{},\"metrics\":{}}}",
+                        if error.is_some() { "failed" } else { "completed" },
+                        error.as_ref().map_or(Json::Null, |error| Json::String(error.clone())),
+                        metrics_json
+                    ),
+                ),
+            }),
+            (_, ["health"] | ["schema"] | ["simulations"] | ["simulations", _] | ["simulations", _, "results"]) => {
+                Response::error(405, "Método no permitido")
+            }
+            _ => Response::error(404, "Ruta no encontrada"),
+        }
+    }
+
+    /// Inicia la simulación descrita por un escenario.
+    fn submit(&self, body: &str) -> Response {
+        let errors = Scenario::validate(body, ScenarioFormat::Json);
+        if !errors.is_empty() {
+            return Response::invalid_scenario(&errors);
+        }
+        let scenario = match Scenario::from_json(body) {
+            Ok(scenario) => scenario,
+            Err(error) => return Response::error(400, &error),
+        };
+
+        let mut handle = scenario.to_simulation().start();
+        // Sin inyecciones, la simulación termina al agotar sus llegadas
+        handle.close_injection();
+
+        let mut jobs = self.jobs.lock().unwrap_or_else(PoisonError::into_inner);
+        let id = jobs.len() + 1;
+        jobs.insert(id, Job::Running { handle: Box::new(handle), total_products: scenario.arrivals.len() });
+        println!("[SERVIDOR] Simulación {} iniciada ({})", id, scenario.algorithm);
+        Response::json(201, format!("{{\"id\":{}}}", id))
+    }
+
+    /// Busca una simulación, actualiza su estado y aplica `respond`.
+    fn with_job(&self, id: &str, respond: impl FnOnce(usize, &Job) -> Response) -> Response {
+        let Ok(id) = id.parse::<usize>() else {
+            return Response::error(400, "Identificador de simulación inválido");
+        };
+        let mut jobs = self.jobs.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(job) = jobs.remove(&id) else {
+            return Response::error(404, "Simulación no encontrada");
+        };
+        let job = job.refresh();
+        let response = respond(id, &job);
+        jobs.insert(id, job);
+        response
+    }
+}
+
+/// Describe el estado y el progreso de una simulación.
+fn status_json(id: usize, job: &Job) -> String {
+    let (status, progress, total_products) = match job {
+        Job::Running { handle, total_products } => ("running", handle.progress(), *total_products),
+        Job::Finished { progress, total_products, error, .. } => (
+            if error.is_some() { "failed" } else { "completed" },
+            *progress,
+            *total_products,
+        ),
+    };
+    format!(
+        "{{\"id\":{},\"status\":\"{}\",\"total_products\":{},\"admitted\":{},\"completed\":{},\"in_flight\":{},\"elapsed\":{:.3}}}",
+        id,
+        status,
+        total_products,
+        progress.admitted,
+        progress.completed,
+        progress.in_flight,
+        progress.elapsed.as_secs_f64()
+    )
+}
+
+/// Lee una solicitud HTTP/1.1 y escribe la respuesta.
+fn handle_connection(server: &Server, stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return write_response(stream, &Response::error(400, "Solicitud inválida"));
+    };
+    let path = target.split('?').next().unwrap_or(target);
+
+    // Encabezados: solo interesa la longitud del cuerpo
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return write_response(stream, &Response::error(413, "Cuerpo demasiado grande"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let response = match String::from_utf8(body) {
+        // Solicitud previa de CORS de los navegadores
+        Ok(_) if method == "OPTIONS" => Response::json(204, String::new()),
+        Ok(body) => server.route(method, path, &body),
+        Err(_) => Response::error(400, "El cuerpo debe ser UTF-8"),
+    };
+    write_response(stream, &response)
+}
+
+/// Escribe una respuesta HTTP con cuerpo JSON y cierra la conexión.
+fn write_response(mut stream: TcpStream, response: &Response) -> std::io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        _ => "Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+fn main() {
+    let address = env::args().nth(1).unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+    let listener = TcpListener::bind(&address).unwrap_or_else(|error| {
+        eprintln!("Error: no se pudo escuchar en {}: {}", address, error);
+        std::process::exit(1);
+    });
+    eprintln!("[SERVIDOR] Escuchando en http://{}", address);
+
+    let server = Arc::new(Server::default());
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let server = Arc::clone(&server);
+        thread::spawn(move || {
+            if let Err(error) = handle_connection(&server, stream) {
+                eprintln!("[SERVIDOR] Error en la conexión: {}", error);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const SCENARIO: &str = r#"{
+        "algorithm": "rr",
+        "quantum_ms": 10,
+        "stations": [{"name": "Corte", "processing_time_ms": 20}],
+        "arrivals_ms": [0, 5]
+    }"#;
+
+    #[test]
+    fn test_submit_poll_and_fetch_results() {
+        let server = Server::default();
+        let created = server.route("POST", "/simulations", SCENARIO);
+        assert_eq!(created.status, 201, "{}", created.body);
+        assert_eq!(created.body, "{\"id\":1}");
+
+        let status = loop {
+            let status = server.route("GET", "/simulations/1", "");
+            assert_eq!(status.status, 200);
+            if !status.body.contains("\"running\"") {
+                break status;
+            }
+            let early = server.route("GET", "/simulations/1/results", "");
+            assert!(early.status == 409 || early.status == 200);
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert!(status.body.contains("\"status\":\"completed\""), "{}", status.body);
+        assert!(status.body.contains("\"completed\":2"), "{}", status.body);
+
+        let results = server.route("GET", "/simulations/1/results", "");
+        assert_eq!(results.status, 200);
+        let results = Json::parse(&results.body).expect("Los resultados deben ser JSON válido");
+        let metrics = results.get("metrics").expect("Faltan las métricas");
+        assert_eq!(metrics.get("products").and_then(Json::as_array).map(<[Json]>::len), Some(2));
+    }
+
+    #[test]
+    fn test_error_responses() {
+        let server = Server::default();
+        assert_eq!(server.route("POST", "/simulations", "{\"algorithm\": 1}").status, 400);
+        let invalid = server.route("POST", "/simulations", "{\"algorithm\": \"rr\",\n \"quantum_ms\": 0}");
+        assert_eq!(invalid.status, 400);
+        let errors = Json::parse(&invalid.body).unwrap();
+        let error = &errors.get("errors").and_then(Json::as_array).unwrap()[0];
+        assert_eq!(error.get("path").and_then(Json::as_str), Some("quantum_ms"));
+        assert_eq!(error.get("line").and_then(Json::as_f64), Some(2.0));
+        let schema = server.route("GET", "/schema", "");
+        assert_eq!(schema.status, 200);
+        assert!(Json::parse(&schema.body).unwrap().get("properties").is_some());
+        assert_eq!(server.route("POST", "/schema", "").status, 405);
+        assert_eq!(server.route("GET", "/simulations/7", "").status, 404);
+        assert_eq!(server.route("GET", "/simulations/x", "").status, 400);
+        assert_eq!(server.route("DELETE", "/simulations", "").status, 405);
+        assert_eq!(server.route("GET", "/otra", "").status, 404);
+    }
+
+    #[test]
+    fn test_deeply_nested_body_is_rejected() {
+        let server = Server::default();
+        // Sin límite de anidamiento, el análisis desbordaría la pila del hilo
+        let response = server.route("POST", "/simulations", &"[".repeat(5000));
+        assert_eq!(response.status, 400);
+        assert!(response.body.contains("niveles de anidamiento"), "{}", response.body);
+    }
+
+    #[test]
+    fn test_out_of_range_time_is_rejected() {
+        let server = Server::default();
+        let response = server.route("POST", "/simulations", r#"{"algorithm": "rr", "quantum_ms": 1e300}"#);
+        assert_eq!(response.status, 400);
+        assert!(response.body.contains("quantum_ms"), "{}", response.body);
+    }
+
+    #[test]
+    fn test_http_round_trip() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = Server::default();
+        let client = thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(stream, "GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        });
+
+        let (stream, _) = listener.accept().unwrap();
+        handle_connection(&server, stream).unwrap();
+        let response = client.join().unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.ends_with("{\"status\":\"ok\"}"));
+    }
+}
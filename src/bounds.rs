@@ -0,0 +1,244 @@
+//! # Módulo de Cotas Inferiores
+//! 
+//! Este módulo analiza la configuración de una simulación sin ejecutarla
+//! (sin lanzar hilos ni esperar) y calcula cotas inferiores de sus
+//! resultados, útiles para revisar una configuración antes de una
+//! ejecución larga:
+//! 
+//! - El tiempo total de servicio: la suma de los tiempos de procesamiento
+//!   de todas las visitas de todos los productos
+//! - El makespan mínimo: ninguna ejecución puede terminar antes de que
+//!   cada producto recorra su ruta desde su llegada, ni antes de que la
+//!   estación más cargada (el cuello de botella) atienda todo su trabajo
+//! - El turnaround mínimo de cada producto: la suma de sus tiempos de
+//!   procesamiento, que solo se alcanza si nunca espera en una cola
+//! 
+//! Las cotas usan la configuración inicial de las estaciones: no
+//! consideran las reconfiguraciones en caliente, y los efectos que solo
+//! agregan tiempo (esperas, curvas de aprendizaje, recursos compartidos,
+//! dependencias) no las invalidan. Los productos de fuentes infinitas no se
+//! conocen de antemano y no se incluyen.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::metrics::MetricsCalculator;
+use crate::simulation::Simulation;
+
+/// Carga de una estación y la cota de makespan que impone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StationBound {
+    /// Nombre de la estación
+    pub name: String,
+    /// Productos que visitan la estación
+    pub products: usize,
+    /// Suma de los tiempos de procesamiento de todas las visitas
+    pub load: Duration,
+    /// Makespan mínimo impuesto por la estación: el primer momento en que
+    /// puede recibir un producto, más su carga, más el menor recorrido
+    /// restante tras la estación
+    pub makespan: Duration,
+}
+
+/// Cota del recorrido de un producto.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProductBound {
+    /// ID del producto
+    pub id: usize,
+    /// Llegada del producto, relativa al inicio de la simulación
+    pub arrival: Duration,
+    /// Turnaround mínimo: la suma de sus tiempos de procesamiento
+    pub min_turnaround: Duration,
+}
+
+impl ProductBound {
+    /// Primer momento en que el producto puede terminar la línea.
+    pub fn earliest_completion(&self) -> Duration {
+        self.arrival + self.min_turnaround
+    }
+}
+
+/// Cotas inferiores de una simulación calculadas sin ejecutarla.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let millis = Duration::from_millis;
+/// let stations = vec![
+///     StationConfig::new("Corte", millis(10)),
+///     StationConfig::new("Pintura", millis(40)),
+/// ];
+/// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// 
+/// let bounds = simulation.analyze().unwrap();
+/// assert_eq!(bounds.total_service_time, millis(150));
+/// // Pintura no recibe nada antes de los 10ms y luego atiende 120ms de trabajo
+/// assert_eq!(bounds.bottleneck, Some(1));
+/// assert_eq!(bounds.makespan, millis(130));
+/// assert_eq!(bounds.products[0].min_turnaround, millis(50));
+/// assert!(bounds.to_string().contains("limitado por la estación Pintura"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LowerBounds {
+    /// Suma de los tiempos de procesamiento de todos los productos
+    pub total_service_time: Duration,
+    /// Makespan mínimo de la simulación, relativo a su inicio
+    pub makespan: Duration,
+    /// Índice de la estación que fija el makespan mínimo, o `None` si lo
+    /// fija el recorrido de un producto (la línea no llega a saturarse)
+    pub bottleneck: Option<usize>,
+    /// Cota de cada estación, en orden de la línea
+    pub stations: Vec<StationBound>,
+    /// Cota de cada producto conocido de antemano, por ID
+    pub products: Vec<ProductBound>,
+    /// Fuentes infinitas cuyos productos no se incluyen
+    pub open_sources: usize,
+}
+
+impl LowerBounds {
+    /// Calcula las cotas de una simulación a partir de los productos que
+    /// crearía al ejecutarse.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si una dependencia referencia un producto o estación
+    /// inexistente, o si un producto referencia un tipo no registrado
+    pub fn of(simulation: &Simulation) -> Self {
+        let mut products: Vec<_> = simulation.create_products().into_iter().flatten().collect();
+        products.sort_by_key(|product| product.id);
+
+        let station_count = simulation.station_configs.len();
+        let mut loads = vec![Duration::ZERO; station_count];
+        let mut visitors = vec![0; station_count];
+        // Primer momento en que cada estación puede recibir un producto y
+        // menor recorrido restante tras ella
+        let mut earliest_ready = vec![None::<Duration>; station_count];
+        let mut shortest_tail = vec![None::<Duration>; station_count];
+
+        for product in &products {
+            let route = product.route();
+            let times: Vec<Duration> = route
+                .iter()
+                .map(|&station| product.processing_time(station))
+                .collect();
+            let total: Duration = times.iter().sum();
+            let mut head = Duration::ZERO;
+            for (visit, &station) in route.iter().enumerate() {
+                loads[station] += times[visit];
+                if !route[..visit].contains(&station) {
+                    visitors[station] += 1;
+                    let ready = product.arrival_offset + head;
+                    earliest_ready[station] = Some(earliest_ready[station].map_or(ready, |current| current.min(ready)));
+                }
+                if !route[visit + 1..].contains(&station) {
+                    let tail = total - head - times[visit];
+                    shortest_tail[station] = Some(shortest_tail[station].map_or(tail, |current| current.min(tail)));
+                }
+                head += times[visit];
+            }
+        }
+
+        let stations: Vec<StationBound> = simulation.station_configs
+            .iter()
+            .enumerate()
+            .map(|(index, config)| StationBound {
+                name: config.name.clone(),
+                products: visitors[index],
+                load: loads[index],
+                makespan: earliest_ready[index].unwrap_or_default()
+                    + loads[index]
+                    + shortest_tail[index].unwrap_or_default(),
+            })
+            .collect();
+
+        let products: Vec<ProductBound> = products
+            .iter()
+            .map(|product| ProductBound {
+                id: product.id,
+                arrival: product.arrival_offset,
+                min_turnaround: product.route().iter().map(|&station| product.processing_time(station)).sum(),
+            })
+            .collect();
+
+        let route_bound = products.iter().map(ProductBound::earliest_completion).max().unwrap_or_default();
+        let station_bound = stations
+            .iter()
+            .enumerate()
+            .filter(|(_, station)| station.products > 0)
+            .max_by_key(|(index, station)| (station.makespan, std::cmp::Reverse(*index)));
+        let (makespan, bottleneck) = match station_bound {
+            Some((index, station)) if station.makespan > route_bound => (station.makespan, Some(index)),
+            _ => (route_bound, None),
+        };
+
+        Self {
+            total_service_time: stations.iter().map(|station| station.load).sum(),
+            makespan,
+            bottleneck,
+            stations,
+            products,
+            open_sources: simulation.sources.iter().filter(|source| source.process.is_infinite()).count(),
+        }
+    }
+
+    /// Promedio de los turnarounds mínimos de los productos.
+    pub fn average_min_turnaround(&self) -> Duration {
+        if self.products.is_empty() {
+            return Duration::ZERO;
+        }
+        self.products.iter().map(|product| product.min_turnaround).sum::<Duration>() / self.products.len() as u32
+    }
+}
+
+impl fmt::Display for LowerBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = MetricsCalculator::format_duration;
+        writeln!(f, "=== COTAS INFERIORES (sin ejecutar) ===")?;
+        writeln!(f, "Productos: {}", self.products.len())?;
+        writeln!(f, "Tiempo total de servicio: {}", time(self.total_service_time))?;
+        match self.bottleneck {
+            Some(index) => writeln!(
+                f,
+                "Makespan mínimo: {} (limitado por la estación {})",
+                time(self.makespan),
+                self.stations[index].name
+            )?,
+            None => writeln!(f, "Makespan mínimo: {} (limitado por el recorrido de los productos)", time(self.makespan))?,
+        }
+        let minimum = self.products.iter().map(|product| product.min_turnaround).min().unwrap_or_default();
+        let maximum = self.products.iter().map(|product| product.min_turnaround).max().unwrap_or_default();
+        writeln!(
+            f,
+            "Turnaround mínimo por producto: promedio {}, entre {} y {}",
+            time(self.average_min_turnaround()),
+            time(minimum),
+            time(maximum)
+        )?;
+        if self.open_sources > 0 {
+            writeln!(f, "Fuentes infinitas no incluidas: {}", self.open_sources)?;
+        }
+
+        let name_width = self.stations
+            .iter()
+            .map(|station| station.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Estación".chars().count());
+        writeln!(f)?;
+        writeln!(f, "{:<name_width$}  {:>9}  {:>12}  {:>14}", "Estación", "Productos", "Carga", "Cota makespan")?;
+        for station in &self.stations {
+            writeln!(
+                f,
+                "{:<name_width$}  {:>9}  {:>12}  {:>14}",
+                station.name,
+                station.products,
+                time(station.load),
+                time(station.makespan)
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+//! # Módulo de Catálogo de Productos
+//! 
+//! Este módulo define los tipos de producto que pueden circular por la línea.
+//! Cada tipo tiene su propia ruta (secuencia de estaciones), sus tiempos de
+//! servicio por estación y su prioridad, lo que permite simular líneas de
+//! modelo mixto en lugar de productos idénticos.
+//! 
+//! Una ruta puede volver a una estación ya visitada (línea reentrante, como
+//! en la fabricación de semiconductores). Como los canales solo avanzan
+//! hacia adelante, el producto recircula desde el final de la línea hasta la
+//! primera estación y vuelve a avanzar hasta la estación que le corresponde.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::product::{Product, ProductSpec};
+use crate::station::StationConfig;
+
+/// Tipo de producto con ruta y tiempos de servicio propios.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::ProductType;
+/// 
+/// let sedan = ProductType::new("sedan")
+///     .with_route(vec![0, 2])
+///     .with_service_time(2, Duration::from_millis(800))
+///     .with_priority(2);
+/// assert_eq!(sedan.route, Some(vec![0, 2]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProductType {
+    /// Nombre único del tipo
+    pub name: String,
+    /// Estaciones que visita el tipo, en orden de visita (`None` = todas)
+    pub route: Option<Vec<usize>>,
+    /// Tiempos de servicio por índice de estación; las estaciones sin tiempo
+    /// propio usan el de su configuración
+    pub service_times: HashMap<usize, Duration>,
+    /// Prioridad por defecto de los productos del tipo
+    pub priority: u32,
+}
+
+impl ProductType {
+    /// Crea un tipo de producto que visita todas las estaciones con sus
+    /// tiempos configurados.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Nombre único del tipo
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            route: None,
+            service_times: HashMap::new(),
+            priority: 0,
+        }
+    }
+
+    /// Asigna la ruta del tipo.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `route` - Índices de las estaciones a visitar, en orden de visita;
+    ///   una estación puede repetirse
+    pub fn with_route(mut self, route: Vec<usize>) -> Self {
+        self.route = Some(route);
+        self
+    }
+
+    /// Asigna el tiempo de servicio del tipo en una estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station` - Índice de la estación
+    /// * `service_time` - Tiempo de procesamiento en esa estación
+    pub fn with_service_time(mut self, station: usize, service_time: Duration) -> Self {
+        self.service_times.insert(station, service_time);
+        self
+    }
+
+    /// Asigna la prioridad por defecto del tipo.
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Indica si la ruta del tipo vuelve a una estación que no está más
+    /// adelante en la línea, por lo que sus productos deben recircular.
+    pub fn is_reentrant(&self) -> bool {
+        self.route
+            .as_ref()
+            .is_some_and(|route| route.windows(2).any(|pair| pair[1] <= pair[0]))
+    }
+
+    /// Verifica que el tipo sea válido para una línea con `station_count` estaciones.
+    /// 
+    /// La ruta no puede estar vacía y solo puede referirse a estaciones
+    /// existentes; puede repetir estaciones (ver [`ProductType::is_reentrant`]).
+    /// Los tiempos de servicio solo pueden referirse a estaciones de la ruta.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si el tipo es válido, o `Err` con la descripción del problema
+    pub fn validate(&self, station_count: usize) -> Result<(), String> {
+        if let Some(route) = &self.route {
+            if route.is_empty() {
+                return Err(format!("El tipo '{}' tiene una ruta vacía", self.name));
+            }
+            if let Some(&station) = route.iter().find(|&&station| station >= station_count) {
+                return Err(format!(
+                    "El tipo '{}' referencia la estación inexistente {}",
+                    self.name, station
+                ));
+            }
+        }
+
+        let mut stations: Vec<_> = self.service_times.keys().copied().collect();
+        stations.sort_unstable();
+        let visits = |station: usize| match &self.route {
+            Some(route) => route.contains(&station),
+            None => station < station_count,
+        };
+        if let Some(station) = stations.into_iter().find(|&station| !visits(station)) {
+            return Err(format!(
+                "El tipo '{}' define tiempo de servicio para la estación {} fuera de su ruta",
+                self.name, station
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Catálogo de tipos de producto de una simulación.
+#[derive(Clone, Debug, Default)]
+pub struct ProductCatalog {
+    types: HashMap<String, ProductType>,
+}
+
+impl ProductCatalog {
+    /// Crea un catálogo vacío.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra un tipo de producto, reemplazando otro con el mismo nombre.
+    pub fn register(&mut self, product_type: ProductType) {
+        self.types.insert(product_type.name.clone(), product_type);
+    }
+
+    /// Busca un tipo de producto por nombre.
+    pub fn get(&self, name: &str) -> Option<&ProductType> {
+        self.types.get(name)
+    }
+
+    /// Tipos registrados, ordenados por nombre.
+    pub fn types(&self) -> Vec<&ProductType> {
+        let mut types: Vec<&ProductType> = self.types.values().collect();
+        types.sort_by(|a, b| a.name.cmp(&b.name));
+        types
+    }
+
+    /// Indica si algún tipo registrado tiene una ruta reentrante.
+    pub fn has_reentrant_routes(&self) -> bool {
+        self.types.values().any(ProductType::is_reentrant)
+    }
+
+    /// Crea un producto resolviendo el tipo referenciado por la especificación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `id` - Identificador único del producto
+    /// * `arrival_offset` - Tiempo de llegada simulado relativo al inicio
+    /// * `spec` - Especificación del producto
+    /// * `station_configs` - Configuraciones de todas las estaciones en la línea
+    /// 
+    /// # Returns
+    /// 
+    /// Un `Arc<Product>` del tipo indicado, o un producto estándar si la
+    /// especificación no referencia ningún tipo
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la especificación referencia un tipo no registrado
+    pub fn create_product(
+        &self,
+        id: usize,
+        arrival_offset: Duration,
+        spec: &ProductSpec,
+        station_configs: &[StationConfig],
+    ) -> Arc<Product> {
+        match &spec.product_type {
+            Some(name) => {
+                let product_type = self.get(name)
+                    .unwrap_or_else(|| panic!("Tipo de producto desconocido: {}", name));
+                Product::from_type(id, arrival_offset, spec, product_type, station_configs)
+            }
+            None => Product::from_spec(id, arrival_offset, spec, station_configs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_route() {
+        assert!(ProductType::new("a").validate(3).is_ok());
+        assert!(ProductType::new("a").with_route(vec![0, 2]).validate(3).is_ok());
+        assert!(ProductType::new("a").with_route(Vec::new()).validate(3).is_err());
+        assert!(ProductType::new("a").with_route(vec![0, 3]).validate(3).is_err());
+        assert!(ProductType::new("a").with_route(vec![0, 2, 1]).validate(3).is_ok());
+        assert!(ProductType::new("a").with_route(vec![0, 2, 1]).is_reentrant());
+        assert!(!ProductType::new("a").with_route(vec![0, 2]).is_reentrant());
+        assert!(ProductType::new("a")
+            .with_route(vec![0, 2])
+            .with_service_time(1, Duration::from_millis(10))
+            .validate(3)
+            .is_err());
+    }
+
+    #[test]
+    fn test_create_product_from_catalog() {
+        let configs = vec![
+            StationConfig::new("A", Duration::from_millis(100)),
+            StationConfig::new("B", Duration::from_millis(200)),
+        ];
+        let mut catalog = ProductCatalog::new();
+        catalog.register(
+            ProductType::new("rapido")
+                .with_route(vec![1])
+                .with_service_time(1, Duration::from_millis(50))
+                .with_priority(3),
+        );
+
+        let product = catalog.create_product(1, Duration::ZERO, &ProductSpec::of_type("rapido"), &configs);
+        assert_eq!(product.route(), vec![1]);
+        assert_eq!(product.first_station(), Some(1));
+        assert_eq!(product.processing_time(0), Duration::ZERO);
+        assert_eq!(product.processing_time(1), Duration::from_millis(50));
+        assert_eq!(product.priority, 3);
+        assert_eq!(product.class.as_deref(), Some("rapido"));
+
+        let standard = catalog.create_product(2, Duration::ZERO, &ProductSpec::default(), &configs);
+        assert_eq!(standard.route(), vec![0, 1]);
+        assert_eq!(standard.processing_time(1), Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,587 @@
+//! # Módulo de Canales entre Estaciones
+//! 
+//! Este módulo permite elegir el tipo de canal con el que se comunican el
+//! generador y las estaciones. Con canales sin límite (el comportamiento
+//! original) los productos nunca esperan para ser entregados; con canales
+//! acotados cada estación tiene un buffer de entrada finito y la estación
+//! anterior se bloquea cuando el buffer está lleno, modelando la
+//! contrapresión de una línea real.
+//! 
+//! Además de los canales de `std::sync::mpsc`, el buffer acotado puede
+//! implementarse con semáforos contadores, como en el problema clásico del
+//! productor-consumidor: un semáforo cuenta los espacios libres, otro los
+//! mensajes disponibles y un mutex protege la cola. Ambas implementaciones
+//! modelan la misma línea, lo que permite comparar las primitivas.
+//! 
+//! Con la feature `crossbeam` se agregan los canales acotados de
+//! `crossbeam-channel`. Con ellos los comandos de control de cada estación
+//! viajan también por un canal de crossbeam y la estación espera productos
+//! y comandos a la vez con `select!`, de modo que una estación ociosa
+//! aplica un comando en cuanto llega en lugar de esperar al siguiente
+//! producto.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "crossbeam")]
+use crate::control::StationCommand;
+use crate::product::lock_recover;
+use crate::station::Message;
+
+/// Tipo de canal utilizado para alimentar a cada estación.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChannelBackend {
+    /// Canales `std::sync::mpsc` sin límite de capacidad
+    #[default]
+    Unbounded,
+    /// Canales `std::sync::mpsc::sync_channel` con capacidad fija
+    Bounded {
+        /// Número máximo de mensajes en tránsito hacia la estación
+        capacity: usize,
+    },
+    /// Buffer acotado sincronizado con semáforos contadores
+    Semaphore {
+        /// Número máximo de mensajes en el buffer de la estación
+        capacity: usize,
+    },
+    /// Canales acotados de `crossbeam-channel` (feature `crossbeam`)
+    #[cfg(feature = "crossbeam")]
+    Crossbeam {
+        /// Número máximo de mensajes en tránsito hacia la estación
+        capacity: usize,
+    },
+}
+
+impl ChannelBackend {
+    /// Crea un canal de entrada para una estación.
+    /// 
+    /// # Returns
+    /// 
+    /// Tupla con el emisor y el receptor del canal
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el buffer con semáforos tiene capacidad cero
+    pub fn station_channel(&self) -> (StationSender, StationReceiver) {
+        match *self {
+            Self::Unbounded => {
+                let (sender, receiver) = mpsc::channel();
+                (StationSender::Unbounded(sender), StationReceiver::Channel(receiver))
+            }
+            Self::Bounded { capacity } => {
+                let (sender, receiver) = mpsc::sync_channel(capacity);
+                (StationSender::Bounded(sender), StationReceiver::Channel(receiver))
+            }
+            Self::Semaphore { capacity } => {
+                assert!(capacity > 0, "La capacidad del buffer con semáforos debe ser mayor que 0");
+                let (sender, receiver) = semaphore_channel(capacity);
+                (StationSender::Semaphore(sender), StationReceiver::Semaphore(receiver))
+            }
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam { capacity } => {
+                let (sender, receiver) = crossbeam_channel::bounded(capacity);
+                (StationSender::Crossbeam(sender), StationReceiver::Crossbeam(receiver))
+            }
+        }
+    }
+
+    /// Capacidad de los buffers de entrada, o `None` si no tienen límite.
+    pub fn capacity(&self) -> Option<usize> {
+        match *self {
+            Self::Unbounded => None,
+            Self::Bounded { capacity } | Self::Semaphore { capacity } => Some(capacity),
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam { capacity } => Some(capacity),
+        }
+    }
+}
+
+impl ChannelBackend {
+    /// Indica si los buffers tienen capacidad fija y el envío puede bloquearse.
+    pub fn is_bounded(&self) -> bool {
+        !matches!(self, Self::Unbounded)
+    }
+}
+
+impl fmt::Display for ChannelBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unbounded => write!(f, "canales mpsc sin límite"),
+            Self::Bounded { capacity } => write!(f, "canales mpsc acotados (capacidad {})", capacity),
+            Self::Semaphore { capacity } => {
+                write!(f, "buffer con semáforos contadores (capacidad {})", capacity)
+            }
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam { capacity } => write!(f, "canales crossbeam acotados (capacidad {})", capacity),
+        }
+    }
+}
+
+impl FromStr for ChannelBackend {
+    type Err = String;
+
+    /// Interpreta `mpsc`, `acotado:<capacidad>`, `semaforo:<capacidad>` o,
+    /// con la feature `crossbeam`, `crossbeam:<capacidad>`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::ChannelBackend;
+    /// 
+    /// assert_eq!("semaforo:4".parse(), Ok(ChannelBackend::Semaphore { capacity: 4 }));
+    /// assert_eq!("mpsc".parse(), Ok(ChannelBackend::Unbounded));
+    /// assert!("semaforo:0".parse::<ChannelBackend>().is_err());
+    /// ```
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (kind, capacity) = match value.split_once(':') {
+            Some((kind, capacity)) => {
+                let capacity = capacity
+                    .parse::<usize>()
+                    .map_err(|_| format!("Capacidad de canal inválida: '{}'", capacity))?;
+                (kind, Some(capacity))
+            }
+            None => (value, None),
+        };
+        match (kind, capacity) {
+            ("mpsc" | "sin-limite", None) => Ok(Self::Unbounded),
+            ("acotado", Some(capacity)) => Ok(Self::Bounded { capacity }),
+            ("semaforo", Some(capacity)) if capacity > 0 => Ok(Self::Semaphore { capacity }),
+            ("semaforo", Some(_)) => Err("El buffer con semáforos requiere capacidad mayor que 0".to_string()),
+            #[cfg(feature = "crossbeam")]
+            ("crossbeam", Some(capacity)) => Ok(Self::Crossbeam { capacity }),
+            #[cfg(not(feature = "crossbeam"))]
+            ("crossbeam", Some(_)) => Err("Los canales crossbeam requieren la feature `crossbeam`".to_string()),
+            _ => Err(format!(
+                "Tipo de canal desconocido: '{}' (use mpsc, acotado:<n>, semaforo:<n> o crossbeam:<n>)",
+                value
+            )),
+        }
+    }
+}
+
+/// Emisor hacia el canal de entrada de una estación.
+#[derive(Clone, Debug)]
+pub enum StationSender {
+    /// Emisor de un canal sin límite
+    Unbounded(mpsc::Sender<Message>),
+    /// Emisor de un canal acotado; `send` se bloquea si el canal está lleno
+    Bounded(mpsc::SyncSender<Message>),
+    /// Emisor de un buffer con semáforos; `send` se bloquea si está lleno
+    Semaphore(SemaphoreSender),
+    /// Emisor de un canal acotado de crossbeam; `send` se bloquea si está lleno
+    #[cfg(feature = "crossbeam")]
+    Crossbeam(crossbeam_channel::Sender<Message>),
+}
+
+impl StationSender {
+    /// Envía un mensaje a la estación, bloqueándose si el canal está lleno.
+    /// 
+    /// # Returns
+    /// 
+    /// `Err` con el mensaje si la estación receptora ya finalizó
+    pub fn send(&self, message: Message) -> Result<(), mpsc::SendError<Message>> {
+        match self {
+            Self::Unbounded(sender) => sender.send(message),
+            Self::Bounded(sender) => sender.send(message),
+            Self::Semaphore(sender) => sender.send(message),
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam(sender) => sender
+                .send(message)
+                .map_err(|crossbeam_channel::SendError(message)| mpsc::SendError(message)),
+        }
+    }
+
+    /// Intenta enviar un mensaje sin bloquearse.
+    /// 
+    /// # Returns
+    /// 
+    /// `Err(TrySendError::Full)` con el mensaje si el canal acotado está
+    /// lleno, o `Err(TrySendError::Disconnected)` si la estación receptora
+    /// ya finalizó
+    pub fn try_send(&self, message: Message) -> Result<(), mpsc::TrySendError<Message>> {
+        match self {
+            Self::Unbounded(sender) => sender
+                .send(message)
+                .map_err(|mpsc::SendError(message)| mpsc::TrySendError::Disconnected(message)),
+            Self::Bounded(sender) => sender.try_send(message),
+            Self::Semaphore(sender) => sender.try_send(message),
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam(sender) => sender.try_send(message).map_err(|error| match error {
+                crossbeam_channel::TrySendError::Full(message) => mpsc::TrySendError::Full(message),
+                crossbeam_channel::TrySendError::Disconnected(message) => mpsc::TrySendError::Disconnected(message),
+            }),
+        }
+    }
+
+    /// Indica si el canal es acotado y `send` puede bloquearse.
+    pub fn is_bounded(&self) -> bool {
+        !matches!(self, Self::Unbounded(_))
+    }
+}
+
+impl From<mpsc::Sender<Message>> for StationSender {
+    fn from(sender: mpsc::Sender<Message>) -> Self {
+        Self::Unbounded(sender)
+    }
+}
+
+impl From<mpsc::SyncSender<Message>> for StationSender {
+    fn from(sender: mpsc::SyncSender<Message>) -> Self {
+        Self::Bounded(sender)
+    }
+}
+
+impl From<SemaphoreSender> for StationSender {
+    fn from(sender: SemaphoreSender) -> Self {
+        Self::Semaphore(sender)
+    }
+}
+
+/// Receptor del canal de entrada de una estación.
+#[derive(Debug)]
+pub enum StationReceiver {
+    /// Receptor de un canal `std::sync::mpsc` (con o sin límite)
+    Channel(mpsc::Receiver<Message>),
+    /// Receptor de un buffer con semáforos
+    Semaphore(SemaphoreReceiver),
+    /// Receptor de un canal acotado de crossbeam
+    #[cfg(feature = "crossbeam")]
+    Crossbeam(crossbeam_channel::Receiver<Message>),
+}
+
+/// Lo que recibe una estación que espera productos y comandos a la vez.
+#[cfg(feature = "crossbeam")]
+#[derive(Debug)]
+pub(crate) enum Received {
+    /// Mensaje de su canal de entrada
+    Message(Message),
+    /// Comando de control
+    Command(StationCommand),
+    /// El canal de comandos se cerró; no volverá a recibir comandos
+    CommandsClosed,
+}
+
+impl StationReceiver {
+    /// Espera el próximo mensaje.
+    /// 
+    /// # Returns
+    /// 
+    /// `Err` si todos los emisores se cerraron y no quedan mensajes
+    pub fn recv(&self) -> Result<Message, mpsc::RecvError> {
+        match self {
+            Self::Channel(receiver) => receiver.recv(),
+            Self::Semaphore(receiver) => receiver.recv(),
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam(receiver) => receiver.recv().map_err(|_| mpsc::RecvError),
+        }
+    }
+
+    /// Espera el próximo mensaje como máximo `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Message, mpsc::RecvTimeoutError> {
+        match self {
+            Self::Channel(receiver) => receiver.recv_timeout(timeout),
+            Self::Semaphore(receiver) => receiver.recv_timeout(timeout),
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam(receiver) => receiver.recv_timeout(timeout).map_err(|error| match error {
+                crossbeam_channel::RecvTimeoutError::Timeout => mpsc::RecvTimeoutError::Timeout,
+                crossbeam_channel::RecvTimeoutError::Disconnected => mpsc::RecvTimeoutError::Disconnected,
+            }),
+        }
+    }
+
+    /// Obtiene un mensaje sin esperar.
+    pub fn try_recv(&self) -> Result<Message, mpsc::TryRecvError> {
+        match self {
+            Self::Channel(receiver) => receiver.try_recv(),
+            Self::Semaphore(receiver) => receiver.try_recv(),
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam(receiver) => receiver.try_recv().map_err(|error| match error {
+                crossbeam_channel::TryRecvError::Empty => mpsc::TryRecvError::Empty,
+                crossbeam_channel::TryRecvError::Disconnected => mpsc::TryRecvError::Disconnected,
+            }),
+        }
+    }
+
+    /// Espera el próximo mensaje o comando como máximo `timeout` (`None` =
+    /// sin límite), con `select!` sobre ambos canales.
+    /// 
+    /// # Returns
+    /// 
+    /// `None` si el receptor no es de crossbeam y no puede esperar ambos
+    /// canales; en ese caso la estación usa [`StationReceiver::recv_timeout`]
+    /// y revisa los comandos aparte
+    #[cfg(feature = "crossbeam")]
+    pub(crate) fn select(
+        &self,
+        commands: &crossbeam_channel::Receiver<StationCommand>,
+        timeout: Option<Duration>,
+    ) -> Option<Result<Received, mpsc::RecvTimeoutError>> {
+        use crossbeam_channel::select;
+
+        let Self::Crossbeam(messages) = self else {
+            return None;
+        };
+        let on_message = |message: Result<Message, crossbeam_channel::RecvError>| {
+            message.map(Received::Message).map_err(|_| mpsc::RecvTimeoutError::Disconnected)
+        };
+        let on_command = |command: Result<StationCommand, crossbeam_channel::RecvError>| {
+            Ok(command.map_or(Received::CommandsClosed, Received::Command))
+        };
+        Some(match timeout {
+            None => select! {
+                recv(messages) -> message => on_message(message),
+                recv(commands) -> command => on_command(command),
+            },
+            Some(timeout) => select! {
+                recv(messages) -> message => on_message(message),
+                recv(commands) -> command => on_command(command),
+                default(timeout) => Err(mpsc::RecvTimeoutError::Timeout),
+            },
+        })
+    }
+}
+
+impl From<mpsc::Receiver<Message>> for StationReceiver {
+    fn from(receiver: mpsc::Receiver<Message>) -> Self {
+        Self::Channel(receiver)
+    }
+}
+
+/// Resultado de intentar tomar un permiso de un semáforo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Permit {
+    Acquired,
+    TimedOut,
+    Closed,
+}
+
+/// Semáforo contador implementado con un mutex y una variable de condición.
+/// 
+/// Un semáforo cerrado sigue entregando los permisos que le queden y luego
+/// rechaza las adquisiciones en lugar de bloquearlas.
+#[derive(Debug)]
+struct Semaphore {
+    /// Permisos disponibles y si el semáforo fue cerrado
+    state: Mutex<(usize, bool)>,
+    released: Condvar,
+}
+
+impl Semaphore {
+    fn new(permits: usize) -> Self {
+        Self {
+            state: Mutex::new((permits, false)),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Operación P (wait): toma un permiso, esperando como máximo hasta
+    /// `deadline` (`None` = sin límite).
+    fn acquire(&self, deadline: Option<Instant>) -> Permit {
+        let mut state = lock_recover(&self.state);
+        loop {
+            match *state {
+                (0, true) => return Permit::Closed,
+                (0, false) => {}
+                (ref mut permits, _) => {
+                    *permits -= 1;
+                    return Permit::Acquired;
+                }
+            }
+            state = match deadline {
+                None => self.released.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner()),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Permit::TimedOut;
+                    }
+                    self.released
+                        .wait_timeout(state, deadline - now)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .0
+                }
+            };
+        }
+    }
+
+    /// Operación V (signal): devuelve un permiso y despierta a un proceso en espera.
+    fn release(&self) {
+        lock_recover(&self.state).0 += 1;
+        self.released.notify_one();
+    }
+
+    /// Cierra el semáforo y despierta a todos los procesos en espera.
+    fn close(&self) {
+        lock_recover(&self.state).1 = true;
+        self.released.notify_all();
+    }
+}
+
+/// Buffer acotado compartido por los emisores y el receptor.
+#[derive(Debug)]
+struct SemaphoreQueue {
+    buffer: Mutex<VecDeque<Message>>,
+    /// Cuenta los mensajes en el buffer; se cierra al irse el último emisor
+    items: Semaphore,
+    /// Cuenta los espacios libres; se cierra al irse el receptor
+    slots: Semaphore,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+}
+
+/// Emisor hacia un buffer con semáforos.
+#[derive(Debug)]
+pub struct SemaphoreSender(Arc<SemaphoreQueue>);
+
+/// Receptor de un buffer con semáforos.
+#[derive(Debug)]
+pub struct SemaphoreReceiver(Arc<SemaphoreQueue>);
+
+/// Crea un buffer con semáforos de la capacidad indicada.
+fn semaphore_channel(capacity: usize) -> (SemaphoreSender, SemaphoreReceiver) {
+    let queue = Arc::new(SemaphoreQueue {
+        buffer: Mutex::new(VecDeque::with_capacity(capacity)),
+        items: Semaphore::new(0),
+        slots: Semaphore::new(capacity),
+        senders: AtomicUsize::new(1),
+        receiver_alive: AtomicBool::new(true),
+    });
+    (SemaphoreSender(Arc::clone(&queue)), SemaphoreReceiver(queue))
+}
+
+impl SemaphoreSender {
+    /// Envía un mensaje: P(espacios), encola, V(mensajes).
+    fn send(&self, message: Message) -> Result<(), mpsc::SendError<Message>> {
+        match self.0.slots.acquire(None) {
+            Permit::Acquired => self.push(message).map_err(mpsc::SendError),
+            _ => Err(mpsc::SendError(message)),
+        }
+    }
+
+    /// Envía un mensaje solo si hay un espacio libre.
+    fn try_send(&self, message: Message) -> Result<(), mpsc::TrySendError<Message>> {
+        match self.0.slots.acquire(Some(Instant::now())) {
+            Permit::Acquired => self.push(message).map_err(mpsc::TrySendError::Disconnected),
+            Permit::TimedOut => Err(mpsc::TrySendError::Full(message)),
+            Permit::Closed => Err(mpsc::TrySendError::Disconnected(message)),
+        }
+    }
+
+    /// Encola un mensaje con un espacio ya reservado.
+    fn push(&self, message: Message) -> Result<(), Message> {
+        if !self.0.receiver_alive.load(Ordering::Acquire) {
+            return Err(message);
+        }
+        lock_recover(&self.0.buffer).push_back(message);
+        self.0.items.release();
+        Ok(())
+    }
+}
+
+impl Clone for SemaphoreSender {
+    fn clone(&self) -> Self {
+        self.0.senders.fetch_add(1, Ordering::AcqRel);
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl Drop for SemaphoreSender {
+    fn drop(&mut self) {
+        if self.0.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.items.close();
+        }
+    }
+}
+
+impl SemaphoreReceiver {
+    fn recv(&self) -> Result<Message, mpsc::RecvError> {
+        match self.0.items.acquire(None) {
+            Permit::Acquired => Ok(self.pop()),
+            _ => Err(mpsc::RecvError),
+        }
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Result<Message, mpsc::RecvTimeoutError> {
+        match self.0.items.acquire(Some(Instant::now() + timeout)) {
+            Permit::Acquired => Ok(self.pop()),
+            Permit::TimedOut => Err(mpsc::RecvTimeoutError::Timeout),
+            Permit::Closed => Err(mpsc::RecvTimeoutError::Disconnected),
+        }
+    }
+
+    fn try_recv(&self) -> Result<Message, mpsc::TryRecvError> {
+        match self.0.items.acquire(Some(Instant::now())) {
+            Permit::Acquired => Ok(self.pop()),
+            Permit::TimedOut => Err(mpsc::TryRecvError::Empty),
+            Permit::Closed => Err(mpsc::TryRecvError::Disconnected),
+        }
+    }
+
+    /// Desencola un mensaje con un permiso ya tomado: desencola, V(espacios).
+    fn pop(&self) -> Message {
+        let message = lock_recover(&self.0.buffer)
+            .pop_front()
+            .expect("El semáforo de mensajes garantiza un mensaje en el buffer");
+        self.0.slots.release();
+        message
+    }
+}
+
+impl Drop for SemaphoreReceiver {
+    fn drop(&mut self) {
+        self.0.receiver_alive.store(false, Ordering::Release);
+        self.0.slots.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_semaphore_channel_blocks_when_full_and_reports_disconnection() {
+        let (sender, receiver) = ChannelBackend::Semaphore { capacity: 1 }.station_channel();
+        sender.send(Message::Shutdown).expect("Hay un espacio libre");
+        assert!(matches!(sender.try_send(Message::Shutdown), Err(mpsc::TrySendError::Full(_))));
+        assert!(matches!(receiver.try_recv(), Ok(Message::Shutdown)));
+        assert!(matches!(receiver.recv_timeout(Duration::from_millis(5)), Err(mpsc::RecvTimeoutError::Timeout)));
+
+        sender.send(Message::Shutdown).expect("El espacio fue liberado");
+        drop(sender);
+        assert!(receiver.recv().is_ok());
+        assert!(receiver.recv().is_err());
+
+        let (sender, receiver) = ChannelBackend::Semaphore { capacity: 1 }.station_channel();
+        drop(receiver);
+        assert!(sender.send(Message::Shutdown).is_err());
+    }
+
+    #[cfg(feature = "crossbeam")]
+    #[test]
+    fn test_crossbeam_receiver_selects_messages_and_commands() {
+        let (sender, receiver) = ChannelBackend::Crossbeam { capacity: 1 }.station_channel();
+        let (commands_tx, commands) = crossbeam_channel::unbounded();
+        sender.send(Message::Shutdown).expect("Hay un espacio libre");
+        assert!(matches!(sender.try_send(Message::Shutdown), Err(mpsc::TrySendError::Full(_))));
+        assert!(matches!(receiver.select(&commands, None), Some(Ok(Received::Message(Message::Shutdown)))));
+
+        // Un comando despierta una espera sin límite aunque no lleguen mensajes
+        let pause = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(10));
+            commands_tx.send(StationCommand::Pause).unwrap();
+        });
+        assert!(matches!(receiver.select(&commands, None), Some(Ok(Received::Command(StationCommand::Pause)))));
+        pause.join().unwrap();
+        assert!(matches!(receiver.select(&commands, Some(Duration::ZERO)), Some(Ok(Received::CommandsClosed))));
+
+        drop(sender);
+        let never = crossbeam_channel::never();
+        assert!(matches!(receiver.select(&never, Some(Duration::from_millis(5))), Some(Err(mpsc::RecvTimeoutError::Disconnected))));
+
+        let (_, receiver) = ChannelBackend::Bounded { capacity: 1 }.station_channel();
+        assert!(receiver.select(&never, None).is_none());
+    }
+}
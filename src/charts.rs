@@ -0,0 +1,496 @@
+//! # Módulo de Gráficos
+//! 
+//! Este módulo (feature `plotters`) genera gráficos de los resultados de una
+//! simulación a partir de [`SimulationMetrics`]: espera por producto,
+//! histograma de turnaround, largo de cola en el tiempo y utilización por
+//! estación. Cada gráfico se obtiene con una sola llamada y se guarda como
+//! SVG (generado directamente, sin dependencias) o como PNG (a través de
+//! `gnuplot`, que debe estar instalado). También puede exportarse el script
+//! de gnuplot para ajustarlo a mano.
+//! 
+//! ```rust,no_run
+//! use assembly_line_simulator::{charts, Simulation, SchedulingAlgorithm};
+//! 
+//! let metrics = Simulation::new(SchedulingAlgorithm::fcfs()).run().unwrap();
+//! charts::wait_times(&metrics).save("espera.svg").unwrap();
+//! charts::utilization(&metrics).save("utilizacion.png").unwrap();
+//! ```
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::metrics::SimulationMetrics;
+use crate::product::TimelineEvent;
+
+/// Ancho de los gráficos en píxeles
+const WIDTH: f64 = 640.0;
+/// Alto de los gráficos en píxeles
+const HEIGHT: f64 = 400.0;
+/// Márgenes (izquierdo, derecho, superior, inferior) del área de trazado
+const MARGIN: (f64, f64, f64, f64) = (70.0, 20.0, 40.0, 60.0);
+/// Número de divisiones del eje vertical
+const Y_TICKS: usize = 5;
+
+/// Datos de un gráfico.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChartData {
+    /// Barras con una etiqueta por categoría
+    Bars(Vec<(String, f64)>),
+    /// Serie escalonada de puntos `(x, y)` ordenados por `x`; cada valor se
+    /// mantiene hasta el punto siguiente
+    Steps(Vec<(f64, f64)>),
+}
+
+/// Gráfico listo para guardarse como SVG o PNG.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chart {
+    /// Título del gráfico
+    pub title: String,
+    /// Etiqueta del eje horizontal
+    pub x_label: String,
+    /// Etiqueta del eje vertical
+    pub y_label: String,
+    /// Datos a graficar
+    pub data: ChartData,
+}
+
+/// Gráfico de barras con el tiempo de espera de cada producto completado.
+pub fn wait_times(metrics: &SimulationMetrics) -> Chart {
+    Chart {
+        title: "Tiempo de espera por producto".to_string(),
+        x_label: "Producto".to_string(),
+        y_label: "Espera (s)".to_string(),
+        data: ChartData::Bars(
+            metrics.products
+                .iter()
+                .map(|product| (format!("#{:02}", product.product_id), product.total_wait_time.as_secs_f64()))
+                .collect(),
+        ),
+    }
+}
+
+/// Histograma del turnaround de los productos completados.
+/// 
+/// # Arguments
+/// 
+/// * `metrics` - Métricas de la simulación
+/// * `bins` - Número de intervalos del histograma
+/// 
+/// # Panics
+/// 
+/// Hace panic si `bins` es cero
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{charts::{self, ChartData}, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+/// let metrics = simulation.run().unwrap();
+/// 
+/// let ChartData::Bars(bars) = charts::turnaround_histogram(&metrics, 3).data else { unreachable!() };
+/// assert_eq!(bars.iter().map(|(_, count)| count).sum::<f64>(), 4.0);
+/// ```
+pub fn turnaround_histogram(metrics: &SimulationMetrics, bins: usize) -> Chart {
+    assert!(bins > 0, "El histograma requiere al menos un intervalo");
+
+    let turnarounds: Vec<f64> = metrics.products
+        .iter()
+        .map(|product| product.turnaround_time.as_secs_f64())
+        .collect();
+    let min = turnarounds.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = turnarounds.iter().copied().fold(0.0, f64::max);
+    let (min, width) = if turnarounds.is_empty() {
+        (0.0, 0.0)
+    } else {
+        (min, (max - min) / bins as f64)
+    };
+
+    let mut counts = vec![0.0; bins];
+    for turnaround in &turnarounds {
+        let bin = if width > 0.0 { ((turnaround - min) / width) as usize } else { 0 };
+        counts[bin.min(bins - 1)] += 1.0;
+    }
+
+    Chart {
+        title: "Histograma de turnaround".to_string(),
+        x_label: "Turnaround (s)".to_string(),
+        y_label: "Productos".to_string(),
+        data: ChartData::Bars(
+            counts
+                .into_iter()
+                .enumerate()
+                .map(|(bin, count)| {
+                    let start = min + width * bin as f64;
+                    (format!("{:.2}-{:.2}", start, start + width), count)
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// Largo de la cola de una estación a lo largo de la simulación.
+/// 
+/// Se reconstruye a partir de las esperas en cola de la línea de tiempo de
+/// cada producto (completos e incompletos).
+/// 
+/// # Arguments
+/// 
+/// * `metrics` - Métricas de la simulación
+/// * `station` - Índice de la estación (0-indexado)
+pub fn queue_length(metrics: &SimulationMetrics, station: usize) -> Chart {
+    let mut changes: Vec<(f64, i64)> = metrics.products
+        .iter()
+        .chain(&metrics.incomplete_products)
+        .flat_map(|product| &product.timeline)
+        .filter(|span| span.station == station && span.event == TimelineEvent::Queued)
+        .flat_map(|span| [(span.start.as_secs_f64(), 1), (span.end.as_secs_f64(), -1)])
+        .collect();
+    // Ante empates, las salidas se procesan antes que las entradas
+    changes.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    let mut points = vec![(0.0, 0.0)];
+    let mut length = 0;
+    for (time, change) in changes {
+        length += change;
+        match points.last_mut() {
+            Some(last) if last.0 == time => last.1 = length as f64,
+            _ => points.push((time, length as f64)),
+        }
+    }
+
+    let name = metrics.station_reports
+        .get(station)
+        .map_or_else(|| format!("Estación {}", station + 1), |report| report.name.clone());
+    Chart {
+        title: format!("Largo de cola en {}", name),
+        x_label: "Tiempo (s)".to_string(),
+        y_label: "Productos en cola".to_string(),
+        data: ChartData::Steps(points),
+    }
+}
+
+/// Gráfico de barras con la utilización de cada estación.
+pub fn utilization(metrics: &SimulationMetrics) -> Chart {
+    Chart {
+        title: "Utilización por estación".to_string(),
+        x_label: "Estación".to_string(),
+        y_label: "Utilización (%)".to_string(),
+        data: ChartData::Bars(
+            metrics.station_reports
+                .iter()
+                .map(|report| (report.name.clone(), report.utilization() * 100.0))
+                .collect(),
+        ),
+    }
+}
+
+impl Chart {
+    /// Valor máximo del eje vertical (al menos 1 para evitar ejes vacíos).
+    fn y_max(&self) -> f64 {
+        let max = match &self.data {
+            ChartData::Bars(bars) => bars.iter().map(|(_, value)| *value).fold(0.0, f64::max),
+            ChartData::Steps(points) => points.iter().map(|(_, value)| *value).fold(0.0, f64::max),
+        };
+        if max > 0.0 { max * 1.1 } else { 1.0 }
+    }
+
+    /// Genera el gráfico como documento SVG.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::charts::{Chart, ChartData};
+    /// 
+    /// let chart = Chart {
+    ///     title: "Prueba".to_string(),
+    ///     x_label: "Producto".to_string(),
+    ///     y_label: "Espera (s)".to_string(),
+    ///     data: ChartData::Bars(vec![("#01".to_string(), 1.0), ("#02".to_string(), 2.0)]),
+    /// };
+    /// let svg = chart.to_svg();
+    /// assert!(svg.starts_with("<svg"));
+    /// assert_eq!(svg.matches("<rect class=\"bar\"").count(), 2);
+    /// ```
+    pub fn to_svg(&self) -> String {
+        let (left, right, top, bottom) = MARGIN;
+        let plot_width = WIDTH - left - right;
+        let plot_height = HEIGHT - top - bottom;
+        let y_max = self.y_max();
+        let y = |value: f64| top + plot_height * (1.0 - value / y_max);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" \
+             viewBox=\"0 0 {WIDTH} {HEIGHT}\" font-family=\"sans-serif\" font-size=\"12\">\n"
+        );
+        svg.push_str(&format!("<rect width=\"{WIDTH}\" height=\"{HEIGHT}\" fill=\"white\"/>\n"));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"24\" text-anchor=\"middle\" font-size=\"16\">{}</text>\n",
+            WIDTH / 2.0,
+            xml_escape(&self.title)
+        ));
+
+        // Eje vertical con sus divisiones
+        for tick in 0..=Y_TICKS {
+            let value = y_max * tick as f64 / Y_TICKS as f64;
+            svg.push_str(&format!(
+                "<line x1=\"{left}\" y1=\"{0:.1}\" x2=\"{1}\" y2=\"{0:.1}\" stroke=\"#ddd\"/>\n\
+                 <text x=\"{2}\" y=\"{3:.1}\" text-anchor=\"end\">{4:.2}</text>\n",
+                y(value),
+                WIDTH - right,
+                left - 6.0,
+                y(value) + 4.0,
+                value
+            ));
+        }
+
+        match &self.data {
+            ChartData::Bars(bars) => {
+                let slot = plot_width / bars.len().max(1) as f64;
+                for (index, (label, value)) in bars.iter().enumerate() {
+                    let x = left + slot * index as f64;
+                    svg.push_str(&format!(
+                        "<rect class=\"bar\" x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"#4c78a8\"/>\n",
+                        x + slot * 0.1,
+                        y(*value),
+                        slot * 0.8,
+                        top + plot_height - y(*value)
+                    ));
+                    svg.push_str(&format!(
+                        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" font-size=\"10\">{}</text>\n",
+                        x + slot / 2.0,
+                        top + plot_height + 14.0,
+                        xml_escape(label)
+                    ));
+                }
+            }
+            ChartData::Steps(points) => {
+                let x_max = points.last().map_or(0.0, |(x, _)| *x).max(f64::EPSILON);
+                let x = |value: f64| left + plot_width * value / x_max;
+                let mut path = String::new();
+                for (index, (px, py)) in points.iter().enumerate() {
+                    if index == 0 {
+                        path.push_str(&format!("M{:.1},{:.1}", x(*px), y(*py)));
+                    } else {
+                        // Escalón: horizontal hasta el nuevo tiempo y luego vertical
+                        path.push_str(&format!(" H{:.1} V{:.1}", x(*px), y(*py)));
+                    }
+                }
+                svg.push_str(&format!("<path d=\"{}\" fill=\"none\" stroke=\"#4c78a8\" stroke-width=\"2\"/>\n", path));
+                for tick in 0..=Y_TICKS {
+                    let value = x_max * tick as f64 / Y_TICKS as f64;
+                    svg.push_str(&format!(
+                        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" font-size=\"10\">{:.2}</text>\n",
+                        x(value),
+                        top + plot_height + 14.0,
+                        value
+                    ));
+                }
+            }
+        }
+
+        // Ejes y etiquetas
+        svg.push_str(&format!(
+            "<line x1=\"{left}\" y1=\"{top}\" x2=\"{left}\" y2=\"{0}\" stroke=\"black\"/>\n\
+             <line x1=\"{left}\" y1=\"{0}\" x2=\"{1}\" y2=\"{0}\" stroke=\"black\"/>\n",
+            top + plot_height,
+            WIDTH - right
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n",
+            left + plot_width / 2.0,
+            HEIGHT - 16.0,
+            xml_escape(&self.x_label)
+        ));
+        svg.push_str(&format!(
+            "<text x=\"16\" y=\"{0:.1}\" text-anchor=\"middle\" transform=\"rotate(-90 16 {0:.1})\">{1}</text>\n",
+            top + plot_height / 2.0,
+            xml_escape(&self.y_label)
+        ));
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Genera un script de gnuplot que dibuja el gráfico.
+    /// 
+    /// La terminal se elige según la extensión de `output` (`pngcairo` para
+    /// `.png`, `svg` para el resto). Requiere gnuplot 5 o superior.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `output` - Ruta del archivo que generará gnuplot
+    pub fn to_gnuplot(&self, output: &str) -> String {
+        let terminal = if output.ends_with(".png") { "pngcairo" } else { "svg" };
+        let mut script = format!(
+            "set terminal {} size {},{}\nset output {}\nset title {}\nset xlabel {}\nset ylabel {}\nset yrange [0:*]\nset grid ytics\n",
+            terminal,
+            WIDTH,
+            HEIGHT,
+            gnuplot_string(output),
+            gnuplot_string(&self.title),
+            gnuplot_string(&self.x_label),
+            gnuplot_string(&self.y_label)
+        );
+        script.push_str("$datos << EOD\n");
+        match &self.data {
+            ChartData::Bars(bars) => {
+                for (index, (label, value)) in bars.iter().enumerate() {
+                    script.push_str(&format!("{} \"{}\" {}\n", index, label.replace('"', "'"), value));
+                }
+                script.push_str(
+                    "EOD\nset style fill solid 0.8\nset boxwidth 0.8\n\
+                     plot $datos using 1:3:xtic(2) with boxes linecolor rgb '#4c78a8' notitle\n",
+                );
+            }
+            ChartData::Steps(points) => {
+                for (x, y) in points {
+                    script.push_str(&format!("{} {}\n", x, y));
+                }
+                script.push_str("EOD\nplot $datos using 1:2 with steps linewidth 2 linecolor rgb '#4c78a8' notitle\n");
+            }
+        }
+        script
+    }
+
+    /// Guarda el gráfico según la extensión de la ruta.
+    /// 
+    /// Con `.svg` el documento se genera directamente; con `.png` se ejecuta
+    /// `gnuplot` con el script de [`Chart::to_gnuplot`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Ruta del archivo (`.svg` o `.png`)
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si el archivo se generó, o un error de E/S si la extensión
+    /// no es compatible, no se pudo escribir el archivo o gnuplot no está
+    /// disponible o falló
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("svg") => fs::write(path, self.to_svg()),
+            Some("png") => {
+                let script = self.to_gnuplot(&path.to_string_lossy());
+                let mut gnuplot = Command::new("gnuplot")
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .spawn()
+                    .map_err(|error| {
+                        io::Error::new(error.kind(), format!("se requiere gnuplot para generar PNG: {}", error))
+                    })?;
+                gnuplot
+                    .stdin
+                    .take()
+                    .expect("La entrada de gnuplot debe estar disponible")
+                    .write_all(script.as_bytes())?;
+                let status = gnuplot.wait()?;
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(io::Error::other(format!("gnuplot terminó con {}", status)))
+                }
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("formato de gráfico no soportado: '{}' (use .svg o .png)", path.display()),
+            )),
+        }
+    }
+}
+
+/// Escapa los caracteres especiales de XML.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Convierte un texto en un string de gnuplot entre comillas simples.
+fn gnuplot_string(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::metrics::ProductMetrics;
+    use crate::product::TimelineSpan;
+
+    fn product(id: usize, queued: &[(u64, u64)]) -> ProductMetrics {
+        ProductMetrics {
+            product_id: id,
+            class: None,
+            metadata: Default::default(),
+            arrival_time: Duration::ZERO,
+            total_wait_time: Duration::ZERO,
+            dependency_wait_time: Duration::ZERO,
+            resource_wait_time: Duration::ZERO,
+            turnaround_time: Duration::ZERO,
+            timer_drift: Duration::ZERO,
+            service_time: Duration::ZERO,
+            slowdown: 1.0,
+            due_date: None,
+            tardiness: Duration::ZERO,
+            station_times: Vec::new(),
+            visit_times: Vec::new(),
+            visit_resources: Vec::new(),
+            jockeys: 0,
+            slices: Vec::new(),
+            slice_units: Vec::new(),
+            slice_counts: Vec::new(),
+            timeline: queued
+                .iter()
+                .map(|&(start, end)| TimelineSpan {
+                    station: 0,
+                    event: TimelineEvent::Queued,
+                    start: Duration::from_millis(start),
+                    end: Duration::from_millis(end),
+                })
+                .collect(),
+            config_epoch: 0,
+            loss: None,
+            replaces: None,
+            replaced_by: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_queue_length_steps() {
+        let mut metrics = crate::metrics::MetricsCalculator::new().calculate_simulation_metrics(
+            &[],
+            &[],
+            crate::clock::Instant::now(),
+            crate::clock::Instant::now(),
+            Vec::new(),
+        );
+        metrics.products = vec![product(1, &[(0, 100)]), product(2, &[(50, 200)])];
+        metrics.incomplete_products = vec![product(3, &[(100, 300)])];
+
+        let ChartData::Steps(points) = queue_length(&metrics, 0).data else {
+            panic!("Se esperaba una serie escalonada");
+        };
+        // En t=0.1 sale el producto 1 y entra el 3: la cola se mantiene en 2
+        assert_eq!(points, [(0.0, 1.0), (0.05, 2.0), (0.1, 2.0), (0.2, 1.0), (0.3, 0.0)]);
+        assert!(queue_length(&metrics, 0).to_svg().contains("<path d=\"M"));
+    }
+
+    #[test]
+    fn test_gnuplot_script_and_unsupported_format() {
+        let chart = Chart {
+            title: "Cola de 'Corte'".to_string(),
+            x_label: "x".to_string(),
+            y_label: "y".to_string(),
+            data: ChartData::Bars(vec![("A".to_string(), 2.0)]),
+        };
+        let script = chart.to_gnuplot("salida.png");
+        assert!(script.starts_with("set terminal pngcairo"));
+        assert!(script.contains("set title 'Cola de ''Corte'''"));
+        assert!(script.contains("0 \"A\" 2\n"));
+        assert_eq!(chart.save("grafico.pdf").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,560 @@
+//! # Módulo de Relojes
+//! 
+//! Este módulo define la fuente de tiempo que usan las estaciones para
+//! registrar sus métricas. El reloj real usa `Instant::now()`, mientras que
+//! el reloj virtual solo avanza cuando el ejecutor lo indica, lo que permite
+//! simular horas de operación en milisegundos de tiempo real y obtener
+//! resultados exactamente reproducibles.
+//! 
+//! El resto de la biblioteca usa el tipo [`Instant`] de este módulo. En
+//! plataformas nativas es `std::time::Instant`; en `wasm32`, donde no hay
+//! reloj del sistema, es un instante puramente virtual, de modo que el modo
+//! de tiempo virtual (`Simulation::run_virtual`) funciona sin hilos ni
+//! acceso al reloj real.
+//! 
+//! Para modelar líneas en otra escala que los milisegundos, [`SimTime`]
+//! expresa los tiempos en ticks. La longitud del tick es un ajuste de cada
+//! simulación (`Simulation::set_tick_length`): sus métricas se convierten a
+//! ticks con `SimulationMetrics::to_ticks` y sus reportes pueden mostrarse
+//! en ticks con [`ClockFormat::Ticks`].
+
+use std::cell::Cell;
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub use self::wasm::Instant;
+
+/// Reloj virtual que avanza de forma explícita.
+/// 
+/// Los instantes que produce son relativos a un origen real tomado al
+/// crearlo, por lo que pueden usarse donde se espera un `Instant`. Las
+/// copias del reloj comparten el mismo tiempo.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::VirtualClock;
+/// 
+/// let clock = VirtualClock::new();
+/// let start = clock.now();
+/// clock.advance_to(start + Duration::from_secs(3600));
+/// assert_eq!(clock.elapsed(), Duration::from_secs(3600));
+/// ```
+#[derive(Clone, Debug)]
+pub struct VirtualClock {
+    /// Instante real que corresponde al tiempo virtual cero
+    origin: Instant,
+    /// Tiempo virtual transcurrido desde el origen, en nanosegundos
+    elapsed_nanos: Arc<AtomicU64>,
+}
+
+impl VirtualClock {
+    /// Crea un reloj virtual detenido en el tiempo cero.
+    pub fn new() -> Self {
+        Self {
+            origin: virtual_origin(),
+            elapsed_nanos: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Retorna el instante virtual actual.
+    pub fn now(&self) -> Instant {
+        self.origin + self.elapsed()
+    }
+
+    /// Retorna el tiempo virtual transcurrido desde el origen.
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_nanos.load(Ordering::Acquire))
+    }
+
+    /// Avanza el reloj hasta el instante indicado.
+    /// 
+    /// El reloj nunca retrocede: si el instante ya pasó, no cambia.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `instant` - Instante virtual al que se desea avanzar
+    pub fn advance_to(&self, instant: Instant) {
+        let target = instant.saturating_duration_since(self.origin).as_nanos();
+        let target = u64::try_from(target).unwrap_or(u64::MAX);
+        self.elapsed_nanos.fetch_max(target, Ordering::AcqRel);
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Instante que corresponde al tiempo virtual cero.
+#[cfg(not(target_arch = "wasm32"))]
+fn virtual_origin() -> Instant {
+    Instant::now()
+}
+
+/// Instante que corresponde al tiempo virtual cero.
+#[cfg(target_arch = "wasm32")]
+fn virtual_origin() -> Instant {
+    Instant::ORIGIN
+}
+
+/// Fuente de tiempo de una simulación.
+#[derive(Clone, Debug, Default)]
+pub enum Clock {
+    /// Tiempo real del sistema (comportamiento por defecto)
+    #[default]
+    Real,
+    /// Tiempo virtual controlado por el ejecutor de la simulación
+    Virtual(VirtualClock),
+}
+
+impl Clock {
+    /// Retorna el instante actual según la fuente de tiempo.
+    /// 
+    /// # Panics
+    /// 
+    /// En `wasm32` hace panic si el reloj es real, ya que la plataforma no
+    /// tiene reloj del sistema
+    pub fn now(&self) -> Instant {
+        match self {
+            Clock::Real => Instant::now(),
+            Clock::Virtual(clock) => clock.now(),
+        }
+    }
+
+    /// Indica si el reloj es virtual.
+    pub fn is_virtual(&self) -> bool {
+        matches!(self, Clock::Virtual(_))
+    }
+}
+
+/// Formato con el que se muestran los tiempos en la salida estándar, los
+/// reportes de texto y el CSV.
+/// 
+/// Los tiempos siempre son relativos al inicio de la simulación. Cada
+/// simulación puede tener su propio formato
+/// ([`Simulation::set_clock_format`](crate::Simulation::set_clock_format)
+/// o [`MetricsCalculator::with_clock_format`](crate::MetricsCalculator::with_clock_format)),
+/// que se aplica a sus registros y reportes sin afectar a las demás
+/// simulaciones del proceso. Las que no fijan uno usan el formato global
+/// ([`ClockFormat::set_global`]), que el binario toma de `--reloj`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClockFormat {
+    /// Milisegundos enteros (`1234ms`)
+    Millis,
+    /// Segundos con tres decimales (`1.234s`, formato por defecto)
+    #[default]
+    Seconds,
+    /// Reloj de pared relativo (`00:00:01.234`)
+    Clock,
+    /// Ticks de simulación enteros (`1234t`; ver [`SimTime`]), con la
+    /// longitud de tick de la simulación
+    Ticks,
+}
+
+/// Formato global, guardado como el discriminante de `ClockFormat`.
+static CLOCK_FORMAT: AtomicU8 = AtomicU8::new(ClockFormat::Seconds as u8);
+
+thread_local! {
+    /// Formato y longitud de tick de la simulación que el hilo está
+    /// ejecutando o reportando.
+    static SCOPED_FORMAT: Cell<Option<(ClockFormat, Duration)>> = const { Cell::new(None) };
+}
+
+impl ClockFormat {
+    /// Retorna el formato global, que usan las simulaciones sin un formato
+    /// propio.
+    pub fn global() -> Self {
+        match CLOCK_FORMAT.load(Ordering::Relaxed) {
+            value if value == Self::Millis as u8 => Self::Millis,
+            value if value == Self::Clock as u8 => Self::Clock,
+            value if value == Self::Ticks as u8 => Self::Ticks,
+            _ => Self::Seconds,
+        }
+    }
+
+    /// Fija el formato por defecto de los registros y los reportes del
+    /// proceso. Las simulaciones con un formato propio no cambian.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{ClockFormat, MetricsCalculator};
+    /// 
+    /// ClockFormat::set_global(ClockFormat::Millis);
+    /// assert_eq!(MetricsCalculator::format_duration(Duration::from_millis(1234)), "1234ms");
+    /// ```
+    pub fn set_global(format: ClockFormat) {
+        CLOCK_FORMAT.store(format as u8, Ordering::Relaxed);
+    }
+
+    /// Retorna el formato vigente en el hilo actual: el de la simulación que
+    /// el hilo está ejecutando o reportando, o el global fuera de ellas.
+    pub fn current() -> Self {
+        SCOPED_FORMAT.with(Cell::get).map_or_else(Self::global, |(format, _)| format)
+    }
+
+    /// Retorna la longitud de tick vigente en el hilo actual: la de la
+    /// simulación que el hilo está ejecutando o reportando, o
+    /// [`SimTime::DEFAULT_TICK_LENGTH`] fuera de ellas.
+    pub fn current_tick_length() -> Duration {
+        SCOPED_FORMAT.with(Cell::get).map_or(SimTime::DEFAULT_TICK_LENGTH, |(_, tick)| tick)
+    }
+
+    /// Hace de este formato y de la longitud de tick `tick` los vigentes en
+    /// el hilo actual hasta que se descarta el guardián, que restaura los
+    /// anteriores.
+    pub(crate) fn enter(self, tick: Duration) -> ClockFormatScope {
+        ClockFormatScope { previous: SCOPED_FORMAT.with(|scoped| scoped.replace(Some((self, tick)))) }
+    }
+
+    /// Formatea una duración con su unidad.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::ClockFormat;
+    /// 
+    /// let elapsed = Duration::from_millis(3_723_004);
+    /// assert_eq!(ClockFormat::Millis.format(elapsed), "3723004ms");
+    /// assert_eq!(ClockFormat::Seconds.format(elapsed), "3723.004s");
+    /// assert_eq!(ClockFormat::Clock.format(elapsed), "01:02:03.004");
+    /// ```
+    pub fn format(&self, duration: Duration) -> String {
+        match self {
+            Self::Millis | Self::Seconds | Self::Ticks => format!("{}{}", self.format_value(duration), self.unit()),
+            Self::Clock => self.format_value(duration),
+        }
+    }
+
+    /// Formatea una duración sin unidad, para columnas de CSV.
+    /// 
+    /// Los ticks usan la longitud de tick vigente
+    /// ([`ClockFormat::current_tick_length`]).
+    pub fn format_value(&self, duration: Duration) -> String {
+        let millis = duration.as_millis();
+        match self {
+            Self::Millis => millis.to_string(),
+            Self::Seconds => format!("{}.{:03}", millis / 1000, millis % 1000),
+            Self::Clock => format!(
+                "{:02}:{:02}:{:02}.{:03}",
+                millis / 3_600_000,
+                millis / 60_000 % 60,
+                millis / 1000 % 60,
+                millis % 1000
+            ),
+            Self::Ticks => SimTime::from_duration(duration, Self::current_tick_length()).ticks().to_string(),
+        }
+    }
+
+    /// Unidad que acompaña a los valores (vacía para el reloj de pared).
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::Millis => "ms",
+            Self::Seconds => "s",
+            Self::Clock => "",
+            Self::Ticks => "t",
+        }
+    }
+}
+
+/// Guardián de [`ClockFormat::enter`].
+pub(crate) struct ClockFormatScope {
+    previous: Option<(ClockFormat, Duration)>,
+}
+
+impl Drop for ClockFormatScope {
+    fn drop(&mut self) {
+        SCOPED_FORMAT.with(|scoped| scoped.set(self.previous));
+    }
+}
+
+impl fmt::Display for ClockFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Millis => write!(f, "ms"),
+            Self::Seconds => write!(f, "s"),
+            Self::Clock => write!(f, "hms"),
+            Self::Ticks => write!(f, "ticks"),
+        }
+    }
+}
+
+impl FromStr for ClockFormat {
+    type Err = String;
+
+    /// Interpreta `ms`, `s`, `hms` o `ticks`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "ms" => Ok(Self::Millis),
+            "s" => Ok(Self::Seconds),
+            "hms" => Ok(Self::Clock),
+            "ticks" => Ok(Self::Ticks),
+            other => Err(format!("Formato de reloj desconocido '{}' (use ms, s, hms o ticks)", other)),
+        }
+    }
+}
+
+/// Tiempo de simulación medido en ticks.
+/// 
+/// Un `SimTime` es solo un número de ticks: la longitud del tick la fija
+/// cada simulación ([`Simulation::set_tick_length`](crate::Simulation::set_tick_length),
+/// [`SimTime::DEFAULT_TICK_LENGTH`] por defecto) y las conversiones entre
+/// ticks y `Duration` la reciben explícitamente, de modo que simulaciones
+/// con distintos ticks pueden correr en paralelo. Así una línea con tiempos
+/// de segundos o de microsegundos se configura con números enteros de
+/// ticks, y en tiempo virtual el tick pasa a ser la unidad natural del
+/// modelo. Las métricas de una simulación se expresan en sus ticks con
+/// [`SimulationMetrics::to_ticks`](crate::metrics::SimulationMetrics::to_ticks).
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{SchedulingAlgorithm, SimTime, Simulation, StationConfig};
+/// 
+/// let tick = Duration::from_micros(500);
+/// let station = StationConfig::new("Soldadura", SimTime::from_ticks(40).to_duration(tick));
+/// assert_eq!(station.processing_time, Duration::from_millis(20));
+/// 
+/// let mut simulation = Simulation::with_config(vec![station], SchedulingAlgorithm::fcfs(), vec![Duration::ZERO]);
+/// simulation.set_tick_length(tick);
+/// let metrics = simulation.run().expect("La simulación falló");
+/// assert_eq!(metrics.to_ticks(metrics.products[0].service_time), SimTime::from_ticks(40));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SimTime(u64);
+
+impl SimTime {
+    /// Tiempo cero
+    pub const ZERO: SimTime = SimTime(0);
+
+    /// Longitud de tick de las simulaciones que no fijan una (1 ms)
+    pub const DEFAULT_TICK_LENGTH: Duration = Duration::from_millis(1);
+
+    /// Crea un tiempo a partir de un número de ticks.
+    pub const fn from_ticks(ticks: u64) -> Self {
+        Self(ticks)
+    }
+
+    /// Retorna el número de ticks.
+    pub const fn ticks(&self) -> u64 {
+        self.0
+    }
+
+    /// Convierte una duración en ticks de longitud `tick`, redondeando al
+    /// tick más cercano.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `tick` es cero
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::SimTime;
+    /// 
+    /// let ticks = SimTime::from_duration(Duration::from_micros(2_600), SimTime::DEFAULT_TICK_LENGTH);
+    /// assert_eq!(ticks.ticks(), 3);
+    /// ```
+    pub fn from_duration(duration: Duration, tick: Duration) -> Self {
+        let tick = tick.as_nanos();
+        assert!(tick > 0, "La longitud del tick debe ser mayor a cero");
+        let ticks = (duration.as_nanos() + tick / 2) / tick;
+        Self(u64::try_from(ticks).unwrap_or(u64::MAX))
+    }
+
+    /// Convierte el tiempo en una duración con ticks de longitud `tick`.
+    pub fn to_duration(&self, tick: Duration) -> Duration {
+        let nanos = u128::from(self.0) * tick.as_nanos();
+        let secs = u64::try_from(nanos / 1_000_000_000).unwrap_or(u64::MAX);
+        Duration::new(secs, (nanos % 1_000_000_000) as u32)
+    }
+
+    /// Resta otro tiempo, retornando cero si es mayor.
+    pub fn saturating_sub(self, other: SimTime) -> SimTime {
+        SimTime(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Add for SimTime {
+    type Output = SimTime;
+
+    fn add(self, other: SimTime) -> SimTime {
+        SimTime(self.0 + other.0)
+    }
+}
+
+impl AddAssign for SimTime {
+    fn add_assign(&mut self, other: SimTime) {
+        self.0 += other.0;
+    }
+}
+
+impl Sub for SimTime {
+    type Output = SimTime;
+
+    fn sub(self, other: SimTime) -> SimTime {
+        SimTime(self.0 - other.0)
+    }
+}
+
+impl fmt::Display for SimTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}t", self.0)
+    }
+}
+
+/// Instante virtual para `wasm32`, con la misma interfaz que
+/// `std::time::Instant`.
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use std::ops::{Add, AddAssign, Sub, SubAssign};
+    use std::time::Duration;
+
+    /// Instante medido como duración desde el origen del tiempo virtual.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Instant(Duration);
+
+    impl Instant {
+        /// Origen del tiempo virtual.
+        pub(crate) const ORIGIN: Instant = Instant(Duration::ZERO);
+
+        /// # Panics
+        /// 
+        /// Siempre hace panic: `wasm32` no tiene reloj del sistema
+        pub fn now() -> Instant {
+            panic!("El reloj real no está disponible en wasm32; use Simulation::run_virtual")
+        }
+
+        pub fn duration_since(&self, earlier: Instant) -> Duration {
+            self.saturating_duration_since(earlier)
+        }
+
+        pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+            self.0.checked_sub(earlier.0)
+        }
+
+        pub fn saturating_duration_since(&self, earlier: Instant) -> Duration {
+            self.0.saturating_sub(earlier.0)
+        }
+
+        pub fn elapsed(&self) -> Duration {
+            Instant::now().duration_since(*self)
+        }
+
+        pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
+            self.0.checked_add(duration).map(Instant)
+        }
+
+        pub fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+            self.0.checked_sub(duration).map(Instant)
+        }
+    }
+
+    impl Add<Duration> for Instant {
+        type Output = Instant;
+
+        fn add(self, duration: Duration) -> Instant {
+            Instant(self.0 + duration)
+        }
+    }
+
+    impl AddAssign<Duration> for Instant {
+        fn add_assign(&mut self, duration: Duration) {
+            self.0 += duration;
+        }
+    }
+
+    impl Sub<Duration> for Instant {
+        type Output = Instant;
+
+        fn sub(self, duration: Duration) -> Instant {
+            Instant(self.0 - duration)
+        }
+    }
+
+    impl SubAssign<Duration> for Instant {
+        fn sub_assign(&mut self, duration: Duration) {
+            self.0 -= duration;
+        }
+    }
+
+    impl Sub<Instant> for Instant {
+        type Output = Duration;
+
+        fn sub(self, other: Instant) -> Duration {
+            self.duration_since(other)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_virtual_clock_never_goes_back() {
+        let clock = VirtualClock::new();
+        let start = clock.now();
+        clock.advance_to(start + Duration::from_millis(500));
+        clock.advance_to(start + Duration::from_millis(200));
+        assert_eq!(clock.elapsed(), Duration::from_millis(500));
+
+        let shared = Clock::Virtual(clock.clone());
+        assert!(shared.is_virtual());
+        assert_eq!(shared.now(), start + Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_clock_format_round_trip() {
+        for format in [ClockFormat::Millis, ClockFormat::Seconds, ClockFormat::Clock, ClockFormat::Ticks] {
+            assert_eq!(format.to_string().parse::<ClockFormat>(), Ok(format));
+        }
+        assert!("minutos".parse::<ClockFormat>().is_err());
+        assert_eq!(ClockFormat::Seconds.format_value(Duration::from_micros(1_500)), "0.001");
+        assert_eq!(ClockFormat::Clock.format(Duration::from_secs(90_000)), "25:00:00.000");
+    }
+
+    #[test]
+    fn test_clock_format_scope_restores_the_previous_format() {
+        let outer = ClockFormat::current();
+        {
+            let _millis = ClockFormat::Millis.enter(SimTime::DEFAULT_TICK_LENGTH);
+            assert_eq!(ClockFormat::current(), ClockFormat::Millis);
+            {
+                let _ticks = ClockFormat::Ticks.enter(Duration::from_micros(100));
+                assert_eq!(ClockFormat::current(), ClockFormat::Ticks);
+                assert_eq!(ClockFormat::Ticks.format(Duration::from_millis(2)), "20t");
+            }
+            assert_eq!(ClockFormat::current(), ClockFormat::Millis);
+            assert_eq!(ClockFormat::Ticks.format(Duration::from_millis(2)), "2t");
+        }
+        assert_eq!(ClockFormat::current(), outer);
+    }
+
+    #[test]
+    fn test_sim_time_arithmetic() {
+        let a = SimTime::from_ticks(7);
+        let b = SimTime::from_ticks(3);
+        assert_eq!(a + b, SimTime::from_ticks(10));
+        assert_eq!(a - b, SimTime::from_ticks(4));
+        assert_eq!(b.saturating_sub(a), SimTime::ZERO);
+        assert_eq!(a.to_string(), "7t");
+        let tick = Duration::from_micros(10);
+        assert_eq!(a.to_duration(tick), Duration::from_micros(70));
+        assert_eq!(SimTime::from_duration(a.to_duration(tick), tick), a);
+    }
+}
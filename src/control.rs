@@ -0,0 +1,243 @@
+//! # Módulo de Control de Estaciones
+//! 
+//! Este módulo define los comandos que modifican una estación mientras la
+//! simulación está en curso (cambiar el quantum o el tiempo de
+//! procesamiento, pausarla o vaciar su cola) y el canal por el que se
+//! envían. Cada estación tiene su propio canal de comandos; [`LineControl`]
+//! agrupa los de toda la línea para enviar un comando a una estación o a
+//! todas a la vez, lo que permite experimentos dinámicos y modos
+//! interactivos sobre una simulación iniciada con
+//! [`Simulation::start`](crate::Simulation::start).
+//! 
+//! Con [`ChannelBackend::Crossbeam`](crate::ChannelBackend) los canales de
+//! comandos son también de crossbeam, para que cada estación los espere
+//! junto con sus productos.
+
+use std::fmt;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::channel::ChannelBackend;
+use crate::metrics::MetricsCalculator;
+use crate::station::StationConfig;
+
+/// Comando que modifica una estación durante la ejecución.
+/// 
+/// La estación aplica los comandos entre porciones de procesamiento, por
+/// lo que la porción en curso siempre termina con los ajustes anteriores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StationCommand {
+    /// Limita cada porción al quantum indicado. Con FCFS, EDD o CR la
+    /// estación pasa a interrumpir los productos como Round Robin
+    SetQuantum(Duration),
+    /// Reemplaza el tiempo de procesamiento de los productos que lleguen a
+    /// la estación después del comando; los que ya están en cola conservan
+    /// su tiempo restante
+    SetProcessingTime(Duration),
+    /// Deja de tomar productos de la cola; los que llegan siguen encolándose
+    Pause,
+    /// Reanuda una estación en pausa
+    Resume,
+    /// Descarta los productos en cola, que se reportan como incompletos
+    FlushQueue,
+}
+
+impl StationCommand {
+    /// Verifica que el comando pueda aplicarse.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el comando asigna un quantum o un tiempo de
+    /// procesamiento nulo
+    fn check(&self) {
+        match self {
+            Self::SetQuantum(quantum) => assert!(!quantum.is_zero(), "El quantum debe ser mayor que cero"),
+            Self::SetProcessingTime(time) => {
+                assert!(!time.is_zero(), "El tiempo de procesamiento debe ser mayor que cero")
+            }
+            Self::Pause | Self::Resume | Self::FlushQueue => {}
+        }
+    }
+}
+
+impl fmt::Display for StationCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = MetricsCalculator::format_duration;
+        match self {
+            Self::SetQuantum(quantum) => write!(f, "quantum = {}", time(*quantum)),
+            Self::SetProcessingTime(processing_time) => {
+                write!(f, "tiempo de procesamiento = {}", time(*processing_time))
+            }
+            Self::Pause => write!(f, "pausar"),
+            Self::Resume => write!(f, "reanudar"),
+            Self::FlushQueue => write!(f, "vaciar cola"),
+        }
+    }
+}
+
+/// Emisor del canal de comandos de una estación.
+#[derive(Clone, Debug)]
+enum CommandSender {
+    Std(mpsc::Sender<StationCommand>),
+    #[cfg(feature = "crossbeam")]
+    Crossbeam(crossbeam_channel::Sender<StationCommand>),
+}
+
+impl CommandSender {
+    fn send(&self, command: StationCommand) -> Result<(), mpsc::SendError<StationCommand>> {
+        match self {
+            Self::Std(sender) => sender.send(command),
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam(sender) => sender
+                .send(command)
+                .map_err(|crossbeam_channel::SendError(command)| mpsc::SendError(command)),
+        }
+    }
+}
+
+/// Receptor del canal de comandos de una estación.
+#[derive(Debug)]
+pub(crate) enum CommandReceiver {
+    Std(mpsc::Receiver<StationCommand>),
+    #[cfg(feature = "crossbeam")]
+    Crossbeam(crossbeam_channel::Receiver<StationCommand>),
+}
+
+impl CommandReceiver {
+    /// Obtiene un comando pendiente sin esperar.
+    pub(crate) fn try_recv(&self) -> Option<StationCommand> {
+        match self {
+            Self::Std(receiver) => receiver.try_recv().ok(),
+            #[cfg(feature = "crossbeam")]
+            Self::Crossbeam(receiver) => receiver.try_recv().ok(),
+        }
+    }
+
+    /// Receptor de crossbeam que la estación espera con `select!` junto con
+    /// su canal de entrada, si los comandos viajan por crossbeam.
+    #[cfg(feature = "crossbeam")]
+    pub(crate) fn selectable(&self) -> Option<crossbeam_channel::Receiver<StationCommand>> {
+        match self {
+            Self::Std(_) => None,
+            Self::Crossbeam(receiver) => Some(receiver.clone()),
+        }
+    }
+}
+
+/// Canales de comandos de todas las estaciones de una línea en ejecución.
+/// 
+/// Se obtiene con [`SimulationHandle::control`](crate::SimulationHandle::control)
+/// y puede clonarse para enviar comandos desde otros hilos. Una estación
+/// ociosa aplica los comandos al recibir su siguiente producto y una en
+/// pausa los revisa periódicamente. Mientras una estación con productos
+/// está en pausa la línea no termina, salvo que se cancele la simulación.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationCommand, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Horno", Duration::from_millis(20))];
+/// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 5]);
+/// let handle = simulation.start();
+/// let control = handle.control();
+/// 
+/// control.send(0, StationCommand::Pause).unwrap();
+/// std::thread::sleep(Duration::from_millis(30));
+/// control.send(0, StationCommand::FlushQueue).unwrap();
+/// assert_eq!(control.broadcast(StationCommand::Resume), 1);
+/// 
+/// let metrics = handle.wait().unwrap();
+/// assert!(!metrics.incomplete_products.is_empty());
+/// assert_eq!(metrics.products.len() + metrics.incomplete_products.len(), 5);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LineControl {
+    /// Nombre y canal de comandos de cada estación, por índice
+    stations: Vec<(String, CommandSender)>,
+}
+
+impl LineControl {
+    /// Crea los canales de comandos de una línea.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station_configs` - Configuración de las estaciones de la línea
+    /// * `backend` - Tipo de canal entre estaciones; con crossbeam los
+    ///   comandos también viajan por canales de crossbeam
+    /// 
+    /// # Returns
+    /// 
+    /// El control de la línea y el receptor de comandos de cada estación,
+    /// en el mismo orden que `station_configs`
+    pub(crate) fn channels(station_configs: &[StationConfig], backend: ChannelBackend) -> (Self, Vec<CommandReceiver>) {
+        let (stations, receivers) = station_configs
+            .iter()
+            .map(|config| {
+                let (sender, receiver) = match backend {
+                    #[cfg(feature = "crossbeam")]
+                    ChannelBackend::Crossbeam { .. } => {
+                        let (sender, receiver) = crossbeam_channel::unbounded();
+                        (CommandSender::Crossbeam(sender), CommandReceiver::Crossbeam(receiver))
+                    }
+                    ChannelBackend::Unbounded | ChannelBackend::Bounded { .. } | ChannelBackend::Semaphore { .. } => {
+                        let (sender, receiver) = mpsc::channel();
+                        (CommandSender::Std(sender), CommandReceiver::Std(receiver))
+                    }
+                };
+                ((config.name.clone(), sender), receiver)
+            })
+            .unzip();
+        (Self { stations }, receivers)
+    }
+
+    /// Busca el índice de una estación por su nombre.
+    pub fn station_index(&self, name: &str) -> Option<usize> {
+        self.stations.iter().position(|(station, _)| station == name)
+    }
+
+    /// Envía un comando a una estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station` - Índice de la estación (0-indexado)
+    /// * `command` - Comando a aplicar
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si el comando fue enviado, o `Err` con el comando si la
+    /// estación ya finalizó
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la estación no existe o si el comando asigna un
+    /// quantum o un tiempo de procesamiento nulo
+    pub fn send(&self, station: usize, command: StationCommand) -> Result<(), mpsc::SendError<StationCommand>> {
+        command.check();
+        let (_, sender) = self
+            .stations
+            .get(station)
+            .unwrap_or_else(|| panic!("Estación inexistente: {}", station));
+        sender.send(command)
+    }
+
+    /// Envía un comando a todas las estaciones.
+    /// 
+    /// # Returns
+    /// 
+    /// Número de estaciones que recibieron el comando (las que ya
+    /// finalizaron lo descartan)
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el comando asigna un quantum o un tiempo de
+    /// procesamiento nulo
+    pub fn broadcast(&self, command: StationCommand) -> usize {
+        command.check();
+        self.stations
+            .iter()
+            .filter(|(_, sender)| sender.send(command).is_ok())
+            .count()
+    }
+}
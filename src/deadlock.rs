@@ -0,0 +1,258 @@
+//! # Módulo de Detección de Interbloqueos
+//! 
+//! Con canales acotados o recursos compartidos la línea puede quedar en un
+//! interbloqueo: por ejemplo, la última estación espera espacio en el buffer
+//! de la primera (ruta reentrante) mientras la primera espera espacio en el
+//! buffer de la segunda. Ningún hilo vuelve a avanzar y la simulación
+//! quedaría esperando indefinidamente.
+//! 
+//! Cada estación publica su estado en vivo en una sonda (`StationProbe`):
+//! estado de operación, productos en cola, producto en servicio y recursos
+//! retenidos. El colector consulta las sondas periódicamente y, si hay
+//! productos en la línea, ninguna estación está procesando y ninguna sonda
+//! registró actividad durante el intervalo configurado, declara el
+//! interbloqueo y retorna un [`DeadlockReport`] con el volcado del estado.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::clock::Instant;
+use crate::error::StalledProduct;
+use crate::metrics::MetricsCalculator;
+use crate::product::{lock_recover, Product};
+use crate::status::{StationObserver, StationStatus, StatusTransition};
+
+/// Estado de una estación en el momento de detectarse un interbloqueo.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StationSnapshot {
+    /// Índice de la estación en la línea
+    pub index: usize,
+    /// Nombre de la estación
+    pub name: String,
+    /// Último estado de operación publicado
+    pub status: StationStatus,
+    /// Producto que la estación tiene tomado (procesándolo o intentando enviarlo)
+    pub in_service: Option<usize>,
+    /// IDs de los productos en la cola interna de la estación
+    pub queue: Vec<usize>,
+    /// IDs de los productos dirigidos a la estación que aún no tomó (en su
+    /// canal de entrada o esperando entrar en él)
+    pub in_buffer: Vec<usize>,
+    /// Recursos compartidos que la estación retiene
+    pub held_resources: Vec<String>,
+    /// Recurso compartido que la estación espera, si está bloqueada por uno
+    pub waiting_for: Option<String>,
+}
+
+impl StationSnapshot {
+    fn new(index: usize, name: String) -> Self {
+        Self {
+            index,
+            name,
+            status: StationStatus::Idle,
+            in_service: None,
+            queue: Vec::new(),
+            in_buffer: Vec::new(),
+            held_resources: Vec::new(),
+            waiting_for: None,
+        }
+    }
+}
+
+impl fmt::Display for StationSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ids = |ids: &[usize]| -> String {
+            if ids.is_empty() {
+                "-".to_string()
+            } else {
+                ids.iter().map(|id| format!("#{:02}", id)).collect::<Vec<_>>().join(" ")
+            }
+        };
+        write!(f, "[{}] {}", self.name, self.status)?;
+        match (&self.waiting_for, self.status) {
+            (Some(resource), _) => write!(f, " esperando el recurso '{}'", resource)?,
+            (None, StationStatus::Blocked) => write!(f, " esperando espacio en el buffer siguiente")?,
+            _ => {}
+        }
+        if let Some(product) = self.in_service {
+            write!(f, "; en servicio: #{:02}", product)?;
+        }
+        write!(f, "; cola: {}; canal: {}", ids(&self.queue), ids(&self.in_buffer))?;
+        if !self.held_resources.is_empty() {
+            write!(f, "; retiene: {}", self.held_resources.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Diagnóstico de un interbloqueo detectado durante la simulación.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeadlockReport {
+    /// Intervalo sin actividad que disparó la detección
+    pub idle_interval: Duration,
+    /// Tiempo transcurrido desde el inicio de la simulación
+    pub elapsed: Duration,
+    /// Estado de cada estación
+    pub stations: Vec<StationSnapshot>,
+    /// Productos pendientes y la estación que los retiene
+    pub in_flight: Vec<StalledProduct>,
+}
+
+impl DeadlockReport {
+    /// Estaciones bloqueadas en el momento de la detección.
+    pub fn blocked_stations(&self) -> Vec<&StationSnapshot> {
+        self.stations
+            .iter()
+            .filter(|station| station.status == StationStatus::Blocked)
+            .collect()
+    }
+}
+
+impl fmt::Display for DeadlockReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Interbloqueo: sin actividad durante {} ({} transcurridos, {} productos retenidos)",
+            MetricsCalculator::format_duration(self.idle_interval),
+            MetricsCalculator::format_duration(self.elapsed),
+            self.in_flight.len()
+        )?;
+        for station in &self.stations {
+            writeln!(f, "  {}", station)?;
+        }
+        Ok(())
+    }
+}
+
+/// Estado en vivo de una estación, actualizado desde su hilo.
+/// 
+/// Cada actualización cuenta como actividad; el detector compara el total
+/// de actividad entre consultas para saber si la línea avanzó.
+#[derive(Debug)]
+pub(crate) struct StationProbe {
+    snapshot: Mutex<StationSnapshot>,
+    activity: AtomicUsize,
+}
+
+impl StationProbe {
+    pub(crate) fn new(index: usize, name: impl Into<String>) -> Self {
+        Self {
+            snapshot: Mutex::new(StationSnapshot::new(index, name.into())),
+            activity: AtomicUsize::new(0),
+        }
+    }
+
+    fn update(&self, change: impl FnOnce(&mut StationSnapshot)) {
+        change(&mut lock_recover(&self.snapshot));
+        self.activity.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Publica el contenido de la cola interna.
+    pub(crate) fn set_queue(&self, queue: &VecDeque<Arc<Product>>) {
+        self.update(|snapshot| snapshot.queue = queue.iter().map(|product| product.id).collect());
+    }
+
+    /// Publica el producto tomado por la estación (`None` al soltarlo).
+    pub(crate) fn set_in_service(&self, product: Option<usize>) {
+        self.update(|snapshot| snapshot.in_service = product);
+    }
+
+    /// Publica el recurso que la estación espera.
+    pub(crate) fn set_waiting_for(&self, resource: Option<&str>) {
+        self.update(|snapshot| snapshot.waiting_for = resource.map(str::to_string));
+    }
+
+    /// Registra la adquisición de un recurso.
+    pub(crate) fn hold(&self, resource: &str) {
+        self.update(|snapshot| snapshot.held_resources.push(resource.to_string()));
+    }
+
+    /// Registra la liberación de todos los recursos retenidos.
+    pub(crate) fn release_all(&self) {
+        self.update(|snapshot| snapshot.held_resources.clear());
+    }
+
+    fn snapshot(&self) -> StationSnapshot {
+        lock_recover(&self.snapshot).clone()
+    }
+}
+
+impl StationObserver for StationProbe {
+    fn on_transition(&self, transition: &StatusTransition) {
+        self.update(|snapshot| snapshot.status = transition.to);
+    }
+}
+
+/// Detector de interbloqueos consultado periódicamente por el colector.
+pub(crate) struct DeadlockWatch {
+    probes: Vec<Arc<StationProbe>>,
+    idle_interval: Duration,
+    last_activity: usize,
+    quiet_since: Instant,
+}
+
+impl DeadlockWatch {
+    pub(crate) fn new(probes: Vec<Arc<StationProbe>>, idle_interval: Duration) -> Self {
+        Self {
+            probes,
+            idle_interval,
+            last_activity: 0,
+            quiet_since: Instant::now(),
+        }
+    }
+
+    /// Evalúa si la línea está interbloqueada.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `in_flight` - Indica si hay productos admitidos sin completar
+    /// 
+    /// # Returns
+    /// 
+    /// `Some(estados)` con el estado de cada estación si no hubo actividad
+    /// durante el intervalo configurado, `None` en caso contrario
+    pub(crate) fn check(&mut self, in_flight: bool) -> Option<Vec<StationSnapshot>> {
+        let activity: usize = self.probes
+            .iter()
+            .map(|probe| probe.activity.load(Ordering::Relaxed))
+            .sum();
+        let snapshots: Vec<StationSnapshot> = self.probes.iter().map(|probe| probe.snapshot()).collect();
+        let processing = snapshots.iter().any(|snapshot| snapshot.status == StationStatus::Busy);
+
+        if activity != self.last_activity || processing || !in_flight {
+            self.last_activity = activity;
+            self.quiet_since = Instant::now();
+            return None;
+        }
+        (self.quiet_since.elapsed() > self.idle_interval).then_some(snapshots)
+    }
+
+    /// Intervalo sin actividad que dispara la detección.
+    pub(crate) fn idle_interval(&self) -> Duration {
+        self.idle_interval
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_requires_quiet_interval_with_products_in_flight() {
+        let probe = Arc::new(StationProbe::new(0, "Corte"));
+        let mut watch = DeadlockWatch::new(vec![Arc::clone(&probe)], Duration::from_millis(20));
+
+        probe.hold("grua");
+        assert!(watch.check(true).is_none());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(watch.check(false).is_none(), "Sin productos en la línea no hay interbloqueo");
+        std::thread::sleep(Duration::from_millis(30));
+
+        let snapshots = watch.check(true).expect("La estación no registró actividad");
+        assert_eq!(snapshots[0].held_resources, vec!["grua".to_string()]);
+        assert_eq!(snapshots[0].to_string(), "[Corte] ociosa; cola: -; canal: -; retiene: grua");
+    }
+}
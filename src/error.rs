@@ -0,0 +1,463 @@
+//! # Módulo de Errores
+//! 
+//! Este módulo define los errores que puede producir la ejecución de una
+//! simulación. Cuando la simulación se interrumpe, el error incluye las
+//! métricas parciales de los productos que alcanzaron a completarse.
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use crate::deadlock::DeadlockReport;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+
+/// Motivo por el que se consideró detenida una simulación.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StallReason {
+    /// Ningún producto terminó la línea durante el tiempo indicado
+    /// mientras había productos en proceso
+    NoProgress(Duration),
+    /// La simulación superó su duración máxima permitida
+    WatchdogExpired(Duration),
+}
+
+impl fmt::Display for StallReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoProgress(timeout) => write!(
+                f,
+                "sin productos completados durante {}",
+                MetricsCalculator::format_duration(*timeout)
+            ),
+            Self::WatchdogExpired(limit) => write!(
+                f,
+                "se superó la duración máxima de {}",
+                MetricsCalculator::format_duration(*limit)
+            ),
+        }
+    }
+}
+
+/// Ubicación de un producto retenido en la línea al detectarse un bloqueo.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StalledProduct {
+    /// ID del producto
+    pub product_id: usize,
+    /// Índice de la estación donde se encuentra el producto
+    pub station_index: usize,
+    /// Nombre de la estación donde se encuentra el producto
+    pub station: String,
+    /// Tiempo de procesamiento restante en esa estación
+    pub remaining: Duration,
+}
+
+/// Errores de configuración detectados antes de iniciar una simulación.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    /// La línea no tiene estaciones.
+    NoStations,
+    /// Una estación tiene tiempo de procesamiento cero.
+    ZeroProcessingTime {
+        /// Índice de la estación (desde 0)
+        station_index: usize,
+        /// Nombre de la estación
+        station: String,
+    },
+    /// Un algoritmo Round Robin tiene quantum cero.
+    ZeroQuantum {
+        /// Índice de la estación con algoritmo propio, o `None` si se trata
+        /// del algoritmo general de la simulación
+        station_index: Option<usize>,
+    },
+    /// Los tiempos de llegada no están ordenados de forma no decreciente.
+    UnsortedArrivals {
+        /// Posición de la primera llegada fuera de orden
+        index: usize,
+        /// Tiempo de la llegada anterior
+        previous: Duration,
+        /// Tiempo de la llegada fuera de orden
+        current: Duration,
+    },
+    /// Hay un algoritmo propio asignado a una estación que no existe.
+    StationAlgorithmOutOfRange {
+        /// Índice de la estación del algoritmo
+        station_index: usize,
+        /// Número de estaciones configuradas
+        station_count: usize,
+    },
+    /// Una reconfiguración en caliente no puede aplicarse a la línea.
+    InvalidReconfiguration {
+        /// Momento de la simulación en que se aplicaría
+        at: Duration,
+        /// Motivo por el que no puede aplicarse
+        reason: String,
+    },
+    /// Una estación de inspección tiene una salida inválida.
+    InvalidInspection {
+        /// Índice de la estación (desde 0)
+        station_index: usize,
+        /// Nombre de la estación
+        station: String,
+        /// Motivo por el que la inspección no es válida
+        reason: String,
+    },
+    /// Un divisor tiene una salida o una política inválida.
+    InvalidSplitter {
+        /// Índice de la estación (desde 0)
+        station_index: usize,
+        /// Nombre de la estación
+        station: String,
+        /// Motivo por el que el divisor no es válido
+        reason: String,
+    },
+    /// Una unión referencia un origen inválido o tiene un peso nulo.
+    InvalidMerger {
+        /// Índice de la estación (desde 0)
+        station_index: usize,
+        /// Nombre de la estación
+        station: String,
+        /// Motivo por el que la unión no es válida
+        reason: String,
+    },
+    /// Ningún producto puede llegar a una estación.
+    UnreachableStation {
+        /// Índice de la estación (desde 0)
+        station_index: usize,
+        /// Nombre de la estación
+        station: String,
+    },
+    /// Un grupo de estaciones forma un ciclo del que los productos no
+    /// pueden salir (un ciclo que no es un retrabajo).
+    ClosedLoop {
+        /// Índices de las estaciones del ciclo, en orden de la línea
+        stations: Vec<usize>,
+        /// Nombres de esas estaciones
+        names: Vec<String>,
+    },
+    /// Una salida o una regla de un divisor no recibe productos.
+    DanglingOutput {
+        /// Índice de la estación (desde 0)
+        station_index: usize,
+        /// Nombre de la estación
+        station: String,
+        /// Descripción de la salida sin uso
+        reason: String,
+    },
+    /// Una ruta referencia una estación que no existe.
+    UnknownRouteStation {
+        /// Descripción de la ruta (por ejemplo, la de un tipo de producto)
+        route: String,
+        /// Índice de la estación referenciada (desde 0)
+        station_index: usize,
+        /// Número de estaciones configuradas
+        station_count: usize,
+    },
+    /// Un producto o una fuente usa un tipo de producto no registrado.
+    UnknownProductType {
+        /// Descripción de quién usa el tipo (un producto o una fuente)
+        used_by: String,
+        /// Nombre del tipo
+        type_name: String,
+    },
+    /// El backend asíncrono no admite una opción configurada en la
+    /// simulación (ver el módulo `simulation_async`).
+    UnsupportedByAsyncBackend {
+        /// Descripción de la opción
+        option: String,
+    },
+    /// Se pidió ejecutar el backend asíncrono sobre un grupo sin hilos.
+    NoWorkers,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoStations => write!(f, "la línea debe tener al menos una estación"),
+            Self::ZeroProcessingTime { station_index, station } => write!(
+                f,
+                "la estación {} ('{}') tiene tiempo de procesamiento cero",
+                station_index + 1,
+                station
+            ),
+            Self::ZeroQuantum { station_index: None } => {
+                write!(f, "el quantum de Round Robin debe ser mayor que cero")
+            }
+            Self::ZeroQuantum { station_index: Some(index) } => write!(
+                f,
+                "el quantum de Round Robin de la estación {} debe ser mayor que cero",
+                index + 1
+            ),
+            Self::UnsortedArrivals { index, previous, current } => write!(
+                f,
+                "la llegada {} ({}) es anterior a la llegada previa ({}); los tiempos deben estar ordenados",
+                index + 1,
+                MetricsCalculator::format_duration(*current),
+                MetricsCalculator::format_duration(*previous)
+            ),
+            Self::StationAlgorithmOutOfRange { station_index, station_count } => write!(
+                f,
+                "hay un algoritmo asignado a la estación {} pero la línea solo tiene {} estaciones",
+                station_index + 1,
+                station_count
+            ),
+            Self::InvalidReconfiguration { at, reason } => write!(
+                f,
+                "la reconfiguración en t={} {}",
+                MetricsCalculator::format_duration(*at),
+                reason
+            ),
+            Self::InvalidInspection { station_index, station, reason } => write!(
+                f,
+                "la inspección de la estación {} ('{}') no es válida: {}",
+                station_index + 1,
+                station,
+                reason
+            ),
+            Self::InvalidSplitter { station_index, station, reason } => write!(
+                f,
+                "el divisor de la estación {} ('{}') no es válido: {}",
+                station_index + 1,
+                station,
+                reason
+            ),
+            Self::InvalidMerger { station_index, station, reason } => write!(
+                f,
+                "la unión de la estación {} ('{}') no es válida: {}",
+                station_index + 1,
+                station,
+                reason
+            ),
+            Self::UnreachableStation { station_index, station } => write!(
+                f,
+                "ningún producto puede llegar a la estación {} ('{}'): revise las salidas de las estaciones anteriores",
+                station_index + 1,
+                station
+            ),
+            Self::ClosedLoop { stations, names } => {
+                let stations: Vec<String> = stations
+                    .iter()
+                    .zip(names)
+                    .map(|(index, name)| format!("{} ('{}')", index + 1, name))
+                    .collect();
+                write!(
+                    f,
+                    "las estaciones {} forman un ciclo del que los productos no pueden salir: \
+                     un retrabajo necesita una salida que continúe la ruta",
+                    stations.join(", ")
+                )
+            }
+            Self::DanglingOutput { station_index, station, reason } => write!(
+                f,
+                "el divisor de la estación {} ('{}') tiene una salida sin uso: {}",
+                station_index + 1,
+                station,
+                reason
+            ),
+            Self::UnknownRouteStation { route, station_index, station_count } => write!(
+                f,
+                "{} referencia la estación {} pero la línea solo tiene {} estaciones",
+                route,
+                station_index + 1,
+                station_count
+            ),
+            Self::UnknownProductType { used_by, type_name } => write!(
+                f,
+                "{} usa el tipo de producto '{}', que no está registrado",
+                used_by,
+                type_name
+            ),
+            Self::UnsupportedByAsyncBackend { option } => write!(
+                f,
+                "el backend asíncrono no admite {}; use Simulation::run",
+                option
+            ),
+            Self::NoWorkers => write!(f, "el grupo de hilos debe tener al menos un hilo"),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+/// Errores que pueden ocurrir durante la ejecución de una simulación.
+#[derive(Debug)]
+pub enum SimulationError {
+    /// El hilo de una estación hizo panic y la simulación se abortó.
+    StationPanicked {
+        /// Nombre de la estación que falló
+        station: String,
+        /// Mensaje del panic
+        cause: String,
+        /// Métricas de los productos completados antes de la falla
+        metrics: Box<SimulationMetrics>,
+    },
+    /// La simulación dejó de progresar (posible bloqueo o producto perdido).
+    /// 
+    /// Los hilos de la simulación quedan desatendidos; el error describe
+    /// qué estación retiene cada producto pendiente.
+    Stalled {
+        /// Motivo de la detección
+        reason: StallReason,
+        /// Productos pendientes y la estación que los retiene
+        in_flight: Vec<StalledProduct>,
+        /// Métricas de los productos completados antes del bloqueo
+        metrics: Box<SimulationMetrics>,
+    },
+    /// Las estaciones quedaron interbloqueadas (por ejemplo, esperando
+    /// espacio en buffers acotados que forman un ciclo).
+    /// 
+    /// Los hilos de la simulación quedan desatendidos; el reporte describe
+    /// el estado, la cola y los recursos retenidos de cada estación.
+    Deadlocked {
+        /// Volcado del estado de la línea al detectar el interbloqueo
+        report: Box<DeadlockReport>,
+        /// Métricas de los productos completados antes del interbloqueo
+        metrics: Box<SimulationMetrics>,
+    },
+    /// El hilo generador de productos hizo panic y la simulación se abortó.
+    GeneratorPanicked {
+        /// Mensaje del panic
+        cause: String,
+        /// Métricas de los productos completados antes de la falla
+        metrics: Box<SimulationMetrics>,
+    },
+    /// La configuración no es válida y la simulación no llegó a iniciarse.
+    InvalidConfig {
+        /// Problema detectado en la configuración
+        error: ConfigError,
+        /// Métricas vacías (ningún producto se procesó)
+        metrics: Box<SimulationMetrics>,
+    },
+}
+
+impl SimulationError {
+    /// Obtiene las métricas parciales asociadas al error.
+    /// 
+    /// # Returns
+    /// 
+    /// Las métricas de los productos que se completaron antes de la falla
+    pub fn partial_metrics(&self) -> &SimulationMetrics {
+        match self {
+            Self::StationPanicked { metrics, .. } => metrics,
+            Self::Stalled { metrics, .. } => metrics,
+            Self::Deadlocked { metrics, .. } => metrics,
+            Self::GeneratorPanicked { metrics, .. } => metrics,
+            Self::InvalidConfig { metrics, .. } => metrics,
+        }
+    }
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StationPanicked { station, cause, metrics } => write!(
+                f,
+                "La estación '{}' falló: {} ({} productos completados)",
+                station,
+                cause,
+                metrics.products.len()
+            ),
+            Self::Stalled { reason, in_flight, metrics } => {
+                write!(
+                    f,
+                    "La simulación se detuvo ({}); {} productos completados",
+                    reason,
+                    metrics.products.len()
+                )?;
+                if !in_flight.is_empty() {
+                    write!(f, "; productos retenidos:")?;
+                    for product in in_flight {
+                        write!(
+                            f,
+                            " #{:02} en '{}' (restante: {})",
+                            product.product_id,
+                            product.station,
+                            MetricsCalculator::format_duration(product.remaining)
+                        )?;
+                    }
+                }
+                Ok(())
+            }
+            Self::Deadlocked { report, metrics } => write!(
+                f,
+                "La simulación quedó interbloqueada ({} estaciones bloqueadas, {} productos retenidos); {} productos completados",
+                report.blocked_stations().len(),
+                report.in_flight.len(),
+                metrics.products.len()
+            ),
+            Self::GeneratorPanicked { cause, metrics } => write!(
+                f,
+                "El generador de productos falló: {} ({} productos completados)",
+                cause,
+                metrics.products.len()
+            ),
+            Self::InvalidConfig { error, .. } => write!(f, "Configuración inválida: {}", error),
+        }
+    }
+}
+
+impl Error for SimulationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidConfig { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Extrae el mensaje de un panic capturado.
+/// 
+/// # Arguments
+/// 
+/// * `payload` - Carga útil del panic obtenida de `catch_unwind` o `join`
+/// 
+/// # Returns
+/// 
+/// El mensaje del panic, o un texto genérico si no es un string
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panic sin mensaje".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panic_message() {
+        let payload = std::panic::catch_unwind(|| panic!("falla de prueba")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "falla de prueba");
+
+        let payload = std::panic::catch_unwind(|| panic!("producto {}", 7)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "producto 7");
+
+        let payload = std::panic::catch_unwind(|| std::panic::panic_any(42)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "panic sin mensaje");
+    }
+
+    #[test]
+    fn test_config_error_messages() {
+        let error = ConfigError::ZeroProcessingTime { station_index: 0, station: "Corte".to_string() };
+        assert_eq!(error.to_string(), "la estación 1 ('Corte') tiene tiempo de procesamiento cero");
+        assert_eq!(
+            ConfigError::ZeroQuantum { station_index: Some(2) }.to_string(),
+            "el quantum de Round Robin de la estación 3 debe ser mayor que cero"
+        );
+        assert_eq!(
+            ConfigError::StationAlgorithmOutOfRange { station_index: 4, station_count: 3 }.to_string(),
+            "hay un algoritmo asignado a la estación 5 pero la línea solo tiene 3 estaciones"
+        );
+        let error = ConfigError::InvalidReconfiguration {
+            at: Duration::from_millis(250),
+            reason: "el quantum debe ser mayor que cero".to_string(),
+        };
+        assert!(error.to_string().starts_with("la reconfiguración en t="), "{}", error);
+        let error = ConfigError::ClosedLoop { stations: vec![1, 2], names: vec!["Ensamblaje".to_string(), "Control".to_string()] };
+        assert!(error.to_string().starts_with("las estaciones 2 ('Ensamblaje'), 3 ('Control') forman un ciclo"), "{}", error);
+    }
+}
@@ -0,0 +1,600 @@
+//! # Módulo de Experimentos
+//! 
+//! Este módulo agrupa varias ejecuciones con nombre de un mismo estudio
+//! (un barrido de parámetros, una comparación de algoritmos, variantes
+//! creadas con [`Simulation::clone_with`]) y guarda la configuración y las
+//! métricas de cada una. Al terminar, el experimento produce un reporte
+//! consolidado con una fila por ejecución, en texto o CSV, sin que el
+//! código del usuario tenga que llevar la cuenta de qué resultado
+//! corresponde a qué configuración.
+//! 
+//! Para decidir qué configuración ganó, [`Experiment::ranking`] combina
+//! varias métricas con los pesos de [`ScoreWeights`] en un único puntaje
+//! y ordena las ejecuciones de mejor a peor.
+
+use std::time::Duration;
+
+use crate::batch::BatchRunner;
+use crate::clock::ClockFormat;
+use crate::error::SimulationError;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::scheduler::SchedulingAlgorithm;
+use crate::simulation::Simulation;
+use crate::station::StationConfig;
+
+/// Ejecución registrada en un experimento.
+#[derive(Debug)]
+pub struct ExperimentRun {
+    /// Nombre de la ejecución, único dentro del experimento
+    pub name: String,
+    /// Estaciones con las que se ejecutó
+    pub stations: Vec<StationConfig>,
+    /// Algoritmo de planificación general
+    pub algorithm: SchedulingAlgorithm,
+    /// Tiempos de llegada de la fuente principal
+    pub arrival_times: Vec<Duration>,
+    /// Resultado de la ejecución
+    pub result: Result<SimulationMetrics, SimulationError>,
+}
+
+impl ExperimentRun {
+    /// Obtiene las métricas de la ejecución, o las parciales si falló.
+    pub fn metrics(&self) -> &SimulationMetrics {
+        match &self.result {
+            Ok(metrics) => metrics,
+            Err(error) => error.partial_metrics(),
+        }
+    }
+}
+
+/// Pesos de los criterios con los que se puntúan las ejecuciones de un
+/// experimento.
+/// 
+/// Cada criterio se normaliza entre las ejecuciones comparadas: la mejor
+/// obtiene 1 y la peor 0 (todas 1 si empatan). El puntaje es el promedio
+/// de los criterios normalizados ponderado por estos pesos, de modo que un
+/// peso 0 ignora el criterio.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::ScoreWeights;
+/// 
+/// // Priorizar el throughput sobre el resto de los criterios
+/// let weights = ScoreWeights { throughput: 3.0, ..ScoreWeights::default() };
+/// assert_eq!(weights.fairness, 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScoreWeights {
+    /// Peso de la espera promedio (menor es mejor)
+    pub average_wait: f64,
+    /// Peso del percentil 99 del turnaround (menor es mejor)
+    pub p99_turnaround: f64,
+    /// Peso del throughput (mayor es mejor)
+    pub throughput: f64,
+    /// Peso del índice de equidad de Jain (mayor es mejor)
+    pub fairness: f64,
+    /// Peso de los cambios de contexto: productos interrumpidos y
+    /// reencolados en todas las estaciones (menor es mejor)
+    pub context_switches: f64,
+}
+
+impl Default for ScoreWeights {
+    /// Todos los criterios pesan lo mismo.
+    fn default() -> Self {
+        Self {
+            average_wait: 1.0,
+            p99_turnaround: 1.0,
+            throughput: 1.0,
+            fairness: 1.0,
+            context_switches: 1.0,
+        }
+    }
+}
+
+impl ScoreWeights {
+    /// Pesos en el orden de [`ScoreWeights::criteria`].
+    fn weights(&self) -> [f64; 5] {
+        [self.average_wait, self.p99_turnaround, self.throughput, self.fairness, self.context_switches]
+    }
+
+    /// Valores de los criterios de una ejecución, orientados para que mayor
+    /// sea mejor.
+    fn criteria(run: &RankedRun) -> [f64; 5] {
+        [
+            -run.average_wait.as_secs_f64(),
+            -run.p99_turnaround.as_secs_f64(),
+            run.throughput,
+            run.fairness,
+            -(run.context_switches as f64),
+        ]
+    }
+
+    /// Verifica que los pesos sean válidos.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si algún peso es negativo o no es finito, o si todos son cero
+    fn check(&self) {
+        let weights = self.weights();
+        assert!(
+            weights.iter().all(|weight| weight.is_finite() && *weight >= 0.0),
+            "Los pesos del puntaje deben ser números finitos no negativos"
+        );
+        assert!(weights.iter().any(|weight| *weight > 0.0), "Al menos un peso del puntaje debe ser positivo");
+    }
+}
+
+/// Posición de una ejecución en el ranking de un experimento.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankedRun {
+    /// Nombre de la ejecución
+    pub name: String,
+    /// Puntaje ponderado, entre 0 (peor en todo) y 1 (mejor en todo)
+    pub score: f64,
+    /// Espera promedio
+    pub average_wait: Duration,
+    /// Percentil 99 del turnaround de los productos completados
+    pub p99_turnaround: Duration,
+    /// Productos completados por segundo
+    pub throughput: f64,
+    /// Índice de equidad de Jain
+    pub fairness: f64,
+    /// Productos interrumpidos y reencolados en todas las estaciones
+    pub context_switches: usize,
+}
+
+impl RankedRun {
+    /// Extrae los criterios de una ejecución, aún sin puntaje.
+    fn of(run: &ExperimentRun) -> Self {
+        let metrics = run.metrics();
+        let mut turnarounds: Vec<Duration> = metrics.products.iter().map(|product| product.turnaround_time).collect();
+        turnarounds.sort();
+        let rank = (0.99 * turnarounds.len() as f64).ceil().max(1.0) as usize;
+        Self {
+            name: run.name.clone(),
+            score: 0.0,
+            average_wait: metrics.average_wait_time,
+            p99_turnaround: turnarounds.get(rank - 1).copied().unwrap_or_default(),
+            throughput: metrics.throughput,
+            fairness: metrics.fairness_index,
+            context_switches: metrics.station_reports.iter().map(|report| report.preemptions).sum(),
+        }
+    }
+}
+
+/// Conjunto de ejecuciones con nombre y su reporte consolidado.
+/// 
+/// Las simulaciones agregadas con [`Experiment::add_run`] quedan pendientes
+/// hasta [`Experiment::execute`], que las ejecuta en paralelo con un
+/// [`BatchRunner`]; los resultados obtenidos por otros medios (por ejemplo,
+/// en tiempo virtual) se registran con [`Experiment::record`].
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Experiment, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+/// let base = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// 
+/// let mut experiment = Experiment::new("Algoritmos");
+/// experiment
+///     .add_run("fcfs", base.clone())
+///     .add_run("rr-5", base.clone_with(|simulation| {
+///         simulation.set_algorithm(SchedulingAlgorithm::round_robin(Duration::from_millis(5)));
+///     }));
+/// experiment.execute();
+/// 
+/// assert_eq!(experiment.runs().len(), 2);
+/// assert_eq!(experiment.get("rr-5").unwrap().metrics().products.len(), 3);
+/// assert_eq!(experiment.to_csv().lines().count(), 3);
+/// ```
+pub struct Experiment {
+    /// Nombre del experimento
+    pub name: String,
+    /// Ejecuciones completadas, en orden de registro
+    runs: Vec<ExperimentRun>,
+    /// Simulaciones pendientes de ejecutar
+    pending: Vec<(String, Simulation)>,
+}
+
+impl Experiment {
+    /// Crea un experimento vacío.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            runs: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Agrega una simulación pendiente de ejecutar.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Nombre de la ejecución
+    /// * `simulation` - Simulación configurada
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable al experimento para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si ya existe una ejecución con ese nombre
+    pub fn add_run(&mut self, name: impl Into<String>, simulation: Simulation) -> &mut Self {
+        let name = name.into();
+        self.check_name(&name);
+        self.pending.push((name, simulation));
+        self
+    }
+
+    /// Registra una ejecución ya realizada.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Nombre de la ejecución
+    /// * `simulation` - Simulación de la que se toma la configuración
+    /// * `result` - Resultado de ejecutarla
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable al experimento para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si ya existe una ejecución con ese nombre
+    pub fn record(
+        &mut self,
+        name: impl Into<String>,
+        simulation: &Simulation,
+        result: Result<SimulationMetrics, SimulationError>,
+    ) -> &mut Self {
+        let name = name.into();
+        self.check_name(&name);
+        self.runs.push(Self::run_of(name, simulation, result));
+        self
+    }
+
+    /// Ejecuta las simulaciones pendientes con el paralelismo por defecto.
+    /// 
+    /// Equivale a `execute_with(&BatchRunner::new())`.
+    pub fn execute(&mut self) -> &mut Self {
+        self.execute_with(&BatchRunner::new())
+    }
+
+    /// Ejecuta las simulaciones pendientes con el ejecutor de lotes indicado.
+    /// 
+    /// Los resultados se registran en el orden en que se agregaron las
+    /// simulaciones, sin importar cuál terminó primero.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si alguna simulación hace panic por una configuración inválida
+    pub fn execute_with(&mut self, runner: &BatchRunner) -> &mut Self {
+        let pending = std::mem::take(&mut self.pending);
+        let scenarios = pending.iter().map(|(_, simulation)| simulation.clone()).collect();
+        let results = runner.run(scenarios);
+        for ((name, simulation), result) in pending.into_iter().zip(results) {
+            self.runs.push(Self::run_of(name, &simulation, result));
+        }
+        self
+    }
+
+    /// Obtiene las ejecuciones completadas, en orden de registro.
+    pub fn runs(&self) -> &[ExperimentRun] {
+        &self.runs
+    }
+
+    /// Busca una ejecución completada por su nombre.
+    pub fn get(&self, name: &str) -> Option<&ExperimentRun> {
+        self.runs.iter().find(|run| run.name == name)
+    }
+
+    /// Número de simulaciones agregadas que aún no se ejecutaron.
+    pub fn pending_runs(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Genera el reporte consolidado en texto, con una fila por ejecución.
+    /// 
+    /// Las ejecuciones que fallaron muestran sus métricas parciales y el
+    /// error debajo de la tabla. Debajo del título se indican los metadatos
+    /// de la primera ejecución (ver [`RunMetadata::summary`]).
+    /// 
+    /// [`RunMetadata::summary`]: crate::RunMetadata::summary
+    pub fn report(&self) -> String {
+        let time = MetricsCalculator::format_duration;
+        let name_width = self.runs.iter().map(|run| run.name.chars().count()).max().unwrap_or(0).max("Ejecución".chars().count());
+
+        let mut report = format!("=== EXPERIMENTO: {} ===\n", self.name);
+        if let Some(run) = self.runs.first() {
+            report.push_str(&format!("Ejecutado con {}\n", run.metrics().run_metadata.summary()));
+        }
+        report.push_str(&format!(
+            "{:<name_width$}  {:>11}  {:>14}  {:>16}  {:>10}  {:>12}\n",
+            "Ejecución", "Completados", "Espera prom.", "Turnaround prom.", "Throughput", "Tiempo total"
+        ));
+        for run in &self.runs {
+            let metrics = run.metrics();
+            report.push_str(&format!(
+                "{:<name_width$}  {:>11}  {:>14}  {:>16}  {:>10.2}  {:>12}\n",
+                run.name,
+                metrics.products.len(),
+                time(metrics.average_wait_time),
+                time(metrics.average_turnaround_time),
+                metrics.throughput,
+                time(metrics.total_simulation_time)
+            ));
+        }
+        for run in &self.runs {
+            if let Err(error) = &run.result {
+                report.push_str(&format!("\n{}: {}", run.name, error));
+            }
+        }
+        report
+    }
+
+    /// Ordena las ejecuciones completadas por su puntaje ponderado.
+    /// 
+    /// Las ejecuciones que fallaron se puntúan con sus métricas parciales.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `weights` - Peso de cada criterio (ver [`ScoreWeights`])
+    /// 
+    /// # Returns
+    /// 
+    /// Las ejecuciones de mayor a menor puntaje; ante un empate se respeta
+    /// el orden de registro
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si algún peso es negativo o no es finito, o si todos son cero
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Experiment, ScoreWeights, Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    /// let base = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    /// 
+    /// let mut experiment = Experiment::new("Algoritmos");
+    /// experiment
+    ///     .add_run("fcfs", base.clone())
+    ///     .add_run("rr-5", base.clone_with(|simulation| {
+    ///         simulation.set_algorithm(SchedulingAlgorithm::round_robin(Duration::from_millis(5)));
+    ///     }));
+    /// experiment.execute();
+    /// 
+    /// // Solo importan los cambios de contexto: FCFS no interrumpe productos
+    /// let weights = ScoreWeights {
+    ///     average_wait: 0.0,
+    ///     p99_turnaround: 0.0,
+    ///     throughput: 0.0,
+    ///     fairness: 0.0,
+    ///     context_switches: 1.0,
+    /// };
+    /// let ranking = experiment.ranking(&weights);
+    /// assert_eq!(ranking[0].name, "fcfs");
+    /// assert_eq!(ranking[0].score, 1.0);
+    /// assert!(ranking[1].context_switches > 0);
+    /// ```
+    pub fn ranking(&self, weights: &ScoreWeights) -> Vec<RankedRun> {
+        weights.check();
+        let mut ranked: Vec<RankedRun> = self.runs.iter().map(RankedRun::of).collect();
+        let criteria: Vec<[f64; 5]> = ranked.iter().map(ScoreWeights::criteria).collect();
+        let weights = weights.weights();
+        let total_weight: f64 = weights.iter().sum();
+
+        for (run, values) in ranked.iter_mut().zip(&criteria) {
+            let weighted: f64 = (0..weights.len())
+                .map(|criterion| {
+                    let column = criteria.iter().map(|values| values[criterion]);
+                    let best = column.clone().fold(f64::NEG_INFINITY, f64::max);
+                    let worst = column.fold(f64::INFINITY, f64::min);
+                    let normalized = if best > worst {
+                        (values[criterion] - worst) / (best - worst)
+                    } else {
+                        1.0
+                    };
+                    weights[criterion] * normalized
+                })
+                .sum();
+            run.score = weighted / total_weight;
+        }
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+        ranked
+    }
+
+    /// Genera el ranking de las ejecuciones en texto, con el puntaje y el
+    /// valor de cada criterio.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si algún peso es negativo o no es finito, o si todos son cero
+    pub fn ranking_report(&self, weights: &ScoreWeights) -> String {
+        let time = MetricsCalculator::format_duration;
+        let ranking = self.ranking(weights);
+        let name_width = ranking.iter().map(|run| run.name.chars().count()).max().unwrap_or(0).max("Ejecución".chars().count());
+
+        let mut report = format!("=== RANKING: {} ===\n", self.name);
+        report.push_str(&format!(
+            "{:>3}  {:<name_width$}  {:>7}  {:>12}  {:>14}  {:>10}  {:>8}  {:>17}\n",
+            "#", "Ejecución", "Puntaje", "Espera prom.", "Turnaround p99", "Throughput", "Equidad", "Cambios contexto"
+        ));
+        for (position, run) in ranking.iter().enumerate() {
+            report.push_str(&format!(
+                "{:>3}  {:<name_width$}  {:>7.3}  {:>12}  {:>14}  {:>10.2}  {:>8.3}  {:>17}\n",
+                position + 1,
+                run.name,
+                run.score,
+                time(run.average_wait),
+                time(run.p99_turnaround),
+                run.throughput,
+                run.fairness,
+                run.context_switches
+            ));
+        }
+        report
+    }
+
+    /// Exporta el reporte consolidado en CSV, con una fila por ejecución y
+    /// los tiempos en el formato de reloj vigente ([`ClockFormat::current`])
+    /// sin unidad. Cada fila incluye la versión, el commit, la fecha y el
+    /// equipo de su ejecución.
+    pub fn to_csv(&self) -> String {
+        let time = |duration: Duration| ClockFormat::current().format_value(duration);
+        let mut csv = String::from(
+            "Run,Algorithm,Stations,Arrivals,Completed,AvgWait,AvgTurnaround,AvgSlowdown,Throughput,TotalTime,Fairness,TotalTardiness,Version,Commit,Timestamp,Host,Error\n",
+        );
+        for run in &self.runs {
+            let metrics = run.metrics();
+            let stations: Vec<&str> = run.stations.iter().map(|station| station.name.as_str()).collect();
+            let error = run.result.as_ref().err().map(ToString::to_string).unwrap_or_default();
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{:.3},{:.3},{},{:.3},{},{},{},{},{},{}\n",
+                MetricsCalculator::csv_field(&run.name),
+                MetricsCalculator::csv_field(&run.algorithm.to_string()),
+                MetricsCalculator::csv_field(&stations.join(";")),
+                run.arrival_times.len(),
+                metrics.products.len(),
+                time(metrics.average_wait_time),
+                time(metrics.average_turnaround_time),
+                metrics.average_slowdown,
+                metrics.throughput,
+                time(metrics.total_simulation_time),
+                metrics.fairness_index,
+                time(metrics.total_tardiness),
+                MetricsCalculator::csv_field(&metrics.run_metadata.crate_version),
+                metrics.run_metadata.git_commit.as_deref().map(MetricsCalculator::csv_field).unwrap_or_default(),
+                metrics.run_metadata.timestamp_utc(),
+                MetricsCalculator::csv_field(&metrics.run_metadata.hostname),
+                MetricsCalculator::csv_field(&error)
+            ));
+        }
+        csv
+    }
+
+    /// Verifica que el nombre no esté en uso.
+    fn check_name(&self, name: &str) {
+        let taken = self.runs.iter().any(|run| run.name == name) || self.pending.iter().any(|(pending, _)| pending == name);
+        assert!(!taken, "Ya existe una ejecución llamada '{}'", name);
+    }
+
+    /// Crea el registro de una ejecución a partir de su simulación.
+    fn run_of(name: String, simulation: &Simulation, result: Result<SimulationMetrics, SimulationError>) -> ExperimentRun {
+        ExperimentRun {
+            name,
+            stations: simulation.station_configs.clone(),
+            algorithm: simulation.algorithm.clone(),
+            arrival_times: simulation.arrival_times.clone(),
+            result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_experiment_records_runs_in_order() {
+        let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+        let base = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+
+        let mut experiment = Experiment::new("Lote");
+        experiment
+            .add_run("dos", base.clone())
+            .add_run("cuatro", base.clone_with(|simulation| {
+                simulation.set_arrival_times(vec![Duration::ZERO; 4]);
+            }));
+        assert_eq!(experiment.pending_runs(), 2);
+        experiment.execute_with(&BatchRunner::new().with_max_parallelism(1));
+        assert_eq!(experiment.pending_runs(), 0);
+
+        let invalid = base.clone_with(|simulation| {
+            simulation.set_processing_time(0, Duration::ZERO);
+        });
+        let result = invalid.clone().run();
+        experiment.record("inválida", &invalid, result);
+
+        let names: Vec<&str> = experiment.runs().iter().map(|run| run.name.as_str()).collect();
+        assert_eq!(names, vec!["dos", "cuatro", "inválida"]);
+        assert_eq!(experiment.get("cuatro").unwrap().metrics().products.len(), 4);
+        assert_eq!(experiment.get("inválida").unwrap().stations[0].processing_time, Duration::ZERO);
+
+        let csv = experiment.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert!(rows[1].starts_with("dos,FCFS,Corte,2,2,"), "{}", rows[1]);
+        assert!(rows[1].ends_with(','));
+        assert!(!rows[3].ends_with(','));
+
+        let report = experiment.report();
+        assert!(report.starts_with("=== EXPERIMENTO: Lote ===\nEjecutado con v"));
+        assert!(report.contains("\ninválida: "), "{}", report);
+    }
+
+    #[test]
+    fn test_ranking_normalizes_and_weights_criteria() {
+        let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+        let base = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+        let mut experiment = Experiment::new("Ranking");
+        experiment
+            .add_run("fcfs", base.clone())
+            .add_run("rr", base.clone_with(|simulation| {
+                simulation.set_algorithm(SchedulingAlgorithm::round_robin(Duration::from_millis(2)));
+            }))
+            .execute_with(&BatchRunner::new().with_max_parallelism(1));
+
+        // Con un solo criterio los puntajes son exactamente 1 y 0
+        let only_switches = ScoreWeights {
+            average_wait: 0.0,
+            p99_turnaround: 0.0,
+            throughput: 0.0,
+            fairness: 0.0,
+            context_switches: 1.0,
+        };
+        let scores: Vec<(String, f64)> = experiment
+            .ranking(&only_switches)
+            .into_iter()
+            .map(|run| (run.name, run.score))
+            .collect();
+        assert_eq!(scores, vec![("fcfs".to_string(), 1.0), ("rr".to_string(), 0.0)]);
+
+        // Round Robin reparte el procesador: es más equitativo
+        let only_fairness = ScoreWeights { fairness: 1.0, context_switches: 0.0, ..only_switches };
+        assert_eq!(experiment.ranking(&only_fairness)[0].name, "rr");
+
+        let report = experiment.ranking_report(&ScoreWeights::default());
+        assert!(report.starts_with("=== RANKING: Ranking ===\n"));
+        assert_eq!(report.lines().count(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Al menos un peso del puntaje debe ser positivo")]
+    fn test_ranking_rejects_all_zero_weights() {
+        let weights = ScoreWeights {
+            average_wait: 0.0,
+            p99_turnaround: 0.0,
+            throughput: 0.0,
+            fairness: 0.0,
+            context_switches: 0.0,
+        };
+        Experiment::new("Vacío").ranking(&weights);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ya existe una ejecución llamada 'base'")]
+    fn test_duplicate_run_name_panics() {
+        let mut experiment = Experiment::new("Duplicado");
+        experiment
+            .add_run("base", Simulation::new(SchedulingAlgorithm::fcfs()))
+            .add_run("base", Simulation::new(SchedulingAlgorithm::fcfs()));
+    }
+}
@@ -0,0 +1,297 @@
+//! # Módulo de Interfaz C
+//! 
+//! Este módulo (feature `ffi`) expone el simulador a otros lenguajes
+//! mediante funciones `extern "C"`. Desde C se crea una simulación opaca,
+//! se le agregan estaciones y productos, se ejecuta y se obtienen las
+//! métricas como JSON. La declaración de estas funciones para C está en
+//! `include/assembly_line_simulator.h`.
+//! 
+//! ```c
+//! SimHandle *sim = sim_new(SIM_ROUND_ROBIN, 300);
+//! sim_add_station(sim, "Corte", 400);
+//! sim_add_product(sim, 0);
+//! if (sim_run(sim) == SIM_OK) {
+//!     puts(sim_metrics_json(sim));
+//! }
+//! sim_free(sim);
+//! ```
+
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::time::Duration;
+
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::scheduler::SchedulingAlgorithm;
+use crate::simulation::Simulation;
+use crate::station::StationConfig;
+
+/// Algoritmo First-Come First-Served
+pub const SIM_FCFS: c_int = 0;
+/// Algoritmo Round Robin (usa el quantum indicado en `sim_new`)
+pub const SIM_ROUND_ROBIN: c_int = 1;
+/// Algoritmo Earliest Due Date
+pub const SIM_EARLIEST_DUE_DATE: c_int = 2;
+/// Algoritmo Critical Ratio
+pub const SIM_CRITICAL_RATIO: c_int = 3;
+
+/// La operación se completó correctamente
+pub const SIM_OK: c_int = 0;
+/// La simulación falló; las métricas parciales quedan disponibles
+pub const SIM_SIMULATION_FAILED: c_int = 1;
+/// Un argumento era nulo o inválido
+pub const SIM_INVALID_ARGUMENT: c_int = -1;
+
+/// Simulación configurada desde C.
+/// 
+/// Es opaca para C: solo se manipula a través de punteros retornados por
+/// [`sim_new`] y se libera con [`sim_free`].
+pub struct SimHandle {
+    algorithm: SchedulingAlgorithm,
+    stations: Vec<StationConfig>,
+    arrivals: Vec<Duration>,
+    metrics_json: Option<CString>,
+}
+
+/// Crea una simulación vacía.
+/// 
+/// # Arguments
+/// 
+/// * `algorithm` - Una de las constantes `SIM_FCFS`, `SIM_ROUND_ROBIN`,
+///   `SIM_EARLIEST_DUE_DATE` o `SIM_CRITICAL_RATIO`
+/// * `quantum_ms` - Quantum en milisegundos (solo para Round Robin)
+/// 
+/// # Returns
+/// 
+/// Un puntero a la simulación, o `NULL` si el algoritmo es desconocido o
+/// el quantum de Round Robin es cero
+#[no_mangle]
+pub extern "C" fn sim_new(algorithm: c_int, quantum_ms: u64) -> *mut SimHandle {
+    let algorithm = match algorithm {
+        SIM_FCFS => SchedulingAlgorithm::fcfs(),
+        SIM_ROUND_ROBIN if quantum_ms > 0 => {
+            SchedulingAlgorithm::round_robin(Duration::from_millis(quantum_ms))
+        }
+        SIM_EARLIEST_DUE_DATE => SchedulingAlgorithm::earliest_due_date(),
+        SIM_CRITICAL_RATIO => SchedulingAlgorithm::critical_ratio(),
+        _ => return ptr::null_mut(),
+    };
+
+    Box::into_raw(Box::new(SimHandle {
+        algorithm,
+        stations: Vec::new(),
+        arrivals: Vec::new(),
+        metrics_json: None,
+    }))
+}
+
+/// Agrega una estación al final de la línea.
+/// 
+/// El nombre se copia, por lo que el llamador puede liberarlo al retornar.
+/// 
+/// # Arguments
+/// 
+/// * `handle` - Simulación creada con [`sim_new`]
+/// * `name` - Nombre de la estación en UTF-8, terminado en NUL
+/// * `processing_time_ms` - Tiempo de procesamiento en milisegundos
+/// 
+/// # Returns
+/// 
+/// `SIM_OK`, o `SIM_INVALID_ARGUMENT` si algún puntero es nulo o el nombre
+/// no es UTF-8 válido
+/// 
+/// # Safety
+/// 
+/// `handle` debe ser nulo o un puntero válido retornado por [`sim_new`], y
+/// `name` debe ser nulo o apuntar a un string terminado en NUL
+#[no_mangle]
+pub unsafe extern "C" fn sim_add_station(
+    handle: *mut SimHandle,
+    name: *const c_char,
+    processing_time_ms: u64,
+) -> c_int {
+    let (Some(simulation), false) = (handle.as_mut(), name.is_null()) else {
+        return SIM_INVALID_ARGUMENT;
+    };
+    let Ok(name) = CStr::from_ptr(name).to_str() else {
+        return SIM_INVALID_ARGUMENT;
+    };
+
+    simulation.stations.push(StationConfig::new(name, Duration::from_millis(processing_time_ms)));
+    SIM_OK
+}
+
+/// Agrega un producto que llega a la línea en el instante indicado.
+/// 
+/// # Arguments
+/// 
+/// * `handle` - Simulación creada con [`sim_new`]
+/// * `arrival_ms` - Tiempo de llegada en milisegundos desde el inicio
+/// 
+/// # Returns
+/// 
+/// `SIM_OK`, o `SIM_INVALID_ARGUMENT` si `handle` es nulo
+/// 
+/// # Safety
+/// 
+/// `handle` debe ser nulo o un puntero válido retornado por [`sim_new`]
+#[no_mangle]
+pub unsafe extern "C" fn sim_add_product(handle: *mut SimHandle, arrival_ms: u64) -> c_int {
+    let Some(simulation) = handle.as_mut() else {
+        return SIM_INVALID_ARGUMENT;
+    };
+    simulation.arrivals.push(Duration::from_millis(arrival_ms));
+    SIM_OK
+}
+
+/// Ejecuta la simulación y guarda sus métricas.
+/// 
+/// # Arguments
+/// 
+/// * `handle` - Simulación creada con [`sim_new`]
+/// 
+/// # Returns
+/// 
+/// `SIM_OK` si la simulación se completó, `SIM_SIMULATION_FAILED` si falló
+/// (las métricas parciales quedan disponibles en [`sim_metrics_json`]), o
+/// `SIM_INVALID_ARGUMENT` si `handle` es nulo o no hay estaciones
+/// 
+/// # Safety
+/// 
+/// `handle` debe ser nulo o un puntero válido retornado por [`sim_new`]
+#[no_mangle]
+pub unsafe extern "C" fn sim_run(handle: *mut SimHandle) -> c_int {
+    let Some(simulation) = handle.as_mut() else {
+        return SIM_INVALID_ARGUMENT;
+    };
+    if simulation.stations.is_empty() {
+        return SIM_INVALID_ARGUMENT;
+    }
+
+    // Un panic no puede cruzar la frontera con C
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        Simulation::with_config(
+            simulation.stations.clone(),
+            simulation.algorithm.clone(),
+            simulation.arrivals.clone(),
+        )
+        .run()
+    }));
+
+    let (status, metrics) = match &result {
+        Ok(Ok(metrics)) => (SIM_OK, Some(metrics)),
+        Ok(Err(error)) => (SIM_SIMULATION_FAILED, Some(error.partial_metrics())),
+        Err(_) => (SIM_SIMULATION_FAILED, None),
+    };
+    simulation.metrics_json = metrics.map(to_json);
+    status
+}
+
+/// Obtiene las métricas de la última ejecución como JSON.
+/// 
+/// # Arguments
+/// 
+/// * `handle` - Simulación creada con [`sim_new`]
+/// 
+/// # Returns
+/// 
+/// Un string JSON terminado en NUL que pertenece a la simulación y es válido
+/// hasta la siguiente llamada a [`sim_run`] o [`sim_free`], o `NULL` si la
+/// simulación no se ha ejecutado
+/// 
+/// # Safety
+/// 
+/// `handle` debe ser nulo o un puntero válido retornado por [`sim_new`]
+#[no_mangle]
+pub unsafe extern "C" fn sim_metrics_json(handle: *const SimHandle) -> *const c_char {
+    handle
+        .as_ref()
+        .and_then(|simulation| simulation.metrics_json.as_ref())
+        .map_or(ptr::null(), |json| json.as_ptr())
+}
+
+/// Libera una simulación y sus métricas.
+/// 
+/// # Arguments
+/// 
+/// * `handle` - Simulación creada con [`sim_new`] (puede ser nulo)
+/// 
+/// # Safety
+/// 
+/// `handle` debe ser nulo o un puntero retornado por [`sim_new`] que no
+/// se haya liberado antes
+#[no_mangle]
+pub unsafe extern "C" fn sim_free(handle: *mut SimHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Convierte las métricas a un string JSON para C.
+fn to_json(metrics: &SimulationMetrics) -> CString {
+    CString::new(MetricsCalculator::new().generate_json_report(metrics))
+        .expect("El JSON de métricas no debe contener caracteres NUL")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_through_c_interface() {
+        unsafe {
+            let handle = sim_new(SIM_ROUND_ROBIN, 10);
+            assert!(!handle.is_null());
+            assert!(sim_metrics_json(handle).is_null());
+            assert_eq!(sim_run(handle), SIM_INVALID_ARGUMENT);
+
+            let name = CString::new("Corte").unwrap();
+            assert_eq!(sim_add_station(handle, name.as_ptr(), 20), SIM_OK);
+            assert_eq!(sim_add_station(handle, ptr::null(), 20), SIM_INVALID_ARGUMENT);
+            assert_eq!(sim_add_product(handle, 0), SIM_OK);
+            assert_eq!(sim_add_product(handle, 5), SIM_OK);
+            assert_eq!(sim_run(handle), SIM_OK);
+
+            let json = CStr::from_ptr(sim_metrics_json(handle)).to_str().unwrap();
+            assert!(json.starts_with("{\"run\":{\"crate_version\":"));
+            assert!(json.contains("\"total_simulation_time\":"));
+            assert!(json.contains("\"completion_order\":[1,2]"));
+            assert!(json.contains("\"name\":\"Corte\""));
+            sim_free(handle);
+        }
+
+        assert!(sim_new(SIM_ROUND_ROBIN, 0).is_null());
+        assert!(sim_new(42, 0).is_null());
+    }
+
+    #[test]
+    fn test_header_declares_exported_functions() {
+        let header = include_str!("../include/assembly_line_simulator.h");
+        for function in [
+            "sim_new(",
+            "sim_add_station(",
+            "sim_add_product(",
+            "sim_run(",
+            "sim_metrics_json(",
+            "sim_free(",
+        ] {
+            assert!(header.contains(function), "Falta {} en el header", function);
+        }
+        for (constant, value) in [
+            ("SIM_FCFS", SIM_FCFS),
+            ("SIM_ROUND_ROBIN", SIM_ROUND_ROBIN),
+            ("SIM_EARLIEST_DUE_DATE", SIM_EARLIEST_DUE_DATE),
+            ("SIM_CRITICAL_RATIO", SIM_CRITICAL_RATIO),
+            ("SIM_OK", SIM_OK),
+            ("SIM_SIMULATION_FAILED", SIM_SIMULATION_FAILED),
+            ("SIM_INVALID_ARGUMENT", SIM_INVALID_ARGUMENT),
+        ] {
+            assert!(
+                header.contains(&format!("#define {} {}", constant, value))
+                    || header.contains(&format!("#define {} ({})", constant, value)),
+                "La constante {} del header no coincide",
+                constant
+            );
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! # Módulo de Trazabilidad
+//! 
+//! Este módulo reconstruye la genealogía de cada producto terminado: los
+//! productos relacionados con él (el pedido desechado al que reemplaza y
+//! los componentes de los que dependía para iniciar), las estaciones que
+//! visitó con sus instantes de entrada y salida y los recursos compartidos
+//! (operadores, herramientas) que se usaron en cada visita. Las visitas
+//! repetidas a una estación se marcan como retrabajo.
+//! 
+//! La genealogía se arma a partir de las métricas, por lo que no depende
+//! del backend con el que se ejecutó la simulación, y se exporta como un
+//! árbol JSON por producto terminado.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::metrics::{MetricsCalculator, ProductMetrics, SimulationMetrics};
+use crate::product::ProductLoss;
+
+/// Relación de un producto con el nodo padre de su genealogía.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    /// El padre es el reemplazo de este producto desechado
+    Replaces,
+    /// El padre dependía de que este producto completara `station`
+    Component { station: usize },
+}
+
+/// Paso de un producto por una estación.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenealogyVisit {
+    /// Índice de la estación (0-indexado)
+    pub station: usize,
+    /// Nombre de la estación
+    pub station_name: String,
+    /// Primera entrada a procesamiento, relativa al inicio de la simulación
+    pub entry: Duration,
+    /// Salida de la estación, relativa al inicio de la simulación
+    pub exit: Duration,
+    /// Recursos compartidos usados en la visita
+    pub resources: Vec<String>,
+    /// Indica si el producto ya había visitado la estación (retrabajo)
+    pub rework: bool,
+}
+
+/// Nodo del árbol genealógico: un producto y sus productos relacionados.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GenealogyNode {
+    /// ID del producto
+    pub product_id: usize,
+    /// Relación con el nodo padre (`None` en la raíz)
+    pub relation: Option<Relation>,
+    /// Clase del producto, si tiene una
+    pub class: Option<String>,
+    /// Llegada del producto, relativa al inicio de la simulación
+    pub arrival_time: Duration,
+    /// Salida del producto de la línea, o `None` si no la completó
+    pub departure: Option<Duration>,
+    /// Pérdida del producto, si fue desechado
+    pub loss: Option<ProductLoss>,
+    /// Visitas completadas o iniciadas, en orden de la ruta
+    pub visits: Vec<GenealogyVisit>,
+    /// Productos relacionados: primero el reemplazado y luego los componentes
+    pub related: Vec<GenealogyNode>,
+}
+
+impl GenealogyNode {
+    /// IDs de todos los productos del árbol, empezando por este, en preorden.
+    pub fn related_ids(&self) -> Vec<usize> {
+        let mut ids = vec![self.product_id];
+        for node in &self.related {
+            ids.extend(node.related_ids());
+        }
+        ids
+    }
+
+    /// Exporta el nodo y sus relacionados como un objeto JSON en una línea.
+    /// 
+    /// Los tiempos se expresan en segundos, como en el reporte JSON de
+    /// métricas; las estaciones se indexan desde 0.
+    pub fn to_json(&self) -> String {
+        let seconds = |duration: Duration| format!("{:.3}", duration.as_secs_f64());
+        let relation = match self.relation {
+            None => "null".to_string(),
+            Some(Relation::Replaces) => "\"replaces\"".to_string(),
+            Some(Relation::Component { station }) => format!("{{\"component_of_station\":{}}}", station),
+        };
+        let loss = self.loss.map_or_else(
+            || "null".to_string(),
+            |loss| format!("{{\"station\":{},\"reason\":\"{}\",\"at\":{}}}", loss.station, loss.reason.key(), seconds(loss.at)),
+        );
+        let visits: Vec<String> = self.visits
+            .iter()
+            .map(|visit| {
+                let resources: Vec<String> = visit.resources.iter().map(|name| MetricsCalculator::json_string(name)).collect();
+                format!(
+                    "{{\"station\":{},\"name\":{},\"entry\":{},\"exit\":{},\"resources\":[{}],\"rework\":{}}}",
+                    visit.station,
+                    MetricsCalculator::json_string(&visit.station_name),
+                    seconds(visit.entry),
+                    seconds(visit.exit),
+                    resources.join(","),
+                    visit.rework
+                )
+            })
+            .collect();
+        let related: Vec<String> = self.related.iter().map(GenealogyNode::to_json).collect();
+        format!(
+            "{{\"id\":{},\"relation\":{},\"class\":{},\"arrival_time\":{},\"departure\":{},\"loss\":{},\"visits\":[{}],\"related\":[{}]}}",
+            self.product_id,
+            relation,
+            self.class.as_deref().map_or_else(|| "null".to_string(), MetricsCalculator::json_string),
+            seconds(self.arrival_time),
+            self.departure.map_or_else(|| "null".to_string(), seconds),
+            loss,
+            visits.join(","),
+            related.join(",")
+        )
+    }
+}
+
+/// Genealogía de los productos terminados de una simulación.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Genealogy, ScrapPolicy, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// // El segundo producto abandona la cola y su reemplazo termina la línea
+/// let stations = vec![StationConfig::new("Horno", Duration::from_millis(30))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// simulation
+///     .set_patience(Duration::from_millis(10))
+///     .set_scrap_policy(ScrapPolicy::replace(1));
+/// let metrics = simulation.run().unwrap();
+/// 
+/// let genealogy = Genealogy::build(&metrics);
+/// let tree = genealogy.tree(3).unwrap();
+/// assert_eq!(tree.related_ids(), vec![3, 2]);
+/// assert_eq!(tree.visits[0].station_name, "Horno");
+/// assert!(genealogy.to_json().starts_with("[{\"id\":1,"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Genealogy {
+    /// Un árbol por producto terminado, en orden de ID
+    pub trees: Vec<GenealogyNode>,
+}
+
+impl Genealogy {
+    /// Construye el árbol genealógico de cada producto terminado.
+    /// 
+    /// Los productos relacionados se buscan entre los terminados, los
+    /// incompletos y los perdidos; un producto que no aparece en las
+    /// métricas se omite del árbol.
+    pub fn build(metrics: &SimulationMetrics) -> Self {
+        // Cada producto con la indicación de si terminó la línea
+        let products: HashMap<usize, (&ProductMetrics, bool)> = metrics.products
+            .iter()
+            .map(|product| (product, true))
+            .chain(metrics.incomplete_products.iter().chain(&metrics.lost_products).map(|product| (product, false)))
+            .map(|(product, finished)| (product.product_id, (product, finished)))
+            .collect();
+        let station_name = |index: usize| {
+            metrics.station_reports
+                .get(index)
+                .map_or_else(|| format!("estación {}", index + 1), |report| report.name.clone())
+        };
+
+        let mut finished: Vec<&ProductMetrics> = metrics.products.iter().collect();
+        finished.sort_by_key(|product| product.product_id);
+        let trees = finished
+            .into_iter()
+            .map(|product| Self::node(product, true, None, &products, &station_name, &mut HashSet::new()))
+            .collect();
+        Self { trees }
+    }
+
+    /// Arma el nodo de un producto, sin volver a los productos ya incluidos
+    /// en la rama para no entrar en ciclos.
+    fn node(
+        product: &ProductMetrics,
+        finished: bool,
+        relation: Option<Relation>,
+        products: &HashMap<usize, (&ProductMetrics, bool)>,
+        station_name: &dyn Fn(usize) -> String,
+        ancestors: &mut HashSet<usize>,
+    ) -> GenealogyNode {
+        ancestors.insert(product.product_id);
+
+        let mut seen = HashSet::new();
+        let visits = product.visit_times
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, entry, exit))| !entry.is_zero() || !exit.is_zero())
+            .map(|(visit, &(station, entry, exit))| GenealogyVisit {
+                station,
+                station_name: station_name(station),
+                entry,
+                exit,
+                resources: product.visit_resources.get(visit).cloned().unwrap_or_default(),
+                rework: !seen.insert(station),
+            })
+            .collect();
+
+        let relations = product.replaces
+            .map(|id| (id, Relation::Replaces))
+            .into_iter()
+            .chain(product.depends_on.iter().map(|&(id, station)| (id, Relation::Component { station })));
+        let mut related = Vec::new();
+        for (id, relation) in relations {
+            if ancestors.contains(&id) {
+                continue;
+            }
+            if let Some(&(other, other_finished)) = products.get(&id) {
+                related.push(Self::node(other, other_finished, Some(relation), products, station_name, ancestors));
+            }
+        }
+        ancestors.remove(&product.product_id);
+
+        GenealogyNode {
+            product_id: product.product_id,
+            relation,
+            class: product.class.clone(),
+            arrival_time: product.arrival_time,
+            departure: finished.then_some(product.arrival_time + product.turnaround_time),
+            loss: product.loss,
+            visits,
+            related,
+        }
+    }
+
+    /// Árbol de un producto terminado.
+    pub fn tree(&self, product_id: usize) -> Option<&GenealogyNode> {
+        self.trees.iter().find(|tree| tree.product_id == product_id)
+    }
+
+    /// Exporta los árboles como un arreglo JSON, un objeto por producto
+    /// terminado (ver [`GenealogyNode::to_json`]).
+    pub fn to_json(&self) -> String {
+        let trees: Vec<String> = self.trees.iter().map(GenealogyNode::to_json).collect();
+        format!("[{}]", trees.join(","))
+    }
+}
@@ -0,0 +1,399 @@
+//! # Módulo de Invariantes de las Métricas
+//! 
+//! Este módulo verifica que las métricas de una ejecución sean coherentes
+//! entre sí, sin importar la configuración de la línea:
+//! 
+//! - Cada visita sale de la estación después de entrar, y ninguna comienza
+//!   antes de que el producto llegue a la línea
+//! - Ninguna espera en cola termina antes de comenzar
+//! - El turnaround de cada producto es la suma de su espera (en cola, por
+//!   dependencias y por recursos), su servicio y sus traslados
+//! - El orden de finalización lista a cada producto completado una vez, en
+//!   el orden de sus salidas de la línea
+//! - Una estación nunca atiende dos porciones a la vez
+//! 
+//! [`Simulation::run`](crate::Simulation::run) verifica las invariantes al
+//! terminar en las compilaciones de depuración y hace panic si alguna no se
+//! cumple, de modo que las pruebas detecten errores de contabilidad.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::time::Duration;
+
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::product::TimelineEvent;
+
+/// Invariante de las métricas que no se cumple.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// Una visita termina antes de comenzar
+    ExitBeforeEntry {
+        /// ID del producto
+        product: usize,
+        /// Índice de la estación visitada
+        station: usize,
+        /// Entrada a la estación
+        entry: Duration,
+        /// Salida de la estación
+        exit: Duration,
+    },
+    /// El producto entra a una estación antes de llegar a la línea
+    EntryBeforeArrival {
+        /// ID del producto
+        product: usize,
+        /// Índice de la estación visitada
+        station: usize,
+        /// Llegada del producto a la línea
+        arrival: Duration,
+        /// Entrada a la estación
+        entry: Duration,
+    },
+    /// Una espera en cola termina antes de comenzar
+    NegativeWait {
+        /// ID del producto
+        product: usize,
+        /// Índice de la estación de la cola
+        station: usize,
+        /// Entrada a la cola
+        start: Duration,
+        /// Salida de la cola
+        end: Duration,
+    },
+    /// El turnaround no coincide con la suma de espera, servicio y traslados
+    TurnaroundMismatch {
+        /// ID del producto
+        product: usize,
+        /// Turnaround reportado
+        turnaround: Duration,
+        /// Suma de espera, servicio y traslados
+        accounted: Duration,
+    },
+    /// Un producto completado falta en el orden de finalización o aparece
+    /// más de una vez, o el orden lista un producto que no se completó
+    CompletionOrderMembership {
+        /// ID del producto
+        product: usize,
+        /// Veces que el producto aparece en el orden de finalización
+        occurrences: usize,
+    },
+    /// El orden de finalización ubica a un producto después de otro que
+    /// salió de la línea más tarde
+    CompletionOrderMismatch {
+        /// ID del producto que aparece antes en el orden
+        previous: usize,
+        /// Salida de la línea del producto anterior
+        previous_exit: Duration,
+        /// ID del producto que aparece después en el orden
+        product: usize,
+        /// Salida de la línea del producto
+        exit: Duration,
+    },
+    /// Una estación atiende dos porciones que se superponen
+    OverlappingSlices {
+        /// Índice de la estación
+        station: usize,
+        /// ID del producto de la primera porción
+        first: usize,
+        /// ID del producto de la segunda porción
+        second: usize,
+        /// Momento en que comienza la superposición
+        at: Duration,
+    },
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = MetricsCalculator::format_duration;
+        match self {
+            Self::ExitBeforeEntry { product, station, entry, exit } => write!(
+                f,
+                "el producto {} sale de la estación {} en {}, antes de entrar en {}",
+                product,
+                station + 1,
+                time(*exit),
+                time(*entry)
+            ),
+            Self::EntryBeforeArrival { product, station, arrival, entry } => write!(
+                f,
+                "el producto {} entra a la estación {} en {}, antes de llegar a la línea en {}",
+                product,
+                station + 1,
+                time(*entry),
+                time(*arrival)
+            ),
+            Self::NegativeWait { product, station, start, end } => write!(
+                f,
+                "la espera del producto {} en la estación {} termina en {}, antes de comenzar en {}",
+                product,
+                station + 1,
+                time(*end),
+                time(*start)
+            ),
+            Self::TurnaroundMismatch { product, turnaround, accounted } => write!(
+                f,
+                "el turnaround del producto {} es {}, pero su espera, servicio y traslados suman {}",
+                product,
+                time(*turnaround),
+                time(*accounted)
+            ),
+            Self::CompletionOrderMembership { product, occurrences } => write!(
+                f,
+                "el producto {} aparece {} veces en el orden de finalización",
+                product, occurrences
+            ),
+            Self::CompletionOrderMismatch { previous, previous_exit, product, exit } => write!(
+                f,
+                "el orden de finalización ubica al producto {} (salida en {}) antes que al producto {} (salida en {})",
+                previous,
+                time(*previous_exit),
+                product,
+                time(*exit)
+            ),
+            Self::OverlappingSlices { station, first, second, at } => write!(
+                f,
+                "la estación {} atiende a los productos {} y {} a la vez en {}",
+                station + 1,
+                first,
+                second,
+                time(*at)
+            ),
+        }
+    }
+}
+
+/// Verifica las invariantes de las métricas de una ejecución.
+/// 
+/// Solo se verifican los productos que completaron la línea; las métricas
+/// parciales de los productos perdidos o en curso no se revisan.
+/// 
+/// # Returns
+/// 
+/// Las invariantes que no se cumplen, vacío si las métricas son coherentes
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{verify_invariants, InvariantViolation, SchedulingAlgorithm, Simulation, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// let mut metrics = simulation.run().unwrap();
+/// assert!(verify_invariants(&metrics).is_empty());
+/// 
+/// metrics.completion_order.reverse();
+/// assert!(matches!(
+///     verify_invariants(&metrics).as_slice(),
+///     [InvariantViolation::CompletionOrderMismatch { previous: 2, product: 1, .. }]
+/// ));
+/// ```
+pub fn verify_invariants(metrics: &SimulationMetrics) -> Vec<InvariantViolation> {
+    let mut violations = Vec::new();
+    let mut exits = HashMap::new();
+    let mut slices: Vec<(usize, Duration, Duration, usize)> = Vec::new();
+
+    for product in &metrics.products {
+        let id = product.product_id;
+        for &(station, entry, exit) in &product.visit_times {
+            if exit < entry {
+                violations.push(InvariantViolation::ExitBeforeEntry { product: id, station, entry, exit });
+            }
+            if entry < product.arrival_time {
+                violations.push(InvariantViolation::EntryBeforeArrival {
+                    product: id,
+                    station,
+                    arrival: product.arrival_time,
+                    entry,
+                });
+            }
+        }
+
+        let mut transfers = product.timeline
+            .first()
+            .map(|span| span.start.saturating_sub(product.arrival_time))
+            .unwrap_or_default();
+        for span in &product.timeline {
+            match span.event {
+                TimelineEvent::Queued if span.end < span.start => {
+                    violations.push(InvariantViolation::NegativeWait {
+                        product: id,
+                        station: span.station,
+                        start: span.start,
+                        end: span.end,
+                    });
+                }
+                TimelineEvent::Transfer => transfers += span.duration(),
+                _ => {}
+            }
+        }
+        let service: Duration = product.slices.iter().map(|&(_, start, end)| end.saturating_sub(start)).sum();
+        let accounted = product.total_wait_time
+            + product.dependency_wait_time
+            + product.resource_wait_time
+            + service
+            + transfers;
+        if accounted != product.turnaround_time {
+            violations.push(InvariantViolation::TurnaroundMismatch {
+                product: id,
+                turnaround: product.turnaround_time,
+                accounted,
+            });
+        }
+
+        exits.insert(id, product.arrival_time + product.turnaround_time);
+        slices.extend(product.slices.iter().map(|&(station, start, end)| (station, start, end, id)));
+    }
+
+    let mut occurrences: HashMap<usize, usize> = HashMap::new();
+    for &id in &metrics.completion_order {
+        *occurrences.entry(id).or_default() += 1;
+    }
+    let mut reported = HashSet::new();
+    for &id in metrics.completion_order.iter().chain(metrics.products.iter().map(|product| &product.product_id)) {
+        let count = occurrences.get(&id).copied().unwrap_or(0);
+        if (count != 1 || !exits.contains_key(&id)) && reported.insert(id) {
+            violations.push(InvariantViolation::CompletionOrderMembership { product: id, occurrences: count });
+        }
+    }
+    let ordered: Vec<(usize, Duration)> = metrics.completion_order
+        .iter()
+        .filter_map(|id| exits.get(id).map(|&exit| (*id, exit)))
+        .collect();
+    for pair in ordered.windows(2) {
+        let ((previous, previous_exit), (product, exit)) = (pair[0], pair[1]);
+        if exit < previous_exit {
+            violations.push(InvariantViolation::CompletionOrderMismatch { previous, previous_exit, product, exit });
+        }
+    }
+
+    slices.sort_by_key(|&(station, start, end, id)| (station, start, end, id));
+    for pair in slices.windows(2) {
+        let ((station, _, first_end, first), (next_station, second_start, _, second)) = (pair[0], pair[1]);
+        if station == next_station && second_start < first_end {
+            violations.push(InvariantViolation::OverlappingSlices { station, first, second, at: second_start });
+        }
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Instant;
+    use crate::metrics::ProductMetrics;
+    use crate::product::TimelineSpan;
+
+    fn millis(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    /// Producto que espera `wait` en la estación 0 y luego se procesa `service`.
+    fn product(id: usize, arrival: u64, wait: u64, service: u64) -> ProductMetrics {
+        let entry = millis(arrival + wait);
+        let exit = entry + millis(service);
+        ProductMetrics {
+            product_id: id,
+            class: None,
+            metadata: HashMap::new(),
+            arrival_time: millis(arrival),
+            total_wait_time: millis(wait),
+            dependency_wait_time: Duration::ZERO,
+            resource_wait_time: Duration::ZERO,
+            turnaround_time: millis(wait + service),
+            timer_drift: Duration::ZERO,
+            service_time: millis(service),
+            slowdown: 1.0,
+            due_date: None,
+            tardiness: Duration::ZERO,
+            station_times: vec![(entry, exit)],
+            visit_times: vec![(0, entry, exit)],
+            visit_resources: vec![Vec::new()],
+            jockeys: 0,
+            slices: vec![(0, entry, exit)],
+            slice_units: vec![Vec::new()],
+            slice_counts: vec![1],
+            timeline: vec![
+                TimelineSpan { station: 0, event: TimelineEvent::Queued, start: millis(arrival), end: entry },
+                TimelineSpan { station: 0, event: TimelineEvent::Processing, start: entry, end: exit },
+            ],
+            config_epoch: 0,
+            loss: None,
+            replaces: None,
+            replaced_by: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    fn metrics(products: Vec<ProductMetrics>, completion_order: Vec<usize>) -> SimulationMetrics {
+        let mut metrics = MetricsCalculator::new().calculate_simulation_metrics(
+            &[],
+            &[],
+            Instant::now(),
+            Instant::now(),
+            completion_order,
+        );
+        metrics.products = products;
+        metrics
+    }
+
+    #[test]
+    fn test_consistent_metrics_have_no_violations() {
+        let line = metrics(vec![product(1, 0, 0, 10), product(2, 0, 10, 10)], vec![1, 2]);
+        assert_eq!(verify_invariants(&line), Vec::new());
+    }
+
+    #[test]
+    fn test_detects_each_kind_of_violation() {
+        let mut late = product(2, 0, 5, 10);
+        late.visit_times[0].2 = millis(1);
+        late.turnaround_time = millis(20);
+        let mut early = product(3, 20, 0, 10);
+        early.visit_times[0].1 = millis(15);
+        early.timeline[0].start = millis(25);
+        early.timeline[0].end = millis(20);
+        let line = metrics(vec![product(1, 0, 0, 10), late, early], vec![3, 1, 1]);
+
+        let violations = verify_invariants(&line);
+        assert!(violations.contains(&InvariantViolation::ExitBeforeEntry {
+            product: 2,
+            station: 0,
+            entry: millis(5),
+            exit: millis(1),
+        }));
+        assert!(violations.contains(&InvariantViolation::EntryBeforeArrival {
+            product: 3,
+            station: 0,
+            arrival: millis(20),
+            entry: millis(15),
+        }));
+        assert!(violations.contains(&InvariantViolation::NegativeWait {
+            product: 3,
+            station: 0,
+            start: millis(25),
+            end: millis(20),
+        }));
+        assert!(violations.contains(&InvariantViolation::TurnaroundMismatch {
+            product: 2,
+            turnaround: millis(20),
+            accounted: millis(15),
+        }));
+        assert!(violations.contains(&InvariantViolation::CompletionOrderMembership { product: 1, occurrences: 2 }));
+        assert!(violations.contains(&InvariantViolation::CompletionOrderMembership { product: 2, occurrences: 0 }));
+        assert!(violations.contains(&InvariantViolation::CompletionOrderMismatch {
+            previous: 3,
+            previous_exit: millis(30),
+            product: 1,
+            exit: millis(10),
+        }));
+        assert!(violations.contains(&InvariantViolation::OverlappingSlices {
+            station: 0,
+            first: 1,
+            second: 2,
+            at: millis(5),
+        }));
+        assert_eq!(
+            violations[0].to_string(),
+            "el producto 2 sale de la estación 1 en 0.001s, antes de entrar en 0.005s"
+        );
+    }
+}
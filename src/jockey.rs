@@ -0,0 +1,500 @@
+//! # Módulo de Cambios de Cola (Jockeying)
+//! 
+//! Cuando la línea tiene colas paralelas, un producto en espera puede
+//! abandonar su cola por otra más corta según una [`JockeyPolicy`]. Hay
+//! dos tipos de colas paralelas:
+//! 
+//! - **Ramas hermanas**: estaciones que un mismo divisor alimenta y que
+//!   continúan hacia la misma estación (por ejemplo, dos cabinas de pintura
+//!   en paralelo antes de Empaque). Entre porciones, cada estación revisa su
+//!   cola: el último producto que aún no comenzó a procesarse se cambia a
+//!   la hermana con menos productos (en cola y en proceso) si la política lo
+//!   permite. Su visita pasa a la hermana con el tiempo de procesamiento de
+//!   ella, conserva la espera acumulada y la ruta sigue igual después de la
+//!   rama. Una estación que ya terminó no recibe productos, y los productos
+//!   de un tipo cuya ruta no incluye la hermana no se cambian.
+//! - **Unidades de un recurso**: con la política activa, cada unidad de un
+//!   recurso compartido (cada máquina) tiene su propia cola. La estación que
+//!   necesita el recurso se forma en la cola más corta, contando la unidad
+//!   ocupada, y mientras espera la última de cada cola se cambia a otra si
+//!   la política lo permite. Sin política, las unidades comparten una única
+//!   cola y no hay cambios.
+//! 
+//! Cada producto cuenta sus cambios ([`Product::jockeys`]) y cada estación
+//! los de su cola ([`StationReport::jockeyed`] y
+//! [`StationReport::unit_jockeys`]). [`JockeyReport`] los resume junto con
+//! la dispersión de la espera de los productos, que es lo que el cambio de
+//! cola busca reducir: [`JockeyReport::variance_change`] la compara con la
+//! de una ejecución sin cambios de cola.
+//! 
+//! Solo el backend con hilos cambia productos de cola.
+//! 
+//! [`StationReport::jockeyed`]: crate::StationReport::jockeyed
+//! [`StationReport::unit_jockeys`]: crate::StationReport::unit_jockeys
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::product::{lock_recover, Product};
+use crate::routing::{RouteTarget, StationKind};
+use crate::station::StationConfig;
+
+/// Política con la que los productos en espera cambian a una cola más corta.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JockeyPolicy {
+    /// Los productos permanecen en la cola a la que llegaron
+    #[default]
+    Disabled,
+    /// El último producto de una cola se cambia a la cola paralela más
+    /// corta cuando en ella quedaría más de `margin` posiciones adelante.
+    /// 
+    /// Con margen 0 basta con que la otra cola tenga dos productos menos;
+    /// como el producto que se cambia queda último en su nueva cola, nunca
+    /// le conviene volver, por lo que no hay cambios de ida y vuelta
+    ShorterQueue {
+        /// Posiciones que debe ganar el producto, además de una, para cambiarse
+        margin: usize,
+    },
+}
+
+impl JockeyPolicy {
+    /// Crea una política que cambia al último producto de una cola a la
+    /// cola paralela más corta.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `margin` - Posiciones que debe ganar el producto, además de una
+    pub fn shorter_queue(margin: usize) -> Self {
+        Self::ShorterQueue { margin }
+    }
+
+    /// Indica si la política permite cambios de cola.
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::Disabled
+    }
+
+    /// Indica si el último producto de una cola debe cambiarse a otra.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `current` - Productos de su cola, en espera y en proceso, incluido él
+    /// * `other` - Productos de la otra cola, en espera y en proceso
+    pub(crate) fn should_move(&self, current: usize, other: usize) -> bool {
+        match self {
+            Self::Disabled => false,
+            Self::ShorterQueue { margin } => other + 1 + margin < current,
+        }
+    }
+}
+
+impl fmt::Display for JockeyPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "sin cambios de cola"),
+            Self::ShorterQueue { margin: 0 } => write!(f, "a la cola más corta"),
+            Self::ShorterQueue { margin } => write!(f, "a la cola más corta (margen {})", margin),
+        }
+    }
+}
+
+/// Estación hermana a la que puede cambiarse un producto en espera.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Sibling {
+    /// Índice de la estación hermana
+    pub(crate) station: usize,
+    /// Divisor que alimenta a ambas estaciones
+    pub(crate) splitter: usize,
+    /// Estación a la que continúan ambas (`None` = fin de la línea)
+    pub(crate) continuation: Option<usize>,
+    /// Salida por la que el producto deja la hermana, si es un divisor de
+    /// una sola salida
+    pub(crate) output: Option<usize>,
+}
+
+/// Calcula las estaciones hermanas de cada estación de la línea.
+/// 
+/// Dos estaciones son hermanas si un mismo divisor (anterior a ambas) las
+/// tiene como salidas y ambas continúan hacia la misma estación. Solo
+/// participan estaciones de procesamiento o divisores de una sola salida:
+/// en una inspección o un divisor con varias salidas la continuación del
+/// producto depende de la estación.
+/// 
+/// # Returns
+/// 
+/// Las hermanas de cada estación, por índice de estación
+pub(crate) fn siblings(station_configs: &[StationConfig]) -> Vec<Vec<Sibling>> {
+    let continuation = |station: usize| -> Option<(Option<usize>, Option<usize>)> {
+        let next = |target: RouteTarget| match target {
+            RouteTarget::Next => Some((station + 1 < station_configs.len()).then_some(station + 1)),
+            RouteTarget::Station(index) => Some(Some(index)),
+            RouteTarget::Reject => None,
+        };
+        match &station_configs[station].kind {
+            StationKind::Processing => Some((next(RouteTarget::Next)?, None)),
+            StationKind::Splitter(splitter) if splitter.outputs.len() == 1 => Some((next(splitter.outputs[0])?, Some(0))),
+            _ => None,
+        }
+    };
+
+    let mut siblings: Vec<Vec<Sibling>> = vec![Vec::new(); station_configs.len()];
+    for (splitter, config) in station_configs.iter().enumerate() {
+        let StationKind::Splitter(outputs) = &config.kind else {
+            continue;
+        };
+        let mut candidates: Vec<Sibling> = Vec::new();
+        for target in &outputs.outputs {
+            let RouteTarget::Station(station) = *target else {
+                continue;
+            };
+            if station <= splitter || candidates.iter().any(|candidate| candidate.station == station) {
+                continue;
+            }
+            if let Some((continuation, output)) = continuation(station) {
+                candidates.push(Sibling { station, splitter, continuation, output });
+            }
+        }
+        for candidate in &candidates {
+            for other in &candidates {
+                let known = siblings[candidate.station].iter().any(|sibling| sibling.station == other.station);
+                if other.station != candidate.station && other.continuation == candidate.continuation && !known {
+                    siblings[candidate.station].push(other.clone());
+                }
+            }
+        }
+    }
+    siblings
+}
+
+/// Cola de una estación en el tablero de cambios de cola.
+#[derive(Debug)]
+struct Slot {
+    /// Indica si la estación sigue recibiendo productos
+    open: bool,
+    /// Productos de la estación, en espera y en proceso
+    load: usize,
+    /// Productos que otras estaciones le cambiaron y que aún no tomó
+    arrivals: Vec<Arc<Product>>,
+}
+
+/// Tablero compartido por las estaciones con hermanas, donde publican la
+/// longitud de su cola y se entregan los productos que cambian de cola.
+/// 
+/// Los productos se entregan por el tablero y no por los canales de la
+/// línea: un canal hacia una estación anterior impediría que esta detecte
+/// que su emisor terminó. Por eso las estaciones con hermanas revisan el
+/// tablero periódicamente mientras esperan productos.
+#[derive(Debug)]
+pub(crate) struct JockeyBoard {
+    policy: JockeyPolicy,
+    siblings: Vec<Vec<Sibling>>,
+    names: Vec<String>,
+    slots: Vec<Mutex<Slot>>,
+}
+
+impl JockeyBoard {
+    /// Crea el tablero de una ejecución.
+    /// 
+    /// # Returns
+    /// 
+    /// `None` si la política no permite cambios o la línea no tiene ramas
+    /// hermanas
+    pub(crate) fn new(policy: JockeyPolicy, station_configs: &[StationConfig]) -> Option<Arc<Self>> {
+        let siblings = siblings(station_configs);
+        if !policy.is_enabled() || siblings.iter().all(Vec::is_empty) {
+            return None;
+        }
+        Some(Arc::new(Self {
+            policy,
+            siblings,
+            names: station_configs.iter().map(|config| config.name.clone()).collect(),
+            slots: station_configs
+                .iter()
+                .map(|_| Mutex::new(Slot { open: true, load: 0, arrivals: Vec::new() }))
+                .collect(),
+        }))
+    }
+
+    /// Indica si la estación tiene hermanas con las que intercambiar productos.
+    pub(crate) fn participates(&self, station: usize) -> bool {
+        !self.siblings[station].is_empty()
+    }
+
+    /// Publica la cantidad de productos de la estación (en espera y en proceso).
+    pub(crate) fn set_load(&self, station: usize, load: usize) {
+        lock_recover(&self.slots[station]).load = load;
+    }
+
+    /// Toma los productos que otras estaciones cambiaron a esta.
+    pub(crate) fn take_arrivals(&self, station: usize) -> Vec<Arc<Product>> {
+        std::mem::take(&mut lock_recover(&self.slots[station]).arrivals)
+    }
+
+    /// Elige la hermana a la que conviene cambiar un producto que aún no
+    /// comenzó a procesarse en `station`, la de menos productos y, entre
+    /// ellas, la de menor índice.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station` - Estación donde espera el producto
+    /// * `load` - Productos de la estación, incluido él
+    /// * `product` - Producto en espera
+    pub(crate) fn best_sibling(&self, station: usize, load: usize, product: &Product) -> Option<&Sibling> {
+        let visit = product.completed_visits();
+        self.siblings[station]
+            .iter()
+            .filter(|sibling| {
+                product.previous_station() == Some(sibling.splitter)
+                    && product.visit_station(visit + 1) == sibling.continuation
+                    && !product.processing_time(sibling.station).is_zero()
+            })
+            .map(|sibling| (lock_recover(&self.slots[sibling.station]).load, sibling))
+            .filter(|&(other, _)| self.policy.should_move(load, other))
+            .min_by_key(|&(other, sibling)| (other, sibling.station))
+            .map(|(_, sibling)| sibling)
+    }
+
+    /// Cambia un producto a la cola de una hermana si sigue abierta y la
+    /// política lo sigue permitiendo, trasladando su visita en curso.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si el producto cambió de cola, o `Err` con el producto si
+    /// debe permanecer en la suya
+    pub(crate) fn try_move(&self, load: usize, product: Arc<Product>, sibling: &Sibling) -> Result<(), Arc<Product>> {
+        let mut slot = lock_recover(&self.slots[sibling.station]);
+        if !slot.open || !self.policy.should_move(load, slot.load) {
+            return Err(product);
+        }
+        product.move_current_visit(sibling.station, sibling.output);
+        slot.load += 1;
+        slot.arrivals.push(product);
+        Ok(())
+    }
+
+    /// Nombre de una estación, para los mensajes de los cambios de cola.
+    pub(crate) fn name(&self, station: usize) -> &str {
+        &self.names[station]
+    }
+
+    /// Deja de recibir productos en la estación si no quedan cambios
+    /// pendientes de tomar.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si la estación quedó cerrada, o `Err` con los productos
+    /// pendientes, que la estación debe atender antes de terminar
+    pub(crate) fn close(&self, station: usize) -> Result<(), Vec<Arc<Product>>> {
+        let mut slot = lock_recover(&self.slots[station]);
+        if !slot.arrivals.is_empty() {
+            return Err(std::mem::take(&mut slot.arrivals));
+        }
+        slot.open = false;
+        slot.load = 0;
+        Ok(())
+    }
+
+    /// Deja de recibir productos en la estación aunque tenga cambios
+    /// pendientes, que quedan sin terminar (tras una parada anticipada o un
+    /// apagado que abandona las colas).
+    pub(crate) fn abandon(&self, station: usize) {
+        let mut slot = lock_recover(&self.slots[station]);
+        slot.open = false;
+        slot.load = 0;
+        slot.arrivals.clear();
+    }
+}
+
+/// Resumen de los cambios de cola de una simulación y de la dispersión de
+/// la espera de sus productos.
+/// 
+/// La espera de un producto es su espera en cola más la espera de recursos
+/// compartidos (ver [`JockeyReport::product_waits`]).
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{JockeyPolicy, RouteTarget, Simulation, SchedulingAlgorithm, Splitter, StationConfig};
+/// 
+/// // Corte alterna los productos entre dos cabinas; la cabina A es lenta
+/// let millis = Duration::from_millis;
+/// let cabinas = vec![RouteTarget::Station(1), RouteTarget::Station(2)];
+/// let stations = vec![
+///     StationConfig::new("Corte", millis(5)).with_splitter(Splitter::round_robin(cabinas)),
+///     StationConfig::new("Pintura A", millis(60)).with_output(RouteTarget::Station(3)),
+///     StationConfig::new("Pintura B", millis(10)),
+///     StationConfig::new("Empaque", millis(5)),
+/// ];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 8]);
+/// simulation.set_jockeying(JockeyPolicy::shorter_queue(0));
+/// 
+/// let metrics = simulation.run().unwrap();
+/// let jockeying = metrics.jockeying.as_ref().unwrap();
+/// assert!(jockeying.branch_moves > 0);
+/// assert_eq!(jockeying.moves, metrics.products.iter().map(|product| product.jockeys).sum::<usize>());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct JockeyReport {
+    /// Política con la que se cambiaron los productos de cola
+    pub policy: JockeyPolicy,
+    /// Cambios de cola de todos los productos
+    pub moves: usize,
+    /// Cambios entre colas de ramas hermanas
+    pub branch_moves: usize,
+    /// Cambios entre colas de unidades de recursos
+    pub unit_moves: usize,
+    /// Productos completados que cambiaron de cola al menos una vez
+    pub products_moved: usize,
+    /// Espera promedio de los productos completados
+    pub average_wait: Duration,
+    /// Varianza de la espera de los productos completados, en segundos al cuadrado
+    pub wait_variance: f64,
+    /// Espera promedio de los productos completados que cambiaron de cola,
+    /// o `None` si ninguno cambió
+    pub moved_average_wait: Option<Duration>,
+    /// Espera promedio de los productos completados que no cambiaron de
+    /// cola, o `None` si todos cambiaron
+    pub stayed_average_wait: Option<Duration>,
+}
+
+impl JockeyReport {
+    /// Resume los cambios de cola de una simulación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// * `policy` - Política de cambios de cola de la simulación
+    pub fn analyze(metrics: &SimulationMetrics, policy: JockeyPolicy) -> Self {
+        let branch_moves = metrics.station_reports.iter().map(|report| report.jockeyed).sum();
+        let unit_moves = metrics.station_reports.iter().map(|report| report.unit_jockeys).sum();
+        let waits = Self::product_waits(metrics);
+        let (moved, stayed): (Vec<_>, Vec<_>) = metrics.products
+            .iter()
+            .zip(&waits)
+            .partition(|(product, _)| product.jockeys > 0);
+        let average = |waits: &[Duration]| -> Option<Duration> {
+            (!waits.is_empty()).then(|| waits.iter().sum::<Duration>() / waits.len() as u32)
+        };
+        let moved: Vec<Duration> = moved.into_iter().map(|(_, &wait)| wait).collect();
+        let stayed: Vec<Duration> = stayed.into_iter().map(|(_, &wait)| wait).collect();
+
+        Self {
+            policy,
+            moves: branch_moves + unit_moves,
+            branch_moves,
+            unit_moves,
+            products_moved: moved.len(),
+            average_wait: average(&waits).unwrap_or_default(),
+            wait_variance: Self::wait_variance(metrics),
+            moved_average_wait: average(&moved),
+            stayed_average_wait: average(&stayed),
+        }
+    }
+
+    /// Obtiene la espera de cada producto completado: su espera en cola más
+    /// la espera de recursos compartidos, en el orden de
+    /// [`SimulationMetrics::products`].
+    pub fn product_waits(metrics: &SimulationMetrics) -> Vec<Duration> {
+        metrics.products
+            .iter()
+            .map(|product| product.total_wait_time + product.resource_wait_time)
+            .collect()
+    }
+
+    /// Calcula la varianza de la espera de los productos completados de una
+    /// simulación, con o sin cambios de cola.
+    /// 
+    /// # Returns
+    /// 
+    /// La varianza poblacional en segundos al cuadrado (0 sin productos)
+    pub fn wait_variance(metrics: &SimulationMetrics) -> f64 {
+        let waits: Vec<f64> = Self::product_waits(metrics).iter().map(Duration::as_secs_f64).collect();
+        if waits.is_empty() {
+            return 0.0;
+        }
+        let mean = waits.iter().sum::<f64>() / waits.len() as f64;
+        waits.iter().map(|wait| (wait - mean).powi(2)).sum::<f64>() / waits.len() as f64
+    }
+
+    /// Mide el efecto de los cambios de cola sobre la dispersión de la
+    /// espera, comparando con una ejecución de la misma línea sin ellos.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `baseline` - Métricas de la ejecución sin cambios de cola
+    /// 
+    /// # Returns
+    /// 
+    /// El cambio relativo de la varianza (-0.25 = 25% menos), o `None` si la
+    /// ejecución de referencia no tuvo dispersión
+    pub fn variance_change(&self, baseline: &SimulationMetrics) -> Option<f64> {
+        let baseline = Self::wait_variance(baseline);
+        (baseline > 0.0).then(|| (self.wait_variance - baseline) / baseline)
+    }
+}
+
+impl fmt::Display for JockeyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let average = |wait: Option<Duration>| wait.map_or("-".to_string(), MetricsCalculator::format_duration);
+        write!(
+            f,
+            "Política: {}\nCambios de cola: {} ({} entre ramas, {} entre unidades de recursos) en {} productos\nEspera promedio: {} (varianza {:.6} s²)\nEspera promedio con cambios: {}, sin cambios: {}",
+            self.policy,
+            self.moves,
+            self.branch_moves,
+            self.unit_moves,
+            self.products_moved,
+            MetricsCalculator::format_duration(self.average_wait),
+            self.wait_variance,
+            average(self.moved_average_wait),
+            average(self.stayed_average_wait)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routing::Splitter;
+
+    fn millis(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_policy_moves_only_when_the_product_gains_a_position() {
+        let policy = JockeyPolicy::shorter_queue(0);
+        assert!(!policy.should_move(2, 1));
+        assert!(policy.should_move(3, 1));
+        // Tras cambiarse, volver no le conviene
+        assert!(!policy.should_move(2, 2));
+        assert!(!JockeyPolicy::shorter_queue(1).should_move(3, 1));
+        assert!(!JockeyPolicy::Disabled.should_move(10, 0));
+    }
+
+    #[test]
+    fn test_siblings_share_splitter_and_continuation() {
+        let stations = vec![
+            StationConfig::new("Corte", millis(5)).with_splitter(Splitter::round_robin(vec![
+                RouteTarget::Station(1),
+                RouteTarget::Station(2),
+                RouteTarget::Station(4),
+            ])),
+            StationConfig::new("Pintura A", millis(20)).with_output(RouteTarget::Station(3)),
+            StationConfig::new("Pintura B", millis(20)),
+            StationConfig::new("Empaque", millis(5)),
+            StationConfig::new("Reproceso", millis(5)),
+        ];
+        let siblings = siblings(&stations);
+        let stations_of = |index: usize| siblings[index].iter().map(|sibling| sibling.station).collect::<Vec<_>>();
+        assert_eq!(stations_of(1), vec![2]);
+        assert_eq!(stations_of(2), vec![1]);
+        // Reproceso es la última estación: no continúa hacia Empaque
+        assert!(stations_of(4).is_empty());
+        assert_eq!(siblings[2][0].output, Some(0));
+        assert_eq!(siblings[1][0].output, None);
+        assert_eq!(siblings[1][0].continuation, Some(3));
+    }
+}
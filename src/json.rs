@@ -0,0 +1,505 @@
+//! # Módulo de JSON
+//! 
+//! Analizador JSON mínimo usado para leer escenarios. La biblioteca no
+//! depende de crates externos, por lo que aquí se implementa solo lo
+//! necesario: valores, objetos y arreglos, con errores que indican la
+//! posición del problema.
+
+use std::fmt;
+
+/// Niveles de arreglos y objetos anidados que admite un documento. El
+/// analizador es recursivo, por lo que sin este límite un documento con
+/// miles de `[` desbordaría la pila del hilo que lo lee.
+pub const MAX_DEPTH: usize = 128;
+
+/// Mensaje de los analizadores cuando un documento supera [`MAX_DEPTH`].
+pub(crate) fn too_deep() -> String {
+    format!("más de {} niveles de anidamiento", MAX_DEPTH)
+}
+
+/// Valor JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// Pares clave-valor en el orden en que aparecen en el documento
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Analiza un documento JSON completo.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - Texto del documento
+    /// 
+    /// # Returns
+    /// 
+    /// El valor del documento, o `Err` con la descripción y la posición del
+    /// primer error; también es un error anidar más de [`MAX_DEPTH`]
+    /// arreglos u objetos
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::json::Json;
+    /// 
+    /// let value = Json::parse(r#"{"quantum_ms": 100, "stations": ["Corte"]}"#).unwrap();
+    /// assert_eq!(value.get("quantum_ms").and_then(Json::as_f64), Some(100.0));
+    /// ```
+    pub fn parse(text: &str) -> Result<Json, String> {
+        Self::parse_located(text).map(|(value, _)| value)
+    }
+
+    /// Analiza un documento JSON y registra la línea de cada valor (ver
+    /// [`Locations`]).
+    pub fn parse_located(text: &str) -> Result<(Json, Locations), String> {
+        let mut parser = Parser { text, position: 0, depth: 0, path: String::new(), locations: Locations::default() };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.position < text.len() {
+            return Err(parser.error("contenido adicional después del documento"));
+        }
+        Ok((value, parser.locations))
+    }
+
+    /// Obtiene el valor de una clave si el valor es un objeto.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries
+                .iter()
+                .find(|(entry_key, _)| entry_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Obtiene el número si el valor es numérico.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Obtiene el texto si el valor es un string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Obtiene los elementos si el valor es un arreglo.
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Obtiene los pares clave-valor si el valor es un objeto.
+    pub fn as_object(&self) -> Option<&[(String, Json)]> {
+        match self {
+            Json::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Indica si el valor no contiene objetos ni arreglos.
+    pub fn is_scalar(&self) -> bool {
+        !matches!(self, Json::Array(_) | Json::Object(_))
+    }
+
+    /// Escribe el valor con sangría de dos espacios, para archivos que se
+    /// editan a mano. Los arreglos de escalares se mantienen en una línea.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::json::Json;
+    /// 
+    /// let value = Json::parse(r#"{"a": [1, 2], "b": {"c": null}}"#).unwrap();
+    /// assert_eq!(value.to_pretty_string(), "{\n  \"a\": [1, 2],\n  \"b\": {\n    \"c\": null\n  }\n}");
+    /// ```
+    pub fn to_pretty_string(&self) -> String {
+        let mut output = String::new();
+        self.write_pretty(&mut output, 0);
+        output
+    }
+
+    fn write_pretty(&self, output: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth + 1);
+        match self {
+            Json::Array(items) if items.iter().all(Json::is_scalar) => {
+                let items: Vec<String> = items.iter().map(Json::to_string).collect();
+                output.push_str(&format!("[{}]", items.join(", ")));
+            }
+            Json::Array(items) => {
+                output.push_str("[\n");
+                for (index, item) in items.iter().enumerate() {
+                    output.push_str(&indent);
+                    item.write_pretty(output, depth + 1);
+                    output.push_str(if index + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                output.push_str(&format!("{}]", "  ".repeat(depth)));
+            }
+            Json::Object(entries) if entries.is_empty() => output.push_str("{}"),
+            Json::Object(entries) => {
+                output.push_str("{\n");
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    output.push_str(&format!("{}{}: ", indent, Json::String(key.clone())));
+                    value.write_pretty(output, depth + 1);
+                    output.push_str(if index + 1 < entries.len() { ",\n" } else { "\n" });
+                }
+                output.push_str(&format!("{}}}", "  ".repeat(depth)));
+            }
+            scalar => output.push_str(&scalar.to_string()),
+        }
+    }
+
+    /// Nombre del tipo del valor, para mensajes de error.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Json::Null => "null",
+            Json::Bool(_) => "booleano",
+            Json::Number(_) => "número",
+            Json::String(_) => "string",
+            Json::Array(_) => "arreglo",
+            Json::Object(_) => "objeto",
+        }
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(number) => write!(f, "{}", number),
+            Json::String(text) => write_string(f, text),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(entries) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// Línea del documento en la que aparece cada valor, identificado por su
+/// ruta (`stations[2].processing_time_ms`; la raíz es la ruta vacía).
+/// 
+/// Los analizadores de JSON, YAML y RON la registran para que los errores de
+/// validación indiquen dónde está el problema en el archivo original.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Locations {
+    entries: Vec<(String, usize)>,
+}
+
+impl Locations {
+    /// Obtiene la línea (desde 1) del valor con esa ruta.
+    pub fn line(&self, path: &str) -> Option<usize> {
+        self.entries.iter().find(|(entry, _)| entry == path).map(|&(_, line)| line)
+    }
+
+    /// Registra la línea de un valor; se conserva la primera registrada.
+    pub(crate) fn record(&mut self, path: &str, line: usize) {
+        if self.line(path).is_none() {
+            self.entries.push((path.to_string(), line));
+        }
+    }
+
+    /// Ruta de la clave `key` dentro del valor `parent`.
+    pub(crate) fn field(parent: &str, key: &str) -> String {
+        if parent.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", parent, key)
+        }
+    }
+
+    /// Ruta del elemento `index` dentro del valor `parent`.
+    pub(crate) fn index(parent: &str, index: usize) -> String {
+        format!("{}[{}]", parent, index)
+    }
+}
+
+/// Escribe un texto como string JSON, escapando los caracteres especiales.
+fn write_string(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for character in text.chars() {
+        match character {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Analizador recursivo descendente.
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+    /// Arreglos y objetos abiertos en la posición actual
+    depth: usize,
+    /// Ruta del valor que se está leyendo
+    path: String,
+    locations: Locations,
+}
+
+impl Parser<'_> {
+    /// Línea (desde 1) de la posición actual.
+    fn line(&self) -> usize {
+        self.text[..self.position].matches('\n').count() + 1
+    }
+
+    /// Lee el valor de la ruta `path`, registrando su línea.
+    fn parse_value_at(&mut self, path: String) -> Result<Json, String> {
+        self.skip_whitespace();
+        let parent = std::mem::replace(&mut self.path, path);
+        self.locations.record(&self.path, self.line());
+        let value = self.parse_value();
+        self.path = parent;
+        value
+    }
+
+    /// Construye un error con la línea y columna de la posición actual.
+    fn error(&self, message: &str) -> String {
+        let consumed = &self.text[..self.position];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.rsplit('\n').next().map_or(0, |last| last.chars().count()) + 1;
+        format!("JSON inválido en línea {}, columna {}: {}", line, column, message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_whitespace()) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("se esperaba '{}'", expected)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        if self.path.is_empty() {
+            self.locations.record("", self.line());
+        }
+        match self.peek() {
+            Some('{') => self.parse_nested(Self::parse_object),
+            Some('[') => self.parse_nested(Self::parse_array),
+            Some('"') => self.parse_string().map(Json::String),
+            Some('t') => self.parse_literal("true", Json::Bool(true)),
+            Some('f') => self.parse_literal("false", Json::Bool(false)),
+            Some('n') => self.parse_literal("null", Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            Some(_) => Err(self.error("valor inesperado")),
+            None => Err(self.error("fin inesperado del documento")),
+        }
+    }
+
+    /// Lee un arreglo u objeto, rechazando los que superan [`MAX_DEPTH`].
+    fn parse_nested(&mut self, parse: fn(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(&too_deep()));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: Json) -> Result<Json, String> {
+        if self.text[self.position..].starts_with(literal) {
+            self.position += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("valor inesperado"))
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Json, String> {
+        let start = self.position;
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+            self.position += c.len_utf8();
+        }
+        self.text[start..self.position]
+            .parse()
+            .map(Json::Number)
+            .map_err(|_| {
+                self.position = start;
+                self.error("número inválido")
+            })
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut result = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("string sin cerrar"));
+            };
+            self.position += c.len_utf8();
+            match c {
+                '"' => return Ok(result),
+                '\\' => {
+                    let Some(escaped) = self.peek() else {
+                        return Err(self.error("string sin cerrar"));
+                    };
+                    self.position += 1;
+                    match escaped {
+                        '"' => result.push('"'),
+                        '\\' => result.push('\\'),
+                        '/' => result.push('/'),
+                        'n' => result.push('\n'),
+                        'r' => result.push('\r'),
+                        't' => result.push('\t'),
+                        'b' => result.push('\u{8}'),
+                        'f' => result.push('\u{c}'),
+                        'u' => {
+                            let code = self.text
+                                .get(self.position..self.position + 4)
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("escape unicode inválido"))?;
+                            self.position += 4;
+                            result.push(code);
+                        }
+                        _ => return Err(self.error("escape inválido")),
+                    }
+                }
+                c => result.push(c),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            let path = Locations::index(&self.path, items.len());
+            items.push(self.parse_value_at(path)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(self.error("se esperaba ',' o ']'")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Json::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let line = self.line();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            let path = Locations::field(&self.path, &key);
+            self.locations.record(&path, line);
+            let value = self.parse_value_at(path)?;
+            entries.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(Json::Object(entries));
+                }
+                _ => return Err(self.error("se esperaba ',' o '}'")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_roundtrip() {
+        let text = r#"{"a":[1,2.5,-3e2],"b":{"c":null,"d":true},"e":"x\"yé\n"}"#;
+        let value = Json::parse(text).unwrap();
+        assert_eq!(value.get("a").and_then(Json::as_array).map(<[Json]>::len), Some(3));
+        assert_eq!(value.get("e").and_then(Json::as_str), Some("x\"yé\n"));
+        assert_eq!(Json::parse(&value.to_string()).unwrap(), value);
+    }
+
+    #[test]
+    fn test_parse_located_records_lines() {
+        let (_, locations) = Json::parse_located("{\n  \"a\": [\n    1,\n    {\"b\": 2}\n  ]\n}").unwrap();
+        assert_eq!(locations.line(""), Some(1));
+        assert_eq!(locations.line("a"), Some(2));
+        assert_eq!(locations.line("a[1].b"), Some(4));
+        assert_eq!(locations.line("c"), None);
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        let error = Json::parse("{\n  \"a\": [1, 2,]\n}").unwrap_err();
+        assert!(error.contains("línea 2"), "{}", error);
+        assert!(Json::parse("[1] 2").is_err());
+        assert!(Json::parse("\"abc").is_err());
+    }
+
+    #[test]
+    fn test_deep_nesting_is_rejected() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(Json::parse(&nested(MAX_DEPTH)).is_ok());
+        let error = Json::parse(&nested(MAX_DEPTH + 1)).unwrap_err();
+        assert!(error.contains("niveles de anidamiento"), "{}", error);
+        // Sin el límite, este documento desbordaría la pila
+        assert!(Json::parse(&"[".repeat(200_000)).is_err());
+        assert!(Json::parse(&"{\"a\":".repeat(200_000)).is_err());
+    }
+}
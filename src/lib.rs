@@ -17,23 +17,174 @@
 //! ## Estructura del proyecto
 //! 
 //! - `station`: Módulo que define las estaciones de trabajo y su configuración
+//! - `status`: Módulo con la máquina de estados de las estaciones y sus observadores
 //! - `product`: Módulo que define los productos y sus métricas asociadas
-//! - `scheduler`: Módulo que implementa los algoritmos de planificación
+//! - `scheduler`: Módulo que implementa los algoritmos de planificación y las disciplinas de cola
 //! - `simulation`: Módulo principal que coordina la simulación
 //! - `metrics`: Módulo para el cálculo y reporte de métricas
+//! - `error`: Módulo con los errores que puede producir la simulación
+//! - `arrival`: Módulo con los procesos y fuentes de llegada de productos
+//! - `stop`: Módulo con las condiciones de parada de la simulación
+//! - `progress`: Módulo con el avance de la simulación y su tiempo restante estimado
+//! - `sink`: Módulo con los destinos que reciben cada producto completado (consola, memoria, NDJSON, canal)
+//! - `routing`: Módulo con los tipos de estación que deciden a dónde sigue cada producto (inspecciones con salidas de aprobados y rechazados, divisores con varias salidas y uniones que alternan sus orígenes)
+//! - `topology`: Módulo con el grafo de la línea y la validación de su topología (estaciones inalcanzables, ciclos sin salida, salidas sin uso y referencias inexistentes)
+//! - `catalog`: Módulo con el catálogo de tipos de producto (rutas y tiempos por tipo)
+//! - `resource`: Módulo con los recursos compartidos entre estaciones
+//! - `jockey`: Módulo con los cambios de cola (jockeying) entre ramas paralelas y unidades de recursos
+//! - `genealogy`: Módulo con la trazabilidad de cada producto terminado (productos relacionados, visitas y recursos usados)
+//! - `deadlock`: Módulo con la detección y el diagnóstico de interbloqueos
+//! - `storage`: Módulo con el modo de almacenamiento de los intervalos de cada visita (completo o en registros compactos por estación)
+//! - `scrap`: Módulo con la política de desecho y el reemplazo automático de productos desechados
+//! - `sequencing`: Módulo con la regla de Johnson para flow shops de dos y tres estaciones y el planificador global que ordena las liberaciones a la primera estación
+//! - `sla`: Módulo con los acuerdos de nivel de servicio (SLA) y su evaluación
+//! - `takt`: Módulo con la comparación del ritmo de salida contra un takt time
+//! - `trajectory`: Módulo con el muestreo periódico de la posición de los productos en curso
+//! - `work`: Módulo con los modelos de trabajo que simulan el procesamiento
+//! - `simulation_async`: Backend asíncrono sobre tokio, de un solo hilo o multihilo, y modo de tiempo virtual (feature `async`)
+//! - `tuning`: Búsqueda del quantum de Round Robin en tiempo virtual (feature `async`)
+//! - `optimize`: Búsqueda de la configuración de la línea por recocido simulado o algoritmo genético (feature `async`)
+//! - `channel`: Módulo con los tipos de canal entre estaciones (sin límite, acotados, con semáforos o de crossbeam con la feature `crossbeam`)
+//! - `control`: Módulo con los comandos que modifican las estaciones durante la ejecución
+//! - `reload`: Módulo con las reconfiguraciones en caliente y sus épocas de configuración
+//! - `clock`: Módulo con las fuentes de tiempo (real o virtual), el formato de los tiempos y los ticks de simulación
+//! - `batch`: Módulo para ejecutar lotes de simulaciones en paralelo
+//! - `replication`: Módulo con las réplicas por semilla y sus reportes con intervalos de confianza (texto, Markdown y HTML)
+//! - `provenance`: Módulo con los metadatos de cada ejecución (versión, commit, fecha, equipo y sistema operativo)
+//! - `experiment`: Módulo que agrupa ejecuciones con nombre y genera un reporte consolidado
+//! - `ffi`: Interfaz C para usar el simulador desde otros lenguajes (feature `ffi`)
+//! - `store`: Almacenamiento de resultados en una base SQLite (feature `store`)
+//! - `charts`: Gráficos de resultados en SVG o PNG (feature `plotters`)
+//! - `affinity`: Modo experimental que fija las estaciones a núcleos y reporta la planificación del sistema operativo (feature `affinity`, solo Linux)
+//! - `json`: Analizador JSON mínimo usado por los escenarios
+//! - `yaml`: Analizador YAML mínimo para escenarios escritos en ese formato
+//! - `ron`: Analizador RON mínimo para escenarios escritos en ese formato
+//! - `toml`: Analizador TOML mínimo para escenarios escritos en ese formato
+//! - `schema`: Esquema JSON del formato de escenarios y validación con errores por campo y línea
+//! - `templates`: Plantillas de escenarios incluidas en la biblioteca (convoy, ráfagas, línea balanceada)
+//! - `scenario`: Módulo para describir simulaciones como documentos JSON y comparar configuraciones
+//! - `repl`: Modo interactivo para configurar y comparar simulaciones
+//! - `analysis`: Módulo con análisis de patrones de planificación (efecto convoy, productos atípicos) y comparación estadística entre ejecuciones
+//! - `analytics`: Módulo con las predicciones M/M/1 y M/D/1 por estación contrastadas con la simulación
+//! - `invariants`: Módulo con la verificación de la coherencia de las métricas (visitas, esperas, turnaround, orden de finalización y porciones por estación)
+//! - `bounds`: Módulo con las cotas inferiores (tiempo de servicio, makespan y turnaround mínimos) calculadas sin ejecutar la simulación
+//! - `trace`: Módulo con la traza de eventos de una ejecución y su comparación con trazas de referencia (golden)
+//! - `testing`: Herramientas de prueba sin esperas reales (reloj controlable y arnés que conduce una estación por llegadas programadas)
 
 pub mod station;
+pub mod status;
 pub mod product;
 pub mod scheduler;
 pub mod simulation;
 pub mod metrics;
+pub mod error;
+pub mod arrival;
+pub mod stop;
+pub mod progress;
+pub mod sink;
+pub mod routing;
+pub mod topology;
+pub mod catalog;
+pub mod resource;
+pub mod jockey;
+pub mod deadlock;
+pub mod scrap;
+pub mod storage;
+pub mod genealogy;
+pub mod sequencing;
+pub mod sla;
+pub mod takt;
+pub mod trajectory;
+pub mod work;
+pub mod channel;
+pub mod clock;
+pub mod control;
+pub mod reload;
+pub mod batch;
+pub mod experiment;
+pub mod provenance;
+pub mod replication;
+pub mod json;
+pub mod yaml;
+pub mod ron;
+pub mod toml;
+pub mod schema;
+pub mod templates;
+pub mod scenario;
+pub mod repl;
+pub mod analysis;
+pub mod analytics;
+pub mod invariants;
+pub mod bounds;
+pub mod trace;
+pub mod testing;
+#[cfg(feature = "async")]
+pub mod simulation_async;
+#[cfg(feature = "async")]
+pub mod tuning;
+#[cfg(feature = "async")]
+pub mod optimize;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "store")]
+pub mod store;
+#[cfg(feature = "plotters")]
+pub mod charts;
+#[cfg(all(feature = "affinity", target_os = "linux"))]
+pub mod affinity;
+mod rng;
 
 // Re-exportar las estructuras principales para facilitar su uso
-pub use station::{Station, StationConfig, StationState};
-pub use product::Product;
-pub use scheduler::SchedulingAlgorithm;
-pub use simulation::Simulation;
-pub use metrics::MetricsCalculator;
+pub use station::{MergeSourceReport, RampUp, ShutdownMode, Station, StationConfig, StationReport, StationState, TimerJitter, WarmUp};
+pub use status::{StationObserver, StationStatus, StatusBoard, StatusTransition};
+pub use product::{LossReason, Product, ProductDependency, ProductLoss, ProductSpec, TimelineEvent, TimelineSpan};
+pub use scheduler::{QueueDiscipline, SchedulingAlgorithm};
+pub use scrap::ScrapPolicy;
+pub use storage::StorageMode;
+pub use simulation::{Simulation, SimulationHandle};
+pub use metrics::{ClassMetrics, DistributionMetric, FlowStatistics, Histogram, MetricsCalculator, PeriodMetrics, ProductMetrics, WaitMatrix};
+pub use arrival::{ArrivalIter, ArrivalOrder, ArrivalPeriod, ArrivalProcess, ArrivalSource};
+pub use control::{LineControl, StationCommand};
+pub use reload::Reconfiguration;
+pub use stop::{LiveMetrics, StopCondition, StopSignal};
+pub use progress::{Progress, ProgressCallback};
+pub use sink::{ChannelSink, ConsoleSink, CsvSink, NdjsonSink, Sink, VecSink};
+pub use routing::{
+    Inspection, InspectionRule, MergePolicy, Merger, RouteTarget, RoutingPolicy, Splitter, StationKind, INSPECTION_TIME,
+};
+pub use topology::{Topology, TopologyEdge, TopologyNode};
+pub use catalog::{ProductCatalog, ProductType};
+pub use resource::{ResourceUnitReport, ResourceUse, SharedResource};
+pub use jockey::{JockeyPolicy, JockeyReport};
+pub use deadlock::{DeadlockReport, StationSnapshot};
+pub use genealogy::{Genealogy, GenealogyNode, GenealogyVisit, Relation};
+pub use sequencing::{FlowShop, ReleasePlan, SequencingComparison};
+pub use sla::{Sla, SlaResult};
+pub use takt::{LateInterval, TaktPeriod, TaktReport};
+pub use trajectory::{Trajectories, TrajectorySample};
+pub use work::{BusyWait, CpuBurn, NoWork, SleepWork, WorkMode, WorkModel};
+pub use channel::{ChannelBackend, SemaphoreReceiver, SemaphoreSender, StationReceiver, StationSender};
+pub use clock::{Clock, ClockFormat, SimTime, VirtualClock};
+pub use batch::{run_batch, BatchRunner};
+pub use experiment::{Experiment, ExperimentRun, RankedRun, ScoreWeights};
+pub use provenance::RunMetadata;
+pub use replication::{ReplicatedStatistic, Replications};
+pub use scenario::{Scenario, ScenarioChange, ScenarioDiff, ScenarioFormat};
+pub use schema::SchemaError;
+pub use templates::{ScenarioTemplate, TEMPLATES};
+pub use analytics::{AnalyticalBaseline, QueueModel, StationBaseline};
+pub use bounds::{LowerBounds, ProductBound, StationBound};
+pub use invariants::{verify_invariants, InvariantViolation};
+pub use trace::{EventTrace, TraceDivergence, TraceEvent, TraceEventKind};
+pub use analysis::{
+    compare_runs, diff_metrics, Convoy, ConvoyAnalysis, ConvoyDetector, MetricComparison, MetricDelta, MetricsDiff, Outlier, OutlierAnalysis,
+    OutlierDetector, RunComparison, TTest,
+};
+pub use error::{ConfigError, SimulationError, StallReason, StalledProduct};
+#[cfg(feature = "async")]
+pub use tuning::{QuantumObjective, QuantumOptimizer, QuantumRecommendation, QuantumSearch};
+#[cfg(feature = "async")]
+pub use optimize::{LineConfiguration, LineObjective, LineOptimization, LineOptimizer, LineSearch, OptimizationStep};
 
 /// Configuración por defecto del simulador
 pub mod config {
@@ -44,22 +195,17 @@ pub mod config {
     
     /// Quantum por defecto para Round Robin (en milisegundos)
     pub const DEFAULT_QUANTUM_MS: u64 = 300;
+
+    /// Tiempo sin actividad tras el cual se declara un interbloqueo cuando
+    /// la línea usa canales acotados o recursos compartidos (en milisegundos)
+    pub const DEFAULT_DEADLOCK_TIMEOUT_MS: u64 = 2000;
     
     /// Configuración de las estaciones de trabajo
     pub fn default_station_configs() -> Vec<super::StationConfig> {
         vec![
-            super::StationConfig {
-                name: "Corte",
-                processing_time: Duration::from_millis(400),
-            },
-            super::StationConfig {
-                name: "Ensamblaje", 
-                processing_time: Duration::from_millis(600),
-            },
-            super::StationConfig {
-                name: "Empaque",
-                processing_time: Duration::from_millis(500),
-            },
+            super::StationConfig::new("Corte", Duration::from_millis(400)),
+            super::StationConfig::new("Ensamblaje", Duration::from_millis(600)),
+            super::StationConfig::new("Empaque", Duration::from_millis(500)),
         ]
     }
     
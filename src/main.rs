@@ -19,21 +19,81 @@
 //! 
 //! # Round Robin con quantum personalizado (default: 300ms)
 //! cargo run -- rr 250
+//! 
+//! # Buffers entre estaciones sincronizados con semáforos contadores
+//! cargo run -- fcfs --canal semaforo:2
+//! 
+//! # Tiempos en milisegundos (también: s, hms, ticks)
+//! cargo run -- rr 250 --reloj ms
+//! 
+//! # Porciones que queman CPU en lugar de dormir, para ver la competencia
+//! # real entre los hilos de las estaciones (también: dormir, espera, nada)
+//! cargo run -- rr 250 --trabajo cpu
+//! 
+//! # Barra de progreso con tiempo restante estimado (en stderr)
+//! cargo run -- fcfs --progreso
+//! 
+//! # Cotas inferiores de la configuración, sin ejecutar la simulación
+//! cargo run -- rr 250 --dry-run
+//! 
+//! # Plantilla incluida (convoy, burst, balanced), o exportarla para editarla
+//! cargo run -- --template convoy
+//! cargo run -- --template convoy --emit convoy.yaml
+//! 
+//! # Modo interactivo
+//! cargo run -- repl
 //! ```
+//! 
+//! CTRL-C cancela la simulación de forma ordenada y muestra el reporte de
+//! los productos completados hasta el momento junto con el estado de los
+//! que quedaron en la línea. Un segundo CTRL-C termina el proceso de
+//! inmediato.
 
 use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
 use std::process;
+use std::thread;
+use std::time::Duration;
 
 use assembly_line_simulator::{
     config,
+    ChannelBackend,
+    ClockFormat,
+    repl::Repl,
+    ScenarioFormat,
+    ScenarioTemplate,
     SchedulingAlgorithm,
     Simulation,
+    TEMPLATES,
+    WorkMode,
 };
 
+/// Origen de la simulación indicado en la línea de comandos.
+enum Source {
+    /// Configuración por defecto con el algoritmo indicado
+    Algorithm(SchedulingAlgorithm),
+    /// Plantilla incluida; con `emit`, se guarda en ese archivo en lugar de ejecutarse
+    Template {
+        template: &'static ScenarioTemplate,
+        emit: Option<PathBuf>,
+    },
+}
+
 fn main() {
+    // Modo interactivo
+    if env::args().nth(1).as_deref() == Some("repl") {
+        if let Err(err) = Repl::new().run(io::stdin().lock(), io::stdout()) {
+            eprintln!("Error: {}", err);
+            process::exit(1);
+        }
+        return;
+    }
+
     // Parsear argumentos de línea de comandos
-    let algorithm = match parse_args() {
-        Ok(alg) => alg,
+    let (source, channel_backend, clock_format, work_mode, show_progress, dry_run) = match parse_args() {
+        Ok(parsed) => parsed,
         Err(err) => {
             eprintln!("Error: {}", err);
             print_usage();
@@ -42,22 +102,214 @@ fn main() {
     };
 
     // Ejecutar simulación
-    let mut simulation = Simulation::new(algorithm);
-    let metrics = simulation.run();
+    ClockFormat::set_global(clock_format);
+    let mut simulation = match source {
+        Source::Algorithm(algorithm) => Simulation::new(algorithm),
+        Source::Template { template, emit: Some(path) } => {
+            if let Err(err) = emit_template(template, &path) {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            }
+            println!("Plantilla '{}' guardada en {}", template.name, path.display());
+            return;
+        }
+        Source::Template { template, emit: None } => template.scenario().to_simulation(),
+    };
+    simulation.set_channel_backend(channel_backend);
+    simulation.set_work_model(work_mode);
+    if show_progress {
+        simulation.on_progress(|progress| eprintln!("[PROGRESO] {}", progress));
+    }
+    if dry_run {
+        match simulation.analyze() {
+            Ok(bounds) => println!("{}", bounds),
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+    if let Err(err) = simulation.validate() {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    }
+
+    // CTRL-C cancela la línea en lugar de terminar el proceso sin reporte
+    interrupt::install();
+    let mut handle = simulation.start();
+    handle.close_injection();
+    while !handle.is_finished() {
+        if interrupt::requested() {
+            eprintln!("\n[INFO] Interrupción recibida, deteniendo la línea...");
+            handle.cancel();
+            break;
+        }
+        thread::sleep(INTERRUPT_POLL_INTERVAL);
+    }
+    let interrupted = interrupt::requested();
+
+    let metrics = match handle.wait() {
+        Ok(metrics) => metrics,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            let report = simulation.generate_report(err.partial_metrics());
+            println!("{}", report);
+            process::exit(1);
+        }
+    };
     
     // Generar y mostrar reporte
     let report = simulation.generate_report(&metrics);
     println!("{}", report);
+
+    if interrupted {
+        eprintln!(
+            "Simulación interrumpida: {} productos completados, {} en la línea",
+            metrics.products.len(),
+            metrics.incomplete_products.len()
+        );
+        process::exit(130);
+    }
+}
+
+/// Intervalo con el que se consulta si el usuario pidió interrumpir la simulación.
+const INTERRUPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Manejo de CTRL-C sin dependencias externas.
+/// 
+/// El manejador solo activa una bandera atómica (operación segura dentro de
+/// un manejador de señales); el hilo principal la consulta y cancela la
+/// simulación. Si la bandera ya estaba activa, el proceso termina de
+/// inmediato.
+mod interrupt {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+    /// Indica si se recibió una interrupción.
+    pub fn requested() -> bool {
+        REQUESTED.load(Ordering::SeqCst)
+    }
+
+    #[cfg(unix)]
+    pub fn install() {
+        const SIGINT: i32 = 2;
+
+        extern "C" {
+            fn signal(signum: i32, handler: extern "C" fn(i32)) -> usize;
+            fn _exit(status: i32) -> !;
+        }
+
+        extern "C" fn handle(_signum: i32) {
+            if REQUESTED.swap(true, Ordering::SeqCst) {
+                // Segundo CTRL-C: terminar sin esperar a la línea
+                unsafe { _exit(130) }
+            }
+        }
+
+        unsafe {
+            signal(SIGINT, handle);
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn install() {
+        extern "system" {
+            fn SetConsoleCtrlHandler(handler: extern "system" fn(u32) -> i32, add: i32) -> i32;
+        }
+
+        extern "system" fn handle(_event: u32) -> i32 {
+            // Retornar 0 en el segundo CTRL-C deja actuar al manejador por defecto
+            i32::from(!REQUESTED.swap(true, Ordering::SeqCst))
+        }
+
+        unsafe {
+            SetConsoleCtrlHandler(handle, 1);
+        }
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    pub fn install() {}
 }
 
-/// Parsea los argumentos de línea de comandos y determina el algoritmo de planificación.
+/// Guarda una plantilla en el formato que indica la extensión del archivo.
+fn emit_template(template: &ScenarioTemplate, path: &PathBuf) -> Result<(), String> {
+    let format = ScenarioFormat::from_path(path).ok_or_else(|| {
+        format!(
+            "Formato de escenario no reconocido para '{}' (use .json, .yaml, .yml, .ron o .toml)",
+            path.display()
+        )
+    })?;
+    fs::write(path, template.render(format))
+        .map_err(|err| format!("No se pudo escribir '{}': {}", path.display(), err))
+}
+
+/// Parsea los argumentos de línea de comandos y determina el origen de la
+/// simulación (algoritmo o plantilla), el tipo de canal entre estaciones, el
+/// formato de los tiempos, el modelo de trabajo de las porciones, si se
+/// muestra el progreso y si solo se calculan las cotas inferiores sin
+/// ejecutar (`--dry-run`).
 /// 
 /// # Returns
 /// 
-/// `Ok(SchedulingAlgorithm)` si los argumentos son válidos,
-/// `Err(String)` con mensaje de error en caso contrario
-fn parse_args() -> Result<SchedulingAlgorithm, String> {
-    let args: Vec<String> = env::args().collect();
+/// `Ok((Source, ChannelBackend, ClockFormat, WorkMode, bool, bool))` si los
+/// argumentos son válidos, `Err(String)` con mensaje de error en caso
+/// contrario
+fn parse_args() -> Result<(Source, ChannelBackend, ClockFormat, WorkMode, bool, bool), String> {
+    let mut args: Vec<String> = env::args().collect();
+
+    let show_progress = args.iter().any(|arg| arg == "--progreso");
+    args.retain(|arg| arg != "--progreso");
+    let dry_run = args.iter().any(|arg| arg == "--dry-run");
+    args.retain(|arg| arg != "--dry-run");
+
+    let mut channel_backend = ChannelBackend::default();
+    if let Some(position) = args.iter().position(|arg| arg == "--canal") {
+        let value = args.get(position + 1)
+            .ok_or_else(|| "--canal requiere un tipo de canal".to_string())?;
+        channel_backend = value.parse()?;
+        args.drain(position..=position + 1);
+    }
+
+    let mut clock_format = ClockFormat::default();
+    if let Some(position) = args.iter().position(|arg| arg == "--reloj") {
+        let value = args.get(position + 1)
+            .ok_or_else(|| "--reloj requiere un formato de tiempo".to_string())?;
+        clock_format = value.parse()?;
+        args.drain(position..=position + 1);
+    }
+
+    let mut work_mode = WorkMode::Sleep;
+    if let Some(position) = args.iter().position(|arg| arg == "--trabajo") {
+        let value = args.get(position + 1)
+            .ok_or_else(|| "--trabajo requiere un modo de trabajo".to_string())?;
+        work_mode = value.parse()?;
+        args.drain(position..=position + 1);
+    }
+
+    let mut emit = None;
+    if let Some(position) = args.iter().position(|arg| arg == "--emit") {
+        let value = args.get(position + 1)
+            .ok_or_else(|| "--emit requiere un archivo de destino".to_string())?;
+        emit = Some(PathBuf::from(value));
+        args.drain(position..=position + 1);
+    }
+
+    if let Some(position) = args.iter().position(|arg| arg == "--template") {
+        let name = args.get(position + 1)
+            .ok_or_else(|| format!("--template requiere un nombre ({})", ScenarioTemplate::names()))?;
+        let template = ScenarioTemplate::find(name)
+            .ok_or_else(|| format!("Plantilla desconocida: '{}' (use {})", name, ScenarioTemplate::names()))?;
+        args.drain(position..=position + 1);
+        if args.len() > 1 {
+            return Err("--template no admite un algoritmo: la plantilla ya define el suyo".to_string());
+        }
+        return Ok((Source::Template { template, emit }, channel_backend, clock_format, work_mode, show_progress, dry_run));
+    }
+    if emit.is_some() {
+        return Err("--emit solo puede usarse con --template".to_string());
+    }
     
     if args.len() < 2 {
         return Err("Se requiere especificar un algoritmo de scheduling".to_string());
@@ -68,7 +320,7 @@ fn parse_args() -> Result<SchedulingAlgorithm, String> {
             if args.len() > 2 {
                 return Err("FCFS no acepta parámetros adicionales".to_string());
             }
-            Ok(SchedulingAlgorithm::fcfs())
+            Ok((Source::Algorithm(SchedulingAlgorithm::fcfs()), channel_backend, clock_format, work_mode, show_progress, dry_run))
         }
         "rr" | "round-robin" => {
             let quantum_ms = if args.len() > 2 {
@@ -82,7 +334,14 @@ fn parse_args() -> Result<SchedulingAlgorithm, String> {
                 return Err("El quantum debe ser mayor que 0".to_string());
             }
 
-            Ok(SchedulingAlgorithm::round_robin(std::time::Duration::from_millis(quantum_ms)))
+            Ok((
+                Source::Algorithm(SchedulingAlgorithm::round_robin(std::time::Duration::from_millis(quantum_ms))),
+                channel_backend,
+                clock_format,
+                work_mode,
+                show_progress,
+                dry_run,
+            ))
         }
         algorithm => Err(format!("Algoritmo desconocido: '{}'", algorithm)),
     }
@@ -93,7 +352,9 @@ fn print_usage() {
     println!("Simulador de Línea de Ensamblaje");
     println!();
     println!("USO:");
-    println!("    cargo run -- <algoritmo> [parámetros]");
+    println!("    cargo run -- <algoritmo> [parámetros] [--canal <tipo>] [--reloj <formato>] [--trabajo <modo>] [--progreso] [--dry-run]");
+    println!("    cargo run -- --template <nombre> [--emit <archivo>] [--canal <tipo>] [--reloj <formato>] [--trabajo <modo>] [--progreso] [--dry-run]");
+    println!("    cargo run -- repl");
     println!();
     println!("ALGORITMOS:");
     println!("    fcfs                    First-Come First-Served (no preemptivo)");
@@ -101,12 +362,69 @@ fn print_usage() {
     println!("                           quantum_ms: tiempo en milisegundos (default: {})", 
              config::DEFAULT_QUANTUM_MS);
     println!();
+    println!("PLANTILLAS:");
+    for template in &TEMPLATES {
+        println!("    {:<23} {}", template.name, template.description);
+    }
+    println!("    --emit <archivo>        Guarda la plantilla para editarla (.json, .yaml, .yml, .ron o .toml)");
+    println!();
+    println!("CANALES:");
+    println!("    mpsc                    Canales mpsc sin límite (default)");
+    println!("    acotado:<capacidad>     Canales mpsc::sync_channel acotados");
+    println!("    semaforo:<capacidad>    Buffer acotado con semáforos contadores");
+    println!("    crossbeam:<capacidad>   Canales acotados de crossbeam (feature crossbeam)");
+    println!();
+    println!("FORMATOS DE RELOJ:");
+    println!("    s                       Segundos con milisegundos, 1.234s (default)");
+    println!("    ms                      Milisegundos enteros, 1234ms");
+    println!("    hms                     Reloj relativo al inicio, 00:00:01.234");
+    println!("    ticks                   Ticks de simulación (1 tick = 1 ms), 1234t");
+    println!();
+    println!("MODOS DE TRABAJO:");
+    println!("    dormir                  Duerme el hilo durante la porción (default)");
+    println!("    espera                  Espera activa hasta que pasa el tiempo de reloj");
+    println!("    cpu                     Quema una cantidad fija de CPU (tasa calibrada)");
+    println!("    cpu:<iteraciones/ms>    Quema CPU con una tasa fija");
+    println!("    nada                    Porciones instantáneas");
+    println!();
     println!("EJEMPLOS:");
     println!("    cargo run -- fcfs");
     println!("    cargo run -- rr");
     println!("    cargo run -- rr 250");
+    println!("    cargo run -- rr 250 --canal semaforo:2");
+    println!("    cargo run -- rr 250 --reloj ms");
+    println!("    cargo run -- rr 250 --trabajo cpu  (competencia real por la CPU entre estaciones)");
+    println!("    cargo run -- fcfs --progreso  (progreso y tiempo restante estimado en stderr)");
+    println!("    cargo run -- rr 250 --dry-run  (cotas inferiores sin ejecutar la simulación)");
+    println!("    cargo run -- --template convoy");
+    println!("    cargo run -- --template burst --emit burst.yaml");
+    println!("    cargo run -- repl          (modo interactivo, 'help' para ver los comandos)");
     println!();
     println!("DESCRIPCIÓN:");
     println!("    Simula una línea de ensamblaje con 3 estaciones (Corte, Ensamblaje, Empaque)");
     println!("    procesando 10 productos con diferentes algoritmos de scheduling.");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assembly_line_simulator::Scenario;
+
+    #[test]
+    fn test_emit_template_in_every_format() {
+        let directory = std::env::temp_dir().join(format!("simulador-plantillas-{}", process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let template = ScenarioTemplate::find("convoy").unwrap();
+
+        for name in ["convoy.json", "convoy.yaml", "convoy.ron", "convoy.toml"] {
+            let path = directory.join(name);
+            emit_template(template, &path).unwrap_or_else(|error| panic!("{}: {}", name, error));
+            let scenario = Scenario::from_file(&path).unwrap_or_else(|error| panic!("{}: {}", name, error));
+            assert_eq!(scenario.arrivals, template.scenario().arrivals);
+            assert_eq!(scenario.stations, template.scenario().stations);
+        }
+        let error = emit_template(template, &directory.join("convoy.ini")).unwrap_err();
+        assert!(error.contains("no reconocido"), "{}", error);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
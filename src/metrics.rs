@@ -3,11 +3,24 @@
 //! Este módulo se encarga de calcular, almacenar y generar reportes de
 //! las métricas de rendimiento de la simulación de línea de ensamblaje.
 
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Write};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-use crate::product::Product;
-use crate::station::StationConfig;
+use crate::analysis::OutlierDetector;
+use crate::arrival::ArrivalPeriod;
+use crate::clock::{ClockFormat, ClockFormatScope, Instant, SimTime};
+use crate::jockey::JockeyReport;
+use crate::json::Json;
+use crate::product::{lock_recover, Product, ProductLoss, TimelineEvent, TimelineSpan};
+use crate::provenance::RunMetadata;
+use crate::sla::SlaResult;
+use crate::resource::ResourceUnitReport;
+use crate::sequencing::ReleasePlan;
+use crate::takt::TaktReport;
+use crate::station::{StationConfig, StationReport};
 
 /// Métricas individuales de un producto en la simulación.
 /// 
@@ -17,14 +30,88 @@ use crate::station::StationConfig;
 pub struct ProductMetrics {
     /// ID del producto
     pub product_id: usize,
+    /// Clase del producto, si fue asignada
+    pub class: Option<String>,
+    /// Metadatos asignados por el usuario al producto
+    pub metadata: HashMap<String, String>,
     /// Tiempo de llegada simulado relativo al inicio
     pub arrival_time: Duration,
     /// Tiempo total de espera en todas las estaciones
     pub total_wait_time: Duration,
+    /// Tiempo total bloqueado esperando dependencias de precedencia
+    pub dependency_wait_time: Duration,
+    /// Tiempo total que las estaciones esperaron recursos compartidos para el producto
+    pub resource_wait_time: Duration,
     /// Tiempo de turnaround (desde llegada hasta finalización)
     pub turnaround_time: Duration,
-    /// Tiempos de entrada y salida por estación
+    /// Retraso que los temporizadores imprecisos agregaron a las porciones
+    /// del producto (ver [`TimerJitter`](crate::TimerJitter)); forma parte
+    /// del turnaround pero no de la espera
+    pub timer_drift: Duration,
+    /// Demanda de servicio: suma de sus tiempos de procesamiento en la ruta
+    pub service_time: Duration,
+    /// Slowdown: turnaround dividido entre la demanda de servicio (1.0 si
+    /// nunca esperó; 1.0 también para productos sin demanda)
+    pub slowdown: f64,
+    /// Fecha de entrega relativa al inicio, si el producto tiene una
+    pub due_date: Option<Duration>,
+    /// Tardanza: cuánto se pasó de su fecha de entrega (cero si llegó a tiempo)
+    pub tardiness: Duration,
+    /// Tiempos de entrada y salida por estación; si la ruta visita una
+    /// estación varias veces, van de la primera entrada a la última salida
     pub station_times: Vec<(Duration, Duration)>, // (entrada, salida) relativas al inicio
+    /// Tiempos de cada visita de la ruta, en orden de visita
+    pub visit_times: Vec<(usize, Duration, Duration)>, // (estación, entrada, salida)
+    /// Recursos compartidos usados en cada visita, alineados con `visit_times`
+    pub visit_resources: Vec<Vec<String>>,
+    /// Veces que el producto cambió a una cola más corta (ver [`crate::jockey`])
+    pub jockeys: usize,
+    /// Porciones de procesamiento que recibió el producto, en orden
+    pub slices: Vec<(usize, Duration, Duration)>, // (estación, inicio, fin)
+    /// Unidades de recursos compartidos (recurso, índice de la unidad) que
+    /// atendieron cada porción, alineadas con `slices`
+    pub slice_units: Vec<Vec<(String, usize)>>,
+    /// Número de porciones (quanta) consumidas en cada estación
+    pub slice_counts: Vec<usize>,
+    /// Línea de tiempo del producto: esperas, porciones de procesamiento,
+    /// expropiaciones y traslados (ver [`Product::timeline`])
+    pub timeline: Vec<TimelineSpan>,
+    /// Época de configuración vigente cuando llegó el producto: 0 para la
+    /// configuración inicial, `n` después de la n-ésima reconfiguración
+    /// (ver [`Reconfiguration`](crate::Reconfiguration))
+    pub config_epoch: usize,
+    /// Pérdida del producto si desistió o abandonó una cola por impaciencia,
+    /// o si fue rechazado en una inspección
+    pub loss: Option<ProductLoss>,
+    /// ID del producto desechado al que este producto reemplaza
+    pub replaces: Option<usize>,
+    /// ID del producto que reemplazó a este tras desecharlo
+    pub replaced_by: Option<usize>,
+    /// Dependencias de precedencia del producto: (ID del producto, estación
+    /// que debía completar)
+    pub depends_on: Vec<(usize, usize)>,
+}
+
+impl ProductMetrics {
+    /// Suma la espera en cola del producto en cada estación, a partir de su
+    /// línea de tiempo.
+    /// 
+    /// Incluye el tiempo en el canal de entrada y los bloqueos por
+    /// dependencias o recursos (ver [`TimelineEvent::Queued`]); con rutas
+    /// reentrantes se suman todas las visitas a la estación.
+    /// 
+    /// # Returns
+    /// 
+    /// La espera por índice de estación, hasta la última estación en la que
+    /// el producto esperó o fue atendido
+    pub fn wait_by_station(&self) -> Vec<Duration> {
+        let station_count = self.timeline.iter().map(|span| span.station + 1).max().unwrap_or(0);
+        let mut waits = vec![Duration::ZERO; station_count];
+        for span in self.timeline.iter().filter(|span| span.event == TimelineEvent::Queued) {
+            waits[span.station] += span.duration();
+        }
+        waits
+    }
 }
 
 /// Métricas agregadas de toda la simulación.
@@ -37,26 +124,521 @@ pub struct SimulationMetrics {
     pub products: Vec<ProductMetrics>,
     /// Tiempo promedio de espera
     pub average_wait_time: Duration,
+    /// Tiempo promedio bloqueado por dependencias de precedencia
+    pub average_dependency_wait_time: Duration,
+    /// Tiempo promedio esperando recursos compartidos
+    pub average_resource_wait_time: Duration,
     /// Tiempo promedio de turnaround
     pub average_turnaround_time: Duration,
+    /// Slowdown promedio de los productos completados
+    pub average_slowdown: f64,
+    /// Slowdown máximo de los productos completados
+    pub max_slowdown: f64,
     /// Orden de finalización de los productos
     pub completion_order: Vec<usize>,
+    /// Distancia de Kendall tau normalizada entre el orden de llegada y el
+    /// de finalización: 0.0 si terminaron en el orden en que llegaron, 1.0
+    /// si en el orden inverso
+    pub completion_order_divergence: f64,
     /// Duración total de la simulación
     pub total_simulation_time: Duration,
     /// Throughput (productos por segundo)
     pub throughput: f64,
+    /// Índice de equidad de Jain sobre el turnaround normalizado por la
+    /// demanda de servicio (1.0 = todos los productos tratados por igual)
+    pub fairness_index: f64,
+    /// Tardanza total acumulada de todos los productos
+    pub total_tardiness: Duration,
+    /// Tardanza promedio entre los productos con fecha de entrega
+    pub average_tardiness: Duration,
+    /// Máximo retraso (lateness) en segundos; negativo si todos llegaron antes
+    /// de su fecha. `None` si ningún producto tiene fecha de entrega
+    pub max_lateness: Option<f64>,
+    /// Número de productos que terminaron después de su fecha de entrega
+    pub tardy_jobs: usize,
+    /// Estadísticas de operación reportadas por cada estación
+    pub station_reports: Vec<StationReport>,
+    /// Métricas parciales de los productos que estaban en la línea al
+    /// finalizar la simulación sin completarla
+    pub incomplete_products: Vec<ProductMetrics>,
+    /// Métricas parciales de los productos impacientes que salieron de la
+    /// línea sin completarla (su turnaround es el tiempo hasta la pérdida)
+    pub lost_products: Vec<ProductMetrics>,
+    /// Estadísticas de estado estable, si se configuró un período de calentamiento
+    pub steady_state: Option<SteadyStateMetrics>,
+    /// Primitiva de sincronización usada entre estaciones (vacía si no se conoce)
+    pub synchronization: String,
+    /// Estadísticas de cada período de llegadas (vacío si no hay períodos)
+    pub periods: Vec<PeriodMetrics>,
+    /// Estadísticas de cada época de configuración, segmentadas por las
+    /// reconfiguraciones en caliente (vacío si no hubo reconfiguraciones)
+    pub config_epochs: Vec<PeriodMetrics>,
+    /// Resultado de cada SLA declarado en la simulación, en orden
+    pub sla_results: Vec<SlaResult>,
+    /// Comparación del ritmo de salida con el takt time, si se configuró
+    pub takt: Option<TaktReport>,
+    /// Carga de cada unidad de los recursos compartidos (vacío si la línea
+    /// no tiene recursos)
+    pub resource_units: Vec<ResourceUnitReport>,
+    /// Cambios de cola y su efecto en la espera, si se configuró una
+    /// política de cambios de cola
+    pub jockeying: Option<JockeyReport>,
+    /// Plan del planificador global, si el orden de llegada lo usa
+    pub release_plan: Option<ReleasePlan>,
+    /// Intervalos entre salidas consecutivas de la última estación, en
+    /// orden de finalización
+    pub inter_departure_times: Vec<Duration>,
+    /// Variabilidad de las llegadas a la línea
+    pub arrival_flow: FlowStatistics,
+    /// Variabilidad de las salidas de la línea
+    pub departure_flow: FlowStatistics,
+    /// Estadísticas de cada clase de producto (vacío si ningún producto
+    /// tiene clase)
+    pub by_class: HashMap<String, ClassMetrics>,
+    /// Versión, commit, fecha, equipo y sistema operativo de la ejecución
+    pub run_metadata: RunMetadata,
+    /// Longitud de tick de la simulación (ver
+    /// [`Simulation::set_tick_length`](crate::Simulation::set_tick_length))
+    pub tick_length: Duration,
+}
+
+impl SimulationMetrics {
+    /// Expresa un tiempo de estas métricas en ticks de la simulación,
+    /// redondeando al tick más cercano.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{SchedulingAlgorithm, SimTime, Simulation, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+    /// simulation.set_tick_length(Duration::from_millis(10));
+    /// let metrics = simulation.run().expect("La simulación falló");
+    /// assert_eq!(metrics.to_ticks(metrics.products[0].service_time), SimTime::from_ticks(2));
+    /// ```
+    pub fn to_ticks(&self, duration: Duration) -> SimTime {
+        SimTime::from_duration(duration, self.tick_length)
+    }
+
+    /// Convierte un tiempo en ticks de la simulación en una duración.
+    pub fn from_ticks(&self, time: SimTime) -> Duration {
+        time.to_duration(self.tick_length)
+    }
+
+
+    /// Indica si se cumplieron todos los SLA declarados.
+    pub fn slas_met(&self) -> bool {
+        self.sla_results.iter().all(|result| result.passed)
+    }
+
+    /// Compara la variabilidad de las salidas con la de las llegadas.
+    /// 
+    /// Un valor menor que 1 indica que la línea suavizó el flujo (las
+    /// salidas son más regulares que las llegadas); mayor que 1, que lo
+    /// volvió más irregular.
+    /// 
+    /// # Returns
+    /// 
+    /// El cociente entre los coeficientes de variación de salidas y
+    /// llegadas, o `None` si las llegadas son perfectamente regulares
+    pub fn flow_variability_ratio(&self) -> Option<f64> {
+        let arrivals = self.arrival_flow.coefficient_of_variation;
+        (arrivals > 0.0).then(|| self.departure_flow.coefficient_of_variation / arrivals)
+    }
+}
+
+/// Estadísticas de los intervalos entre eventos consecutivos de un flujo
+/// (llegadas a la línea o salidas de la última estación).
+/// 
+/// El coeficiente de variación es 0 para un flujo perfectamente regular y
+/// cercano a 1 para llegadas de Poisson; valores mayores indican ráfagas.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FlowStatistics {
+    /// Número de intervalos considerados (eventos menos uno)
+    pub intervals: usize,
+    /// Intervalo promedio entre eventos
+    pub mean_interval: Duration,
+    /// Desviación estándar de los intervalos
+    pub std_dev: Duration,
+    /// Coeficiente de variación: desviación estándar sobre la media
+    /// (0 si la media es cero)
+    pub coefficient_of_variation: f64,
+}
+
+impl FlowStatistics {
+    /// Calcula las estadísticas de los intervalos entre eventos.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `intervals` - Tiempos entre eventos consecutivos
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::FlowStatistics;
+    /// 
+    /// let regular = FlowStatistics::from_intervals(&[Duration::from_millis(100); 4]);
+    /// assert_eq!(regular.coefficient_of_variation, 0.0);
+    /// 
+    /// let bursty = FlowStatistics::from_intervals(&[Duration::ZERO, Duration::ZERO, Duration::from_millis(300)]);
+    /// assert_eq!(bursty.mean_interval, Duration::from_millis(100));
+    /// assert!((bursty.coefficient_of_variation - 2f64.sqrt()).abs() < 1e-9);
+    /// ```
+    pub fn from_intervals(intervals: &[Duration]) -> Self {
+        if intervals.is_empty() {
+            return Self::default();
+        }
+        let count = intervals.len() as f64;
+        let mean = intervals.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = intervals
+            .iter()
+            .map(|interval| (interval.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        Self {
+            intervals: intervals.len(),
+            mean_interval: Duration::from_secs_f64(mean),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+            coefficient_of_variation: if mean > 0.0 { variance.sqrt() / mean } else { 0.0 },
+        }
+    }
+
+    /// Calcula los intervalos entre eventos consecutivos.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `times` - Momentos de los eventos, en cualquier orden
+    /// 
+    /// # Returns
+    /// 
+    /// Las diferencias entre eventos consecutivos una vez ordenados
+    pub fn intervals_between(times: &[Duration]) -> Vec<Duration> {
+        let mut times = times.to_vec();
+        times.sort();
+        times.windows(2).map(|pair| pair[1] - pair[0]).collect()
+    }
+}
+
+/// Estadísticas de un período de llegadas (turno, hora pico, etc.).
+/// 
+/// Los tiempos de espera y turnaround corresponden a los productos que
+/// llegaron durante el período, aunque hayan terminado después; el
+/// throughput cuenta las finalizaciones ocurridas dentro del período. Así
+/// se observa cómo la línea se recupera después de un pico de carga.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeriodMetrics {
+    /// Nombre del período
+    pub name: String,
+    /// Inicio del período
+    pub start: Duration,
+    /// Fin del período
+    pub end: Duration,
+    /// Productos completados que llegaron durante el período
+    pub arrivals: usize,
+    /// Productos que terminaron la línea durante el período
+    pub completed: usize,
+    /// Tiempo promedio de espera de los productos que llegaron en el período
+    pub average_wait_time: Duration,
+    /// Tiempo promedio de turnaround de los productos que llegaron en el período
+    pub average_turnaround_time: Duration,
+    /// Productos completados por segundo durante el período
+    pub throughput: f64,
+}
+
+/// Estadísticas agregadas de los productos de una clase.
+/// 
+/// La clase de un producto es la de su especificación o, si no tiene una,
+/// el nombre de su tipo de producto (ver [`ProductSpec::class`](crate::ProductSpec::class)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassMetrics {
+    /// Nombre de la clase
+    pub class: String,
+    /// Productos de la clase que completaron la línea
+    pub completed: usize,
+    /// Productos de la clase que quedaron en la línea al finalizar
+    pub incomplete: usize,
+    /// Productos de la clase que salieron de la línea sin completarla
+    pub lost: usize,
+    /// Tiempo promedio de espera de los productos completados de la clase
+    pub average_wait_time: Duration,
+    /// Tiempo promedio de turnaround de los productos completados de la clase
+    pub average_turnaround_time: Duration,
+    /// Slowdown promedio de los productos completados de la clase
+    pub average_slowdown: f64,
+    /// Slowdown máximo entre los productos completados de la clase
+    pub max_slowdown: f64,
+    /// Productos completados de la clase por segundo
+    pub throughput: f64,
+}
+
+/// Estadísticas de estado estable de la simulación.
+/// 
+/// Excluyen los productos que llegaron durante el período de calentamiento,
+/// cuando la línea aún estaba vacía y las colas no reflejaban la carga normal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SteadyStateMetrics {
+    /// Duración del período de calentamiento descartado
+    pub warmup: Duration,
+    /// Número de productos completados que llegaron después del calentamiento
+    pub products_considered: usize,
+    /// Tiempo promedio de espera de los productos considerados
+    pub average_wait_time: Duration,
+    /// Tiempo promedio de turnaround de los productos considerados
+    pub average_turnaround_time: Duration,
+    /// Productos completados por segundo después del calentamiento
+    pub throughput: f64,
+}
+
+/// Matriz de esperas producto × estación.
+/// 
+/// Cada fila es un producto completado (en orden de ID) y cada columna una
+/// estación de la línea; la celda es la espera en cola del producto en esa
+/// estación (ver [`ProductMetrics::wait_by_station`]). Se exporta en CSV o
+/// JSON para visualizarla como mapa de calor.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{MetricsCalculator, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![
+///     StationConfig::new("Corte", Duration::from_millis(20)),
+///     StationConfig::new("Empaque", Duration::from_millis(5)),
+/// ];
+/// let mut simulation = Simulation::with_config(stations.clone(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// let metrics = simulation.run().expect("La simulación falló");
+/// 
+/// let matrix = MetricsCalculator::wait_matrix(&metrics, &stations);
+/// assert_eq!(matrix.product_ids, vec![1, 2, 3]);
+/// // La espera se concentra en la primera estación, la más lenta
+/// let columns = matrix.column_totals();
+/// assert!(columns[0] > columns[1]);
+/// assert!(matrix.to_csv().starts_with("Product,Corte,Empaque\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaitMatrix {
+    /// Nombres de las estaciones (columnas)
+    pub stations: Vec<String>,
+    /// IDs de los productos (filas)
+    pub product_ids: Vec<usize>,
+    /// Espera de cada producto en cada estación, `waits[fila][columna]`
+    pub waits: Vec<Vec<Duration>>,
+}
+
+impl WaitMatrix {
+    /// Obtiene la espera total de cada producto (marginal de filas).
+    pub fn row_totals(&self) -> Vec<Duration> {
+        self.waits.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// Obtiene la espera total en cada estación (marginal de columnas).
+    pub fn column_totals(&self) -> Vec<Duration> {
+        (0..self.stations.len())
+            .map(|column| self.waits.iter().map(|row| row[column]).sum())
+            .collect()
+    }
+
+    /// Obtiene la espera total de la matriz.
+    pub fn total(&self) -> Duration {
+        self.row_totals().into_iter().sum()
+    }
+
+    /// Exporta la matriz en CSV: una fila por producto y una columna por
+    /// estación, con los tiempos en el formato de reloj vigente
+    /// ([`ClockFormat::current`]) sin unidad.
+    pub fn to_csv(&self) -> String {
+        let time = |duration: Duration| ClockFormat::current().format_value(duration);
+        let mut csv = String::from("Product");
+        for station in &self.stations {
+            csv.push(',');
+            csv.push_str(&MetricsCalculator::csv_field(station));
+        }
+        csv.push('\n');
+        for (id, row) in self.product_ids.iter().zip(&self.waits) {
+            csv.push_str(&id.to_string());
+            for wait in row {
+                csv.push(',');
+                csv.push_str(&time(*wait));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Exporta la matriz en JSON, con los tiempos en segundos y los
+    /// marginales de filas y columnas.
+    pub fn to_json(&self) -> String {
+        let seconds = |durations: &[Duration]| {
+            durations
+                .iter()
+                .map(|duration| format!("{:.3}", duration.as_secs_f64()))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let stations: Vec<String> = self.stations.iter().map(|name| MetricsCalculator::json_string(name)).collect();
+        let products: Vec<String> = self.product_ids.iter().map(usize::to_string).collect();
+        let rows: Vec<String> = self.waits.iter().map(|row| format!("[{}]", seconds(row))).collect();
+        format!(
+            "{{\"stations\":[{}],\"products\":[{}],\"waits\":[{}],\"row_totals\":[{}],\"column_totals\":[{}]}}",
+            stations.join(","),
+            products.join(","),
+            rows.join(","),
+            seconds(&self.row_totals()),
+            seconds(&self.column_totals())
+        )
+    }
+}
+
+/// Métrica por producto cuya distribución se puede graficar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DistributionMetric {
+    /// Tiempo total de espera
+    WaitTime,
+    /// Tiempo de turnaround
+    Turnaround,
+}
+
+impl DistributionMetric {
+    /// Obtiene el valor de la métrica para un producto.
+    pub fn value(&self, product: &ProductMetrics) -> Duration {
+        match self {
+            Self::WaitTime => product.total_wait_time,
+            Self::Turnaround => product.turnaround_time,
+        }
+    }
+}
+
+impl fmt::Display for DistributionMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::WaitTime => "Tiempo de espera",
+            Self::Turnaround => "Turnaround",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Histograma de una métrica por producto.
+/// 
+/// Los intervalos tienen el mismo ancho y empiezan en cero: el intervalo
+/// `i` cuenta los productos con valor en `[i * ancho, (i + 1) * ancho)`.
+/// Se incluyen los intervalos vacíos intermedios para que la forma de la
+/// distribución se vea tal cual. Al mostrarse con `Display` se dibuja como
+/// un histograma de texto con una barra por intervalo.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Histogram {
+    /// Métrica graficada
+    pub metric: DistributionMetric,
+    /// Ancho de cada intervalo
+    pub bucket_width: Duration,
+    /// Productos en cada intervalo, desde cero hasta el valor máximo
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Ancho máximo de las barras, en caracteres.
+    const BAR_WIDTH: usize = 40;
+
+    /// Obtiene el inicio de un intervalo.
+    pub fn bucket_start(&self, bucket: usize) -> Duration {
+        self.bucket_width * bucket as u32
+    }
+
+    /// Obtiene el número total de productos contados.
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} (intervalos de {}):",
+            self.metric,
+            MetricsCalculator::format_duration(self.bucket_width)
+        )?;
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+        for (bucket, &count) in self.counts.iter().enumerate() {
+            // Las barras se escalan al intervalo más poblado; un intervalo
+            // no vacío siempre muestra al menos un carácter
+            let bar = if max == 0 { 0 } else { (count * Self::BAR_WIDTH).div_ceil(max) };
+            writeln!(
+                f,
+                "  {:>9} - {:>9} | {:<width$} {}",
+                MetricsCalculator::format_duration(self.bucket_start(bucket)),
+                MetricsCalculator::format_duration(self.bucket_start(bucket + 1)),
+                "#".repeat(bar),
+                count,
+                width = Self::BAR_WIDTH
+            )?;
+        }
+        Ok(())
+    }
 }
 
 /// Calculadora de métricas para la simulación.
 /// 
 /// Proporciona métodos para calcular métricas individuales y agregadas,
 /// así como para generar reportes formateados de los resultados.
-pub struct MetricsCalculator;
+/// 
+/// Los reportes de texto y CSV usan el formato de reloj del calculador
+/// ([`MetricsCalculator::with_clock_format`]), o el global si no tiene uno,
+/// y las métricas que calcula registran su longitud de tick
+/// ([`MetricsCalculator::with_tick_length`]).
+#[derive(Clone, Copy, Debug)]
+pub struct MetricsCalculator {
+    clock_format: Option<ClockFormat>,
+    tick_length: Duration,
+}
 
 impl MetricsCalculator {
+    /// Número aproximado de intervalos de los histogramas del reporte.
+    const REPORT_BUCKETS: u128 = 10;
+
     /// Crea una nueva instancia del calculador de métricas.
     pub fn new() -> Self {
-        Self
+        Self { clock_format: None, tick_length: SimTime::DEFAULT_TICK_LENGTH }
+    }
+
+    /// Fija el formato de los tiempos de los reportes de este calculador,
+    /// en lugar del formato global.
+    pub fn with_clock_format(mut self, format: ClockFormat) -> Self {
+        self.clock_format = Some(format);
+        self
+    }
+
+    /// Retorna el formato de los tiempos de los reportes: el propio si se
+    /// fijó uno, o el global.
+    pub fn clock_format(&self) -> ClockFormat {
+        self.clock_format.unwrap_or_else(ClockFormat::global)
+    }
+
+    /// Fija la longitud de tick que registran las métricas calculadas (ver
+    /// [`SimulationMetrics::to_ticks`]).
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `tick` es cero
+    pub fn with_tick_length(mut self, tick: Duration) -> Self {
+        assert!(!tick.is_zero(), "La longitud del tick debe ser mayor a cero");
+        self.tick_length = tick;
+        self
+    }
+
+    /// Retorna la longitud de tick de las métricas calculadas.
+    pub fn tick_length(&self) -> Duration {
+        self.tick_length
+    }
+
+    /// Hace del formato de reloj y de la longitud de tick del calculador los
+    /// vigentes en el hilo actual mientras viva el guardián.
+    pub(crate) fn enter_clock(&self) -> ClockFormatScope {
+        self.clock_format().enter(self.tick_length)
     }
 
     /// Calcula las métricas para un producto individual.
@@ -65,7 +647,6 @@ impl MetricsCalculator {
     /// 
     /// * `product` - Referencia al producto
     /// * `station_configs` - Configuraciones de las estaciones
-    /// * `start_time` - Momento de inicio de la simulación
     /// 
     /// # Returns
     /// 
@@ -75,41 +656,142 @@ impl MetricsCalculator {
         &self,
         product: &Arc<Product>,
         station_configs: &[StationConfig],
-        start_time: Instant,
     ) -> Option<ProductMetrics> {
-        // Verificar que el producto esté completado
-        if !product.is_completed() {
+        // Verificar que el producto esté completado (un producto rechazado
+        // termina su ruta, pero sale de la línea sin completarla)
+        if !product.is_completed() || product.loss().is_some() {
             return None;
         }
 
-        let arrival_instant = product.get_arrival_instant()?;
-        let arrival_time = arrival_instant.duration_since(start_time);
-        let total_wait_time = product.total_wait_time();
-        let turnaround_time = product.turnaround_time(start_time)?;
-
-        // Calcular tiempos por estación
-        let mut station_times = Vec::new();
-        for (index, _config) in station_configs.iter().enumerate() {
-            let state = product.station_state(index).lock()
-                .expect("No se pudo obtener lock del estado de estación");
-            
-            let entry_time = state.first_entry
-                .map(|t| t.duration_since(start_time))
-                .unwrap_or_default();
-            
-            let exit_time = state.final_exit
-                .map(|t| t.duration_since(start_time))
-                .unwrap_or_default();
-            
-            station_times.push((entry_time, exit_time));
+        let turnaround_time = product.turnaround_time()?;
+        self.build_product_metrics(product, station_configs, turnaround_time)
+    }
+
+    /// Calcula las métricas parciales de un producto que no completó la línea.
+    /// 
+    /// El turnaround reportado es el tiempo que el producto llevaba en el
+    /// sistema al finalizar la simulación, o hasta su pérdida si desistió,
+    /// abandonó una cola o fue rechazado; las estaciones que no alcanzó a completar tienen
+    /// tiempo de salida cero.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product` - Referencia al producto
+    /// * `station_configs` - Configuraciones de las estaciones
+    /// * `end_time` - Momento de finalización relativo al inicio de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// `ProductMetrics` parciales, o `None` si el producto fue completado
+    /// o nunca llegó a la línea
+    pub fn calculate_incomplete_product_metrics(
+        &self,
+        product: &Arc<Product>,
+        station_configs: &[StationConfig],
+        end_time: Duration,
+    ) -> Option<ProductMetrics> {
+        if product.is_completed() && product.loss().is_none() {
+            return None;
         }
 
+        let arrival_time = product.get_arrival_time()?;
+        let end_time = product.loss().map_or(end_time, |loss| loss.at);
+        let time_in_system = end_time.saturating_sub(arrival_time);
+        self.build_product_metrics(product, station_configs, time_in_system)
+    }
+
+    /// Construye las métricas de un producto dado su turnaround.
+    fn build_product_metrics(
+        &self,
+        product: &Arc<Product>,
+        station_configs: &[StationConfig],
+        turnaround_time: Duration,
+    ) -> Option<ProductMetrics> {
+        let arrival_time = product.get_arrival_time()?;
+        let total_wait_time = product.total_wait_time();
+        let dependency_wait_time = product.total_dependency_wait();
+        let resource_wait_time = product.total_resource_wait();
+        let timer_drift = product.total_timer_drift();
+        let completion_time = arrival_time + turnaround_time;
+        let service_time = product.total_work();
+        let tardiness = product.due_date
+            .map(|due| completion_time.saturating_sub(due))
+            .unwrap_or_default();
+
+        // Calcular tiempos por visita
+        let route = product.route();
+        let visit_times: Vec<(usize, Duration, Duration)> = route
+            .iter()
+            .enumerate()
+            .map(|(visit, &index)| {
+                let state = lock_recover(product.visit_state(visit));
+                
+                (index, state.first_entry.unwrap_or_default(), state.final_exit.unwrap_or_default())
+            })
+            .collect();
+
+        let visit_resources = (0..route.len())
+            .map(|visit| lock_recover(product.visit_state(visit)).resources.clone())
+            .collect();
+
+        // Resumir por estación: de la primera entrada a la última salida
+        let station_times = (0..station_configs.len())
+            .map(|index| {
+                let mut visits = visit_times.iter().filter(|(station, _, _)| *station == index);
+                let first = visits.next();
+                match (first, visits.next_back().or(first)) {
+                    (Some(&(_, entry, _)), Some(&(_, _, exit))) => (entry, exit),
+                    _ => (Duration::ZERO, Duration::ZERO),
+                }
+            })
+            .collect();
+
+        // Porciones de procesamiento de cada visita
+        let slices: Vec<(usize, Duration, Duration)> = route
+            .iter()
+            .enumerate()
+            .flat_map(|(visit, &index)| {
+                lock_recover(product.visit_state(visit))
+                    .slices
+                    .iter()
+                    .map(|&(start, end)| (index, start, end))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let slice_units = (0..route.len())
+            .flat_map(|visit| lock_recover(product.visit_state(visit)).slice_units.clone())
+            .collect();
+        let slice_counts = (0..station_configs.len())
+            .map(|index| slices.iter().filter(|(station, _, _)| *station == index).count())
+            .collect();
+
         Some(ProductMetrics {
             product_id: product.id,
+            class: product.class.clone(),
+            metadata: product.metadata.clone(),
             arrival_time,
             total_wait_time,
+            dependency_wait_time,
+            resource_wait_time,
             turnaround_time,
+            timer_drift,
+            service_time,
+            slowdown: Self::slowdown(turnaround_time, service_time),
+            due_date: product.due_date,
+            tardiness,
             station_times,
+            visit_times,
+            visit_resources,
+            jockeys: product.jockeys(),
+            slices,
+            slice_units,
+            slice_counts,
+            timeline: product.timeline(),
+            config_epoch: 0,
+            loss: product.loss(),
+            replaces: product.replaces(),
+            replaced_by: product.replaced_by(),
+            depends_on: product.dependencies(),
         })
     }
 
@@ -134,18 +816,74 @@ impl MetricsCalculator {
         end_time: Instant,
         completion_order: Vec<usize>,
     ) -> SimulationMetrics {
-        let mut product_metrics = Vec::new();
+        self.aggregate_simulation_metrics(
+            products,
+            HashMap::new(),
+            station_configs,
+            start_time,
+            end_time,
+            completion_order,
+        )
+    }
+
+    /// Agrega las métricas de la simulación a partir de las métricas de los
+    /// productos que el colector ya finalizó al salir de la línea.
+    /// 
+    /// Solo se calculan (bloqueando el estado de cada visita) los productos
+    /// que no están en `finished`: los incompletos, los perdidos y los
+    /// completados que el colector no alcanzó a recibir. Los productos
+    /// conservan el orden de `products`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `products` - Todos los productos de la ejecución
+    /// * `finished` - Métricas de los productos completados, por id
+    /// * `station_configs` - Configuraciones de las estaciones
+    /// * `start_time` - Momento de inicio de la simulación
+    /// * `end_time` - Momento de finalización de la simulación
+    /// * `completion_order` - Orden en que se completaron los productos
+    pub(crate) fn aggregate_simulation_metrics(
+        &self,
+        products: &[Arc<Product>],
+        mut finished: HashMap<usize, ProductMetrics>,
+        station_configs: &[StationConfig],
+        start_time: Instant,
+        end_time: Instant,
+        completion_order: Vec<usize>,
+    ) -> SimulationMetrics {
+        let mut product_metrics = Vec::with_capacity(finished.len());
         let mut total_wait = Duration::ZERO;
+        let mut total_dependency_wait = Duration::ZERO;
+        let mut total_resource_wait = Duration::ZERO;
         let mut total_turnaround = Duration::ZERO;
         let mut completed_count = 0;
 
-        // Calcular métricas individuales
+        let mut incomplete_products = Vec::new();
+        let mut lost_products = Vec::new();
+        let total_simulation_time = end_time.saturating_duration_since(start_time);
+
+        // Métricas individuales: las finalizadas por el colector o, si no
+        // las hay, calculadas ahora
         for product in products {
-            if let Some(metrics) = self.calculate_product_metrics(product, station_configs, start_time) {
+            let completed = finished
+                .remove(&product.id)
+                .or_else(|| self.calculate_product_metrics(product, station_configs));
+            if let Some(metrics) = completed {
                 total_wait += metrics.total_wait_time;
+                total_dependency_wait += metrics.dependency_wait_time;
+                total_resource_wait += metrics.resource_wait_time;
                 total_turnaround += metrics.turnaround_time;
                 completed_count += 1;
                 product_metrics.push(metrics);
+            } else if let Some(metrics) = self.calculate_incomplete_product_metrics(
+                product,
+                station_configs,
+                total_simulation_time,
+            ) {
+                match metrics.loss {
+                    Some(_) => lost_products.push(metrics),
+                    None => incomplete_products.push(metrics),
+                }
             }
         }
 
@@ -156,6 +894,18 @@ impl MetricsCalculator {
             Duration::ZERO
         };
 
+        let average_dependency_wait_time = if completed_count > 0 {
+            total_dependency_wait / completed_count as u32
+        } else {
+            Duration::ZERO
+        };
+
+        let average_resource_wait_time = if completed_count > 0 {
+            total_resource_wait / completed_count as u32
+        } else {
+            Duration::ZERO
+        };
+
         let average_turnaround_time = if completed_count > 0 {
             total_turnaround / completed_count as u32
         } else {
@@ -163,84 +913,633 @@ impl MetricsCalculator {
         };
 
         // Calcular throughput
-        let total_simulation_time = end_time.duration_since(start_time);
         let throughput = if total_simulation_time.as_secs_f64() > 0.0 {
             completed_count as f64 / total_simulation_time.as_secs_f64()
         } else {
             0.0
         };
 
+        // Calcular métricas de cumplimiento de fechas de entrega
+        let with_due_date: Vec<&ProductMetrics> = product_metrics
+            .iter()
+            .filter(|metrics| metrics.due_date.is_some())
+            .collect();
+        let total_tardiness: Duration = with_due_date.iter().map(|m| m.tardiness).sum();
+        let average_tardiness = if with_due_date.is_empty() {
+            Duration::ZERO
+        } else {
+            total_tardiness / with_due_date.len() as u32
+        };
+        let max_lateness = with_due_date
+            .iter()
+            .map(|m| Self::lateness(m))
+            .reduce(f64::max);
+        let tardy_jobs = with_due_date
+            .iter()
+            .filter(|m| !m.tardiness.is_zero())
+            .count();
+
+        let fairness_index = Self::fairness_index(&product_metrics);
+        let average_slowdown = if completed_count > 0 {
+            product_metrics.iter().map(|m| m.slowdown).sum::<f64>() / completed_count as f64
+        } else {
+            0.0
+        };
+        let max_slowdown = product_metrics.iter().map(|m| m.slowdown).fold(0.0, f64::max);
+        let completion_order_divergence = Self::completion_order_divergence(&product_metrics, &completion_order);
+
+        // Variabilidad del flujo: las llegadas incluyen a los productos que
+        // no alcanzaron a terminar; las salidas son las finalizaciones
+        let arrivals: Vec<Duration> = product_metrics
+            .iter()
+            .chain(&incomplete_products)
+            .chain(&lost_products)
+            .map(|metrics| metrics.arrival_time)
+            .collect();
+        let departures: Vec<Duration> = product_metrics
+            .iter()
+            .map(|metrics| metrics.arrival_time + metrics.turnaround_time)
+            .collect();
+        let inter_departure_times = FlowStatistics::intervals_between(&departures);
+        let arrival_flow = FlowStatistics::from_intervals(&FlowStatistics::intervals_between(&arrivals));
+        let departure_flow = FlowStatistics::from_intervals(&inter_departure_times);
+        let by_class = Self::class_metrics(&product_metrics, &incomplete_products, &lost_products, total_simulation_time);
+
         SimulationMetrics {
             products: product_metrics,
             average_wait_time,
+            average_dependency_wait_time,
+            average_resource_wait_time,
             average_turnaround_time,
+            average_slowdown,
+            max_slowdown,
             completion_order,
+            completion_order_divergence,
             total_simulation_time,
             throughput,
+            fairness_index,
+            total_tardiness,
+            average_tardiness,
+            max_lateness,
+            tardy_jobs,
+            station_reports: Vec::new(),
+            incomplete_products,
+            lost_products,
+            steady_state: None,
+            synchronization: String::new(),
+            periods: Vec::new(),
+            config_epochs: Vec::new(),
+            sla_results: Vec::new(),
+            takt: None,
+            resource_units: Vec::new(),
+            jockeying: None,
+            release_plan: None,
+            inter_departure_times,
+            arrival_flow,
+            departure_flow,
+            by_class,
+            run_metadata: RunMetadata::capture(),
+            tick_length: self.tick_length,
         }
     }
 
-    /// Genera un reporte detallado de los resultados de la simulación.
+    /// Agrupa los productos por clase y calcula las estadísticas de cada una.
     /// 
     /// # Arguments
     /// 
-    /// * `metrics` - Métricas de la simulación
-    /// * `station_configs` - Configuraciones de las estaciones
+    /// * `completed` - Productos completados
+    /// * `incomplete` - Productos que quedaron en la línea
+    /// * `lost` - Productos perdidos
+    /// * `total_simulation_time` - Duración de la simulación, para el throughput
+    fn class_metrics(
+        completed: &[ProductMetrics],
+        incomplete: &[ProductMetrics],
+        lost: &[ProductMetrics],
+        total_simulation_time: Duration,
+    ) -> HashMap<String, ClassMetrics> {
+        let mut classes: Vec<&String> = completed
+            .iter()
+            .chain(incomplete)
+            .chain(lost)
+            .filter_map(|product| product.class.as_ref())
+            .collect();
+        classes.sort();
+        classes.dedup();
+
+        let elapsed = total_simulation_time.as_secs_f64();
+        fn of_class<'a>(products: &'a [ProductMetrics], class: &String) -> Vec<&'a ProductMetrics> {
+            products.iter().filter(|product| product.class.as_ref() == Some(class)).collect()
+        }
+        classes
+            .into_iter()
+            .map(|class| {
+                let finished = of_class(completed, class);
+                let count = finished.len();
+                let average = |total: Duration| if count > 0 { total / count as u32 } else { Duration::ZERO };
+                let metrics = ClassMetrics {
+                    class: class.clone(),
+                    completed: count,
+                    incomplete: of_class(incomplete, class).len(),
+                    lost: of_class(lost, class).len(),
+                    average_wait_time: average(finished.iter().map(|product| product.total_wait_time).sum()),
+                    average_turnaround_time: average(finished.iter().map(|product| product.turnaround_time).sum()),
+                    average_slowdown: if count > 0 {
+                        finished.iter().map(|product| product.slowdown).sum::<f64>() / count as f64
+                    } else {
+                        0.0
+                    },
+                    max_slowdown: finished.iter().map(|product| product.slowdown).fold(0.0, f64::max),
+                    throughput: if elapsed > 0.0 { count as f64 / elapsed } else { 0.0 },
+                };
+                (class.clone(), metrics)
+            })
+            .collect()
+    }
+
+    /// Calcula las estadísticas de estado estable descartando el calentamiento.
+    /// 
+    /// Solo se consideran los productos que llegaron después del período de
+    /// calentamiento. El throughput se mide sobre las finalizaciones
+    /// ocurridas después del calentamiento.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación completa
+    /// * `warmup` - Duración del período de calentamiento
     /// 
     /// # Returns
     /// 
-    /// String con el reporte formateado
-    pub fn generate_report(
-        &self,
-        metrics: &SimulationMetrics,
-        station_configs: &[StationConfig],
-    ) -> String {
-        let mut report = String::new();
-        
-        report.push_str("\n=== REPORTE DE RESULTADOS ===\n\n");
+    /// `SteadyStateMetrics` con las estadísticas sin el calentamiento
+    pub fn steady_state_metrics(metrics: &SimulationMetrics, warmup: Duration) -> SteadyStateMetrics {
+        let considered: Vec<&ProductMetrics> = metrics.products
+            .iter()
+            .filter(|product| product.arrival_time >= warmup)
+            .collect();
+        let count = considered.len();
 
-        // Encabezado de la tabla
-        report.push_str(&format!(
-            "{:^8} {:^12} {:^15} {:^15} {:^15} {:^12} {:^15}\n",
-            "Prod", "Llegada", 
-            station_configs.get(0).map(|c| c.name).unwrap_or("Est1"),
-            station_configs.get(1).map(|c| c.name).unwrap_or("Est2"),
-            station_configs.get(2).map(|c| c.name).unwrap_or("Est3"),
-            "Espera", "Turnaround"
-        ));
+        let average = |total: Duration| {
+            if count > 0 {
+                total / count as u32
+            } else {
+                Duration::ZERO
+            }
+        };
+        let average_wait_time = average(considered.iter().map(|p| p.total_wait_time).sum());
+        let average_turnaround_time = average(considered.iter().map(|p| p.turnaround_time).sum());
 
-        report.push_str(&format!("{}\n", "-".repeat(100)));
+        let completed_after_warmup = metrics.products
+            .iter()
+            .filter(|product| product.arrival_time + product.turnaround_time >= warmup)
+            .count();
+        let measured = metrics.total_simulation_time.saturating_sub(warmup).as_secs_f64();
+        let throughput = if measured > 0.0 {
+            completed_after_warmup as f64 / measured
+        } else {
+            0.0
+        };
 
-        // Datos de cada producto
-        for product_metrics in &metrics.products {
-            let mut station_ranges = Vec::new();
-            for (entry, exit) in &product_metrics.station_times {
-                station_ranges.push(format!(
-                    "{}-{}",
-                    Self::format_duration(*entry),
-                    Self::format_duration(*exit)
-                ));
-            }
+        SteadyStateMetrics {
+            warmup,
+            products_considered: count,
+            average_wait_time,
+            average_turnaround_time,
+            throughput,
+        }
+    }
+
+    /// Calcula las estadísticas de cada período de llegadas.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación completa
+    /// * `periods` - Períodos en los que se segmenta la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// Un `PeriodMetrics` por período, en el mismo orden
+    pub fn period_metrics(metrics: &SimulationMetrics, periods: &[ArrivalPeriod]) -> Vec<PeriodMetrics> {
+        periods
+            .iter()
+            .map(|period| {
+                Self::interval_metrics(metrics, period.name.clone(), period.start, period.end, |time| {
+                    period.contains(time)
+                })
+            })
+            .collect()
+    }
+
+    /// Calcula las estadísticas de cada época de configuración.
+    /// 
+    /// Cada época va desde su inicio hasta el inicio de la siguiente; la
+    /// última se extiende hasta el final de la simulación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación completa
+    /// * `starts` - Inicio de cada época, en orden (la primera en cero)
+    /// 
+    /// # Returns
+    /// 
+    /// Un `PeriodMetrics` por época, llamado "Configuración n"
+    pub fn epoch_metrics(metrics: &SimulationMetrics, starts: &[Duration]) -> Vec<PeriodMetrics> {
+        starts
+            .iter()
+            .enumerate()
+            .map(|(epoch, &start)| {
+                let next = starts.get(epoch + 1).copied();
+                let end = next.unwrap_or(metrics.total_simulation_time.max(start));
+                Self::interval_metrics(metrics, format!("Configuración {}", epoch), start, end, |time| {
+                    time >= start && next.is_none_or(|next| time < next)
+                })
+            })
+            .collect()
+    }
 
-            // Asegurar que tenemos exactamente 3 estaciones para el formato
-            while station_ranges.len() < 3 {
-                station_ranges.push("N/A".to_string());
+    /// Calcula las estadísticas de los productos que llegaron dentro de un
+    /// intervalo y de las finalizaciones ocurridas en él.
+    fn interval_metrics(
+        metrics: &SimulationMetrics,
+        name: String,
+        start: Duration,
+        end: Duration,
+        contains: impl Fn(Duration) -> bool,
+    ) -> PeriodMetrics {
+        let arrived: Vec<&ProductMetrics> = metrics.products
+            .iter()
+            .filter(|product| contains(product.arrival_time))
+            .collect();
+        let average = |total: Duration| {
+            if arrived.is_empty() {
+                Duration::ZERO
+            } else {
+                total / arrived.len() as u32
             }
+        };
+        let completed = metrics.products
+            .iter()
+            .filter(|product| contains(product.arrival_time + product.turnaround_time))
+            .count();
+        let length = end.saturating_sub(start);
 
-            report.push_str(&format!(
-                "{:^8} {:^12} {:^15} {:^15} {:^15} {:^12} {:^15}\n",
-                format!("#{:02}", product_metrics.product_id),
-                Self::format_duration(product_metrics.arrival_time),
-                station_ranges[0],
-                station_ranges.get(1).unwrap_or(&"N/A".to_string()),
-                station_ranges.get(2).unwrap_or(&"N/A".to_string()),
-                Self::format_duration(product_metrics.total_wait_time),
-                Self::format_duration(product_metrics.turnaround_time),
-            ));
+        PeriodMetrics {
+            name,
+            start,
+            end,
+            arrivals: arrived.len(),
+            completed,
+            average_wait_time: average(arrived.iter().map(|p| p.total_wait_time).sum()),
+            average_turnaround_time: average(arrived.iter().map(|p| p.turnaround_time).sum()),
+            throughput: if length.is_zero() { 0.0 } else { completed as f64 / length.as_secs_f64() },
+        }
+    }
+
+    /// Calcula el índice de equidad de Jain de un conjunto de valores.
+    /// 
+    /// El índice es `(Σx)² / (n·Σx²)`: vale 1.0 cuando todos los valores son
+    /// iguales y se acerca a `1/n` cuando uno solo concentra todo.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `values` - Valores no negativos a comparar
+    /// 
+    /// # Returns
+    /// 
+    /// El índice entre `1/n` y 1.0; 1.0 si no hay valores o todos son cero
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::MetricsCalculator;
+    /// 
+    /// assert_eq!(MetricsCalculator::jain_index(&[2.0, 2.0, 2.0]), 1.0);
+    /// assert_eq!(MetricsCalculator::jain_index(&[1.0, 0.0, 0.0, 0.0]), 0.25);
+    /// ```
+    pub fn jain_index(values: &[f64]) -> f64 {
+        let sum: f64 = values.iter().sum();
+        let sum_of_squares: f64 = values.iter().map(|value| value * value).sum();
+        if sum_of_squares == 0.0 {
+            return 1.0;
+        }
+        sum * sum / (values.len() as f64 * sum_of_squares)
+    }
+
+    /// Calcula el slowdown de un producto.
+    /// 
+    /// A diferencia del turnaround absoluto, el slowdown no castiga a los
+    /// productos largos: un producto que nunca esperó tiene slowdown 1.0
+    /// sin importar cuánto procesamiento requiera.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `turnaround_time` - Turnaround del producto
+    /// * `service_time` - Demanda total de servicio del producto
+    /// 
+    /// # Returns
+    /// 
+    /// `turnaround_time / service_time`, o 1.0 si el producto no tiene demanda
+    pub fn slowdown(turnaround_time: Duration, service_time: Duration) -> f64 {
+        if service_time.is_zero() {
+            1.0
+        } else {
+            turnaround_time.as_secs_f64() / service_time.as_secs_f64()
+        }
+    }
+
+    /// Calcula la distancia de Kendall tau normalizada entre el orden de
+    /// llegada y el orden de finalización.
+    /// 
+    /// Es la fracción de pares de productos que terminaron en orden distinto
+    /// al de llegada, por lo que resume en un número cuánto reordenó la
+    /// línea (por ejemplo, por expropiaciones de Round Robin). Los productos
+    /// que llegaron al mismo tiempo se ordenan por ID.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `products` - Métricas de los productos completados
+    /// * `completion_order` - IDs en orden de finalización
+    /// 
+    /// # Returns
+    /// 
+    /// Un valor entre 0.0 (mismo orden) y 1.0 (orden inverso); 0.0 si hay
+    /// menos de dos productos
+    pub fn completion_order_divergence(products: &[ProductMetrics], completion_order: &[usize]) -> f64 {
+        let mut arrival_order: Vec<&ProductMetrics> = products.iter().collect();
+        arrival_order.sort_by_key(|product| (product.arrival_time, product.product_id));
+        let arrival_order: Vec<usize> = arrival_order.iter().map(|product| product.product_id).collect();
+
+        let n = arrival_order.len();
+        if n < 2 {
+            return 0.0;
+        }
+        let pairs = n * (n - 1) / 2;
+        Self::kendall_tau_distance(&arrival_order, completion_order) as f64 / pairs as f64
+    }
+
+    /// Cuenta los pares de elementos que aparecen en orden distinto en dos
+    /// secuencias (distancia de Kendall tau).
+    /// 
+    /// Solo se consideran los elementos presentes en ambas secuencias. El
+    /// conteo usa ordenamiento por mezcla, por lo que es `O(n log n)`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `reference` - Orden de referencia
+    /// * `observed` - Orden observado
+    /// 
+    /// # Returns
+    /// 
+    /// El número de pares discordantes
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::MetricsCalculator;
+    /// 
+    /// assert_eq!(MetricsCalculator::kendall_tau_distance(&[1, 2, 3], &[1, 2, 3]), 0);
+    /// assert_eq!(MetricsCalculator::kendall_tau_distance(&[1, 2, 3], &[3, 2, 1]), 3);
+    /// ```
+    pub fn kendall_tau_distance(reference: &[usize], observed: &[usize]) -> usize {
+        let ranks: HashMap<usize, usize> = reference
+            .iter()
+            .enumerate()
+            .map(|(rank, &id)| (id, rank))
+            .collect();
+        let mut sequence: Vec<usize> = observed.iter().filter_map(|id| ranks.get(id).copied()).collect();
+        let mut buffer = vec![0; sequence.len()];
+        Self::count_inversions(&mut sequence, &mut buffer)
+    }
+
+    /// Ordena `values` y retorna el número de inversiones que tenía.
+    fn count_inversions(values: &mut [usize], buffer: &mut [usize]) -> usize {
+        let len = values.len();
+        if len < 2 {
+            return 0;
+        }
+        let middle = len / 2;
+        let mut inversions = Self::count_inversions(&mut values[..middle], &mut buffer[..middle])
+            + Self::count_inversions(&mut values[middle..], &mut buffer[middle..]);
+
+        let (mut left, mut right) = (0, middle);
+        for slot in buffer[..len].iter_mut() {
+            if right >= len || (left < middle && values[left] <= values[right]) {
+                *slot = values[left];
+                left += 1;
+            } else {
+                // Todos los restantes de la izquierda son mayores que este
+                inversions += middle - left;
+                *slot = values[right];
+                right += 1;
+            }
+        }
+        values.copy_from_slice(&buffer[..len]);
+        inversions
+    }
+
+    /// Calcula el índice de equidad de Jain de los productos.
+    /// 
+    /// Cada producto aporta su turnaround dividido entre su demanda de
+    /// servicio, para que un producto largo no parezca maltratado solo por
+    /// necesitar más procesamiento. Se omiten los productos sin demanda.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `products` - Métricas de los productos completados
+    /// 
+    /// # Returns
+    /// 
+    /// El índice de Jain del turnaround normalizado
+    pub fn fairness_index(products: &[ProductMetrics]) -> f64 {
+        let normalized: Vec<f64> = products
+            .iter()
+            .filter(|product| !product.service_time.is_zero())
+            .map(|product| product.slowdown)
+            .collect();
+        Self::jain_index(&normalized)
+    }
+
+    /// Calcula el retraso (lateness) de un producto respecto a su fecha de entrega.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas del producto
+    /// 
+    /// # Returns
+    /// 
+    /// Diferencia en segundos entre la finalización y la fecha de entrega
+    /// (negativa si terminó antes); `0.0` si el producto no tiene fecha
+    pub fn lateness(metrics: &ProductMetrics) -> f64 {
+        let Some(due) = metrics.due_date else {
+            return 0.0;
+        };
+        let completion = metrics.arrival_time + metrics.turnaround_time;
+        completion.as_secs_f64() - due.as_secs_f64()
+    }
+
+    /// Construye la matriz de esperas producto × estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// * `station_configs` - Configuraciones de las estaciones (nombres de
+    ///   las columnas)
+    /// 
+    /// # Returns
+    /// 
+    /// `WaitMatrix` con una fila por producto completado, ordenadas por ID
+    pub fn wait_matrix(metrics: &SimulationMetrics, station_configs: &[StationConfig]) -> WaitMatrix {
+        let mut products: Vec<&ProductMetrics> = metrics.products.iter().collect();
+        products.sort_by_key(|product| product.product_id);
+        let rows: Vec<Vec<Duration>> = products.iter().map(|product| product.wait_by_station()).collect();
+
+        let station_count = rows.iter().map(Vec::len).max().unwrap_or(0).max(station_configs.len());
+        let stations = (0..station_count)
+            .map(|index| {
+                station_configs
+                    .get(index)
+                    .map_or_else(|| format!("Est{}", index + 1), |config| config.name.clone())
+            })
+            .collect();
+        let waits = rows
+            .into_iter()
+            .map(|mut row| {
+                row.resize(station_count, Duration::ZERO);
+                row
+            })
+            .collect();
+
+        WaitMatrix {
+            stations,
+            product_ids: products.iter().map(|product| product.product_id).collect(),
+            waits,
+        }
+    }
+
+    /// Agrupa una métrica de los productos completados en intervalos de
+    /// ancho fijo.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// * `metric` - Métrica por producto a agrupar
+    /// * `bucket_width` - Ancho de cada intervalo
+    /// 
+    /// # Returns
+    /// 
+    /// `Histogram` con un conteo por intervalo; vacío si no hay productos
+    /// completados
+    /// 
+    /// # Panics
+    /// 
+    /// Si `bucket_width` es cero
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{DistributionMetric, MetricsCalculator, Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    /// let metrics = simulation.run().expect("La simulación falló");
+    /// 
+    /// let histogram = MetricsCalculator::histogram(&metrics, DistributionMetric::Turnaround, Duration::from_millis(5));
+    /// assert_eq!(histogram.total(), 3);
+    /// println!("{}", histogram);
+    /// ```
+    pub fn histogram(metrics: &SimulationMetrics, metric: DistributionMetric, bucket_width: Duration) -> Histogram {
+        assert!(!bucket_width.is_zero(), "El ancho de los intervalos debe ser mayor que cero");
+
+        let mut counts = Vec::new();
+        for product in &metrics.products {
+            let bucket = (metric.value(product).as_nanos() / bucket_width.as_nanos()) as usize;
+            if bucket >= counts.len() {
+                counts.resize(bucket + 1, 0);
+            }
+            counts[bucket] += 1;
+        }
+
+        Histogram { metric, bucket_width, counts }
+    }
+
+    /// Elige el ancho de intervalo de los histogramas del reporte: milisegundos
+    /// enteros, de forma que el valor máximo caiga en el último intervalo.
+    fn report_bucket_width(metrics: &SimulationMetrics, metric: DistributionMetric) -> Duration {
+        let max = metrics.products.iter().map(|product| metric.value(product)).max().unwrap_or_default();
+        Duration::from_millis((max.as_millis() / Self::REPORT_BUCKETS) as u64 + 1)
+    }
+
+    /// Genera un reporte detallado de los resultados de la simulación.
+    /// 
+    /// La tabla de productos tiene una columna por estación configurada, sin
+    /// importar cuántas tenga la línea.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// * `station_configs` - Configuraciones de las estaciones
+    /// 
+    /// # Returns
+    /// 
+    /// String con el reporte formateado
+    pub fn generate_report(
+        &self,
+        metrics: &SimulationMetrics,
+        station_configs: &[StationConfig],
+    ) -> String {
+        let _clock_format = self.clock_format().enter(metrics.tick_length);
+        let mut report = String::new();
+        
+        report.push_str("\n=== REPORTE DE RESULTADOS ===\n\n");
+
+        // Encabezado de la tabla: una columna por estación configurada
+        let station_count = station_configs.len().max(
+            metrics.products.iter().map(|p| p.station_times.len()).max().unwrap_or(0),
+        );
+        let mut header = format!("{:^8} {:^12}", "Prod", "Llegada");
+        for index in 0..station_count {
+            let name = station_configs
+                .get(index)
+                .map_or_else(|| format!("Est{}", index + 1), |c| c.name.clone());
+            header.push_str(&format!(" {:^15}", name));
+        }
+        header.push_str(&format!(" {:^12} {:^15}\n", "Espera", "Turnaround"));
+        let width = header.chars().count().saturating_sub(1).max(60);
+        report.push_str(&header);
+
+        report.push_str(&format!("{}\n", "-".repeat(width)));
+
+        // Datos de cada producto
+        for product_metrics in &metrics.products {
+            let mut row = format!(
+                "{:^8} {:^12}",
+                format!("#{:02}", product_metrics.product_id),
+                Self::format_duration(product_metrics.arrival_time),
+            );
+            for index in 0..station_count {
+                let range = product_metrics.station_times.get(index).map_or_else(
+                    || "N/A".to_string(),
+                    |(entry, exit)| {
+                        format!("{}-{}", Self::format_duration(*entry), Self::format_duration(*exit))
+                    },
+                );
+                row.push_str(&format!(" {:^15}", range));
+            }
+            row.push_str(&format!(
+                " {:^12} {:^15}\n",
+                Self::format_duration(product_metrics.total_wait_time),
+                Self::format_duration(product_metrics.turnaround_time),
+            ));
+            report.push_str(&row);
         }
 
         // Estadísticas resumidas
         report.push_str("\n=== ESTADÍSTICAS RESUMIDAS ===\n");
+        if !metrics.synchronization.is_empty() {
+            report.push_str(&format!(
+                "Sincronización entre estaciones: {}\n",
+                metrics.synchronization
+            ));
+        }
         report.push_str(&format!(
             "Productos completados: {}\n",
             metrics.products.len()
@@ -249,10 +1548,27 @@ impl MetricsCalculator {
             "Tiempo promedio de espera: {}\n",
             Self::format_duration(metrics.average_wait_time)
         ));
+        if !metrics.average_dependency_wait_time.is_zero() {
+            report.push_str(&format!(
+                "Tiempo promedio bloqueado por dependencias: {}\n",
+                Self::format_duration(metrics.average_dependency_wait_time)
+            ));
+        }
+        if !metrics.average_resource_wait_time.is_zero() {
+            report.push_str(&format!(
+                "Tiempo promedio esperando recursos compartidos: {}\n",
+                Self::format_duration(metrics.average_resource_wait_time)
+            ));
+        }
         report.push_str(&format!(
             "Tiempo promedio de turnaround: {}\n",
             Self::format_duration(metrics.average_turnaround_time)
         ));
+        report.push_str(&format!(
+            "Slowdown promedio: {:.2} (máximo {:.2})\n",
+            metrics.average_slowdown,
+            metrics.max_slowdown
+        ));
         report.push_str(&format!(
             "Duración total de simulación: {}\n",
             Self::format_duration(metrics.total_simulation_time)
@@ -261,16 +1577,405 @@ impl MetricsCalculator {
             "Throughput: {:.3} productos/segundo\n",
             metrics.throughput
         ));
+        report.push_str(&format!(
+            "Índice de equidad (Jain): {:.3}\n",
+            metrics.fairness_index
+        ));
         report.push_str(&format!(
             "Orden de finalización: {:?}\n",
             metrics.completion_order
         ));
+        report.push_str(&format!(
+            "Divergencia respecto al orden de llegada (Kendall tau): {:.3}\n",
+            metrics.completion_order_divergence
+        ));
+        if metrics.departure_flow.intervals > 0 {
+            report.push_str(&format!(
+                "Intervalo entre llegadas: promedio {}, CV {:.3}\n",
+                Self::format_duration(metrics.arrival_flow.mean_interval),
+                metrics.arrival_flow.coefficient_of_variation
+            ));
+            report.push_str(&format!(
+                "Intervalo entre salidas: promedio {}, CV {:.3}\n",
+                Self::format_duration(metrics.departure_flow.mean_interval),
+                metrics.departure_flow.coefficient_of_variation
+            ));
+        }
+
+        if !metrics.products.is_empty() {
+            report.push_str("\n=== DISTRIBUCIÓN DE TIEMPOS ===\n");
+            for metric in [DistributionMetric::WaitTime, DistributionMetric::Turnaround] {
+                let width = Self::report_bucket_width(metrics, metric);
+                report.push_str(&Self::histogram(metrics, metric, width).to_string());
+            }
+        }
+
+        let outliers = OutlierDetector::new().detect(metrics);
+        if !outliers.suspects.is_empty() {
+            report.push_str("\n=== DIAGNÓSTICO: PRODUCTOS ATÍPICOS ===\n");
+            report.push_str(&format!(
+                "Límites (Q3 + 1.5·IQR): espera {}, turnaround {}\n",
+                Self::format_duration(outliers.wait_threshold),
+                Self::format_duration(outliers.turnaround_threshold)
+            ));
+            for outlier in &outliers.suspects {
+                let delays: Vec<String> = outlier.station_waits
+                    .iter()
+                    .map(|&(station, wait)| {
+                        let name = station_configs
+                            .get(station)
+                            .map_or_else(|| format!("Est{}", station + 1), |c| c.name.clone());
+                        format!("{} {}", name, Self::format_duration(wait))
+                    })
+                    .collect();
+                report.push_str(&format!(
+                    "#{:02}: espera {}, turnaround {}; espera por estación: {}\n",
+                    outlier.product_id,
+                    Self::format_duration(outlier.total_wait_time),
+                    Self::format_duration(outlier.turnaround_time),
+                    if delays.is_empty() { "ninguna".to_string() } else { delays.join(", ") }
+                ));
+            }
+        }
+
+        if !metrics.station_reports.is_empty() {
+            report.push_str("\n=== ESTADÍSTICAS POR ESTACIÓN ===\n");
+            report.push_str(&format!(
+                "{:<15} {:>10} {:>12} {:>12} {:>12} {:>8} {:>12} {:>10} {:>12}\n",
+                "Estación", "Productos", "Ocupada", "Ociosa", "Bloqueada", "Porciones",
+                "Preempciones", "Cola máx", "Utilización"
+            ));
+            for station in &metrics.station_reports {
+                report.push_str(&format!(
+                    "{:<15} {:>10} {:>12} {:>12} {:>12} {:>8} {:>12} {:>10} {:>11.1}%\n",
+                    station.name,
+                    station.products_processed,
+                    Self::format_duration(station.busy_time),
+                    Self::format_duration(station.idle_time),
+                    Self::format_duration(station.blocked_time),
+                    station.slices_executed,
+                    station.preemptions,
+                    station.max_queue_length,
+                    station.utilization() * 100.0
+                ));
+            }
+            for station in &metrics.station_reports {
+                if let Some(description) = &station.description {
+                    report.push_str(&format!("  {}: {}\n", station.name, description));
+                }
+            }
+        }
+
+        // El desfase solo aparece al ejecutar en tiempo real
+        if metrics.station_reports.iter().any(|station| !station.slice_drift.is_zero()) {
+            report.push_str("\n=== PERFIL DE EJECUCIÓN ===\n");
+            report.push_str(&format!(
+                "{:<15} {:>8} {:>12} {:>12} {:>7} {:>12} {:>12} {:>12} {:>10} {:>8}\n",
+                "Estación", "Porciones", "Solicitado", "Medido", "Factor", "Desfase prom", "Desfase máx", "Motor", "% ocupado", "Esperas"
+            ));
+            for station in &metrics.station_reports {
+                let average = u32::try_from(station.slices_executed)
+                    .ok()
+                    .filter(|&slices| slices > 0)
+                    .map_or(Duration::ZERO, |slices| station.slice_drift / slices);
+                report.push_str(&format!(
+                    "{:<15} {:>8} {:>12} {:>12} {:>6.2}x {:>12} {:>12} {:>12} {:>9.1}% {:>8}\n",
+                    station.name,
+                    station.slices_executed,
+                    Self::format_duration(station.slice_time),
+                    Self::format_duration(station.measured_slice_time()),
+                    station.slice_stretch(),
+                    Self::format_duration(average),
+                    Self::format_duration(station.max_slice_drift),
+                    Self::format_duration(station.engine_overhead()),
+                    station.drift_ratio() * 100.0,
+                    station.wakeups
+                ));
+            }
+            let total: Duration = metrics.station_reports.iter().map(|station| station.slice_drift).sum();
+            report.push_str(&format!(
+                "Desfase total respecto de los tiempos nominales: {}\n",
+                Self::format_duration(total)
+            ));
+        }
+
+        // La imprecisión de los temporizadores solo aparece si se modeló
+        if metrics.station_reports.iter().any(|station| !station.injected_drift.is_zero()) {
+            report.push_str("\n=== IMPRECISIÓN DE TEMPORIZADORES ===\n");
+            for station in metrics.station_reports.iter().filter(|station| !station.injected_drift.is_zero()) {
+                let average = u32::try_from(station.slices_executed)
+                    .ok()
+                    .filter(|&slices| slices > 0)
+                    .map_or(Duration::ZERO, |slices| station.injected_drift / slices);
+                report.push_str(&format!(
+                    "{}: {} inyectados en {} porciones (promedio {})\n",
+                    station.name,
+                    Self::format_duration(station.injected_drift),
+                    station.slices_executed,
+                    Self::format_duration(average)
+                ));
+            }
+            let timer_drift: Duration = metrics.products.iter().map(|product| product.timer_drift).sum();
+            let wait: Duration = metrics.products.iter().map(|product| product.total_wait_time).sum();
+            report.push_str(&format!(
+                "Productos completados: {} de retraso en sus porciones, {} de espera en cola\n",
+                Self::format_duration(timer_drift),
+                Self::format_duration(wait)
+            ));
+        }
+
+        // Los calentamientos solo aparecen si alguna estación se enfrió
+        if metrics.station_reports.iter().any(|station| station.warm_ups > 0) {
+            report.push_str("\n=== CALENTAMIENTOS ===\n");
+            for station in metrics.station_reports.iter().filter(|station| station.warm_ups > 0) {
+                report.push_str(&format!(
+                    "{}: {} calentamientos, {} en total\n",
+                    station.name,
+                    station.warm_ups,
+                    Self::format_duration(station.warm_up_time)
+                ));
+            }
+        }
+
+        // Las inspecciones solo aparecen si la línea tiene estaciones de inspección
+        let inspections = |station: &&StationReport| station.inspections_passed + station.inspections_failed > 0;
+        if metrics.station_reports.iter().any(|station| inspections(&station)) {
+            report.push_str("\n=== INSPECCIONES ===\n");
+            for station in metrics.station_reports.iter().filter(inspections) {
+                let total = station.inspections_passed + station.inspections_failed;
+                report.push_str(&format!(
+                    "{}: {} inspecciones, {} aprobadas ({:.1}%), {} no aprobadas, {} productos rechazados\n",
+                    station.name,
+                    total,
+                    station.inspections_passed,
+                    station.inspections_passed as f64 / total as f64 * 100.0,
+                    station.inspections_failed,
+                    station.rejected
+                ));
+            }
+        }
+
+        // Los repartos solo aparecen si la línea tiene divisores con más de una salida
+        let splitters = |station: &&StationReport| station.routed.len() > 1;
+        if metrics.station_reports.iter().any(|station| splitters(&station)) {
+            report.push_str("\n=== DIVISORES ===\n");
+            for station in metrics.station_reports.iter().filter(splitters) {
+                let outputs: Vec<String> = station.routed
+                    .iter()
+                    .enumerate()
+                    .map(|(output, count)| format!("salida {}: {}", output + 1, count))
+                    .collect();
+                report.push_str(&format!(
+                    "{}: {} productos repartidos ({})\n",
+                    station.name,
+                    station.routed.iter().sum::<usize>(),
+                    outputs.join(", ")
+                ));
+            }
+        }
+
+        // Las atenciones por origen solo aparecen si la línea tiene uniones
+        if metrics.station_reports.iter().any(|station| !station.merge_sources.is_empty()) {
+            report.push_str("\n=== UNIONES ===\n");
+            for station in metrics.station_reports.iter().filter(|station| !station.merge_sources.is_empty()) {
+                report.push_str(&format!("{}:\n", station.name));
+                for source in &station.merge_sources {
+                    let name = source.source
+                        .and_then(|index| metrics.station_reports.get(index))
+                        .map_or("entrada", |origin| origin.name.as_str());
+                    report.push_str(&format!(
+                        "  desde {}: {} productos, espera promedio {}, máxima {}, omitido {} veces (máx. {} seguidas)\n",
+                        name,
+                        source.merged,
+                        Self::format_duration(source.average_wait()),
+                        Self::format_duration(source.max_wait),
+                        source.bypassed,
+                        source.max_consecutive_bypassed
+                    ));
+                }
+            }
+        }
+
+        if !metrics.incomplete_products.is_empty() {
+            report.push_str("\n=== PRODUCTOS INCOMPLETOS ===\n");
+            for product in &metrics.incomplete_products {
+                let completed_stations = product.visit_times
+                    .iter()
+                    .filter(|(_, _, exit)| !exit.is_zero())
+                    .count();
+                report.push_str(&format!(
+                    "#{:02}: llegada {}, {}/{} estaciones completadas, en sistema {}, espera {}\n",
+                    product.product_id,
+                    Self::format_duration(product.arrival_time),
+                    completed_stations,
+                    product.visit_times.len(),
+                    Self::format_duration(product.turnaround_time),
+                    Self::format_duration(product.total_wait_time)
+                ));
+            }
+        }
+
+        if !metrics.lost_products.is_empty() {
+            report.push_str("\n=== PRODUCTOS PERDIDOS ===\n");
+            for product in &metrics.lost_products {
+                let Some(loss) = product.loss else {
+                    continue;
+                };
+                let station = metrics.station_reports
+                    .get(loss.station)
+                    .map_or_else(|| format!("estación {}", loss.station + 1), |station| station.name.clone());
+                let replacement = product.replaced_by
+                    .map(|id| format!(", reemplazado por #{:02}", id))
+                    .unwrap_or_default();
+                report.push_str(&format!(
+                    "#{:02}: {} en {} a los {}, llegada {}, espera {}{}\n",
+                    product.product_id,
+                    loss.reason,
+                    station,
+                    Self::format_duration(loss.at),
+                    Self::format_duration(product.arrival_time),
+                    Self::format_duration(product.total_wait_time),
+                    replacement
+                ));
+            }
+        }
+
+        if let Some(steady) = &metrics.steady_state {
+            report.push_str("\n=== ESTADO ESTABLE ===\n");
+            report.push_str(&format!(
+                "Calentamiento descartado: {}\n",
+                Self::format_duration(steady.warmup)
+            ));
+            report.push_str(&format!(
+                "Productos considerados: {}\n",
+                steady.products_considered
+            ));
+            report.push_str(&format!(
+                "Tiempo promedio de espera: {}\n",
+                Self::format_duration(steady.average_wait_time)
+            ));
+            report.push_str(&format!(
+                "Tiempo promedio de turnaround: {}\n",
+                Self::format_duration(steady.average_turnaround_time)
+            ));
+            report.push_str(&format!(
+                "Throughput: {:.3} productos/segundo\n",
+                steady.throughput
+            ));
+        }
+
+        let period_line = |period: &PeriodMetrics| {
+            format!(
+                "{} [{} - {}]: {} llegadas, {} completados, espera promedio {}, turnaround promedio {}, {:.3} productos/segundo\n",
+                period.name,
+                Self::format_duration(period.start),
+                Self::format_duration(period.end),
+                period.arrivals,
+                period.completed,
+                Self::format_duration(period.average_wait_time),
+                Self::format_duration(period.average_turnaround_time),
+                period.throughput
+            )
+        };
+        if !metrics.periods.is_empty() {
+            report.push_str("\n=== ESTADÍSTICAS POR PERÍODO ===\n");
+            for period in &metrics.periods {
+                report.push_str(&period_line(period));
+            }
+        }
+        if !metrics.config_epochs.is_empty() {
+            report.push_str("\n=== ÉPOCAS DE CONFIGURACIÓN ===\n");
+            for epoch in &metrics.config_epochs {
+                report.push_str(&period_line(epoch));
+            }
+        }
+
+        if !metrics.by_class.is_empty() {
+            report.push_str("\n=== MÉTRICAS POR CLASE ===\n");
+            for class in Self::sorted_classes(metrics) {
+                report.push_str(&format!(
+                    "{}: {} completados, {} incompletos, {} perdidos, espera promedio {}, turnaround promedio {}, slowdown promedio {:.2} (máx. {:.2}), {:.3} productos/segundo\n",
+                    class.class,
+                    class.completed,
+                    class.incomplete,
+                    class.lost,
+                    Self::format_duration(class.average_wait_time),
+                    Self::format_duration(class.average_turnaround_time),
+                    class.average_slowdown,
+                    class.max_slowdown,
+                    class.throughput
+                ));
+            }
+        }
+
+        if !metrics.sla_results.is_empty() {
+            report.push_str("\n=== ACUERDOS DE NIVEL DE SERVICIO ===\n");
+            for result in &metrics.sla_results {
+                report.push_str(&format!("{}\n", result));
+            }
+        }
+
+        if let Some(takt) = &metrics.takt {
+            report.push_str("\n=== TAKT TIME ===\n");
+            report.push_str(&format!("{}\n", takt));
+        }
+
+        if let Some(plan) = &metrics.release_plan {
+            report.push_str("\n=== PLAN DE LIBERACIÓN ===\n");
+            report.push_str(&format!("{}\n", plan));
+            report.push_str(&format!(
+                "Makespan alcanzado: {} ({:+.1}% frente a decisiones locales)\n",
+                Self::format_duration(metrics.total_simulation_time),
+                -plan.improvement(metrics.total_simulation_time) * 100.0
+            ));
+        }
+
+        if !metrics.resource_units.is_empty() {
+            report.push_str("\n=== UNIDADES DE RECURSOS ===\n");
+            for unit in &metrics.resource_units {
+                report.push_str(&format!("{}\n", unit));
+            }
+        }
+
+        if let Some(jockeying) = &metrics.jockeying {
+            report.push_str("\n=== CAMBIOS DE COLA ===\n");
+            report.push_str(&format!("{}\n", jockeying));
+        }
+
+        if let Some(max_lateness) = metrics.max_lateness {
+            report.push_str("\n=== FECHAS DE ENTREGA ===\n");
+            report.push_str(&format!(
+                "Tardanza total: {}\n",
+                Self::format_duration(metrics.total_tardiness)
+            ));
+            report.push_str(&format!(
+                "Tardanza promedio: {}\n",
+                Self::format_duration(metrics.average_tardiness)
+            ));
+            report.push_str(&format!(
+                "Retraso máximo (lateness): {:+.3}s\n",
+                max_lateness
+            ));
+            report.push_str(&format!(
+                "Productos atrasados: {}\n",
+                metrics.tardy_jobs
+            ));
+        }
+
+        report.push_str("\n=== DATOS DE LA EJECUCIÓN ===\n");
+        report.push_str(&format!("{}\n", metrics.run_metadata));
 
         report
     }
 
     /// Genera un reporte resumido en formato CSV.
     /// 
+    /// Los tiempos usan el formato de reloj del calculador sin unidad (por defecto,
+    /// segundos con precisión de milisegundos). Se emite un par de columnas
+    /// `StationN_Entry,StationN_Exit` por cada estación de la línea. El
+    /// encabezado va precedido por los metadatos de la ejecución como líneas
+    /// de comentario (ver [`RunMetadata::csv_preamble`]).
+    /// 
     /// # Arguments
     /// 
     /// * `metrics` - Métricas de la simulación
@@ -279,34 +1984,373 @@ impl MetricsCalculator {
     /// 
     /// String con los datos en formato CSV
     pub fn generate_csv_report(&self, metrics: &SimulationMetrics) -> String {
-        let mut csv = String::new();
-        
-        // Encabezado CSV
-        csv.push_str("ProductID,ArrivalTime,WaitTime,Turnaround,Station1_Entry,Station1_Exit,Station2_Entry,Station2_Exit,Station3_Entry,Station3_Exit\n");
-        
-        // Datos de cada producto
+        let mut csv = Vec::new();
+        self.write_csv_report(metrics, &mut csv)
+            .expect("Escribir en memoria no puede fallar");
+        String::from_utf8(csv).expect("El reporte CSV es UTF-8 válido")
+    }
+
+    /// Escribe el reporte de [`MetricsCalculator::generate_csv_report`] en
+    /// `writer`, fila por fila.
+    /// 
+    /// No arma el reporte completo en memoria, por lo que conviene en
+    /// ejecuciones de cientos de miles de productos. Se hace una escritura
+    /// por campo: para archivos o sockets, envolver el escritor en un
+    /// [`std::io::BufWriter`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// * `writer` - Destino del reporte
+    /// 
+    /// # Errors
+    /// 
+    /// Devuelve el primer error de escritura
+    pub fn write_csv_report<W: Write>(&self, metrics: &SimulationMetrics, mut writer: W) -> io::Result<()> {
+        let _clock_format = self.clock_format().enter(metrics.tick_length);
+        writer.write_all(metrics.run_metadata.csv_preamble().as_bytes())?;
+
+        // Un par de columnas por estación de la línea
+        let station_count = metrics.station_reports.len().max(
+            metrics.products.iter().map(|p| p.station_times.len()).max().unwrap_or(0),
+        );
+        Self::write_csv_header(&mut writer, station_count)?;
         for product_metrics in &metrics.products {
-            csv.push_str(&format!("{},", product_metrics.product_id));
-            csv.push_str(&format!("{:.3},", product_metrics.arrival_time.as_secs_f64()));
-            csv.push_str(&format!("{:.3},", product_metrics.total_wait_time.as_secs_f64()));
-            csv.push_str(&format!("{:.3},", product_metrics.turnaround_time.as_secs_f64()));
-            
-            // Tiempos por estación
-            for (entry, exit) in &product_metrics.station_times {
-                csv.push_str(&format!("{:.3},{:.3},", entry.as_secs_f64(), exit.as_secs_f64()));
-            }
-            
-            // Completar con N/A si faltan estaciones
-            for _ in product_metrics.station_times.len()..3 {
-                csv.push_str("N/A,N/A,");
-            }
-            
-            csv.push('\n');
+            Self::write_csv_row(&mut writer, product_metrics, station_count)?;
+        }
+        Ok(())
+    }
+
+    /// Escribe el encabezado del reporte CSV por producto.
+    pub(crate) fn write_csv_header<W: Write>(writer: &mut W, station_count: usize) -> io::Result<()> {
+        writer.write_all(b"ProductID,ArrivalTime,WaitTime,Turnaround,")?;
+        for index in 1..=station_count {
+            write!(writer, "Station{index}_Entry,Station{index}_Exit,")?;
+        }
+        writer.write_all(b"Metadata\n")
+    }
+
+    /// Escribe la fila de un producto del reporte CSV, completando con
+    /// `N/A` las estaciones que no visitó hasta `station_count`.
+    pub(crate) fn write_csv_row<W: Write>(
+        writer: &mut W,
+        product_metrics: &ProductMetrics,
+        station_count: usize,
+    ) -> io::Result<()> {
+        let time = |duration: Duration| ClockFormat::current().format_value(duration);
+        write!(
+            writer,
+            "{},{},{},{},",
+            product_metrics.product_id,
+            time(product_metrics.arrival_time),
+            time(product_metrics.total_wait_time),
+            time(product_metrics.turnaround_time)
+        )?;
+
+        // Tiempos por estación
+        for (entry, exit) in &product_metrics.station_times {
+            write!(writer, "{},{},", time(*entry), time(*exit))?;
+        }
+
+        // Completar con N/A si faltan estaciones
+        for _ in product_metrics.station_times.len()..station_count {
+            writer.write_all(b"N/A,N/A,")?;
+        }
+
+        writeln!(writer, "{}", Self::format_metadata_field(&product_metrics.metadata))
+    }
+
+    /// Genera un reporte CSV con las estadísticas de cada clase de producto.
+    /// 
+    /// Complementa a [`MetricsCalculator::generate_csv_report`], que tiene
+    /// una fila por producto. Los tiempos usan el formato de reloj del calculador
+    /// sin unidad y, como en ese reporte, el encabezado va precedido por los
+    /// metadatos de la ejecución.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Las métricas de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// String con los metadatos, el encabezado y una fila por clase, en orden
+    /// alfabético
+    pub fn generate_class_csv_report(&self, metrics: &SimulationMetrics) -> String {
+        let _clock_format = self.clock_format().enter(metrics.tick_length);
+        let time = |duration: Duration| ClockFormat::current().format_value(duration);
+        let mut csv = metrics.run_metadata.csv_preamble();
+        csv.push_str("Class,Completed,Incomplete,Lost,AvgWait,AvgTurnaround,AvgSlowdown,MaxSlowdown,Throughput\n");
+        for class in Self::sorted_classes(metrics) {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{:.4},{:.4},{:.4}\n",
+                Self::csv_field(&class.class),
+                class.completed,
+                class.incomplete,
+                class.lost,
+                time(class.average_wait_time),
+                time(class.average_turnaround_time),
+                class.average_slowdown,
+                class.max_slowdown,
+                class.throughput
+            ));
         }
-        
         csv
     }
 
+    /// Estadísticas por clase ordenadas por nombre de clase.
+    fn sorted_classes(metrics: &SimulationMetrics) -> Vec<&ClassMetrics> {
+        let mut classes: Vec<&ClassMetrics> = metrics.by_class.values().collect();
+        classes.sort_by(|a, b| a.class.cmp(&b.class));
+        classes
+    }
+
+    /// Genera un reporte en formato JSON de las métricas.
+    /// 
+    /// Los tiempos se expresan siempre en segundos (con precisión de
+    /// milisegundos), sin importar el formato de reloj. Pensado para integraciones que no pueden
+    /// usar las estructuras de Rust directamente, como la interfaz C.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Las métricas de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// String con un objeto JSON con los metadatos de la ejecución (`run`),
+    /// los promedios, los productos (completados y perdidos) y las estaciones
+    pub fn generate_json_report(&self, metrics: &SimulationMetrics) -> String {
+        let seconds = |duration: Duration| format!("{:.3}", duration.as_secs_f64());
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+
+        let products: Vec<String> = metrics.products.iter().map(Self::product_json).collect();
+
+        let stations: Vec<String> = metrics.station_reports
+            .iter()
+            .map(|report| {
+                format!(
+                    "{{\"name\":{},\"description\":{},\"color\":{},\"products_processed\":{},\"busy_time\":{},\"idle_time\":{},\"blocked_time\":{},\"utilization\":{:.4},\"slices_executed\":{},\"slice_time\":{},\"preemptions\":{},\"max_queue_length\":{},\"slice_drift\":{},\"max_slice_drift\":{},\"work_overrun\":{},\"injected_drift\":{},\"wakeups\":{},\"balked\":{},\"reneged\":{},\"warm_ups\":{},\"warm_up_time\":{},\"inspections_passed\":{},\"inspections_failed\":{},\"rejected\":{},\"jockeyed\":{},\"unit_jockeys\":{},\"routed\":[{}],\"merge_sources\":[{}]}}",
+                    Self::json_string(&report.name),
+                    optional(report.description.as_deref().map(Self::json_string)),
+                    optional(report.color.as_deref().map(Self::json_string)),
+                    report.products_processed,
+                    seconds(report.busy_time),
+                    seconds(report.idle_time),
+                    seconds(report.blocked_time),
+                    report.utilization(),
+                    report.slices_executed,
+                    seconds(report.slice_time),
+                    report.preemptions,
+                    report.max_queue_length,
+                    seconds(report.slice_drift),
+                    seconds(report.max_slice_drift),
+                    seconds(report.work_overrun),
+                    seconds(report.injected_drift),
+                    report.wakeups,
+                    report.balked,
+                    report.reneged,
+                    report.warm_ups,
+                    seconds(report.warm_up_time),
+                    report.inspections_passed,
+                    report.inspections_failed,
+                    report.rejected,
+                    report.jockeyed,
+                    report.unit_jockeys,
+                    report.routed.iter().map(usize::to_string).collect::<Vec<_>>().join(","),
+                    report.merge_sources
+                        .iter()
+                        .map(|source| format!(
+                            "{{\"source\":{},\"merged\":{},\"total_wait\":{},\"max_wait\":{},\"bypassed\":{},\"max_consecutive_bypassed\":{}}}",
+                            optional(source.source.map(|index| index.to_string())),
+                            source.merged,
+                            seconds(source.total_wait),
+                            seconds(source.max_wait),
+                            source.bypassed,
+                            source.max_consecutive_bypassed
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )
+            })
+            .collect();
+
+        let lost: Vec<String> = metrics.lost_products
+            .iter()
+            .filter_map(|product| {
+                let loss = product.loss?;
+                Some(format!(
+                    "{{\"id\":{},\"station\":{},\"reason\":\"{}\",\"at\":{},\"arrival_time\":{},\"wait_time\":{},\"replaced_by\":{}}}",
+                    product.product_id,
+                    loss.station,
+                    loss.reason.key(),
+                    seconds(loss.at),
+                    seconds(product.arrival_time),
+                    seconds(product.total_wait_time),
+                    optional(product.replaced_by.map(|id| id.to_string()))
+                ))
+            })
+            .collect();
+
+        let completion_order: Vec<String> = metrics.completion_order.iter().map(usize::to_string).collect();
+        let slas: Vec<String> = metrics.sla_results
+            .iter()
+            .map(|result| {
+                format!(
+                    "{{\"sla\":{},\"passed\":{},\"actual\":{:.4},\"target\":{:.4},\"margin\":{:.4}}}",
+                    Self::json_string(&result.sla.to_string()),
+                    result.passed,
+                    result.actual,
+                    result.target,
+                    result.margin
+                )
+            })
+            .collect();
+
+        let resource_units: Vec<String> = metrics.resource_units
+            .iter()
+            .map(|unit| {
+                format!(
+                    "{{\"resource\":{},\"unit\":{},\"slices\":{},\"products\":{},\"busy_time\":{},\"utilization\":{:.4}}}",
+                    Self::json_string(&unit.resource),
+                    unit.unit,
+                    unit.slices,
+                    unit.products,
+                    seconds(unit.busy_time),
+                    unit.utilization
+                )
+            })
+            .collect();
+
+        let jockeying = metrics.jockeying.as_ref().map(|jockeying| {
+            format!(
+                "{{\"policy\":{},\"moves\":{},\"branch_moves\":{},\"unit_moves\":{},\"products_moved\":{},\"average_wait\":{},\"wait_variance\":{:.6},\"moved_average_wait\":{},\"stayed_average_wait\":{}}}",
+                Self::json_string(&jockeying.policy.to_string()),
+                jockeying.moves,
+                jockeying.branch_moves,
+                jockeying.unit_moves,
+                jockeying.products_moved,
+                seconds(jockeying.average_wait),
+                jockeying.wait_variance,
+                optional(jockeying.moved_average_wait.map(seconds)),
+                optional(jockeying.stayed_average_wait.map(seconds))
+            )
+        });
+
+        format!(
+            "{{\"run\":{},\"total_simulation_time\":{},\"average_wait_time\":{},\"average_turnaround_time\":{},\"average_slowdown\":{:.4},\"max_slowdown\":{:.4},\"throughput\":{:.4},\"fairness_index\":{:.4},\"completion_order\":[{}],\"completion_order_divergence\":{:.4},\"arrival_cv\":{:.4},\"departure_cv\":{:.4},\"synchronization\":{},\"slas\":[{}],\"products\":[{}],\"lost_products\":[{}],\"stations\":[{}],\"resource_units\":[{}],\"jockeying\":{}}}",
+            metrics.run_metadata.to_json(),
+            seconds(metrics.total_simulation_time),
+            seconds(metrics.average_wait_time),
+            seconds(metrics.average_turnaround_time),
+            metrics.average_slowdown,
+            metrics.max_slowdown,
+            metrics.throughput,
+            metrics.fairness_index,
+            completion_order.join(","),
+            metrics.completion_order_divergence,
+            metrics.arrival_flow.coefficient_of_variation,
+            metrics.departure_flow.coefficient_of_variation,
+            Self::json_string(&metrics.synchronization),
+            slas.join(","),
+            products.join(","),
+            lost.join(","),
+            stations.join(","),
+            resource_units.join(","),
+            optional(jockeying)
+        )
+    }
+
+    /// Genera el objeto JSON de un producto, el mismo que aparece en la lista
+    /// `products` de [`MetricsCalculator::generate_json_report`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product` - Métricas del producto
+    /// 
+    /// # Returns
+    /// 
+    /// String con el objeto JSON en una sola línea
+    pub fn product_json(product: &ProductMetrics) -> String {
+        let seconds = |duration: Duration| format!("{:.3}", duration.as_secs_f64());
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+
+        let station_times: Vec<String> = product.station_times
+            .iter()
+            .map(|(entry, exit)| format!("[{},{}]", seconds(*entry), seconds(*exit)))
+            .collect();
+        let visit_times: Vec<String> = product.visit_times
+            .iter()
+            .map(|(station, entry, exit)| format!("[{},{},{}]", station, seconds(*entry), seconds(*exit)))
+            .collect();
+        let slices: Vec<String> = product.slices
+            .iter()
+            .map(|(station, start, end)| format!("[{},{},{}]", station, seconds(*start), seconds(*end)))
+            .collect();
+        let slice_counts: Vec<String> = product.slice_counts.iter().map(usize::to_string).collect();
+        let timeline: Vec<String> = product.timeline
+            .iter()
+            .map(|span| {
+                format!("[{},\"{}\",{},{}]", span.station, span.event.key(), seconds(span.start), seconds(span.end))
+            })
+            .collect();
+        let mut metadata: Vec<_> = product.metadata.iter().collect();
+        metadata.sort();
+        let metadata: Vec<String> = metadata
+            .iter()
+            .map(|(key, value)| format!("{}:{}", Self::json_string(key), Self::json_string(value)))
+            .collect();
+        format!(
+            "{{\"id\":{},\"class\":{},\"arrival_time\":{},\"wait_time\":{},\"turnaround_time\":{},\"timer_drift\":{},\"service_time\":{},\"slowdown\":{:.4},\"due_date\":{},\"tardiness\":{},\"station_times\":[{}],\"visit_times\":[{}],\"slices\":[{}],\"slice_counts\":[{}],\"timeline\":[{}],\"metadata\":{{{}}},\"replaces\":{},\"jockeys\":{}}}",
+            product.product_id,
+            optional(product.class.as_deref().map(Self::json_string)),
+            seconds(product.arrival_time),
+            seconds(product.total_wait_time),
+            seconds(product.turnaround_time),
+            seconds(product.timer_drift),
+            seconds(product.service_time),
+            product.slowdown,
+            optional(product.due_date.map(seconds)),
+            seconds(product.tardiness),
+            station_times.join(","),
+            visit_times.join(","),
+            slices.join(","),
+            slice_counts.join(","),
+            timeline.join(","),
+            metadata.join(","),
+            optional(product.replaces.map(|id| id.to_string())),
+            product.jockeys
+        )
+    }
+
+    /// Escribe un texto como string JSON, escapando los caracteres especiales.
+    pub(crate) fn json_string(text: &str) -> String {
+        Json::String(text.to_string()).to_string()
+    }
+
+    /// Formatea los metadatos como un campo CSV con pares `clave=valor`.
+    /// 
+    /// Los pares se ordenan por clave y se separan con `;`. El campo se
+    /// encierra entre comillas cuando contiene caracteres especiales de CSV.
+    fn format_metadata_field(metadata: &HashMap<String, String>) -> String {
+        let mut pairs: Vec<_> = metadata.iter().collect();
+        pairs.sort();
+        let field = pairs
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(";");
+        Self::csv_field(&field)
+    }
+
+    /// Encierra un campo CSV entre comillas cuando contiene caracteres
+    /// especiales, duplicando las comillas internas.
+    pub(crate) fn csv_field(field: &str) -> String {
+        if field.contains([',', '"', '\n']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+
     /// Formatea una duración para mostrar en formato legible.
     /// 
     /// # Arguments
@@ -315,12 +2359,10 @@ impl MetricsCalculator {
     /// 
     /// # Returns
     /// 
-    /// String formateado con la duración en formato "s.mmm"
+    /// String formateado con el formato de reloj vigente (por defecto
+    /// "s.mmm"; ver [`ClockFormat::current`])
     pub fn format_duration(duration: Duration) -> String {
-        let millis = duration.as_millis();
-        let seconds = millis / 1000;
-        let milliseconds = millis % 1000;
-        format!("{}.{:03}s", seconds, milliseconds)
+        ClockFormat::current().format(duration)
     }
 }
 
@@ -333,8 +2375,6 @@ impl Default for MetricsCalculator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::station::StationConfig;
-    use std::sync::Mutex;
 
     #[test]
     fn test_format_duration() {
@@ -352,13 +2392,181 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_json_string_escaping() {
+        assert_eq!(MetricsCalculator::json_string("Corte"), "\"Corte\"");
+        assert_eq!(
+            MetricsCalculator::json_string("a\"b\\c\nd\u{1}"),
+            "\"a\\\"b\\\\c\\nd\\u0001\""
+        );
+    }
+
+    #[test]
+    fn test_lateness() {
+        let metrics = ProductMetrics {
+            product_id: 1,
+            class: None,
+            metadata: HashMap::new(),
+            arrival_time: Duration::from_millis(100),
+            total_wait_time: Duration::ZERO,
+            dependency_wait_time: Duration::ZERO,
+            resource_wait_time: Duration::ZERO,
+            turnaround_time: Duration::from_millis(400),
+            timer_drift: Duration::ZERO,
+            service_time: Duration::from_millis(300),
+            slowdown: 4.0 / 3.0,
+            due_date: Some(Duration::from_millis(300)),
+            tardiness: Duration::from_millis(200),
+            station_times: Vec::new(),
+            visit_times: Vec::new(),
+            visit_resources: Vec::new(),
+            jockeys: 0,
+            slices: Vec::new(),
+            slice_units: Vec::new(),
+            slice_counts: Vec::new(),
+            timeline: Vec::new(),
+            config_epoch: 0,
+            loss: None,
+            replaces: None,
+            replaced_by: None,
+            depends_on: Vec::new(),
+        };
+        assert!((MetricsCalculator::lateness(&metrics) - 0.2).abs() < 1e-9);
+
+        let early = ProductMetrics {
+            due_date: Some(Duration::from_millis(1000)),
+            ..metrics.clone()
+        };
+        assert!((MetricsCalculator::lateness(&early) + 0.5).abs() < 1e-9);
+
+        let no_due_date = ProductMetrics { due_date: None, ..metrics };
+        assert_eq!(MetricsCalculator::lateness(&no_due_date), 0.0);
+    }
+
+    #[test]
+    fn test_fairness_index() {
+        let product = |turnaround_ms: u64, service_ms: u64| ProductMetrics {
+            product_id: 1,
+            class: None,
+            metadata: HashMap::new(),
+            arrival_time: Duration::ZERO,
+            total_wait_time: Duration::ZERO,
+            dependency_wait_time: Duration::ZERO,
+            resource_wait_time: Duration::ZERO,
+            turnaround_time: Duration::from_millis(turnaround_ms),
+            timer_drift: Duration::ZERO,
+            service_time: Duration::from_millis(service_ms),
+            slowdown: MetricsCalculator::slowdown(
+                Duration::from_millis(turnaround_ms),
+                Duration::from_millis(service_ms),
+            ),
+            due_date: None,
+            tardiness: Duration::ZERO,
+            station_times: Vec::new(),
+            visit_times: Vec::new(),
+            visit_resources: Vec::new(),
+            jockeys: 0,
+            slices: Vec::new(),
+            slice_units: Vec::new(),
+            slice_counts: Vec::new(),
+            timeline: Vec::new(),
+            config_epoch: 0,
+            loss: None,
+            replaces: None,
+            replaced_by: None,
+            depends_on: Vec::new(),
+        };
+
+        // Mismo turnaround relativo a la demanda: equidad perfecta
+        let equal = [product(200, 100), product(800, 400)];
+        assert!((MetricsCalculator::fairness_index(&equal) - 1.0).abs() < 1e-9);
+
+        // Turnaround normalizado 1 y 3: (1 + 3)² / (2 · (1 + 9)) = 0.8
+        let unequal = [product(100, 100), product(300, 100), product(50, 0)];
+        assert!((MetricsCalculator::fairness_index(&unequal) - 0.8).abs() < 1e-9);
+        assert_eq!(MetricsCalculator::fairness_index(&[]), 1.0);
+    }
+
+    #[test]
+    fn test_kendall_tau_distance() {
+        assert_eq!(MetricsCalculator::kendall_tau_distance(&[], &[]), 0);
+        assert_eq!(MetricsCalculator::kendall_tau_distance(&[1, 2, 3, 4], &[2, 1, 4, 3]), 2);
+        assert_eq!(MetricsCalculator::kendall_tau_distance(&[1, 2, 3, 4, 5], &[5, 4, 3, 2, 1]), 10);
+        // El producto 9 no está en la referencia y se ignora
+        assert_eq!(MetricsCalculator::kendall_tau_distance(&[1, 2, 3], &[3, 9, 1, 2]), 2);
+
+        let reversed: Vec<usize> = (0..1000).rev().collect();
+        let ordered: Vec<usize> = (0..1000).collect();
+        assert_eq!(MetricsCalculator::kendall_tau_distance(&ordered, &reversed), 1000 * 999 / 2);
+    }
+
+    #[test]
+    fn test_slowdown() {
+        let slowdown = |turnaround_ms, service_ms| {
+            MetricsCalculator::slowdown(Duration::from_millis(turnaround_ms), Duration::from_millis(service_ms))
+        };
+        assert_eq!(slowdown(400, 400), 1.0);
+        assert_eq!(slowdown(900, 300), 3.0);
+        assert_eq!(slowdown(50, 0), 1.0);
+    }
+
+    #[test]
+    fn test_format_metadata_field() {
+        let mut metadata = HashMap::new();
+        assert_eq!(MetricsCalculator::format_metadata_field(&metadata), "");
+
+        metadata.insert("sku".to_string(), "A-1".to_string());
+        metadata.insert("orden".to_string(), "42".to_string());
+        assert_eq!(MetricsCalculator::format_metadata_field(&metadata), "orden=42;sku=A-1");
+
+        metadata.insert("cliente".to_string(), "Pérez, \"JP\"".to_string());
+        assert_eq!(
+            MetricsCalculator::format_metadata_field(&metadata),
+            "\"cliente=Pérez, \"\"JP\"\";orden=42;sku=A-1\""
+        );
+    }
+
+    #[test]
+    fn test_histogram_display() {
+        let histogram = Histogram {
+            metric: DistributionMetric::Turnaround,
+            bucket_width: Duration::from_millis(100),
+            counts: vec![4, 0, 1],
+        };
+        assert_eq!(histogram.total(), 5);
+
+        let text = histogram.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Turnaround (intervalos de 0.100s):");
+        assert!(lines[1].contains("0.000s -    0.100s |"));
+        assert!(lines[1].ends_with(&format!("{} 4", "#".repeat(40))));
+        assert!(lines[2].ends_with(&format!("|{} 0", " ".repeat(41))));
+        // Un intervalo con un solo producto muestra al menos su parte de la barra
+        assert!(lines[3].ends_with(&format!("| {}{} 1", "#".repeat(10), " ".repeat(30))));
+    }
+
+    #[test]
+    fn test_wait_matrix_marginals_and_exports() {
+        let millis = Duration::from_millis;
+        let matrix = WaitMatrix {
+            stations: vec!["Corte".to_string(), "Pintura, final".to_string()],
+            product_ids: vec![1, 2],
+            waits: vec![vec![millis(0), millis(100)], vec![millis(50), millis(250)]],
+        };
+        assert_eq!(matrix.row_totals(), vec![millis(100), millis(300)]);
+        assert_eq!(matrix.column_totals(), vec![millis(50), millis(350)]);
+        assert_eq!(matrix.total(), millis(400));
+        assert_eq!(matrix.to_csv(), "Product,Corte,\"Pintura, final\"\n1,0.000,0.100\n2,0.050,0.250\n");
+        assert_eq!(
+            matrix.to_json(),
+            "{\"stations\":[\"Corte\",\"Pintura, final\"],\"products\":[1,2],\"waits\":[[0.000,0.100],[0.050,0.250]],\"row_totals\":[0.100,0.300],\"column_totals\":[0.050,0.350]}"
+        );
+    }
+
     #[test]
     fn test_metrics_calculator_creation() {
-        let calculator = MetricsCalculator::new();
-        let default_calculator = MetricsCalculator::default();
-        
         // Simplemente verificar que se pueden crear
-        drop(calculator);
-        drop(default_calculator);
+        let _calculator = MetricsCalculator::new();
+        let _default_calculator: MetricsCalculator = Default::default();
     }
 }
\ No newline at end of file
@@ -0,0 +1,633 @@
+//! # Módulo de Optimización de la Línea
+//! 
+//! Este módulo busca la configuración de la línea que minimiza un objetivo
+//! ponderado de espera, turnaround y energía. El espacio de búsqueda
+//! combina dos dimensiones:
+//! 
+//! - **Reparto del tiempo de procesamiento**: el tiempo total de la línea
+//!   se mantiene fijo y se transfiere entre estaciones en pasos de `step`,
+//!   como si se moviera personal o equipamiento de una estación a otra.
+//! - **Algoritmo de planificación**: el algoritmo general se elige entre
+//!   una lista de candidatos (por ejemplo, FCFS y Round Robin con varios
+//!   quanta).
+//! 
+//! Cada configuración se evalúa con una ejecución en tiempo virtual
+//! ([`Simulation::run_virtual`]) y los resultados se reutilizan si la
+//! búsqueda vuelve a visitarla. Hay dos estrategias: recocido simulado, que
+//! recorre el espacio con movimientos vecinos, y un algoritmo genético, que
+//! combina las mejores configuraciones de cada generación. Ambas son
+//! reproducibles con la misma semilla y registran su historial para graficar
+//! la convergencia.
+
+use std::time::Duration;
+
+use crate::error::SimulationError;
+use crate::metrics::SimulationMetrics;
+use crate::rng::SimRng;
+use crate::scheduler::SchedulingAlgorithm;
+use crate::simulation::Simulation;
+
+/// Objetivo ponderado que el optimizador minimiza.
+/// 
+/// El valor es `wait_weight · espera promedio + turnaround_weight ·
+/// turnaround promedio + energy_weight · energía`, con los tiempos en
+/// segundos. La energía se estima a partir de los reportes de las
+/// estaciones: cada estación consume `busy_power` mientras procesa y
+/// `idle_power` mientras está inactiva o bloqueada (en watts, por lo que
+/// la energía queda en joules).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineObjective {
+    /// Peso de la espera promedio
+    pub wait_weight: f64,
+    /// Peso del turnaround promedio
+    pub turnaround_weight: f64,
+    /// Peso de la energía consumida
+    pub energy_weight: f64,
+    /// Potencia de una estación mientras procesa (W)
+    pub busy_power: f64,
+    /// Potencia de una estación mientras está inactiva o bloqueada (W)
+    pub idle_power: f64,
+}
+
+impl Default for LineObjective {
+    /// Minimiza solo la espera promedio.
+    fn default() -> Self {
+        Self {
+            wait_weight: 1.0,
+            turnaround_weight: 0.0,
+            energy_weight: 0.0,
+            busy_power: 1.0,
+            idle_power: 0.2,
+        }
+    }
+}
+
+impl LineObjective {
+    /// Crea un objetivo con los pesos indicados y las potencias por defecto.
+    pub fn weighted(wait_weight: f64, turnaround_weight: f64, energy_weight: f64) -> Self {
+        Self {
+            wait_weight,
+            turnaround_weight,
+            energy_weight,
+            ..Self::default()
+        }
+    }
+
+    /// Indica la potencia de las estaciones ocupadas e inactivas (en watts).
+    pub fn with_power(mut self, busy_power: f64, idle_power: f64) -> Self {
+        self.busy_power = busy_power;
+        self.idle_power = idle_power;
+        self
+    }
+
+    /// Estima la energía consumida por las estaciones (en joules).
+    pub fn energy(&self, metrics: &SimulationMetrics) -> f64 {
+        metrics
+            .station_reports
+            .iter()
+            .map(|report| {
+                report.busy_time.as_secs_f64() * self.busy_power
+                    + (report.idle_time + report.blocked_time).as_secs_f64() * self.idle_power
+            })
+            .sum()
+    }
+
+    /// Obtiene el valor del objetivo en los resultados de una simulación.
+    pub fn value(&self, metrics: &SimulationMetrics) -> f64 {
+        let mut value = self.wait_weight * metrics.average_wait_time.as_secs_f64()
+            + self.turnaround_weight * metrics.average_turnaround_time.as_secs_f64();
+        if self.energy_weight != 0.0 {
+            value += self.energy_weight * self.energy(metrics);
+        }
+        value
+    }
+}
+
+/// Estrategia de búsqueda del optimizador.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineSearch {
+    /// Recocido simulado: en cada iteración evalúa una configuración vecina
+    /// y la acepta si mejora, o con probabilidad `exp(-Δ / T)` si empeora,
+    /// donde `Δ` es el empeoramiento relativo al valor inicial y `T` la
+    /// temperatura
+    Annealing {
+        /// Número de configuraciones vecinas evaluadas
+        iterations: usize,
+        /// Temperatura inicial
+        initial_temperature: f64,
+        /// Factor por el que se multiplica la temperatura en cada iteración
+        cooling: f64,
+    },
+    /// Algoritmo genético: cada generación conserva las dos mejores
+    /// configuraciones y completa la población cruzando padres elegidos
+    /// por torneo
+    Genetic {
+        /// Configuraciones por generación
+        population: usize,
+        /// Número de generaciones después de la inicial
+        generations: usize,
+        /// Probabilidad de mutar cada hijo
+        mutation_rate: f64,
+    },
+}
+
+/// Configuración de la línea evaluada por el optimizador.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineConfiguration {
+    /// Tiempo de procesamiento de cada estación
+    pub processing_times: Vec<Duration>,
+    /// Algoritmo de planificación general
+    pub algorithm: SchedulingAlgorithm,
+}
+
+impl LineConfiguration {
+    /// Obtiene la configuración actual de una simulación.
+    pub fn of(simulation: &Simulation) -> Self {
+        Self {
+            processing_times: simulation.station_configs.iter().map(|config| config.processing_time).collect(),
+            algorithm: simulation.algorithm.clone(),
+        }
+    }
+
+    /// Aplica la configuración a una simulación.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la simulación no tiene tantas estaciones como tiempos
+    /// de procesamiento la configuración
+    pub fn apply(&self, simulation: &mut Simulation) {
+        assert_eq!(
+            simulation.station_configs.len(),
+            self.processing_times.len(),
+            "La configuración no corresponde a las estaciones de la simulación"
+        );
+        for (config, &time) in simulation.station_configs.iter_mut().zip(&self.processing_times) {
+            config.processing_time = time;
+        }
+        simulation.algorithm = self.algorithm.clone();
+    }
+}
+
+/// Registro de una iteración de la búsqueda.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptimizationStep {
+    /// Iteración (recocido) o generación (genético), desde 0 para la
+    /// configuración inicial
+    pub iteration: usize,
+    /// Valor de la configuración evaluada en la iteración (recocido) o de
+    /// la mejor de la generación (genético)
+    pub value: f64,
+    /// Mejor valor encontrado hasta la iteración
+    pub best: f64,
+}
+
+/// Mejor configuración encontrada y el historial de la búsqueda.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineOptimization {
+    /// Configuración con el menor valor del objetivo
+    pub configuration: LineConfiguration,
+    /// Valor del objetivo con la configuración recomendada
+    pub value: f64,
+    /// Valor del objetivo con la configuración inicial
+    pub initial_value: f64,
+    /// Historial de la búsqueda, una entrada por iteración o generación
+    pub history: Vec<OptimizationStep>,
+    /// Número de simulaciones ejecutadas (sin contar las reutilizadas)
+    pub evaluations: usize,
+}
+
+impl LineOptimization {
+    /// Exporta el historial en CSV para graficar la convergencia.
+    pub fn history_csv(&self) -> String {
+        let mut csv = String::from("Iteration,Value,Best\n");
+        for step in &self.history {
+            csv.push_str(&format!("{},{:.6},{:.6}\n", step.iteration, step.value, step.best));
+        }
+        csv
+    }
+}
+
+/// Semilla por defecto del optimizador.
+const DEFAULT_SEED: u64 = 0x5EED;
+
+/// Optimizador de la configuración de la línea.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{LineObjective, LineOptimizer, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let millis = Duration::from_millis;
+/// let stations = vec![StationConfig::new("Corte", millis(50)), StationConfig::new("Empaque", millis(10))];
+/// let arrivals = (0..5).map(|i| millis(30 * i)).collect();
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+/// 
+/// let result = LineOptimizer::annealing(40)
+///     .minimizing(LineObjective::weighted(1.0, 0.0, 0.0))
+///     .allocating(millis(5), millis(5))
+///     .with_algorithms(vec![SchedulingAlgorithm::fcfs(), SchedulingAlgorithm::round_robin(millis(10))])
+///     .optimize(&mut simulation)
+///     .expect("La búsqueda falló");
+/// 
+/// // Equilibrar las estaciones reduce la espera en la primera
+/// assert!(result.value < result.initial_value);
+/// assert_eq!(result.configuration.processing_times.iter().sum::<Duration>(), millis(60));
+/// assert_eq!(result.history.len(), 41);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineOptimizer {
+    /// Estrategia de búsqueda
+    pub search: LineSearch,
+    /// Objetivo a minimizar
+    pub objective: LineObjective,
+    /// Paso y tiempo mínimo por estación con los que se reparte el tiempo
+    /// de procesamiento; `None` conserva los tiempos de la simulación
+    pub allocation: Option<(Duration, Duration)>,
+    /// Algoritmos candidatos; vacío conserva el algoritmo de la simulación
+    pub algorithms: Vec<SchedulingAlgorithm>,
+    /// Semilla del generador pseudoaleatorio
+    pub seed: u64,
+}
+
+impl LineOptimizer {
+    /// Crea un optimizador por recocido simulado.
+    /// 
+    /// La temperatura inicial es 0.1 y se enfría hasta el 1% de ese valor en
+    /// la última iteración.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `iterations` es cero
+    pub fn annealing(iterations: usize) -> Self {
+        assert!(iterations > 0, "El recocido necesita al menos una iteración");
+        Self::with_search(LineSearch::Annealing {
+            iterations,
+            initial_temperature: 0.1,
+            cooling: 0.01f64.powf(1.0 / iterations as f64),
+        })
+    }
+
+    /// Crea un optimizador genético.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `population` es menor que 2 o `mutation_rate` no está
+    /// entre 0 y 1
+    pub fn genetic(population: usize, generations: usize, mutation_rate: f64) -> Self {
+        assert!(population >= 2, "La población debe tener al menos dos configuraciones");
+        assert!((0.0..=1.0).contains(&mutation_rate), "La tasa de mutación debe estar entre 0 y 1");
+        Self::with_search(LineSearch::Genetic {
+            population,
+            generations,
+            mutation_rate,
+        })
+    }
+
+    /// Crea un optimizador con la estrategia indicada.
+    fn with_search(search: LineSearch) -> Self {
+        Self {
+            search,
+            objective: LineObjective::default(),
+            allocation: None,
+            algorithms: Vec::new(),
+            seed: DEFAULT_SEED,
+        }
+    }
+
+    /// Indica el objetivo a minimizar (por defecto, la espera promedio).
+    pub fn minimizing(mut self, objective: LineObjective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Habilita el reparto del tiempo de procesamiento entre estaciones.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `step` - Tiempo que se transfiere en cada movimiento
+    /// * `min` - Tiempo mínimo de procesamiento de cada estación
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `step` es cero
+    pub fn allocating(mut self, step: Duration, min: Duration) -> Self {
+        assert!(!step.is_zero(), "El paso del reparto debe ser mayor que cero");
+        self.allocation = Some((step, min));
+        self
+    }
+
+    /// Indica los algoritmos candidatos para el algoritmo general.
+    pub fn with_algorithms(mut self, algorithms: Vec<SchedulingAlgorithm>) -> Self {
+        self.algorithms = algorithms;
+        self
+    }
+
+    /// Indica la semilla del generador pseudoaleatorio.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Busca la configuración recomendada para el escenario de la simulación.
+    /// 
+    /// La búsqueda parte de la configuración actual de la simulación; los
+    /// algoritmos propios de cada estación y los tiempos de los tipos de
+    /// producto del catálogo se conservan. Al terminar, la simulación
+    /// recupera su configuración original; para usar la recomendada, se
+    /// aplica con [`LineConfiguration::apply`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `simulation` - Escenario a optimizar
+    /// 
+    /// # Returns
+    /// 
+    /// La mejor configuración con el historial de la búsqueda, o el error de
+    /// la primera ejecución que falló
+    pub fn optimize(&self, simulation: &mut Simulation) -> Result<LineOptimization, SimulationError> {
+        let original = LineConfiguration::of(simulation);
+        let mut search = Search {
+            optimizer: self,
+            rng: SimRng::new(self.seed),
+            cache: Vec::new(),
+        };
+        let result = match self.search {
+            LineSearch::Annealing {
+                iterations,
+                initial_temperature,
+                cooling,
+            } => search.anneal(simulation, &original, iterations, initial_temperature, cooling),
+            LineSearch::Genetic {
+                population,
+                generations,
+                mutation_rate,
+            } => search.evolve(simulation, &original, population, generations, mutation_rate),
+        };
+        original.apply(simulation);
+        let history = result?;
+
+        let initial_value = search.cache[0].1;
+        let (configuration, value) = search
+            .cache
+            .iter()
+            .fold(None::<&(LineConfiguration, f64)>, |best, entry| match best {
+                Some(best) if best.1 <= entry.1 => Some(best),
+                _ => Some(entry),
+            })
+            .cloned()
+            .expect("La búsqueda evalúa al menos la configuración inicial");
+        Ok(LineOptimization {
+            configuration,
+            value,
+            initial_value,
+            history,
+            evaluations: search.cache.len(),
+        })
+    }
+}
+
+/// Estado de una búsqueda en curso.
+struct Search<'a> {
+    optimizer: &'a LineOptimizer,
+    rng: SimRng,
+    /// Configuraciones evaluadas con su valor, en orden de evaluación
+    cache: Vec<(LineConfiguration, f64)>,
+}
+
+impl Search<'_> {
+    /// Recocido simulado a partir de la configuración inicial.
+    fn anneal(
+        &mut self,
+        simulation: &mut Simulation,
+        initial: &LineConfiguration,
+        iterations: usize,
+        initial_temperature: f64,
+        cooling: f64,
+    ) -> Result<Vec<OptimizationStep>, SimulationError> {
+        let mut current = initial.clone();
+        let mut current_value = self.evaluate(simulation, &current)?;
+        let scale = current_value.abs().max(f64::EPSILON);
+        let mut best = current_value;
+        let mut history = vec![OptimizationStep { iteration: 0, value: current_value, best }];
+
+        let mut temperature = initial_temperature;
+        for iteration in 1..=iterations {
+            let candidate = self.neighbor(&current);
+            let value = self.evaluate(simulation, &candidate)?;
+            let delta = (value - current_value) / scale;
+            if delta <= 0.0 || (temperature > 0.0 && self.rng.next_f64() < (-delta / temperature).exp()) {
+                current = candidate;
+                current_value = value;
+            }
+            best = best.min(value);
+            history.push(OptimizationStep { iteration, value, best });
+            temperature *= cooling;
+        }
+        Ok(history)
+    }
+
+    /// Algoritmo genético a partir de la configuración inicial.
+    fn evolve(
+        &mut self,
+        simulation: &mut Simulation,
+        initial: &LineConfiguration,
+        population_size: usize,
+        generations: usize,
+        mutation_rate: f64,
+    ) -> Result<Vec<OptimizationStep>, SimulationError> {
+        // La población inicial se dispersa alrededor de la configuración actual
+        let mut population = vec![initial.clone()];
+        while population.len() < population_size {
+            let mut individual = initial.clone();
+            for _ in 0..=population.len() {
+                individual = self.neighbor(&individual);
+            }
+            population.push(individual);
+        }
+
+        let mut history = Vec::with_capacity(generations + 1);
+        let mut best = f64::INFINITY;
+        for generation in 0..=generations {
+            let mut scored = Vec::with_capacity(population.len());
+            for individual in population {
+                let value = self.evaluate(simulation, &individual)?;
+                scored.push((individual, value));
+            }
+            scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+            best = best.min(scored[0].1);
+            history.push(OptimizationStep { iteration: generation, value: scored[0].1, best });
+
+            population = scored.iter().take(2).map(|(individual, _)| individual.clone()).collect();
+            while population.len() < population_size {
+                let first = self.tournament(&scored);
+                let second = self.tournament(&scored);
+                let mut child = self.crossover(first, second);
+                if self.rng.next_f64() < mutation_rate {
+                    child = self.neighbor(&child);
+                }
+                population.push(child);
+            }
+        }
+        Ok(history)
+    }
+
+    /// Elige el mejor de dos individuos al azar.
+    fn tournament<'s>(&mut self, scored: &'s [(LineConfiguration, f64)]) -> &'s LineConfiguration {
+        let first = self.index(scored.len());
+        let second = self.index(scored.len());
+        &scored[first.min(second)].0
+    }
+
+    /// Combina dos configuraciones: cada estación toma el tiempo de uno de
+    /// los padres y luego se corrige el total para conservar el presupuesto.
+    fn crossover(&mut self, first: &LineConfiguration, second: &LineConfiguration) -> LineConfiguration {
+        let mut processing_times: Vec<Duration> = first
+            .processing_times
+            .iter()
+            .zip(&second.processing_times)
+            .map(|(&a, &b)| if self.rng.next_f64() < 0.5 { a } else { b })
+            .collect();
+        if let Some((step, min)) = self.optimizer.allocation {
+            let budget: Duration = first.processing_times.iter().sum();
+            // Ambos padres parten del mismo reparto con transferencias de
+            // `step`, así que la diferencia es siempre un múltiplo del paso
+            while processing_times.iter().sum::<Duration>() < budget {
+                let station = self.index(processing_times.len());
+                processing_times[station] += step;
+            }
+            while processing_times.iter().sum::<Duration>() > budget {
+                let donors: Vec<usize> = (0..processing_times.len())
+                    .filter(|&station| processing_times[station] >= min + step)
+                    .collect();
+                let station = donors[self.index(donors.len())];
+                processing_times[station] -= step;
+            }
+        }
+        let algorithm = if self.rng.next_f64() < 0.5 { &first.algorithm } else { &second.algorithm };
+        LineConfiguration {
+            processing_times,
+            algorithm: algorithm.clone(),
+        }
+    }
+
+    /// Genera una configuración vecina: transfiere un paso de tiempo entre
+    /// dos estaciones o cambia el algoritmo por otro candidato.
+    fn neighbor(&mut self, configuration: &LineConfiguration) -> LineConfiguration {
+        let mut neighbor = configuration.clone();
+        let donors: Vec<usize> = match self.optimizer.allocation {
+            Some((step, min)) if neighbor.processing_times.len() > 1 => (0..neighbor.processing_times.len())
+                .filter(|&station| neighbor.processing_times[station] >= min + step)
+                .collect(),
+            _ => Vec::new(),
+        };
+        let alternatives: Vec<&SchedulingAlgorithm> = self
+            .optimizer
+            .algorithms
+            .iter()
+            .filter(|&algorithm| *algorithm != neighbor.algorithm)
+            .collect();
+
+        let change_algorithm = match (donors.is_empty(), alternatives.is_empty()) {
+            (true, true) => return neighbor,
+            (true, false) => true,
+            (false, true) => false,
+            (false, false) => self.rng.next_f64() < 0.5,
+        };
+        if change_algorithm {
+            neighbor.algorithm = alternatives[self.index(alternatives.len())].clone();
+        } else {
+            let (step, _) = self.optimizer.allocation.expect("Hay donantes solo con reparto habilitado");
+            let donor = donors[self.index(donors.len())];
+            let receiver = (donor + 1 + self.index(neighbor.processing_times.len() - 1)) % neighbor.processing_times.len();
+            neighbor.processing_times[donor] -= step;
+            neighbor.processing_times[receiver] += step;
+        }
+        neighbor
+    }
+
+    /// Índice uniforme en `[0, len)`.
+    fn index(&mut self, len: usize) -> usize {
+        (self.rng.next_u64() % len as u64) as usize
+    }
+
+    /// Evalúa una configuración, reutilizando el resultado si ya se evaluó.
+    fn evaluate(&mut self, simulation: &mut Simulation, configuration: &LineConfiguration) -> Result<f64, SimulationError> {
+        if let Some((_, value)) = self.cache.iter().find(|(evaluated, _)| evaluated == configuration) {
+            return Ok(*value);
+        }
+        configuration.apply(simulation);
+        let value = self.optimizer.objective.value(&simulation.run_virtual()?);
+        self.cache.push((configuration.clone(), value));
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::station::StationConfig;
+
+    fn unbalanced_line() -> Simulation {
+        let millis = Duration::from_millis;
+        let stations = vec![
+            StationConfig::new("Corte", millis(60)),
+            StationConfig::new("Ensamblaje", millis(10)),
+            StationConfig::new("Empaque", millis(20)),
+        ];
+        let arrivals = (0..6).map(|i| millis(30 * i)).collect();
+        Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals)
+    }
+
+    #[test]
+    fn test_annealing_balances_line_and_restores_simulation() {
+        let millis = Duration::from_millis;
+        let mut simulation = unbalanced_line();
+        let optimizer = LineOptimizer::annealing(60).allocating(millis(10), millis(10));
+        let result = optimizer.optimize(&mut simulation).unwrap();
+
+        // Con llegadas cada 30 ms, ninguna estación debería superar ese tiempo
+        assert!(result.value < result.initial_value);
+        assert!(result.configuration.processing_times.iter().all(|&time| time <= millis(30)));
+        assert_eq!(result.configuration.processing_times.iter().sum::<Duration>(), millis(90));
+        assert_eq!(result.history.len(), 61);
+        assert!(result.history.windows(2).all(|pair| pair[1].best <= pair[0].best));
+        assert_eq!(result.history.last().unwrap().best, result.value);
+        assert_eq!(LineConfiguration::of(&simulation).processing_times, vec![millis(60), millis(10), millis(20)]);
+
+        // Con la misma semilla la búsqueda es reproducible
+        assert_eq!(optimizer.optimize(&mut simulation).unwrap(), result);
+    }
+
+    #[test]
+    fn test_genetic_search_over_allocation_and_algorithms() {
+        let millis = Duration::from_millis;
+        let mut simulation = unbalanced_line();
+        let result = LineOptimizer::genetic(6, 8, 0.5)
+            .minimizing(LineObjective::weighted(1.0, 1.0, 0.1))
+            .allocating(millis(10), millis(10))
+            .with_algorithms(vec![SchedulingAlgorithm::fcfs(), SchedulingAlgorithm::round_robin(millis(20))])
+            .with_seed(3)
+            .optimize(&mut simulation)
+            .unwrap();
+
+        assert!(result.value < result.initial_value);
+        assert_eq!(result.configuration.processing_times.iter().sum::<Duration>(), millis(90));
+        assert!(result.configuration.processing_times.iter().all(|&time| time >= millis(10)));
+        assert_eq!(result.history.len(), 9);
+        assert!(result.evaluations <= 6 * 9);
+        assert!(result.history_csv().starts_with("Iteration,Value,Best\n0,"));
+        assert_eq!(simulation.algorithm, SchedulingAlgorithm::fcfs());
+    }
+
+    #[test]
+    fn test_objective_weights_energy() {
+        let metrics = unbalanced_line().run_virtual().unwrap();
+        let objective = LineObjective::weighted(0.0, 0.0, 1.0).with_power(2.0, 0.0);
+
+        // Sin potencia en reposo, la energía es el doble del trabajo total
+        let busy: f64 = metrics.station_reports.iter().map(|report| report.busy_time.as_secs_f64()).sum();
+        assert!((objective.value(&metrics) - 2.0 * busy).abs() < 1e-9);
+        assert!((LineObjective::default().value(&metrics) - metrics.average_wait_time.as_secs_f64()).abs() < 1e-9);
+    }
+}
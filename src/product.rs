@@ -3,11 +3,189 @@
 //! Este módulo define la estructura de los productos que atraviesan la línea
 //! de ensamblaje y las métricas asociadas a su procesamiento.
 
-use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::Duration;
 
+use crate::catalog::ProductType;
+use crate::routing::{self, ResolvedRoute};
 use crate::station::{StationConfig, StationState};
 
+/// Obtiene el lock de un mutex, recuperando su contenido si quedó envenenado.
+/// 
+/// Si una estación hace panic mientras actualiza las métricas de un producto,
+/// el mutex queda envenenado. Las métricas registradas hasta ese momento
+/// siguen siendo útiles para el reporte parcial, por lo que se recuperan en
+/// lugar de propagar el panic.
+pub(crate) fn lock_recover<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Especificación de un producto que se introduce en la línea.
+/// 
+/// Se utiliza para describir productos inyectados dinámicamente durante
+/// la ejecución (por ejemplo, pedidos urgentes) sin conocer aún su
+/// identificador ni su momento de llegada.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProductSpec {
+    /// Prioridad del producto (mayor valor = se atiende antes en las colas)
+    pub priority: u32,
+    /// Fecha de entrega relativa al inicio de la simulación
+    pub due_date: Option<Duration>,
+    /// Clase del producto (por ejemplo, la fuente o familia a la que pertenece)
+    pub class: Option<String>,
+    /// Metadatos libres del usuario (número de orden, SKU, cliente, etc.)
+    pub metadata: HashMap<String, String>,
+    /// Nombre del tipo de producto del catálogo de la simulación, si tiene uno
+    pub product_type: Option<String>,
+    /// Tiempo máximo que el producto espera en la cola de una estación antes
+    /// de abandonarla; `None` usa la paciencia general de la simulación
+    pub patience: Option<Duration>,
+    /// Tamaño o complejidad relativa del producto, que escala su tiempo de
+    /// procesamiento según la sensibilidad de cada estación; `None` equivale
+    /// a 1.0
+    pub size: Option<f64>,
+}
+
+impl ProductSpec {
+    /// Crea una especificación con la prioridad indicada.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::ProductSpec;
+    /// 
+    /// let rush = ProductSpec::with_priority(10);
+    /// assert_eq!(rush.priority, 10);
+    /// ```
+    pub fn with_priority(priority: u32) -> Self {
+        Self {
+            priority,
+            ..Self::default()
+        }
+    }
+
+    /// Crea una especificación que referencia un tipo del catálogo de productos.
+    /// 
+    /// El tipo determina la ruta, los tiempos de servicio y la prioridad por
+    /// defecto del producto (ver [`ProductType`](crate::ProductType)).
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::ProductSpec;
+    /// 
+    /// let spec = ProductSpec::of_type("sedan");
+    /// assert_eq!(spec.product_type.as_deref(), Some("sedan"));
+    /// ```
+    pub fn of_type(product_type: impl Into<String>) -> Self {
+        Self {
+            product_type: Some(product_type.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Asigna una fecha de entrega relativa al inicio de la simulación.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::ProductSpec;
+    /// 
+    /// let spec = ProductSpec::with_priority(5).due_at(Duration::from_secs(2));
+    /// assert_eq!(spec.due_date, Some(Duration::from_secs(2)));
+    /// ```
+    pub fn due_at(mut self, due_date: Duration) -> Self {
+        self.due_date = Some(due_date);
+        self
+    }
+
+    /// Asigna la clase del producto.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::ProductSpec;
+    /// 
+    /// let spec = ProductSpec::default().with_class("retrabajo");
+    /// assert_eq!(spec.class.as_deref(), Some("retrabajo"));
+    /// ```
+    pub fn with_class(mut self, class: impl Into<String>) -> Self {
+        self.class = Some(class.into());
+        self
+    }
+
+    /// Agrega un metadato al producto.
+    /// 
+    /// Los metadatos no afectan la simulación; se copian a las métricas del
+    /// producto y a los reportes exportados.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::ProductSpec;
+    /// 
+    /// let spec = ProductSpec::default().with_metadata("orden", "OC-1042");
+    /// assert_eq!(spec.metadata.get("orden").map(String::as_str), Some("OC-1042"));
+    /// ```
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Asigna la paciencia del producto.
+    /// 
+    /// Si el producto espera en la cola de una estación más que su paciencia
+    /// sin comenzar a procesarse, la abandona y se reporta como perdido.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::ProductSpec;
+    /// 
+    /// let spec = ProductSpec::default().with_patience(Duration::from_millis(500));
+    /// assert_eq!(spec.patience, Some(Duration::from_millis(500)));
+    /// ```
+    pub fn with_patience(mut self, patience: Duration) -> Self {
+        self.patience = Some(patience);
+        self
+    }
+
+    /// Asigna el tamaño del producto.
+    /// 
+    /// En cada estación el tiempo de procesamiento se multiplica por
+    /// `tamaño ^ sensibilidad` (ver [`StationConfig::with_size_sensitivity`]).
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el tamaño no es un número positivo
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Product, ProductSpec, StationConfig};
+    /// 
+    /// let configs = vec![
+    ///     StationConfig::new("Corte", Duration::from_millis(100)),
+    ///     StationConfig::new("Empaque", Duration::from_millis(100)).with_size_sensitivity(0.0),
+    /// ];
+    /// let spec = ProductSpec::default().with_size(2.0);
+    /// let product = Product::from_spec(1, Duration::ZERO, &spec, &configs);
+    /// assert_eq!(product.processing_time(0), Duration::from_millis(200));
+    /// assert_eq!(product.processing_time(1), Duration::from_millis(100));
+    /// ```
+    pub fn with_size(mut self, size: f64) -> Self {
+        assert!(size.is_finite() && size > 0.0, "El tamaño del producto debe ser un número positivo");
+        self.size = Some(size);
+        self
+    }
+}
+
 /// Representa un producto que atraviesa la línea de ensamblaje.
 /// 
 /// Cada producto mantiene su identificador único, tiempo de llegada simulado,
@@ -23,10 +201,175 @@ pub struct Product {
     pub id: usize,
     /// Tiempo de llegada simulado relativo al inicio de la simulación
     pub arrival_offset: Duration,
-    /// Momento real en que el producto fue generado en la simulación
-    pub arrival_instant: Mutex<Option<Instant>>,
-    /// Estado y métricas del producto en cada estación de la línea
+    /// Prioridad del producto (mayor valor = se atiende antes en las colas)
+    pub priority: u32,
+    /// Fecha de entrega relativa al inicio de la simulación
+    pub due_date: Option<Duration>,
+    /// Clase del producto, si fue asignada
+    pub class: Option<String>,
+    /// Metadatos libres asignados por el usuario
+    pub metadata: HashMap<String, String>,
+    /// Nombre del tipo de producto, si fue creado a partir del catálogo
+    pub product_type: Option<String>,
+    /// Paciencia propia del producto en las colas, si tiene una
+    pub patience: Option<Duration>,
+    /// Tamaño relativo del producto (1.0 por defecto), ya aplicado a
+    /// `processing_times`
+    pub size: f64,
+    /// Índices de las estaciones que visita el producto, en orden (ver
+    /// [`Product::route`]); atómicos porque una visita puede cambiar de
+    /// estación si el producto cambia de cola
+    route: Vec<AtomicUsize>,
+    /// Indica si el producto no aprueba una inspección y sale de la línea
+    /// como rechazado al terminar su ruta (ver [`crate::routing`])
+    pub rejected: bool,
+    /// Tiempo de procesamiento del producto en cada estación, escalado por
+    /// su tamaño (cero en las estaciones que no forman parte de su ruta)
+    pub processing_times: Vec<Duration>,
+    /// Momento real en que el producto fue generado, relativo al inicio de
+    /// la simulación.
+    /// 
+    /// Se asigna una sola vez, por lo que puede leerse sin bloqueos desde
+    /// cualquier hilo (por ejemplo, al calcular el turnaround)
+    pub arrival_time: OnceLock<Duration>,
+    /// Estado y métricas del producto en cada visita de su ruta, en el
+    /// mismo orden que `route`.
+    /// 
+    /// Durante la simulación cada estado solo es modificado por el hilo de
+    /// su estación; los demás hilos consultan el progreso del producto a
+    /// través de contadores atómicos para no competir por estos locks
     pub stations: Vec<Mutex<StationState>>,
+    /// Número de visitas de la ruta que el producto ya completó
+    completed_stations: AtomicUsize,
+    /// Productos que deben completar una estación antes de que este producto
+    /// pueda iniciar su procesamiento en la primera estación
+    dependencies: Mutex<Vec<(Arc<Product>, usize)>>,
+    /// Pérdida del producto, si desistió, abandonó una cola o fue rechazado
+    loss: OnceLock<ProductLoss>,
+    /// Producto desechado al que este producto reemplaza
+    replaces: OnceLock<usize>,
+    /// Producto que reemplazó a este, si fue desechado y repuesto
+    replaced_by: OnceLock<usize>,
+    /// Veces que el producto cambió de cola (ver [`crate::jockey`])
+    jockeys: AtomicUsize,
+}
+
+/// Restricción de precedencia entre dos productos.
+/// 
+/// Indica que `product` no puede iniciar su procesamiento en la primera
+/// estación hasta que `depends_on` haya completado la estación `station`
+/// (por ejemplo, para modelar pares de piezas que se ensamblan juntas).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProductDependency {
+    /// ID del producto que debe esperar
+    pub product: usize,
+    /// ID del producto del que depende
+    pub depends_on: usize,
+    /// Índice de la estación (0-indexado) que `depends_on` debe completar
+    pub station: usize,
+}
+
+/// Tipo de intervalo en la línea de tiempo de un producto.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimelineEvent {
+    /// Espera en la cola de la estación (incluye el tiempo en su canal de
+    /// entrada y el bloqueo por dependencias o recursos)
+    Queued,
+    /// Porción de procesamiento con la que el producto completó la estación
+    Processing,
+    /// Porción de procesamiento interrumpida por el quantum de Round Robin;
+    /// el producto vuelve a la cola de la misma estación
+    Preempted,
+    /// Traslado desde la estación anterior de la ruta hasta la cola de esta
+    Transfer,
+}
+
+impl TimelineEvent {
+    /// Identificador estable del evento para exportaciones (JSON, SQLite).
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Processing => "processing",
+            Self::Preempted => "preempted",
+            Self::Transfer => "transfer",
+        }
+    }
+}
+
+impl fmt::Display for TimelineEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Queued => "en cola",
+            Self::Processing => "procesando",
+            Self::Preempted => "procesando (expropiado)",
+            Self::Transfer => "traslado",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Intervalo de la línea de tiempo de un producto.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimelineSpan {
+    /// Índice de la estación (0-indexado); en los traslados, la estación de destino
+    pub station: usize,
+    /// Qué hizo el producto durante el intervalo
+    pub event: TimelineEvent,
+    /// Inicio relativo al inicio de la simulación
+    pub start: Duration,
+    /// Fin relativo al inicio de la simulación
+    pub end: Duration,
+}
+
+impl TimelineSpan {
+    /// Duración del intervalo.
+    pub fn duration(&self) -> Duration {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+/// Motivo por el que un producto salió de la línea sin completarla.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LossReason {
+    /// El producto no se unió a la cola porque superaba el umbral de la estación
+    Balked,
+    /// El producto abandonó la cola tras esperar más que su paciencia
+    Reneged,
+    /// El producto no aprobó una inspección y salió de la línea
+    Rejected,
+}
+
+impl LossReason {
+    /// Identificador estable del motivo para exportaciones (JSON, SQLite).
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Balked => "balked",
+            Self::Reneged => "reneged",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+impl fmt::Display for LossReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::Balked => "desistió (cola llena)",
+            Self::Reneged => "abandonó la cola",
+            Self::Rejected => "rechazado",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Pérdida de un producto que salió de la línea sin completarla.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProductLoss {
+    /// Índice de la estación (0-indexado) donde se perdió el producto
+    pub station: usize,
+    /// Motivo de la pérdida
+    pub reason: LossReason,
+    /// Momento de la pérdida, relativo al inicio de la simulación
+    pub at: Duration,
 }
 
 impl Product {
@@ -49,29 +392,253 @@ impl Product {
     /// use assembly_line_simulator::{Product, StationConfig};
     /// 
     /// let configs = vec![
-    ///     StationConfig { name: "Corte", processing_time: Duration::from_millis(400) },
-    ///     StationConfig { name: "Ensamblaje", processing_time: Duration::from_millis(600) },
+    ///     StationConfig::new("Corte", Duration::from_millis(400)),
+    ///     StationConfig::new("Ensamblaje", Duration::from_millis(600)),
     /// ];
     /// 
     /// let product = Product::new(1, Duration::from_millis(100), &configs);
     /// assert_eq!(product.id, 1);
     /// ```
     pub fn new(id: usize, arrival_offset: Duration, station_configs: &[StationConfig]) -> Arc<Self> {
-        let stations = station_configs
+        Self::from_spec(id, arrival_offset, &ProductSpec::default(), station_configs)
+    }
+
+    /// Crea un nuevo producto a partir de una especificación.
+    /// 
+    /// El producto visita todas las estaciones, salvo que una inspección o
+    /// un divisor lo desvíe, o que una inspección lo rechace (ver
+    /// [`crate::routing`]).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `id` - Identificador único del producto
+    /// * `arrival_offset` - Tiempo de llegada simulado relativo al inicio
+    /// * `spec` - Especificación con los atributos del producto (prioridad, etc.)
+    /// * `station_configs` - Configuraciones de todas las estaciones en la línea
+    /// 
+    /// # Returns
+    /// 
+    /// Un `Arc<Product>` listo para ser compartido entre hilos
+    pub fn from_spec(
+        id: usize,
+        arrival_offset: Duration,
+        spec: &ProductSpec,
+        station_configs: &[StationConfig],
+    ) -> Arc<Self> {
+        let route = routing::resolve_route(
+            id,
+            (0..station_configs.len()).collect(),
+            spec.product_type.as_deref(),
+            &spec.metadata,
+            station_configs,
+        );
+        let size = spec.size.unwrap_or(1.0);
+        let processing_times = station_configs
             .iter()
-            .map(|_| Mutex::new(StationState::new()))
+            .map(|config| config.processing_time_for(size))
+            .collect();
+        Self::build(id, arrival_offset, spec, spec.priority, route, processing_times)
+    }
+
+    /// Crea un nuevo producto de un tipo del catálogo.
+    /// 
+    /// El producto sigue la ruta del tipo (con los desvíos que decidan las
+    /// inspecciones y los divisores) y usa sus tiempos de servicio; las
+    /// estaciones sin tiempo propio del tipo usan el de su configuración, y
+    /// ambos se escalan por el tamaño de la especificación. La
+    /// prioridad de la especificación tiene precedencia sobre la del tipo
+    /// cuando es distinta de cero, y el nombre del tipo se usa como clase si
+    /// la especificación no indica una.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `id` - Identificador único del producto
+    /// * `arrival_offset` - Tiempo de llegada simulado relativo al inicio
+    /// * `spec` - Especificación con los atributos del producto
+    /// * `product_type` - Tipo de producto
+    /// * `station_configs` - Configuraciones de todas las estaciones en la línea
+    /// 
+    /// # Returns
+    /// 
+    /// Un `Arc<Product>` listo para ser compartido entre hilos
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Product, ProductSpec, ProductType, StationConfig};
+    /// 
+    /// let configs = vec![
+    ///     StationConfig::new("Corte", Duration::from_millis(400)),
+    ///     StationConfig::new("Pintura", Duration::from_millis(600)),
+    /// ];
+    /// let chasis = ProductType::new("chasis")
+    ///     .with_route(vec![0])
+    ///     .with_service_time(0, Duration::from_millis(250));
+    /// 
+    /// let product = Product::from_type(1, Duration::ZERO, &ProductSpec::default(), &chasis, &configs);
+    /// assert!(!product.visits(1));
+    /// assert_eq!(product.processing_time(0), Duration::from_millis(250));
+    /// ```
+    pub fn from_type(
+        id: usize,
+        arrival_offset: Duration,
+        spec: &ProductSpec,
+        product_type: &ProductType,
+        station_configs: &[StationConfig],
+    ) -> Arc<Self> {
+        let base = product_type.route
+            .clone()
+            .unwrap_or_else(|| (0..station_configs.len()).collect());
+        let route = routing::resolve_route(id, base, Some(&product_type.name), &spec.metadata, station_configs);
+        let size = spec.size.unwrap_or(1.0);
+        let processing_times = station_configs
+            .iter()
+            .enumerate()
+            .map(|(index, config)| {
+                if !route.route.contains(&index) {
+                    Duration::ZERO
+                } else {
+                    product_type.service_times
+                        .get(&index)
+                        .copied()
+                        .unwrap_or(config.processing_time)
+                        .mul_f64(config.size_factor(size))
+                }
+            })
+            .collect();
+        let priority = if spec.priority != 0 {
+            spec.priority
+        } else {
+            product_type.priority
+        };
+        let spec = ProductSpec {
+            class: spec.class.clone().or_else(|| Some(product_type.name.clone())),
+            product_type: Some(product_type.name.clone()),
+            ..spec.clone()
+        };
+        Self::build(id, arrival_offset, &spec, priority, route, processing_times)
+    }
+
+    /// Construye el producto con la ruta y los tiempos ya resueltos.
+    fn build(
+        id: usize,
+        arrival_offset: Duration,
+        spec: &ProductSpec,
+        priority: u32,
+        route: ResolvedRoute,
+        processing_times: Vec<Duration>,
+    ) -> Arc<Self> {
+        let ResolvedRoute { route, inspections, outputs, rejected } = route;
+        let stations = inspections
+            .into_iter()
+            .zip(outputs)
+            .map(|(inspection, output)| Mutex::new(StationState { inspection, output, ..StationState::new() }))
             .collect();
 
         Arc::new(Self {
             id,
             arrival_offset,
-            arrival_instant: Mutex::new(None),
+            priority,
+            due_date: spec.due_date,
+            class: spec.class.clone(),
+            metadata: spec.metadata.clone(),
+            product_type: spec.product_type.clone(),
+            patience: spec.patience,
+            size: spec.size.unwrap_or(1.0),
+            route: route.into_iter().map(AtomicUsize::new).collect(),
+            rejected,
+            processing_times,
+            arrival_time: OnceLock::new(),
             stations,
+            completed_stations: AtomicUsize::new(0),
+            dependencies: Mutex::new(Vec::new()),
+            loss: OnceLock::new(),
+            replaces: OnceLock::new(),
+            replaced_by: OnceLock::new(),
+            jockeys: AtomicUsize::new(0),
         })
     }
 
+    /// Obtiene las estaciones que visita el producto, en orden.
+    /// 
+    /// Una estación puede aparecer más de una vez (rutas reentrantes). La
+    /// ruta se decide al crear el producto y solo cambia si el producto
+    /// deja la cola de una rama paralela por la de una rama hermana (ver
+    /// [`crate::jockey`]).
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Product, StationConfig};
+    /// 
+    /// let configs = vec![
+    ///     StationConfig::new("Corte", Duration::from_millis(100)),
+    ///     StationConfig::new("Empaque", Duration::from_millis(50)),
+    /// ];
+    /// let product = Product::new(1, Duration::ZERO, &configs);
+    /// assert_eq!(product.route(), vec![0, 1]);
+    /// assert_eq!(product.visit_station(1), Some(1));
+    /// ```
+    pub fn route(&self) -> Vec<usize> {
+        self.route_stations().collect()
+    }
+
+    /// Obtiene la estación de una visita de la ruta.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `visit` - Posición de la visita en la ruta (0-indexada)
+    /// 
+    /// # Returns
+    /// 
+    /// `None` si la posición está fuera de la ruta
+    pub fn visit_station(&self, visit: usize) -> Option<usize> {
+        self.route.get(visit).map(|station| station.load(Ordering::Acquire))
+    }
+
+    /// Recorre las estaciones de la ruta sin copiarla.
+    fn route_stations(&self) -> impl Iterator<Item = usize> + '_ {
+        self.route.iter().map(|station| station.load(Ordering::Acquire))
+    }
+
+    /// Obtiene las veces que el producto cambió de cola, entre ramas
+    /// paralelas o entre unidades de un recurso (ver [`crate::jockey`]).
+    pub fn jockeys(&self) -> usize {
+        self.jockeys.load(Ordering::Relaxed)
+    }
+
+    /// Cuenta cambios de cola del producto.
+    pub(crate) fn add_jockeys(&self, moves: usize) {
+        self.jockeys.fetch_add(moves, Ordering::Relaxed);
+    }
+
+    /// Traslada la visita en curso a otra estación, cuyo tiempo de
+    /// procesamiento se asignará al llegar a ella.
+    /// 
+    /// Solo debe usarse con productos que aún no comenzaron a procesarse en
+    /// la visita; la espera acumulada se conserva.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station` - Estación que atenderá la visita
+    /// * `output` - Salida por la que el producto dejará esa estación, si es un divisor
+    pub(crate) fn move_current_visit(&self, station: usize, output: Option<usize>) {
+        let visit = self.completed_visits();
+        let mut state = lock_recover(&self.stations[visit]);
+        state.remaining = Duration::ZERO;
+        state.output = output;
+        self.route[visit].store(station, Ordering::Release);
+        drop(state);
+        self.add_jockeys(1);
+    }
+
     /// Obtiene una referencia al estado protegido del producto en una estación específica.
     /// 
+    /// Si la ruta visita la estación más de una vez, corresponde a la
+    /// primera visita; el resto se obtiene con [`Product::visit_state`].
+    /// 
     /// # Arguments
     /// 
     /// * `index` - Índice de la estación (0-indexado)
@@ -82,72 +649,252 @@ impl Product {
     /// 
     /// # Panics
     /// 
-    /// Hace panic si el índice está fuera de rango
+    /// Hace panic si la estación no forma parte de la ruta del producto
     /// 
     /// # Examples
     /// 
     /// ```rust
     /// # use std::time::Duration;
     /// # use assembly_line_simulator::{Product, StationConfig};
-    /// # let configs = vec![StationConfig { name: "Test", processing_time: Duration::from_millis(100) }];
+    /// # let configs = vec![StationConfig::new("Test", Duration::from_millis(100))];
     /// # let product = Product::new(1, Duration::ZERO, &configs);
     /// let station_state = product.station_state(0);
     /// let mut state = station_state.lock().unwrap();
     /// // Modificar el estado...
     /// ```
     pub fn station_state(&self, index: usize) -> &Mutex<StationState> {
-        &self.stations[index]
+        let visit = self.route_stations()
+            .position(|station| station == index)
+            .unwrap_or_else(|| panic!("La estación {} no está en la ruta del producto {}", index, self.id));
+        &self.stations[visit]
     }
 
-    /// Registra el momento real de llegada del producto a la simulación.
+    /// Obtiene el estado del producto en una visita de su ruta.
     /// 
-    /// Este método debe ser llamado por el generador cuando el producto
-    /// es efectivamente introducido en la línea de ensamblaje.
+    /// # Arguments
+    /// 
+    /// * `visit` - Posición de la visita en la ruta (0-indexada)
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la posición está fuera de la ruta
+    pub fn visit_state(&self, visit: usize) -> &Mutex<StationState> {
+        &self.stations[visit]
+    }
+
+    /// Obtiene la pérdida del producto, si desistió al llegar a una estación,
+    /// abandonó su cola o fue rechazado en una inspección.
+    pub fn loss(&self) -> Option<ProductLoss> {
+        self.loss.get().copied()
+    }
+
+    /// Registra la pérdida del producto; solo la primera tiene efecto.
+    pub(crate) fn mark_lost(&self, loss: ProductLoss) {
+        let _ = self.loss.set(loss);
+    }
+
+    /// Obtiene el ID del producto desechado al que este producto reemplaza.
+    pub fn replaces(&self) -> Option<usize> {
+        self.replaces.get().copied()
+    }
+
+    /// Obtiene el ID del producto que reemplazó a este tras desecharlo.
+    pub fn replaced_by(&self) -> Option<usize> {
+        self.replaced_by.get().copied()
+    }
+
+    /// Vincula un producto desechado con su reemplazo.
+    pub(crate) fn link_replacement(parent: &Product, child: &Product) {
+        let _ = parent.replaced_by.set(child.id);
+        let _ = child.replaces.set(parent.id);
+    }
+
+    /// Reconstruye la especificación con la que se creó el producto, para
+    /// crear otro igual (por ejemplo, su reemplazo).
+    pub(crate) fn spec(&self) -> ProductSpec {
+        ProductSpec {
+            priority: self.priority,
+            due_date: self.due_date,
+            class: self.class.clone(),
+            metadata: self.metadata.clone(),
+            product_type: self.product_type.clone(),
+            patience: self.patience,
+            size: Some(self.size),
+        }
+    }
+
+    /// Obtiene el estado de la visita en curso del producto.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el producto ya completó su ruta
+    pub(crate) fn current_state(&self) -> &Mutex<StationState> {
+        &self.stations[self.completed_visits()]
+    }
+
+    /// Obtiene el número de visitas de la ruta que el producto ya completó.
+    /// 
+    /// Es también la posición en la ruta de la visita en curso.
+    pub fn completed_visits(&self) -> usize {
+        self.completed_stations.load(Ordering::Acquire)
+    }
+
+    /// Obtiene la estación de la visita en curso del producto.
+    /// 
+    /// # Returns
+    /// 
+    /// `Some(índice)` de la estación que el producto debe visitar a
+    /// continuación, `None` si ya completó su ruta
+    pub fn current_station(&self) -> Option<usize> {
+        self.visit_station(self.completed_visits())
+    }
+
+    /// Obtiene la estación de la visita anterior del producto.
+    /// 
+    /// # Returns
+    /// 
+    /// `None` si el producto está en la primera visita de su ruta
+    pub fn previous_station(&self) -> Option<usize> {
+        self.completed_visits()
+            .checked_sub(1)
+            .and_then(|visit| self.visit_station(visit))
+    }
+
+    /// Cuenta los retornos de la ruta: pasos hacia una estación que no está
+    /// más adelante en la línea que la anterior.
+    /// 
+    /// Cada retorno obliga al producto a recircular desde el final de la
+    /// línea hasta la primera estación.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Product, ProductSpec, ProductType, StationConfig};
+    /// 
+    /// let configs = vec![
+    ///     StationConfig::new("Litografía", Duration::from_millis(100)),
+    ///     StationConfig::new("Grabado", Duration::from_millis(100)),
+    /// ];
+    /// let oblea = ProductType::new("oblea").with_route(vec![0, 1, 0, 1]);
+    /// let product = Product::from_type(1, Duration::ZERO, &ProductSpec::default(), &oblea, &configs);
+    /// assert_eq!(product.reentries(), 1);
+    /// ```
+    pub fn reentries(&self) -> usize {
+        self.route().windows(2).filter(|pair| pair[1] <= pair[0]).count()
+    }
+
+    /// Cuenta los retornos que le quedan al producto a partir de su
+    /// visita en curso.
+    pub(crate) fn pending_reentries(&self) -> usize {
+        let route = self.route();
+        let current = self.completed_visits().min(route.len());
+        route[current..].windows(2).filter(|pair| pair[1] <= pair[0]).count()
+    }
+
+    /// Indica si la estación forma parte de la ruta del producto.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `index` - Índice de la estación (0-indexado)
+    pub fn visits(&self, index: usize) -> bool {
+        self.route_stations().any(|station| station == index)
+    }
+
+    /// Obtiene el índice de la primera estación de la ruta del producto.
+    /// 
+    /// # Returns
+    /// 
+    /// `Some(índice)` de la primera estación, `None` si la ruta está vacía
+    pub fn first_station(&self) -> Option<usize> {
+        self.visit_station(0)
+    }
+
+    /// Obtiene el tiempo de procesamiento del producto en una estación.
     /// 
     /// # Arguments
     /// 
-    /// * `instant` - Momento real en que el producto llegó
+    /// * `index` - Índice de la estación (0-indexado)
+    /// 
+    /// # Returns
+    /// 
+    /// Tiempo de procesamiento (cero si la estación no está en su ruta)
     /// 
     /// # Panics
     /// 
-    /// Hace panic si no se puede obtener el lock del arrival_instant
-    pub fn set_arrival_instant(&self, instant: Instant) {
-        *self.arrival_instant.lock()
-            .expect("No se pudo obtener lock del arrival_instant") = Some(instant);
+    /// Hace panic si el índice está fuera de rango
+    pub fn processing_time(&self, index: usize) -> Duration {
+        self.processing_times[index]
+    }
+
+    /// Registra el momento real de llegada del producto a la simulación.
+    /// 
+    /// Este método debe ser llamado por el generador cuando el producto
+    /// es efectivamente introducido en la línea de ensamblaje. Las llamadas
+    /// posteriores no modifican el momento ya registrado.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `time` - Tiempo transcurrido desde el inicio de la simulación
+    ///   cuando el producto llegó
+    pub fn set_arrival_time(&self, time: Duration) {
+        let _ = self.arrival_time.set(time);
     }
 
     /// Obtiene el momento real de llegada del producto.
     /// 
+    /// A diferencia de `arrival_offset`, incluye el retraso con el que el
+    /// generador introdujo el producto en la línea.
+    /// 
     /// # Returns
     /// 
-    /// `Some(Instant)` si el producto ya fue generado, `None` en caso contrario
+    /// `Some(Duration)` relativo al inicio de la simulación si el producto
+    /// ya fue generado, `None` en caso contrario
+    pub fn get_arrival_time(&self) -> Option<Duration> {
+        self.arrival_time.get().copied()
+    }
+
+    /// Registra que el producto completó una estación de su ruta.
     /// 
-    /// # Panics
+    /// Debe llamarse después de asignar `final_exit` en el estado de la
+    /// estación, para que quien observe el progreso vea también ese valor.
+    pub(crate) fn mark_station_completed(&self) {
+        self.completed_stations.fetch_add(1, Ordering::Release);
+    }
+
+    /// Indica si el producto ya completó una estación, sin tomar locks.
+    /// 
+    /// Como los productos recorren su ruta en orden, la estación está
+    /// completada si su primera posición en la ruta es menor que el número
+    /// de visitas completadas.
     /// 
-    /// Hace panic si no se puede obtener el lock del arrival_instant
-    pub fn get_arrival_instant(&self) -> Option<Instant> {
-        *self.arrival_instant.lock()
-            .expect("No se pudo obtener lock del arrival_instant")
+    /// # Arguments
+    /// 
+    /// * `index` - Índice de la estación (0-indexado)
+    /// 
+    /// # Returns
+    /// 
+    /// `true` si la estación está en la ruta y el producto ya la completó
+    pub fn has_completed_station(&self, index: usize) -> bool {
+        let completed = self.completed_visits();
+        self.route_stations()
+            .position(|station| station == index)
+            .is_some_and(|position| position < completed)
     }
 
     /// Calcula el tiempo total de espera del producto en todas las estaciones.
     /// 
-    /// Suma los tiempos de espera acumulados en cada estación para obtener
+    /// Suma los tiempos de espera acumulados en cada visita para obtener
     /// el tiempo total que el producto pasó esperando en colas.
     /// 
     /// # Returns
     /// 
     /// Duración total de espera acumulada
-    /// 
-    /// # Panics
-    /// 
-    /// Hace panic si no se puede obtener el lock de alguna estación
     pub fn total_wait_time(&self) -> Duration {
         self.stations
             .iter()
             .map(|station| {
-                station.lock()
-                    .expect("No se pudo obtener lock del estado de estación")
+                lock_recover(station)
                     .total_wait
             })
             .sum()
@@ -156,54 +903,277 @@ impl Product {
     /// Calcula el tiempo de turnaround del producto.
     /// 
     /// El turnaround es la diferencia entre el momento de finalización
-    /// completa del producto (salida de la última estación) y su momento
-    /// de llegada a la simulación.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `start_time` - Momento de inicio de la simulación para cálculos relativos
+    /// completa del producto (salida de la última estación de su ruta) y su
+    /// momento de llegada a la simulación.
     /// 
     /// # Returns
     /// 
     /// `Some(Duration)` con el turnaround si el producto fue completado,
     /// `None` si aún está en procesamiento
+    pub fn turnaround_time(&self) -> Option<Duration> {
+        let arrival = self.get_arrival_time()?;
+        let final_exit = self.completion_time()?;
+
+        Some(final_exit.saturating_sub(arrival))
+    }
+
+    /// Obtiene el momento en que el producto salió de la última estación de su ruta.
     /// 
-    /// # Panics
+    /// # Returns
     /// 
-    /// Hace panic si no se puede obtener los locks necesarios
-    pub fn turnaround_time(&self, _start_time: Instant) -> Option<Duration> {
-        let arrival = self.get_arrival_instant()?;
-        let last_station_index = self.stations.len() - 1;
-        
-        let final_exit = self.stations[last_station_index]
-            .lock()
-            .expect("No se pudo obtener lock de la última estación")
-            .final_exit?;
+    /// `Some(Duration)` relativo al inicio de la simulación si el producto
+    /// fue completado, `None` en caso contrario
+    pub fn completion_time(&self) -> Option<Duration> {
+        lock_recover(self.stations.last()?).final_exit
+    }
 
-        Some(final_exit.duration_since(arrival))
+    /// Calcula el tiempo de servicio total de la ruta del producto.
+    /// 
+    /// # Returns
+    /// 
+    /// Suma de los tiempos de procesamiento de cada visita de la ruta, más
+    /// el tiempo agregado por las curvas de aprendizaje en las visitas ya
+    /// iniciadas
+    pub fn total_work(&self) -> Duration {
+        self.route_stations().map(|station| self.processing_times[station]).sum::<Duration>() + self.total_ramp_up()
     }
 
-    /// Verifica si el producto ha completado su procesamiento en todas las estaciones.
+    /// Calcula el tiempo de procesamiento que las curvas de aprendizaje de
+    /// las estaciones agregaron al producto.
     /// 
     /// # Returns
     /// 
-    /// `true` si el producto terminó de procesarse en todas las estaciones,
-    /// `false` en caso contrario
+    /// Duración total agregada en todas las visitas
+    pub fn total_ramp_up(&self) -> Duration {
+        self.stations
+            .iter()
+            .map(|station| {
+                lock_recover(station)
+                    .ramp_up
+            })
+            .sum()
+    }
+
+    /// Calcula el tiempo de servicio que le falta al producto para completar
+    /// su ruta.
     /// 
-    /// # Panics
+    /// En la visita en curso cuenta el tiempo restante de la estación si el
+    /// producto ya comenzó a procesarse en ella; las visitas siguientes
+    /// cuentan completas.
     /// 
-    /// Hace panic si no se puede obtener el lock de alguna estación
+    /// # Returns
+    /// 
+    /// Tiempo de servicio pendiente (cero si el producto fue completado)
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Product, StationConfig};
+    /// 
+    /// let configs = vec![
+    ///     StationConfig::new("Corte", Duration::from_millis(100)),
+    ///     StationConfig::new("Empaque", Duration::from_millis(50)),
+    /// ];
+    /// let product = Product::new(1, Duration::ZERO, &configs);
+    /// assert_eq!(product.remaining_work(), product.total_work());
+    /// assert_eq!(product.total_work(), Duration::from_millis(150));
+    /// ```
+    pub fn remaining_work(&self) -> Duration {
+        let current = self.completed_visits();
+        self.route_stations()
+            .enumerate()
+            .skip(current)
+            .map(|(visit, station)| {
+                let state = lock_recover(&self.stations[visit]);
+                if visit == current && state.first_entry.is_some() {
+                    state.remaining
+                } else {
+                    self.processing_times[station]
+                }
+            })
+            .sum()
+    }
+
+    /// Verifica si el producto ha completado su procesamiento en todas las
+    /// estaciones de su ruta.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` si el producto terminó de procesarse en todas las estaciones
+    /// de su ruta, `false` en caso contrario
     pub fn is_completed(&self) -> bool {
-        if self.stations.is_empty() {
-            return true;
+        self.completed_visits() >= self.route.len()
+    }
+
+    /// Agrega una dependencia de precedencia al producto.
+    /// 
+    /// El producto no podrá iniciar su procesamiento en la primera estación
+    /// hasta que `depends_on` complete la estación indicada.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `depends_on` - Producto del que depende
+    /// * `station` - Índice de la estación que `depends_on` debe completar
+    pub fn add_dependency(&self, depends_on: Arc<Product>, station: usize) {
+        lock_recover(&self.dependencies)
+            .push((depends_on, station));
+    }
+
+    /// Obtiene las dependencias de precedencia del producto.
+    /// 
+    /// # Returns
+    /// 
+    /// Pares (ID del producto, índice de la estación que debe completar)
+    pub fn dependencies(&self) -> Vec<(usize, usize)> {
+        lock_recover(&self.dependencies)
+            .iter()
+            .map(|(product, station)| (product.id, *station))
+            .collect()
+    }
+
+    /// Verifica si todas las dependencias del producto fueron satisfechas.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` si el producto no tiene dependencias pendientes
+    pub fn dependencies_satisfied(&self) -> bool {
+        lock_recover(&self.dependencies)
+            .iter()
+            .all(|(product, station)| product.has_completed_station(*station))
+    }
+
+    /// Obtiene el momento en que se satisfizo la última dependencia del producto.
+    /// 
+    /// # Returns
+    /// 
+    /// `Some(Duration)` con la finalización más tardía entre las dependencias,
+    /// relativa al inicio de la simulación, o `None` si el producto no tiene
+    /// dependencias o alguna sigue pendiente
+    pub fn dependencies_ready_at(&self) -> Option<Duration> {
+        let dependencies = lock_recover(&self.dependencies);
+        
+        let mut ready_at: Option<Duration> = None;
+        for (product, station) in dependencies.iter() {
+            let exit = lock_recover(product.station_state(*station))
+                .final_exit?;
+            ready_at = Some(ready_at.map_or(exit, |current| current.max(exit)));
         }
+        ready_at
+    }
+
+    /// Calcula el tiempo total que el producto estuvo bloqueado por dependencias.
+    /// 
+    /// # Returns
+    /// 
+    /// Duración total de bloqueo por dependencias en todas las estaciones
+    pub fn total_dependency_wait(&self) -> Duration {
+        self.stations
+            .iter()
+            .map(|station| {
+                lock_recover(station)
+                    .dependency_wait
+            })
+            .sum()
+    }
+
+    /// Calcula el retraso total que los temporizadores imprecisos de las
+    /// estaciones agregaron a las porciones del producto.
+    /// 
+    /// # Returns
+    /// 
+    /// Duración total del retraso inyectado en todas las visitas
+    pub fn total_timer_drift(&self) -> Duration {
+        self.stations
+            .iter()
+            .map(|station| {
+                lock_recover(station)
+                    .timer_drift
+            })
+            .sum()
+    }
+
+    /// Calcula el tiempo total que las estaciones esperaron recursos
+    /// compartidos para procesar el producto.
+    /// 
+    /// # Returns
+    /// 
+    /// Duración total de espera de recursos en todas las visitas
+    pub fn total_resource_wait(&self) -> Duration {
+        self.stations
+            .iter()
+            .map(|station| {
+                lock_recover(station)
+                    .resource_wait
+            })
+            .sum()
+    }
+
+    /// Reconstruye la línea de tiempo del producto en la línea.
+    /// 
+    /// A diferencia de la primera entrada y la última salida de cada
+    /// estación, la línea de tiempo muestra cada espera en cola, cada
+    /// porción de procesamiento (marcando las interrumpidas por el quantum)
+    /// y los traslados entre visitas de la ruta.
+    /// 
+    /// # Returns
+    /// 
+    /// Intervalos ordenados cronológicamente; las visitas en curso solo
+    /// incluyen lo ocurrido hasta el momento
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{SchedulingAlgorithm, Simulation, StationConfig, TimelineEvent};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(30))];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(Duration::from_millis(10)), vec![Duration::ZERO]);
+    /// let metrics = simulation.run().expect("La simulación falló");
+    /// let events: Vec<TimelineEvent> = metrics.products[0].timeline.iter().map(|span| span.event).collect();
+    /// assert_eq!(events.iter().filter(|&&event| event == TimelineEvent::Preempted).count(), 2);
+    /// assert_eq!(events.last(), Some(&TimelineEvent::Processing));
+    /// ```
+    pub fn timeline(&self) -> Vec<TimelineSpan> {
+        let mut timeline = Vec::new();
+        let mut previous_exit = None;
+
+        for (visit, station) in self.route_stations().enumerate() {
+            let state = lock_recover(&self.stations[visit]);
+            if let (Some(exit), Some(&(entry, _))) = (previous_exit, state.queued.first()) {
+                timeline.push(TimelineSpan {
+                    station,
+                    event: TimelineEvent::Transfer,
+                    start: exit,
+                    end: entry,
+                });
+            }
 
-        let last_station_index = self.stations.len() - 1;
-        self.stations[last_station_index]
-            .lock()
-            .expect("No se pudo obtener lock de la última estación")
-            .final_exit
-            .is_some()
+            // Cada porción va precedida por su espera en cola
+            for (index, &(queue_entry, queue_exit)) in state.queued.iter().enumerate() {
+                timeline.push(TimelineSpan {
+                    station,
+                    event: TimelineEvent::Queued,
+                    start: queue_entry,
+                    end: queue_exit,
+                });
+                if let Some(&(slice_start, slice_end)) = state.slices.get(index) {
+                    let completed = state.final_exit.is_some() && index + 1 == state.slices.len();
+                    timeline.push(TimelineSpan {
+                        station,
+                        event: if completed { TimelineEvent::Processing } else { TimelineEvent::Preempted },
+                        start: slice_start,
+                        end: slice_end,
+                    });
+                }
+            }
+
+            previous_exit = state.final_exit;
+            if previous_exit.is_none() {
+                break;
+            }
+        }
+        timeline
     }
 
     /// Obtiene una representación string del estado actual del producto.
@@ -217,10 +1187,8 @@ impl Product {
     pub fn status_summary(&self) -> String {
         let completed_stations = self.stations
             .iter()
-            .enumerate()
-            .filter(|(_, station)| {
-                station.lock()
-                    .expect("No se pudo obtener lock de estación")
+            .filter(|station| {
+                lock_recover(station)
                     .final_exit
                     .is_some()
             })
@@ -230,7 +1198,7 @@ impl Product {
             "Producto {:02}: {}/{} estaciones completadas",
             self.id,
             completed_stations,
-            self.stations.len()
+            self.route.len()
         )
     }
 }
\ No newline at end of file
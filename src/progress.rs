@@ -0,0 +1,149 @@
+//! # Módulo de Progreso
+//! 
+//! Este módulo define el avance de una simulación en curso y los callbacks
+//! que lo reciben. El colector publica el avance cada vez que un producto
+//! completa la línea, junto con una estimación del tiempo restante basada
+//! en el trabajo de servicio pendiente. Medir el avance recorre todos los
+//! productos conocidos, por lo que en las ejecuciones de más de
+//! [`MAX_UPDATES`] productos se publica solo cada tanto (ver
+//! [`Progress::is_due`]).
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::metrics::MetricsCalculator;
+use crate::product::Product;
+
+/// Cantidad máxima de avisos de progreso por ejecución.
+pub const MAX_UPDATES: usize = 1000;
+
+/// Avance de una simulación en curso.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Progress {
+    /// Productos que completaron toda la línea
+    pub completed: usize,
+    /// Productos conocidos (programados o inyectados), o `None` si alguna
+    /// fuente de llegadas es infinita
+    pub total: Option<usize>,
+    /// Tiempo transcurrido desde el inicio de la simulación
+    pub elapsed: Duration,
+    /// Tiempo de servicio pendiente de los productos conocidos
+    pub remaining_work: Duration,
+    /// Tiempo restante estimado, o `None` si todavía no se completó trabajo
+    /// suficiente para estimarlo
+    pub eta: Option<Duration>,
+}
+
+impl Progress {
+    /// Calcula el avance a partir de los productos conocidos.
+    /// 
+    /// La estimación supone que el trabajo pendiente se completa al mismo
+    /// ritmo que el ya realizado: `eta = elapsed * pendiente / realizado`.
+    /// No considera las llegadas futuras, por lo que subestima el tiempo
+    /// restante cuando los productos llegan espaciados.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `products` - Productos conocidos por la simulación
+    /// * `completed` - Productos que completaron la línea
+    /// * `elapsed` - Tiempo transcurrido desde el inicio
+    /// * `open_loop` - Indica si alguna fuente de llegadas es infinita
+    pub(crate) fn measure(products: &[Arc<Product>], completed: usize, elapsed: Duration, open_loop: bool) -> Self {
+        let (total_work, remaining_work) = products.iter().fold(
+            (Duration::ZERO, Duration::ZERO),
+            |(total, remaining), product| (total + product.total_work(), remaining + product.remaining_work()),
+        );
+        let done = total_work.saturating_sub(remaining_work);
+        let eta = (!done.is_zero())
+            .then(|| elapsed.mul_f64(remaining_work.as_secs_f64() / done.as_secs_f64()));
+
+        Self {
+            completed,
+            total: (!open_loop).then_some(products.len()),
+            elapsed,
+            remaining_work,
+            eta,
+        }
+    }
+
+    /// Indica si corresponde publicar el avance tras una finalización.
+    /// 
+    /// Con hasta [`MAX_UPDATES`] productos conocidos se publica en cada
+    /// finalización; con más, cada `known / MAX_UPDATES` finalizaciones
+    /// (redondeado hacia arriba) y al completarse el último producto
+    /// conocido, de modo que el costo total de medir el avance crece
+    /// linealmente con los productos.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `completed` - Productos que completaron la línea
+    /// * `known` - Productos conocidos por la simulación
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::Progress;
+    /// 
+    /// assert!(Progress::is_due(7, 10));
+    /// assert!(!Progress::is_due(7, 100_000));
+    /// assert!(Progress::is_due(200, 100_000));
+    /// assert!(Progress::is_due(100_000, 100_000));
+    /// ```
+    pub fn is_due(completed: usize, known: usize) -> bool {
+        let step = known.div_ceil(MAX_UPDATES).max(1);
+        completed.is_multiple_of(step) || completed >= known
+    }
+
+    /// Obtiene la fracción de productos completados.
+    /// 
+    /// # Returns
+    /// 
+    /// Valor entre 0 y 1, o `None` si el total de productos no se conoce
+    pub fn fraction(&self) -> Option<f64> {
+        self.total.map(|total| {
+            if total == 0 {
+                1.0
+            } else {
+                (self.completed as f64 / total as f64).min(1.0)
+            }
+        })
+    }
+
+    /// Dibuja una barra de progreso de texto.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `width` - Número de caracteres de la barra (sin los corchetes)
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::Progress;
+    /// 
+    /// let progress = Progress { completed: 5, total: Some(10), ..Progress::default() };
+    /// assert_eq!(progress.bar(10), "[#####-----]");
+    /// ```
+    pub fn bar(&self, width: usize) -> String {
+        let filled = self.fraction()
+            .map_or(0, |fraction| (fraction * width as f64).round() as usize);
+        format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.total {
+            Some(total) => write!(f, "{} {}/{}", self.bar(20), self.completed, total)?,
+            None => write!(f, "{} completados", self.completed)?,
+        }
+        write!(f, " t={}", MetricsCalculator::format_duration(self.elapsed))?;
+        match self.eta {
+            Some(eta) => write!(f, " ETA {}", MetricsCalculator::format_duration(eta)),
+            None => write!(f, " ETA --"),
+        }
+    }
+}
+
+/// Callback que recibe el avance de la simulación.
+pub type ProgressCallback = Arc<dyn Fn(&Progress) + Send + Sync>;
@@ -0,0 +1,165 @@
+//! # Módulo de Metadatos de Ejecución
+//! 
+//! Este módulo registra con qué se produjo cada resultado: la versión del
+//! simulador, el commit de git del que se compiló (si se compiló dentro de
+//! un repositorio), el momento de la ejecución, el equipo y el sistema
+//! operativo. Las métricas de cada simulación guardan estos datos y los
+//! reportes en texto, CSV y JSON los incluyen, de modo que un resultado
+//! siga siendo atribuible meses después.
+//! 
+//! El momento es el de la hora del sistema al calcular las métricas, aun
+//! cuando la simulación se ejecute con un reloj virtual.
+
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::metrics::MetricsCalculator;
+
+/// Datos del entorno en el que se ejecutó una simulación.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::RunMetadata;
+/// 
+/// let run = RunMetadata::capture();
+/// assert_eq!(run.crate_version, env!("CARGO_PKG_VERSION"));
+/// assert!(run.timestamp_utc().ends_with('Z'));
+/// assert!(run.to_json().starts_with("{\"crate_version\":"));
+/// assert!(run.csv_preamble().lines().all(|line| line.starts_with("# ")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunMetadata {
+    /// Versión del simulador que produjo el resultado
+    pub crate_version: String,
+    /// Commit de git del que se compiló el simulador, si se conoce
+    pub git_commit: Option<String>,
+    /// Momento de la ejecución según la hora del sistema
+    pub timestamp: SystemTime,
+    /// Nombre del equipo
+    pub hostname: String,
+    /// Sistema operativo y arquitectura (por ejemplo, `linux x86_64`)
+    pub os: String,
+}
+
+impl RunMetadata {
+    /// Registra los datos de la ejecución actual.
+    pub fn capture() -> Self {
+        static HOSTNAME: OnceLock<String> = OnceLock::new();
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: option_env!("SIMULATOR_GIT_COMMIT").map(str::to_string),
+            timestamp: SystemTime::now(),
+            hostname: HOSTNAME.get_or_init(hostname).clone(),
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        }
+    }
+
+    /// Formatea el momento de la ejecución en UTC según RFC 3339, con
+    /// precisión de segundos (por ejemplo, `2025-03-14T09:26:53Z`).
+    pub fn timestamp_utc(&self) -> String {
+        let seconds = self.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+        let time = seconds % 86_400;
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            time / 3600,
+            time % 3600 / 60,
+            time % 60
+        )
+    }
+
+    /// Exporta los datos como un objeto JSON de una sola línea.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"crate_version\":{},\"git_commit\":{},\"timestamp\":{},\"hostname\":{},\"os\":{}}}",
+            MetricsCalculator::json_string(&self.crate_version),
+            self.git_commit.as_deref().map_or_else(|| "null".to_string(), MetricsCalculator::json_string),
+            MetricsCalculator::json_string(&self.timestamp_utc()),
+            MetricsCalculator::json_string(&self.hostname),
+            MetricsCalculator::json_string(&self.os)
+        )
+    }
+
+    /// Exporta los datos como líneas de comentario (`# clave: valor`) para
+    /// anteponerlas a un CSV. La mayoría de las herramientas las omiten al
+    /// indicarles `#` como carácter de comentario.
+    pub fn csv_preamble(&self) -> String {
+        format!(
+            "# crate_version: {}\n# git_commit: {}\n# timestamp: {}\n# hostname: {}\n# os: {}\n",
+            self.crate_version,
+            self.git_commit.as_deref().unwrap_or(""),
+            self.timestamp_utc(),
+            self.hostname,
+            self.os
+        )
+    }
+
+    /// Resume los datos en una línea: versión, commit, momento y equipo.
+    pub fn summary(&self) -> String {
+        let commit = self.git_commit.as_deref().map(|commit| format!(" ({})", commit)).unwrap_or_default();
+        format!("v{}{}, {}, {} ({})", self.crate_version, commit, self.timestamp_utc(), self.hostname, self.os)
+    }
+}
+
+impl fmt::Display for RunMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Versión del simulador: {}", self.crate_version)?;
+        writeln!(f, "Commit: {}", self.git_commit.as_deref().unwrap_or("desconocido"))?;
+        writeln!(f, "Fecha (UTC): {}", self.timestamp_utc())?;
+        writeln!(f, "Equipo: {}", self.hostname)?;
+        write!(f, "Sistema operativo: {}", self.os)
+    }
+}
+
+/// Obtiene el nombre del equipo de las variables de entorno o, en Linux,
+/// de `/etc/hostname`.
+fn hostname() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .filter_map(|variable| std::env::var(variable).ok())
+        .chain(
+            ["/etc/hostname", "/proc/sys/kernel/hostname"]
+                .iter()
+                .filter_map(|path| std::fs::read_to_string(path).ok()),
+        )
+        .map(|name| name.trim().to_string())
+        .find(|name| !name.is_empty())
+        .unwrap_or_else(|| "desconocido".to_string())
+}
+
+/// Convierte días desde 1970-01-01 en una fecha (año, mes, día) del
+/// calendario gregoriano.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timestamp_is_formatted_in_utc() {
+        let mut run = RunMetadata::capture();
+        run.timestamp = UNIX_EPOCH;
+        assert_eq!(run.timestamp_utc(), "1970-01-01T00:00:00Z");
+        run.timestamp = UNIX_EPOCH + Duration::from_secs(951_782_400 + 3_723);
+        assert_eq!(run.timestamp_utc(), "2000-02-29T01:02:03Z");
+        run.timestamp = UNIX_EPOCH + Duration::from_secs(1_735_689_599);
+        assert_eq!(run.timestamp_utc(), "2024-12-31T23:59:59Z");
+    }
+}
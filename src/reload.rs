@@ -0,0 +1,183 @@
+//! # Módulo de Reconfiguración en Caliente
+//! 
+//! Este módulo permite aplicar una configuración nueva a una simulación en
+//! curso en un momento simulado dado, por ejemplo para modelar una mejora
+//! de proceso a mitad de turno. Cada reconfiguración se traduce en los
+//! comandos de estación equivalentes ([`StationCommand`]) que cada estación
+//! aplica al llegar ese momento, y abre una nueva época de configuración:
+//! las métricas de cada producto indican la época en que llegó y el reporte
+//! resume cada época por separado.
+//! 
+//! La estructura de la línea no cambia durante la ejecución: la
+//! configuración nueva debe tener las mismas estaciones, en el mismo orden,
+//! y solo puede modificar sus tiempos de procesamiento y el quantum.
+
+use std::time::Duration;
+
+use crate::control::StationCommand;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::scenario::Scenario;
+use crate::station::StationConfig;
+
+/// Configuración que reemplaza a la vigente a partir de un momento de la
+/// simulación.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Reconfiguration, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Horno", Duration::from_millis(100))];
+/// let arrivals = (0..4).map(|i| Duration::from_millis(i * 150)).collect();
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+/// 
+/// // Desde t = 250ms el horno tarda la mitad
+/// let improved = vec![StationConfig::new("Horno", Duration::from_millis(50))];
+/// simulation.add_reconfiguration(Reconfiguration::new(Duration::from_millis(250), improved));
+/// 
+/// let metrics = simulation.run().unwrap();
+/// let epochs: Vec<usize> = metrics.products.iter().map(|product| product.config_epoch).collect();
+/// assert_eq!(epochs, vec![0, 0, 1, 1]);
+/// assert_eq!(metrics.config_epochs.len(), 2);
+/// assert!(metrics.products[3].turnaround_time < Duration::from_millis(100));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reconfiguration {
+    /// Momento de la simulación desde el que rige la configuración
+    pub at: Duration,
+    /// Estaciones de la línea con sus tiempos de procesamiento nuevos
+    pub stations: Vec<StationConfig>,
+    /// Quantum nuevo de todas las estaciones (`None` lo deja sin cambios)
+    pub quantum: Option<Duration>,
+}
+
+impl Reconfiguration {
+    /// Crea una reconfiguración de los tiempos de procesamiento.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `at` - Momento de la simulación en que se aplica
+    /// * `stations` - Estaciones de la línea, en el mismo orden, con sus
+    ///   tiempos de procesamiento nuevos
+    pub fn new(at: Duration, stations: Vec<StationConfig>) -> Self {
+        Self {
+            at,
+            stations,
+            quantum: None,
+        }
+    }
+
+    /// Asigna el quantum que pasan a usar todas las estaciones.
+    pub fn with_quantum(mut self, quantum: Duration) -> Self {
+        self.quantum = Some(quantum);
+        self
+    }
+
+    /// Crea una reconfiguración a partir de un escenario.
+    /// 
+    /// Se toman las estaciones del escenario y, si su algoritmo es Round
+    /// Robin, su quantum; el resto del escenario (llegadas, fechas de
+    /// entrega) se ignora.
+    pub fn from_scenario(at: Duration, scenario: &Scenario) -> Self {
+        Self {
+            at,
+            stations: scenario.stations.clone(),
+            quantum: scenario.algorithm.get_quantum(),
+        }
+    }
+
+    /// Lee una reconfiguración desde un documento JSON de escenario (ver
+    /// [`Scenario::from_json`]).
+    /// 
+    /// # Returns
+    /// 
+    /// La reconfiguración, o `Err` con la descripción del problema si el
+    /// documento no es un escenario válido
+    pub fn from_json(at: Duration, text: &str) -> Result<Self, String> {
+        Ok(Self::from_scenario(at, &Scenario::from_json(text)?))
+    }
+
+    /// Verifica que la reconfiguración pueda aplicarse a la línea.
+    /// 
+    /// # Returns
+    /// 
+    /// `Err` con el motivo si cambia las estaciones de la línea o asigna un
+    /// tiempo de procesamiento o un quantum nulo
+    pub(crate) fn check(&self, line: &[StationConfig]) -> Result<(), String> {
+        let names = |stations: &[StationConfig]| {
+            stations.iter().map(|station| station.name.clone()).collect::<Vec<_>>()
+        };
+        if names(&self.stations) != names(line) {
+            return Err(format!(
+                "debe tener las mismas estaciones que la línea ({})",
+                names(line).join(", ")
+            ));
+        }
+        if let Some(station) = self.stations.iter().find(|station| station.processing_time.is_zero()) {
+            return Err(format!("la estación '{}' tiene tiempo de procesamiento cero", station.name));
+        }
+        if self.quantum.is_some_and(|quantum| quantum.is_zero()) {
+            return Err("el quantum debe ser mayor que cero".to_string());
+        }
+        Ok(())
+    }
+
+    /// Traduce la reconfiguración en comandos de estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `previous` - Estaciones de la configuración vigente hasta ahora
+    /// 
+    /// # Returns
+    /// 
+    /// Pares (índice de estación, comando) con los cambios respecto a
+    /// `previous`
+    pub(crate) fn commands(&self, previous: &[StationConfig]) -> Vec<(usize, StationCommand)> {
+        let processing_times = self.stations
+            .iter()
+            .zip(previous)
+            .enumerate()
+            .filter(|(_, (station, before))| station.processing_time != before.processing_time)
+            .map(|(index, (station, _))| (index, StationCommand::SetProcessingTime(station.processing_time)));
+        let quanta = self.quantum
+            .into_iter()
+            .flat_map(|quantum| (0..self.stations.len()).map(move |index| (index, StationCommand::SetQuantum(quantum))));
+        processing_times.chain(quanta).collect()
+    }
+}
+
+/// Calcula la época de configuración vigente en un momento.
+/// 
+/// # Arguments
+/// 
+/// * `reconfigurations` - Reconfiguraciones ordenadas por momento
+/// * `time` - Momento relativo al inicio de la simulación
+/// 
+/// # Returns
+/// 
+/// 0 para la configuración inicial, `n` a partir de la n-ésima reconfiguración
+pub(crate) fn epoch_at(reconfigurations: &[Reconfiguration], time: Duration) -> usize {
+    reconfigurations.iter().take_while(|reconfiguration| reconfiguration.at <= time).count()
+}
+
+/// Anota en las métricas la época de configuración de cada producto y
+/// calcula las estadísticas de cada época.
+/// 
+/// No modifica las métricas si no hubo reconfiguraciones.
+pub(crate) fn annotate_epochs(metrics: &mut SimulationMetrics, reconfigurations: &[Reconfiguration]) {
+    if reconfigurations.is_empty() {
+        return;
+    }
+    let products = metrics.products
+        .iter_mut()
+        .chain(metrics.incomplete_products.iter_mut())
+        .chain(metrics.lost_products.iter_mut());
+    for product in products {
+        product.config_epoch = epoch_at(reconfigurations, product.arrival_time);
+    }
+    let starts: Vec<Duration> = std::iter::once(Duration::ZERO)
+        .chain(reconfigurations.iter().map(|reconfiguration| reconfiguration.at))
+        .collect();
+    metrics.config_epochs = MetricsCalculator::epoch_metrics(metrics, &starts);
+}
@@ -0,0 +1,541 @@
+//! # Módulo de Modo Interactivo
+//! 
+//! Este módulo implementa el REPL de la aplicación (`simulador repl`), que
+//! permite armar una línea de ensamblaje con comandos, ejecutarla, comparar
+//! algoritmos e inspeccionar los resultados sin reiniciar el programa.
+//! 
+//! ```text
+//! > clear
+//! > add-station corte 400 fcfs
+//! > add-station empaque 200 rr 100
+//! > arrivals 0 100 200
+//! > run
+//! > compare edd
+//! > product 2
+//! ```
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+use crate::analysis::compare_runs;
+use crate::config;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::scenario::ScenarioDiff;
+use crate::scheduler::SchedulingAlgorithm;
+use crate::simulation::Simulation;
+use crate::station::StationConfig;
+
+/// Ayuda mostrada por el comando `help`
+const HELP: &str = "\
+Comandos disponibles:
+  add-station <nombre> <ms> [algoritmo]  Agrega una estación al final de la línea
+  remove-station <nombre>                Elimina una estación
+  algorithm <algoritmo>                  Algoritmo general de las estaciones
+  arrivals <ms>...                       Reemplaza los tiempos de llegada
+  due-dates <ms>... | none               Asigna o elimina las fechas de entrega
+  config                                 Muestra la configuración actual
+  clear                                  Elimina todas las estaciones y llegadas
+  defaults                               Restaura la configuración por defecto
+  run                                    Ejecuta la simulación
+  compare <algoritmo>                    Compara el último resultado con otro algoritmo
+                                         (incluye valores p, tamaño del efecto y
+                                         los cambios de configuración)
+  report | csv | json                    Muestra el último resultado en ese formato
+  waits [csv|json]                       Matriz de esperas producto × estación (mapa de calor)
+  product <id>                           Muestra el detalle de un producto
+  help                                   Muestra esta ayuda
+  quit | exit                            Sale del modo interactivo
+
+Algoritmos: fcfs, rr [quantum_ms], edd, cr";
+
+/// Estado de una sesión interactiva.
+/// 
+/// Cada comando modifica la configuración de la línea o consulta el
+/// resultado de la última ejecución.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::repl::Repl;
+/// 
+/// let mut repl = Repl::new();
+/// repl.execute("clear").unwrap();
+/// repl.execute("add-station corte 5 rr 2").unwrap();
+/// repl.execute("arrivals 0 1 2").unwrap();
+/// let summary = repl.execute("run").unwrap();
+/// assert!(summary.contains("Productos completados: 3"));
+/// assert!(repl.execute("product 9").is_err());
+/// ```
+#[derive(Debug)]
+pub struct Repl {
+    /// Estaciones de la línea con su algoritmo propio, si tienen uno
+    pub stations: Vec<(StationConfig, Option<SchedulingAlgorithm>)>,
+    /// Algoritmo de las estaciones sin algoritmo propio
+    pub algorithm: SchedulingAlgorithm,
+    /// Tiempos de llegada de los productos
+    pub arrivals: Vec<Duration>,
+    /// Fechas de entrega de los productos
+    pub due_dates: Vec<Duration>,
+    /// Resultado de la última ejecución
+    pub last_run: Option<RunResult>,
+}
+
+/// Resultado de una ejecución del REPL.
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    /// Descripción de los algoritmos usados
+    pub label: String,
+    /// Estaciones con las que se ejecutó
+    pub stations: Vec<StationConfig>,
+    /// Métricas obtenidas
+    pub metrics: SimulationMetrics,
+}
+
+impl Repl {
+    /// Crea una sesión con la configuración por defecto.
+    pub fn new() -> Self {
+        Self {
+            stations: config::default_station_configs()
+                .into_iter()
+                .map(|station| (station, None))
+                .collect(),
+            algorithm: SchedulingAlgorithm::fcfs(),
+            arrivals: config::default_arrival_times(),
+            due_dates: Vec::new(),
+            last_run: None,
+        }
+    }
+
+    /// Lee comandos de `input` hasta `quit`, `exit` o el fin de la entrada.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `input` - Fuente de los comandos, una línea por comando
+    /// * `output` - Destino de las respuestas y del prompt
+    /// 
+    /// # Returns
+    /// 
+    /// `Err` solo si falla la lectura o escritura; los errores de los
+    /// comandos se muestran y la sesión continúa
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        writeln!(output, "Simulador de Línea de Ensamblaje - modo interactivo ('help' para ver los comandos)")?;
+        write!(output, "> ")?;
+        output.flush()?;
+
+        for line in input.lines() {
+            let line = line?;
+            if matches!(line.trim(), "quit" | "exit") {
+                break;
+            }
+            match self.execute(&line) {
+                Ok(response) if response.is_empty() => {}
+                Ok(response) => writeln!(output, "{}", response)?,
+                Err(error) => writeln!(output, "Error: {}", error)?,
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        writeln!(output)
+    }
+
+    /// Ejecuta un comando.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `line` - Comando con sus argumentos separados por espacios
+    /// 
+    /// # Returns
+    /// 
+    /// La respuesta a mostrar (vacía para líneas en blanco), o `Err` con la
+    /// descripción del problema si el comando es inválido
+    pub fn execute(&mut self, line: &str) -> Result<String, String> {
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            return Ok(String::new());
+        };
+        let args: Vec<&str> = words.collect();
+
+        match command {
+            "help" => Ok(HELP.to_string()),
+            "add-station" => self.add_station(&args),
+            "remove-station" => self.remove_station(&args),
+            "algorithm" => {
+                self.algorithm = parse_algorithm(&args)?;
+                Ok(format!("Algoritmo general: {}", self.algorithm))
+            }
+            "arrivals" => {
+                self.arrivals = parse_millis_list(&args)?;
+                Ok(format!("{} llegadas programadas", self.arrivals.len()))
+            }
+            "due-dates" => {
+                self.due_dates = match args.as_slice() {
+                    ["none"] => Vec::new(),
+                    _ => parse_millis_list(&args)?,
+                };
+                Ok(format!("{} fechas de entrega asignadas", self.due_dates.len()))
+            }
+            "config" => Ok(self.describe_config()),
+            "clear" => {
+                self.stations.clear();
+                self.arrivals.clear();
+                self.due_dates.clear();
+                Ok("Línea vacía".to_string())
+            }
+            "defaults" => {
+                *self = Self { last_run: self.last_run.take(), ..Self::new() };
+                Ok("Configuración por defecto restaurada".to_string())
+            }
+            "run" => {
+                let result = self.simulate(None)?;
+                let summary = summarize(&result);
+                self.last_run = Some(result);
+                Ok(summary)
+            }
+            "compare" => self.compare(&args),
+            "report" | "csv" | "json" => {
+                let run = self.last_result()?;
+                let calculator = MetricsCalculator::new();
+                Ok(match command {
+                    "report" => calculator.generate_report(&run.metrics, &run.stations),
+                    "csv" => calculator.generate_csv_report(&run.metrics),
+                    _ => calculator.generate_json_report(&run.metrics),
+                })
+            }
+            "waits" => {
+                let run = self.last_result()?;
+                let matrix = MetricsCalculator::wait_matrix(&run.metrics, &run.stations);
+                match args.as_slice() {
+                    [] | ["csv"] => Ok(matrix.to_csv()),
+                    ["json"] => Ok(matrix.to_json()),
+                    _ => Err("Uso: waits [csv|json]".to_string()),
+                }
+            }
+            "product" => self.describe_product(&args),
+            other => Err(format!("Comando desconocido: '{}' (use 'help')", other)),
+        }
+    }
+
+    /// Agrega una estación al final de la línea.
+    fn add_station(&mut self, args: &[&str]) -> Result<String, String> {
+        let [name, processing_time, algorithm @ ..] = args else {
+            return Err("Uso: add-station <nombre> <ms> [algoritmo]".to_string());
+        };
+        let processing_time = parse_millis(processing_time)?;
+        let algorithm = (!algorithm.is_empty())
+            .then(|| parse_algorithm(algorithm))
+            .transpose()?;
+
+        self.stations.push((StationConfig::new(*name, processing_time), algorithm));
+        Ok(format!("Estación {} agregada: {}", self.stations.len(), name))
+    }
+
+    /// Elimina la estación con el nombre indicado.
+    fn remove_station(&mut self, args: &[&str]) -> Result<String, String> {
+        let [name] = args else {
+            return Err("Uso: remove-station <nombre>".to_string());
+        };
+        let index = self
+            .stations
+            .iter()
+            .position(|(station, _)| station.name == *name)
+            .ok_or_else(|| format!("No existe la estación '{}'", name))?;
+        self.stations.remove(index);
+        Ok(format!("Estación '{}' eliminada", name))
+    }
+
+    /// Ejecuta la línea actual y compara sus resultados con otro algoritmo
+    /// aplicado a todas las estaciones.
+    fn compare(&mut self, args: &[&str]) -> Result<String, String> {
+        let alternative = parse_algorithm(args)?;
+        let current = match self.last_run.take() {
+            Some(run) => run,
+            None => self.simulate(None)?,
+        };
+        let other = self.simulate(Some(alternative.clone()))?;
+
+        let mut output = String::new();
+        let _ = writeln!(output, "{:<24} {:>28} {:>28}", "Métrica", current.label, other.label);
+        let rows = [
+            ("Tiempo total", current.metrics.total_simulation_time, other.metrics.total_simulation_time),
+            ("Espera promedio", current.metrics.average_wait_time, other.metrics.average_wait_time),
+            ("Turnaround promedio", current.metrics.average_turnaround_time, other.metrics.average_turnaround_time),
+            ("Tardanza total", current.metrics.total_tardiness, other.metrics.total_tardiness),
+        ];
+        for (name, current_value, other_value) in rows {
+            let _ = writeln!(
+                output,
+                "{:<24} {:>28} {:>28}",
+                name,
+                MetricsCalculator::format_duration(current_value),
+                MetricsCalculator::format_duration(other_value)
+            );
+        }
+        let _ = write!(
+            output,
+            "{:<24} {:>28.3} {:>28.3}",
+            "Throughput (prod/s)", current.metrics.throughput, other.metrics.throughput
+        );
+        let changes = ScenarioDiff::compare(&self.build_simulation(None), &self.build_simulation(Some(alternative)));
+        let _ = write!(output, "\n\n{}", changes);
+        let _ = write!(output, "\n{}", compare_runs(&current.metrics, &other.metrics));
+
+        self.last_run = Some(current);
+        Ok(output)
+    }
+
+    /// Ejecuta la simulación configurada.
+    /// 
+    /// Si se indica `override_algorithm`, todas las estaciones lo usan en
+    /// lugar de sus algoritmos configurados.
+    fn simulate(&self, override_algorithm: Option<SchedulingAlgorithm>) -> Result<RunResult, String> {
+        if self.stations.is_empty() {
+            return Err("La línea no tiene estaciones (use add-station o defaults)".to_string());
+        }
+        if self.arrivals.is_empty() {
+            return Err("No hay llegadas programadas (use arrivals)".to_string());
+        }
+
+        let stations: Vec<StationConfig> = self.stations.iter().map(|(station, _)| station.clone()).collect();
+        let label = match &override_algorithm {
+            Some(algorithm) => algorithm.to_string(),
+            None => self.describe_algorithms(),
+        };
+        let metrics = match self.build_simulation(override_algorithm).run() {
+            Ok(metrics) => metrics,
+            Err(error) => return Err(format!("La simulación falló: {}", error)),
+        };
+        Ok(RunResult { label, stations, metrics })
+    }
+
+    /// Arma la simulación de la línea actual, con todas las estaciones
+    /// usando `override_algorithm` si se indica.
+    fn build_simulation(&self, override_algorithm: Option<SchedulingAlgorithm>) -> Simulation {
+        let stations: Vec<StationConfig> = self.stations.iter().map(|(station, _)| station.clone()).collect();
+        let mut simulation = Simulation::with_config(
+            stations,
+            override_algorithm.clone().unwrap_or_else(|| self.algorithm.clone()),
+            self.arrivals.clone(),
+        );
+        if override_algorithm.is_none() {
+            for (index, (_, algorithm)) in self.stations.iter().enumerate() {
+                if let Some(algorithm) = algorithm {
+                    simulation.set_station_algorithm(index, algorithm.clone());
+                }
+            }
+        }
+        simulation.set_due_dates(self.due_dates.clone());
+        simulation
+    }
+
+    /// Describe los algoritmos de la línea en una etiqueta corta.
+    fn describe_algorithms(&self) -> String {
+        if self.stations.iter().all(|(_, algorithm)| algorithm.is_none()) {
+            self.algorithm.to_string()
+        } else {
+            "Configuración actual".to_string()
+        }
+    }
+
+    /// Describe la configuración actual de la línea.
+    fn describe_config(&self) -> String {
+        let mut output = format!("Algoritmo general: {}\nEstaciones:", self.algorithm);
+        if self.stations.is_empty() {
+            output.push_str(" ninguna");
+        }
+        for (index, (station, algorithm)) in self.stations.iter().enumerate() {
+            let _ = write!(
+                output,
+                "\n  {}. {} ({} ms)",
+                index + 1,
+                station.name,
+                station.processing_time.as_millis()
+            );
+            if let Some(algorithm) = algorithm {
+                let _ = write!(output, " - {}", algorithm);
+            }
+        }
+        let _ = write!(output, "\nLlegadas (ms): {}", format_millis_list(&self.arrivals));
+        if !self.due_dates.is_empty() {
+            let _ = write!(output, "\nFechas de entrega (ms): {}", format_millis_list(&self.due_dates));
+        }
+        output
+    }
+
+    /// Describe las métricas de un producto de la última ejecución.
+    fn describe_product(&self, args: &[&str]) -> Result<String, String> {
+        let [id] = args else {
+            return Err("Uso: product <id>".to_string());
+        };
+        let id: usize = id.parse().map_err(|_| format!("ID de producto inválido: '{}'", id))?;
+        let run = self.last_result()?;
+        let product = run
+            .metrics
+            .products
+            .iter()
+            .chain(&run.metrics.incomplete_products)
+            .find(|product| product.product_id == id)
+            .ok_or_else(|| format!("No existe el producto {} en la última ejecución", id))?;
+
+        let mut output = format!(
+            "Producto {}\n  Llegada: {}\n  Espera total: {}\n  Turnaround: {}",
+            id,
+            MetricsCalculator::format_duration(product.arrival_time),
+            MetricsCalculator::format_duration(product.total_wait_time),
+            MetricsCalculator::format_duration(product.turnaround_time)
+        );
+        if let Some(due_date) = product.due_date {
+            let _ = write!(
+                output,
+                "\n  Fecha de entrega: {} (tardanza {})",
+                MetricsCalculator::format_duration(due_date),
+                MetricsCalculator::format_duration(product.tardiness)
+            );
+        }
+        for (station, (entry, exit)) in run.stations.iter().zip(&product.station_times) {
+            let _ = write!(
+                output,
+                "\n  {}: {} - {}",
+                station.name,
+                MetricsCalculator::format_duration(*entry),
+                MetricsCalculator::format_duration(*exit)
+            );
+        }
+        Ok(output)
+    }
+
+    /// Obtiene el resultado de la última ejecución.
+    fn last_result(&self) -> Result<&RunResult, String> {
+        self.last_run
+            .as_ref()
+            .ok_or_else(|| "Todavía no hay resultados (use run)".to_string())
+    }
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resume los resultados de una ejecución.
+fn summarize(run: &RunResult) -> String {
+    let metrics = &run.metrics;
+    format!(
+        "Resultado ({})\n  Productos completados: {}\n  Tiempo total: {}\n  Espera promedio: {}\n  Turnaround promedio: {}\n  Throughput: {:.3} prod/s\n  Orden de finalización: {:?}",
+        run.label,
+        metrics.products.len(),
+        MetricsCalculator::format_duration(metrics.total_simulation_time),
+        MetricsCalculator::format_duration(metrics.average_wait_time),
+        MetricsCalculator::format_duration(metrics.average_turnaround_time),
+        metrics.throughput,
+        metrics.completion_order
+    )
+}
+
+/// Lee un algoritmo: `fcfs`, `rr [quantum_ms]`, `edd` o `cr`.
+fn parse_algorithm(args: &[&str]) -> Result<SchedulingAlgorithm, String> {
+    match args {
+        ["fcfs"] => Ok(SchedulingAlgorithm::fcfs()),
+        ["rr"] => Ok(SchedulingAlgorithm::round_robin(Duration::from_millis(config::DEFAULT_QUANTUM_MS))),
+        ["rr", quantum] => {
+            let quantum = parse_millis(quantum)?;
+            if quantum.is_zero() {
+                return Err("El quantum debe ser mayor que 0".to_string());
+            }
+            Ok(SchedulingAlgorithm::round_robin(quantum))
+        }
+        ["edd"] => Ok(SchedulingAlgorithm::earliest_due_date()),
+        ["cr"] => Ok(SchedulingAlgorithm::critical_ratio()),
+        [] => Err("Falta el algoritmo (fcfs, rr [quantum_ms], edd o cr)".to_string()),
+        _ => Err(format!(
+            "Algoritmo inválido: '{}' (use fcfs, rr [quantum_ms], edd o cr)",
+            args.join(" ")
+        )),
+    }
+}
+
+/// Lee un tiempo en milisegundos.
+fn parse_millis(text: &str) -> Result<Duration, String> {
+    text.parse::<u64>()
+        .map(Duration::from_millis)
+        .map_err(|_| format!("Tiempo inválido: '{}' (se esperan milisegundos enteros)", text))
+}
+
+/// Lee una lista de tiempos en milisegundos.
+fn parse_millis_list(args: &[&str]) -> Result<Vec<Duration>, String> {
+    args.iter().map(|arg| parse_millis(arg)).collect()
+}
+
+/// Formatea una lista de tiempos en milisegundos.
+fn format_millis_list(times: &[Duration]) -> String {
+    let times: Vec<String> = times.iter().map(|time| time.as_millis().to_string()).collect();
+    if times.is_empty() {
+        "ninguna".to_string()
+    } else {
+        times.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configuration_commands() {
+        let mut repl = Repl::new();
+        assert_eq!(repl.stations.len(), config::STATION_COUNT);
+
+        repl.execute("clear").unwrap();
+        repl.execute("add-station corte 400 rr 100").unwrap();
+        repl.execute("add-station empaque 200").unwrap();
+        repl.execute("arrivals 0 100 200").unwrap();
+        assert_eq!(repl.stations[0].1, Some(SchedulingAlgorithm::round_robin(Duration::from_millis(100))));
+        assert_eq!(repl.stations[1].1, None);
+        assert_eq!(repl.arrivals.len(), 3);
+
+        let config = repl.execute("config").unwrap();
+        assert!(config.contains("corte (400 ms) - Round Robin"), "{}", config);
+
+        repl.execute("remove-station corte").unwrap();
+        assert_eq!(repl.stations.len(), 1);
+        assert!(repl.execute("add-station x abc").is_err());
+        assert!(repl.execute("algorithm rr 0").is_err());
+        assert!(repl.execute("frobnicate").is_err());
+        assert_eq!(repl.execute("   ").unwrap(), "");
+    }
+
+    #[test]
+    fn test_run_compare_and_inspect() {
+        let mut repl = Repl::new();
+        assert!(repl.execute("report").is_err());
+        repl.execute("clear").unwrap();
+        assert!(repl.execute("run").is_err());
+
+        repl.execute("add-station corte 10").unwrap();
+        repl.execute("arrivals 0 1").unwrap();
+        let comparison = repl.execute("compare rr 3").unwrap();
+        assert!(comparison.contains("FCFS") && comparison.contains("Round Robin"), "{}", comparison);
+        assert!(comparison.contains("t pareada p = "), "{}", comparison);
+        assert!(comparison.contains("Algoritmo general: FCFS -> Round Robin (quantum 3 ms)"), "{}", comparison);
+        assert_eq!(repl.last_run.as_ref().unwrap().metrics.completion_order, vec![1, 2]);
+
+        let product = repl.execute("product 2").unwrap();
+        assert!(product.contains("corte:"), "{}", product);
+        assert!(repl.execute("json").unwrap().starts_with('{'));
+        assert!(repl.execute("waits").unwrap().starts_with("Product,corte\n"));
+        assert!(repl.execute("waits json").unwrap().contains("\"column_totals\""));
+        assert!(repl.execute("waits xml").is_err());
+    }
+
+    #[test]
+    fn test_session_stops_at_quit() {
+        let mut repl = Repl::new();
+        let mut output = Vec::new();
+        repl.run("help\nalgorithm edd\nquit\nalgorithm fcfs\n".as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("add-station"));
+        assert!(repl.algorithm.is_due_date_rule());
+    }
+}
@@ -0,0 +1,254 @@
+//! # Módulo de Réplicas
+//! 
+//! Este módulo ejecuta réplicas de una misma simulación que solo difieren
+//! en la semilla del orden de llegada y resume sus resultados. En lugar de
+//! los valores de una única ejecución, los reportes muestran para cada
+//! estadística la media entre réplicas y la semiamplitud de su intervalo
+//! de confianza (`media ± IC`), con la cantidad de réplicas y el rango de
+//! semillas en el encabezado. Los reportes se generan en texto, Markdown
+//! o HTML.
+//! 
+//! Bajo el encabezado se indican los metadatos de la primera réplica
+//! (versión, commit, fecha y equipo; ver [`RunMetadata`]).
+//! 
+//! El intervalo usa la distribución t de Student con `n - 1` grados de
+//! libertad, por lo que requiere al menos dos réplicas; con una sola la
+//! semiamplitud es infinita.
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::analysis::t_critical;
+use crate::arrival::ArrivalOrder;
+use crate::batch::BatchRunner;
+use crate::error::SimulationError;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::provenance::RunMetadata;
+use crate::simulation::Simulation;
+
+/// Media e intervalo de confianza de una estadística entre réplicas.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplicatedStatistic {
+    /// Nombre de la estadística
+    pub name: String,
+    /// Media entre réplicas
+    pub mean: f64,
+    /// Semiamplitud del intervalo de confianza
+    pub half_width: f64,
+    /// Indica si la estadística es un tiempo en segundos
+    pub is_time: bool,
+}
+
+impl ReplicatedStatistic {
+    /// Formatea la estadística como `media ± semiamplitud`; los tiempos
+    /// usan el formato de duración de los reportes.
+    pub fn format(&self) -> String {
+        if !self.half_width.is_finite() {
+            return format!("{} ± ∞", self.format_value(self.mean));
+        }
+        format!("{} ± {}", self.format_value(self.mean), self.format_value(self.half_width))
+    }
+
+    /// Formatea un valor con las unidades de la estadística.
+    fn format_value(&self, value: f64) -> String {
+        if self.is_time {
+            MetricsCalculator::format_duration(Duration::from_secs_f64(value.max(0.0)))
+        } else {
+            format!("{:.3}", value)
+        }
+    }
+}
+
+/// Resultados de las réplicas de una simulación.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Replications, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+/// let arrivals = (0..4).map(|i| Duration::from_millis(i * 2)).collect();
+/// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+/// 
+/// let replications = Replications::run(&simulation, 1..=3).unwrap();
+/// assert_eq!(replications.seeds, vec![1, 2, 3]);
+/// let report = replications.report();
+/// assert!(report.starts_with("=== RÉPLICAS (n = 3, semillas 1-3, IC 95%) ==="));
+/// assert!(report.contains(" ± "));
+/// assert!(replications.to_markdown().contains("| Estadística | Media ± IC 95% |"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Replications {
+    /// Semilla de cada réplica
+    pub seeds: Vec<u64>,
+    /// Métricas de cada réplica, en el mismo orden que `seeds`
+    pub metrics: Vec<SimulationMetrics>,
+    /// Nivel de confianza de los intervalos (0.95 por defecto)
+    pub confidence: f64,
+}
+
+impl Replications {
+    /// Ejecuta una réplica por semilla en paralelo.
+    /// 
+    /// Cada réplica es una copia de `simulation` cuyo orden de llegada se
+    /// baraja con la semilla (ver [`ArrivalOrder::shuffled`]); los tiempos
+    /// de llegada y el resto de la configuración no cambian.
+    /// 
+    /// # Errors
+    /// 
+    /// Devuelve el error de la primera réplica que falle
+    pub fn run(simulation: &Simulation, seeds: RangeInclusive<u64>) -> Result<Self, SimulationError> {
+        let seeds: Vec<u64> = seeds.collect();
+        let replicas = seeds
+            .iter()
+            .map(|&seed| simulation.clone_with(|replica| {
+                replica.set_arrival_order(ArrivalOrder::shuffled(seed));
+            }))
+            .collect();
+        let metrics = BatchRunner::new()
+            .run(replicas)
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_runs(seeds.into_iter().zip(metrics).collect()))
+    }
+
+    /// Agrupa réplicas ejecutadas por el usuario.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `runs` - Pares (semilla, métricas) de cada réplica
+    pub fn from_runs(runs: Vec<(u64, SimulationMetrics)>) -> Self {
+        let (seeds, metrics) = runs.into_iter().unzip();
+        Self {
+            seeds,
+            metrics,
+            confidence: 0.95,
+        }
+    }
+
+    /// Cambia el nivel de confianza de los intervalos.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `confidence` no está entre 0 y 1 (excluidos)
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        assert!(
+            confidence > 0.0 && confidence < 1.0,
+            "El nivel de confianza debe estar entre 0 y 1"
+        );
+        self.confidence = confidence;
+        self
+    }
+
+    /// Calcula la media y el intervalo de confianza de las estadísticas
+    /// principales.
+    pub fn statistics(&self) -> Vec<ReplicatedStatistic> {
+        let seconds = |value: fn(&SimulationMetrics) -> Duration| -> Vec<f64> {
+            self.metrics.iter().map(|metrics| value(metrics).as_secs_f64()).collect()
+        };
+        let values = |value: fn(&SimulationMetrics) -> f64| -> Vec<f64> {
+            self.metrics.iter().map(value).collect()
+        };
+        vec![
+            self.statistic("Tiempo promedio de espera", seconds(|m| m.average_wait_time), true),
+            self.statistic("Tiempo promedio de turnaround", seconds(|m| m.average_turnaround_time), true),
+            self.statistic("Makespan", seconds(|m| m.total_simulation_time), true),
+            self.statistic("Throughput (productos/segundo)", values(|m| m.throughput), false),
+            self.statistic("Slowdown promedio", values(|m| m.average_slowdown), false),
+            self.statistic("Índice de equidad de Jain", values(|m| m.fairness_index), false),
+        ]
+    }
+
+    /// Calcula la media y la semiamplitud del intervalo de una muestra.
+    fn statistic(&self, name: &str, values: Vec<f64>, is_time: bool) -> ReplicatedStatistic {
+        let n = values.len() as f64;
+        let mean = if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / n };
+        let half_width = if values.len() < 2 {
+            f64::INFINITY
+        } else {
+            let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            t_critical(self.confidence, n - 1.0) * (variance / n).sqrt()
+        };
+        ReplicatedStatistic {
+            name: name.to_string(),
+            mean,
+            half_width,
+            is_time,
+        }
+    }
+
+    /// Describe las réplicas: cantidad, rango de semillas y confianza.
+    fn header(&self) -> String {
+        let seeds = match (self.seeds.iter().min(), self.seeds.iter().max()) {
+            (Some(first), Some(last)) => format!("semillas {}-{}", first, last),
+            _ => "sin semillas".to_string(),
+        };
+        format!(
+            "RÉPLICAS (n = {}, {}, IC {}%)",
+            self.metrics.len(),
+            seeds,
+            self.confidence * 100.0
+        )
+    }
+
+    /// Metadatos de la primera réplica, si hay alguna.
+    fn run_metadata(&self) -> Option<&RunMetadata> {
+        self.metrics.first().map(|metrics| &metrics.run_metadata)
+    }
+
+    /// Genera el reporte de texto, una estadística por línea.
+    pub fn report(&self) -> String {
+        let statistics = self.statistics();
+        let width = statistics.iter().map(|statistic| statistic.name.chars().count()).max().unwrap_or(0);
+        let mut report = format!("=== {} ===\n", self.header());
+        if let Some(run) = self.run_metadata() {
+            report.push_str(&format!("Ejecutado con {}\n", run.summary()));
+        }
+        for statistic in &statistics {
+            report.push_str(&format!("{:<width$}  {}\n", statistic.name, statistic.format()));
+        }
+        report
+    }
+
+    /// Genera el reporte como una tabla Markdown precedida por un título.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("## {}\n\n", self.header());
+        if let Some(run) = self.run_metadata() {
+            markdown.push_str(&format!("Ejecutado con {}\n\n", run.summary()));
+        }
+        markdown.push_str(&format!("| Estadística | Media ± IC {}% |\n", self.confidence * 100.0));
+        markdown.push_str("| --- | --- |\n");
+        for statistic in self.statistics() {
+            markdown.push_str(&format!("| {} | {} |\n", statistic.name, statistic.format()));
+        }
+        markdown
+    }
+
+    /// Genera el reporte como un fragmento HTML con un título y una tabla.
+    pub fn to_html(&self) -> String {
+        let mut html = format!("<h2>{}</h2>\n", escape_html(&self.header()));
+        if let Some(run) = self.run_metadata() {
+            html.push_str(&format!("<p>Ejecutado con {}</p>\n", escape_html(&run.summary())));
+        }
+        html.push_str("<table>\n");
+        html.push_str(&format!(
+            "  <tr><th>Estadística</th><th>Media ± IC {}%</th></tr>\n",
+            self.confidence * 100.0
+        ));
+        for statistic in self.statistics() {
+            html.push_str(&format!(
+                "  <tr><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&statistic.name),
+                escape_html(&statistic.format())
+            ));
+        }
+        html.push_str("</table>\n");
+        html
+    }
+}
+
+/// Escapa los caracteres especiales de HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
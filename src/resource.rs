@@ -0,0 +1,465 @@
+//! # Módulo de Recursos Compartidos
+//! 
+//! Este módulo modela recursos físicos que varias estaciones comparten, como
+//! una grúa que usan Corte y Empaque o un único operador calificado. Antes de
+//! procesar un producto que lo requiere, la estación debe adquirir una unidad
+//! del recurso; si todas están en uso, la estación queda bloqueada hasta que
+//! otra la libere. Así aparece contención entre estaciones que no están
+//! conectadas por la línea.
+//! 
+//! Un recurso se retiene solo durante una porción de procesamiento: con Round
+//! Robin se libera al expropiar el producto. Cuando una estación necesita
+//! varios recursos los adquiere siempre en el orden en que fueron registrados
+//! en la simulación, lo que evita interbloqueos por espera circular.
+//! 
+//! Las unidades de un recurso son idénticas (por ejemplo, dos operadores
+//! con la misma calificación), pero cada porción registra qué unidad la
+//! atendió; [`ResourceUnitReport`] resume la carga de cada una para mostrar
+//! desbalances entre ellas. Con una política de cambios de cola cada unidad
+//! tiene su propia cola y quien espera puede cambiarse a la de otra unidad
+//! (ver [`crate::jockey`]).
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use crate::jockey::JockeyPolicy;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::product::{lock_recover, Product};
+
+/// Recurso compartido entre estaciones.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{SharedResource, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![
+///     StationConfig::new("Corte", Duration::from_millis(20)),
+///     StationConfig::new("Empaque", Duration::from_millis(20)),
+/// ];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// simulation.add_shared_resource(SharedResource::new("grua", 1).used_by(0).used_by(1));
+/// let metrics = simulation.run().expect("La simulación falló");
+/// assert_eq!(metrics.products.len(), 3);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SharedResource {
+    /// Nombre del recurso
+    pub name: String,
+    /// Unidades disponibles del recurso
+    pub capacity: usize,
+    /// Estaciones que usan el recurso y para qué productos
+    pub users: Vec<ResourceUse>,
+}
+
+/// Uso de un recurso compartido por una estación.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceUse {
+    /// Índice de la estación (0-indexado)
+    pub station: usize,
+    /// Tipo de producto que requiere el recurso (`None` = todos los productos)
+    pub product_type: Option<String>,
+}
+
+impl SharedResource {
+    /// Crea un recurso sin estaciones que lo usen.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Nombre del recurso
+    /// * `capacity` - Unidades disponibles
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `capacity` es cero
+    pub fn new(name: impl Into<String>, capacity: usize) -> Self {
+        assert!(capacity > 0, "Un recurso compartido requiere al menos una unidad");
+        Self {
+            name: name.into(),
+            capacity,
+            users: Vec::new(),
+        }
+    }
+
+    /// Indica que una estación requiere el recurso para todos sus productos.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station` - Índice de la estación
+    pub fn used_by(mut self, station: usize) -> Self {
+        self.users.push(ResourceUse { station, product_type: None });
+        self
+    }
+
+    /// Indica que una estación requiere el recurso solo para los productos
+    /// de un tipo del catálogo.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station` - Índice de la estación
+    /// * `product_type` - Nombre del tipo de producto
+    pub fn used_by_type(mut self, station: usize, product_type: impl Into<String>) -> Self {
+        self.users.push(ResourceUse {
+            station,
+            product_type: Some(product_type.into()),
+        });
+        self
+    }
+
+    /// Verifica que el recurso sea válido para una línea con `station_count` estaciones.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si todas las estaciones que lo usan existen, o `Err` con la
+    /// descripción del problema
+    pub fn validate(&self, station_count: usize) -> Result<(), String> {
+        match self.users.iter().find(|user| user.station >= station_count) {
+            Some(user) => Err(format!(
+                "El recurso '{}' referencia la estación inexistente {}",
+                self.name, user.station
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Carga de una unidad de un recurso compartido durante una simulación.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{SharedResource, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// // Dos operadores atienden una estación: el primero libre toma cada porción
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// simulation.add_shared_resource(SharedResource::new("operario", 2).used_by(0));
+/// let metrics = simulation.run().expect("La simulación falló");
+/// 
+/// let units = &metrics.resource_units;
+/// assert_eq!(units.len(), 2);
+/// assert_eq!(units[0].slices, 3);
+/// assert_eq!(units[1].slices, 0);
+/// assert_eq!(metrics.products[0].slice_units[0], vec![("operario".to_string(), 0)]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceUnitReport {
+    /// Nombre del recurso
+    pub resource: String,
+    /// Índice de la unidad (0-indexado)
+    pub unit: usize,
+    /// Porciones de procesamiento que atendió la unidad
+    pub slices: usize,
+    /// Productos distintos que atendió la unidad
+    pub products: usize,
+    /// Tiempo total que la unidad estuvo asignada a porciones
+    pub busy_time: Duration,
+    /// Fracción del tiempo de simulación que la unidad estuvo ocupada
+    pub utilization: f64,
+}
+
+impl ResourceUnitReport {
+    /// Resume la carga de cada unidad de los recursos de una simulación a
+    /// partir de las porciones de todos los productos (completados,
+    /// incompletos y perdidos).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// * `resources` - Recursos compartidos de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// Un reporte por unidad, en orden de recurso y de unidad; las unidades
+    /// que nunca se usaron aparecen sin porciones
+    pub fn analyze(metrics: &SimulationMetrics, resources: &[SharedResource]) -> Vec<Self> {
+        let elapsed = metrics.total_simulation_time.as_secs_f64();
+        let products: Vec<_> = metrics.products
+            .iter()
+            .chain(&metrics.incomplete_products)
+            .chain(&metrics.lost_products)
+            .collect();
+
+        let mut reports = Vec::new();
+        for resource in resources {
+            for unit in 0..resource.capacity {
+                let mut slices = 0;
+                let mut busy_time = Duration::ZERO;
+                let mut served = HashSet::new();
+                for product in &products {
+                    for (&(_, start, end), units) in product.slices.iter().zip(&product.slice_units) {
+                        if units.iter().any(|(name, index)| *name == resource.name && *index == unit) {
+                            slices += 1;
+                            busy_time += end.saturating_sub(start);
+                            served.insert(product.product_id);
+                        }
+                    }
+                }
+                reports.push(Self {
+                    resource: resource.name.clone(),
+                    unit,
+                    slices,
+                    products: served.len(),
+                    busy_time,
+                    utilization: if elapsed > 0.0 { busy_time.as_secs_f64() / elapsed } else { 0.0 },
+                });
+            }
+        }
+        reports
+    }
+}
+
+impl fmt::Display for ResourceUnitReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} #{}: {} porciones, {} productos, ocupada {} ({:.1}%)",
+            self.resource,
+            self.unit + 1,
+            self.slices,
+            self.products,
+            MetricsCalculator::format_duration(self.busy_time),
+            self.utilization * 100.0
+        )
+    }
+}
+
+/// Unidades de un recurso durante una ejecución.
+/// 
+/// Las unidades son idénticas pero se identifican por su índice, para
+/// reportar qué unidad atendió cada porción de procesamiento; siempre se
+/// entrega la unidad libre de menor índice. Con una política de cambios de
+/// cola cada unidad tiene su propia cola de espera (ver [`crate::jockey`]).
+#[derive(Debug)]
+pub(crate) struct ResourcePool {
+    pub(crate) name: String,
+    /// Unidades en uso y colas de espera
+    state: Mutex<PoolState>,
+    released: Condvar,
+    /// Política con la que los que esperan cambian de cola de unidad
+    policy: JockeyPolicy,
+}
+
+/// Estado compartido de las unidades de un recurso.
+#[derive(Debug)]
+struct PoolState {
+    /// Indica, por unidad, si está en uso
+    in_use: Vec<bool>,
+    /// Turnos que esperan cada unidad, en orden de llegada (solo con una
+    /// política de cambios de cola)
+    queues: Vec<VecDeque<u64>>,
+    /// Siguiente turno a entregar
+    next_ticket: u64,
+}
+
+impl PoolState {
+    /// Productos de una unidad: en espera y en uso.
+    fn load(&self, unit: usize) -> usize {
+        self.queues[unit].len() + usize::from(self.in_use[unit])
+    }
+
+    /// Unidad con menos productos y, entre ellas, la de menor índice.
+    fn shortest(&self, except: Option<usize>) -> Option<usize> {
+        (0..self.in_use.len())
+            .filter(|&unit| Some(unit) != except)
+            .min_by_key(|&unit| (self.load(unit), unit))
+    }
+}
+
+impl ResourcePool {
+    /// Crea el conjunto de unidades de un recurso.
+    pub(crate) fn new(resource: &SharedResource) -> Self {
+        Self {
+            name: resource.name.clone(),
+            state: Mutex::new(PoolState {
+                in_use: vec![false; resource.capacity],
+                queues: vec![VecDeque::new(); resource.capacity],
+                next_ticket: 0,
+            }),
+            released: Condvar::new(),
+            policy: JockeyPolicy::Disabled,
+        }
+    }
+
+    /// Asigna la política con la que los que esperan una unidad cambian de
+    /// cola; con un recurso de una sola unidad no tiene efecto.
+    pub(crate) fn with_jockeying(mut self, policy: JockeyPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Marca como ocupada la primera unidad libre sin turnos en espera, si la hay.
+    fn take_unit(&self, state: &mut PoolState) -> Option<ResourceGuard<'_>> {
+        let unit = (0..state.in_use.len()).find(|&unit| !state.in_use[unit] && state.queues[unit].is_empty())?;
+        state.in_use[unit] = true;
+        Some(ResourceGuard { pool: self, unit })
+    }
+
+    /// Adquiere una unidad sin esperar.
+    /// 
+    /// # Returns
+    /// 
+    /// `Some(guard)` si había una unidad libre, `None` en caso contrario
+    pub(crate) fn try_acquire(&self) -> Option<ResourceGuard<'_>> {
+        self.take_unit(&mut lock_recover(&self.state))
+    }
+
+    /// Adquiere una unidad, esperando a que otra estación libere una.
+    /// 
+    /// Sin política de cambios de cola se entrega la primera unidad que se
+    /// libere. Con ella, se espera en la cola de la unidad con menos
+    /// productos y, cada vez que cambian las colas, el último de la cola se
+    /// cambia a otra si la política lo permite.
+    /// 
+    /// # Returns
+    /// 
+    /// La unidad adquirida y las veces que se cambió de cola
+    pub(crate) fn acquire(&self) -> (ResourceGuard<'_>, usize) {
+        let mut state = lock_recover(&self.state);
+        if !self.policy.is_enabled() || state.in_use.len() < 2 {
+            loop {
+                if let Some(guard) = self.take_unit(&mut state) {
+                    return (guard, 0);
+                }
+                state = self.released
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+        }
+
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        let mut unit = state.shortest(None).expect("El recurso tiene unidades");
+        state.queues[unit].push_back(ticket);
+        let mut moves = 0;
+        loop {
+            if !state.in_use[unit] && state.queues[unit].front() == Some(&ticket) {
+                state.queues[unit].pop_front();
+                state.in_use[unit] = true;
+                // Quien sigue en la cola puede querer cambiarse
+                self.released.notify_all();
+                return (ResourceGuard { pool: self, unit }, moves);
+            }
+            if state.queues[unit].back() == Some(&ticket) {
+                let current = state.load(unit);
+                let target = state.shortest(Some(unit)).filter(|&other| self.policy.should_move(current, state.load(other)));
+                if let Some(other) = target {
+                    state.queues[unit].pop_back();
+                    state.queues[other].push_back(ticket);
+                    unit = other;
+                    moves += 1;
+                    self.released.notify_all();
+                    continue;
+                }
+            }
+            state = self.released
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+    }
+}
+
+/// Unidad adquirida de un recurso; se libera al descartarse, incluso si la
+/// estación hace panic mientras la retiene.
+#[derive(Debug)]
+pub(crate) struct ResourceGuard<'a> {
+    pool: &'a ResourcePool,
+    unit: usize,
+}
+
+impl ResourceGuard<'_> {
+    /// Recurso e índice (0-indexado) de la unidad adquirida.
+    pub(crate) fn assignment(&self) -> (String, usize) {
+        (self.pool.name.clone(), self.unit)
+    }
+}
+
+impl Drop for ResourceGuard<'_> {
+    fn drop(&mut self) {
+        lock_recover(&self.pool.state).in_use[self.unit] = false;
+        // Con colas por unidad debe despertar quien encabeza la de esta unidad
+        if self.pool.policy.is_enabled() {
+            self.pool.released.notify_all();
+        } else {
+            self.pool.released.notify_one();
+        }
+    }
+}
+
+/// Recurso requerido por una estación.
+#[derive(Clone, Debug)]
+pub(crate) struct StationResource {
+    pub(crate) pool: Arc<ResourcePool>,
+    /// Tipos de producto que lo requieren en la estación (`None` = todos)
+    pub(crate) product_types: Vec<Option<String>>,
+}
+
+impl StationResource {
+    /// Indica si la estación necesita el recurso para procesar el producto.
+    pub(crate) fn applies_to(&self, product: &Product) -> bool {
+        self.product_types.iter().any(|product_type| match product_type {
+            Some(product_type) => product.product_type.as_deref() == Some(product_type.as_str()),
+            None => true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_counts_units() {
+        let pool = ResourcePool::new(&SharedResource::new("grua", 2));
+        let first = pool.try_acquire().expect("Debe haber una unidad libre");
+        let (_second, moves) = pool.acquire();
+        assert_eq!(moves, 0);
+        assert!(pool.try_acquire().is_none());
+        drop(first);
+        assert!(pool.try_acquire().is_some());
+        assert!(SharedResource::new("grua", 1).used_by(3).validate(3).is_err());
+    }
+
+    #[test]
+    fn test_waiters_jockey_to_a_shorter_unit_queue() {
+        let pool = ResourcePool::new(&SharedResource::new("operario", 2)).with_jockeying(JockeyPolicy::shorter_queue(0));
+        let wait_until = |done: &dyn Fn(&PoolState) -> bool| {
+            while !done(&lock_recover(&pool.state)) {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        };
+        let first = pool.try_acquire().expect("Debe haber una unidad libre");
+        let second = pool.try_acquire().expect("Debe haber una unidad libre");
+
+        std::thread::scope(|scope| {
+            // Los tres se forman en la cola más corta: 0, 1 y 0
+            let mut waiters: Vec<_> = (1..=3)
+                .map(|queued| {
+                    let waiter = scope.spawn(|| pool.acquire());
+                    wait_until(&|state| state.queues.iter().map(VecDeque::len).sum::<usize>() == queued);
+                    waiter
+                })
+                .collect();
+            assert_eq!(lock_recover(&pool.state).queues[0].len(), 2);
+
+            // Al liberarse la unidad 1, el último de la cola 0 se cambia a
+            // ella; el primero ya no gana posiciones y se queda
+            drop(second);
+            wait_until(&|state| state.in_use[1] && state.queues[0].len() == 1 && state.queues[1].len() == 1);
+            let (guard, moves) = waiters.remove(1).join().unwrap();
+            assert_eq!((guard.unit, moves), (1, 0));
+
+            drop(guard);
+            let (guard, moves) = waiters.remove(1).join().unwrap();
+            assert_eq!((guard.unit, moves), (1, 1));
+
+            drop(first);
+            let (guard, moves) = waiters.remove(0).join().unwrap();
+            assert_eq!((guard.unit, moves), (0, 0));
+        });
+    }
+}
@@ -0,0 +1,66 @@
+//! # Módulo de Números Aleatorios
+//! 
+//! Generador pseudoaleatorio determinista (SplitMix64) utilizado por los
+//! procesos de llegada aleatorios. Con la misma semilla produce siempre la
+//! misma secuencia, lo que hace reproducibles las simulaciones.
+
+/// Generador pseudoaleatorio SplitMix64.
+#[derive(Clone, Debug)]
+pub(crate) struct SimRng {
+    state: u64,
+}
+
+impl SimRng {
+    /// Crea un generador a partir de una semilla.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Genera el siguiente número de 64 bits.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Genera un número uniforme en el intervalo `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Genera una muestra de una distribución exponencial con la media dada.
+    pub(crate) fn next_exponential(&mut self, mean: f64) -> f64 {
+        -(1.0 - self.next_f64()).ln() * mean
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SimRng::new(42);
+        let mut b = SimRng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_uniform_and_exponential_ranges() {
+        let mut rng = SimRng::new(7);
+        let samples: Vec<f64> = (0..10_000).map(|_| rng.next_exponential(2.0)).collect();
+        assert!(samples.iter().all(|&x| x >= 0.0));
+
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!((mean - 2.0).abs() < 0.1);
+
+        for _ in 0..1000 {
+            let u = rng.next_f64();
+            assert!((0.0..1.0).contains(&u));
+        }
+    }
+}
@@ -0,0 +1,457 @@
+//! # Módulo de RON
+//! 
+//! Analizador mínimo de RON (Rusty Object Notation) para leer escenarios
+//! escritos en ese formato. Como el analizador YAML, produce el mismo árbol
+//! [`Json`] que el analizador JSON, por lo que el esquema de los escenarios
+//! es el mismo en los tres formatos.
+//! 
+//! Las estructuras (`Scenario(algorithm: "rr")` o `(name: "Corte")`) y los
+//! mapas (`{"clave": 1}`) se leen como objetos; las listas (`[...]`) y las
+//! tuplas (`(1, 2)`) como arreglos; `Some(x)` como `x` y `None` como nulo.
+//! Los identificadores sueltos, como variantes de enum (`Fcfs`), se leen
+//! como strings. Se admiten comentarios `//` y `/* */` y comas finales.
+//! 
+//! ```text
+//! Scenario(
+//!     algorithm: "rr",
+//!     quantum_ms: 100,
+//!     stations: [(name: "Corte", processing_time_ms: 400)],
+//!     arrivals_ms: [0, 120, 260],
+//! )
+//! ```
+
+use crate::json::{too_deep, Json, Locations, MAX_DEPTH};
+
+/// Analiza un documento RON.
+/// 
+/// # Arguments
+/// 
+/// * `text` - Texto del documento
+/// 
+/// # Returns
+/// 
+/// El valor del documento, o `Err` con la descripción y la posición del
+/// primer error
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::json::Json;
+/// use assembly_line_simulator::ron;
+/// 
+/// let value = ron::parse("(quantum_ms: 100, stations: [\"Corte\"], due_dates_ms: None)").unwrap();
+/// assert_eq!(value, Json::parse(r#"{"quantum_ms": 100, "stations": ["Corte"], "due_dates_ms": null}"#).unwrap());
+/// ```
+pub fn parse(text: &str) -> Result<Json, String> {
+    parse_located(text).map(|(value, _)| value)
+}
+
+/// Analiza un documento RON y registra la línea de cada valor (ver
+/// [`Locations`]).
+pub fn parse_located(text: &str) -> Result<(Json, Locations), String> {
+    let mut parser = Parser { text, position: 0, depth: 0, path: String::new(), locations: Locations::default() };
+    parser.skip_whitespace()?;
+    parser.locations.record("", parser.line());
+    let value = parser.parse_value()?;
+    parser.skip_whitespace()?;
+    if parser.position < text.len() {
+        return Err(parser.error("contenido adicional después del documento"));
+    }
+    Ok((value, parser.locations))
+}
+
+/// Escribe un valor como documento RON con sangría de cuatro espacios.
+/// 
+/// Los objetos cuyas claves son identificadores se escriben como
+/// estructuras sin nombre (`(clave: valor)`) y los demás como mapas; el
+/// nulo se escribe como `None`. Los arreglos de escalares se mantienen en
+/// una línea.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::json::Json;
+/// use assembly_line_simulator::ron;
+/// 
+/// let value = Json::parse(r#"{"algorithm": "fcfs", "arrivals_ms": [0, 5]}"#).unwrap();
+/// let text = ron::to_string(&value);
+/// assert_eq!(text, "(\n    algorithm: \"fcfs\",\n    arrivals_ms: [0, 5],\n)");
+/// assert_eq!(ron::parse(&text).unwrap(), value);
+/// ```
+pub fn to_string(value: &Json) -> String {
+    let mut output = String::new();
+    write_value(value, &mut output, 0);
+    output
+}
+
+fn write_value(value: &Json, output: &mut String, depth: usize) {
+    let indent = "    ".repeat(depth + 1);
+    let close = "    ".repeat(depth);
+    match value {
+        Json::Null => output.push_str("None"),
+        Json::Array(items) if items.iter().all(Json::is_scalar) => {
+            output.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    output.push_str(", ");
+                }
+                write_value(item, output, depth);
+            }
+            output.push(']');
+        }
+        Json::Array(items) => {
+            output.push_str("[\n");
+            for item in items {
+                output.push_str(&indent);
+                write_value(item, output, depth + 1);
+                output.push_str(",\n");
+            }
+            output.push_str(&close);
+            output.push(']');
+        }
+        Json::Object(entries) => {
+            let is_struct = entries.iter().all(|(key, _)| {
+                key.starts_with(is_identifier_start) && key.chars().all(|c| c == '_' || c.is_alphanumeric())
+            });
+            output.push_str(if is_struct { "(\n" } else { "{\n" });
+            for (key, value) in entries {
+                output.push_str(&indent);
+                if is_struct {
+                    output.push_str(key);
+                } else {
+                    output.push_str(&Json::String(key.clone()).to_string());
+                }
+                output.push_str(": ");
+                write_value(value, output, depth + 1);
+                output.push_str(",\n");
+            }
+            output.push_str(&close);
+            output.push(if is_struct { ')' } else { '}' });
+        }
+        // Los strings de JSON y RON comparten los escapes usados aquí
+        scalar => output.push_str(&scalar.to_string()),
+    }
+}
+
+/// Analizador recursivo descendente.
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+    /// Colecciones, estructuras y `Some` abiertos en la posición actual
+    depth: usize,
+    /// Ruta del valor que se está leyendo
+    path: String,
+    locations: Locations,
+}
+
+impl Parser<'_> {
+    /// Línea (desde 1) de la posición actual.
+    fn line(&self) -> usize {
+        self.text[..self.position].matches('\n').count() + 1
+    }
+
+    /// Lee el valor de la ruta `path`, que empieza en la línea `line`.
+    fn parse_value_at(&mut self, path: String, line: usize) -> Result<Json, String> {
+        self.locations.record(&path, line);
+        let parent = std::mem::replace(&mut self.path, path);
+        let value = self.parse_value();
+        self.path = parent;
+        value
+    }
+
+    /// Construye un error con la línea y columna de la posición actual.
+    fn error(&self, message: &str) -> String {
+        let consumed = &self.text[..self.position];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.rsplit('\n').next().map_or(0, |last| last.chars().count()) + 1;
+        format!("RON inválido en línea {}, columna {}: {}", line, column, message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn rest(&self) -> &str {
+        &self.text[self.position..]
+    }
+
+    /// Salta espacios y comentarios.
+    fn skip_whitespace(&mut self) -> Result<(), String> {
+        loop {
+            while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+                self.position += c.len_utf8();
+            }
+            if self.rest().starts_with("//") {
+                self.position += self.rest().find('\n').unwrap_or(self.rest().len());
+            } else if self.rest().starts_with("/*") {
+                let end = self.rest().find("*/").ok_or_else(|| self.error("comentario sin cerrar"))?;
+                self.position += end + 2;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace()?;
+        match self.peek() {
+            Some('(') => self.parse_nested(Self::parse_parenthesized),
+            Some('[') => self.parse_nested(Self::parse_list),
+            Some('{') => self.parse_nested(Self::parse_map),
+            Some('"') => self.parse_string().map(Json::String),
+            Some(c) if c == '-' || c == '+' || c == '.' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) if is_identifier_start(c) => self.parse_identifier_value(),
+            Some(_) => Err(self.error("valor inesperado")),
+            None => Err(self.error("fin inesperado del documento")),
+        }
+    }
+
+    /// Lee un valor que empieza con un identificador: un booleano,
+    /// `Some(x)`, `None`, una estructura con nombre o una variante de enum.
+    fn parse_identifier_value(&mut self) -> Result<Json, String> {
+        let identifier = self.parse_identifier().to_string();
+        let start = self.position;
+        self.skip_whitespace()?;
+        let has_body = self.peek() == Some('(');
+        match identifier.as_str() {
+            "true" => return Ok(Json::Bool(true)),
+            "false" => return Ok(Json::Bool(false)),
+            "None" => return Ok(Json::Null),
+            "Some" if has_body => {
+                self.position += 1;
+                let value = self.parse_nested(Self::parse_value)?;
+                self.skip_whitespace()?;
+                self.expect(')')?;
+                return Ok(value);
+            }
+            _ => {}
+        }
+        if has_body {
+            self.parse_nested(Self::parse_parenthesized)
+        } else {
+            self.position = start;
+            Ok(Json::String(identifier))
+        }
+    }
+
+    /// Lee un valor anidado, rechazando los que superan [`MAX_DEPTH`].
+    fn parse_nested(&mut self, parse: fn(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(&too_deep()));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_identifier(&mut self) -> &str {
+        let start = self.position;
+        while let Some(c) = self.peek().filter(|&c| c == '_' || c.is_alphanumeric()) {
+            self.position += c.len_utf8();
+        }
+        &self.text[start..self.position]
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("se esperaba '{}'", expected)))
+        }
+    }
+
+    /// Lee una estructura (`(campo: valor, ...)`) o una tupla (`(a, b)`).
+    fn parse_parenthesized(&mut self) -> Result<Json, String> {
+        self.expect('(')?;
+        self.skip_whitespace()?;
+        let start = self.position;
+        let is_struct = self.peek().is_some_and(is_identifier_start) && {
+            self.parse_identifier();
+            self.skip_whitespace()?;
+            let colon = self.peek() == Some(':');
+            self.position = start;
+            colon
+        };
+
+        if is_struct {
+            let entries = self.parse_entries(')', |parser| Ok(parser.parse_identifier().to_string()))?;
+            Ok(Json::Object(entries))
+        } else {
+            self.parse_items(')').map(Json::Array)
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+        self.parse_items(']').map(Json::Array)
+    }
+
+    fn parse_map(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        let entries = self.parse_entries('}', |parser| match parser.parse_value()? {
+            Json::String(key) => Ok(key),
+            other => Ok(other.to_string()),
+        })?;
+        Ok(Json::Object(entries))
+    }
+
+    /// Lee valores separados por comas hasta `close` (coma final opcional).
+    fn parse_items(&mut self, close: char) -> Result<Vec<Json>, String> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            if self.peek() == Some(close) {
+                self.position += 1;
+                return Ok(items);
+            }
+            let path = Locations::index(&self.path, items.len());
+            let line = self.line();
+            items.push(self.parse_value_at(path, line)?);
+            self.skip_separator(close)?;
+        }
+    }
+
+    /// Lee pares `clave: valor` separados por comas hasta `close`.
+    fn parse_entries(
+        &mut self,
+        close: char,
+        key: impl Fn(&mut Self) -> Result<String, String>,
+    ) -> Result<Vec<(String, Json)>, String> {
+        let mut entries: Vec<(String, Json)> = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            if self.peek() == Some(close) {
+                self.position += 1;
+                return Ok(entries);
+            }
+            let start = self.position;
+            let line = self.line();
+            let name = key(self)?;
+            if name.is_empty() {
+                return Err(self.error("se esperaba un nombre de campo"));
+            }
+            if entries.iter().any(|(existing, _)| *existing == name) {
+                self.position = start;
+                return Err(self.error(&format!("campo repetido '{}'", name)));
+            }
+            self.skip_whitespace()?;
+            self.expect(':')?;
+            let value = self.parse_value_at(Locations::field(&self.path, &name), line)?;
+            entries.push((name, value));
+            self.skip_separator(close)?;
+        }
+    }
+
+    /// Consume la coma entre elementos, o verifica que sigue `close`.
+    fn skip_separator(&mut self, close: char) -> Result<(), String> {
+        self.skip_whitespace()?;
+        match self.peek() {
+            Some(',') => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(c) if c == close => Ok(()),
+            _ => Err(self.error(&format!("se esperaba ',' o '{}'", close))),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Json, String> {
+        let start = self.position;
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_alphanumeric() || "+-._".contains(*c)) {
+            self.position += c.len_utf8();
+        }
+        let number = self.text[start..self.position].replace('_', "");
+        // Sufijos de tipo de Rust (`100u64`, `2.5f32`)
+        let number = number
+            .find(['u', 'i', 'f'])
+            .map_or(number.as_str(), |suffix| &number[..suffix]);
+        number
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .map(Json::Number)
+            .ok_or_else(|| {
+                self.position = start;
+                self.error("número inválido")
+            })
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut result = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("string sin cerrar"));
+            };
+            self.position += c.len_utf8();
+            match c {
+                '"' => return Ok(result),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("string sin cerrar"))?;
+                    self.position += escaped.len_utf8();
+                    result.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '0' => '\0',
+                        '"' | '\\' | '\'' => escaped,
+                        _ => return Err(self.error("escape inválido")),
+                    });
+                }
+                c => result.push(c),
+            }
+        }
+    }
+}
+
+/// Indica si el carácter puede iniciar un identificador.
+fn is_identifier_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_structs_tuples_and_maps() {
+        let text = r#"
+            /* escenario */ Linea(
+                algoritmo: Fcfs,
+                tiempos: [1, 2.5, -3e2, 1_000u64],
+                par: (1, "dos"),
+                mapa: {"a": Some(true), "b": None},
+                vacio: (),
+            )"#;
+        let expected = Json::parse(
+            r#"{"algoritmo": "Fcfs", "tiempos": [1, 2.5, -300, 1000], "par": [1, "dos"], "mapa": {"a": true, "b": null}, "vacio": []}"#,
+        )
+        .unwrap();
+        assert_eq!(parse(text).unwrap(), expected);
+
+        let (_, locations) = parse_located(text).unwrap();
+        assert_eq!(locations.line(""), Some(2));
+        assert_eq!(locations.line("tiempos[3]"), Some(4));
+        assert_eq!(locations.line("mapa.b"), Some(6));
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        let error = parse("(\n  a: [1, 2\n)").unwrap_err();
+        assert!(error.contains("línea 3"), "{}", error);
+        assert!(parse("(a: 1, a: 2)").unwrap_err().contains("campo repetido"));
+        assert!(parse("(a: 1) 2").is_err());
+        assert!(parse("/* abierto").is_err());
+    }
+
+    #[test]
+    fn test_deep_nesting_is_rejected() {
+        assert!(parse(&format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH))).is_ok());
+        // Sin el límite, estos documentos desbordarían la pila
+        for text in ["[".repeat(200_000), "(".repeat(200_000), "Some(".repeat(200_000), "A(".repeat(200_000)] {
+            assert!(parse(&text).unwrap_err().contains("niveles de anidamiento"));
+        }
+    }
+}
@@ -0,0 +1,942 @@
+//! # Módulo de Ruteo entre Estaciones
+//! 
+//! Este módulo define los tipos de estación que deciden a dónde sigue cada
+//! producto:
+//! 
+//! - Una estación de inspección revisa el producto en un tiempo casi nulo y
+//!   lo envía a su salida de aprobados o de rechazados, según una
+//!   probabilidad con semilla o una condición sobre sus metadatos
+//! - Un divisor reparte los productos entre varias salidas según una
+//!   política (alternando, por tipo de producto o por un metadato), lo que
+//!   permite líneas con ramas paralelas
+//! - Una unión reúne los productos de varias estaciones de origen (por
+//!   ejemplo, el final de cada rama) y elige entre ellas de forma justa:
+//!   alternando, por pesos o por prioridad
+//! 
+//! Cada salida puede continuar la ruta, llevar el producto a otra estación
+//! (por ejemplo, a una rama paralela o de vuelta a una estación anterior
+//! para retrabajarlo) o sacarlo de la línea como rechazado.
+//! 
+//! Las decisiones se toman al crear el producto, recorriendo su ruta: el
+//! resultado de cada inspección se sortea con una secuencia propia del
+//! producto, derivada de la semilla, de su ID y de la estación. Así la ruta
+//! de cada producto es reproducible con la misma semilla, sin depender del
+//! orden en que los hilos atienden a los productos, y sus métricas se
+//! registran por visita como en cualquier otra ruta. Durante la ejecución
+//! cada estación envía los productos que saltan hacia adelante directamente
+//! al canal de su destino, de modo que una rama no espera a las estaciones
+//! de otra rama.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::rng::SimRng;
+use crate::station::StationConfig;
+
+/// Tiempo de servicio de una estación de inspección creada con
+/// [`StationConfig::inspection`].
+pub const INSPECTION_TIME: Duration = Duration::from_millis(1);
+
+/// Máximo de visitas de una ruta resuelta, que evita rutas sin fin cuando
+/// una inspección devuelve productos a una estación anterior.
+const MAX_ROUTE_VISITS: usize = 10_000;
+
+/// Tipo de una estación según lo que hace con los productos.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum StationKind {
+    /// Procesa los productos y los envía a la siguiente visita de su ruta
+    #[default]
+    Processing,
+    /// Inspecciona los productos y los envía a la salida de aprobados o a
+    /// la de rechazados
+    Inspection(Inspection),
+    /// Procesa los productos y los reparte entre sus salidas
+    Splitter(Splitter),
+    /// Atiende los productos de varias estaciones de origen alternándolas
+    /// según una política, y los envía a la siguiente visita de su ruta
+    Merger(Merger),
+}
+
+impl StationKind {
+    /// Destinos que puede elegir la estación para los productos que procesa.
+    pub fn targets(&self) -> Vec<RouteTarget> {
+        match self {
+            Self::Processing | Self::Merger(_) => vec![RouteTarget::Next],
+            Self::Inspection(inspection) => vec![inspection.on_pass, inspection.on_fail],
+            Self::Splitter(splitter) => splitter.outputs.clone(),
+        }
+    }
+
+    /// Verifica que los destinos de la estación `station` sean válidos en
+    /// una línea con `station_count` estaciones.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si la estación es válida, o `Err` con la descripción del problema
+    pub fn check(&self, station: usize, station_count: usize) -> Result<(), String> {
+        match self {
+            Self::Processing => Ok(()),
+            Self::Inspection(inspection) => inspection.check(station, station_count),
+            Self::Splitter(splitter) => splitter.check(station, station_count),
+            Self::Merger(merger) => merger.check(station, station_count),
+        }
+    }
+}
+
+/// Destino de una salida de estación.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RouteTarget {
+    /// Continúa con la siguiente visita de la ruta del producto
+    Next,
+    /// Continúa en la estación indicada (índice desde 0): en la siguiente
+    /// visita de la ruta a esa estación o, si la ruta no la visita más
+    /// adelante, en la última visita anterior (un bucle de retrabajo). Si la
+    /// ruta no visita la estación, el producto la visita y sigue con su ruta
+    Station(usize),
+    /// Sale de la línea como rechazado
+    Reject,
+}
+
+impl fmt::Display for RouteTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Next => write!(f, "siguiente estación"),
+            Self::Station(index) => write!(f, "estación {}", index + 1),
+            Self::Reject => write!(f, "rechazo"),
+        }
+    }
+}
+
+/// Criterio con el que una inspección aprueba los productos.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InspectionRule {
+    /// Aprueba cada inspección con la probabilidad indicada
+    Probability {
+        /// Probabilidad de aprobar (entre 0.0 y 1.0)
+        pass_rate: f64,
+        /// Semilla de los sorteos
+        seed: u64,
+    },
+    /// Aprueba los productos cuyo metadato `key` vale `value`
+    Metadata {
+        /// Clave del metadato
+        key: String,
+        /// Valor con el que se aprueba
+        value: String,
+    },
+}
+
+impl fmt::Display for InspectionRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Probability { pass_rate, seed } => {
+                write!(f, "aprueba el {:.1}% (semilla {})", pass_rate * 100.0, seed)
+            }
+            Self::Metadata { key, value } => write!(f, "aprueba si {} = {}", key, value),
+        }
+    }
+}
+
+/// Configuración de una estación de inspección.
+/// 
+/// Por defecto los productos aprobados siguen su ruta y los rechazados
+/// salen de la línea; ambos destinos pueden cambiarse con
+/// [`Inspection::with_pass_target`] y [`Inspection::with_fail_target`].
+/// Los productos rechazados se reportan como perdidos (ver
+/// [`LossReason::Rejected`](crate::LossReason::Rejected)) y, con una
+/// política de reemplazo, se reponen.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Inspection, RouteTarget, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// // Los productos que no aprueban el control vuelven a Ensamblaje
+/// let stations = vec![
+///     StationConfig::new("Ensamblaje", Duration::from_millis(10)),
+///     StationConfig::inspection("Control", Inspection::pass_rate(0.5, 7).with_fail_target(RouteTarget::Station(0))),
+///     StationConfig::new("Empaque", Duration::from_millis(5)),
+/// ];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+/// 
+/// let metrics = simulation.run().unwrap();
+/// assert_eq!(metrics.products.len(), 4);
+/// let control = &metrics.station_reports[1];
+/// assert_eq!(control.inspections_passed, 4);
+/// assert_eq!(control.products_processed, 4 + control.inspections_failed);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inspection {
+    /// Criterio de aprobación
+    pub rule: InspectionRule,
+    /// Destino de los productos aprobados
+    pub on_pass: RouteTarget,
+    /// Destino de los productos rechazados
+    pub on_fail: RouteTarget,
+}
+
+impl Inspection {
+    /// Crea una inspección que aprueba cada producto con la probabilidad
+    /// indicada.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pass_rate` - Probabilidad de aprobar (entre 0.0 y 1.0)
+    /// * `seed` - Semilla de los sorteos
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la probabilidad no está entre 0.0 y 1.0
+    pub fn pass_rate(pass_rate: f64, seed: u64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&pass_rate),
+            "La probabilidad de aprobar debe estar entre 0.0 y 1.0"
+        );
+        Self::with_rule(InspectionRule::Probability { pass_rate, seed })
+    }
+
+    /// Crea una inspección que aprueba los productos cuyo metadato `key`
+    /// vale `value`.
+    pub fn metadata(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::with_rule(InspectionRule::Metadata { key: key.into(), value: value.into() })
+    }
+
+    fn with_rule(rule: InspectionRule) -> Self {
+        Self {
+            rule,
+            on_pass: RouteTarget::Next,
+            on_fail: RouteTarget::Reject,
+        }
+    }
+
+    /// Asigna el destino de los productos aprobados.
+    pub fn with_pass_target(mut self, target: RouteTarget) -> Self {
+        self.on_pass = target;
+        self
+    }
+
+    /// Asigna el destino de los productos rechazados.
+    pub fn with_fail_target(mut self, target: RouteTarget) -> Self {
+        self.on_fail = target;
+        self
+    }
+
+    /// Decide si un producto aprueba la inspección.
+    fn passes(&self, metadata: &HashMap<String, String>, rng: &mut SimRng) -> bool {
+        match &self.rule {
+            InspectionRule::Probability { pass_rate, .. } => rng.next_f64() < *pass_rate,
+            InspectionRule::Metadata { key, value } => metadata.get(key) == Some(value),
+        }
+    }
+
+    /// Resultados que puede tener la inspección: `true` si algún producto
+    /// puede aprobarla y `false` si alguno puede no aprobarla.
+    pub(crate) fn outcomes(&self) -> Vec<bool> {
+        match self.rule {
+            InspectionRule::Probability { pass_rate, .. } => [(true, pass_rate > 0.0), (false, pass_rate < 1.0)]
+                .into_iter()
+                .filter_map(|(passed, possible)| possible.then_some(passed))
+                .collect(),
+            InspectionRule::Metadata { .. } => vec![true, false],
+        }
+    }
+
+    /// Crea la secuencia de sorteos de un producto en la estación.
+    fn rng(&self, product_id: usize, station: usize) -> SimRng {
+        let seed = match self.rule {
+            InspectionRule::Probability { seed, .. } => seed,
+            InspectionRule::Metadata { .. } => 0,
+        };
+        SimRng::new(seed ^ (product_id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ ((station as u64) << 48))
+    }
+
+    /// Verifica que la inspección sea válida en la estación `station` de
+    /// una línea con `station_count` estaciones.
+    /// 
+    /// Los destinos deben ser estaciones existentes, y una salida que
+    /// devuelve productos a una estación anterior (o a la misma) no puede
+    /// elegirse siempre: el producto volvería a la inspección para siempre.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si la inspección es válida, o `Err` con la descripción del problema
+    pub fn check(&self, station: usize, station_count: usize) -> Result<(), String> {
+        if let InspectionRule::Probability { pass_rate, .. } = self.rule {
+            if !(0.0..=1.0).contains(&pass_rate) {
+                return Err(format!("la probabilidad de aprobar {} no está entre 0 y 1", pass_rate));
+            }
+        }
+        for (label, target) in [("aprobados", self.on_pass), ("rechazados", self.on_fail)] {
+            if let RouteTarget::Station(index) = target {
+                if index >= station_count {
+                    return Err(format!("la salida de {} referencia la estación inexistente {}", label, index + 1));
+                }
+            }
+        }
+
+        let loops_back = |target: RouteTarget| matches!(target, RouteTarget::Station(index) if index <= station);
+        // Salidas que se toman en cada inspección: con una regla determinista,
+        // un producto rechazado (o aprobado) lo es siempre
+        let forced = match self.rule {
+            InspectionRule::Probability { pass_rate, .. } if pass_rate >= 1.0 => vec![("aprobados", self.on_pass)],
+            InspectionRule::Probability { pass_rate, .. } if pass_rate <= 0.0 => vec![("rechazados", self.on_fail)],
+            InspectionRule::Probability { .. } => Vec::new(),
+            InspectionRule::Metadata { .. } => vec![("aprobados", self.on_pass), ("rechazados", self.on_fail)],
+        };
+        match forced.into_iter().find(|&(_, target)| loops_back(target)) {
+            Some((label, target)) => Err(format!(
+                "la salida de {} vuelve a la {} y se tomaría en cada inspección, por lo que el producto nunca saldría",
+                label, target
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Criterio con el que un divisor elige la salida de cada producto.
+/// 
+/// Las salidas se indican por su posición en [`Splitter::outputs`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum RoutingPolicy {
+    /// Alterna las salidas en orden de ID: el producto `n` sale por la
+    /// salida `(n - 1) % salidas` y, si vuelve al divisor, por la siguiente
+    RoundRobin,
+    /// Elige la salida según el tipo del producto; los productos sin tipo o
+    /// de un tipo no listado salen por la primera salida
+    ByType(Vec<(String, usize)>),
+    /// Elige la salida según el valor del metadato `key`; los productos sin
+    /// el metadato o con un valor no listado salen por la primera salida
+    ByMetadata {
+        /// Clave del metadato
+        key: String,
+        /// Salida de cada valor
+        routes: Vec<(String, usize)>,
+    },
+}
+
+impl fmt::Display for RoutingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RoundRobin => write!(f, "alternando salidas"),
+            Self::ByType(_) => write!(f, "por tipo de producto"),
+            Self::ByMetadata { key, .. } => write!(f, "por el metadato {}", key),
+        }
+    }
+}
+
+/// Configuración de un divisor: una estación que reparte los productos
+/// entre varias salidas.
+/// 
+/// Un divisor con una sola salida fija a dónde siguen los productos de la
+/// estación (ver [`StationConfig::with_output`]), por ejemplo al final de
+/// una rama paralela.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{RouteTarget, Simulation, SchedulingAlgorithm, Splitter, StationConfig};
+/// 
+/// // Corte alterna los productos entre dos cabinas de pintura en paralelo
+/// let millis = Duration::from_millis;
+/// let cabinas = vec![RouteTarget::Station(1), RouteTarget::Station(2)];
+/// let stations = vec![
+///     StationConfig::new("Corte", millis(5)).with_splitter(Splitter::round_robin(cabinas)),
+///     StationConfig::new("Pintura A", millis(20)).with_output(RouteTarget::Station(3)),
+///     StationConfig::new("Pintura B", millis(20)),
+///     StationConfig::new("Empaque", millis(5)),
+/// ];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+/// 
+/// let metrics = simulation.run().unwrap();
+/// assert_eq!(metrics.station_reports[0].routed, vec![2, 2]);
+/// assert_eq!(metrics.station_reports[1].products_processed, 2);
+/// assert_eq!(metrics.station_reports[2].products_processed, 2);
+/// assert_eq!(metrics.station_reports[3].products_processed, 4);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Splitter {
+    /// Destinos entre los que se reparten los productos
+    pub outputs: Vec<RouteTarget>,
+    /// Criterio con el que se elige la salida
+    pub policy: RoutingPolicy,
+}
+
+impl Splitter {
+    /// Crea un divisor que alterna las salidas en orden de ID de producto.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si no hay salidas
+    pub fn round_robin(outputs: Vec<RouteTarget>) -> Self {
+        assert!(!outputs.is_empty(), "El divisor debe tener al menos una salida");
+        Self { outputs, policy: RoutingPolicy::RoundRobin }
+    }
+
+    /// Crea un divisor que elige la salida según el tipo del producto.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `outputs` - Destinos del divisor; el primero recibe los tipos no listados
+    /// * `routes` - Pares (tipo, posición de la salida)
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si no hay salidas
+    pub fn by_type<S: Into<String>>(outputs: Vec<RouteTarget>, routes: impl IntoIterator<Item = (S, usize)>) -> Self {
+        assert!(!outputs.is_empty(), "El divisor debe tener al menos una salida");
+        let routes = routes.into_iter().map(|(name, output)| (name.into(), output)).collect();
+        Self { outputs, policy: RoutingPolicy::ByType(routes) }
+    }
+
+    /// Crea un divisor que elige la salida según el valor de un metadato.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `outputs` - Destinos del divisor; el primero recibe los valores no listados
+    /// * `key` - Clave del metadato
+    /// * `routes` - Pares (valor, posición de la salida)
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si no hay salidas
+    pub fn by_metadata<S: Into<String>>(
+        outputs: Vec<RouteTarget>,
+        key: impl Into<String>,
+        routes: impl IntoIterator<Item = (S, usize)>,
+    ) -> Self {
+        assert!(!outputs.is_empty(), "El divisor debe tener al menos una salida");
+        let routes = routes.into_iter().map(|(value, output)| (value.into(), output)).collect();
+        Self { outputs, policy: RoutingPolicy::ByMetadata { key: key.into(), routes } }
+    }
+
+    /// Elige la salida de un producto.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product_id` - ID del producto
+    /// * `visit` - Visitas previas del producto al divisor
+    /// * `product_type` - Tipo del producto, si tiene uno
+    /// * `metadata` - Metadatos del producto
+    fn choose(
+        &self,
+        product_id: usize,
+        visit: usize,
+        product_type: Option<&str>,
+        metadata: &HashMap<String, String>,
+    ) -> usize {
+        let lookup = |routes: &[(String, usize)], value: Option<&str>| {
+            routes
+                .iter()
+                .find(|(candidate, _)| Some(candidate.as_str()) == value)
+                .map_or(0, |&(_, output)| output)
+        };
+        match &self.policy {
+            RoutingPolicy::RoundRobin => (product_id.saturating_sub(1) + visit) % self.outputs.len(),
+            RoutingPolicy::ByType(routes) => lookup(routes, product_type),
+            RoutingPolicy::ByMetadata { key, routes } => lookup(routes, metadata.get(key).map(String::as_str)),
+        }
+    }
+
+    /// Salidas que el divisor puede elegir para los productos de un tipo,
+    /// en orden y sin repetir.
+    pub(crate) fn reachable_outputs(&self, product_type: Option<&str>) -> Vec<usize> {
+        let mut outputs = match &self.policy {
+            RoutingPolicy::RoundRobin => (0..self.outputs.len()).collect(),
+            RoutingPolicy::ByType(_) => vec![self.choose(0, 0, product_type, &HashMap::new())],
+            RoutingPolicy::ByMetadata { routes, .. } => {
+                std::iter::once(0).chain(routes.iter().map(|&(_, output)| output)).collect::<Vec<_>>()
+            }
+        };
+        outputs.retain(|&output| output < self.outputs.len());
+        outputs.sort_unstable();
+        outputs.dedup();
+        outputs
+    }
+
+    /// Verifica que el divisor sea válido en la estación `station` de una
+    /// línea con `station_count` estaciones.
+    /// 
+    /// Las salidas deben ser estaciones existentes y las políticas deben
+    /// referirse a salidas existentes. Con una política por tipo o por
+    /// metadato, un producto sale siempre por la misma salida, por lo que
+    /// ninguna salida puede devolverlo a una estación anterior (o a la
+    /// misma); al alternar, basta con que una salida no lo haga.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si el divisor es válido, o `Err` con la descripción del problema
+    pub fn check(&self, station: usize, station_count: usize) -> Result<(), String> {
+        if self.outputs.is_empty() {
+            return Err("el divisor no tiene salidas".to_string());
+        }
+        for (output, target) in self.outputs.iter().enumerate() {
+            if let RouteTarget::Station(index) = target {
+                if *index >= station_count {
+                    return Err(format!("la salida {} referencia la estación inexistente {}", output + 1, index + 1));
+                }
+            }
+        }
+        let routes = match &self.policy {
+            RoutingPolicy::RoundRobin => &[][..],
+            RoutingPolicy::ByType(routes) | RoutingPolicy::ByMetadata { routes, .. } => routes.as_slice(),
+        };
+        if let Some((value, output)) = routes.iter().find(|(_, output)| *output >= self.outputs.len()) {
+            return Err(format!(
+                "'{}' se envía a la salida {}, pero el divisor tiene {} salidas",
+                value,
+                output + 1,
+                self.outputs.len()
+            ));
+        }
+
+        let loops_back = |target: &RouteTarget| matches!(target, RouteTarget::Station(index) if *index <= station);
+        let looping = match self.policy {
+            RoutingPolicy::RoundRobin if self.outputs.iter().all(loops_back) => self.outputs.first(),
+            RoutingPolicy::RoundRobin => None,
+            _ => self.outputs.iter().find(|target| loops_back(target)),
+        };
+        match looping {
+            Some(target) => Err(format!(
+                "la salida hacia la {} se tomaría en cada visita, por lo que el producto nunca saldría",
+                target
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Criterio con el que una unión elige la estación de origen del siguiente
+/// producto a atender.
+/// 
+/// El origen de un producto es la estación de su visita anterior, o la
+/// entrada de la línea (`None`) si es su primera visita.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum MergePolicy {
+    /// Alterna los orígenes en orden de la línea, omitiendo los que no
+    /// tienen productos en espera
+    #[default]
+    Alternating,
+    /// Reparte las atenciones en proporción a los pesos de los orígenes
+    /// (round robin ponderado suave); los orígenes no listados pesan 1
+    Weighted(Vec<(Option<usize>, u32)>),
+    /// Atiende siempre el primer origen de la lista que tenga productos en
+    /// espera; los orígenes no listados van al final, en orden de la línea
+    Priority(Vec<Option<usize>>),
+}
+
+impl fmt::Display for MergePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Alternating => write!(f, "alternando orígenes"),
+            Self::Weighted(_) => write!(f, "por pesos"),
+            Self::Priority(_) => write!(f, "por prioridad de origen"),
+        }
+    }
+}
+
+/// Configuración de una unión: una estación que recibe productos de varias
+/// estaciones de origen y elige entre ellas de forma justa.
+/// 
+/// Todos los orígenes llegan por el canal de entrada de la estación; la
+/// unión agrupa los productos en espera por origen y, en cada atención,
+/// elige primero el origen según su política y luego el producto de ese
+/// origen según el algoritmo de la estación. El reporte de la estación
+/// registra por origen los productos atendidos, sus esperas y las veces que
+/// el origen tenía productos en espera y se atendió a otro
+/// ([`crate::MergeSourceReport`]).
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Merger, RouteTarget, Simulation, SchedulingAlgorithm, Splitter, StationConfig};
+/// 
+/// // Empaque reúne las dos cabinas de pintura, dando el doble de turnos a la A
+/// let millis = Duration::from_millis;
+/// let cabinas = vec![RouteTarget::Station(1), RouteTarget::Station(2)];
+/// let stations = vec![
+///     StationConfig::new("Corte", millis(5)).with_splitter(Splitter::round_robin(cabinas)),
+///     StationConfig::new("Pintura A", millis(10)).with_output(RouteTarget::Station(3)),
+///     StationConfig::new("Pintura B", millis(10)),
+///     StationConfig::new("Empaque", millis(5)).with_merger(Merger::weighted([(Some(1), 2), (Some(2), 1)])),
+/// ];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+/// 
+/// let metrics = simulation.run().unwrap();
+/// let sources = &metrics.station_reports[3].merge_sources;
+/// assert_eq!(sources.iter().map(|source| (source.source, source.merged)).collect::<Vec<_>>(), vec![(Some(1), 2), (Some(2), 2)]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Merger {
+    /// Criterio con el que se elige el origen
+    pub policy: MergePolicy,
+}
+
+impl Merger {
+    /// Crea una unión que alterna los orígenes.
+    pub fn alternating() -> Self {
+        Self { policy: MergePolicy::Alternating }
+    }
+
+    /// Crea una unión que reparte las atenciones según pesos.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `weights` - Pares (origen, peso); `None` es la entrada de la línea
+    pub fn weighted(weights: impl IntoIterator<Item = (Option<usize>, u32)>) -> Self {
+        Self { policy: MergePolicy::Weighted(weights.into_iter().collect()) }
+    }
+
+    /// Crea una unión que atiende los orígenes por prioridad.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order` - Orígenes de mayor a menor prioridad; `None` es la entrada de la línea
+    pub fn priority(order: impl IntoIterator<Item = Option<usize>>) -> Self {
+        Self { policy: MergePolicy::Priority(order.into_iter().collect()) }
+    }
+
+    /// Elige el origen del siguiente producto a atender entre los que tienen
+    /// productos en espera.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `waiting` - Orígenes con productos listos, en orden de la línea y sin repetir
+    /// * `state` - Estado de la unión entre atenciones
+    pub(crate) fn choose(&self, waiting: &[Option<usize>], state: &mut MergeState) -> Option<Option<usize>> {
+        let chosen = match &self.policy {
+            MergePolicy::Alternating => waiting
+                .iter()
+                .find(|&&source| state.last.is_some_and(|last| source > last))
+                .or_else(|| waiting.first())
+                .copied(),
+            MergePolicy::Weighted(weights) => {
+                let weight = |source: Option<usize>| {
+                    weights
+                        .iter()
+                        .find(|(candidate, _)| *candidate == source)
+                        .map_or(1, |&(_, weight)| i64::from(weight))
+                };
+                let total: i64 = waiting.iter().map(|&source| weight(source)).sum();
+                for &source in waiting {
+                    *state.credits.entry(source).or_default() += weight(source);
+                }
+                let chosen = waiting
+                    .iter()
+                    .copied()
+                    .max_by_key(|source| (state.credits[source], std::cmp::Reverse(*source)));
+                if let Some(source) = chosen {
+                    *state.credits.entry(source).or_default() -= total;
+                }
+                chosen
+            }
+            MergePolicy::Priority(order) => order
+                .iter()
+                .find(|source| waiting.contains(source))
+                .or_else(|| waiting.iter().find(|source| !order.contains(source)))
+                .copied(),
+        };
+        if chosen.is_some() {
+            state.last = chosen;
+        }
+        chosen
+    }
+
+    /// Verifica que la unión sea válida en la estación `station` de una
+    /// línea con `station_count` estaciones.
+    /// 
+    /// Los orígenes de la política deben ser estaciones existentes distintas
+    /// de la unión, y los pesos deben ser positivos.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si la unión es válida, o `Err` con la descripción del problema
+    pub fn check(&self, station: usize, station_count: usize) -> Result<(), String> {
+        let sources: Vec<Option<usize>> = match &self.policy {
+            MergePolicy::Alternating => Vec::new(),
+            MergePolicy::Weighted(weights) => {
+                if let Some((source, _)) = weights.iter().find(|(_, weight)| *weight == 0) {
+                    return Err(format!("el origen {} tiene peso 0", source_name(*source)));
+                }
+                weights.iter().map(|(source, _)| *source).collect()
+            }
+            MergePolicy::Priority(order) => order.clone(),
+        };
+        match sources.into_iter().flatten().find(|&source| source >= station_count || source == station) {
+            Some(source) if source == station => Err("la unión no puede ser su propio origen".to_string()),
+            Some(source) => Err(format!("el origen {} no existe", source_name(Some(source)))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Estado de una unión entre atenciones.
+#[derive(Debug, Default)]
+pub(crate) struct MergeState {
+    /// Último origen atendido (`Some(None)` es la entrada de la línea)
+    last: Option<Option<usize>>,
+    /// Créditos de cada origen en el round robin ponderado
+    credits: HashMap<Option<usize>, i64>,
+}
+
+/// Describe un origen de una unión: una estación (desde 1) o la entrada de
+/// la línea.
+fn source_name(source: Option<usize>) -> String {
+    source.map_or_else(|| "entrada".to_string(), |station| format!("estación {}", station + 1))
+}
+
+/// Ruta de un producto con las decisiones de sus estaciones ya tomadas.
+#[derive(Debug)]
+pub(crate) struct ResolvedRoute {
+    /// Estaciones que visita el producto, en orden
+    pub(crate) route: Vec<usize>,
+    /// Resultado de la inspección de cada visita, alineado con `route`
+    /// (`None` en las visitas a estaciones que no inspeccionan)
+    pub(crate) inspections: Vec<Option<bool>>,
+    /// Salida elegida en cada visita a un divisor, alineada con `route`
+    pub(crate) outputs: Vec<Option<usize>>,
+    /// Indica si la ruta termina con el producto rechazado
+    pub(crate) rejected: bool,
+}
+
+/// Resuelve la ruta de un producto recorriendo su ruta base y aplicando
+/// las decisiones de las estaciones de inspección y de los divisores.
+/// 
+/// # Arguments
+/// 
+/// * `product_id` - ID del producto, del que se derivan sus sorteos
+/// * `base` - Ruta del producto sin desvíos (la de su tipo o todas las estaciones)
+/// * `product_type` - Tipo del producto, si tiene uno
+/// * `metadata` - Metadatos del producto
+/// * `station_configs` - Configuraciones de las estaciones de la línea
+/// 
+/// # Panics
+/// 
+/// Hace panic si la ruta supera las 10.000 visitas, lo que indica una
+/// inspección que devuelve los productos a una estación anterior con una
+/// probabilidad de aprobar casi nula
+pub(crate) fn resolve_route(
+    product_id: usize,
+    base: Vec<usize>,
+    product_type: Option<&str>,
+    metadata: &HashMap<String, String>,
+    station_configs: &[StationConfig],
+) -> ResolvedRoute {
+    let routes = |station: usize| {
+        station_configs
+            .get(station)
+            .is_some_and(|config| matches!(config.kind, StationKind::Inspection(_) | StationKind::Splitter(_)))
+    };
+    if !base.iter().any(|&station| routes(station)) {
+        let inspections = vec![None; base.len()];
+        let outputs = vec![None; base.len()];
+        return ResolvedRoute { route: base, inspections, outputs, rejected: false };
+    }
+
+    let mut rngs: HashMap<usize, SimRng> = HashMap::new();
+    let mut visits: HashMap<usize, usize> = HashMap::new();
+    let mut route = Vec::new();
+    let mut inspections = Vec::new();
+    let mut outputs = Vec::new();
+    let mut rejected = false;
+    // Posición en la ruta base de la visita actual; en una visita fuera de
+    // la ruta base, la de la visita que envió al producto a ella
+    let mut position = 0;
+    let mut station = base.first().copied();
+    while let Some(current) = station {
+        assert!(
+            route.len() < MAX_ROUTE_VISITS,
+            "La ruta del producto {} supera las {} visitas: revise las salidas de las inspecciones",
+            product_id,
+            MAX_ROUTE_VISITS
+        );
+        route.push(current);
+        let target = match &station_configs[current].kind {
+            StationKind::Inspection(inspection) => {
+                let rng = rngs
+                    .entry(current)
+                    .or_insert_with(|| inspection.rng(product_id, current));
+                let passed = inspection.passes(metadata, rng);
+                inspections.push(Some(passed));
+                outputs.push(None);
+                if passed { inspection.on_pass } else { inspection.on_fail }
+            }
+            StationKind::Splitter(splitter) => {
+                let visit = visits.entry(current).or_default();
+                let output = splitter.choose(product_id, *visit, product_type, metadata);
+                *visit += 1;
+                inspections.push(None);
+                outputs.push(Some(output));
+                splitter.outputs[output]
+            }
+            StationKind::Processing | StationKind::Merger(_) => {
+                inspections.push(None);
+                outputs.push(None);
+                RouteTarget::Next
+            }
+        };
+
+        rejected = target == RouteTarget::Reject;
+        let next = next_visit(&base, position, target);
+        if let Some((next_position, _)) = next {
+            position = next_position;
+        }
+        station = next.map(|(_, station)| station);
+    }
+
+    ResolvedRoute { route, inspections, outputs, rejected }
+}
+
+/// Calcula la siguiente visita de un producto que sale por `target` de la
+/// visita en la posición `position` de su ruta base.
+/// 
+/// Un salto a una estación continúa en su siguiente aparición en la ruta
+/// base o, si no vuelve a aparecer, en su última aparición anterior; si la
+/// ruta base no la incluye, es un desvío que conserva la posición.
+/// 
+/// # Returns
+/// 
+/// La posición en la ruta base y la estación de la siguiente visita, o
+/// `None` si el producto termina su ruta o es rechazado
+pub(crate) fn next_visit(base: &[usize], position: usize, target: RouteTarget) -> Option<(usize, usize)> {
+    match target {
+        RouteTarget::Next => base.get(position + 1).map(|&station| (position + 1, station)),
+        RouteTarget::Station(index) => {
+            let ahead = base[position + 1..].iter().position(|&visit| visit == index);
+            let behind = base[..=position].iter().rposition(|&visit| visit == index);
+            let position = match (ahead, behind) {
+                (Some(offset), _) => position + 1 + offset,
+                (None, Some(visit)) => visit,
+                (None, None) => position,
+            };
+            Some((position, index))
+        }
+        RouteTarget::Reject => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line() -> Vec<StationConfig> {
+        vec![
+            StationConfig::new("Corte", Duration::from_millis(10)),
+            StationConfig::new("Ensamblaje", Duration::from_millis(10)),
+            StationConfig::inspection("Control", Inspection::metadata("calidad", "ok")),
+            StationConfig::new("Empaque", Duration::from_millis(10)),
+        ]
+    }
+
+    #[test]
+    fn test_metadata_rule_routes_to_targets() {
+        let mut configs = line();
+        let ok = HashMap::from([("calidad".to_string(), "ok".to_string())]);
+        let passed = resolve_route(1, vec![0, 1, 2, 3], None, &ok, &configs);
+        assert_eq!(passed.route, vec![0, 1, 2, 3]);
+        assert_eq!(passed.inspections, vec![None, None, Some(true), None]);
+
+        let rejected = resolve_route(2, vec![0, 1, 2, 3], None, &HashMap::new(), &configs);
+        assert_eq!(rejected.route, vec![0, 1, 2]);
+        assert_eq!(rejected.inspections.last(), Some(&Some(false)));
+        assert!(rejected.rejected && !passed.rejected);
+
+        // Fuera de la ruta base, el producto visita la estación y sigue con su ruta
+        configs[2] = StationConfig::inspection(
+            "Control",
+            Inspection::metadata("calidad", "ok").with_fail_target(RouteTarget::Station(1)),
+        );
+        let detour = resolve_route(3, vec![0, 2, 3], None, &HashMap::new(), &configs);
+        assert_eq!(detour.route, vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_rework_loops_are_reproducible() {
+        let mut configs = line();
+        configs[2] = StationConfig::inspection(
+            "Control",
+            Inspection::pass_rate(0.5, 11).with_fail_target(RouteTarget::Station(1)),
+        );
+        let routes: Vec<_> = (1..=50)
+            .map(|id| resolve_route(id, vec![0, 1, 2, 3], None, &HashMap::new(), &configs).route)
+            .collect();
+        for (id, route) in (1..=50).zip(&routes) {
+            assert_eq!(route, &resolve_route(id, vec![0, 1, 2, 3], None, &HashMap::new(), &configs).route);
+            // Cada rechazo repite Ensamblaje y Control antes de Empaque
+            assert_eq!(route[..2], [0, 1]);
+            assert!(route[2..route.len() - 1].chunks(2).all(|pair| pair == [2, 1] || pair == [2]));
+            assert_eq!(route[route.len() - 2..], [2, 3]);
+        }
+        assert!(routes.iter().any(|route| route.len() > 4));
+        assert!(routes.iter().any(|route| route.len() == 4));
+    }
+
+    #[test]
+    fn test_splitter_policies_choose_outputs() {
+        let branches = vec![RouteTarget::Station(1), RouteTarget::Station(2)];
+        let mut configs = vec![
+            StationConfig::new("Corte", Duration::from_millis(10)),
+            StationConfig::new("Pintura A", Duration::from_millis(10)).with_output(RouteTarget::Station(3)),
+            StationConfig::new("Pintura B", Duration::from_millis(10)),
+            StationConfig::new("Empaque", Duration::from_millis(10)),
+        ];
+        let resolve = |id: usize, product_type: Option<&str>, metadata: &HashMap<String, String>, configs: &[StationConfig]| {
+            resolve_route(id, vec![0, 1, 2, 3], product_type, metadata, configs)
+        };
+
+        configs[0] = configs[0].clone().with_splitter(Splitter::round_robin(branches.clone()));
+        let none = HashMap::new();
+        assert_eq!(resolve(1, None, &none, &configs).route, vec![0, 1, 3]);
+        assert_eq!(resolve(2, None, &none, &configs).route, vec![0, 2, 3]);
+        assert_eq!(resolve(3, None, &none, &configs).outputs, vec![Some(0), Some(0), None]);
+
+        configs[0].kind = StationKind::Splitter(Splitter::by_type(branches.clone(), [("suv", 1)]));
+        assert_eq!(resolve(1, Some("suv"), &none, &configs).route, vec![0, 2, 3]);
+        assert_eq!(resolve(2, Some("sedan"), &none, &configs).route, vec![0, 1, 3]);
+
+        configs[0].kind = StationKind::Splitter(Splitter::by_metadata(branches, "color", [("rojo", 1)]));
+        let rojo = HashMap::from([("color".to_string(), "rojo".to_string())]);
+        assert_eq!(resolve(1, None, &rojo, &configs).route, vec![0, 2, 3]);
+        assert_eq!(resolve(2, None, &none, &configs).route, vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_merge_policies_choose_sources() {
+        let picks = |merger: Merger, waiting: &[Option<usize>], count: usize| {
+            let mut state = MergeState::default();
+            (0..count).map(|_| merger.choose(waiting, &mut state).unwrap()).collect::<Vec<_>>()
+        };
+        let (a, b) = (Some(1), Some(2));
+
+        assert_eq!(picks(Merger::alternating(), &[None, a, b], 4), vec![None, a, b, None]);
+        assert_eq!(picks(Merger::weighted([(a, 2)]), &[a, b], 6), vec![a, b, a, a, b, a]);
+        assert_eq!(picks(Merger::priority([b]), &[None, a, b], 2), vec![b, b]);
+        assert_eq!(picks(Merger::priority([b]), &[None, a], 1), vec![None]);
+        assert_eq!(Merger::alternating().choose(&[], &mut MergeState::default()), None);
+
+        assert!(Merger::weighted([(a, 0)]).check(3, 4).is_err());
+        assert!(Merger::priority([Some(3)]).check(3, 4).is_err());
+        assert!(Merger::priority([Some(4)]).check(3, 4).is_err());
+        assert!(Merger::priority([None, a, b]).check(3, 4).is_ok());
+    }
+
+    #[test]
+    fn test_check_rejects_endless_loops() {
+        let back = RouteTarget::Station(0);
+        assert!(Inspection::pass_rate(0.9, 1).with_fail_target(back).check(1, 3).is_ok());
+        assert!(Inspection::pass_rate(0.0, 1).with_fail_target(back).check(1, 3).is_err());
+        assert!(Inspection::pass_rate(1.0, 1).with_pass_target(back).check(1, 3).is_err());
+        assert!(Inspection::metadata("a", "b").with_fail_target(back).check(1, 3).is_err());
+        assert!(Inspection::metadata("a", "b").with_fail_target(RouteTarget::Station(2)).check(1, 3).is_ok());
+        assert!(Inspection::metadata("a", "b").with_pass_target(RouteTarget::Station(3)).check(1, 3).is_err());
+
+        let outputs = vec![back, RouteTarget::Station(2)];
+        assert!(Splitter::round_robin(outputs.clone()).check(1, 3).is_ok());
+        assert!(Splitter::round_robin(vec![back]).check(1, 3).is_err());
+        assert!(Splitter::by_type(outputs.clone(), [("a", 1)]).check(1, 3).is_err());
+        assert!(Splitter::by_type(vec![RouteTarget::Next, RouteTarget::Station(2)], [("a", 2)]).check(1, 3).is_err());
+    }
+}
@@ -0,0 +1,933 @@
+//! # Módulo de Escenarios
+//! 
+//! Este módulo permite describir una simulación como datos (JSON, YAML, RON
+//! o TOML) en lugar de código, para ejecutarla desde herramientas externas como el servidor
+//! HTTP. Un escenario indica el algoritmo, las estaciones y los tiempos de
+//! llegada; los campos omitidos usan la configuración por defecto.
+//! 
+//! ```json
+//! {
+//!   "algorithm": "rr",
+//!   "quantum_ms": 100,
+//!   "stations": [{"name": "Corte", "processing_time_ms": 400, "description": "Sierra CNC", "color": "#1c7ed6"}],
+//!   "arrivals_ms": [0, 120, 260],
+//!   "due_dates_ms": [900, 1200, 1500]
+//! }
+//! ```
+//! 
+//! En lugar de `arrivals_ms`, las llegadas pueden describirse como ráfagas:
+//! `"burst": {"size": 5, "gap_ms": 2000, "count": 3}` (con `start_ms`
+//! opcional) genera 3 ráfagas de 5 productos separadas por 2 segundos.
+//! 
+//! El mismo escenario puede escribirse en YAML, RON o TOML, con las mismas
+//! claves ([`Scenario::from_yaml`], [`Scenario::from_ron`],
+//! [`Scenario::from_toml`]);
+//! [`Scenario::from_file`] detecta el formato por la extensión del archivo.
+//! 
+//! Antes de construir el escenario, el documento se valida contra el
+//! esquema del formato (ver [`crate::schema`]); los errores indican el
+//! campo y la línea de cada problema.
+//! 
+//! [`ScenarioDiff`] lista qué estaciones, tiempos, algoritmos y patrones de
+//! llegada difieren entre dos configuraciones.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::arrival::{ArrivalOrder, ArrivalProcess};
+use crate::config;
+use crate::json::{Json, Locations};
+use crate::ron;
+use crate::scheduler::SchedulingAlgorithm;
+use crate::schema::{self, SchemaError};
+use crate::simulation::Simulation;
+use crate::station::StationConfig;
+use crate::toml;
+use crate::yaml;
+
+/// Descripción de una simulación como datos.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::Scenario;
+/// 
+/// let scenario = Scenario::from_json(r#"{
+///     "algorithm": "fcfs",
+///     "stations": [{"name": "Corte", "processing_time_ms": 10}],
+///     "arrivals_ms": [0, 5]
+/// }"#).expect("Escenario inválido");
+/// assert_eq!(scenario.arrivals, vec![Duration::ZERO, Duration::from_millis(5)]);
+/// 
+/// let metrics = scenario.to_simulation().run().expect("La simulación falló");
+/// assert_eq!(metrics.products.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Scenario {
+    /// Algoritmo de planificación de todas las estaciones
+    pub algorithm: SchedulingAlgorithm,
+    /// Estaciones de la línea, en orden
+    pub stations: Vec<StationConfig>,
+    /// Tiempos de llegada de los productos
+    pub arrivals: Vec<Duration>,
+    /// Fechas de entrega de los productos (una por llegada)
+    pub due_dates: Option<Vec<Duration>>,
+}
+
+impl Scenario {
+    /// Lee un escenario desde un documento JSON.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - Documento JSON del escenario
+    /// 
+    /// # Returns
+    /// 
+    /// El escenario, o `Err` con el error de sintaxis o los problemas
+    /// encontrados al validarlo (ver [`Scenario::parse`])
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        Self::parse(text, ScenarioFormat::Json)
+    }
+
+    /// Lee un escenario desde un documento YAML, con las mismas claves que
+    /// el formato JSON (ver [`crate::yaml`]).
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::Scenario;
+    /// 
+    /// let scenario = Scenario::from_yaml("
+    /// algorithm: rr
+    /// quantum_ms: 50
+    /// stations:
+    ///   - name: Corte
+    ///     processing_time_ms: 10
+    /// arrivals_ms: [0, 5]
+    /// ").expect("Escenario inválido");
+    /// assert_eq!(scenario.algorithm.get_quantum(), Some(Duration::from_millis(50)));
+    /// assert_eq!(scenario.stations[0].name, "Corte");
+    /// ```
+    pub fn from_yaml(text: &str) -> Result<Self, String> {
+        Self::parse(text, ScenarioFormat::Yaml)
+    }
+
+    /// Lee un escenario desde un documento RON, con las mismas claves que
+    /// el formato JSON (ver [`crate::ron`]).
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::Scenario;
+    /// 
+    /// let scenario = Scenario::from_ron(r#"Scenario(
+    ///     algorithm: "edd",
+    ///     stations: [(name: "Corte", processing_time_ms: 10)],
+    ///     arrivals_ms: [0, 5],
+    ///     due_dates_ms: Some([40, 20]),
+    /// )"#).expect("Escenario inválido");
+    /// assert!(scenario.algorithm.is_due_date_rule());
+    /// assert_eq!(scenario.due_dates.map(|dates| dates.len()), Some(2));
+    /// ```
+    pub fn from_ron(text: &str) -> Result<Self, String> {
+        Self::parse(text, ScenarioFormat::Ron)
+    }
+
+    /// Lee un escenario desde un documento TOML, con las mismas claves que
+    /// el formato JSON (ver [`crate::toml`]).
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::Scenario;
+    /// 
+    /// let scenario = Scenario::from_toml(r#"
+    /// algorithm = "fcfs"
+    /// arrivals_ms = [0, 5]
+    /// 
+    /// [[stations]]
+    /// name = "Corte"
+    /// processing_time_ms = 10
+    /// "#).expect("Escenario inválido");
+    /// assert_eq!(scenario.stations[0].name, "Corte");
+    /// assert_eq!(scenario.arrivals.len(), 2);
+    /// ```
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        Self::parse(text, ScenarioFormat::Toml)
+    }
+
+    /// Lee un escenario escrito en el formato indicado.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - Documento del escenario
+    /// * `format` - Formato del documento
+    /// 
+    /// # Returns
+    /// 
+    /// El escenario, o `Err` con el error de sintaxis o con los problemas
+    /// encontrados al validar el documento contra el esquema (ver
+    /// [`Scenario::validate`]), uno por línea
+    pub fn parse(text: &str, format: ScenarioFormat) -> Result<Self, String> {
+        let (document, locations) = format.parse(text)?;
+        let errors = schema::validate(&schema::scenario_schema(), &document, &locations);
+        if !errors.is_empty() {
+            let errors: Vec<String> = errors.iter().map(SchemaError::to_string).collect();
+            return Err(errors.join("\n"));
+        }
+
+        let algorithm = parse_algorithm(&document)?;
+        let stations = match document.get("stations") {
+            Some(stations) => parse_stations(stations)?,
+            None => config::default_station_configs(),
+        };
+        let arrivals = match (document.get("arrivals_ms"), document.get("burst")) {
+            (Some(_), Some(_)) => {
+                return Err("'arrivals_ms' y 'burst' no pueden usarse juntos".to_string());
+            }
+            (Some(arrivals), None) => parse_durations("arrivals_ms", arrivals)?,
+            (None, Some(burst)) => parse_burst(burst)?.offsets(),
+            (None, None) => config::default_arrival_times(),
+        };
+        let due_dates = document
+            .get("due_dates_ms")
+            .map(|due_dates| parse_durations("due_dates_ms", due_dates))
+            .transpose()?;
+        if due_dates.as_ref().is_some_and(|due_dates| due_dates.len() != arrivals.len()) {
+            return Err("'due_dates_ms' debe tener una fecha por cada llegada".to_string());
+        }
+
+        Ok(Self { algorithm, stations, arrivals, due_dates })
+    }
+
+    /// Valida un documento de escenario contra su esquema (ver
+    /// [`schema::scenario_schema`]) sin construir el escenario.
+    /// 
+    /// # Returns
+    /// 
+    /// Todos los problemas encontrados con su campo y su línea; un error de
+    /// sintaxis se informa como único problema, sin campo, al igual que un
+    /// documento con más de [`MAX_DEPTH`](crate::json::MAX_DEPTH) niveles de
+    /// anidamiento, que se rechaza al analizarlo y nunca llega a validarse
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{Scenario, ScenarioFormat};
+    /// 
+    /// let errors = Scenario::validate("
+    /// algorithm: rr
+    /// quantum_ms: 0
+    /// stations:
+    ///   - name: Corte
+    ///     processing_time_ms: 10
+    ///   - name: Empaque
+    ///     processing_time_ms: -5
+    /// ", ScenarioFormat::Yaml);
+    /// let messages: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+    /// assert_eq!(messages, vec![
+    ///     "línea 8: stations[1].processing_time_ms: debe ser no negativo (>= 0), no -5",
+    ///     "línea 3: quantum_ms: debe ser > 0 cuando algorithm = rr, no 0",
+    /// ]);
+    /// ```
+    pub fn validate(text: &str, format: ScenarioFormat) -> Vec<SchemaError> {
+        match format.parse(text) {
+            Ok((document, locations)) => schema::validate(&schema::scenario_schema(), &document, &locations),
+            Err(message) => vec![SchemaError { path: String::new(), line: None, message }],
+        }
+    }
+
+    /// Lee un escenario desde un archivo, detectando el formato por la
+    /// extensión (ver [`ScenarioFormat::from_path`]).
+    /// 
+    /// # Returns
+    /// 
+    /// El escenario, o `Err` si la extensión no corresponde a un formato
+    /// admitido, el archivo no puede leerse o su contenido no es válido
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let format = ScenarioFormat::from_path(path).ok_or_else(|| {
+            format!(
+                "Formato de escenario no reconocido para '{}' (use .json, .yaml, .yml, .ron o .toml)",
+                path.display()
+            )
+        })?;
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("No se pudo leer '{}': {}", path.display(), error))?;
+        Self::parse(&text, format).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    /// Crea la simulación descrita por el escenario.
+    pub fn to_simulation(&self) -> Simulation {
+        let mut simulation = Simulation::with_config(
+            self.stations.clone(),
+            self.algorithm.clone(),
+            self.arrivals.clone(),
+        );
+        if let Some(due_dates) = &self.due_dates {
+            simulation.set_due_dates(due_dates.clone());
+        }
+        simulation
+    }
+}
+
+/// Formato de un documento de escenario.
+/// 
+/// Todos los formatos se leen al mismo árbol de valores, por lo que admiten
+/// exactamente las mismas claves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScenarioFormat {
+    /// JSON (`.json`)
+    Json,
+    /// YAML (`.yaml` o `.yml`)
+    Yaml,
+    /// RON (`.ron`)
+    Ron,
+    /// TOML (`.toml`)
+    Toml,
+}
+
+impl ScenarioFormat {
+    /// Detecta el formato por la extensión del archivo (sin distinguir
+    /// mayúsculas), o `None` si no es una extensión admitida.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "ron" => Some(Self::Ron),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    /// Analiza un documento en este formato, registrando la línea de
+    /// cada valor.
+    fn parse(self, text: &str) -> Result<(Json, Locations), String> {
+        match self {
+            Self::Json => Json::parse_located(text),
+            Self::Yaml => yaml::parse_located(text),
+            Self::Ron => ron::parse_located(text),
+            Self::Toml => toml::parse_located(text),
+        }
+    }
+}
+
+/// Diferencia de configuración entre dos escenarios.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScenarioChange {
+    /// Cambió el algoritmo general de la línea
+    Algorithm {
+        /// Algoritmo de `a`
+        a: SchedulingAlgorithm,
+        /// Algoritmo de `b`
+        b: SchedulingAlgorithm,
+    },
+    /// Cambió el algoritmo efectivo de una estación con algoritmo propio
+    StationAlgorithm {
+        /// Índice de la estación (desde 0)
+        station: usize,
+        /// Algoritmo de la estación en `a`
+        a: SchedulingAlgorithm,
+        /// Algoritmo de la estación en `b`
+        b: SchedulingAlgorithm,
+    },
+    /// La estación de ese índice tiene otro nombre
+    StationRenamed {
+        /// Índice de la estación (desde 0)
+        station: usize,
+        /// Nombre en `a`
+        a: String,
+        /// Nombre en `b`
+        b: String,
+    },
+    /// Cambió el tiempo de procesamiento de una estación
+    ProcessingTime {
+        /// Índice de la estación (desde 0)
+        station: usize,
+        /// Tiempo en `a`
+        a: Duration,
+        /// Tiempo en `b`
+        b: Duration,
+    },
+    /// La estación solo existe en `b`
+    StationAdded {
+        /// Índice de la estación (desde 0)
+        station: usize,
+        /// Configuración de la estación
+        config: StationConfig,
+    },
+    /// La estación solo existe en `a`
+    StationRemoved {
+        /// Índice de la estación (desde 0)
+        station: usize,
+        /// Configuración de la estación
+        config: StationConfig,
+    },
+    /// Cambió la cantidad de llegadas programadas
+    ArrivalCount {
+        /// Llegadas de `a`
+        a: usize,
+        /// Llegadas de `b`
+        b: usize,
+    },
+    /// Con la misma cantidad de llegadas, algunas ocurren en otro instante
+    ArrivalTimes {
+        /// Cantidad de llegadas que cambiaron
+        changed: usize,
+        /// Brecha promedio entre llegadas consecutivas en `a`
+        mean_gap_a: Duration,
+        /// Brecha promedio entre llegadas consecutivas en `b`
+        mean_gap_b: Duration,
+    },
+    /// Cambió el orden en que los productos ocupan los tiempos de llegada
+    ArrivalOrder {
+        /// Orden de `a`
+        a: ArrivalOrder,
+        /// Orden de `b`
+        b: ArrivalOrder,
+    },
+    /// Cambiaron las fechas de entrega
+    DueDates {
+        /// Fechas de `a`
+        a: usize,
+        /// Fechas de `b`
+        b: usize,
+        /// Cantidad de fechas presentes en ambas que cambiaron
+        changed: usize,
+    },
+}
+
+impl fmt::Display for ScenarioChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = |time: &Duration| format!("{} ms", time.as_secs_f64() * 1000.0);
+        match self {
+            Self::Algorithm { a, b } => write!(f, "Algoritmo general: {} -> {}", a, b),
+            Self::StationAlgorithm { station, a, b } => {
+                write!(f, "Algoritmo de la estación {}: {} -> {}", station + 1, a, b)
+            }
+            Self::StationRenamed { station, a, b } => {
+                write!(f, "Estación {} renombrada: {} -> {}", station + 1, a, b)
+            }
+            Self::ProcessingTime { station, a, b } => {
+                write!(f, "Tiempo de la estación {}: {} -> {}", station + 1, millis(a), millis(b))
+            }
+            Self::StationAdded { station, config } => write!(
+                f,
+                "Estación {} agregada: {} ({})",
+                station + 1,
+                config.name,
+                millis(&config.processing_time)
+            ),
+            Self::StationRemoved { station, config } => write!(
+                f,
+                "Estación {} eliminada: {} ({})",
+                station + 1,
+                config.name,
+                millis(&config.processing_time)
+            ),
+            Self::ArrivalCount { a, b } => write!(f, "Llegadas programadas: {} -> {}", a, b),
+            Self::ArrivalTimes { changed, mean_gap_a, mean_gap_b } => write!(
+                f,
+                "Tiempos de llegada: {} distintos, brecha promedio {} -> {}",
+                changed,
+                millis(mean_gap_a),
+                millis(mean_gap_b)
+            ),
+            Self::ArrivalOrder { a, b } => write!(f, "Orden de llegada: {} -> {}", a, b),
+            Self::DueDates { a, b, changed } => {
+                write!(f, "Fechas de entrega: {} -> {} ({} distintas)", a, b, changed)
+            }
+        }
+    }
+}
+
+/// Diferencias de configuración entre dos simulaciones.
+/// 
+/// Se adjunta a los reportes de comparación (ver
+/// [`MetricsDiff::with_scenario`](crate::MetricsDiff::with_scenario)) para
+/// que quien los lea sepa exactamente qué cambió entre las dos ejecuciones.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{ScenarioChange, ScenarioDiff, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![
+///     StationConfig::new("Corte", Duration::from_millis(10)),
+///     StationConfig::new("Empaque", Duration::from_millis(5)),
+/// ];
+/// let base = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// let variant = base.clone_with(|simulation| {
+///     simulation
+///         .set_algorithm(SchedulingAlgorithm::earliest_due_date())
+///         .set_processing_time(1, Duration::from_millis(8));
+/// });
+/// 
+/// let diff = ScenarioDiff::compare(&base, &variant);
+/// assert_eq!(diff.changes.len(), 2);
+/// assert!(matches!(diff.changes[1], ScenarioChange::ProcessingTime { station: 1, .. }));
+/// assert!(diff.to_string().contains("Algoritmo general: FCFS -> EDD"));
+/// assert!(ScenarioDiff::compare(&base, &base.clone()).is_empty());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScenarioDiff {
+    /// Cambios de `a` a `b`: algoritmos, estaciones, llegadas y fechas de
+    /// entrega, en ese orden
+    pub changes: Vec<ScenarioChange>,
+}
+
+impl ScenarioDiff {
+    /// Compara la configuración de dos simulaciones.
+    /// 
+    /// Las estaciones se comparan por índice. El algoritmo de una estación
+    /// se informa solo si alguna de las dos simulaciones le asigna uno
+    /// propio; si no, basta con el cambio del algoritmo general.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `a` - Configuración de referencia
+    /// * `b` - Configuración comparada
+    pub fn compare(a: &Simulation, b: &Simulation) -> Self {
+        let mut changes = Vec::new();
+        if a.algorithm != b.algorithm {
+            changes.push(ScenarioChange::Algorithm { a: a.algorithm.clone(), b: b.algorithm.clone() });
+        }
+
+        for station in 0..a.station_configs.len().min(b.station_configs.len()) {
+            let effective = |simulation: &Simulation| {
+                simulation.station_algorithms.get(&station).unwrap_or(&simulation.algorithm).clone()
+            };
+            let overridden = a.station_algorithms.contains_key(&station) || b.station_algorithms.contains_key(&station);
+            let (algorithm_a, algorithm_b) = (effective(a), effective(b));
+            if overridden && algorithm_a != algorithm_b {
+                changes.push(ScenarioChange::StationAlgorithm { station, a: algorithm_a, b: algorithm_b });
+            }
+        }
+        for (station, (config_a, config_b)) in a.station_configs.iter().zip(&b.station_configs).enumerate() {
+            if config_a.name != config_b.name {
+                changes.push(ScenarioChange::StationRenamed {
+                    station,
+                    a: config_a.name.clone(),
+                    b: config_b.name.clone(),
+                });
+            }
+            if config_a.processing_time != config_b.processing_time {
+                changes.push(ScenarioChange::ProcessingTime {
+                    station,
+                    a: config_a.processing_time,
+                    b: config_b.processing_time,
+                });
+            }
+        }
+        let common = a.station_configs.len().min(b.station_configs.len());
+        changes.extend(b.station_configs.iter().enumerate().skip(common).map(|(station, config)| {
+            ScenarioChange::StationAdded { station, config: config.clone() }
+        }));
+        changes.extend(a.station_configs.iter().enumerate().skip(common).map(|(station, config)| {
+            ScenarioChange::StationRemoved { station, config: config.clone() }
+        }));
+
+        if a.arrival_times.len() != b.arrival_times.len() {
+            changes.push(ScenarioChange::ArrivalCount { a: a.arrival_times.len(), b: b.arrival_times.len() });
+        } else {
+            let changed = a.arrival_times.iter().zip(&b.arrival_times).filter(|(a, b)| a != b).count();
+            if changed > 0 {
+                changes.push(ScenarioChange::ArrivalTimes {
+                    changed,
+                    mean_gap_a: mean_gap(&a.arrival_times),
+                    mean_gap_b: mean_gap(&b.arrival_times),
+                });
+            }
+        }
+        if a.arrival_order != b.arrival_order {
+            changes.push(ScenarioChange::ArrivalOrder { a: a.arrival_order, b: b.arrival_order });
+        }
+
+        let changed = a.due_dates.iter().zip(&b.due_dates).filter(|(a, b)| a != b).count();
+        if changed > 0 || a.due_dates.len() != b.due_dates.len() {
+            changes.push(ScenarioChange::DueDates { a: a.due_dates.len(), b: b.due_dates.len(), changed });
+        }
+
+        Self { changes }
+    }
+
+    /// Compara dos escenarios (ver [`ScenarioDiff::compare`]).
+    pub fn compare_scenarios(a: &Scenario, b: &Scenario) -> Self {
+        Self::compare(&a.to_simulation(), &b.to_simulation())
+    }
+
+    /// Indica si ambas configuraciones son iguales en lo comparado.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for ScenarioDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== CAMBIOS DE CONFIGURACIÓN (a -> b) ===")?;
+        if self.changes.is_empty() {
+            return writeln!(f, "Sin cambios");
+        }
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Brecha promedio entre llegadas consecutivas (cero con menos de dos).
+fn mean_gap(arrivals: &[Duration]) -> Duration {
+    let (Some(first), Some(last)) = (arrivals.iter().min(), arrivals.iter().max()) else {
+        return Duration::ZERO;
+    };
+    if arrivals.len() < 2 {
+        return Duration::ZERO;
+    }
+    (*last - *first) / (arrivals.len() - 1) as u32
+}
+
+/// Lee el algoritmo y, para Round Robin, su quantum.
+fn parse_algorithm(document: &Json) -> Result<SchedulingAlgorithm, String> {
+    let name = document
+        .get("algorithm")
+        .ok_or("Falta la clave 'algorithm'")?
+        .as_str()
+        .ok_or("'algorithm' debe ser un string")?;
+
+    match name {
+        "fcfs" => Ok(SchedulingAlgorithm::fcfs()),
+        "rr" => {
+            let quantum = document
+                .get("quantum_ms")
+                .ok_or_else(|| "Round Robin requiere 'quantum_ms'".to_string())
+                .and_then(|quantum| parse_millis("quantum_ms", quantum))?;
+            if quantum.is_zero() {
+                return Err("'quantum_ms' debe ser mayor a cero".to_string());
+            }
+            Ok(SchedulingAlgorithm::round_robin(quantum))
+        }
+        "edd" => Ok(SchedulingAlgorithm::earliest_due_date()),
+        "cr" => Ok(SchedulingAlgorithm::critical_ratio()),
+        other => Err(format!(
+            "Algoritmo desconocido: '{}' (use fcfs, rr, edd o cr)",
+            other
+        )),
+    }
+}
+
+/// Lee la lista de estaciones.
+fn parse_stations(stations: &Json) -> Result<Vec<StationConfig>, String> {
+    let stations = stations.as_array().ok_or("'stations' debe ser un arreglo")?;
+    if stations.is_empty() {
+        return Err("'stations' debe tener al menos una estación".to_string());
+    }
+
+    stations
+        .iter()
+        .enumerate()
+        .map(|(index, station)| {
+            let name = station
+                .get("name")
+                .and_then(Json::as_str)
+                .ok_or_else(|| format!("La estación {} requiere un 'name' de tipo string", index))?;
+            let processing_time = station
+                .get("processing_time_ms")
+                .ok_or_else(|| format!("La estación '{}' requiere 'processing_time_ms'", name))
+                .and_then(|time| parse_millis("processing_time_ms", time))?;
+            let text = |key: &str| -> Result<Option<String>, String> {
+                match station.get(key) {
+                    None => Ok(None),
+                    Some(value) => value
+                        .as_str()
+                        .map(|text| Some(text.to_string()))
+                        .ok_or_else(|| format!("'{}' de la estación '{}' debe ser un string", key, name)),
+                }
+            };
+            Ok(StationConfig {
+                description: text("description")?,
+                color: text("color")?,
+                ..StationConfig::new(name, processing_time)
+            })
+        })
+        .collect()
+}
+
+/// Lee un proceso de llegadas en ráfagas.
+fn parse_burst(burst: &Json) -> Result<ArrivalProcess, String> {
+    if burst.as_object().is_none() {
+        return Err(format!("'burst' debe ser un objeto, no un {}", burst.type_name()));
+    }
+    let count = |key: &str| -> Result<usize, String> {
+        match burst.get(key).and_then(Json::as_f64) {
+            Some(value) if value >= 1.0 && value.fract() == 0.0 => Ok(value as usize),
+            _ => Err(format!("'burst.{}' debe ser un entero positivo", key)),
+        }
+    };
+    let millis = |key: &str| -> Result<Option<Duration>, String> {
+        burst.get(key).map(|value| parse_millis(&format!("burst.{}", key), value)).transpose()
+    };
+
+    Ok(ArrivalProcess::Burst {
+        start: millis("start_ms")?.unwrap_or_default(),
+        size: count("size")?,
+        gap: millis("gap_ms")?.ok_or_else(|| "'burst' requiere 'gap_ms'".to_string())?,
+        bursts: count("count")?,
+    })
+}
+
+/// Lee un arreglo de tiempos en milisegundos.
+fn parse_durations(key: &str, values: &Json) -> Result<Vec<Duration>, String> {
+    values
+        .as_array()
+        .ok_or_else(|| format!("'{}' debe ser un arreglo", key))?
+        .iter()
+        .map(|value| parse_millis(key, value))
+        .collect()
+}
+
+/// Lee un tiempo en milisegundos (se admiten fracciones).
+fn parse_millis(key: &str, value: &Json) -> Result<Duration, String> {
+    match value.as_f64() {
+        // Los valores enormes, aunque finitos, no caben en un `Duration`
+        Some(millis) => Duration::try_from_secs_f64(millis / 1000.0)
+            .map_err(|_| format!("'{}' debe ser un tiempo no negativo, no {}", key, millis)),
+        None => Err(format!("'{}' debe contener números, no un {}", key, value.type_name())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_range_times_are_rejected() {
+        let error = Scenario::from_json(r#"{"algorithm": "rr", "quantum_ms": 1e300}"#).unwrap_err();
+        assert!(error.contains("'quantum_ms' debe ser un tiempo no negativo"), "{}", error);
+        assert!(Scenario::from_json(r#"{"algorithm": "fcfs", "arrivals_ms": [0, -1]}"#).is_err());
+    }
+
+    #[test]
+    fn test_scenario_defaults_and_due_dates() {
+        let scenario = Scenario::from_json(r#"{"algorithm": "edd", "arrivals_ms": [0, 10.5], "due_dates_ms": [100, 50]}"#)
+            .unwrap();
+        assert_eq!(scenario.stations.len(), config::STATION_COUNT);
+        assert_eq!(scenario.arrivals[1], Duration::from_micros(10_500));
+        assert_eq!(scenario.due_dates, Some(vec![Duration::from_millis(100), Duration::from_millis(50)]));
+        assert!(scenario.algorithm.is_due_date_rule());
+    }
+
+    #[test]
+    fn test_scenario_errors() {
+        let error = |text: &str| Scenario::from_json(text).unwrap_err();
+        assert!(error(r#"{"algorithm": "rr"}"#).contains("quantum_ms"));
+        assert!(error(r#"{"algorithm": "lifo"}"#).contains("lifo"));
+        assert!(error(r#"{"algorithm": "fcfs", "extra": 1}"#).contains("extra"));
+        assert!(error(r#"{"algorithm": "fcfs", "arrivals_ms": [-1]}"#).contains("no negativo"));
+        assert!(error(r#"{"algorithm": "fcfs", "stations": [{"name": "A"}]}"#).contains("processing_time_ms"));
+        assert!(error(r#"{"algorithm": "fcfs", "due_dates_ms": [1]}"#).contains("due_dates_ms"));
+        assert!(error("[]").contains("objeto"));
+    }
+
+    #[test]
+    fn test_scenario_burst_arrivals() {
+        let scenario = Scenario::from_json(r#"{"algorithm": "fcfs", "burst": {"size": 2, "gap_ms": 500, "count": 2}}"#)
+            .unwrap();
+        let millis = Duration::from_millis;
+        assert_eq!(scenario.arrivals, vec![millis(0), millis(0), millis(500), millis(500)]);
+
+        let error = |text: &str| Scenario::from_json(text).unwrap_err();
+        assert!(error(r#"{"algorithm": "fcfs", "burst": {"size": 0, "gap_ms": 1, "count": 1}}"#).contains("burst.size"));
+        assert!(error(r#"{"algorithm": "fcfs", "burst": {"size": 1, "count": 1}}"#).contains("gap_ms"));
+        assert!(error(r#"{"algorithm": "fcfs", "arrivals_ms": [0], "burst": {}}"#).contains("juntos"));
+    }
+
+    #[test]
+    fn test_station_names_and_display_metadata() {
+        let scenario = Scenario::from_json(
+            r##"{"algorithm": "fcfs", "stations": [
+                {"name": "Pintura", "processing_time_ms": 50, "description": "Cabina 2", "color": "#d9480f"},
+                {"name": "Secado", "processing_time_ms": 20}
+            ]}"##,
+        )
+        .unwrap();
+        assert_eq!(scenario.stations[0].name, "Pintura");
+        assert_eq!(scenario.stations[0].description.as_deref(), Some("Cabina 2"));
+        assert_eq!(scenario.stations[0].color.as_deref(), Some("#d9480f"));
+        assert_eq!(scenario.stations[1], StationConfig::new("Secado", Duration::from_millis(20)));
+
+        let error = Scenario::from_json(r#"{"algorithm": "fcfs", "stations": [{"name": "A", "processing_time_ms": 1, "color": 3}]}"#);
+        assert!(error.unwrap_err().contains("color"));
+    }
+
+    #[test]
+    fn test_scenario_diff_lists_stations_and_arrivals() {
+        let a = Scenario::from_json(
+            r#"{"algorithm": "fcfs", "stations": [{"name": "Corte", "processing_time_ms": 10}], "arrivals_ms": [0, 10, 20]}"#,
+        )
+        .unwrap();
+        let b = Scenario::from_json(
+            r#"{"algorithm": "fcfs", "stations": [{"name": "Sierra", "processing_time_ms": 10}, {"name": "Empaque", "processing_time_ms": 4}],
+                "arrivals_ms": [0, 20, 40], "due_dates_ms": [50, 60, 70]}"#,
+        )
+        .unwrap();
+
+        let diff = ScenarioDiff::compare_scenarios(&a, &b);
+        assert_eq!(
+            diff.changes[..2],
+            [
+                ScenarioChange::StationRenamed { station: 0, a: "Corte".to_string(), b: "Sierra".to_string() },
+                ScenarioChange::StationAdded { station: 1, config: StationConfig::new("Empaque", Duration::from_millis(4)) },
+            ]
+        );
+        assert_eq!(
+            diff.changes[2],
+            ScenarioChange::ArrivalTimes {
+                changed: 2,
+                mean_gap_a: Duration::from_millis(10),
+                mean_gap_b: Duration::from_millis(20),
+            }
+        );
+        assert_eq!(diff.changes[3], ScenarioChange::DueDates { a: 0, b: 3, changed: 0 });
+        let text = diff.to_string();
+        assert!(text.contains("Estación 2 agregada: Empaque (4 ms)"), "{}", text);
+        assert!(text.contains("brecha promedio 10 ms -> 20 ms"), "{}", text);
+
+        let reverse = ScenarioDiff::compare_scenarios(&b, &a);
+        assert!(matches!(reverse.changes[1], ScenarioChange::StationRemoved { station: 1, .. }));
+        assert!(ScenarioDiff::compare_scenarios(&a, &a).to_string().contains("Sin cambios"));
+    }
+
+    #[test]
+    fn test_yaml_ron_and_toml_match_json() {
+        let json = Scenario::from_json(
+            r##"{"algorithm": "rr", "quantum_ms": 25,
+                "stations": [{"name": "Corte", "processing_time_ms": 40, "color": "#1c7ed6"}, {"name": "Empaque", "processing_time_ms": 10}],
+                "burst": {"size": 2, "gap_ms": 100, "count": 2}, "due_dates_ms": [300, 300, 500, 500]}"##,
+        )
+        .unwrap();
+        let yaml = Scenario::from_yaml(
+            "# Línea de dos estaciones\n\
+             algorithm: rr\n\
+             quantum_ms: 25\n\
+             stations:\n\
+             - name: Corte\n  \
+               processing_time_ms: 40\n  \
+               color: '#1c7ed6'\n\
+             - {name: Empaque, processing_time_ms: 10}\n\
+             burst:\n  \
+               size: 2\n  \
+               gap_ms: 100\n  \
+               count: 2\n\
+             due_dates_ms: [300, 300, 500, 500]\n",
+        )
+        .unwrap();
+        let ron = Scenario::from_ron(
+            r##"Scenario(
+                algorithm: "rr", quantum_ms: 25,
+                stations: [
+                    (name: "Corte", processing_time_ms: 40, color: "#1c7ed6"),
+                    (name: "Empaque", processing_time_ms: 10), // sin color
+                ],
+                burst: (size: 2, gap_ms: 100, count: 2),
+                due_dates_ms: Some([300, 300, 500, 500]),
+            )"##,
+        )
+        .unwrap();
+        let toml = Scenario::from_toml(
+            r##"algorithm = "rr"
+                quantum_ms = 25
+                burst = { size = 2, gap_ms = 100, count = 2 }
+                due_dates_ms = [300, 300, 500, 500]
+
+                [[stations]]
+                name = "Corte"
+                processing_time_ms = 40
+                color = "#1c7ed6"
+
+                [[stations]] # sin color
+                name = "Empaque"
+                processing_time_ms = 10
+            "##,
+        )
+        .unwrap();
+
+        for other in [&yaml, &ron, &toml] {
+            assert_eq!(other.algorithm, json.algorithm);
+            assert_eq!(other.stations, json.stations);
+            assert_eq!(other.arrivals, json.arrivals);
+            assert_eq!(other.due_dates, json.due_dates);
+        }
+        assert!(Scenario::from_yaml("algorithm: lifo").unwrap_err().contains("lifo"));
+        assert!(Scenario::from_ron("(algorithm: \"fcfs\", extra: 1)").unwrap_err().contains("extra"));
+        let error = Scenario::from_toml("algorithm = \"fcfs\"\n\n[[stations]]\nname = 3\n").unwrap_err();
+        assert!(error.contains("línea 4: stations[0].name"), "{}", error);
+    }
+
+    #[test]
+    fn test_format_detected_by_extension() {
+        assert_eq!(ScenarioFormat::from_path("linea.json"), Some(ScenarioFormat::Json));
+        assert_eq!(ScenarioFormat::from_path("clase/linea.YML"), Some(ScenarioFormat::Yaml));
+        assert_eq!(ScenarioFormat::from_path("linea.yaml"), Some(ScenarioFormat::Yaml));
+        assert_eq!(ScenarioFormat::from_path("linea.ron"), Some(ScenarioFormat::Ron));
+        assert_eq!(ScenarioFormat::from_path("linea.toml"), Some(ScenarioFormat::Toml));
+        assert_eq!(ScenarioFormat::from_path("linea.ini"), None);
+        assert_eq!(ScenarioFormat::from_path("linea"), None);
+        assert!(Scenario::from_file("linea.ini").unwrap_err().contains("no reconocido"));
+        assert!(Scenario::from_file("no-existe.yaml").unwrap_err().contains("No se pudo leer"));
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem_with_its_line() {
+        let ron = r##"(
+            algorithm: "rr",
+            quantum_ms: 10,
+            stations: [
+                (name: "Corte", processing_time_ms: 5, colour: "#fff"),
+                (name: 7, processing_time_ms: 5),
+            ],
+            burst: (size: 1.5, gap_ms: 10, count: 2),
+            arrivals_ms: [0],
+        )"##;
+        let messages: Vec<String> = Scenario::validate(ron, ScenarioFormat::Ron).iter().map(SchemaError::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "línea 5: stations[0].colour: clave desconocida",
+                "línea 6: stations[1].name: debe ser un string, no un número",
+                "línea 8: burst.size: debe ser un entero, no un número",
+                "línea 1: 'arrivals_ms' y 'burst' no pueden usarse juntos",
+            ]
+        );
+        let error = Scenario::from_ron(ron).unwrap_err();
+        assert_eq!(error.lines().count(), 4, "{}", error);
+
+        let syntax = Scenario::validate("algorithm: [rr", ScenarioFormat::Yaml);
+        assert_eq!(syntax.len(), 1);
+        assert!(syntax[0].path.is_empty() && syntax[0].message.contains("línea 1"));
+        assert!(Scenario::validate(r#"{"algorithm": "cr"}"#, ScenarioFormat::Json).is_empty());
+    }
+
+    #[test]
+    fn test_validation_rejects_deep_nesting_in_every_format() {
+        // Sin el límite de anidamiento, el análisis desbordaría la pila
+        let nested = "[".repeat(200_000);
+        let toml = format!("algorithm = {}", nested);
+        for (text, format) in [
+            (&nested, ScenarioFormat::Json),
+            (&nested, ScenarioFormat::Yaml),
+            (&nested, ScenarioFormat::Ron),
+            (&toml, ScenarioFormat::Toml),
+        ] {
+            let errors = Scenario::validate(text, format);
+            assert_eq!(errors.len(), 1, "{:?}", format);
+            assert!(errors[0].path.is_empty() && errors[0].message.contains("niveles de anidamiento"));
+            assert!(Scenario::parse(text, format).is_err());
+        }
+    }
+}
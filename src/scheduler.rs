@@ -31,6 +31,19 @@ pub enum SchedulingAlgorithm {
         /// Tiempo máximo de procesamiento continuo por ronda
         quantum: Duration,
     },
+
+    /// Earliest Due Date: procesamiento no preemptivo por fecha de entrega.
+    /// 
+    /// Entre los productos en cola se atiende primero el de fecha de entrega
+    /// más próxima. Los productos sin fecha de entrega se atienden al final.
+    EarliestDueDate,
+
+    /// Critical Ratio: procesamiento no preemptivo por razón crítica.
+    /// 
+    /// La razón crítica es el tiempo que falta hasta la fecha de entrega
+    /// dividido entre el trabajo restante en la estación. Se atiende primero
+    /// el producto con la menor razón (el más urgente relativo a su trabajo).
+    CriticalRatio,
 }
 
 impl SchedulingAlgorithm {
@@ -73,6 +86,34 @@ impl SchedulingAlgorithm {
         Self::RoundRobin { quantum }
     }
 
+    /// Crea un nuevo algoritmo EDD (Earliest Due Date).
+    /// 
+    /// # Returns
+    /// 
+    /// Una instancia de `SchedulingAlgorithm::EarliestDueDate`
+    pub fn earliest_due_date() -> Self {
+        Self::EarliestDueDate
+    }
+
+    /// Crea un nuevo algoritmo CR (Critical Ratio).
+    /// 
+    /// # Returns
+    /// 
+    /// Una instancia de `SchedulingAlgorithm::CriticalRatio`
+    pub fn critical_ratio() -> Self {
+        Self::CriticalRatio
+    }
+
+    /// Determina si el algoritmo es una regla de despacho por fecha de entrega.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` para EDD y CR, que eligen el siguiente producto según su
+    /// fecha de entrega en lugar de su orden de llegada
+    pub fn is_due_date_rule(&self) -> bool {
+        matches!(self, Self::EarliestDueDate | Self::CriticalRatio)
+    }
+
     /// Determina si el algoritmo es preemptivo.
     /// 
     /// # Returns
@@ -91,7 +132,7 @@ impl SchedulingAlgorithm {
     /// ```
     pub fn is_preemptive(&self) -> bool {
         match self {
-            Self::Fcfs => false,
+            Self::Fcfs | Self::EarliestDueDate | Self::CriticalRatio => false,
             Self::RoundRobin { .. } => true,
         }
     }
@@ -121,7 +162,7 @@ impl SchedulingAlgorithm {
     /// ```
     pub fn calculate_quantum(&self, remaining_time: Duration) -> Duration {
         match self {
-            Self::Fcfs => remaining_time,
+            Self::Fcfs | Self::EarliestDueDate | Self::CriticalRatio => remaining_time,
             Self::RoundRobin { quantum } => remaining_time.min(*quantum),
         }
     }
@@ -134,7 +175,7 @@ impl SchedulingAlgorithm {
     /// `None` si es FCFS
     pub fn get_quantum(&self) -> Option<Duration> {
         match self {
-            Self::Fcfs => None,
+            Self::Fcfs | Self::EarliestDueDate | Self::CriticalRatio => None,
             Self::RoundRobin { quantum } => Some(*quantum),
         }
     }
@@ -166,6 +207,8 @@ impl SchedulingAlgorithm {
                     quantum.as_millis()
                 )
             }
+            Self::EarliestDueDate => "Earliest Due Date (no preemptivo)".to_string(),
+            Self::CriticalRatio => "Critical Ratio (no preemptivo)".to_string(),
         }
     }
 }
@@ -177,6 +220,63 @@ impl fmt::Display for SchedulingAlgorithm {
             Self::RoundRobin { quantum } => {
                 write!(f, "Round Robin (quantum {} ms)", quantum.as_millis())
             }
+            Self::EarliestDueDate => write!(f, "EDD"),
+            Self::CriticalRatio => write!(f, "CR"),
+        }
+    }
+}
+
+/// Disciplina con la que una estación elige entre los productos en espera.
+/// 
+/// Complementa al algoritmo de planificación: el algoritmo decide cuánto
+/// procesar cada producto (y, con EDD o CR, cuáles son los más urgentes) y
+/// la disciplina decide cuál de los candidatos equivalentes se atiende
+/// primero. Con FCFS y Round Robin los candidatos son los productos listos
+/// de mayor prioridad; con EDD y CR, los que empatan en prioridad y en
+/// urgencia.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueueDiscipline {
+    /// First-In First-Out: se atiende el producto que llegó primero
+    #[default]
+    Fifo,
+    /// Last-In First-Out: se atiende el producto que llegó último
+    Lifo,
+    /// Service In Random Order: se atiende un producto al azar, con un
+    /// generador determinista a partir de la semilla
+    Random {
+        /// Semilla del generador pseudoaleatorio de la estación
+        seed: u64,
+    },
+}
+
+impl QueueDiscipline {
+    /// Crea la disciplina FIFO.
+    pub fn fifo() -> Self {
+        Self::Fifo
+    }
+
+    /// Crea la disciplina LIFO.
+    pub fn lifo() -> Self {
+        Self::Lifo
+    }
+
+    /// Crea la disciplina de servicio en orden aleatorio (SIRO).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `seed` - Semilla del generador; con la misma semilla la estación
+    ///   elige siempre en el mismo orden
+    pub fn random(seed: u64) -> Self {
+        Self::Random { seed }
+    }
+}
+
+impl fmt::Display for QueueDiscipline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fifo => write!(f, "FIFO"),
+            Self::Lifo => write!(f, "LIFO"),
+            Self::Random { seed } => write!(f, "SIRO (semilla {})", seed),
         }
     }
 }
@@ -257,5 +357,27 @@ mod tests {
         
         let rr = SchedulingAlgorithm::round_robin(Duration::from_millis(250));
         assert_eq!(format!("{}", rr), "Round Robin (quantum 250 ms)");
+
+        assert_eq!(format!("{}", SchedulingAlgorithm::earliest_due_date()), "EDD");
+        assert_eq!(format!("{}", SchedulingAlgorithm::critical_ratio()), "CR");
+    }
+
+    #[test]
+    fn test_due_date_rules_are_non_preemptive() {
+        let remaining = Duration::from_millis(800);
+        for algorithm in [SchedulingAlgorithm::earliest_due_date(), SchedulingAlgorithm::critical_ratio()] {
+            assert!(algorithm.is_due_date_rule());
+            assert!(!algorithm.is_preemptive());
+            assert_eq!(algorithm.calculate_quantum(remaining), remaining);
+            assert_eq!(algorithm.get_quantum(), None);
+        }
+        assert!(!SchedulingAlgorithm::fcfs().is_due_date_rule());
+    }
+
+    #[test]
+    fn test_queue_discipline_display() {
+        assert_eq!(QueueDiscipline::default(), QueueDiscipline::fifo());
+        assert_eq!(QueueDiscipline::lifo().to_string(), "LIFO");
+        assert_eq!(QueueDiscipline::random(7).to_string(), "SIRO (semilla 7)");
     }
 }
\ No newline at end of file
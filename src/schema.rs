@@ -0,0 +1,341 @@
+//! # Módulo de Esquema de Escenarios
+//! 
+//! Este módulo define el formato de los escenarios como un
+//! [JSON Schema](https://json-schema.org/) y valida los documentos contra
+//! él antes de construir el escenario. A diferencia de un error genérico de
+//! lectura, cada problema se informa con la ruta del campo y la línea del
+//! archivo en la que aparece, y se informan todos los problemas a la vez:
+//! 
+//! ```text
+//! línea 4: stations[1].processing_time_ms: debe ser no negativo (>= 0), no -5
+//! línea 1: quantum_ms: debe ser > 0 cuando algorithm = rr, no 0
+//! ```
+//! 
+//! El validador implementa solo las palabras clave que usa el esquema de
+//! escenarios: `type`, `enum`, `const`, `properties`, `required`,
+//! `additionalProperties`, `items`, `minItems`, `minimum`,
+//! `exclusiveMinimum`, `allOf`, `if`/`then` y `not`.
+
+use std::fmt;
+
+use crate::json::{Json, Locations};
+
+/// Esquema del formato de escenarios (ver [`crate::Scenario`])
+const SCENARIO_SCHEMA: &str = r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Escenario del simulador de línea de ensamblaje",
+  "type": "object",
+  "required": ["algorithm"],
+  "additionalProperties": false,
+  "properties": {
+    "algorithm": {
+      "description": "Algoritmo de planificación de todas las estaciones",
+      "enum": ["fcfs", "rr", "edd", "cr"]
+    },
+    "quantum_ms": {
+      "description": "Quantum de Round Robin, en milisegundos",
+      "type": "number",
+      "minimum": 0
+    },
+    "stations": {
+      "description": "Estaciones de la línea, en orden",
+      "type": "array",
+      "minItems": 1,
+      "items": {
+        "type": "object",
+        "required": ["name", "processing_time_ms"],
+        "additionalProperties": false,
+        "properties": {
+          "name": {"type": "string"},
+          "processing_time_ms": {"type": "number", "minimum": 0},
+          "description": {"type": "string"},
+          "color": {"type": "string"}
+        }
+      }
+    },
+    "arrivals_ms": {
+      "description": "Tiempos de llegada de los productos, en milisegundos",
+      "type": "array",
+      "items": {"type": "number", "minimum": 0}
+    },
+    "burst": {
+      "description": "Llegadas en ráfagas de `size` productos cada `gap_ms`",
+      "type": "object",
+      "required": ["size", "gap_ms", "count"],
+      "additionalProperties": false,
+      "properties": {
+        "size": {"type": "integer", "minimum": 1},
+        "gap_ms": {"type": "number", "minimum": 0},
+        "count": {"type": "integer", "minimum": 1},
+        "start_ms": {"type": "number", "minimum": 0}
+      }
+    },
+    "due_dates_ms": {
+      "description": "Fechas de entrega de los productos (una por llegada), en milisegundos",
+      "type": "array",
+      "items": {"type": "number", "minimum": 0}
+    }
+  },
+  "allOf": [
+    {
+      "if": {"required": ["algorithm"], "properties": {"algorithm": {"const": "rr"}}},
+      "then": {"required": ["quantum_ms"], "properties": {"quantum_ms": {"exclusiveMinimum": 0}}}
+    },
+    {"not": {"required": ["arrivals_ms", "burst"]}}
+  ]
+}"##;
+
+/// Problema encontrado al validar un documento contra un esquema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaError {
+    /// Ruta del campo (`stations[1].processing_time_ms`; vacía para la raíz)
+    pub path: String,
+    /// Línea del documento en la que está el campo, si se conoce
+    pub line: Option<usize>,
+    /// Descripción del problema
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "línea {}: ", line)?;
+        }
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Obtiene el JSON Schema del formato de escenarios.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::schema;
+/// 
+/// let schema = schema::scenario_schema();
+/// assert_eq!(schema.get("type").and_then(|kind| kind.as_str()), Some("object"));
+/// assert!(schema.get("properties").and_then(|properties| properties.get("stations")).is_some());
+/// ```
+pub fn scenario_schema() -> Json {
+    Json::parse(SCENARIO_SCHEMA).expect("El esquema de escenarios debe ser JSON válido")
+}
+
+/// Texto del JSON Schema del formato de escenarios, para publicarlo o
+/// guardarlo junto a los archivos de escenario.
+pub fn scenario_schema_text() -> &'static str {
+    SCENARIO_SCHEMA
+}
+
+/// Valida un documento contra un esquema.
+/// 
+/// # Arguments
+/// 
+/// * `schema` - Esquema (ver [`scenario_schema`])
+/// * `document` - Documento a validar
+/// * `locations` - Líneas de los valores del documento, registradas por el
+///   analizador (ver [`Json::parse_located`])
+/// 
+/// # Returns
+/// 
+/// Todos los problemas encontrados, en orden del documento; vacío si el
+/// documento es válido
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::json::Json;
+/// use assembly_line_simulator::schema;
+/// 
+/// let text = "{\"algorithm\": \"rr\",\n \"stations\": [{\"name\": \"Corte\"}]}";
+/// let (document, locations) = Json::parse_located(text).unwrap();
+/// let errors = schema::validate(&schema::scenario_schema(), &document, &locations);
+/// let messages: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+/// assert_eq!(messages, vec![
+///     "línea 2: stations[0].processing_time_ms: falta la clave obligatoria",
+///     "línea 1: quantum_ms: falta la clave obligatoria cuando algorithm = rr",
+/// ]);
+/// ```
+pub fn validate(schema: &Json, document: &Json, locations: &Locations) -> Vec<SchemaError> {
+    let mut validator = Validator { locations, errors: Vec::new() };
+    validator.check(schema, document, "", "");
+    validator.errors
+}
+
+/// Recorre el documento junto con el esquema acumulando los problemas.
+struct Validator<'a> {
+    locations: &'a Locations,
+    errors: Vec<SchemaError>,
+}
+
+impl Validator<'_> {
+    /// Registra un problema; los campos faltantes se ubican en la línea del
+    /// objeto que debería contenerlos.
+    fn error(&mut self, path: &str, message: String) {
+        let line = self.locations.line(path).or_else(|| self.locations.line(parent(path)));
+        self.errors.push(SchemaError { path: path.to_string(), line, message });
+    }
+
+    /// Valida `value` (en la ruta `path`) contra `schema`. `condition`
+    /// describe la condición de un `if` que activó este esquema.
+    fn check(&mut self, schema: &Json, value: &Json, path: &str, condition: &str) {
+        if let Some(kind) = schema.get("type").and_then(Json::as_str) {
+            if !has_type(value, kind) {
+                let message = format!("debe ser {}, no un {}{}", type_description(kind), value.type_name(), condition);
+                self.error(path, message);
+                return;
+            }
+        }
+        if let Some(options) = schema.get("enum").and_then(Json::as_array) {
+            if !options.contains(value) {
+                let names: Vec<String> = options.iter().map(|option| option.as_str().map_or_else(|| option.to_string(), str::to_string)).collect();
+                let message = format!("valor desconocido {}{} (use {})", value, condition, join_options(&names));
+                self.error(path, message);
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if value != expected {
+                self.error(path, format!("debe ser {}{}, no {}", expected, condition, value));
+            }
+        }
+        if let Some(number) = value.as_f64() {
+            if let Some(minimum) = schema.get("minimum").and_then(Json::as_f64) {
+                if number < minimum {
+                    let bound = if minimum == 0.0 { "no negativo (>= 0)".to_string() } else { format!(">= {}", minimum) };
+                    self.error(path, format!("debe ser {}{}, no {}", bound, condition, value));
+                }
+            }
+            if let Some(minimum) = schema.get("exclusiveMinimum").and_then(Json::as_f64) {
+                if number <= minimum {
+                    self.error(path, format!("debe ser > {}{}, no {}", minimum, condition, value));
+                }
+            }
+        }
+        if let Some(items) = value.as_array() {
+            if let Some(minimum) = schema.get("minItems").and_then(Json::as_f64) {
+                if (items.len() as f64) < minimum {
+                    self.error(path, format!("debe tener al menos {} elemento(s){}", minimum, condition));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    self.check(item_schema, item, &Locations::index(path, index), condition);
+                }
+            }
+        }
+        if let Some(entries) = value.as_object() {
+            self.check_object(schema, entries, path, condition);
+        }
+        for part in schema.get("allOf").and_then(Json::as_array).unwrap_or_default() {
+            self.check(part, value, path, condition);
+        }
+        if let (Some(premise), Some(consequence)) = (schema.get("if"), schema.get("then")) {
+            if validate(premise, value, self.locations).is_empty() {
+                let condition = format!("{} cuando {}", condition, describe_condition(premise));
+                self.check(consequence, value, path, &condition);
+            }
+        }
+        if let Some(forbidden) = schema.get("not") {
+            if validate(forbidden, value, self.locations).is_empty() {
+                self.error(path, describe_negation(forbidden));
+            }
+        }
+    }
+
+    /// Valida las claves de un objeto: obligatorias, desconocidas y el
+    /// esquema de cada propiedad.
+    fn check_object(&mut self, schema: &Json, entries: &[(String, Json)], path: &str, condition: &str) {
+        let properties = schema.get("properties");
+        if schema.get("additionalProperties") == Some(&Json::Bool(false)) {
+            for (key, _) in entries {
+                if properties.and_then(|properties| properties.get(key)).is_none() {
+                    self.error(&Locations::field(path, key), format!("clave desconocida{}", condition));
+                }
+            }
+        }
+        for (key, value) in entries {
+            if let Some(property) = properties.and_then(|properties| properties.get(key)) {
+                self.check(property, value, &Locations::field(path, key), condition);
+            }
+        }
+        for key in schema.get("required").and_then(Json::as_array).unwrap_or_default() {
+            let Some(key) = key.as_str() else { continue };
+            if !entries.iter().any(|(entry, _)| entry == key) {
+                self.error(&Locations::field(path, key), format!("falta la clave obligatoria{}", condition));
+            }
+        }
+    }
+}
+
+/// Ruta del valor que contiene al de `path`.
+fn parent(path: &str) -> &str {
+    let end = path.rfind(['.', '[']).unwrap_or(0);
+    &path[..end]
+}
+
+/// Indica si un valor es del tipo de JSON Schema indicado.
+fn has_type(value: &Json, kind: &str) -> bool {
+    match (kind, value) {
+        ("object", Json::Object(_)) | ("array", Json::Array(_)) | ("string", Json::String(_)) => true,
+        ("boolean", Json::Bool(_)) | ("null", Json::Null) | ("number", Json::Number(_)) => true,
+        ("integer", Json::Number(number)) => number.fract() == 0.0,
+        _ => false,
+    }
+}
+
+/// Describe un tipo de JSON Schema para los mensajes.
+fn type_description(kind: &str) -> &str {
+    match kind {
+        "object" => "un objeto",
+        "array" => "un arreglo",
+        "string" => "un string",
+        "number" => "un número",
+        "integer" => "un entero",
+        "boolean" => "un booleano",
+        "null" => "null",
+        other => other,
+    }
+}
+
+/// Une opciones como "a, b o c".
+fn join_options(options: &[String]) -> String {
+    match options {
+        [] => String::new(),
+        [only] => only.clone(),
+        [init @ .., last] => format!("{} o {}", init.join(", "), last),
+    }
+}
+
+/// Describe la premisa de un `if` a partir de sus `const` (`algorithm = rr`).
+fn describe_condition(premise: &Json) -> String {
+    let conditions: Vec<String> = premise
+        .get("properties")
+        .and_then(Json::as_object)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|(key, property)| {
+            property.get("const").map(|value| format!("{} = {}", key, value.as_str().map_or_else(|| value.to_string(), str::to_string)))
+        })
+        .collect();
+    conditions.join(" y ")
+}
+
+/// Describe un esquema prohibido; con `required` indica las claves que no
+/// pueden aparecer juntas.
+fn describe_negation(forbidden: &Json) -> String {
+    let keys: Vec<String> = forbidden
+        .get("required")
+        .and_then(Json::as_array)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(Json::as_str)
+        .map(|key| format!("'{}'", key))
+        .collect();
+    if keys.is_empty() {
+        "el valor coincide con un esquema prohibido".to_string()
+    } else {
+        format!("{} no pueden usarse juntos", keys.join(" y "))
+    }
+}
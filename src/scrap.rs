@@ -0,0 +1,216 @@
+//! # Módulo de Desecho y Reemplazo
+//! 
+//! Este módulo define qué ocurre con los productos desechados: los que
+//! salen de la línea sin completarla porque desistieron de una cola llena o
+//! la abandonaron tras agotar su paciencia (por ejemplo, un pedido
+//! cancelado). Con la política de reemplazo, cada producto desechado genera
+//! una orden de reposición: un producto nuevo con la misma especificación
+//! que entra por la primera estación de su ruta, vinculado al original en
+//! las métricas.
+//! 
+//! Las estaciones publican los productos desechados en las órdenes de
+//! reemplazo y el generador principal las atiende como productos
+//! inyectados. Como cualquier producto en curso puede desecharse, el
+//! generador no envía la señal de apagado mientras queden productos en la
+//! línea.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::Waker;
+use std::time::Duration;
+
+use crate::product::{lock_recover, Product};
+
+/// Política con la que se tratan los productos desechados.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{ScrapPolicy, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// // El segundo producto agota su paciencia esperando al primero y se repone
+/// let stations = vec![StationConfig::new("Horno", Duration::from_millis(30))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// simulation
+///     .set_patience(Duration::from_millis(10))
+///     .set_scrap_policy(ScrapPolicy::replace(1));
+/// 
+/// let metrics = simulation.run().unwrap();
+/// assert_eq!(metrics.lost_products[0].product_id, 2);
+/// assert_eq!(metrics.lost_products[0].replaced_by, Some(3));
+/// assert_eq!(metrics.products[1].replaces, Some(2));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScrapPolicy {
+    /// Los productos desechados solo se reportan como perdidos
+    #[default]
+    Discard,
+    /// Cada producto desechado se repone con uno nuevo que entra por la
+    /// primera estación, hasta `max_replacements` veces por pedido original
+    /// (los reemplazos de reemplazos cuentan para el mismo pedido)
+    Replace { max_replacements: usize },
+}
+
+impl ScrapPolicy {
+    /// Crea una política de reemplazo.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `max_replacements` - Reemplazos admitidos por pedido original
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `max_replacements` es cero
+    pub fn replace(max_replacements: usize) -> Self {
+        assert!(max_replacements > 0, "Se debe admitir al menos un reemplazo");
+        Self::Replace { max_replacements }
+    }
+}
+
+impl fmt::Display for ScrapPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Discard => write!(f, "descartar"),
+            Self::Replace { max_replacements } => {
+                write!(f, "reemplazar (hasta {} por pedido)", max_replacements)
+            }
+        }
+    }
+}
+
+/// Órdenes de reemplazo de los productos desechados.
+/// 
+/// Es compartida por las estaciones, que publican los productos desechados
+/// y descuentan los que salen de la línea, y por los generadores, que
+/// cuentan los productos que liberan. El generador principal espera en ella
+/// tanto en el backend de hilos (con una variable de condición) como en el
+/// asíncrono (con un waker).
+pub(crate) struct ReplacementOrders {
+    max_replacements: usize,
+    state: Mutex<OrdersState>,
+    changed: Condvar,
+}
+
+/// Estado protegido de las órdenes de reemplazo.
+#[derive(Default)]
+struct OrdersState {
+    /// Productos desechados pendientes de reposición
+    scrapped: VecDeque<Arc<Product>>,
+    /// Productos liberados que aún no salieron de la línea
+    in_line: usize,
+    /// Número de reemplazo de cada producto repuesto (1 para el primero)
+    generations: HashMap<usize, usize>,
+    /// Waker del generador asíncrono que espera una orden
+    waker: Option<Waker>,
+    /// Aviso emitido sin un waker registrado, pendiente de entregar
+    missed_notice: bool,
+}
+
+impl ReplacementOrders {
+    /// Crea las órdenes de reemplazo de una política.
+    /// 
+    /// # Returns
+    /// 
+    /// `None` si la política descarta los productos
+    pub(crate) fn for_policy(policy: ScrapPolicy) -> Option<Arc<Self>> {
+        match policy {
+            ScrapPolicy::Discard => None,
+            ScrapPolicy::Replace { max_replacements } => Some(Arc::new(Self {
+                max_replacements,
+                state: Mutex::default(),
+                changed: Condvar::new(),
+            })),
+        }
+    }
+
+    /// Cuenta un producto liberado en la línea.
+    pub(crate) fn admit(&self) {
+        lock_recover(&self.state).in_line += 1;
+    }
+
+    /// Descuenta un producto que salió de la línea (completado, perdido o
+    /// descartado) y avisa al generador.
+    pub(crate) fn settle(&self) {
+        let mut state = lock_recover(&self.state);
+        state.in_line = state.in_line.saturating_sub(1);
+        self.notify(&mut state);
+    }
+
+    /// Publica un producto desechado para reponerlo, salvo que su pedido
+    /// ya agotó los reemplazos admitidos.
+    /// 
+    /// Debe llamarse antes de descontar el producto con
+    /// [`ReplacementOrders::settle`], para que el generador no dé la línea
+    /// por terminada con la orden en camino.
+    pub(crate) fn scrap(&self, product: &Arc<Product>) {
+        let mut state = lock_recover(&self.state);
+        let generation = state.generations.get(&product.id).copied().unwrap_or(0);
+        if generation < self.max_replacements {
+            state.scrapped.push_back(Arc::clone(product));
+            self.notify(&mut state);
+        }
+    }
+
+    /// Obtiene el próximo producto desechado pendiente de reposición.
+    pub(crate) fn next(&self) -> Option<Arc<Product>> {
+        lock_recover(&self.state).scrapped.pop_front()
+    }
+
+    /// Vincula un producto desechado con su reemplazo.
+    pub(crate) fn link(&self, parent: &Product, child: &Product) {
+        let mut state = lock_recover(&self.state);
+        let generation = state.generations.get(&parent.id).copied().unwrap_or(0);
+        state.generations.insert(child.id, generation + 1);
+        Product::link_replacement(parent, child);
+    }
+
+    /// Indica si no quedan productos en la línea ni órdenes pendientes, de
+    /// modo que ya no pueden surgir reemplazos.
+    pub(crate) fn is_settled(&self) -> bool {
+        let state = lock_recover(&self.state);
+        state.in_line == 0 && state.scrapped.is_empty()
+    }
+
+    /// Espera hasta que cambien las órdenes o pase `timeout`.
+    pub(crate) fn wait(&self, timeout: Duration) {
+        let state = lock_recover(&self.state);
+        if state.scrapped.is_empty() {
+            let _ = self.changed.wait_timeout(state, timeout);
+        }
+    }
+
+    /// Registra el waker del generador asíncrono, que se despierta con el
+    /// próximo cambio de las órdenes.
+    /// 
+    /// # Returns
+    /// 
+    /// `false`, sin registrar el waker, si hubo un aviso mientras ningún
+    /// waker estaba registrado; el generador debe revisar las órdenes sin
+    /// esperar
+    #[cfg(feature = "async")]
+    pub(crate) fn register_waker(&self, waker: Waker) -> bool {
+        let mut state = lock_recover(&self.state);
+        if std::mem::take(&mut state.missed_notice) {
+            return false;
+        }
+        state.waker = Some(waker);
+        true
+    }
+
+    /// Avisa un cambio de las órdenes al generador, por ejemplo porque
+    /// terminó otro generador.
+    pub(crate) fn wake(&self) {
+        self.notify(&mut lock_recover(&self.state));
+    }
+
+    /// Despierta al generador, esté esperando en un hilo o en una tarea.
+    fn notify(&self, state: &mut OrdersState) {
+        self.changed.notify_all();
+        match state.waker.take() {
+            Some(waker) => waker.wake(),
+            None => state.missed_notice = true,
+        }
+    }
+}
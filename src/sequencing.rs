@@ -0,0 +1,373 @@
+//! # Módulo de Secuenciación
+//! 
+//! Este módulo implementa el planificador global de la línea: en lugar de
+//! que cada estación decida localmente a quién atender, decide el orden en
+//! que los productos de la fuente principal se liberan a la primera
+//! estación mirando los tiempos de servicio de todas las estaciones.
+//! 
+//! El orden se obtiene con la regla de Johnson sobre dos máquinas
+//! sustitutas: la suma de los tiempos de todas las estaciones salvo la
+//! última y la de todas salvo la primera. Con dos estaciones es la regla
+//! de Johnson exacta, que minimiza el makespan; con tres es su extensión
+//! clásica y con más estaciones es una heurística. El makespan de cada
+//! orden se estima con la recurrencia del flow shop de permutación, que
+//! corresponde a una línea FCFS sin traslados.
+//! 
+//! [`FlowShop`] expone la regla de Johnson para líneas de dos y tres
+//! estaciones sobre un conjunto de productos cualquiera, y
+//! [`SequencingComparison`] ejecuta una simulación con el orden dado (FCFS)
+//! y con el de Johnson para comparar el makespan obtenido.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::arrival::ArrivalOrder;
+use crate::error::SimulationError;
+use crate::metrics::MetricsCalculator;
+use crate::simulation::Simulation;
+
+/// Plan de liberación del planificador global.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{ArrivalOrder, ProductType, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let ms = Duration::from_millis;
+/// let stations = vec![
+///     StationConfig::new("Corte", ms(10)),
+///     StationConfig::new("Empaque", ms(10)),
+/// ];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// simulation
+///     .register_product_type(ProductType::new("lento").with_service_time(0, ms(40)).with_service_time(1, ms(5)))
+///     .register_product_type(ProductType::new("rapido").with_service_time(0, ms(5)).with_service_time(1, ms(40)))
+///     .set_product_type(1, "lento")
+///     .set_product_type(2, "rapido")
+///     .set_arrival_order(ArrivalOrder::lookahead());
+/// 
+/// // El producto rápido en Corte se libera primero para alimentar Empaque
+/// let plan = simulation.release_plan().unwrap();
+/// assert_eq!(plan.order, vec![2, 1]);
+/// assert_eq!(plan.baseline_makespan, ms(85));
+/// assert_eq!(plan.planned_makespan, ms(50));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleasePlan {
+    /// IDs de los productos de la fuente principal, en el orden en que
+    /// ocupan sus tiempos de llegada
+    pub order: Vec<usize>,
+    /// Makespan estimado con el orden dado, en el que solo deciden las
+    /// estaciones (línea base local)
+    pub baseline_makespan: Duration,
+    /// Makespan estimado con el orden del plan
+    pub planned_makespan: Duration,
+}
+
+impl ReleasePlan {
+    /// Arma el plan de liberación para los productos de la fuente principal.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `releases` - Tiempos de llegada, en orden
+    /// * `times` - Tiempo de servicio de cada producto en cada estación, en
+    ///   orden de producto
+    pub(crate) fn lookahead(releases: &[Duration], times: &[Vec<Duration>]) -> Self {
+        let given: Vec<usize> = (0..times.len()).collect();
+        let order = johnson_order(times);
+        Self {
+            baseline_makespan: flow_shop_makespan(releases, times, &given),
+            planned_makespan: flow_shop_makespan(releases, times, &order),
+            order: order.into_iter().map(|idx| idx + 1).collect(),
+        }
+    }
+
+    /// Compara el makespan alcanzado en una ejecución con el de la línea
+    /// base local.
+    /// 
+    /// # Returns
+    /// 
+    /// Fracción del makespan de la línea base que se ahorró (negativa si
+    /// el plan resultó peor)
+    pub fn improvement(&self, achieved: Duration) -> f64 {
+        if self.baseline_makespan.is_zero() {
+            return 0.0;
+        }
+        1.0 - achieved.as_secs_f64() / self.baseline_makespan.as_secs_f64()
+    }
+}
+
+impl fmt::Display for ReleasePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order: Vec<String> = self.order.iter().map(|id| format!("#{:02}", id)).collect();
+        writeln!(f, "Orden de liberación: {}", order.join(", "))?;
+        writeln!(
+            f,
+            "Makespan estimado con decisiones locales: {}",
+            MetricsCalculator::format_duration(self.baseline_makespan)
+        )?;
+        write!(
+            f,
+            "Makespan estimado con el plan: {}",
+            MetricsCalculator::format_duration(self.planned_makespan)
+        )
+    }
+}
+
+/// Flow shop de dos o tres estaciones: el tiempo de servicio de cada
+/// producto en cada estación.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::FlowShop;
+/// 
+/// let secs = |times: [u64; 2]| times.iter().map(|&t| Duration::from_secs(t)).collect();
+/// let shop = FlowShop::new(vec![secs([8, 2]), secs([3, 6]), secs([1, 4]), secs([6, 5])]);
+/// 
+/// let order = shop.johnson();
+/// assert_eq!(order, vec![2, 1, 3, 0]);
+/// assert!(shop.is_optimal());
+/// assert_eq!(shop.makespan(&order), Duration::from_secs(20));
+/// assert_eq!(shop.makespan(&[0, 1, 2, 3]), Duration::from_secs(26));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FlowShop {
+    /// Tiempo de servicio de cada producto en cada estación
+    pub times: Vec<Vec<Duration>>,
+}
+
+impl FlowShop {
+    /// Crea un flow shop con los tiempos de servicio de los productos.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `times` - Un vector por producto con su tiempo en cada estación
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si algún producto no tiene exactamente dos o tres
+    /// tiempos, o si no todos tienen la misma cantidad
+    pub fn new(times: Vec<Vec<Duration>>) -> Self {
+        let stations = times.first().map_or(2, Vec::len);
+        assert!(
+            (2..=3).contains(&stations),
+            "La regla de Johnson requiere dos o tres estaciones"
+        );
+        assert!(
+            times.iter().all(|product| product.len() == stations),
+            "Todos los productos deben tener un tiempo por estación"
+        );
+        Self { times }
+    }
+
+    /// Crea el flow shop de los productos de la fuente principal de una
+    /// simulación, con sus tiempos según el catálogo de tipos.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la línea no tiene dos o tres estaciones o si un
+    /// producto referencia un tipo no registrado
+    pub fn from_simulation(simulation: &Simulation) -> Self {
+        Self::new(simulation.main_service_times())
+    }
+
+    /// Número de estaciones del flow shop.
+    pub fn stations(&self) -> usize {
+        self.times.first().map_or(0, Vec::len)
+    }
+
+    /// Ordena los productos con la regla de Johnson (con tres estaciones,
+    /// sobre las máquinas sustitutas `p1 + p2` y `p2 + p3`).
+    /// 
+    /// # Returns
+    /// 
+    /// Índices (desde 0) de los productos en el orden de liberación
+    pub fn johnson(&self) -> Vec<usize> {
+        johnson_order(&self.times)
+    }
+
+    /// Indica si el orden de Johnson es óptimo: siempre con dos estaciones
+    /// y, con tres, cuando la estación central no es cuello de botella
+    /// (`min p1 ≥ max p2` o `min p3 ≥ max p2`).
+    pub fn is_optimal(&self) -> bool {
+        if self.stations() < 3 {
+            return true;
+        }
+        let column = |station: usize| self.times.iter().map(move |product| product[station]);
+        let middle = column(1).max().unwrap_or_default();
+        column(0).min().unwrap_or_default() >= middle || column(2).min().unwrap_or_default() >= middle
+    }
+
+    /// Calcula el makespan de un orden si todos los productos están
+    /// disponibles desde el inicio.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order` - Índices de los productos en el orden de liberación
+    pub fn makespan(&self, order: &[usize]) -> Duration {
+        flow_shop_makespan(&[], &self.times, order)
+    }
+}
+
+/// Makespan de una simulación liberando sus productos en el orden dado
+/// (FCFS) y en el de la regla de Johnson.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{ProductType, Simulation, SchedulingAlgorithm, SequencingComparison, StationConfig};
+/// 
+/// let ms = Duration::from_millis;
+/// let stations = vec![
+///     StationConfig::new("Corte", ms(10)),
+///     StationConfig::new("Empaque", ms(10)),
+/// ];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// simulation
+///     .register_product_type(ProductType::new("lento").with_service_time(0, ms(40)).with_service_time(1, ms(5)))
+///     .register_product_type(ProductType::new("rapido").with_service_time(0, ms(5)).with_service_time(1, ms(40)))
+///     .set_product_type(1, "lento")
+///     .set_product_type(2, "rapido");
+/// 
+/// let comparison = SequencingComparison::run(&simulation).unwrap();
+/// assert_eq!(comparison.order, vec![2, 1]);
+/// assert!(comparison.johnson_makespan < comparison.fcfs_makespan);
+/// println!("{}", comparison);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SequencingComparison {
+    /// IDs de los productos en el orden de Johnson
+    pub order: Vec<usize>,
+    /// Indica si el orden de Johnson es óptimo para la línea (ver
+    /// [`FlowShop::is_optimal`])
+    pub optimal: bool,
+    /// Makespan estimado con el orden dado
+    pub predicted_fcfs_makespan: Duration,
+    /// Makespan estimado con el orden de Johnson
+    pub predicted_johnson_makespan: Duration,
+    /// Makespan simulado con el orden dado
+    pub fcfs_makespan: Duration,
+    /// Makespan simulado con el orden de Johnson
+    pub johnson_makespan: Duration,
+}
+
+impl SequencingComparison {
+    /// Ejecuta la simulación con el orden dado y con el de Johnson.
+    /// 
+    /// Ambas ejecuciones parten de la misma configuración y solo cambian el
+    /// orden de llegada; los tiempos de llegada se conservan.
+    /// 
+    /// # Errors
+    /// 
+    /// Devuelve el error de la primera ejecución que falle
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la línea no tiene dos o tres estaciones
+    pub fn run(simulation: &Simulation) -> Result<Self, SimulationError> {
+        let shop = FlowShop::from_simulation(simulation);
+        let mut fcfs = simulation.clone_with(|simulation| {
+            simulation.set_arrival_order(ArrivalOrder::AsGiven);
+        });
+        let mut johnson = simulation.clone_with(|simulation| {
+            simulation.set_arrival_order(ArrivalOrder::lookahead());
+        });
+        let plan = johnson.release_plan().expect("El orden de Johnson genera un plan");
+
+        Ok(Self {
+            order: plan.order,
+            optimal: shop.is_optimal(),
+            predicted_fcfs_makespan: plan.baseline_makespan,
+            predicted_johnson_makespan: plan.planned_makespan,
+            fcfs_makespan: fcfs.run()?.total_simulation_time,
+            johnson_makespan: johnson.run()?.total_simulation_time,
+        })
+    }
+
+    /// Fracción del makespan FCFS simulado que ahorra el orden de Johnson.
+    pub fn improvement(&self) -> f64 {
+        if self.fcfs_makespan.is_zero() {
+            return 0.0;
+        }
+        1.0 - self.johnson_makespan.as_secs_f64() / self.fcfs_makespan.as_secs_f64()
+    }
+}
+
+impl fmt::Display for SequencingComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = MetricsCalculator::format_duration;
+        let order: Vec<String> = self.order.iter().map(|id| format!("#{:02}", id)).collect();
+        writeln!(
+            f,
+            "=== SECUENCIACIÓN DE JOHNSON ({}) ===",
+            if self.optimal { "óptima" } else { "heurística" }
+        )?;
+        writeln!(f, "Orden: {}", order.join(", "))?;
+        writeln!(f, "{:<8}  {:>12}  {:>12}", "Orden", "Estimado", "Simulado")?;
+        writeln!(f, "{:<8}  {:>12}  {:>12}", "FCFS", time(self.predicted_fcfs_makespan), time(self.fcfs_makespan))?;
+        writeln!(f, "{:<8}  {:>12}  {:>12}", "Johnson", time(self.predicted_johnson_makespan), time(self.johnson_makespan))?;
+        write!(f, "Reducción del makespan: {:.1}%", self.improvement() * 100.0)
+    }
+}
+
+/// Ordena los productos con la regla de Johnson sobre las dos máquinas
+/// sustitutas (todas las estaciones salvo la última y todas salvo la
+/// primera).
+/// 
+/// Los productos más cortos en la primera máquina van al principio y los
+/// más cortos en la segunda al final; los empates conservan el orden dado.
+/// 
+/// # Returns
+/// 
+/// Índices de los productos en el orden de liberación
+pub(crate) fn johnson_order(times: &[Vec<Duration>]) -> Vec<usize> {
+    let surrogate = |product: &[Duration]| -> (Duration, Duration) {
+        match product.len() {
+            0 | 1 => (Duration::ZERO, Duration::ZERO),
+            stations => (
+                product[..stations - 1].iter().sum(),
+                product[1..].iter().sum(),
+            ),
+        }
+    };
+    let (mut front, mut back): (Vec<_>, Vec<_>) = times
+        .iter()
+        .enumerate()
+        .map(|(idx, product)| {
+            let (first, second) = surrogate(product);
+            (idx, first, second)
+        })
+        .partition(|&(_, first, second)| first < second);
+    front.sort_by_key(|&(_, first, _)| first);
+    back.sort_by_key(|&(_, _, second)| std::cmp::Reverse(second));
+    front.into_iter().chain(back).map(|(idx, _, _)| idx).collect()
+}
+
+/// Estima el makespan de un orden con la recurrencia del flow shop de
+/// permutación: cada producto entra a una estación cuando la dejó el
+/// anterior y cuando él mismo salió de la estación previa.
+/// 
+/// # Arguments
+/// 
+/// * `releases` - Tiempo de llegada de cada posición del orden
+/// * `times` - Tiempo de servicio de cada producto en cada estación
+/// * `order` - Índice del producto que ocupa cada posición
+pub(crate) fn flow_shop_makespan(releases: &[Duration], times: &[Vec<Duration>], order: &[usize]) -> Duration {
+    let stations = times.iter().map(Vec::len).max().unwrap_or(0);
+    let mut finished = vec![Duration::ZERO; stations];
+    let mut makespan = Duration::ZERO;
+    for (slot, &product) in order.iter().enumerate() {
+        let mut ready = releases.get(slot).copied().unwrap_or_default();
+        for (station, free_at) in finished.iter_mut().enumerate() {
+            let service = times[product].get(station).copied().unwrap_or_default();
+            ready = ready.max(*free_at) + service;
+            *free_at = ready;
+        }
+        makespan = makespan.max(ready);
+    }
+    makespan
+}
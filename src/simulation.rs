@@ -4,15 +4,43 @@
 //! de línea de ensamblaje, incluyendo la generación de productos, 
 //! coordinación de estaciones y recolección de resultados.
 
-use std::sync::{Arc, mpsc};
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
+use crate::arrival::{ArrivalOrder, ArrivalPeriod, ArrivalSource};
+use crate::bounds::LowerBounds;
+use crate::catalog::{ProductCatalog, ProductType};
+use crate::channel::{ChannelBackend, StationReceiver, StationSender};
+use crate::clock::{ClockFormat, Instant};
 use crate::config;
-use crate::metrics::{MetricsCalculator, SimulationMetrics};
-use crate::product::Product;
-use crate::scheduler::SchedulingAlgorithm;
-use crate::station::{Message, Station, StationConfig};
+use crate::control::{CommandReceiver, LineControl, StationCommand};
+use crate::deadlock::{DeadlockReport, DeadlockWatch, StationProbe, StationSnapshot};
+use crate::error::{panic_message, ConfigError, SimulationError, StallReason, StalledProduct};
+use crate::invariants::verify_invariants;
+use crate::jockey::{JockeyBoard, JockeyPolicy, JockeyReport};
+use crate::metrics::{MetricsCalculator, ProductMetrics, SimulationMetrics};
+use crate::progress::{Progress, ProgressCallback};
+use crate::product::{lock_recover, Product, ProductDependency, ProductSpec};
+use crate::reload::{self, Reconfiguration};
+use crate::routing::{RouteTarget, StationKind};
+use crate::resource::{ResourcePool, ResourceUnitReport, SharedResource, StationResource};
+use crate::scheduler::{QueueDiscipline, SchedulingAlgorithm};
+use crate::scrap::{ReplacementOrders, ScrapPolicy};
+use crate::storage::StorageMode;
+use crate::sequencing::ReleasePlan;
+use crate::sink::{ConsoleSink, Sink};
+use crate::sla::Sla;
+use crate::takt::TaktReport;
+use crate::topology::{self, Topology};
+use crate::station::{Message, RampUp, ShutdownMode, Station, StationConfig, StationReport, TimerJitter, WarmUp};
+use crate::status::StationObserver;
+use crate::stop::{LiveMetrics, StopCondition, StopSignal};
+use crate::work::{SleepWork, WorkModel};
 
 /// Orquestador principal de la simulación de línea de ensamblaje.
 /// 
@@ -21,15 +49,86 @@ use crate::station::{Message, Station, StationConfig};
 /// - Generación y distribución de productos
 /// - Sincronización entre hilos
 /// - Recolección y cálculo de métricas
+#[derive(Clone)]
 pub struct Simulation {
     /// Configuraciones de todas las estaciones en la línea
-    station_configs: Vec<StationConfig>,
+    pub(crate) station_configs: Vec<StationConfig>,
     /// Algoritmo de scheduling utilizado por todas las estaciones
-    algorithm: SchedulingAlgorithm,
+    pub(crate) algorithm: SchedulingAlgorithm,
     /// Tiempos de llegada de los productos
-    arrival_times: Vec<Duration>,
-    /// Calculadora de métricas para generar reportes
-    metrics_calculator: MetricsCalculator,
+    pub(crate) arrival_times: Vec<Duration>,
+    /// Orden en que los productos de la fuente principal ocupan sus tiempos de llegada
+    pub(crate) arrival_order: ArrivalOrder,
+    /// Algoritmos que reemplazan al general en estaciones específicas, por índice
+    pub(crate) station_algorithms: HashMap<usize, SchedulingAlgorithm>,
+    /// Disciplinas de cola de estaciones específicas, por índice (FIFO por defecto)
+    queue_disciplines: HashMap<usize, QueueDiscipline>,
+    /// Longitud de cola a partir de la cual los productos desisten, por estación
+    pub(crate) balking_thresholds: HashMap<usize, usize>,
+    /// Curva de aprendizaje de las estaciones que la tienen
+    pub(crate) ramp_ups: HashMap<usize, RampUp>,
+    /// Calentamiento tras un período ocioso de las estaciones que lo necesitan
+    pub(crate) warm_ups: HashMap<usize, WarmUp>,
+    /// Imprecisión del temporizador de las estaciones que la modelan
+    pub(crate) timer_jitters: HashMap<usize, TimerJitter>,
+    /// Paciencia de los productos que no tienen una propia
+    pub(crate) patience: Option<Duration>,
+    /// Tratamiento de los productos que desisten o abandonan una cola
+    pub(crate) scrap_policy: ScrapPolicy,
+    /// Política con la que los productos en espera cambian a una cola más corta
+    pub(crate) jockeying: JockeyPolicy,
+    /// Forma en que se guardan los intervalos de las visitas
+    pub(crate) storage_mode: StorageMode,
+    /// Fuentes de llegada adicionales a la principal
+    pub(crate) sources: Vec<ArrivalSource>,
+    /// Restricciones de precedencia entre productos
+    pub(crate) dependencies: Vec<ProductDependency>,
+    /// Recursos compartidos entre estaciones, en orden de adquisición
+    pub(crate) resources: Vec<SharedResource>,
+    /// Fechas de entrega de los productos (relativas al inicio)
+    pub(crate) due_dates: Vec<Duration>,
+    /// Metadatos adicionales de los productos programados, por ID
+    pub(crate) product_metadata: HashMap<usize, HashMap<String, String>>,
+    /// Catálogo de tipos de producto disponibles
+    pub(crate) catalog: ProductCatalog,
+    /// Tipos de producto asignados a los productos programados, por ID
+    pub(crate) product_types: HashMap<usize, String>,
+    /// Tamaños de los productos programados que no son de tamaño 1.0, por ID
+    pub(crate) product_sizes: HashMap<usize, f64>,
+    /// Tiempo máximo sin productos completados antes de declarar un bloqueo
+    pub(crate) completion_timeout: Option<Duration>,
+    /// Duración máxima de la simulación antes de declarar un bloqueo
+    pub(crate) watchdog_timeout: Option<Duration>,
+    /// Tiempo sin actividad en las estaciones antes de declarar un interbloqueo
+    pub(crate) deadlock_timeout: Option<Duration>,
+    /// Condición que determina cuándo finaliza la simulación
+    pub(crate) stop_condition: StopCondition,
+    /// Período inicial excluido de las estadísticas de estado estable
+    pub(crate) warmup: Option<Duration>,
+    /// Períodos en los que se segmenta el reporte
+    report_periods: Vec<ArrivalPeriod>,
+    /// Objetivos de servicio evaluados al finalizar
+    pub(crate) slas: Vec<Sla>,
+    /// Takt time contra el que se compara el ritmo de salida de la línea
+    pub(crate) takt_time: Option<Duration>,
+    /// Modelo con el que las estaciones ejecutan el procesamiento (`None`
+    /// usa [`SleepWork`])
+    pub(crate) work_model: Option<Arc<dyn WorkModel>>,
+    /// Tipo de canal con el que se alimenta a cada estación
+    pub(crate) channel_backend: ChannelBackend,
+    /// Reacción de las estaciones ante la señal de apagado
+    pub(crate) shutdown_mode: ShutdownMode,
+    /// Reconfiguraciones en caliente, ordenadas por momento
+    pub(crate) reconfigurations: Vec<Reconfiguration>,
+    /// Observadores de los cambios de estado de las estaciones
+    pub(crate) station_observers: Vec<Arc<dyn StationObserver>>,
+    /// Callbacks que reciben el avance de la simulación
+    pub(crate) progress_callbacks: Vec<ProgressCallback>,
+    /// Destinos que reciben cada producto completado
+    pub(crate) sinks: Vec<Arc<dyn Sink>>,
+    /// Calculadora de métricas para generar reportes, con el formato de reloj
+    /// y la longitud de tick de la simulación
+    pub(crate) metrics_calculator: MetricsCalculator,
 }
 
 impl Simulation {
@@ -55,6 +154,40 @@ impl Simulation {
             station_configs: config::default_station_configs(),
             algorithm,
             arrival_times: config::default_arrival_times(),
+            station_algorithms: HashMap::new(),
+            queue_disciplines: HashMap::new(),
+            balking_thresholds: HashMap::new(),
+            ramp_ups: HashMap::new(),
+            warm_ups: HashMap::new(),
+            timer_jitters: HashMap::new(),
+            patience: None,
+            scrap_policy: ScrapPolicy::Discard,
+            jockeying: JockeyPolicy::Disabled,
+            storage_mode: StorageMode::Full,
+            sources: Vec::new(),
+            dependencies: Vec::new(),
+            resources: Vec::new(),
+            due_dates: Vec::new(),
+            arrival_order: ArrivalOrder::AsGiven,
+            product_metadata: HashMap::new(),
+            catalog: ProductCatalog::new(),
+            product_types: HashMap::new(),
+            product_sizes: HashMap::new(),
+            completion_timeout: None,
+            watchdog_timeout: None,
+            deadlock_timeout: None,
+            stop_condition: StopCondition::AllCompleted,
+            warmup: None,
+            report_periods: Vec::new(),
+            slas: Vec::new(),
+            takt_time: None,
+            work_model: None,
+            channel_backend: ChannelBackend::Unbounded,
+            shutdown_mode: ShutdownMode::DrainQueues,
+            reconfigurations: Vec::new(),
+            station_observers: Vec::new(),
+            progress_callbacks: Vec::new(),
+            sinks: vec![Arc::new(ConsoleSink)],
             metrics_calculator: MetricsCalculator::new(),
         }
     }
@@ -79,308 +212,3174 @@ impl Simulation {
             station_configs,
             algorithm,
             arrival_times,
+            station_algorithms: HashMap::new(),
+            queue_disciplines: HashMap::new(),
+            balking_thresholds: HashMap::new(),
+            ramp_ups: HashMap::new(),
+            warm_ups: HashMap::new(),
+            timer_jitters: HashMap::new(),
+            patience: None,
+            scrap_policy: ScrapPolicy::Discard,
+            jockeying: JockeyPolicy::Disabled,
+            storage_mode: StorageMode::Full,
+            sources: Vec::new(),
+            dependencies: Vec::new(),
+            resources: Vec::new(),
+            due_dates: Vec::new(),
+            arrival_order: ArrivalOrder::AsGiven,
+            product_metadata: HashMap::new(),
+            catalog: ProductCatalog::new(),
+            product_types: HashMap::new(),
+            product_sizes: HashMap::new(),
+            completion_timeout: None,
+            watchdog_timeout: None,
+            deadlock_timeout: None,
+            stop_condition: StopCondition::AllCompleted,
+            warmup: None,
+            report_periods: Vec::new(),
+            slas: Vec::new(),
+            takt_time: None,
+            work_model: None,
+            channel_backend: ChannelBackend::Unbounded,
+            shutdown_mode: ShutdownMode::DrainQueues,
+            reconfigurations: Vec::new(),
+            station_observers: Vec::new(),
+            progress_callbacks: Vec::new(),
+            sinks: vec![Arc::new(ConsoleSink)],
             metrics_calculator: MetricsCalculator::new(),
         }
     }
 
-    /// Ejecuta la simulación completa y retorna las métricas resultantes.
+    /// Crea una copia de la simulación con un cambio aplicado.
     /// 
-    /// Este método implementa el ciclo completo de la simulación:
-    /// 1. Inicializa productos y canales de comunicación
-    /// 2. Lanza hilos para cada estación de trabajo
-    /// 3. Lanza el generador de productos
-    /// 4. Recolecta productos completados
-    /// 5. Calcula y retorna métricas finales
+    /// Permite evaluar variantes de un escenario ya configurado ("¿qué pasa
+    /// si...?") sin reconstruirlo: la copia conserva estaciones, llegadas,
+    /// dependencias, recursos, SLAs y demás opciones, y `tweak` modifica
+    /// solo lo que cambia. Los observadores, callbacks de avance y sinks se
+    /// comparten con la simulación original.
     /// 
-    /// # Returns
+    /// # Arguments
     /// 
-    /// `SimulationMetrics` con todos los resultados y estadísticas
+    /// * `tweak` - Función que modifica la copia
     /// 
-    /// # Panics
+    /// # Returns
     /// 
-    /// Puede hacer panic si:
-    /// - No se pueden crear los canales de comunicación
-    /// - Los hilos de estaciones fallan durante la ejecución
-    /// - El generador de productos falla
+    /// La simulación modificada; la original no cambia
     /// 
     /// # Examples
     /// 
     /// ```rust
-    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm};
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{diff_metrics, Simulation, SchedulingAlgorithm, StationConfig};
     /// 
-    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
-    /// let metrics = simulation.run();
-    /// println!("Tiempo promedio de espera: {:?}", metrics.average_wait_time);
+    /// let stations = vec![
+    ///     StationConfig::new("Corte", Duration::from_millis(10)),
+    ///     StationConfig::new("Ensamblaje", Duration::from_millis(30)),
+    /// ];
+    /// let mut base = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    /// // ¿Y si el ensamblaje fuera el doble de rápido?
+    /// let mut faster = base.clone_with(|simulation| {
+    ///     simulation.set_processing_time(1, Duration::from_millis(15));
+    /// });
+    /// 
+    /// let diff = diff_metrics(&base.run().unwrap(), &faster.run().unwrap());
+    /// assert!(diff.get("Tiempo promedio de espera").unwrap().difference() < 0.0);
     /// ```
-    pub fn run(&mut self) -> SimulationMetrics {
-        println!(
-            "=== Simulación de línea de ensamblaje ({}) ===",
-            self.algorithm
-        );
-        println!("Configuración:");
-        for (i, config) in self.station_configs.iter().enumerate() {
-            println!("  Estación {}: {} ({}ms)", 
-                i + 1, 
-                config.name, 
-                config.processing_time.as_millis()
-            );
-        }
-        println!("Productos a procesar: {}", self.arrival_times.len());
-        println!();
-
-        let start_time = Instant::now();
-        
-        // Crear productos
-        let products = self.create_products();
-        let total_products = products.len();
-
-        // Configurar canales de comunicación
-        let (channels, collector_rx) = self.setup_channels();
-        
-        // Lanzar estaciones de trabajo
-        let station_handles = self.launch_stations(channels);
-        
-        // Lanzar generador de productos
-        let first_sender = station_handles.first()
-            .expect("Debe haber al menos una estación")
-            .sender.clone();
-        
-        let generator_handle = self.launch_generator(
-            first_sender,
-            products.clone(),
-            start_time,
-        );
-
-        // Recolectar productos completados
-        let completion_order = self.collect_completed_products(collector_rx, total_products);
-        
-        let end_time = Instant::now();
-
-        // Esperar a que terminen todos los hilos
-        generator_handle.join()
-            .expect("El generador falló");
-        
-        for handle_info in station_handles {
-            handle_info.handle.join()
-                .expect("Una estación falló");
-        }
-
-        // Calcular y retornar métricas
-        let metrics = self.metrics_calculator.calculate_simulation_metrics(
-            &products,
-            &self.station_configs,
-            start_time,
-            end_time,
-            completion_order,
-        );
-
-        println!("\n=== Simulación completada ===");
-        println!("Duración total: {}", 
-            MetricsCalculator::format_duration(end_time.duration_since(start_time)));
+    pub fn clone_with<F>(&self, tweak: F) -> Self
+    where
+        F: FnOnce(&mut Simulation),
+    {
+        let mut simulation = self.clone();
+        tweak(&mut simulation);
+        simulation
+    }
 
-        metrics
+    /// Cambia el algoritmo de planificación general.
+    /// 
+    /// Las estaciones con un algoritmo propio (ver
+    /// [`Simulation::set_station_algorithm`]) lo conservan.
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_algorithm(&mut self, algorithm: SchedulingAlgorithm) -> &mut Self {
+        self.algorithm = algorithm;
+        self
     }
 
-    /// Genera un reporte detallado de los resultados.
+    /// Cambia el tiempo de procesamiento de una estación.
     /// 
     /// # Arguments
     /// 
-    /// * `metrics` - Métricas de la simulación
+    /// * `station_index` - Índice de la estación (desde 0)
+    /// * `processing_time` - Nuevo tiempo de procesamiento por producto
     /// 
     /// # Returns
     /// 
-    /// String con el reporte formateado
-    pub fn generate_report(&self, metrics: &SimulationMetrics) -> String {
-        self.metrics_calculator.generate_report(metrics, &self.station_configs)
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `station_index` no corresponde a una estación configurada
+    pub fn set_processing_time(&mut self, station_index: usize, processing_time: Duration) -> &mut Self {
+        assert!(
+            station_index < self.station_configs.len(),
+            "La estación {} no existe",
+            station_index
+        );
+        self.station_configs[station_index].processing_time = processing_time;
+        self
     }
 
-    /// Genera un reporte en formato CSV.
+    /// Cambia la sensibilidad de una estación al tamaño de los productos
+    /// (ver [`StationConfig::with_size_sensitivity`]).
     /// 
     /// # Arguments
     /// 
-    /// * `metrics` - Métricas de la simulación
+    /// * `station_index` - Índice de la estación (desde 0)
+    /// * `sensitivity` - Exponente aplicado al tamaño: 1.0 escala el tiempo
+    ///   en proporción al tamaño y 0.0 lo ignora
     /// 
     /// # Returns
     /// 
-    /// String con los datos en formato CSV
-    pub fn generate_csv_report(&self, metrics: &SimulationMetrics) -> String {
-        self.metrics_calculator.generate_csv_report(metrics)
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `station_index` no corresponde a una estación
+    /// configurada, o si la sensibilidad es negativa o no es finita
+    pub fn set_size_sensitivity(&mut self, station_index: usize, sensitivity: f64) -> &mut Self {
+        assert!(
+            station_index < self.station_configs.len(),
+            "La estación {} no existe",
+            station_index
+        );
+        let config = self.station_configs[station_index].clone();
+        self.station_configs[station_index] = config.with_size_sensitivity(sensitivity);
+        self
     }
 
-    /// Crea todos los productos para la simulación.
-    fn create_products(&self) -> Vec<Arc<Product>> {
-        self.arrival_times
-            .iter()
-            .enumerate()
-            .map(|(idx, &offset)| {
-                Product::new(idx + 1, offset, &self.station_configs)
-            })
-            .collect()
+    /// Reemplaza los tiempos de llegada de la fuente principal.
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_arrival_times(&mut self, arrival_times: Vec<Duration>) -> &mut Self {
+        self.arrival_times = arrival_times;
+        self
     }
 
-    /// Configura los canales de comunicación entre estaciones.
-    fn setup_channels(&self) -> (Vec<ChannelPair>, mpsc::Receiver<Arc<Product>>) {
-        let (collector_tx, collector_rx) = mpsc::channel::<Arc<Product>>();
-        
-        let mut channels = Vec::new();
-        for i in 0..self.station_configs.len() {
-            let (tx, rx) = mpsc::channel::<Message>();
-            let next_sender = if i + 1 < self.station_configs.len() {
-                None // Se configurará después
-            } else {
-                None
-            };
-            let collector = if i + 1 == self.station_configs.len() {
-                Some(collector_tx.clone())
-            } else {
-                None
-            };
-            
-            channels.push(ChannelPair {
-                sender: tx,
-                receiver: rx,
-                next_sender,
-                collector,
-            });
-        }
+    /// Configura el orden en que los productos de la fuente principal
+    /// ocupan sus tiempos de llegada.
+    /// 
+    /// Los tiempos de llegada se conservan; cambia qué producto llega en
+    /// cada uno. Las fuentes adicionales no se ven afectadas.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order` - Orden de llegada (ver [`ArrivalOrder`])
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{ArrivalOrder, BatchRunner, Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+    /// let arrivals = (0..4).map(|i| Duration::from_millis(i * 10)).collect();
+    /// let base = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    /// 
+    /// // Una réplica por semilla, con los mismos tiempos de llegada
+    /// let replications: Vec<Simulation> = (1..=3)
+    ///     .map(|seed| base.clone_with(|simulation| {
+    ///         simulation.set_arrival_order(ArrivalOrder::shuffled(seed));
+    ///     }))
+    ///     .collect();
+    /// for result in BatchRunner::new().run(replications) {
+    ///     let metrics = result.unwrap();
+    ///     let mut arrivals: Vec<_> = metrics.products.iter().map(|product| product.arrival_time).collect();
+    ///     arrivals.sort();
+    ///     assert_eq!(arrivals.len(), 4);
+    ///     assert!(arrivals[3] >= Duration::from_millis(30));
+    /// }
+    /// ```
+    pub fn set_arrival_order(&mut self, order: ArrivalOrder) -> &mut Self {
+        self.arrival_order = order;
+        self
+    }
 
-        // Configurar next_sender para cada canal
-        for i in 0..(channels.len() - 1) {
-            channels[i].next_sender = Some(channels[i + 1].sender.clone());
+    /// Calcula el plan del planificador global, si el orden de llegada es
+    /// [`ArrivalOrder::Lookahead`].
+    /// 
+    /// El plan indica qué producto de la fuente principal ocupa cada tiempo
+    /// de llegada y el makespan estimado frente al del orden dado. Se
+    /// incluye en [`SimulationMetrics::release_plan`] al ejecutar.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si un producto referencia un tipo no registrado
+    pub fn release_plan(&self) -> Option<ReleasePlan> {
+        if self.arrival_order != ArrivalOrder::Lookahead {
+            return None;
         }
-
-        (channels, collector_rx)
+        Some(ReleasePlan::lookahead(&self.arrival_times, &self.main_service_times()))
     }
 
-    /// Lanza todos los hilos de las estaciones de trabajo.
-    fn launch_stations(&self, channels: Vec<ChannelPair>) -> Vec<StationHandle> {
-        let mut handles = Vec::new();
-        
-        for (index, (config, channel)) in self.station_configs.iter().zip(channels).enumerate() {
-            let station = Station::new(index, *config, self.algorithm.clone());
-            
-            let handle = thread::spawn(move || {
-                station.run(
-                    channel.receiver,
-                    channel.next_sender,
-                    channel.collector,
-                );
-            });
-            
-            handles.push(StationHandle {
-                handle,
-                sender: channel.sender,
-            });
-        }
-        
-        handles
+    /// Calcula el tiempo de servicio de cada producto de la fuente principal
+    /// en cada estación, según su tipo.
+    pub(crate) fn main_service_times(&self) -> Vec<Vec<Duration>> {
+        (1..=self.arrival_times.len())
+            .map(|id| {
+                let spec = ProductSpec {
+                    product_type: self.product_types.get(&id).cloned(),
+                    size: self.product_sizes.get(&id).copied(),
+                    ..ProductSpec::default()
+                };
+                let product = self.catalog.create_product(id, Duration::ZERO, &spec, &self.station_configs);
+                (0..self.station_configs.len()).map(|station| product.processing_time(station)).collect()
+            })
+            .collect()
     }
 
-    /// Lanza el generador de productos.
-    fn launch_generator(
-        &self,
-        sender: mpsc::Sender<Message>,
-        products: Vec<Arc<Product>>,
-        start_time: Instant,
-    ) -> thread::JoinHandle<()> {
-        let first_station_config = self.station_configs[0];
-        
-        thread::spawn(move || {
-            ProductGenerator::run(sender, products, first_station_config, start_time);
-        })
+    /// Asigna un algoritmo de planificación propio a una estación.
+    /// 
+    /// Las demás estaciones siguen usando el algoritmo general de la
+    /// simulación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station_index` - Índice de la estación (desde 0)
+    /// * `algorithm` - Algoritmo que usará esa estación
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `station_index` no corresponde a una estación configurada
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm};
+    /// 
+    /// // Round Robin en todas las estaciones salvo el empaque
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::round_robin(Duration::from_millis(300)));
+    /// simulation.set_station_algorithm(2, SchedulingAlgorithm::fcfs());
+    /// ```
+    pub fn set_station_algorithm(&mut self, station_index: usize, algorithm: SchedulingAlgorithm) -> &mut Self {
+        assert!(
+            station_index < self.station_configs.len(),
+            "La estación {} no existe",
+            station_index
+        );
+        self.station_algorithms.insert(station_index, algorithm);
+        self
     }
 
-    /// Recolecta los productos completados en orden de finalización.
-    fn collect_completed_products(
-        &self,
-        collector_rx: mpsc::Receiver<Arc<Product>>,
-        total_products: usize,
-    ) -> Vec<usize> {
-        let mut completion_order = Vec::new();
-        
-        for _ in 0..total_products {
-            if let Ok(product) = collector_rx.recv() {
-                completion_order.push(product.id);
-                println!("[COMPLETADO] Producto {:02} terminó toda la línea", product.id);
-            }
-        }
-        
-        completion_order
+    /// Asigna la disciplina de cola de una estación.
+    /// 
+    /// La disciplina decide a cuál de los productos en espera atiende la
+    /// estación: FIFO (por defecto), LIFO o en orden aleatorio. Combinada
+    /// con el algoritmo de planificación permite las comparaciones clásicas
+    /// de teoría de colas, por ejemplo FCFS con LIFO frente a FCFS con FIFO.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station_index` - Índice de la estación (desde 0)
+    /// * `discipline` - Disciplina que usará esa estación
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `station_index` no corresponde a una estación configurada
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{QueueDiscipline, Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(50))];
+    /// let arrivals = (0..4).map(|i| Duration::from_millis(i * 10)).collect();
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    /// simulation.set_queue_discipline(0, QueueDiscipline::lifo());
+    /// 
+    /// // El primero toma la estación ociosa; el resto se atiende del último al primero
+    /// let metrics = simulation.run().unwrap();
+    /// assert_eq!(metrics.completion_order, vec![1, 4, 3, 2]);
+    /// ```
+    pub fn set_queue_discipline(&mut self, station_index: usize, discipline: QueueDiscipline) -> &mut Self {
+        assert!(
+            station_index < self.station_configs.len(),
+            "La estación {} no existe",
+            station_index
+        );
+        self.queue_disciplines.insert(station_index, discipline);
+        self
     }
-}
 
-/// Generador de productos que respeta los tiempos de llegada simulados.
-struct ProductGenerator;
+    /// Asigna el umbral de desistimiento de una estación.
+    /// 
+    /// Un producto que llega a la estación cuando su cola ya tiene
+    /// `threshold` productos esperando desiste de unirse: sale de la línea y
+    /// se reporta en `lost_products`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station_index` - Índice de la estación (desde 0)
+    /// * `threshold` - Longitud de cola a partir de la cual los productos desisten
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `station_index` no corresponde a una estación configurada
+    /// o si `threshold` es cero
+    pub fn set_balking_threshold(&mut self, station_index: usize, threshold: usize) -> &mut Self {
+        assert!(
+            station_index < self.station_configs.len(),
+            "La estación {} no existe",
+            station_index
+        );
+        assert!(threshold > 0, "El umbral de desistimiento debe ser mayor que cero");
+        self.balking_thresholds.insert(station_index, threshold);
+        self
+    }
 
-impl ProductGenerator {
-    /// Ejecuta el ciclo de generación de productos.
+    /// Asigna la paciencia de los productos en las colas.
     /// 
-    /// Genera productos respetando sus tiempos de llegada simulados,
-    /// los introduce en la primera estación y envía la señal de apagado
-    /// al final.
-    fn run(
-        sender: mpsc::Sender<Message>,
-        products: Vec<Arc<Product>>,
-        first_station: StationConfig,
-        start_time: Instant,
-    ) {
-        println!("[GENERADOR] Iniciando generación de {} productos", products.len());
-        
-        for product in products {
-            // Esperar hasta el momento de llegada simulado
-            let target_time = start_time + product.arrival_offset;
-            let now = Instant::now();
-            
-            if now < target_time {
-                let wait_time = target_time - now;
-                thread::sleep(wait_time);
-            }
-
-            // Registrar llegada real
-            let arrival_instant = Instant::now();
-            product.set_arrival_instant(arrival_instant);
-
-            // Inicializar estado en la primera estación
-            {
-                let mut station_state = product.station_state(0).lock()
-                    .expect("No se pudo obtener lock del estado de la primera estación");
-                station_state.queue_entry = Some(arrival_instant);
-                if station_state.remaining.is_zero() {
-                    station_state.remaining = first_station.processing_time;
-                }
-            }
+    /// Un producto que espera en la cola de una estación más que su
+    /// paciencia sin comenzar a procesarse la abandona: sale de la línea y
+    /// se reporta en `lost_products` con sus métricas parciales. Aplica a
+    /// los productos sin paciencia propia ([`ProductSpec::with_patience`]).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `patience` - Espera máxima en cola
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{LossReason, Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Caja", Duration::from_millis(100))];
+    /// let arrivals = vec![Duration::ZERO; 3];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    /// simulation.set_patience(Duration::from_millis(150));
+    /// 
+    /// // El tercero tendría que esperar 200ms: abandona la cola
+    /// let metrics = simulation.run().unwrap();
+    /// assert_eq!(metrics.products.len(), 2);
+    /// assert_eq!(metrics.lost_products[0].loss.unwrap().reason, LossReason::Reneged);
+    /// ```
+    pub fn set_patience(&mut self, patience: Duration) -> &mut Self {
+        self.patience = Some(patience);
+        self
+    }
 
-            println!(
-                "[GENERADOR] Producto {:02} disponible en t={}",
-                product.id,
-                MetricsCalculator::format_duration(arrival_instant.duration_since(start_time))
-            );
+    /// Indica qué hacer con los productos desechados.
+    /// 
+    /// Un producto se desecha cuando desiste de unirse a una cola o la
+    /// abandona tras agotar su paciencia. Con [`ScrapPolicy::Replace`] cada
+    /// producto desechado se repone con uno nuevo de la misma especificación
+    /// que entra por la primera estación en el momento del desecho; en las
+    /// métricas, `replaced_by` del original y `replaces` del reemplazo
+    /// vinculan a ambos. Mientras queden productos en la línea el generador
+    /// principal sigue activo para atender los reemplazos.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `policy` - Política de desecho (por defecto, [`ScrapPolicy::Discard`])
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_scrap_policy(&mut self, policy: ScrapPolicy) -> &mut Self {
+        self.scrap_policy = policy;
+        self
+    }
 
-            // Enviar producto a la primera estación
-            sender
-                .send(Message::Product(product))
-                .expect("No se pudo enviar producto a la primera estación");
-        }
+    /// Permite que los productos en espera cambien a una cola paralela más
+    /// corta (jockeying).
+    /// 
+    /// Hay colas paralelas entre estaciones hermanas (las que un mismo
+    /// divisor alimenta y que continúan hacia la misma estación) y entre las
+    /// unidades de un recurso compartido, que con la política tienen una
+    /// cola cada una. Las métricas resumen los cambios en
+    /// [`SimulationMetrics::jockeying`] (ver [`crate::jockey`]). Solo el
+    /// backend con hilos admite cambios de cola.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `policy` - Política de cambios de cola (por defecto, [`JockeyPolicy::Disabled`])
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_jockeying(&mut self, policy: JockeyPolicy) -> &mut Self {
+        self.jockeying = policy;
+        self
+    }
 
-        // Enviar señal de apagado
-        sender
-            .send(Message::Shutdown)
-            .expect("No se pudo enviar señal de apagado");
-        
-        println!("[GENERADOR] Generación completada, señal de apagado enviada");
+    /// Indica cómo se guardan los intervalos de espera y procesamiento de
+    /// cada visita durante la ejecución.
+    /// 
+    /// Con [`StorageMode::Compact`] las estaciones agregan los intervalos a
+    /// registros compactos que se incorporan a los productos al terminar,
+    /// lo que reduce la memoria de las corridas con muchos productos; las
+    /// métricas de los productos se calculan entonces al final y no al
+    /// salir de la línea. Solo lo aplican los backends asíncronos (ver
+    /// [`StorageMode`]).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - Modo de almacenamiento (por defecto, [`StorageMode::Full`])
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_storage_mode(&mut self, mode: StorageMode) -> &mut Self {
+        self.storage_mode = mode;
+        self
     }
-}
 
-/// Información de canales para una estación.
+    /// Asigna una curva de aprendizaje a una estación.
+    /// 
+    /// Los primeros productos que inician su procesamiento en la estación
+    /// tardan más que el tiempo nominal, según [`RampUp`]. El tiempo
+    /// agregado se cuenta como servicio y no como espera, por lo que se
+    /// refleja en el slowdown y en la utilización de la estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station_index` - Índice de la estación (desde 0)
+    /// * `ramp_up` - Curva de aprendizaje de esa estación
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `station_index` no corresponde a una estación configurada
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{RampUp, Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Montaje", Duration::from_millis(40))];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    /// simulation.set_ramp_up(0, RampUp::new(2, 2.0));
+    /// 
+    /// // El primero tarda el doble, el segundo x1.5 y el tercero el nominal
+    /// let metrics = simulation.run().unwrap();
+    /// assert_eq!(metrics.products[0].service_time, Duration::from_millis(80));
+    /// assert_eq!(metrics.products[1].service_time, Duration::from_millis(60));
+    /// assert_eq!(metrics.products[2].service_time, Duration::from_millis(40));
+    /// ```
+    pub fn set_ramp_up(&mut self, station_index: usize, ramp_up: RampUp) -> &mut Self {
+        assert!(
+            station_index < self.station_configs.len(),
+            "La estación {} no existe",
+            station_index
+        );
+        self.ramp_ups.insert(station_index, ramp_up);
+        self
+    }
+
+    /// Asigna a una estación un calentamiento tras los períodos ociosos.
+    /// 
+    /// Cuando la estación estuvo ociosa más del umbral de [`WarmUp`], se
+    /// calienta antes de atender al siguiente producto. El calentamiento
+    /// ocupa a la estación y el producto lo espera en la cola; el reporte de
+    /// la estación registra cuántos hubo y su tiempo total.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station_index` - Índice de la estación (desde 0)
+    /// * `warm_up` - Calentamiento de esa estación
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `station_index` no corresponde a una estación configurada
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig, WarmUp};
+    /// 
+    /// // El horno se enfría si pasa más de 100ms sin trabajo
+    /// let stations = vec![StationConfig::new("Horno", Duration::from_millis(20))];
+    /// let arrivals = vec![Duration::ZERO, Duration::from_millis(200)];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    /// simulation.set_warm_up(0, WarmUp::new(Duration::from_millis(100), Duration::from_millis(30)));
+    /// 
+    /// let metrics = simulation.run().unwrap();
+    /// let report = &metrics.station_reports[0];
+    /// assert_eq!(report.warm_ups, 1);
+    /// assert_eq!(report.warm_up_time, Duration::from_millis(30));
+    /// assert!(metrics.products[1].total_wait_time >= Duration::from_millis(30));
+    /// ```
+    pub fn set_warm_up(&mut self, station_index: usize, warm_up: WarmUp) -> &mut Self {
+        assert!(
+            station_index < self.station_configs.len(),
+            "La estación {} no existe",
+            station_index
+        );
+        self.warm_ups.insert(station_index, warm_up);
+        self
+    }
+
+    /// Modela un temporizador impreciso en una estación.
+    /// 
+    /// Cada porción de procesamiento de la estación dura, además de lo
+    /// nominal, un retraso aleatorio según [`TimerJitter`], como si el
+    /// temporizador despertara tarde. El retraso se cuenta en el turnaround
+    /// y en la ocupación de la estación, pero no como espera: el reporte de
+    /// la estación lo acumula en `injected_drift` y las métricas de cada
+    /// producto en `timer_drift`. Funciona tanto en tiempo real como en
+    /// tiempo virtual.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station_index` - Índice de la estación (desde 0)
+    /// * `jitter` - Imprecisión del temporizador de esa estación
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `station_index` no corresponde a una estación configurada
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig, TimerJitter};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    /// let algorithm = SchedulingAlgorithm::round_robin(Duration::from_millis(10));
+    /// let mut simulation = Simulation::with_config(stations, algorithm, vec![Duration::ZERO; 2]);
+    /// simulation.set_timer_jitter(0, TimerJitter::uniform(Duration::from_millis(5)).with_seed(1));
+    /// 
+    /// let metrics = simulation.run().unwrap();
+    /// let report = &metrics.station_reports[0];
+    /// assert!(report.injected_drift <= Duration::from_millis(5) * 4);
+    /// let timer_drift: Duration = metrics.products.iter().map(|product| product.timer_drift).sum();
+    /// assert_eq!(timer_drift, report.injected_drift);
+    /// ```
+    pub fn set_timer_jitter(&mut self, station_index: usize, jitter: TimerJitter) -> &mut Self {
+        assert!(
+            station_index < self.station_configs.len(),
+            "La estación {} no existe",
+            station_index
+        );
+        self.timer_jitters.insert(station_index, jitter);
+        self
+    }
+
+    /// Disciplina de cola de la estación indicada.
+    pub(crate) fn queue_discipline(&self, station_index: usize) -> QueueDiscipline {
+        self.queue_disciplines.get(&station_index).copied().unwrap_or_default()
+    }
+
+    /// Algoritmo con el que opera la estación indicada.
+    pub(crate) fn station_algorithm(&self, station_index: usize) -> SchedulingAlgorithm {
+        self.station_algorithms
+            .get(&station_index)
+            .unwrap_or(&self.algorithm)
+            .clone()
+    }
+
+    /// Agrega una fuente de llegadas independiente.
+    /// 
+    /// Cada fuente se ejecuta en su propio hilo generador y alimenta la primera
+    /// estación junto con la fuente principal (los tiempos de llegada de la
+    /// configuración). La línea se apaga solo cuando todas las fuentes terminan.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `source` - Fuente de llegadas a agregar
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{ArrivalProcess, ArrivalSource, ProductSpec, Simulation, SchedulingAlgorithm};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation.add_arrival_source(ArrivalSource::new(
+    ///     "retrabajo",
+    ///     ArrivalProcess::Fixed(vec![Duration::from_millis(500)]),
+    ///     ProductSpec::default().with_class("retrabajo"),
+    /// ));
+    /// ```
+    pub fn add_arrival_source(&mut self, source: ArrivalSource) -> &mut Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// Asigna fechas de entrega a los productos programados.
+    /// 
+    /// La fecha en la posición `i` corresponde al producto con ID `i + 1`;
+    /// los productos sin fecha asignada no tienen fecha de entrega. Las
+    /// fechas se usan en las reglas de despacho EDD y CR y en las métricas
+    /// de tardanza.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `due_dates` - Fechas de entrega relativas al inicio de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_due_dates(&mut self, due_dates: Vec<Duration>) -> &mut Self {
+        self.due_dates = due_dates;
+        self
+    }
+
+    /// Registra un tipo de producto en el catálogo de la simulación.
+    /// 
+    /// Los productos referencian un tipo a través de
+    /// [`ProductSpec::product_type`] (en fuentes de llegada o productos
+    /// inyectados) o con [`Simulation::set_product_type`] para los productos
+    /// de la fuente principal.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product_type` - Tipo de producto a registrar
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la ruta o los tiempos de servicio del tipo no son
+    /// válidos para las estaciones de la simulación
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{ProductType, Simulation, SchedulingAlgorithm};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation
+    ///     .register_product_type(
+    ///         ProductType::new("basico")
+    ///             .with_route(vec![0, 2])
+    ///             .with_service_time(2, Duration::from_millis(200)),
+    ///     )
+    ///     .set_product_type(1, "basico");
+    /// ```
+    pub fn register_product_type(&mut self, product_type: ProductType) -> &mut Self {
+        if let Err(message) = product_type.validate(self.station_configs.len()) {
+            panic!("{}", message);
+        }
+        self.catalog.register(product_type);
+        self
+    }
+
+    /// Asigna un tipo del catálogo a un producto programado.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product_id` - ID del producto
+    /// * `type_name` - Nombre del tipo registrado con
+    ///   [`Simulation::register_product_type`]
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_product_type(&mut self, product_id: usize, type_name: impl Into<String>) -> &mut Self {
+        self.product_types.insert(product_id, type_name.into());
+        self
+    }
+
+    /// Asigna el tamaño de un producto programado.
+    /// 
+    /// En cada estación el tiempo de procesamiento del producto se
+    /// multiplica por `tamaño ^ sensibilidad` de la estación. Aplica a los
+    /// productos creados al inicio de la simulación; los productos
+    /// inyectados o de fuentes adicionales reciben su tamaño a través de su
+    /// [`ProductSpec`] (ver [`ProductSpec::with_size`]).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product_id` - ID del producto
+    /// * `size` - Tamaño relativo del producto (1.0 es el tamaño normal)
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el tamaño no es un número positivo
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![
+    ///     StationConfig::new("Corte", Duration::from_millis(20)),
+    ///     StationConfig::new("Empaque", Duration::from_millis(10)).with_size_sensitivity(0.0),
+    /// ];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+    /// simulation.set_product_size(2, 2.0);
+    /// 
+    /// let metrics = simulation.run().unwrap();
+    /// let big = metrics.products.iter().find(|product| product.product_id == 2).unwrap();
+    /// assert_eq!(big.service_time, Duration::from_millis(40 + 10));
+    /// ```
+    pub fn set_product_size(&mut self, product_id: usize, size: f64) -> &mut Self {
+        assert!(size.is_finite() && size > 0.0, "El tamaño del producto debe ser un número positivo");
+        self.product_sizes.insert(product_id, size);
+        self
+    }
+
+    /// Agrega un metadato a un producto programado.
+    /// 
+    /// Aplica a los productos creados al inicio de la simulación (fuente
+    /// principal y fuentes finitas); los productos inyectados o de fuentes
+    /// infinitas reciben sus metadatos a través de su [`ProductSpec`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product_id` - ID del producto
+    /// * `key` - Clave del metadato (por ejemplo, `"orden"` o `"sku"`)
+    /// * `value` - Valor del metadato
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation
+    ///     .set_product_metadata(1, "orden", "OC-1042")
+    ///     .set_product_metadata(1, "cliente", "ACME");
+    /// ```
+    pub fn set_product_metadata(
+        &mut self,
+        product_id: usize,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> &mut Self {
+        self.product_metadata
+            .entry(product_id)
+            .or_default()
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Configura el tiempo máximo de espera entre productos completados.
+    /// 
+    /// Si mientras hay productos en la línea ninguno la termina durante este
+    /// tiempo, la simulación se aborta con [`SimulationError::Stalled`]
+    /// indicando qué estación retiene cada producto.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `timeout` - Tiempo máximo sin progreso
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_completion_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.completion_timeout = Some(timeout);
+        self
+    }
+
+    /// Configura la duración máxima de la simulación (watchdog global).
+    /// 
+    /// Si la simulación no termina dentro de este tiempo se aborta con
+    /// [`SimulationError::Stalled`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `limit` - Duración máxima permitida
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_watchdog_timeout(&mut self, limit: Duration) -> &mut Self {
+        self.watchdog_timeout = Some(limit);
+        self
+    }
+
+    /// Configura el tiempo sin actividad tras el cual se declara un interbloqueo.
+    /// 
+    /// Con la detección activa cada estación publica su estado en vivo; si
+    /// hay productos en la línea, ninguna estación está procesando y ninguna
+    /// cambia de estado durante este tiempo, la simulación se aborta con
+    /// [`SimulationError::Deadlocked`] y un volcado del estado de cada
+    /// estación. La detección se activa sola, con
+    /// [`config::DEFAULT_DEADLOCK_TIMEOUT_MS`], cuando la línea usa canales
+    /// acotados o recursos compartidos.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `timeout` - Tiempo máximo sin actividad
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_deadlock_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.deadlock_timeout = Some(timeout);
+        self
+    }
+
+    /// Tiempo sin actividad tras el cual se declara un interbloqueo, si la
+    /// detección está activa.
+    fn effective_deadlock_timeout(&self) -> Option<Duration> {
+        let at_risk = self.channel_backend.is_bounded() || !self.resources.is_empty();
+        self.deadlock_timeout.or_else(|| {
+            at_risk.then(|| Duration::from_millis(config::DEFAULT_DEADLOCK_TIMEOUT_MS))
+        })
+    }
+
+    /// Configura la condición de parada de la simulación.
+    /// 
+    /// Por defecto la simulación termina cuando todos los productos completan
+    /// la línea. Con otra condición, al cumplirse se detienen los generadores
+    /// y las estaciones; los productos que aún estaban en la línea se reportan
+    /// como incompletos con sus métricas parciales.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `condition` - Condición de parada
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StopCondition};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation.set_stop_condition(StopCondition::Completions(5));
+    /// ```
+    pub fn set_stop_condition(&mut self, condition: StopCondition) -> &mut Self {
+        self.stop_condition = condition;
+        self
+    }
+
+    /// Registra un observador de los cambios de estado de las estaciones.
+    /// 
+    /// Cada estación publica sus cambios entre los estados `Idle`, `Busy`,
+    /// `Blocked` y `Down` a todos los observadores registrados. Con
+    /// [`StatusBoard`](crate::StatusBoard) se puede consultar el estado actual
+    /// de cada estación mientras la simulación avanza.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `observer` - Observador a registrar
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StatusBoard};
+    /// 
+    /// let board = StatusBoard::new();
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation.add_station_observer(board.clone());
+    /// ```
+    pub fn add_station_observer(&mut self, observer: impl StationObserver + 'static) -> &mut Self {
+        self.station_observers.push(Arc::new(observer));
+        self
+    }
+
+    /// Registra un callback que recibe el avance de la simulación.
+    /// 
+    /// El colector invoca el callback cada vez que un producto completa la
+    /// línea, con los productos completados, el tiempo transcurrido y el
+    /// tiempo restante estimado a partir del trabajo de servicio pendiente.
+    /// Con más de [`progress::MAX_UPDATES`](crate::progress::MAX_UPDATES)
+    /// productos lo invoca solo cada tanto (ver [`Progress::is_due`]).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `callback` - Función que recibe el [`Progress`] de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation.on_progress(|progress| eprintln!("{}", progress));
+    /// ```
+    pub fn on_progress<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        self.progress_callbacks.push(Arc::new(callback));
+        self
+    }
+
+    /// Registra un destino que recibe cada producto al completar la línea.
+    /// 
+    /// La simulación incluye por defecto un [`ConsoleSink`] que imprime una
+    /// línea por producto; use [`Simulation::clear_sinks`] para quitarlo.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sink` - Destino a registrar (ver [`Sink`])
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig, VecSink};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+    /// let sink = VecSink::new();
+    /// simulation.clear_sinks().add_sink(sink.clone());
+    /// simulation.run().expect("La simulación falló");
+    /// assert_eq!(sink.len(), 2);
+    /// ```
+    pub fn add_sink(&mut self, sink: impl Sink + 'static) -> &mut Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Quita todos los destinos registrados, incluido el [`ConsoleSink`]
+    /// por defecto.
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn clear_sinks(&mut self) -> &mut Self {
+        self.sinks.clear();
+        self
+    }
+
+    /// Configura el modelo de trabajo de las estaciones.
+    /// 
+    /// Por defecto las estaciones duermen durante cada porción
+    /// ([`SleepWork`]). Con [`BusyWait`](crate::BusyWait) consumen CPU real
+    /// y con [`NoWork`](crate::NoWork) el procesamiento es instantáneo.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `work_model` - Modelo de trabajo a utilizar
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{NoWork, Simulation, SchedulingAlgorithm};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation.set_work_model(NoWork);
+    /// ```
+    pub fn set_work_model(&mut self, work_model: impl WorkModel + 'static) -> &mut Self {
+        self.work_model = Some(Arc::new(work_model));
+        self
+    }
+
+    /// Configura el tipo de canal entre el generador y las estaciones.
+    /// 
+    /// Con [`ChannelBackend::Bounded`] cada estación tiene un buffer de
+    /// entrada de capacidad fija: cuando está lleno, la estación anterior (o
+    /// el generador) se bloquea hasta que haya espacio. El tiempo bloqueado
+    /// se reporta en [`StationReport::blocked_time`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `backend` - Tipo de canal a utilizar
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{ChannelBackend, Simulation, SchedulingAlgorithm};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation.set_channel_backend(ChannelBackend::Bounded { capacity: 2 });
+    /// ```
+    pub fn set_channel_backend(&mut self, backend: ChannelBackend) -> &mut Self {
+        self.channel_backend = backend;
+        self
+    }
+
+    /// Configura cómo reaccionan las estaciones a la señal de apagado.
+    /// 
+    /// Por defecto ([`ShutdownMode::DrainQueues`]) cada estación termina los
+    /// productos de su cola antes de reenviar la señal. Con
+    /// [`ShutdownMode::Immediate`] o [`ShutdownMode::AfterDeadline`] las
+    /// estaciones abandonan su cola y los productos sin terminar se
+    /// reportan en `incomplete_products` con su progreso parcial.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - Modo de apagado de todas las estaciones
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, ShutdownMode, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(40))];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    /// simulation.set_shutdown_mode(ShutdownMode::AfterDeadline(Duration::from_millis(60)));
+    /// let metrics = simulation.run().expect("La simulación falló");
+    /// assert_eq!(metrics.products.len() + metrics.incomplete_products.len(), 4);
+    /// assert!(!metrics.incomplete_products.is_empty());
+    /// ```
+    pub fn set_shutdown_mode(&mut self, mode: ShutdownMode) -> &mut Self {
+        self.shutdown_mode = mode;
+        self
+    }
+
+    /// Configura el formato de los tiempos de los registros y los reportes
+    /// de esta simulación.
+    /// 
+    /// Por defecto se usa el formato global ([`ClockFormat::set_global`]).
+    /// El formato propio solo afecta a esta simulación: otras que corran en
+    /// paralelo conservan el suyo.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `format` - Formato de reloj de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{ClockFormat, Simulation, SchedulingAlgorithm};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation.set_clock_format(ClockFormat::Millis);
+    /// assert_eq!(simulation.clock_format(), ClockFormat::Millis);
+    /// ```
+    pub fn set_clock_format(&mut self, format: ClockFormat) -> &mut Self {
+        self.metrics_calculator = self.metrics_calculator.with_clock_format(format);
+        self
+    }
+
+    /// Retorna el formato de los tiempos de esta simulación: el propio si se
+    /// fijó uno, o el global.
+    pub fn clock_format(&self) -> ClockFormat {
+        self.metrics_calculator.clock_format()
+    }
+
+    /// Configura la longitud de un tick de esta simulación.
+    /// 
+    /// Las métricas resultantes la registran en
+    /// [`SimulationMetrics::tick_length`], de modo que sus tiempos se
+    /// expresan en ticks con [`SimulationMetrics::to_ticks`], y los
+    /// registros y reportes en [`ClockFormat::Ticks`] la usan. Por defecto
+    /// es [`SimTime::DEFAULT_TICK_LENGTH`](crate::SimTime::DEFAULT_TICK_LENGTH);
+    /// otras simulaciones que corran en paralelo conservan la suya.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `tick` - Duración de un tick
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `tick` es cero
+    pub fn set_tick_length(&mut self, tick: Duration) -> &mut Self {
+        self.metrics_calculator = self.metrics_calculator.with_tick_length(tick);
+        self
+    }
+
+    /// Retorna la longitud de un tick de esta simulación.
+    pub fn tick_length(&self) -> Duration {
+        self.metrics_calculator.tick_length()
+    }
+
+    /// Configura el período de calentamiento para las estadísticas de estado estable.
+    /// 
+    /// Los productos que llegan antes de que termine el calentamiento se
+    /// excluyen de [`SimulationMetrics::steady_state`]. Es útil junto con
+    /// fuentes de llegada infinitas para estudiar la línea en régimen.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `warmup` - Duración del calentamiento desde el inicio de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{
+    ///     ArrivalProcess, ArrivalSource, ProductSpec, Simulation, SchedulingAlgorithm, StopCondition,
+    /// };
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation
+    ///     .add_arrival_source(ArrivalSource::new(
+    ///         "poisson",
+    ///         ArrivalProcess::Exponential {
+    ///             mean_interarrival: Duration::from_millis(500),
+    ///             seed: 42,
+    ///             count: None,
+    ///         },
+    ///         ProductSpec::default(),
+    ///     ))
+    ///     .set_stop_condition(StopCondition::Duration(Duration::from_secs(60)))
+    ///     .set_warmup(Duration::from_secs(10));
+    /// ```
+    pub fn set_warmup(&mut self, warmup: Duration) -> &mut Self {
+        self.warmup = Some(warmup);
+        self
+    }
+
+    /// Configura los períodos en los que se segmenta el reporte.
+    /// 
+    /// Para cada período se informan las llegadas, las finalizaciones y los
+    /// tiempos promedio en [`SimulationMetrics::periods`]. Si no se
+    /// configuran, se usan los períodos de la primera fuente con llegadas
+    /// por período ([`ArrivalProcess::Piecewise`]).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `periods` - Períodos del reporte
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn set_report_periods(&mut self, periods: Vec<ArrivalPeriod>) -> &mut Self {
+        self.report_periods = periods;
+        self
+    }
+
+    /// Programa una reconfiguración en caliente de la línea.
+    /// 
+    /// Al llegar el momento indicado, cada estación pasa a usar el tiempo de
+    /// procesamiento (para los productos que le lleguen desde entonces) y el
+    /// quantum de la reconfiguración. Los productos quedan anotados con la
+    /// época de configuración en que llegaron
+    /// ([`ProductMetrics::config_epoch`]) y el reporte resume cada época en
+    /// [`SimulationMetrics::config_epochs`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `reconfiguration` - Configuración nueva y momento en que rige
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn add_reconfiguration(&mut self, reconfiguration: Reconfiguration) -> &mut Self {
+        let position = self.reconfigurations.partition_point(|scheduled| scheduled.at <= reconfiguration.at);
+        self.reconfigurations.insert(position, reconfiguration);
+        self
+    }
+
+    /// Traduce las reconfiguraciones en los comandos programados de cada
+    /// estación, por índice.
+    pub(crate) fn reconfiguration_schedule(&self) -> Vec<Vec<(Duration, StationCommand)>> {
+        let mut schedule = vec![Vec::new(); self.station_configs.len()];
+        let mut previous = self.station_configs.as_slice();
+        for reconfiguration in &self.reconfigurations {
+            for (index, command) in reconfiguration.commands(previous) {
+                schedule[index].push((reconfiguration.at, command));
+            }
+            previous = &reconfiguration.stations;
+        }
+        schedule
+    }
+
+    /// Períodos en los que se segmenta el reporte.
+    pub(crate) fn report_periods(&self) -> Vec<ArrivalPeriod> {
+        if !self.report_periods.is_empty() {
+            return self.report_periods.clone();
+        }
+        self.sources
+            .iter()
+            .find_map(|source| source.process.periods())
+            .map(<[ArrivalPeriod]>::to_vec)
+            .unwrap_or_default()
+    }
+
+    /// Declara un objetivo de servicio (SLA) de la simulación.
+    /// 
+    /// Al finalizar, cada SLA se evalúa sobre las métricas y su resultado se
+    /// incluye en [`SimulationMetrics::sla_results`] y en los reportes.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sla` - Objetivo a evaluar
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    pub fn add_sla(&mut self, sla: Sla) -> &mut Self {
+        self.slas.push(sla);
+        self
+    }
+
+    /// Configura el takt time de la línea: el intervalo entre productos
+    /// terminados que exige la demanda.
+    /// 
+    /// Al finalizar, los intervalos entre salidas consecutivas se comparan
+    /// con el takt time en [`SimulationMetrics::takt`], por cada período del
+    /// reporte (ver [`Simulation::set_report_periods`]), y el reporte marca
+    /// los intervalos en los que la línea se atrasó.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `takt_time` - Intervalo objetivo
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el takt time es cero
+    pub fn set_takt_time(&mut self, takt_time: Duration) -> &mut Self {
+        assert!(!takt_time.is_zero(), "El takt time debe ser mayor que cero");
+        self.takt_time = Some(takt_time);
+        self
+    }
+
+    /// Agrega una restricción de precedencia entre dos productos.
+    /// 
+    /// El producto `dependency.product` no podrá iniciar en la primera estación
+    /// hasta que `dependency.depends_on` complete la estación `dependency.station`.
+    /// El tiempo bloqueado se reporta por separado de la espera en cola.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dependency` - Restricción de precedencia a agregar
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{ProductDependency, Simulation, SchedulingAlgorithm};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// simulation.add_dependency(ProductDependency { product: 2, depends_on: 1, station: 1 });
+    /// ```
+    pub fn add_dependency(&mut self, dependency: ProductDependency) -> &mut Self {
+        self.dependencies.push(dependency);
+        self
+    }
+
+    /// Agrega un recurso compartido entre estaciones.
+    /// 
+    /// Las estaciones que lo usan deben adquirir una unidad antes de cada
+    /// porción de procesamiento de los productos que lo requieren. Si
+    /// necesitan varios recursos, los adquieren en el orden en que se
+    /// agregaron. El tiempo esperando recursos se reporta por separado de
+    /// la espera en cola, y durante esa espera la estación está `Blocked`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `resource` - Recurso a agregar
+    /// 
+    /// # Returns
+    /// 
+    /// Referencia mutable a la simulación para encadenar llamadas
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el recurso referencia una estación inexistente
+    pub fn add_shared_resource(&mut self, resource: SharedResource) -> &mut Self {
+        if let Err(message) = resource.validate(self.station_configs.len()) {
+            panic!("{}", message);
+        }
+        self.resources.push(resource);
+        self
+    }
+
+    /// Crea las unidades disponibles de cada recurso para una ejecución.
+    pub(crate) fn resource_pools(&self) -> Vec<Arc<ResourcePool>> {
+        self.resources
+            .iter()
+            .map(|resource| Arc::new(ResourcePool::new(resource).with_jockeying(self.jockeying)))
+            .collect()
+    }
+
+    /// Obtiene los recursos que usa una estación, en orden de adquisición.
+    pub(crate) fn station_resources(&self, pools: &[Arc<ResourcePool>], station_index: usize) -> Vec<StationResource> {
+        self.resources
+            .iter()
+            .zip(pools)
+            .filter_map(|(resource, pool)| {
+                let product_types: Vec<Option<String>> = resource.users
+                    .iter()
+                    .filter(|user| user.station == station_index)
+                    .map(|user| user.product_type.clone())
+                    .collect();
+                (!product_types.is_empty()).then(|| StationResource {
+                    pool: Arc::clone(pool),
+                    product_types,
+                })
+            })
+            .collect()
+    }
+
+    /// Verifica que la configuración de la simulación sea válida.
+    /// 
+    /// Se rechazan las líneas sin estaciones, las estaciones con tiempo de
+    /// procesamiento cero, los algoritmos Round Robin con quantum cero (el
+    /// general o el de una estación), los tiempos de llegada desordenados y
+    /// los algoritmos propios asignados a estaciones inexistentes. Los
+    /// tiempos de llegada son `Duration`, por lo que no pueden ser negativos.
+    /// 
+    /// También se verifica la topología de la línea (ver [`Topology`]):
+    /// estaciones que ningún producto alcanza, ciclos de los que los
+    /// productos no pueden salir, salidas de divisores sin uso, uniones con
+    /// orígenes que nunca les envían productos, y rutas, dependencias o
+    /// productos que referencian estaciones o tipos inexistentes.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si la configuración es válida, o el primer `ConfigError`
+    /// encontrado
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{ConfigError, Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    /// let arrivals = vec![Duration::from_millis(50), Duration::from_millis(10)];
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    /// 
+    /// assert!(matches!(simulation.validate(), Err(ConfigError::UnsortedArrivals { index: 1, .. })));
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.station_configs.is_empty() {
+            return Err(ConfigError::NoStations);
+        }
+        for (station_index, config) in self.station_configs.iter().enumerate() {
+            if config.processing_time.is_zero() {
+                return Err(ConfigError::ZeroProcessingTime {
+                    station_index,
+                    station: config.name.clone(),
+                });
+            }
+            match &config.kind {
+                StationKind::Processing => {}
+                StationKind::Inspection(inspection) => {
+                    inspection.check(station_index, self.station_configs.len()).map_err(|reason| {
+                        ConfigError::InvalidInspection {
+                            station_index,
+                            station: config.name.clone(),
+                            reason,
+                        }
+                    })?;
+                }
+                StationKind::Splitter(splitter) => {
+                    splitter.check(station_index, self.station_configs.len()).map_err(|reason| {
+                        ConfigError::InvalidSplitter {
+                            station_index,
+                            station: config.name.clone(),
+                            reason,
+                        }
+                    })?;
+                }
+                StationKind::Merger(merger) => {
+                    merger.check(station_index, self.station_configs.len()).map_err(|reason| {
+                        ConfigError::InvalidMerger {
+                            station_index,
+                            station: config.name.clone(),
+                            reason,
+                        }
+                    })?;
+                }
+            }
+        }
+        if matches!(self.algorithm, SchedulingAlgorithm::RoundRobin { quantum } if quantum.is_zero()) {
+            return Err(ConfigError::ZeroQuantum { station_index: None });
+        }
+        let mut overrides: Vec<_> = self.station_algorithms.iter().collect();
+        overrides.sort_by_key(|(index, _)| **index);
+        for (&station_index, algorithm) in overrides {
+            if station_index >= self.station_configs.len() {
+                return Err(ConfigError::StationAlgorithmOutOfRange {
+                    station_index,
+                    station_count: self.station_configs.len(),
+                });
+            }
+            if matches!(algorithm, SchedulingAlgorithm::RoundRobin { quantum } if quantum.is_zero()) {
+                return Err(ConfigError::ZeroQuantum { station_index: Some(station_index) });
+            }
+        }
+        for reconfiguration in &self.reconfigurations {
+            reconfiguration.check(&self.station_configs).map_err(|reason| {
+                ConfigError::InvalidReconfiguration { at: reconfiguration.at, reason }
+            })?;
+        }
+        for (index, pair) in self.arrival_times.windows(2).enumerate() {
+            if pair[1] < pair[0] {
+                return Err(ConfigError::UnsortedArrivals {
+                    index: index + 1,
+                    previous: pair[0],
+                    current: pair[1],
+                });
+            }
+        }
+        topology::check(self)
+    }
+
+    /// Construye el grafo de la línea: las conexiones entre la entrada, las
+    /// estaciones y la salida que pueden recorrer los productos según las
+    /// inspecciones, divisores y uniones de la línea y las rutas de los
+    /// tipos del catálogo (ver [`Topology`]).
+    pub fn topology(&self) -> Topology {
+        Topology::of(self)
+    }
+
+    /// Describe la línea en formato DOT de Graphviz: las estaciones con su
+    /// tiempo de servicio, algoritmo, tipo y buffer de entrada, los recursos
+    /// compartidos que usan y las conexiones del grafo de la línea (ver
+    /// [`Simulation::topology`]).
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![
+    ///     StationConfig::new("Corte", Duration::from_millis(10)),
+    ///     StationConfig::new("Pintura", Duration::from_millis(40)),
+    /// ];
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO]);
+    /// 
+    /// let dot = simulation.export_topology_dot();
+    /// assert!(dot.starts_with("digraph linea {"));
+    /// assert!(dot.contains("estacion_1 [label=\"Pintura\\n0.040s · FCFS\", shape=box];"));
+    /// assert!(dot.contains("estacion_0 -> estacion_1;"));
+    /// ```
+    pub fn export_topology_dot(&self) -> String {
+        topology::to_dot(self, None)
+    }
+
+    /// Igual que [`Simulation::export_topology_dot`], pero anota cada
+    /// estación con la utilización que tuvo en una ejecución y la colorea
+    /// de verde (ociosa) a rojo (saturada).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de una ejecución de esta simulación
+    pub fn export_topology_dot_with_utilization(&self, metrics: &SimulationMetrics) -> String {
+        topology::to_dot(self, Some(metrics))
+    }
+
+    /// Analiza la configuración sin ejecutar la simulación y calcula cotas
+    /// inferiores de sus resultados: el tiempo total de servicio, el
+    /// makespan mínimo (limitado por el recorrido de los productos o por la
+    /// estación más cargada) y el turnaround mínimo de cada producto.
+    /// 
+    /// No lanza hilos ni espera, por lo que sirve para revisar una
+    /// configuración antes de una ejecución larga (ver [`LowerBounds`]).
+    /// 
+    /// # Returns
+    /// 
+    /// Las cotas de la simulación, o el primer `ConfigError` encontrado por
+    /// [`Simulation::validate`]
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si una dependencia referencia un producto inexistente
+    pub fn analyze(&self) -> Result<LowerBounds, ConfigError> {
+        self.validate()?;
+        Ok(LowerBounds::of(self))
+    }
+
+    /// Valida la configuración y la convierte en un `SimulationError` con
+    /// métricas vacías si no es válida.
+    pub(crate) fn check_config(&self) -> Result<(), SimulationError> {
+        self.validate().map_err(|error| self.invalid_config(error))
+    }
+
+    /// Convierte un problema de configuración en un `SimulationError` con
+    /// métricas vacías.
+    pub(crate) fn invalid_config(&self, error: ConfigError) -> SimulationError {
+        let now = Instant::now();
+        let metrics = self.metrics_calculator.calculate_simulation_metrics(
+            &[],
+            &self.station_configs,
+            now,
+            now,
+            Vec::new(),
+        );
+        SimulationError::InvalidConfig { error, metrics: Box::new(metrics) }
+    }
+
+    /// Ejecuta la simulación completa y retorna las métricas resultantes.
+    /// 
+    /// Este método implementa el ciclo completo de la simulación:
+    /// 1. Inicializa productos y canales de comunicación
+    /// 2. Lanza hilos para cada estación de trabajo
+    /// 3. Lanza el generador de productos
+    /// 4. Recolecta productos completados
+    /// 5. Calcula y retorna métricas finales
+    /// 
+    /// Equivale a llamar a [`Simulation::start`] seguido de
+    /// [`SimulationHandle::wait`] sin inyectar productos adicionales, previa
+    /// verificación de la configuración con [`Simulation::validate`].
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(SimulationMetrics)` con todos los resultados y estadísticas, o
+    /// `Err(SimulationError)` con las métricas parciales si algún hilo de la
+    /// simulación hizo panic, o `SimulationError::InvalidConfig` si la
+    /// configuración no es válida
+    /// 
+    /// # Panics
+    /// 
+    /// En las compilaciones de depuración hace panic si las métricas de la
+    /// ejecución no cumplen sus invariantes (ver [`verify_invariants`])
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm};
+    /// 
+    /// let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
+    /// let metrics = simulation.run().expect("La simulación falló");
+    /// println!("Tiempo promedio de espera: {:?}", metrics.average_wait_time);
+    /// ```
+    pub fn run(&mut self) -> Result<SimulationMetrics, SimulationError> {
+        self.check_config()?;
+        let result = self.start().wait();
+        // En depuración, un error de contabilidad hace fallar a quien ejecute
+        // la simulación (ver `verify_invariants`)
+        if cfg!(debug_assertions) {
+            if let Ok(metrics) = &result {
+                let violations = verify_invariants(metrics);
+                assert!(
+                    violations.is_empty(),
+                    "Las métricas de la simulación no son coherentes:\n{}",
+                    violations.iter().map(|violation| format!("  - {}\n", violation)).collect::<String>()
+                );
+            }
+        }
+        result
+    }
+
+    /// Inicia la simulación sin bloquear y retorna un handle para controlarla.
+    /// 
+    /// El handle permite inyectar productos adicionales (por ejemplo, pedidos
+    /// urgentes de alta prioridad) mientras la simulación está en curso. La
+    /// línea no se apaga hasta que se llama a [`SimulationHandle::wait`], que
+    /// cierra la admisión de nuevos productos y espera a que terminen todos.
+    /// 
+    /// # Returns
+    /// 
+    /// `SimulationHandle` de la simulación en ejecución
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si hay una fuente de llegadas infinita y la condición de
+    /// parada es [`StopCondition::AllCompleted`], ya que nunca se cumpliría
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{ProductSpec, Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO]);
+    /// 
+    /// let handle = simulation.start();
+    /// handle.inject_product(ProductSpec::with_priority(10)).unwrap();
+    /// let metrics = handle.wait().unwrap();
+    /// assert_eq!(metrics.products.len(), 2);
+    /// ```
+    pub fn start(&self) -> SimulationHandle {
+        let open_loop = self.sources.iter().any(|source| source.process.is_infinite());
+        assert!(
+            !open_loop || self.stop_condition.is_monitored(),
+            "Una fuente de llegadas infinita requiere una condición de parada"
+        );
+        let calculator = self.metrics_calculator;
+        let _clock_format = calculator.enter_clock();
+
+        println!(
+            "=== Simulación de línea de ensamblaje ({}) ===",
+            self.algorithm
+        );
+        println!("Configuración:");
+        for (i, config) in self.station_configs.iter().enumerate() {
+            println!("  Estación {}: {} ({})", 
+                i + 1, 
+                config.name, 
+                MetricsCalculator::format_duration(config.processing_time)
+            );
+            if let Some(description) = &config.description {
+                println!("    {}", description);
+            }
+            if let Some(algorithm) = self.station_algorithms.get(&i) {
+                println!("    Algoritmo propio: {}", algorithm);
+            }
+            if let Some(discipline) = self.queue_disciplines.get(&i) {
+                println!("    Disciplina de cola: {}", discipline);
+            }
+            if let Some(threshold) = self.balking_thresholds.get(&i) {
+                println!("    Desistimiento con {} productos en cola", threshold);
+            }
+            if let Some(ramp_up) = self.ramp_ups.get(&i) {
+                println!("    Curva de aprendizaje: {}", ramp_up);
+            }
+            if let Some(warm_up) = self.warm_ups.get(&i) {
+                println!("    Calentamiento: {}", warm_up);
+            }
+            if let Some(jitter) = self.timer_jitters.get(&i) {
+                println!("    Temporizador impreciso: {}", jitter);
+            }
+        }
+        if open_loop {
+            println!("Productos a procesar: ilimitados (ciclo abierto)");
+        } else {
+            let total_products = self.arrival_times.len()
+                + self.sources
+                    .iter()
+                    .filter_map(|source| source.process.expected_count())
+                    .sum::<usize>();
+            println!("Productos a procesar: {}", total_products);
+        }
+        if self.scrap_policy != ScrapPolicy::Discard {
+            println!("Productos desechados: {}", self.scrap_policy);
+        }
+        if self.jockeying.is_enabled() {
+            println!("Cambios de cola: {}", self.jockeying);
+        }
+        if self.arrival_order != ArrivalOrder::AsGiven {
+            println!("Orden de llegada: {}", self.arrival_order);
+        }
+        if !self.sources.is_empty() {
+            println!("Fuentes de llegada: {}", self.sources.len() + 1);
+        }
+        println!();
+
+        let start_time = Instant::now();
+        
+        // Crear productos y registrarlos para el cálculo de métricas
+        let products_by_source = self.create_products();
+        let registry = Arc::new(Mutex::new(products_by_source.concat()));
+
+        // Configurar canales de comunicación
+        let (channels, collector_rx) = self.setup_channels();
+        
+        // Lanzar estaciones de trabajo; si los productos pueden recircular la
+        // primera estación lleva la cuenta de los productos que deben recircular
+        let stop_signal = StopSignal::new();
+        let pending_returns = self
+            .may_recirculate()
+            .then(|| Arc::new(AtomicUsize::new(0)));
+        let deadlock_timeout = self.effective_deadlock_timeout();
+        let probes: Vec<Arc<StationProbe>> = match deadlock_timeout {
+            Some(_) => self.station_configs
+                .iter()
+                .enumerate()
+                .map(|(index, config)| Arc::new(StationProbe::new(index, config.name.as_str())))
+                .collect(),
+            None => Vec::new(),
+        };
+        let (control, commands) = LineControl::channels(&self.station_configs, self.channel_backend);
+        let context = LineContext {
+            start_time,
+            stop_signal,
+            pending_returns,
+            replacements: ReplacementOrders::for_policy(self.scrap_policy),
+            discarded: Arc::new(AtomicUsize::new(0)),
+        };
+        let (station_handles, first_sender) = self.launch_stations(channels, commands, &probes, &context);
+        
+        // Las fuentes infinitas generan sus productos a medida que llegan
+        let arrivals_by_source = products_by_source
+            .into_iter()
+            .enumerate()
+            .map(|(idx, products)| -> Arrivals {
+                match idx.checked_sub(1).map(|source_idx| &self.sources[source_idx]) {
+                    Some(source) if source.process.is_infinite() => {
+                        let spec = source.spec.clone();
+                        Box::new(source.process.iter().map(move |offset| {
+                            PendingArrival::Generated { offset, spec: spec.clone() }
+                        }))
+                    }
+                    _ => Box::new(products.into_iter().map(PendingArrival::Scheduled)),
+                }
+            })
+            .collect();
+
+        // Lanzar un generador de productos por fuente
+        let (injector, injection_rx) = mpsc::channel::<ProductSpec>();
+        let (generator_handles, admitted) = self.launch_generators(
+            first_sender,
+            arrivals_by_source,
+            injection_rx,
+            Arc::clone(&registry),
+            &context,
+        );
+        let LineContext { stop_signal, replacements, discarded, .. } = context;
+
+        // Recolectar productos completados en un hilo dedicado
+        let streams: MetricsStreams = Arc::new(Mutex::new(Some(Vec::new())));
+        let collector = Collector {
+            registry: Arc::clone(&registry),
+            admitted,
+            discarded: Arc::clone(&discarded),
+            station_configs: self.station_configs.clone(),
+            start_time,
+            completion_timeout: self.completion_timeout,
+            watchdog_timeout: self.watchdog_timeout,
+            deadlock_timeout,
+            probes,
+            stop_condition: self.stop_condition.clone(),
+            stop_signal: stop_signal.clone(),
+            progress_callbacks: self.progress_callbacks.clone(),
+            sinks: self.sinks.clone(),
+            streams: Arc::clone(&streams),
+            reconfigurations: self.reconfigurations.clone(),
+            open_loop,
+            replacements,
+        };
+        let collector_handle = thread::spawn(move || {
+            let _clock_format = calculator.enter_clock();
+            collector.run(collector_rx)
+        });
+
+        SimulationHandle {
+            injector: Some(injector),
+            generator_handles,
+            collector_handle,
+            station_handles,
+            registry,
+            station_configs: self.station_configs.clone(),
+            start_time,
+            warmup: self.warmup,
+            report_periods: self.report_periods(),
+            slas: self.slas.clone(),
+            takt_time: self.takt_time,
+            resources: self.resources.clone(),
+            jockeying: self.jockeying,
+            release_plan: self.release_plan(),
+            reconfigurations: self.reconfigurations.clone(),
+            channel_backend: self.channel_backend,
+            metrics_calculator: calculator,
+            stop_signal,
+            cancelled: AtomicBool::new(false),
+            streams,
+            control,
+            discarded,
+        }
+    }
+
+    /// Genera un reporte detallado de los resultados.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// String con el reporte formateado
+    pub fn generate_report(&self, metrics: &SimulationMetrics) -> String {
+        self.metrics_calculator.generate_report(metrics, &self.station_configs)
+    }
+
+    /// Genera un reporte en formato CSV.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// String con los datos en formato CSV
+    pub fn generate_csv_report(&self, metrics: &SimulationMetrics) -> String {
+        self.metrics_calculator.generate_csv_report(metrics)
+    }
+
+    /// Escribe el reporte CSV en `writer` sin armarlo en memoria.
+    /// 
+    /// Ver [`MetricsCalculator::write_csv_report`].
+    /// 
+    /// # Errors
+    /// 
+    /// Devuelve el primer error de escritura
+    pub fn write_csv_report<W: Write>(&self, metrics: &SimulationMetrics, writer: W) -> io::Result<()> {
+        self.metrics_calculator.write_csv_report(metrics, writer)
+    }
+
+    /// Indica si algún producto puede volver a una estación que no está más
+    /// adelante en la línea y debe recircular: por la ruta de su tipo o
+    /// porque una inspección o un divisor lo devuelve a una estación anterior.
+    pub(crate) fn may_recirculate(&self) -> bool {
+        self.catalog.has_reentrant_routes()
+            || self.station_configs.iter().enumerate().any(|(index, config)| {
+                config.kind
+                    .targets()
+                    .iter()
+                    .any(|target| matches!(target, RouteTarget::Station(station) if *station <= index))
+            })
+    }
+
+    /// Estaciones posteriores a las que la estación `index` salta
+    /// directamente (sin pasar por la siguiente), en orden y sin repetir.
+    pub(crate) fn branch_targets(&self, index: usize) -> Vec<usize> {
+        let mut targets: Vec<usize> = self.station_configs[index]
+            .kind
+            .targets()
+            .into_iter()
+            .filter_map(|target| match target {
+                RouteTarget::Station(station) if station > index + 1 && station < self.station_configs.len() => {
+                    Some(station)
+                }
+                _ => None,
+            })
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+        targets
+    }
+
+    /// Crea todos los productos para la simulación y resuelve sus dependencias.
+    /// 
+    /// Los IDs se asignan de forma consecutiva: primero los productos de la
+    /// fuente principal y luego los de cada fuente adicional en orden. Las
+    /// fuentes infinitas no crean productos por adelantado: sus productos
+    /// reciben IDs a medida que llegan.
+    /// 
+    /// # Returns
+    /// 
+    /// Los productos agrupados por fuente, comenzando por la fuente principal
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si una dependencia referencia un producto o estación
+    /// inexistente, o si un producto referencia un tipo no registrado
+    pub(crate) fn create_products(&self) -> Vec<Vec<Arc<Product>>> {
+        let mut next_id = 1;
+        let mut create = |offset: Duration, spec: &ProductSpec| {
+            let mut spec = spec.clone();
+            if let Some(metadata) = self.product_metadata.get(&next_id) {
+                spec.metadata.extend(metadata.clone());
+            }
+            if let Some(type_name) = self.product_types.get(&next_id) {
+                spec.product_type = Some(type_name.clone());
+            }
+            if let Some(&size) = self.product_sizes.get(&next_id) {
+                spec.size = Some(size);
+            }
+            let product = self.catalog.create_product(next_id, offset, &spec, &self.station_configs);
+            next_id += 1;
+            product
+        };
+
+        // Tiempo de llegada de cada producto de la fuente principal según el orden configurado
+        let mut offsets = self.arrival_times.clone();
+        let permutation = match self.release_plan() {
+            Some(plan) => plan.order.iter().map(|id| id - 1).collect(),
+            None => self.arrival_order.permutation(offsets.len()),
+        };
+        let mut slots = vec![0; offsets.len()];
+        for (slot, idx) in permutation.into_iter().enumerate() {
+            offsets[idx] = self.arrival_times[slot];
+            slots[idx] = slot;
+        }
+
+        let mut products_by_source = Vec::with_capacity(self.sources.len() + 1);
+        products_by_source.push(
+            offsets
+                .iter()
+                .enumerate()
+                .map(|(idx, &offset)| {
+                    let spec = ProductSpec {
+                        due_date: self.due_dates.get(idx).copied(),
+                        ..ProductSpec::default()
+                    };
+                    create(offset, &spec)
+                })
+                .collect::<Vec<_>>(),
+        );
+        for source in &self.sources {
+            if source.process.is_infinite() {
+                products_by_source.push(Vec::new());
+                continue;
+            }
+            products_by_source.push(
+                source.process
+                    .offsets()
+                    .into_iter()
+                    .map(|offset| create(offset, &source.spec))
+                    .collect(),
+            );
+        }
+
+        let products = products_by_source.concat();
+        let find = |id: usize| {
+            products
+                .get(id.wrapping_sub(1))
+                .unwrap_or_else(|| panic!("Dependencia con producto inexistente: {}", id))
+        };
+
+        for dependency in &self.dependencies {
+            assert!(
+                dependency.station < self.station_configs.len(),
+                "Dependencia con estación inexistente: {}",
+                dependency.station
+            );
+            find(dependency.product)
+                .add_dependency(Arc::clone(find(dependency.depends_on)), dependency.station);
+        }
+
+        // Los generadores liberan los productos en orden de llegada; los que
+        // llegan en el mismo instante, en el orden de sus tiempos de llegada
+        products_by_source[0].sort_by_key(|product| (product.arrival_offset, slots[product.id - 1]));
+        products_by_source
+    }
+
+    /// Configura los canales de comunicación entre estaciones.
+    fn setup_channels(&self) -> (Vec<ChannelPair>, mpsc::Receiver<Arc<Product>>) {
+        let (collector_tx, collector_rx) = mpsc::channel::<Arc<Product>>();
+        
+        let mut channels = Vec::new();
+        for i in 0..self.station_configs.len() {
+            let (tx, rx) = self.channel_backend.station_channel();
+            // El next_sender se configura después de crear todos los canales
+            let next_sender = None;
+            let collector = if i + 1 == self.station_configs.len() {
+                Some(collector_tx.clone())
+            } else {
+                None
+            };
+            
+            channels.push(ChannelPair {
+                sender: tx,
+                receiver: rx,
+                next_sender,
+                collector,
+            });
+        }
+
+        // Configurar next_sender para cada canal
+        for i in 0..(channels.len() - 1) {
+            channels[i].next_sender = Some(channels[i + 1].sender.clone());
+        }
+
+        (channels, collector_rx)
+    }
+
+    /// Lanza todos los hilos de las estaciones de trabajo.
+    /// 
+    /// Cada estación se ejecuta dentro de `catch_unwind`: si hace panic, sus
+    /// canales se cierran durante el desenrollado, lo que propaga el apagado
+    /// al resto de la línea en lugar de dejarla bloqueada.
+    /// 
+    /// # Returns
+    /// 
+    /// Los handles de las estaciones y el canal de entrada de la primera
+    /// estación. Solo se conserva ese canal para que el cierre de cualquier
+    /// estación desconecte a las siguientes.
+    /// 
+    /// Si hay un contador de retornos pendientes, la primera estación lo
+    /// recibe y la última obtiene un canal de recirculación hacia la
+    /// primera. Ese canal cierra un ciclo, por lo que con canales acotados
+    /// una línea reentrante saturada puede bloquearse por completo.
+    /// 
+    /// Cada estación recibe además su canal de comandos y el contador de
+    /// productos descartados de la línea, que comparte con el colector.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si no hay estaciones configuradas
+    fn launch_stations(
+        &self,
+        channels: Vec<ChannelPair>,
+        commands: Vec<CommandReceiver>,
+        probes: &[Arc<StationProbe>],
+        context: &LineContext,
+    ) -> (Vec<StationHandle>, StationSender) {
+        let LineContext { start_time, stop_signal, pending_returns, replacements, discarded } = context;
+        let pending_returns = pending_returns.as_ref();
+        let mut handles = Vec::new();
+        let mut first_sender = None;
+        let last_index = self.station_configs.len().saturating_sub(1);
+        let mut loopback = pending_returns
+            .and(channels.first())
+            .map(|channel| channel.sender.clone());
+        let pools = self.resource_pools();
+        let jockeying = JockeyBoard::new(self.jockeying, &self.station_configs);
+        let mut schedule = self.reconfiguration_schedule();
+        
+        let senders: Vec<StationSender> = channels.iter().map(|channel| channel.sender.clone()).collect();
+        let stations = self.station_configs.iter().zip(channels).zip(commands).enumerate();
+        for (index, ((config, channel), commands)) in stations {
+            let mut station = self
+                .station_observers
+                .iter()
+                .fold(
+                    Station::new(index, config.clone(), self.station_algorithm(index)),
+                    |station, observer| station.with_observer(Arc::clone(observer)),
+                )
+                .with_start_time(*start_time)
+                .with_shutdown_mode(self.shutdown_mode)
+                .with_discipline(self.queue_discipline(index))
+                .with_impatience(self.balking_thresholds.get(&index).copied(), self.patience)
+                .with_ramp_up(self.ramp_ups.get(&index).copied())
+                .with_warm_up(self.warm_ups.get(&index).copied())
+                .with_timer_jitter(self.timer_jitters.get(&index).copied())
+                .with_replacements(replacements.clone())
+                .with_stop_signal(stop_signal.clone())
+                .with_work_model(self.work_model.clone().unwrap_or_else(|| Arc::new(SleepWork)))
+                .with_resources(self.station_resources(&pools, index))
+                .with_jockeying(jockeying.clone())
+                .with_commands(commands, Arc::clone(discarded), pending_returns.cloned())
+                .with_scheduled_commands(std::mem::take(&mut schedule[index]));
+            if let Some(probe) = probes.get(index) {
+                station = station.with_probe(Arc::clone(probe));
+            }
+            for target in self.branch_targets(index) {
+                station = station.with_branch(target, senders[target].clone());
+            }
+            if let (0, Some(pending_returns)) = (index, pending_returns) {
+                station = station.with_pending_returns(Arc::clone(pending_returns));
+            }
+            if index == last_index {
+                if let Some(loopback) = loopback.take() {
+                    station = station.with_loopback(loopback);
+                }
+            }
+            let ChannelPair { sender, receiver, next_sender, collector } = channel;
+            if index == 0 {
+                first_sender = Some(sender);
+            }
+            
+            let calculator = self.metrics_calculator;
+            let handle = thread::spawn(move || {
+                let _clock_format = calculator.enter_clock();
+                panic::catch_unwind(AssertUnwindSafe(move || {
+                    station.run(receiver, next_sender, collector)
+                }))
+                .map_err(|payload| (Instant::now(), panic_message(payload.as_ref())))
+            });
+            
+            handles.push(StationHandle {
+                handle,
+                name: config.name.clone(),
+            });
+        }
+        
+        let first_sender = first_sender.expect("Debe haber al menos una estación");
+        (handles, first_sender)
+    }
+
+    /// Lanza un generador de productos por cada fuente de llegadas.
+    /// 
+    /// El primer grupo de llegadas corresponde a la fuente principal, cuyo
+    /// generador también atiende los productos inyectados. Además de los
+    /// hilos, devuelve el contador de productos liberados en la línea.
+    fn launch_generators(
+        &self,
+        sender: StationSender,
+        arrivals_by_source: Vec<Arrivals>,
+        injections: mpsc::Receiver<ProductSpec>,
+        registry: ProductRegistry,
+        context: &LineContext,
+    ) -> (Vec<thread::JoinHandle<()>>, Arc<AtomicUsize>) {
+        let admitted = Arc::new(AtomicUsize::new(0));
+        let active_generators = Arc::new(AtomicUsize::new(arrivals_by_source.len()));
+        let catalog = Arc::new(self.catalog.clone());
+        let names = std::iter::once("principal".to_string())
+            .chain(self.sources.iter().map(|source| source.name.clone()));
+        let mut injections = Some(injections);
+        
+        let handles = arrivals_by_source
+            .into_iter()
+            .zip(names)
+            .enumerate()
+            .map(|(idx, (arrivals, name))| {
+                let generator = ProductGenerator {
+                    name,
+                    sender: sender.clone(),
+                    registry: Arc::clone(&registry),
+                    admitted: Arc::clone(&admitted),
+                    station_configs: self.station_configs.clone(),
+                    catalog: Arc::clone(&catalog),
+                    start_time: context.start_time,
+                    active_generators: Arc::clone(&active_generators),
+                    stop_signal: context.stop_signal.clone(),
+                    pending_returns: context.pending_returns.clone(),
+                    replacements: context.replacements.clone(),
+                    primary: idx == 0,
+                };
+                let injections = injections.take();
+                let calculator = self.metrics_calculator;
+                thread::spawn(move || {
+                    let _clock_format = calculator.enter_clock();
+                    generator.run(arrivals, injections)
+                })
+            })
+            .collect();
+        (handles, admitted)
+    }
+}
+
+/// Estado de una ejecución que comparten las estaciones, los generadores y
+/// el colector.
+struct LineContext {
+    /// Momento de inicio de la simulación
+    start_time: Instant,
+    /// Señal de parada anticipada
+    stop_signal: StopSignal,
+    /// Productos que deben recircular (solo en líneas reentrantes)
+    pending_returns: Option<Arc<AtomicUsize>>,
+    /// Órdenes de reemplazo de los productos desechados
+    replacements: Option<Arc<ReplacementOrders>>,
+    /// Productos descartados de las colas, contados por las estaciones
+    discarded: Arc<AtomicUsize>,
+}
+
+/// Intervalo con el que los generadores revisan la señal de parada.
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Intervalo con el que el colector revisa los tiempos límite de progreso.
+const COLLECTOR_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Resultado de la recolección de productos completados.
+struct Collection {
+    /// Orden de finalización de los productos
+    completion_order: Vec<usize>,
+    /// Momento en que terminó la recolección
+    end_time: Instant,
+    /// Motivo y diagnóstico si la recolección se abortó por falta de progreso
+    stall: Option<(StallReason, Vec<StalledProduct>)>,
+    /// Diagnóstico si la recolección se abortó por un interbloqueo
+    deadlock: Option<DeadlockReport>,
+    /// Métricas de los productos recolectados, finalizadas al completar la
+    /// línea, por id
+    finished: HashMap<usize, ProductMetrics>,
+}
+
+/// Colector final de productos completados.
+/// 
+/// Además de registrar el orden de finalización, vigila que la línea siga
+/// progresando para que un producto perdido o un bloqueo se reporten como
+/// error en lugar de dejar la simulación esperando indefinidamente.
+struct Collector {
+    registry: ProductRegistry,
+    /// Productos liberados en la línea, contados por los generadores
+    admitted: Arc<AtomicUsize>,
+    /// Productos descartados de las colas, contados por las estaciones
+    discarded: Arc<AtomicUsize>,
+    station_configs: Vec<StationConfig>,
+    start_time: Instant,
+    completion_timeout: Option<Duration>,
+    watchdog_timeout: Option<Duration>,
+    deadlock_timeout: Option<Duration>,
+    /// Sondas de estado de las estaciones (vacío sin detección de interbloqueos)
+    probes: Vec<Arc<StationProbe>>,
+    stop_condition: StopCondition,
+    stop_signal: StopSignal,
+    progress_callbacks: Vec<ProgressCallback>,
+    sinks: Vec<Arc<dyn Sink>>,
+    /// Suscriptores de [`SimulationHandle::metrics_stream`]
+    streams: MetricsStreams,
+    /// Reconfiguraciones en caliente, para anotar la época de cada producto
+    reconfigurations: Vec<Reconfiguration>,
+    /// Indica si alguna fuente de llegadas es infinita
+    open_loop: bool,
+    /// Órdenes de reemplazo, donde se descuentan los productos completados
+    replacements: Option<Arc<ReplacementOrders>>,
+}
+
+impl Collector {
+    /// Recolecta los productos completados en orden de finalización.
+    /// 
+    /// La recolección termina cuando la última estación se apaga y cierra
+    /// el canal del colector, por lo que no depende de conocer de antemano
+    /// el número total de productos. Si hay tiempos límite configurados,
+    /// también termina cuando ningún producto en proceso se completa dentro
+    /// del tiempo límite o cuando se supera la duración máxima.
+    /// 
+    /// Cuando se cumple la condición de parada, activa la señal de parada y
+    /// sigue recolectando hasta que la línea termina de detenerse. Al
+    /// terminar, por cualquier motivo, avisa a los destinos.
+    fn run(&self, collector_rx: mpsc::Receiver<Arc<Product>>) -> Collection {
+        let collection = self.collect(collector_rx);
+        for sink in &self.sinks {
+            sink.on_finish();
+        }
+        // Descartar los emisores cierra los flujos de métricas
+        lock_recover(&self.streams).take();
+        collection
+    }
+
+    /// Recibe los productos completados hasta que la línea termina o se
+    /// declara un bloqueo.
+    fn collect(&self, collector_rx: mpsc::Receiver<Arc<Product>>) -> Collection {
+        let mut completion_order = Vec::new();
+        let mut finished = HashMap::new();
+        let monitored = self.completion_timeout.is_some()
+            || self.watchdog_timeout.is_some()
+            || self.deadlock_timeout.is_some()
+            || self.stop_condition.is_monitored();
+        let mut deadlock_watch = self.deadlock_timeout
+            .map(|timeout| DeadlockWatch::new(self.probes.clone(), timeout));
+        let mut last_progress = Instant::now();
+        let mut total_turnaround = Duration::ZERO;
+        
+        loop {
+            let received = if monitored {
+                collector_rx.recv_timeout(COLLECTOR_POLL_INTERVAL)
+            } else {
+                collector_rx.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected)
+            };
+
+            match received {
+                Ok(product) => {
+                    completion_order.push(product.id);
+                    if let Some(replacements) = &self.replacements {
+                        replacements.settle();
+                    }
+                    last_progress = Instant::now();
+                    total_turnaround += product.turnaround_time().unwrap_or_default();
+                    if let Some(metrics) = self.finalize(&product) {
+                        self.deliver(&metrics);
+                        finished.insert(product.id, metrics);
+                    }
+                    self.check_stop_condition(completion_order.len(), total_turnaround);
+                    self.report_progress(completion_order.len());
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    self.check_stop_condition(completion_order.len(), total_turnaround);
+
+                    let in_flight = self.in_flight(completion_order.len()) > 0;
+                    if !in_flight {
+                        // Sin productos en la línea no se espera progreso
+                        last_progress = Instant::now();
+                    }
+
+                    let stations = deadlock_watch.as_mut().and_then(|watch| watch.check(in_flight));
+                    if let (Some(stations), Some(watch)) = (stations, &deadlock_watch) {
+                        return Collection {
+                            completion_order,
+                            end_time: Instant::now(),
+                            stall: None,
+                            deadlock: Some(self.deadlock_report(watch.idle_interval(), stations)),
+                            finished,
+                        };
+                    }
+
+                    let reason = match (self.watchdog_timeout, self.completion_timeout) {
+                        (Some(limit), _) if self.start_time.elapsed() > limit => {
+                            Some(StallReason::WatchdogExpired(limit))
+                        }
+                        (_, Some(timeout)) if last_progress.elapsed() > timeout => {
+                            Some(StallReason::NoProgress(timeout))
+                        }
+                        _ => None,
+                    };
+
+                    if let Some(reason) = reason {
+                        // El diagnóstico recorre el registro, por lo que
+                        // solo se calcula al declarar el bloqueo
+                        return Collection {
+                            completion_order,
+                            end_time: Instant::now(),
+                            stall: Some((reason, self.in_flight_products())),
+                            deadlock: None,
+                            finished,
+                        };
+                    }
+                }
+            }
+        }
+        
+        Collection {
+            completion_order,
+            end_time: Instant::now(),
+            stall: None,
+            deadlock: None,
+            finished,
+        }
+    }
+
+    /// Calcula las métricas de un producto que completó la línea, con la
+    /// época de configuración en la que llegó.
+    /// 
+    /// El estado de sus visitas ya no cambia, por lo que estas métricas son
+    /// las definitivas y el cálculo final solo las agrega.
+    fn finalize(&self, product: &Arc<Product>) -> Option<ProductMetrics> {
+        let mut metrics = MetricsCalculator::new().calculate_product_metrics(product, &self.station_configs)?;
+        metrics.config_epoch = reload::epoch_at(&self.reconfigurations, metrics.arrival_time);
+        Some(metrics)
+    }
+
+    /// Entrega las métricas de un producto completado a los destinos y a
+    /// los flujos de métricas.
+    fn deliver(&self, metrics: &ProductMetrics) {
+        for sink in &self.sinks {
+            sink.on_product(metrics);
+        }
+        if let Some(senders) = lock_recover(&self.streams).as_mut() {
+            // Los suscriptores que descartaron su receptor se olvidan
+            senders.retain(|sender| sender.send(metrics.clone()).is_ok());
+        }
+    }
+
+    /// Publica el avance de la simulación a los callbacks registrados.
+    fn report_progress(&self, completed: usize) {
+        if self.progress_callbacks.is_empty() {
+            return;
+        }
+        // Se mide sobre el registro sin copiarlo y solo cuando corresponde,
+        // ya que la medición recorre todos los productos
+        let progress = {
+            let products = lock_recover(&self.registry);
+            if !Progress::is_due(completed, products.len()) {
+                return;
+            }
+            Progress::measure(&products, completed, self.start_time.elapsed(), self.open_loop)
+        };
+        for callback in &self.progress_callbacks {
+            callback(&progress);
+        }
+    }
+
+    /// Evalúa la condición de parada y activa la señal si se cumple.
+    fn check_stop_condition(&self, completed: usize, total_turnaround: Duration) {
+        if !self.stop_condition.is_monitored() || self.stop_signal.is_stop_requested() {
+            return;
+        }
+
+        let live = LiveMetrics {
+            elapsed: self.start_time.elapsed(),
+            completed,
+            admitted: self.admitted.load(Ordering::Relaxed),
+            in_flight: self.in_flight(completed),
+            average_turnaround: if completed > 0 {
+                total_turnaround / completed as u32
+            } else {
+                Duration::ZERO
+            },
+        };
+
+        if self.stop_condition.is_met(&live) {
+            println!(
+                "[INFO] Condición de parada cumplida ({} completados, {} en proceso)",
+                live.completed,
+                live.in_flight
+            );
+            self.stop_signal.request_stop();
+        }
+    }
+
+    /// Cuenta los productos que siguen en la línea: admitidos y no
+    /// completados ni descartados.
+    fn in_flight(&self, completed: usize) -> usize {
+        self.admitted
+            .load(Ordering::Relaxed)
+            .saturating_sub(completed + self.discarded.load(Ordering::Relaxed))
+    }
+
+    /// Arma el diagnóstico de un interbloqueo a partir del estado publicado
+    /// por las estaciones.
+    /// 
+    /// Los productos pendientes que no figuran en ninguna cola ni en servicio
+    /// siguen en el canal de entrada de la estación que deben visitar.
+    fn deadlock_report(&self, idle_interval: Duration, mut stations: Vec<StationSnapshot>) -> DeadlockReport {
+        let in_flight = self.in_flight_products();
+        for product in &in_flight {
+            // Un producto tomado por una estación ya apunta a su próxima visita
+            let taken = stations.iter().any(|station| {
+                station.in_service == Some(product.product_id)
+                    || station.queue.contains(&product.product_id)
+            });
+            if let (false, Some(station)) = (taken, stations.get_mut(product.station_index)) {
+                station.in_buffer.push(product.product_id);
+            }
+        }
+        DeadlockReport {
+            idle_interval,
+            elapsed: self.start_time.elapsed(),
+            stations,
+            in_flight,
+        }
+    }
+
+    /// Determina qué estación retiene cada producto admitido y no completado.
+    fn in_flight_products(&self) -> Vec<StalledProduct> {
+        let registry = lock_recover(&self.registry);
+        registry
+            .iter()
+            .filter(|product| product.get_arrival_time().is_some() && !product.is_completed())
+            .filter_map(|product| {
+                let index = product.current_station()?;
+                Some(StalledProduct {
+                    product_id: product.id,
+                    station_index: index,
+                    station: self.station_configs[index].name.clone(),
+                    remaining: lock_recover(product.current_state()).remaining,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Registro compartido de todos los productos admitidos en la simulación.
+type ProductRegistry = Arc<Mutex<Vec<Arc<Product>>>>;
+
+/// Emisores de los flujos de métricas en vivo; `None` cuando el colector
+/// terminó y ya no se completarán más productos.
+type MetricsStreams = Arc<Mutex<Option<Vec<mpsc::Sender<ProductMetrics>>>>>;
+
+/// Handle de una simulación en ejecución.
+/// 
+/// Se obtiene con [`Simulation::start`] y permite inyectar productos
+/// mientras la línea está operando, además de esperar los resultados.
+pub struct SimulationHandle {
+    /// Canal para enviar productos inyectados al generador
+    injector: Option<mpsc::Sender<ProductSpec>>,
+    /// Hilos de los generadores de productos (uno por fuente)
+    generator_handles: Vec<thread::JoinHandle<()>>,
+    /// Hilo del colector final
+    collector_handle: thread::JoinHandle<Collection>,
+    /// Hilos de las estaciones de trabajo
+    station_handles: Vec<StationHandle>,
+    /// Todos los productos admitidos (iniciales e inyectados)
+    registry: ProductRegistry,
+    /// Configuraciones de las estaciones
+    station_configs: Vec<StationConfig>,
+    /// Momento de inicio de la simulación
+    start_time: Instant,
+    /// Período de calentamiento para las estadísticas de estado estable
+    warmup: Option<Duration>,
+    /// Períodos en los que se segmenta el reporte
+    report_periods: Vec<ArrivalPeriod>,
+    /// Objetivos de servicio evaluados al finalizar
+    slas: Vec<Sla>,
+    /// Takt time contra el que se compara el ritmo de salida
+    takt_time: Option<Duration>,
+    /// Recursos compartidos cuyas unidades se reportan al finalizar
+    resources: Vec<SharedResource>,
+    /// Política de cambios de cola, cuyo efecto se resume al finalizar
+    jockeying: JockeyPolicy,
+    /// Plan del planificador global, si se configuró
+    release_plan: Option<ReleasePlan>,
+    /// Reconfiguraciones en caliente que segmentan las métricas en épocas
+    reconfigurations: Vec<Reconfiguration>,
+    /// Tipo de canal usado entre estaciones
+    channel_backend: ChannelBackend,
+    /// Calculadora de las métricas finales, con el formato de los tiempos
+    /// de los registros y la longitud de tick de la simulación
+    metrics_calculator: MetricsCalculator,
+    /// Señal de parada compartida con el colector, los generadores y las estaciones
+    stop_signal: StopSignal,
+    /// Indica si la simulación fue cancelada con [`SimulationHandle::cancel`]
+    cancelled: AtomicBool,
+    /// Suscriptores de [`SimulationHandle::metrics_stream`], compartidos con el colector
+    streams: MetricsStreams,
+    /// Canales de comandos de las estaciones
+    control: LineControl,
+    /// Productos descartados de las colas por comandos
+    discarded: Arc<AtomicUsize>,
+}
+
+impl SimulationHandle {
+    /// Inyecta un nuevo producto en la línea mientras la simulación está en curso.
+    /// 
+    /// El producto llega a la primera estación de inmediato y recibe el
+    /// siguiente identificador disponible. Su prioridad determina su posición
+    /// en las colas de las estaciones.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `spec` - Especificación del producto a inyectar
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(())` si el producto fue aceptado, o `Err` con la especificación
+    /// (en una caja, para que el error sea pequeño) si el generador ya no
+    /// admite productos
+    pub fn inject_product(&self, spec: ProductSpec) -> Result<(), mpsc::SendError<Box<ProductSpec>>> {
+        match &self.injector {
+            Some(injector) => injector.send(spec).map_err(|mpsc::SendError(spec)| mpsc::SendError(Box::new(spec))),
+            None => Err(mpsc::SendError(Box::new(spec))),
+        }
+    }
+
+    /// Cierra la admisión de productos inyectados sin esperar a que la
+    /// línea termine.
+    /// 
+    /// Mientras la admisión está abierta el generador principal sigue
+    /// activo, por lo que la simulación no puede terminar. Cerrarla permite
+    /// consultar [`SimulationHandle::is_finished`] y llamar a
+    /// [`SimulationHandle::wait`] solo cuando ya no bloqueará.
+    pub fn close_injection(&mut self) {
+        self.injector.take();
+    }
+
+    /// Cancela la simulación en curso de forma ordenada.
+    /// 
+    /// Los generadores dejan de admitir productos y cada estación se detiene
+    /// al terminar la porción que está procesando, abandonando su cola.
+    /// [`SimulationHandle::wait`] retorna entonces las métricas de los
+    /// productos completados, y los que quedaron en la línea aparecen en
+    /// `incomplete_products`. Puede llamarse desde otro hilo.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Horno", Duration::from_millis(50))];
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 10]);
+    /// let handle = simulation.start();
+    /// std::thread::sleep(Duration::from_millis(75));
+    /// handle.cancel();
+    /// let metrics = handle.wait().unwrap();
+    /// assert!(metrics.products.len() < 10);
+    /// assert!(!metrics.incomplete_products.is_empty());
+    /// ```
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.stop_signal.request_stop();
+    }
+
+    /// Indica si la línea ya terminó de procesar, de modo que
+    /// [`SimulationHandle::wait`] retornará sin bloquear.
+    pub fn is_finished(&self) -> bool {
+        self.collector_handle.is_finished()
+    }
+
+    /// Obtiene el progreso de la simulación en curso.
+    /// 
+    /// # Returns
+    /// 
+    /// Las métricas en vivo de la simulación: productos admitidos,
+    /// completados y en proceso, y el turnaround promedio hasta el momento
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+    /// let mut handle = simulation.start();
+    /// handle.close_injection();
+    /// while !handle.is_finished() {
+    ///     std::thread::sleep(Duration::from_millis(5));
+    /// }
+    /// assert_eq!(handle.progress().completed, 2);
+    /// # handle.wait().unwrap();
+    /// ```
+    pub fn progress(&self) -> LiveMetrics {
+        let registry = lock_recover(&self.registry);
+        let admitted = registry
+            .iter()
+            .filter(|product| product.get_arrival_time().is_some())
+            .count();
+        let turnarounds: Vec<Duration> = registry
+            .iter()
+            .filter(|product| product.is_completed() && product.loss().is_none())
+            .filter_map(|product| product.turnaround_time())
+            .collect();
+        let completed = turnarounds.len();
+
+        LiveMetrics {
+            elapsed: self.start_time.elapsed(),
+            completed,
+            admitted,
+            in_flight: admitted.saturating_sub(completed + self.discarded.load(Ordering::Relaxed)),
+            average_turnaround: if completed > 0 {
+                turnarounds.iter().sum::<Duration>() / completed as u32
+            } else {
+                Duration::ZERO
+            },
+        }
+    }
+
+    /// Suscribe un receptor a las métricas de los productos a medida que
+    /// completan la línea.
+    /// 
+    /// Cada producto que termine después de la suscripción se envía por el
+    /// canal en orden de finalización, con las mismas métricas que reciben
+    /// los [`Sink`] registrados, por lo que el consumidor puede actualizar
+    /// promedios o gráficos sin esperar el resultado final. El canal se
+    /// cierra cuando el colector termina; si ya terminó, el receptor no
+    /// recibe nada. Para no perder los productos que completan antes de
+    /// suscribirse, registre un [`ChannelSink`](crate::ChannelSink) antes
+    /// de iniciar la simulación.
+    /// 
+    /// # Returns
+    /// 
+    /// Receptor de las métricas de cada producto completado
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    /// let mut handle = simulation.start();
+    /// let stream = handle.metrics_stream();
+    /// handle.close_injection();
+    /// 
+    /// // Turnaround promedio actualizado con cada producto completado
+    /// let (mut completed, mut total) = (0, Duration::ZERO);
+    /// for product in stream {
+    ///     completed += 1;
+    ///     total += product.turnaround_time;
+    ///     println!("Promedio parcial: {:?}", total / completed);
+    /// }
+    /// assert_eq!(completed, 3);
+    /// handle.wait().expect("La simulación falló");
+    /// ```
+    pub fn metrics_stream(&self) -> mpsc::Receiver<ProductMetrics> {
+        let (sender, receiver) = mpsc::channel();
+        if let Some(senders) = lock_recover(&self.streams).as_mut() {
+            senders.push(sender);
+        }
+        receiver
+    }
+
+    /// Obtiene el control de las estaciones de la línea en ejecución.
+    /// 
+    /// El control envía comandos ([`StationCommand`]) que cambian el
+    /// quantum o el tiempo de procesamiento de una estación, la pausan o
+    /// vacían su cola sin detener la simulación.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationCommand, StationConfig};
+    /// 
+    /// let stations = vec![
+    ///     StationConfig::new("Corte", Duration::from_millis(10)),
+    ///     StationConfig::new("Ensamblaje", Duration::from_millis(40)),
+    /// ];
+    /// let rr = SchedulingAlgorithm::round_robin(Duration::from_millis(20));
+    /// let simulation = Simulation::with_config(stations, rr, vec![Duration::ZERO; 3]);
+    /// let handle = simulation.start();
+    /// 
+    /// // Reducir a la mitad el quantum del ensamblaje en plena ejecución
+    /// let control = handle.control();
+    /// let assembly = control.station_index("Ensamblaje").unwrap();
+    /// control.send(assembly, StationCommand::SetQuantum(Duration::from_millis(10))).unwrap();
+    /// 
+    /// let metrics = handle.wait().unwrap();
+    /// assert_eq!(metrics.products.len(), 3);
+    /// ```
+    pub fn control(&self) -> LineControl {
+        self.control.clone()
+    }
+
+    /// Cierra la admisión de productos, espera a que la línea termine y
+    /// calcula las métricas finales.
+    /// 
+    /// Si alguna estación o el generador hizo panic, la línea se apaga de
+    /// forma ordenada y se retorna el error con las métricas parciales.
+    /// Cuando fallan varias estaciones se reporta la primera en fallar,
+    /// ya que las demás suelen ser consecuencia de ella.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(SimulationMetrics)` con todos los resultados y estadísticas, o
+    /// `Err(SimulationError)` describiendo la falla
+    pub fn wait(mut self) -> Result<SimulationMetrics, SimulationError> {
+        let _clock_format = self.metrics_calculator.enter_clock();
+        // Sin inyector, el generador termina al agotar las llegadas programadas
+        self.injector.take();
+
+        let collection = self.collector_handle.join()
+            .expect("El colector falló");
+        let end_time = collection.end_time;
+
+        // Ante un interbloqueo los hilos nunca terminan: se reportan métricas
+        // parciales junto con el estado de cada estación
+        if let Some(report) = collection.deadlock {
+            let products = lock_recover(&self.registry).clone();
+            let metrics = self.metrics_calculator.aggregate_simulation_metrics(
+                &products,
+                collection.finished,
+                &self.station_configs,
+                self.start_time,
+                end_time,
+                collection.completion_order,
+            );
+            println!("\n[ERROR] {}", report);
+            return Err(SimulationError::Deadlocked {
+                report: Box::new(report),
+                metrics: Box::new(metrics),
+            });
+        }
+
+        // Ante un bloqueo no se esperan los hilos: se reportan métricas parciales
+        if let Some((reason, in_flight)) = collection.stall {
+            let products = lock_recover(&self.registry).clone();
+            let metrics = self.metrics_calculator.aggregate_simulation_metrics(
+                &products,
+                collection.finished,
+                &self.station_configs,
+                self.start_time,
+                end_time,
+                collection.completion_order,
+            );
+            let error = SimulationError::Stalled {
+                reason,
+                in_flight,
+                metrics: Box::new(metrics),
+            };
+            println!("\n[ERROR] {}", error);
+            return Err(error);
+        }
+        let completion_order = collection.completion_order;
+        let finished = collection.finished;
+
+        // Esperar a que terminen todos los hilos
+        let generator_failure = self.generator_handles
+            .into_iter()
+            .filter_map(|handle| handle.join().err())
+            .next();
+
+        let mut station_reports = Vec::new();
+        let mut station_failure: Option<(Instant, String, String)> = None;
+        for handle_info in self.station_handles {
+            let result = handle_info.handle.join()
+                .unwrap_or_else(|payload| Err((Instant::now(), panic_message(payload.as_ref()))));
+            match result {
+                Ok(report) => station_reports.push(report),
+                Err((failed_at, cause)) => {
+                    let is_first = station_failure
+                        .as_ref()
+                        .is_none_or(|(first_at, _, _)| failed_at < *first_at);
+                    if is_first {
+                        station_failure = Some((failed_at, handle_info.name, cause));
+                    }
+                }
+            }
+        }
+
+        let products = lock_recover(&self.registry).clone();
+
+        // Calcular métricas (parciales si hubo fallas)
+        let mut metrics = self.metrics_calculator.aggregate_simulation_metrics(
+            &products,
+            finished,
+            &self.station_configs,
+            self.start_time,
+            end_time,
+            completion_order,
+        );
+        metrics.station_reports = station_reports;
+        metrics.steady_state = self.warmup
+            .map(|warmup| MetricsCalculator::steady_state_metrics(&metrics, warmup));
+        metrics.synchronization = self.channel_backend.to_string();
+        metrics.periods = MetricsCalculator::period_metrics(&metrics, &self.report_periods);
+        reload::annotate_epochs(&mut metrics, &self.reconfigurations);
+        metrics.sla_results = self.slas.iter().map(|sla| sla.evaluate(&metrics)).collect();
+        metrics.takt = self.takt_time
+            .map(|takt_time| TaktReport::analyze(&metrics, takt_time, &self.report_periods));
+        metrics.resource_units = ResourceUnitReport::analyze(&metrics, &self.resources);
+        metrics.jockeying = self.jockeying
+            .is_enabled()
+            .then(|| JockeyReport::analyze(&metrics, self.jockeying));
+        metrics.release_plan = self.release_plan;
+
+        if let Some((_, station, cause)) = station_failure {
+            println!("\n[ERROR] La estación '{}' falló: {}", station, cause);
+            return Err(SimulationError::StationPanicked {
+                station,
+                cause,
+                metrics: Box::new(metrics),
+            });
+        }
+
+        if let Some(payload) = generator_failure {
+            let cause = panic_message(payload.as_ref());
+            println!("\n[ERROR] El generador falló: {}", cause);
+            return Err(SimulationError::GeneratorPanicked {
+                cause,
+                metrics: Box::new(metrics),
+            });
+        }
+
+        if self.cancelled.load(Ordering::SeqCst) {
+            println!("\n=== Simulación cancelada ===");
+        } else {
+            println!("\n=== Simulación completada ===");
+        }
+        println!("Duración total: {}", 
+            MetricsCalculator::format_duration(end_time.duration_since(self.start_time)));
+
+        Ok(metrics)
+    }
+}
+
+/// Llegada pendiente de un generador.
+enum PendingArrival {
+    /// Producto creado al inicio de la simulación
+    Scheduled(Arc<Product>),
+    /// Producto de una fuente infinita que se crea al llegar
+    Generated { offset: Duration, spec: ProductSpec },
+}
+
+impl PendingArrival {
+    /// Tiempo de llegada relativo al inicio de la simulación.
+    fn offset(&self) -> Duration {
+        match self {
+            Self::Scheduled(product) => product.arrival_offset,
+            Self::Generated { offset, .. } => *offset,
+        }
+    }
+}
+
+/// Secuencia de llegadas de una fuente, posiblemente infinita.
+type Arrivals = Box<dyn Iterator<Item = PendingArrival> + Send>;
+
+/// Generador de productos que respeta los tiempos de llegada simulados.
+/// 
+/// Cada fuente de llegadas tiene su propio generador. El generador de la
+/// fuente principal atiende además los productos inyectados a través del
+/// handle de la simulación.
+struct ProductGenerator {
+    name: String,
+    sender: StationSender,
+    registry: ProductRegistry,
+    admitted: Arc<AtomicUsize>,
+    station_configs: Vec<StationConfig>,
+    catalog: Arc<ProductCatalog>,
+    start_time: Instant,
+    active_generators: Arc<AtomicUsize>,
+    stop_signal: StopSignal,
+    /// Retornos pendientes de la línea, si hay rutas reentrantes
+    pending_returns: Option<Arc<AtomicUsize>>,
+    /// Órdenes de reemplazo de los productos desechados, si se reponen
+    replacements: Option<Arc<ReplacementOrders>>,
+    /// Indica si es el generador de la fuente principal, que atiende los
+    /// productos inyectados y los reemplazos
+    primary: bool,
+}
+
+impl ProductGenerator {
+    /// Ejecuta el ciclo de generación de productos.
+    /// 
+    /// Genera productos respetando sus tiempos de llegada simulados y, mientras
+    /// espera la siguiente llegada, admite productos inyectados si tiene un
+    /// canal de inyección. Cuando se agotan sus llegadas programadas (y se
+    /// cierra el canal de inyección), finaliza; el último generador activo
+    /// envía la señal de apagado a la primera estación.
+    fn run(&self, arrivals: Arrivals, injections: Option<mpsc::Receiver<ProductSpec>>) {
+        match arrivals.size_hint() {
+            (_, Some(count)) => println!(
+                "[GENERADOR:{}] Iniciando generación de {} productos",
+                self.name,
+                count
+            ),
+            (_, None) => println!(
+                "[GENERADOR:{}] Iniciando generación ilimitada de productos",
+                self.name
+            ),
+        }
+        
+        let mut pending = arrivals.peekable();
+        let mut injections = injections;
+
+        loop {
+            if self.stop_signal.is_stop_requested() {
+                self.active_generators.fetch_sub(1, Ordering::SeqCst);
+                println!("[GENERADOR:{}] Generación detenida", self.name);
+                return;
+            }
+
+            // Los productos desechados se reponen en cuanto se publican
+            if let Some(replacements) = self.replacements.as_ref().filter(|_| self.primary) {
+                while let Some(parent) = replacements.next() {
+                    self.replace(replacements, &parent);
+                }
+            }
+
+            match (pending.peek(), &injections) {
+                (Some(arrival), _) => {
+                    // Esperar hasta el momento de llegada simulado, revisando
+                    // periódicamente si se solicitó detener la simulación
+                    let target_time = self.start_time + arrival.offset();
+                    let now = Instant::now();
+
+                    if now >= target_time {
+                        let product = match pending.next().expect("Llegada pendiente") {
+                            PendingArrival::Scheduled(product) => product,
+                            PendingArrival::Generated { offset, spec } => self.admit(offset, &spec),
+                        };
+                        self.release(product);
+                        continue;
+                    }
+
+                    let wait_time = (target_time - now).min(STOP_POLL_INTERVAL);
+                    if let Some(receiver) = &injections {
+                        match receiver.recv_timeout(wait_time) {
+                            Ok(spec) => self.inject(spec),
+                            Err(mpsc::RecvTimeoutError::Timeout) => {}
+                            Err(mpsc::RecvTimeoutError::Disconnected) => injections = None,
+                        }
+                    } else {
+                        self.idle(wait_time);
+                    }
+                }
+                (None, Some(receiver)) => match receiver.recv_timeout(STOP_POLL_INTERVAL) {
+                    Ok(spec) => self.inject(spec),
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => injections = None,
+                },
+                (None, None) if self.awaiting_replacements() => self.idle(STOP_POLL_INTERVAL),
+                (None, None) => break,
+            }
+        }
+
+        // Solo el último generador en terminar envía la señal de apagado
+        if self.active_generators.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.sender
+                .send(Message::Shutdown)
+                .expect("No se pudo enviar señal de apagado");
+            println!("[GENERADOR:{}] Generación completada, señal de apagado enviada", self.name);
+        } else {
+            println!("[GENERADOR:{}] Generación completada", self.name);
+            // El generador principal puede estar esperando a que termine este
+            if let Some(replacements) = &self.replacements {
+                replacements.wake();
+            }
+        }
+    }
+
+    /// Crea y libera un producto inyectado durante la simulación.
+    fn inject(&self, spec: ProductSpec) {
+        let product = self.admit(self.start_time.elapsed(), &spec);
+
+        println!(
+            "[GENERADOR:{}] Producto {:02} inyectado (prioridad {})",
+            self.name,
+            product.id,
+            product.priority
+        );
+        self.release(product);
+    }
+
+    /// Crea y libera el reemplazo de un producto desechado.
+    fn replace(&self, replacements: &ReplacementOrders, parent: &Product) {
+        let product = self.admit(self.start_time.elapsed(), &parent.spec());
+        replacements.link(parent, &product);
+
+        println!(
+            "[GENERADOR:{}] Producto {:02} reemplaza al producto {:02} desechado",
+            self.name,
+            product.id,
+            parent.id
+        );
+        self.release(product);
+    }
+
+    /// Indica si el generador principal debe seguir activo porque aún
+    /// pueden desecharse productos que habría que reponer: quedan productos
+    /// en la línea u otros generadores por liberar los suyos.
+    fn awaiting_replacements(&self) -> bool {
+        self.primary
+            && self.replacements.as_ref().is_some_and(|replacements| {
+                !replacements.is_settled() || self.active_generators.load(Ordering::SeqCst) > 1
+            })
+    }
+
+    /// Espera hasta `timeout`; el generador principal despierta antes si se
+    /// publica un producto desechado.
+    fn idle(&self, timeout: Duration) {
+        match self.replacements.as_ref().filter(|_| self.primary) {
+            Some(replacements) => replacements.wait(timeout),
+            None => thread::sleep(timeout),
+        }
+    }
+
+    /// Crea un producto con el siguiente ID disponible y lo agrega al registro.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la especificación referencia un tipo no registrado
+    fn admit(&self, offset: Duration, spec: &ProductSpec) -> Arc<Product> {
+        let mut registry = lock_recover(&self.registry);
+        let product = self.catalog.create_product(registry.len() + 1, offset, spec, &self.station_configs);
+        registry.push(Arc::clone(&product));
+        product
+    }
+
+    /// Registra la llegada de un producto y lo envía a la primera estación.
+    fn release(&self, product: Arc<Product>) {
+        // Registrar llegada real
+        let arrival_time = self.start_time.elapsed();
+        product.set_arrival_time(arrival_time);
+        self.admitted.fetch_add(1, Ordering::Relaxed);
+        if let Some(replacements) = &self.replacements {
+            replacements.admit();
+        }
+        // Los retornos se cuentan antes de liberar el producto, para que la
+        // primera estación nunca finalice con un retorno en camino
+        if let Some(pending_returns) = &self.pending_returns {
+            pending_returns.fetch_add(product.reentries(), Ordering::SeqCst);
+        }
+
+        // Inicializar estado en la primera estación (si su ruta comienza ahí)
+        if product.current_station() == Some(0) {
+            let mut station_state = lock_recover(product.visit_state(0));
+            station_state.queue_entry = Some(arrival_time);
+            if station_state.remaining.is_zero() {
+                station_state.remaining = product.processing_time(0);
+            }
+        }
+
+        println!(
+            "[GENERADOR:{}] Producto {:02} disponible en t={}",
+            self.name,
+            product.id,
+            MetricsCalculator::format_duration(arrival_time)
+        );
+
+        // Enviar producto a la primera estación (que puede haber finalizado
+        // si se solicitó una parada anticipada)
+        if self.sender.send(Message::Product(product)).is_err()
+            && !self.stop_signal.is_stop_requested()
+        {
+            panic!("No se pudo enviar producto a la primera estación");
+        }
+    }
+}
+
+/// Información de canales para una estación.
 struct ChannelPair {
-    sender: mpsc::Sender<Message>,
-    receiver: mpsc::Receiver<Message>,
-    next_sender: Option<mpsc::Sender<Message>>,
+    sender: StationSender,
+    receiver: StationReceiver,
+    next_sender: Option<StationSender>,
     collector: Option<mpsc::Sender<Arc<Product>>>,
 }
 
 /// Handle para controlar una estación.
 struct StationHandle {
-    handle: thread::JoinHandle<()>,
-    sender: mpsc::Sender<Message>,
-}
\ No newline at end of file
+    handle: thread::JoinHandle<Result<StationReport, (Instant, String)>>,
+    name: String,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::VecSink;
+
+    #[test]
+    fn test_wait_aggregates_metrics_finalized_by_the_collector() {
+        let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+        let mut simulation = Simulation::with_config(
+            stations,
+            SchedulingAlgorithm::round_robin(Duration::from_millis(4)),
+            vec![Duration::ZERO; 2],
+        );
+        let collected = VecSink::new();
+        simulation.clear_sinks().add_sink(collected.clone());
+        let mut handle = simulation.start();
+        handle.close_injection();
+        while !handle.is_finished() {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        // Después de la recolección se borran los intervalos de las visitas:
+        // las métricas finales no vuelven a leerlos
+        for product in lock_recover(&handle.registry).iter() {
+            lock_recover(product.visit_state(0)).slices.clear();
+        }
+        let metrics = handle.wait().expect("La simulación debe completarse");
+
+        let delivered = collected.products();
+        assert_eq!(metrics.products.len(), 2);
+        for product in &metrics.products {
+            let finished = delivered
+                .iter()
+                .find(|finished| finished.product_id == product.product_id)
+                .expect("El destino recibió el producto");
+            assert_eq!(product.slices.len(), 3);
+            assert_eq!(product.slices, finished.slices);
+        }
+    }
+}
@@ -0,0 +1,1166 @@
+//! # Módulo de Simulación Asíncrona
+//! 
+//! Backend alternativo (feature `async`) en el que las estaciones, los
+//! generadores y el colector son tareas asíncronas que se comunican por
+//! canales `tokio::sync::mpsc`, en lugar de un hilo del sistema operativo
+//! por estación. Esto permite simular líneas con miles de estaciones o
+//! productos sin crear miles de hilos.
+//! 
+//! En tiempo real las tareas se ejecutan en un runtime de tokio: el de un
+//! solo hilo ([`Simulation::run_async`]) o el multihilo con la cantidad de
+//! hilos indicada ([`Simulation::run_pooled`]), y los tiempos de
+//! procesamiento se esperan con `tokio::time::sleep_until`. En el segundo
+//! caso cada estación sigue siendo una máquina de estados que cede el
+//! control al esperar un producto o un temporizador, y cualquier hilo libre
+//! del runtime ejecuta el siguiente paso de la estación que esté lista.
+//! 
+//! Las estaciones reutilizan la misma lógica de planificación y
+//! contabilidad que el backend con hilos, por lo que las métricas
+//! resultantes tienen exactamente la misma forma.
+//! 
+//! Limitaciones respecto al backend con hilos: no admite inyección de
+//! productos, condiciones de parada distintas de
+//! [`StopCondition::AllCompleted`](crate::StopCondition::AllCompleted),
+//! tiempos límite, fuentes de llegada infinitas, cambios de cola, canales
+//! de estación acotados ni modelos de trabajo, porque el procesamiento se
+//! simula siempre con temporizadores. Una simulación que configure alguna
+//! de esas opciones se rechaza antes de empezar con
+//! [`ConfigError::UnsupportedByAsyncBackend`], que nombra la opción. Las
+//! estaciones que esperan un recurso compartido lo consultan periódicamente,
+//! por lo que esa espera se mide con la granularidad de ese intervalo.
+//! 
+//! El modo de tiempo virtual ([`Simulation::run_virtual`]) usa las mismas
+//! tareas y canales, pero no el runtime de tokio: las ejecuta un
+//! planificador mínimo en el hilo actual que, cuando ninguna tarea puede
+//! avanzar, adelanta un [`VirtualClock`] hasta el próximo temporizador. Así
+//! el tiempo real de ejecución solo refleja el costo del motor, y los
+//! tiempos de las métricas son exactos y reproducibles. No se usa el reloj
+//! pausado de tokio porque sus temporizadores tienen resolución de un
+//! milisegundo (dos eventos dentro del mismo milisegundo podrían atenderse
+//! en otro orden, y las métricas y las trazas de referencia dejarían de ser
+//! exactas) y porque depende del reloj del sistema, que no existe en
+//! `wasm32`. Los temporizadores virtuales que vencen en el mismo instante
+//! despiertan a sus tareas en el orden en que se registraron.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::future::{self, Future};
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use crate::channel::ChannelBackend;
+use crate::clock::{Clock, Instant, VirtualClock};
+use crate::error::{panic_message, ConfigError, SimulationError};
+use crate::metrics::{MetricsCalculator, ProductMetrics, SimulationMetrics};
+use crate::product::{lock_recover, Product};
+use crate::progress::Progress;
+use crate::reload;
+use crate::scrap::ReplacementOrders;
+use crate::simulation::Simulation;
+use crate::station::{enqueue, Message, Station, StationReport, DEPENDENCY_POLL_INTERVAL};
+use crate::status::StationStatus;
+use crate::storage::{StorageMode, VisitLog};
+use crate::resource::ResourceUnitReport;
+use crate::takt::TaktReport;
+
+impl Simulation {
+    /// Ejecuta la simulación con el backend asíncrono de un solo hilo.
+    /// 
+    /// Produce las mismas métricas que [`Simulation::run`], pero todas las
+    /// estaciones son tareas del runtime de tokio de un solo hilo.
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(SimulationMetrics)` con todos los resultados y estadísticas, o
+    /// `Err(SimulationError)` con las métricas parciales si alguna tarea
+    /// hizo panic, si la configuración no es válida o si usa opciones que
+    /// este backend no admite (ver las limitaciones del módulo)
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    /// let metrics = simulation.run_async().expect("La simulación falló");
+    /// assert_eq!(metrics.products.len(), 3);
+    /// ```
+    pub fn run_async(&self) -> Result<SimulationMetrics, SimulationError> {
+        self.check_async_config()?;
+        println!(
+            "=== Simulación asíncrona de línea de ensamblaje ({}) ===",
+            self.algorithm
+        );
+        self.run_on_executor(Clock::Real, 1)
+    }
+
+    /// Ejecuta la simulación con el backend asíncrono sobre un grupo de hilos.
+    /// 
+    /// Las estaciones son tareas del runtime multihilo de tokio que avanzan
+    /// de forma cooperativa: cada hilo del grupo toma la siguiente tarea lista (una
+    /// estación, un generador o el colector), la hace avanzar hasta que
+    /// espera un producto o un temporizador y pasa a la siguiente. Así una
+    /// línea con cientos de estaciones usa solo `workers` hilos del sistema
+    /// operativo y, a diferencia de [`Simulation::run_async`], las porciones
+    /// de estaciones distintas pueden ejecutarse en paralelo. Produce las
+    /// mismas métricas que [`Simulation::run`] y tiene las mismas
+    /// limitaciones que [`Simulation::run_async`].
+    /// 
+    /// # Arguments
+    /// 
+    /// * `workers` - Cantidad de hilos del grupo
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(SimulationMetrics)` con todos los resultados y estadísticas, o
+    /// `Err(SimulationError)` con las métricas parciales si alguna tarea
+    /// hizo panic, si la configuración no es válida, si usa opciones que
+    /// este backend no admite (ver las limitaciones del módulo) o si
+    /// `workers` es cero
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// // Doscientas estaciones atendidas por cuatro hilos
+    /// let stations = (0..200)
+    ///     .map(|index| StationConfig::new(&format!("Estación {}", index), Duration::from_millis(1)))
+    ///     .collect();
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    /// let metrics = simulation.run_pooled(4).expect("La simulación falló");
+    /// assert_eq!(metrics.products.len(), 3);
+    /// assert_eq!(metrics.station_reports.len(), 200);
+    /// ```
+    pub fn run_pooled(&self, workers: usize) -> Result<SimulationMetrics, SimulationError> {
+        self.check_async_config()?;
+        if workers == 0 {
+            return Err(self.invalid_config(ConfigError::NoWorkers));
+        }
+        println!(
+            "=== Simulación con grupo de {} hilos de línea de ensamblaje ({}) ===",
+            workers,
+            self.algorithm
+        );
+        self.run_on_executor(Clock::Real, workers)
+    }
+
+    /// Ejecuta la simulación en tiempo virtual.
+    /// 
+    /// Usa las tareas del backend asíncrono en el hilo actual, pero el reloj
+    /// avanza directamente al próximo evento en lugar de esperar en tiempo real.
+    /// Las métricas se expresan en tiempo virtual y no dependen de la carga
+    /// de la máquina, por lo que dos ejecuciones producen los mismos tiempos.
+    /// Tiene las mismas limitaciones que [`Simulation::run_async`].
+    /// 
+    /// # Returns
+    /// 
+    /// `Ok(SimulationMetrics)` con todos los resultados y estadísticas, o
+    /// `Err(SimulationError)` con las métricas parciales si alguna tarea
+    /// hizo panic, si la configuración no es válida o si usa opciones que
+    /// este backend no admite (ver las limitaciones del módulo)
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// // Una hora de procesamiento simulada de forma instantánea
+    /// let stations = vec![StationConfig::new("Horno", Duration::from_secs(1200))];
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    /// let metrics = simulation.run_virtual().expect("La simulación falló");
+    /// assert_eq!(metrics.total_simulation_time, Duration::from_secs(3600));
+    /// ```
+    pub fn run_virtual(&self) -> Result<SimulationMetrics, SimulationError> {
+        self.check_async_config()?;
+        println!(
+            "=== Simulación en tiempo virtual de línea de ensamblaje ({}) ===",
+            self.algorithm
+        );
+        self.run_on_executor(Clock::Virtual(VirtualClock::new()), 1)
+    }
+
+    /// Valida la configuración como [`Simulation::run`] y además rechaza las
+    /// opciones que el backend asíncrono no admite.
+    fn check_async_config(&self) -> Result<(), SimulationError> {
+        self.check_config()?;
+        let unsupported = [
+            (self.sources.iter().any(|source| source.process.is_infinite()), "fuentes de llegada infinitas"),
+            (self.jockeying.is_enabled(), "cambios de cola (jockeying)"),
+            (self.stop_condition.is_monitored(), "condiciones de parada distintas de AllCompleted"),
+            (self.completion_timeout.is_some(), "el tiempo límite sin completados (completion_timeout)"),
+            (self.watchdog_timeout.is_some(), "el tiempo límite de la simulación (watchdog_timeout)"),
+            (self.deadlock_timeout.is_some(), "la detección de interbloqueos (deadlock_timeout)"),
+            (self.channel_backend != ChannelBackend::Unbounded, "canales de estación acotados (channel_backend)"),
+            (self.work_model.is_some(), "modelos de trabajo (work_model)"),
+        ]
+        .into_iter()
+        .find_map(|(used, option)| used.then_some(option));
+        match unsupported {
+            Some(option) => Err(self.invalid_config(ConfigError::UnsupportedByAsyncBackend {
+                option: option.to_string(),
+            })),
+            None => Ok(()),
+        }
+    }
+
+    /// Ejecuta la simulación con el reloj y la cantidad de hilos indicados:
+    /// con el reloj real en un runtime de tokio y con el virtual en el
+    /// planificador de tiempo virtual, que usa un único hilo.
+    fn run_on_executor(&self, clock: Clock, workers: usize) -> Result<SimulationMetrics, SimulationError> {
+        let calculator = self.metrics_calculator;
+        let _clock_format = calculator.enter_clock();
+        let start_time = clock.now();
+        let products_by_source = self.create_products();
+        // Los reemplazos de productos desechados se agregan durante la ejecución
+        let products = Arc::new(Mutex::new(products_by_source.concat()));
+        let replacements = ReplacementOrders::for_policy(self.scrap_policy);
+
+        let timers = Timers::for_clock(&clock);
+        let mut tasks: Vec<(TaskKind, Task)> = Vec::new();
+        let station_count = self.station_configs.len();
+        let reports: Arc<Mutex<Vec<Option<StationReport>>>> =
+            Arc::new(Mutex::new(vec![None; station_count]));
+        let completion_order = Arc::new(Mutex::new(Vec::new()));
+        // Métricas finalizadas por el colector; en el modo compacto los
+        // intervalos se incorporan al final, por lo que no se guardan
+        let finished: Arc<Mutex<HashMap<usize, ProductMetrics>>> = Arc::default();
+        let finalize = self.storage_mode == StorageMode::Full;
+        // En el modo compacto cada estación agrega sus intervalos a un registro propio
+        let visit_logs: Vec<Option<Arc<Mutex<VisitLog>>>> = (0..station_count)
+            .map(|_| (self.storage_mode == StorageMode::Compact).then(Arc::default))
+            .collect();
+
+        // Canales entre estaciones: el de la estación i alimenta a la estación i
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..station_count).map(|_| unbounded_channel()).unzip();
+        let (collector_tx, mut collector_rx) = unbounded_channel::<Arc<Product>>();
+
+        // Si los productos pueden recircular, la última estación los envía a la
+        // primera, que no finaliza mientras queden retornos pendientes
+        let pending_returns = self
+            .may_recirculate()
+            .then(|| Arc::new(AtomicUsize::new(0)));
+        let mut loopback = pending_returns
+            .as_ref()
+            .and(senders.first())
+            .cloned();
+
+        // Tareas de las estaciones
+        let pools = self.resource_pools();
+        let mut schedule = self.reconfiguration_schedule();
+        let mut next_senders: Vec<Option<UnboundedSender<Message>>> =
+            senders.iter().skip(1).cloned().map(Some).chain(std::iter::once(None)).collect();
+        let mut branches: Vec<Vec<(usize, UnboundedSender<Message>)>> = (0..senders.len())
+            .map(|index| {
+                self.branch_targets(index)
+                    .into_iter()
+                    .map(|target| (target, senders[target].clone()))
+                    .collect()
+            })
+            .collect();
+        let first_sender = senders.into_iter().next();
+        for (index, receiver) in receivers.into_iter().enumerate() {
+            let station = self
+                .station_observers
+                .iter()
+                .fold(
+                    Station::new(index, self.station_configs[index].clone(), self.station_algorithm(index)),
+                    |station, observer| station.with_observer(Arc::clone(observer)),
+                )
+                .with_clock(clock.clone())
+                .with_start_time(start_time)
+                .with_shutdown_mode(self.shutdown_mode)
+                .with_discipline(self.queue_discipline(index))
+                .with_impatience(self.balking_thresholds.get(&index).copied(), self.patience)
+                .with_ramp_up(self.ramp_ups.get(&index).copied())
+                .with_warm_up(self.warm_ups.get(&index).copied())
+                .with_timer_jitter(self.timer_jitters.get(&index).copied())
+                .with_replacements(replacements.clone())
+                .with_visit_log(visit_logs[index].clone())
+                .with_resources(self.station_resources(&pools, index))
+                .with_scheduled_commands(std::mem::take(&mut schedule[index]));
+            let station = match (index, &pending_returns) {
+                (0, Some(pending_returns)) => station.with_pending_returns(Arc::clone(pending_returns)),
+                _ => station,
+            };
+            let next_sender = next_senders[index].take();
+            let collector = next_sender.is_none().then(|| collector_tx.clone());
+            let outputs = Outputs {
+                loopback: if next_sender.is_none() { loopback.take() } else { None },
+                next_sender,
+                branches: std::mem::take(&mut branches[index]),
+                collector,
+            };
+            let timers = timers.clone();
+            let reports = Arc::clone(&reports);
+            tasks.push((TaskKind::Station(station.config.name.clone()), Box::pin(async move {
+                let report = run_station(station, receiver, outputs, timers).await;
+                lock_recover(&reports)[index] = Some(report);
+            })));
+        }
+        drop(collector_tx);
+
+        // Tareas de los generadores (una por fuente)
+        let active_generators = Arc::new(AtomicUsize::new(products_by_source.len()));
+        let names = std::iter::once("principal".to_string())
+            .chain(self.sources.iter().map(|source| source.name.clone()));
+        if let Some(first_sender) = first_sender {
+            for (idx, (source_products, name)) in products_by_source.into_iter().zip(names).enumerate() {
+                let sender = first_sender.clone();
+                let timers = timers.clone();
+                let active_generators = Arc::clone(&active_generators);
+                let pending_returns = pending_returns.clone();
+                let clock = clock.clone();
+                let replacements = replacements.clone();
+                // Solo el generador principal repone los productos desechados
+                let orders = replacements.clone().filter(|_| idx == 0);
+                let products = Arc::clone(&products);
+                let catalog = self.catalog.clone();
+                let station_configs = self.station_configs.clone();
+                tasks.push((TaskKind::Generator, Box::pin(async move {
+                    let release = |product: Arc<Product>| {
+                        let arrival_time = clock.now().duration_since(start_time);
+                        product.set_arrival_time(arrival_time);
+                        if product.current_station() == Some(0) {
+                            lock_recover(product.visit_state(0)).queue_entry.get_or_insert(arrival_time);
+                        }
+                        if let Some(pending_returns) = &pending_returns {
+                            pending_returns.fetch_add(product.reentries(), Ordering::SeqCst);
+                        }
+                        if let Some(replacements) = &replacements {
+                            replacements.admit();
+                        }
+                        println!(
+                            "[GENERADOR:{}] Producto {:02} disponible en t={}",
+                            name,
+                            product.id,
+                            MetricsCalculator::format_duration(arrival_time)
+                        );
+                        deliver(&sender, Message::Product(product));
+                    };
+
+                    let mut pending = source_products.into_iter().peekable();
+                    loop {
+                        if let Some(orders) = &orders {
+                            while let Some(parent) = orders.next() {
+                                let id = lock_recover(&products).len() + 1;
+                                let offset = clock.now().duration_since(start_time);
+                                let product = catalog.create_product(id, offset, &parent.spec(), &station_configs);
+                                lock_recover(&products).push(Arc::clone(&product));
+                                orders.link(&parent, &product);
+                                println!(
+                                    "[GENERADOR:{}] Producto {:02} reemplaza al producto {:02} desechado",
+                                    name,
+                                    product.id,
+                                    parent.id
+                                );
+                                release(product);
+                            }
+                        }
+
+                        match (pending.peek(), &orders) {
+                            (Some(product), orders) => {
+                                let deadline = start_time + product.arrival_offset;
+                                match orders {
+                                    Some(orders) if clock.now() < deadline => {
+                                        orders_changed(orders, Some(deadline), &timers).await;
+                                        continue;
+                                    }
+                                    Some(_) => {}
+                                    None => timers.sleep_until(deadline).await,
+                                }
+                                release(pending.next().expect("Llegada pendiente"));
+                            }
+                            // Mientras queden productos en la línea pueden desecharse
+                            (None, Some(orders)) if !orders.is_settled() || active_generators.load(Ordering::SeqCst) > 1 => {
+                                orders_changed(orders, None, &timers).await;
+                            }
+                            (None, _) => break,
+                        }
+                    }
+
+                    if active_generators.fetch_sub(1, Ordering::SeqCst) == 1 {
+                        deliver(&sender, Message::Shutdown);
+                    } else if let Some(replacements) = &replacements {
+                        replacements.wake();
+                    }
+                })));
+            }
+        }
+
+        // Tarea del colector
+        {
+            let completion_order = Arc::clone(&completion_order);
+            let finished = Arc::clone(&finished);
+            let progress_callbacks = self.progress_callbacks.clone();
+            let sinks = self.sinks.clone();
+            let station_configs = self.station_configs.clone();
+            let known_products = lock_recover(&products).clone();
+            let replacements = replacements.clone();
+            let clock = clock.clone();
+            let reconfigurations = self.reconfigurations.clone();
+            tasks.push((TaskKind::Collector, Box::pin(async move {
+                while let Some(product) = collector_rx.recv().await {
+                    let metrics = (finalize || !sinks.is_empty())
+                        .then(|| MetricsCalculator::new().calculate_product_metrics(&product, &station_configs))
+                        .flatten();
+                    if let Some(mut metrics) = metrics {
+                        metrics.config_epoch = reload::epoch_at(&reconfigurations, metrics.arrival_time);
+                        for sink in &sinks {
+                            sink.on_product(&metrics);
+                        }
+                        if finalize {
+                            lock_recover(&finished).insert(product.id, metrics);
+                        }
+                    }
+                    let completed = {
+                        let mut completion_order = lock_recover(&completion_order);
+                        completion_order.push(product.id);
+                        completion_order.len()
+                    };
+                    if let Some(replacements) = &replacements {
+                        replacements.settle();
+                    }
+                    if !progress_callbacks.is_empty() && Progress::is_due(completed, known_products.len()) {
+                        let elapsed = clock.now().duration_since(start_time);
+                        let progress = Progress::measure(&known_products, completed, elapsed, false);
+                        for callback in &progress_callbacks {
+                            callback(&progress);
+                        }
+                    }
+                }
+            })));
+        }
+
+        // Los hilos del runtime no heredan el formato de reloj del hilo
+        // actual: cada tarea lo fija mientras avanza
+        let tasks = tasks
+            .into_iter()
+            .map(|(kind, mut task)| {
+                let task: Task = Box::pin(future::poll_fn(move |context| {
+                    let _clock_format = calculator.enter_clock();
+                    task.as_mut().poll(context)
+                }));
+                (kind, task)
+            })
+            .collect();
+        let failure = match &timers {
+            #[cfg(not(target_arch = "wasm32"))]
+            Timers::Tokio => run_on_tokio(tasks, workers),
+            Timers::Virtual(virtual_timers) => run_virtual_tasks(tasks, virtual_timers),
+        }
+        .err();
+        let end_time = clock.now();
+        for sink in &self.sinks {
+            sink.on_finish();
+        }
+
+        let products = std::mem::take(&mut *lock_recover(&products));
+        merge_visit_logs(visit_logs, &products);
+
+        let mut metrics = calculator.aggregate_simulation_metrics(
+            &products,
+            std::mem::take(&mut *lock_recover(&finished)),
+            &self.station_configs,
+            start_time,
+            end_time,
+            std::mem::take(&mut *lock_recover(&completion_order)),
+        );
+        metrics.station_reports = std::mem::take(&mut *lock_recover(&reports)).into_iter().flatten().collect();
+        metrics.steady_state = self.warmup
+            .map(|warmup| MetricsCalculator::steady_state_metrics(&metrics, warmup));
+        metrics.synchronization = match workers {
+            1 => "canales asíncronos en un solo hilo".to_string(),
+            workers => format!("canales asíncronos en un grupo de {} hilos", workers),
+        };
+        metrics.periods = MetricsCalculator::period_metrics(&metrics, &self.report_periods());
+        reload::annotate_epochs(&mut metrics, &self.reconfigurations);
+        metrics.sla_results = self.slas.iter().map(|sla| sla.evaluate(&metrics)).collect();
+        metrics.takt = self.takt_time
+            .map(|takt_time| TaktReport::analyze(&metrics, takt_time, &self.report_periods()));
+        metrics.resource_units = ResourceUnitReport::analyze(&metrics, &self.resources);
+        metrics.release_plan = self.release_plan();
+
+        match failure {
+            Some((TaskKind::Station(station), cause)) => {
+                println!("\n[ERROR] La estación '{}' falló: {}", station, cause);
+                Err(SimulationError::StationPanicked {
+                    station,
+                    cause,
+                    metrics: Box::new(metrics),
+                })
+            }
+            Some((_, cause)) => {
+                println!("\n[ERROR] El generador falló: {}", cause);
+                Err(SimulationError::GeneratorPanicked {
+                    cause,
+                    metrics: Box::new(metrics),
+                })
+            }
+            None => {
+                println!("\n=== Simulación completada ===");
+                println!("Duración total: {}",
+                    MetricsCalculator::format_duration(metrics.total_simulation_time));
+                Ok(metrics)
+            }
+        }
+    }
+}
+
+/// Incorpora a los productos los intervalos de los registros compactos de
+/// las estaciones, liberando cada registro al terminar con él.
+fn merge_visit_logs(visit_logs: Vec<Option<Arc<Mutex<VisitLog>>>>, products: &[Arc<Product>]) {
+    let visit_logs: Vec<VisitLog> = visit_logs
+        .into_iter()
+        .flatten()
+        .map(|visit_log| std::mem::take(&mut *lock_recover(&visit_log)))
+        .collect();
+    if visit_logs.is_empty() {
+        return;
+    }
+
+    let records: usize = visit_logs.iter().map(VisitLog::len).sum();
+    let bytes: usize = visit_logs.iter().map(VisitLog::heap_bytes).sum();
+    println!(
+        "[INFO] Registros compactos: {} intervalos en {:.1} KiB",
+        records,
+        bytes as f64 / 1024.0
+    );
+
+    let mut by_id: Vec<Option<&Arc<Product>>> = vec![None; products.iter().map(|product| product.id).max().unwrap_or(0)];
+    for product in products {
+        by_id[product.id - 1] = Some(product);
+    }
+    for visit_log in visit_logs {
+        visit_log.merge_into(&by_id);
+    }
+}
+
+/// Canales de salida de una estación asíncrona.
+struct Outputs {
+    /// Canal hacia la siguiente estación, si no es la última
+    next_sender: Option<UnboundedSender<Message>>,
+    /// Canal de recirculación hacia la primera estación (solo la última
+    /// estación, y solo si hay rutas reentrantes)
+    loopback: Option<UnboundedSender<Message>>,
+    /// Canales directos hacia las estaciones a las que la estación salta
+    /// hacia adelante, por índice de destino
+    branches: Vec<(usize, UnboundedSender<Message>)>,
+    /// Canal hacia el colector final, si es la última estación
+    collector: Option<UnboundedSender<Arc<Product>>>,
+}
+
+/// Ciclo de vida asíncrono de una estación.
+/// 
+/// Equivale a [`Station::run`]: recibe productos, los procesa según el
+/// algoritmo de planificación y los envía a la siguiente estación, a la
+/// primera (recirculación) o al colector, propagando la señal de apagado
+/// al terminar.
+async fn run_station(
+    station: Station,
+    mut receiver: UnboundedReceiver<Message>,
+    outputs: Outputs,
+    timers: Timers,
+) -> StationReport {
+    let mut queue: VecDeque<Arc<Product>> = VecDeque::new();
+    let mut shutdown_received = false;
+    let mut shutdown_at = None;
+    let mut status = station.status_machine(station.clock.now());
+    let mut report = station.new_report();
+
+    let Outputs { next_sender, loopback, branches, collector } = outputs;
+    let forward = |product: Arc<Product>| {
+        station.stamp_queue_entry(&product);
+        let next_station = product.current_station();
+        if let Some((_, sender)) = branches.iter().find(|(station, _)| Some(*station) == next_station) {
+            deliver(sender, Message::Product(product));
+            return;
+        }
+        match (&next_sender, &loopback, &collector) {
+            (Some(sender), _, _) => deliver(sender, Message::Product(product)),
+            (None, Some(loopback), _) if !product.is_completed() => deliver(loopback, Message::Product(product)),
+            (None, _, Some(collector)) => deliver(collector, product),
+            (None, _, None) => {}
+        }
+    };
+    // Procesa un mensaje; retorna `true` si es la señal de apagado
+    let handle = |message: Message, queue: &mut VecDeque<Arc<Product>>, report: &mut StationReport| {
+        match message {
+            Message::Product(product) => {
+                station.register_return(&product);
+                if product.current_station() != Some(station.index) {
+                    forward(product);
+                    return false;
+                }
+                station.admit(product, queue, report);
+            }
+            Message::Shutdown => return true,
+        }
+        false
+    };
+
+    println!("[INFO] Estación '{}' iniciada", station.config.name);
+
+    loop {
+        if shutdown_received {
+            shutdown_at.get_or_insert(station.clock.now());
+        }
+        // Con un modo de apagado que no drena, vencido el plazo se
+        // abandonan la cola y los retornos pendientes
+        if (!queue.is_empty() || station.awaiting_returns()) && station.shutdown_expired(shutdown_at) {
+            if let Some(sender) = &next_sender {
+                deliver(sender, Message::Shutdown);
+            }
+            println!(
+                "[INFO] Estación '{}' finalizando sin procesar {} productos en cola",
+                station.config.name,
+                queue.len()
+            );
+            break;
+        }
+
+        if queue.is_empty() {
+            if shutdown_received && !station.awaiting_returns() {
+                if let Some(sender) = &next_sender {
+                    deliver(sender, Message::Shutdown);
+                }
+                println!("[INFO] Estación '{}' finalizando", station.config.name);
+                break;
+            }
+
+            status.transition(StationStatus::Idle, station.clock.now(), &mut report);
+            report.wakeups += 1;
+            let deadline = station.shutdown_mode.deadline(shutdown_at);
+            match recv_until(&mut receiver, deadline, &timers).await {
+                Received::Item(message) => {
+                    // Las reconfiguraciones vencidas mientras la estación
+                    // esperaba se aplican antes del mensaje que la despertó
+                    station.apply_commands(&mut queue);
+                    shutdown_received |= handle(message, &mut queue, &mut report)
+                }
+                Received::Closed => break,
+                Received::Timeout => {}
+            }
+            continue;
+        }
+
+        station.apply_commands(&mut queue);
+        while let Ok(message) = receiver.try_recv() {
+            shutdown_received |= handle(message, &mut queue, &mut report);
+        }
+        if station.renege(&mut queue, &mut report) && queue.is_empty() {
+            continue;
+        }
+
+        match station.take_next_ready(&mut queue, &mut report) {
+            Some(product) => {
+                if let Some(warm_up) = station.start_warm_up(&product, &mut status, &mut report) {
+                    timers.sleep_until(station.clock.now() + warm_up).await;
+                }
+                // Adquirir los recursos compartidos sin bloquear el ejecutor
+                let wait_started = station.clock.now();
+                let mut resources = Vec::new();
+                for resource in station.resources.iter().filter(|resource| resource.applies_to(&product)) {
+                    loop {
+                        if let Some(unit) = resource.pool.try_acquire() {
+                            resources.push(unit);
+                            break;
+                        }
+                        status.transition(StationStatus::Blocked, station.clock.now(), &mut report);
+                        timers.sleep_until(station.clock.now() + DEPENDENCY_POLL_INTERVAL).await;
+                    }
+                }
+                let resource_wait = station.clock.now().saturating_duration_since(wait_started);
+
+                let (slice_start, remaining, slice) = station.start_slice(&product, &resources, resource_wait);
+                status.transition(StationStatus::Busy, slice_start, &mut report);
+                let overshoot = station.timer_overshoot();
+                timers.sleep_until(slice_start + slice + overshoot).await;
+                let completed = station.finish_slice(&product, slice_start, remaining, slice, overshoot, &mut report);
+                drop(resources);
+                if completed && station.reject(&product, &mut report) {
+                    continue;
+                }
+                if completed {
+                    forward(product);
+                } else {
+                    enqueue(&mut queue, product);
+                    report.max_queue_length = report.max_queue_length.max(queue.len());
+                }
+            }
+            None => {
+                // Todos los productos en cola están bloqueados por dependencias
+                status.transition(StationStatus::Idle, station.clock.now(), &mut report);
+                report.wakeups += 1;
+                let deadline = station.clock.now() + DEPENDENCY_POLL_INTERVAL;
+                if let Received::Item(message) = recv_until(&mut receiver, Some(deadline), &timers).await {
+                    station.apply_commands(&mut queue);
+                    shutdown_received |= handle(message, &mut queue, &mut report);
+                }
+            }
+        }
+    }
+
+    status.transition(StationStatus::Down, station.clock.now(), &mut report);
+    report
+}
+
+/// Tipo de tarea, usado para reportar qué componente falló.
+#[derive(Clone, Debug)]
+enum TaskKind {
+    Station(String),
+    Generator,
+    Collector,
+}
+
+type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Ejecuta las tareas en un runtime de tokio hasta que todas terminan.
+/// 
+/// Con un solo hilo usa el runtime de un hilo; con más, el runtime
+/// multihilo con `workers` hilos.
+/// 
+/// # Returns
+/// 
+/// `Err` con el tipo de tarea y el mensaje si alguna tarea hizo panic; en
+/// ese caso las demás tareas se cancelan
+#[cfg(not(target_arch = "wasm32"))]
+fn run_on_tokio(tasks: Vec<(TaskKind, Task)>, workers: usize) -> Result<(), (TaskKind, String)> {
+    let mut builder = match workers {
+        1 => tokio::runtime::Builder::new_current_thread(),
+        workers => {
+            let mut builder = tokio::runtime::Builder::new_multi_thread();
+            builder.worker_threads(workers);
+            builder
+        }
+    };
+    let runtime = builder.enable_time().build().expect("No se pudo crear el runtime de tokio");
+
+    runtime.block_on(async move {
+        let mut running = tokio::task::JoinSet::new();
+        let mut kinds = HashMap::new();
+        for (kind, task) in tasks {
+            kinds.insert(running.spawn(task).id(), kind);
+        }
+        while let Some(result) = running.join_next().await {
+            match result {
+                Err(error) if error.is_panic() => {
+                    // Sin la tarea que falló la línea no puede terminar
+                    running.abort_all();
+                    let kind = kinds.remove(&error.id()).expect("La tarea fue lanzada por el backend");
+                    return Err((kind, panic_message(error.into_panic().as_ref())));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Ejecuta las tareas en el hilo actual en tiempo virtual.
+/// 
+/// Hace avanzar las tareas listas en el orden en que despertaron y, cuando
+/// ninguna puede avanzar, adelanta el reloj virtual al próximo
+/// temporizador. Termina cuando todas las tareas terminan o cuando ninguna
+/// puede avanzar y no quedan temporizadores.
+/// 
+/// # Returns
+/// 
+/// `Err` con el tipo de tarea y el mensaje si alguna tarea hizo panic
+fn run_virtual_tasks(tasks: Vec<(TaskKind, Task)>, timers: &Mutex<VirtualTimers>) -> Result<(), (TaskKind, String)> {
+    let ready = Arc::new(Mutex::new((0..tasks.len()).collect::<VecDeque<usize>>()));
+    let mut pending = tasks.len();
+    let mut tasks: Vec<Option<(TaskKind, Task)>> = tasks.into_iter().map(Some).collect();
+
+    while pending > 0 {
+        let next = lock_recover(&ready).pop_front();
+        let Some(id) = next else {
+            if lock_recover(timers).advance() {
+                continue;
+            }
+            // Ninguna tarea puede avanzar
+            break;
+        };
+        // Una tarea que ya terminó puede recibir despertares tardíos
+        let Some((kind, task)) = tasks[id].as_mut() else {
+            continue;
+        };
+        let waker = Waker::from(Arc::new(TaskWaker { id, ready: Arc::clone(&ready) }));
+        let mut context = Context::from_waker(&waker);
+        match panic::catch_unwind(AssertUnwindSafe(|| task.as_mut().poll(&mut context))) {
+            Ok(Poll::Ready(())) => {
+                tasks[id] = None;
+                pending -= 1;
+            }
+            Ok(Poll::Pending) => {}
+            Err(payload) => return Err((kind.clone(), panic_message(payload.as_ref()))),
+        }
+    }
+    Ok(())
+}
+
+/// Waker que reencola la tarea en la cola de tareas listas del
+/// planificador de tiempo virtual.
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        lock_recover(&self.ready).push_back(self.id);
+    }
+}
+
+/// Envía un valor por un canal. Si el receptor ya no existe porque su
+/// tarea falló, el valor se descarta: la falla se reporta al terminar.
+fn deliver<T>(sender: &UnboundedSender<T>, value: T) {
+    let _ = sender.send(value);
+}
+
+/// Resultado de una recepción con tiempo límite.
+enum Received<T> {
+    Item(T),
+    Closed,
+    Timeout,
+}
+
+/// Espera el próximo valor del canal como máximo hasta `deadline` (`None`
+/// = sin límite).
+async fn recv_until<T>(receiver: &mut UnboundedReceiver<T>, deadline: Option<Instant>, timers: &Timers) -> Received<T> {
+    let Some(deadline) = deadline else {
+        return receiver.recv().await.map_or(Received::Closed, Received::Item);
+    };
+    tokio::select! {
+        biased;
+        item = receiver.recv() => item.map_or(Received::Closed, Received::Item),
+        () = timers.sleep_until(deadline) => Received::Timeout,
+    }
+}
+
+/// Temporizadores con los que esperan las tareas.
+#[derive(Clone)]
+enum Timers {
+    /// Temporizadores de tokio, en tiempo real
+    #[cfg(not(target_arch = "wasm32"))]
+    Tokio,
+    /// Temporizadores del planificador de tiempo virtual
+    Virtual(Arc<Mutex<VirtualTimers>>),
+}
+
+impl Timers {
+    /// Crea los temporizadores que corresponden al reloj de la simulación.
+    /// 
+    /// # Panics
+    /// 
+    /// En `wasm32` hace panic si el reloj es real
+    fn for_clock(clock: &Clock) -> Self {
+        match clock {
+            #[cfg(not(target_arch = "wasm32"))]
+            Clock::Real => Self::Tokio,
+            #[cfg(target_arch = "wasm32")]
+            Clock::Real => panic!("El reloj real no está disponible en wasm32; use Simulation::run_virtual"),
+            Clock::Virtual(clock) => Self::Virtual(Arc::new(Mutex::new(VirtualTimers::new(clock.clone())))),
+        }
+    }
+
+    /// Espera hasta el instante indicado.
+    async fn sleep_until(&self, deadline: Instant) {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Tokio => tokio::time::sleep_until(deadline.into()).await,
+            Self::Virtual(timers) => VirtualSleep { deadline, timers, timer: None }.await,
+        }
+    }
+}
+
+/// Temporizadores pendientes en tiempo virtual, ordenados por vencimiento
+/// y, a igual vencimiento, por orden de registro.
+struct VirtualTimers {
+    clock: VirtualClock,
+    deadlines: BinaryHeap<Reverse<(Instant, u64)>>,
+    /// Waker de cada temporizador vigente; los cancelados no figuran
+    wakers: HashMap<u64, Waker>,
+    next_id: u64,
+}
+
+impl VirtualTimers {
+    fn new(clock: VirtualClock) -> Self {
+        Self {
+            clock,
+            deadlines: BinaryHeap::new(),
+            wakers: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Registra un temporizador que despierta a `waker` al llegar
+    /// `deadline`, o actualiza el waker de uno ya registrado.
+    /// 
+    /// # Returns
+    /// 
+    /// El identificador del temporizador
+    fn register(&mut self, timer: Option<u64>, deadline: Instant, waker: &Waker) -> u64 {
+        if let Some(id) = timer.filter(|id| self.wakers.contains_key(id)) {
+            self.wakers.insert(id, waker.clone());
+            return id;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.deadlines.push(Reverse((deadline, id)));
+        self.wakers.insert(id, waker.clone());
+        id
+    }
+
+    /// Cancela un temporizador, que ya no adelantará el reloj.
+    fn cancel(&mut self, id: u64) {
+        self.wakers.remove(&id);
+    }
+
+    /// Adelanta el reloj al próximo temporizador vigente y despierta, en
+    /// orden de registro, a todas las tareas que vencen en ese instante.
+    /// 
+    /// # Returns
+    /// 
+    /// `false` si no quedan temporizadores vigentes
+    fn advance(&mut self) -> bool {
+        let Some(deadline) = self.next_deadline() else {
+            return false;
+        };
+        self.clock.advance_to(deadline);
+        while let Some(&Reverse((next, id))) = self.deadlines.peek() {
+            if next > deadline {
+                break;
+            }
+            self.deadlines.pop();
+            if let Some(waker) = self.wakers.remove(&id) {
+                waker.wake();
+            }
+        }
+        true
+    }
+
+    /// Vencimiento más próximo de los temporizadores vigentes, descartando
+    /// los cancelados que estén primero.
+    fn next_deadline(&mut self) -> Option<Instant> {
+        while let Some(&Reverse((deadline, id))) = self.deadlines.peek() {
+            if self.wakers.contains_key(&id) {
+                return Some(deadline);
+            }
+            self.deadlines.pop();
+        }
+        None
+    }
+}
+
+/// Futuro que se completa cuando el reloj virtual llega a su vencimiento.
+/// Si se descarta antes, cancela su temporizador.
+struct VirtualSleep<'a> {
+    deadline: Instant,
+    timers: &'a Mutex<VirtualTimers>,
+    timer: Option<u64>,
+}
+
+impl Future for VirtualSleep<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let mut timers = lock_recover(this.timers);
+        if timers.clock.now() >= this.deadline {
+            if let Some(id) = this.timer.take() {
+                timers.cancel(id);
+            }
+            return Poll::Ready(());
+        }
+        this.timer = Some(timers.register(this.timer, this.deadline, context.waker()));
+        Poll::Pending
+    }
+}
+
+impl Drop for VirtualSleep<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = self.timer {
+            lock_recover(self.timers).cancel(id);
+        }
+    }
+}
+
+/// Espera un cambio en las órdenes de reemplazo o, si se indica, el
+/// instante `deadline`.
+async fn orders_changed(orders: &ReplacementOrders, deadline: Option<Instant>, timers: &Timers) {
+    let changed = OrdersChanged { orders, registered: false };
+    match deadline {
+        Some(deadline) => tokio::select! {
+            biased;
+            () = changed => {}
+            () = timers.sleep_until(deadline) => {}
+        },
+        None => changed.await,
+    }
+}
+
+/// Futuro que se completa con el primer aviso de las órdenes de reemplazo.
+struct OrdersChanged<'a> {
+    orders: &'a ReplacementOrders,
+    registered: bool,
+}
+
+impl Future for OrdersChanged<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        // Cualquier despertar posterior al registro es un aviso o el
+        // vencimiento; el generador vuelve a revisar las órdenes
+        if this.registered {
+            return Poll::Ready(());
+        }
+        // Un aviso previo al registro (por ejemplo, desde otro hilo del
+        // runtime) se atiende sin esperar
+        if !this.orders.register_waker(context.waker().clone()) {
+            return Poll::Ready(());
+        }
+        this.registered = true;
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn virtual_timers() -> (VirtualClock, Timers) {
+        let clock = VirtualClock::new();
+        let timers = Timers::for_clock(&Clock::Virtual(clock.clone()));
+        (clock, timers)
+    }
+
+    fn virtual_run(timers: &Timers, tasks: Vec<(TaskKind, Task)>) -> Result<(), (TaskKind, String)> {
+        match timers {
+            Timers::Virtual(timers) => run_virtual_tasks(tasks, timers),
+            _ => unreachable!("Temporizadores virtuales"),
+        }
+    }
+
+    #[test]
+    fn test_virtual_timers_with_the_same_deadline_wake_in_registration_order() {
+        let (clock, timers) = virtual_timers();
+        let deadline = clock.now() + Duration::from_nanos(1_500);
+        let woken = Arc::new(Mutex::new(Vec::new()));
+        let tasks = (0..4)
+            .map(|index| {
+                let (timers, woken, clock) = (timers.clone(), Arc::clone(&woken), clock.clone());
+                // La última tarea vence antes que las demás aunque se registra después
+                let deadline = if index == 3 { deadline - Duration::from_nanos(1) } else { deadline };
+                let task: Task = Box::pin(async move {
+                    timers.sleep_until(deadline).await;
+                    lock_recover(&woken).push((index, clock.elapsed()));
+                });
+                (TaskKind::Generator, task)
+            })
+            .collect();
+
+        assert!(virtual_run(&timers, tasks).is_ok());
+        let expected = Duration::from_nanos(1_500);
+        assert_eq!(
+            *lock_recover(&woken),
+            vec![(3, expected - Duration::from_nanos(1)), (0, expected), (1, expected), (2, expected)]
+        );
+    }
+
+    #[test]
+    fn test_late_wakeups_and_cancelled_timers_do_not_affect_finished_tasks() {
+        let (clock, timers) = virtual_timers();
+        let start = clock.now();
+        let (sender, mut receiver) = unbounded_channel();
+        let saved_waker: Arc<Mutex<Option<Waker>>> = Arc::default();
+
+        // Recibe antes de su plazo de 10 s y termina guardando su waker
+        let receiving: Task = {
+            let (timers, saved_waker) = (timers.clone(), Arc::clone(&saved_waker));
+            Box::pin(async move {
+                let received = recv_until(&mut receiver, Some(start + Duration::from_secs(10)), &timers).await;
+                assert!(matches!(received, Received::Item(1)));
+                std::future::poll_fn(|context| {
+                    *lock_recover(&saved_waker) = Some(context.waker().clone());
+                    Poll::Ready(())
+                })
+                .await;
+            })
+        };
+        // Envía al segundo, despierta a la tarea terminada y envía a su receptor descartado
+        let sending: Task = {
+            let timers = timers.clone();
+            Box::pin(async move {
+                timers.sleep_until(start + Duration::from_secs(1)).await;
+                deliver(&sender, 1);
+                timers.sleep_until(start + Duration::from_secs(2)).await;
+                lock_recover(&saved_waker).take().expect("La tarea guardó su waker").wake();
+                deliver(&sender, 2);
+            })
+        };
+
+        let tasks = vec![(TaskKind::Collector, receiving), (TaskKind::Generator, sending)];
+        assert!(virtual_run(&timers, tasks).is_ok());
+        // El plazo cancelado de 10 s no adelantó el reloj
+        assert_eq!(clock.elapsed(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_virtual_run_stops_when_no_task_can_advance_and_reports_panics() {
+        let (clock, timers) = virtual_timers();
+        let (_sender, mut receiver) = unbounded_channel::<()>();
+        let stuck: Task = Box::pin(async move {
+            receiver.recv().await;
+        });
+        assert!(virtual_run(&timers, vec![(TaskKind::Collector, stuck)]).is_ok());
+        assert_eq!(clock.elapsed(), Duration::ZERO);
+
+        let failing: Task = Box::pin(async { panic!("falla de prueba") });
+        match virtual_run(&timers, vec![(TaskKind::Station("Horno".to_string()), failing)]) {
+            Err((TaskKind::Station(station), cause)) => {
+                assert_eq!(station, "Horno");
+                assert_eq!(cause, "falla de prueba");
+            }
+            other => panic!("Se esperaba la falla de la estación, se obtuvo {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tokio_run_cancels_remaining_tasks_after_a_panic() {
+        for workers in [1, 2] {
+            let sleeping: Task = Box::pin(async {
+                Timers::Tokio.sleep_until(Instant::now() + Duration::from_secs(3600)).await;
+            });
+            let failing: Task = Box::pin(async {
+                Timers::Tokio.sleep_until(Instant::now() + Duration::from_millis(5)).await;
+                panic!("falla de prueba");
+            });
+            let started = std::time::Instant::now();
+            let tasks = vec![
+                (TaskKind::Generator, Box::pin(async {}) as Task),
+                (TaskKind::Collector, sleeping),
+                (TaskKind::Station("Horno".to_string()), failing),
+            ];
+            match run_on_tokio(tasks, workers) {
+                Err((TaskKind::Station(station), cause)) => {
+                    assert_eq!(station, "Horno");
+                    assert_eq!(cause, "falla de prueba");
+                }
+                other => panic!("Se esperaba la falla de la estación, se obtuvo {:?}", other),
+            }
+            assert!(started.elapsed() < Duration::from_secs(60));
+        }
+    }
+}
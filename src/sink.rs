@@ -0,0 +1,356 @@
+//! # Módulo de Destinos de Resultados
+//! 
+//! Este módulo define los destinos (`Sink`) que reciben cada producto en el
+//! momento en que completa la línea. El colector de la simulación calcula
+//! las métricas del producto y las entrega a todos los destinos registrados,
+//! por lo que los resultados pueden procesarse a medida que se producen en
+//! lugar de esperar al reporte final.
+//! 
+//! Destinos incluidos:
+//! 
+//! - [`ConsoleSink`]: imprime una línea por producto (destino por defecto)
+//! - [`VecSink`]: acumula las métricas en memoria
+//! - [`NdjsonSink`]: escribe un objeto JSON por línea en cualquier `Write`
+//! - [`CsvSink`]: escribe una fila CSV por producto en cualquier `Write`
+//! - [`ChannelSink`]: reenvía las métricas por un canal `mpsc`
+//! - Cualquier closure `Fn(&ProductMetrics)`
+
+use std::io::{self, Write};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+
+use crate::metrics::{MetricsCalculator, ProductMetrics};
+use crate::product::lock_recover;
+
+/// Destino de los productos que completan la línea.
+/// 
+/// Los métodos se invocan desde el hilo del colector (o la tarea del
+/// colector en el backend asíncrono), en orden de finalización.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+/// use assembly_line_simulator::{ProductMetrics, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// let finished = Arc::new(Mutex::new(Vec::new()));
+/// let ids = Arc::clone(&finished);
+/// simulation.add_sink(move |product: &ProductMetrics| ids.lock().unwrap().push(product.product_id));
+/// simulation.run().expect("La simulación falló");
+/// assert_eq!(*finished.lock().unwrap(), vec![1, 2, 3]);
+/// ```
+pub trait Sink: Send + Sync {
+    /// Recibe las métricas de un producto que completó la línea.
+    fn on_product(&self, product: &ProductMetrics);
+
+    /// Se invoca una vez cuando el colector termina, incluso si la
+    /// simulación se detuvo por un bloqueo.
+    fn on_finish(&self) {}
+}
+
+impl<F> Sink for F
+where
+    F: Fn(&ProductMetrics) + Send + Sync,
+{
+    fn on_product(&self, product: &ProductMetrics) {
+        self(product)
+    }
+}
+
+/// Destino que imprime una línea por producto completado en la salida
+/// estándar. Es el destino que la simulación registra por defecto.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConsoleSink;
+
+impl Sink for ConsoleSink {
+    fn on_product(&self, product: &ProductMetrics) {
+        println!(
+            "[COMPLETADO] Producto {:02} terminó toda la línea en t={}",
+            product.product_id,
+            MetricsCalculator::format_duration(product.arrival_time + product.turnaround_time)
+        );
+    }
+}
+
+/// Destino que acumula las métricas de los productos en memoria.
+/// 
+/// Las copias comparten el mismo contenido, por lo que se registra una
+/// copia en la simulación y se consulta la otra.
+#[derive(Clone, Debug, Default)]
+pub struct VecSink {
+    products: Arc<Mutex<Vec<ProductMetrics>>>,
+}
+
+impl VecSink {
+    /// Crea un destino vacío.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Obtiene las métricas recibidas, en orden de finalización.
+    pub fn products(&self) -> Vec<ProductMetrics> {
+        lock_recover(&self.products).clone()
+    }
+
+    /// Obtiene el número de productos recibidos.
+    pub fn len(&self) -> usize {
+        lock_recover(&self.products).len()
+    }
+
+    /// Indica si todavía no se recibió ningún producto.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Sink for VecSink {
+    fn on_product(&self, product: &ProductMetrics) {
+        lock_recover(&self.products).push(product.clone());
+    }
+}
+
+/// Destino que escribe cada producto como una línea JSON (NDJSON).
+/// 
+/// Cada línea tiene el mismo objeto que el producto en
+/// [`MetricsCalculator::generate_json_report`]. El escritor se vacía al
+/// terminar la simulación. Si una escritura falla, las siguientes se omiten
+/// y el error queda disponible en [`NdjsonSink::take_error`].
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{NdjsonSink, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// let sink = NdjsonSink::new(Vec::new());
+/// simulation.add_sink(sink.clone());
+/// simulation.run().expect("La simulación falló");
+/// drop(simulation);
+/// 
+/// let output = String::from_utf8(sink.into_inner().unwrap()).unwrap();
+/// assert_eq!(output.lines().count(), 2);
+/// assert!(output.starts_with("{\"id\":1,"));
+/// ```
+#[derive(Debug)]
+pub struct NdjsonSink<W> {
+    state: Arc<Mutex<NdjsonState<W>>>,
+}
+
+/// Escritor de un [`NdjsonSink`] y el primer error de escritura.
+#[derive(Debug)]
+struct NdjsonState<W> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write + Send> NdjsonSink<W> {
+    /// Crea un destino que escribe en `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(NdjsonState { writer, error: None })),
+        }
+    }
+
+    /// Obtiene el primer error de escritura, si lo hubo.
+    pub fn take_error(&self) -> Option<io::Error> {
+        lock_recover(&self.state).error.take()
+    }
+
+    /// Recupera el escritor.
+    /// 
+    /// # Returns
+    /// 
+    /// El escritor, o `None` si otra copia del destino sigue viva (la
+    /// simulación conserva la copia registrada hasta que se descarta)
+    pub fn into_inner(self) -> Option<W> {
+        Arc::try_unwrap(self.state)
+            .ok()
+            .map(|state| state.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()).writer)
+    }
+
+    /// Ejecuta una escritura si no hubo errores previos y registra el error.
+    fn write_with(&self, operation: impl FnOnce(&mut W) -> io::Result<()>) {
+        let mut state = lock_recover(&self.state);
+        if state.error.is_none() {
+            if let Err(error) = operation(&mut state.writer) {
+                state.error = Some(error);
+            }
+        }
+    }
+}
+
+impl<W> Clone for NdjsonSink<W> {
+    fn clone(&self) -> Self {
+        Self { state: Arc::clone(&self.state) }
+    }
+}
+
+impl<W: Write + Send> Sink for NdjsonSink<W> {
+    fn on_product(&self, product: &ProductMetrics) {
+        let line = MetricsCalculator::product_json(product);
+        self.write_with(|writer| writeln!(writer, "{}", line));
+    }
+
+    fn on_finish(&self) {
+        self.write_with(|writer| writer.flush());
+    }
+}
+
+/// Destino que escribe cada producto como una fila CSV.
+/// 
+/// Las filas tienen el formato de [`MetricsCalculator::generate_csv_report`]
+/// y se escriben a medida que los productos completan la línea, por lo que
+/// el reporte de una ejecución grande nunca está completo en memoria. El
+/// encabezado se escribe antes de la primera fila (o al terminar, si ningún
+/// producto completó la línea) con un par de columnas por cada una de las
+/// `station_count` estaciones; no lleva los metadatos de la ejecución, que
+/// solo se conocen al final. Un producto que recircula puede tener más
+/// pares de columnas que el encabezado.
+/// 
+/// Como en [`NdjsonSink`], el escritor se vacía al terminar la simulación y
+/// el primer error de escritura queda disponible en
+/// [`CsvSink::take_error`].
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{CsvSink, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// let sink = CsvSink::new(Vec::new(), 1);
+/// simulation.add_sink(sink.clone());
+/// simulation.run().expect("La simulación falló");
+/// drop(simulation);
+/// 
+/// let output = String::from_utf8(sink.into_inner().unwrap()).unwrap();
+/// assert_eq!(output.lines().count(), 3);
+/// assert!(output.starts_with("ProductID,ArrivalTime,WaitTime,Turnaround,Station1_Entry,Station1_Exit,"));
+/// ```
+#[derive(Debug)]
+pub struct CsvSink<W> {
+    state: Arc<Mutex<CsvState<W>>>,
+}
+
+/// Escritor de un [`CsvSink`], el estado del encabezado y el primer error
+/// de escritura.
+#[derive(Debug)]
+struct CsvState<W> {
+    writer: W,
+    station_count: usize,
+    header_written: bool,
+    error: Option<io::Error>,
+}
+
+impl<W: Write + Send> CsvSink<W> {
+    /// Crea un destino que escribe en `writer` las filas de una línea de
+    /// `station_count` estaciones.
+    pub fn new(writer: W, station_count: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CsvState {
+                writer,
+                station_count,
+                header_written: false,
+                error: None,
+            })),
+        }
+    }
+
+    /// Obtiene el primer error de escritura, si lo hubo.
+    pub fn take_error(&self) -> Option<io::Error> {
+        lock_recover(&self.state).error.take()
+    }
+
+    /// Recupera el escritor.
+    /// 
+    /// # Returns
+    /// 
+    /// El escritor, o `None` si otra copia del destino sigue viva (la
+    /// simulación conserva la copia registrada hasta que se descarta)
+    pub fn into_inner(self) -> Option<W> {
+        Arc::try_unwrap(self.state)
+            .ok()
+            .map(|state| state.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()).writer)
+    }
+
+    /// Ejecuta una escritura, precedida por el encabezado si todavía no se
+    /// escribió, siempre que no haya habido errores previos.
+    fn write_with(&self, operation: impl FnOnce(&mut W, usize) -> io::Result<()>) {
+        let mut state = lock_recover(&self.state);
+        if state.error.is_some() {
+            return;
+        }
+        let CsvState { writer, station_count, header_written, .. } = &mut *state;
+        let mut result = Ok(());
+        if !*header_written {
+            result = MetricsCalculator::write_csv_header(writer, *station_count);
+            *header_written = result.is_ok();
+        }
+        if let Err(error) = result.and_then(|()| operation(writer, *station_count)) {
+            state.error = Some(error);
+        }
+    }
+}
+
+impl<W> Clone for CsvSink<W> {
+    fn clone(&self) -> Self {
+        Self { state: Arc::clone(&self.state) }
+    }
+}
+
+impl<W: Write + Send> Sink for CsvSink<W> {
+    fn on_product(&self, product: &ProductMetrics) {
+        self.write_with(|writer, station_count| MetricsCalculator::write_csv_row(writer, product, station_count));
+    }
+
+    fn on_finish(&self) {
+        self.write_with(|writer, _| writer.flush());
+    }
+}
+
+/// Destino que reenvía las métricas de cada producto por un canal.
+/// 
+/// Si el receptor se descartó, los productos siguientes se ignoran.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{ChannelSink, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// let (sink, receiver) = ChannelSink::new();
+/// simulation.add_sink(sink);
+/// simulation.run().expect("La simulación falló");
+/// assert_eq!(receiver.try_iter().count(), 2);
+/// ```
+#[derive(Debug)]
+pub struct ChannelSink {
+    sender: mpsc::Sender<ProductMetrics>,
+}
+
+impl ChannelSink {
+    /// Crea un destino y el receptor de sus productos.
+    pub fn new() -> (Self, mpsc::Receiver<ProductMetrics>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self { sender }, receiver)
+    }
+
+    /// Crea un destino que envía por un canal existente.
+    pub fn with_sender(sender: mpsc::Sender<ProductMetrics>) -> Self {
+        Self { sender }
+    }
+}
+
+impl Sink for ChannelSink {
+    fn on_product(&self, product: &ProductMetrics) {
+        let _ = self.sender.send(product.clone());
+    }
+}
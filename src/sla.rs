@@ -0,0 +1,156 @@
+//! # Módulo de Acuerdos de Nivel de Servicio
+//! 
+//! Este módulo permite declarar objetivos de servicio (SLA) sobre una
+//! simulación, como "el 95% de los productos termina en menos de 3 s" o
+//! "la línea completa al menos 2 productos por segundo", y evaluarlos sobre
+//! las métricas finales. Cada resultado indica si el objetivo se cumplió y
+//! con qué margen, lo que facilita la corrección automática y los ciclos de
+//! optimización de parámetros.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+
+/// Objetivo de servicio que debe cumplir la simulación.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Sla {
+    /// Al menos la fracción `fraction` de los productos completados debe
+    /// tener un turnaround menor o igual a `limit`.
+    TurnaroundWithin {
+        /// Fracción de productos requerida, entre 0 y 1 (por ejemplo, 0.95)
+        fraction: f64,
+        /// Turnaround máximo permitido
+        limit: Duration,
+    },
+    /// El throughput debe ser al menos el indicado (productos por segundo).
+    MinThroughput(f64),
+    /// El tiempo promedio de espera no debe superar el indicado.
+    MaxAverageWait(Duration),
+}
+
+/// Resultado de evaluar un SLA.
+/// 
+/// `actual`, `target` y `margin` se expresan en la unidad del objetivo:
+/// segundos para los tiempos y productos por segundo para el throughput.
+/// El margen es positivo (o cero) cuando el objetivo se cumple y negativo
+/// cuando no.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlaResult {
+    /// Objetivo evaluado
+    pub sla: Sla,
+    /// Indica si el objetivo se cumplió
+    pub passed: bool,
+    /// Valor obtenido en la simulación
+    pub actual: f64,
+    /// Valor exigido por el objetivo
+    pub target: f64,
+    /// Holgura respecto del objetivo (negativa si no se cumplió)
+    pub margin: f64,
+}
+
+impl Sla {
+    /// Evalúa el objetivo sobre las métricas de una simulación.
+    /// 
+    /// Para [`Sla::TurnaroundWithin`] el valor obtenido es el percentil
+    /// `fraction` del turnaround (por rango más cercano) de los productos
+    /// completados; sin productos completados el objetivo no se cumple.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas finales de la simulación
+    /// 
+    /// # Returns
+    /// 
+    /// `SlaResult` con el veredicto y el margen
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{Sla, Simulation, SchedulingAlgorithm, StationConfig};
+    /// 
+    /// let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+    /// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+    /// simulation.add_sla(Sla::TurnaroundWithin { fraction: 0.95, limit: Duration::from_secs(3) });
+    /// let metrics = simulation.run().expect("La simulación falló");
+    /// assert!(metrics.sla_results[0].passed);
+    /// assert!(metrics.sla_results[0].margin > 2.9);
+    /// ```
+    pub fn evaluate(&self, metrics: &SimulationMetrics) -> SlaResult {
+        let (actual, target, margin) = match self {
+            Self::TurnaroundWithin { fraction, limit } => {
+                let mut turnarounds: Vec<Duration> = metrics.products
+                    .iter()
+                    .map(|product| product.turnaround_time)
+                    .collect();
+                turnarounds.sort();
+                let rank = (fraction * turnarounds.len() as f64).ceil().max(1.0) as usize;
+                let target = limit.as_secs_f64();
+                match turnarounds.get(rank - 1) {
+                    Some(percentile) => {
+                        let actual = percentile.as_secs_f64();
+                        (actual, target, target - actual)
+                    }
+                    None => (0.0, target, -target),
+                }
+            }
+            Self::MinThroughput(target) => (metrics.throughput, *target, metrics.throughput - target),
+            Self::MaxAverageWait(limit) => {
+                let actual = metrics.average_wait_time.as_secs_f64();
+                let target = limit.as_secs_f64();
+                (actual, target, target - actual)
+            }
+        };
+
+        SlaResult {
+            sla: self.clone(),
+            passed: margin >= 0.0,
+            actual,
+            target,
+            margin,
+        }
+    }
+}
+
+impl fmt::Display for Sla {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TurnaroundWithin { fraction, limit } => write!(
+                f,
+                "{:.0}% de los productos con turnaround <= {}",
+                fraction * 100.0,
+                MetricsCalculator::format_duration(*limit)
+            ),
+            Self::MinThroughput(target) => write!(f, "throughput >= {:.3} productos/segundo", target),
+            Self::MaxAverageWait(limit) => write!(
+                f,
+                "espera promedio <= {}",
+                MetricsCalculator::format_duration(*limit)
+            ),
+        }
+    }
+}
+
+impl fmt::Display for SlaResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.passed { "CUMPLE" } else { "NO CUMPLE" };
+        match self.sla {
+            Sla::TurnaroundWithin { fraction, .. } => write!(
+                f,
+                "{}: {} (p{:.0} = {:.3}s, margen {:+.3}s)",
+                self.sla, verdict, fraction * 100.0, self.actual, self.margin
+            ),
+            Sla::MinThroughput(_) => write!(
+                f,
+                "{}: {} (obtenido {:.3}, margen {:+.3})",
+                self.sla, verdict, self.actual, self.margin
+            ),
+            Sla::MaxAverageWait(_) => write!(
+                f,
+                "{}: {} (obtenido {:.3}s, margen {:+.3}s)",
+                self.sla, verdict, self.actual, self.margin
+            ),
+        }
+    }
+}
@@ -5,43 +5,235 @@
 //! secuencial aplicando algoritmos de planificación.
 
 use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc;
-use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 use std::thread;
 
-use crate::product::Product;
-use crate::scheduler::SchedulingAlgorithm;
+#[cfg(feature = "crossbeam")]
+use crate::channel::Received;
+use crate::channel::{StationReceiver, StationSender};
+use crate::clock::{Clock, Instant};
+use crate::control::{CommandReceiver, StationCommand};
+use crate::deadlock::StationProbe;
+use crate::jockey::JockeyBoard;
+use crate::product::{lock_recover, LossReason, Product, ProductLoss};
+use crate::resource::{ResourceGuard, StationResource};
+use crate::rng::SimRng;
+use crate::routing::{Inspection, MergeState, Merger, RouteTarget, Splitter, StationKind, INSPECTION_TIME};
+use crate::scheduler::{QueueDiscipline, SchedulingAlgorithm};
+use crate::scrap::ReplacementOrders;
+use crate::status::{StationObserver, StationStatus, StatusMachine};
+use crate::stop::StopSignal;
+use crate::storage::VisitLog;
+use crate::work::{SleepWork, WorkModel};
 use crate::metrics::MetricsCalculator;
 
 /// Configuración estática para una estación de trabajo.
 /// 
 /// Define las características inmutables de una estación, como su nombre
-/// y el tiempo de procesamiento requerido para cada producto.
-#[derive(Clone, Copy, Debug)]
+/// y el tiempo de procesamiento requerido para cada producto, además de
+/// metadatos opcionales de presentación que usan los reportes.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::StationConfig;
+/// 
+/// let name = String::from("Pintura");
+/// let station = StationConfig::new(name, Duration::from_millis(500))
+///     .with_description("Cabina de pintura electrostática")
+///     .with_color("#d9480f");
+/// assert_eq!(station.name, "Pintura");
+/// assert_eq!(station.color.as_deref(), Some("#d9480f"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
 pub struct StationConfig {
     /// Nombre identificador de la estación
-    pub name: &'static str,
+    pub name: String,
     /// Tiempo requerido para procesar completamente un producto
     pub processing_time: Duration,
+    /// Descripción de la estación, mostrada en los reportes
+    pub description: Option<String>,
+    /// Color con el que las interfaces representan la estación (por
+    /// ejemplo, `#1c7ed6`), exportado en el reporte JSON
+    pub color: Option<String>,
+    /// Sensibilidad del tiempo de procesamiento al tamaño del producto: el
+    /// tiempo se multiplica por `tamaño ^ sensibilidad`, de modo que 1.0
+    /// (por defecto) lo hace proporcional al tamaño y 0.0 lo ignora
+    pub size_sensitivity: f64,
+    /// Tipo de la estación: de procesamiento (por defecto) o de inspección
+    pub kind: StationKind,
+}
+
+impl StationConfig {
+    /// Crea la configuración de una estación sin metadatos de presentación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Nombre de la estación
+    /// * `processing_time` - Tiempo de procesamiento por producto
+    pub fn new(name: impl Into<String>, processing_time: Duration) -> Self {
+        Self {
+            name: name.into(),
+            processing_time,
+            description: None,
+            color: None,
+            size_sensitivity: 1.0,
+            kind: StationKind::Processing,
+        }
+    }
+
+    /// Crea una estación de inspección con un tiempo de servicio casi nulo
+    /// ([`INSPECTION_TIME`]).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Nombre de la estación
+    /// * `inspection` - Criterio de aprobación y destinos de la inspección
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{Inspection, StationConfig, StationKind, INSPECTION_TIME};
+    /// 
+    /// let control = StationConfig::inspection("Control", Inspection::metadata("lote", "A"));
+    /// assert_eq!(control.processing_time, INSPECTION_TIME);
+    /// assert!(matches!(control.kind, StationKind::Inspection(_)));
+    /// ```
+    pub fn inspection(name: impl Into<String>, inspection: Inspection) -> Self {
+        Self::new(name, INSPECTION_TIME).with_inspection(inspection)
+    }
+
+    /// Convierte la estación en una estación de inspección, conservando su
+    /// tiempo de servicio.
+    pub fn with_inspection(mut self, inspection: Inspection) -> Self {
+        self.kind = StationKind::Inspection(inspection);
+        self
+    }
+
+    /// Convierte la estación en un divisor que reparte los productos entre
+    /// sus salidas, conservando su tiempo de servicio.
+    pub fn with_splitter(mut self, splitter: Splitter) -> Self {
+        self.kind = StationKind::Splitter(splitter);
+        self
+    }
+
+    /// Convierte la estación en una unión que elige de forma justa entre los
+    /// productos de sus estaciones de origen, conservando su tiempo de
+    /// servicio.
+    pub fn with_merger(mut self, merger: Merger) -> Self {
+        self.kind = StationKind::Merger(merger);
+        self
+    }
+
+    /// Fija a dónde siguen los productos que completa la estación, por
+    /// ejemplo para que el final de una rama paralela salte a la estación
+    /// donde se reúnen las ramas.
+    pub fn with_output(self, target: RouteTarget) -> Self {
+        self.with_splitter(Splitter::round_robin(vec![target]))
+    }
+
+    /// Asigna la descripción de la estación.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Asigna el color de la estación.
+    pub fn with_color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Asigna la sensibilidad de la estación al tamaño de los productos.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la sensibilidad es negativa o no es finita
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::StationConfig;
+    /// 
+    /// // Un producto grande cuesta el doble en Corte y lo mismo en Empaque
+    /// let corte = StationConfig::new("Corte", Duration::from_millis(100));
+    /// let empaque = StationConfig::new("Empaque", Duration::from_millis(100)).with_size_sensitivity(0.0);
+    /// assert_eq!(corte.processing_time_for(2.0), Duration::from_millis(200));
+    /// assert_eq!(empaque.processing_time_for(2.0), Duration::from_millis(100));
+    /// ```
+    pub fn with_size_sensitivity(mut self, sensitivity: f64) -> Self {
+        assert!(
+            sensitivity.is_finite() && sensitivity >= 0.0,
+            "La sensibilidad al tamaño debe ser un número no negativo"
+        );
+        self.size_sensitivity = sensitivity;
+        self
+    }
+
+    /// Calcula el factor por el que se multiplica el tiempo de
+    /// procesamiento de un producto del tamaño indicado.
+    pub fn size_factor(&self, size: f64) -> f64 {
+        size.powf(self.size_sensitivity)
+    }
+
+    /// Calcula el tiempo de procesamiento efectivo de un producto del
+    /// tamaño indicado.
+    pub fn processing_time_for(&self, size: f64) -> Duration {
+        self.processing_time.mul_f64(self.size_factor(size))
+    }
 }
 
 /// Estado mutable de un producto dentro de una estación específica.
 /// 
 /// Almacena las métricas temporales de un producto durante su paso
-/// por una estación particular.
+/// por una estación particular. Los momentos se guardan como tiempo
+/// transcurrido desde el inicio de la simulación, por lo que no dependen
+/// del reloj con el que se ejecutó ni de conservar el instante de inicio.
 #[derive(Debug, Default)]
 pub struct StationState {
     /// Momento en que el producto entró por primera vez a la cola de la estación
-    pub queue_entry: Option<Instant>,
+    pub queue_entry: Option<Duration>,
     /// Momento en que el producto comenzó a ser procesado por primera vez
-    pub first_entry: Option<Instant>,
+    pub first_entry: Option<Duration>,
     /// Momento en que el producto completó totalmente el procesamiento en la estación
-    pub final_exit: Option<Instant>,
+    pub final_exit: Option<Duration>,
     /// Tiempo total acumulado que el producto esperó en cola en esta estación
     pub total_wait: Duration,
+    /// Tiempo que el producto estuvo bloqueado esperando sus dependencias
+    pub dependency_wait: Duration,
+    /// Tiempo que la estación esperó recursos compartidos para el producto
+    pub resource_wait: Duration,
     /// Tiempo de procesamiento restante para completar el producto en esta estación
     pub remaining: Duration,
+    /// Intervalos (inicio, fin) que el producto pasó en la cola de la estación
+    pub queued: Vec<(Duration, Duration)>,
+    /// Intervalos (inicio, fin) de cada porción de procesamiento en la estación
+    pub slices: Vec<(Duration, Duration)>,
+    /// Tiempo de procesamiento agregado por la curva de aprendizaje de la
+    /// estación, incluido en `remaining`
+    pub ramp_up: Duration,
+    /// Retraso que la imprecisión del temporizador de la estación agregó a
+    /// las porciones del producto (incluido en `slices`)
+    pub timer_drift: Duration,
+    /// Recursos compartidos (operadores, herramientas) que la estación usó
+    /// para procesar el producto
+    pub resources: Vec<String>,
+    /// Unidades de recursos (recurso, índice de la unidad) que atendieron
+    /// cada porción, alineadas con `slices`
+    pub slice_units: Vec<Vec<(String, usize)>>,
+    /// Resultado de la inspección en esta visita: `Some(true)` si el
+    /// producto la aprobó, `Some(false)` si no, y `None` si la estación no
+    /// inspecciona. Se decide al crear el producto (ver [`crate::routing`])
+    pub inspection: Option<bool>,
+    /// Salida por la que el producto deja la estación en esta visita, si la
+    /// estación es un divisor (posición en [`Splitter::outputs`])
+    pub output: Option<usize>,
 }
 
 impl StationState {
@@ -56,11 +248,42 @@ impl StationState {
             first_entry: None,
             final_exit: None,
             total_wait: Duration::default(),
+            dependency_wait: Duration::default(),
+            resource_wait: Duration::default(),
             remaining: Duration::default(),
+            queued: Vec::new(),
+            slices: Vec::new(),
+            ramp_up: Duration::default(),
+            timer_drift: Duration::default(),
+            resources: Vec::new(),
+            slice_units: Vec::new(),
+            inspection: None,
+            output: None,
         }
     }
 }
 
+/// Intervalo con el que se revisan las dependencias de productos bloqueados.
+pub(crate) const DEPENDENCY_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Intervalo con el que una estación en pausa revisa sus comandos.
+pub(crate) const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Intervalo con el que una estación sin productos revisa si una estación
+/// hermana le cambió alguno (ver [`crate::jockey`]).
+pub(crate) const JOCKEY_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Ajustes de una estación modificados por comandos durante la ejecución.
+#[derive(Debug, Default)]
+struct Overrides {
+    /// Quantum que reemplaza al del algoritmo
+    quantum: Option<Duration>,
+    /// Tiempo de procesamiento de los productos que lleguen
+    processing_time: Option<Duration>,
+    /// Indica si la estación está en pausa
+    paused: bool,
+}
+
 /// Representa una estación de trabajo física en la línea de ensamblaje.
 /// 
 /// Cada estación se ejecuta en su propio hilo y procesa productos de forma
@@ -72,6 +295,243 @@ pub struct Station {
     pub config: StationConfig,
     /// Algoritmo de planificación que utiliza la estación
     pub algorithm: SchedulingAlgorithm,
+    /// Señal opcional para detener la estación de forma anticipada
+    pub stop_signal: Option<StopSignal>,
+    /// Modelo con el que la estación ejecuta cada porción de procesamiento
+    pub work_model: Arc<dyn WorkModel>,
+    /// Fuente de tiempo con la que la estación registra sus métricas
+    pub clock: Clock,
+    /// Inicio de la simulación, desde el que se miden los momentos que la
+    /// estación registra en los productos. Si no se asigna, se toma el
+    /// momento en que la estación comienza a operar
+    pub(crate) start_time: OnceLock<Instant>,
+    /// Reacción de la estación ante la señal de apagado
+    pub shutdown_mode: ShutdownMode,
+    /// Observadores que reciben los cambios de estado de la estación
+    pub observers: Vec<Arc<dyn StationObserver>>,
+    /// Retornos pendientes de los productos con rutas reentrantes (solo en
+    /// la primera estación, que no finaliza mientras queden retornos)
+    pub pending_returns: Option<Arc<AtomicUsize>>,
+    /// Canal hacia la primera estación por el que la última estación
+    /// recircula los productos con rutas reentrantes
+    pub(crate) loopback: Option<StationSender>,
+    /// Canales directos hacia las estaciones a las que la estación salta
+    /// hacia adelante (ramas de un divisor), por índice de destino
+    pub(crate) branches: Vec<(usize, StationSender)>,
+    /// Recursos compartidos que usa la estación, en orden de adquisición
+    pub(crate) resources: Vec<StationResource>,
+    /// Sonda donde la estación publica su estado para detectar interbloqueos
+    pub(crate) probe: Option<Arc<StationProbe>>,
+    /// Canal por el que la estación recibe comandos durante la ejecución
+    /// (protegido para que la estación pueda compartirse entre los hilos
+    /// del backend asíncrono)
+    pub(crate) commands: Option<Mutex<CommandReceiver>>,
+    /// Ajustes aplicados por los comandos recibidos
+    overrides: Mutex<Overrides>,
+    /// Comandos programados para un momento de la simulación (relativo al
+    /// inicio), en orden; provienen de las reconfiguraciones en caliente
+    scheduled: Mutex<VecDeque<(Duration, StationCommand)>>,
+    /// Productos descartados de la línea, contados junto con el colector
+    pub(crate) discarded: Option<Arc<AtomicUsize>>,
+    /// Retornos pendientes de la línea, que se descuentan al descartar
+    /// productos con rutas reentrantes
+    pub(crate) line_returns: Option<Arc<AtomicUsize>>,
+    /// Disciplina con la que se elige entre los productos en espera
+    pub(crate) discipline: QueueDiscipline,
+    /// Generador con el que la disciplina aleatoria elige el siguiente producto
+    queue_rng: Mutex<SimRng>,
+    /// Estado con el que la unión alterna sus orígenes
+    merge_state: Mutex<MergeState>,
+    /// Longitud de cola a partir de la cual los productos que llegan desisten
+    pub(crate) balking_threshold: Option<usize>,
+    /// Paciencia de los productos que no tienen una propia
+    pub(crate) patience: Option<Duration>,
+    /// Curva de aprendizaje de la estación
+    pub(crate) ramp_up: Option<RampUp>,
+    /// Productos que comenzaron a procesarse en la estación, para ubicarlos
+    /// en la curva de aprendizaje
+    ramp_started: AtomicUsize,
+    /// Calentamiento que la estación necesita tras un período ocioso
+    pub(crate) warm_up: Option<WarmUp>,
+    /// Imprecisión del temporizador con el que la estación mide sus porciones
+    pub(crate) timer_jitter: Option<TimerJitter>,
+    /// Generador de los retrasos del temporizador
+    jitter_rng: Mutex<SimRng>,
+    /// Órdenes de reemplazo donde se publican los productos desechados
+    pub(crate) replacements: Option<Arc<ReplacementOrders>>,
+    /// Registro compacto donde la estación agrega los intervalos de las
+    /// visitas en lugar de guardarlos en cada producto
+    visit_log: Option<Arc<Mutex<VisitLog>>>,
+    /// Tablero donde la estación intercambia productos con sus hermanas
+    pub(crate) jockeying: Option<Arc<JockeyBoard>>,
+}
+
+/// Estadísticas de operación de una estación durante la simulación.
+/// 
+/// Es retornado por [`Station::run`] al finalizar el hilo de la estación,
+/// de modo que la información de la estación sobrevive al hilo y puede
+/// integrarse en las métricas de la simulación.
+#[derive(Clone, Debug, Default)]
+pub struct StationReport {
+    /// Índice de la estación en la línea
+    pub index: usize,
+    /// Nombre de la estación
+    pub name: String,
+    /// Descripción de la estación, si la configuración tiene una
+    pub description: Option<String>,
+    /// Color de la estación, si la configuración tiene uno
+    pub color: Option<String>,
+    /// Productos que completaron su procesamiento en la estación
+    pub products_processed: usize,
+    /// Tiempo total en estado `Busy`: procesando productos
+    pub busy_time: Duration,
+    /// Tiempo total en estado `Idle`: sin productos listos para procesar
+    pub idle_time: Duration,
+    /// Tiempo total en estado `Blocked`: esperando espacio en el buffer de
+    /// la siguiente estación o un recurso compartido
+    pub blocked_time: Duration,
+    /// Número de porciones de procesamiento ejecutadas
+    pub slices_executed: usize,
+    /// Duración solicitada (nominal) total de las porciones ejecutadas
+    pub slice_time: Duration,
+    /// Número de veces que un producto fue interrumpido y reencolado
+    pub preemptions: usize,
+    /// Longitud máxima alcanzada por la cola de espera
+    pub max_queue_length: usize,
+    /// Desfase acumulado entre la duración real de las porciones y su
+    /// duración nominal (en tiempo virtual, solo el retraso inyectado)
+    pub slice_drift: Duration,
+    /// Mayor desfase de una sola porción
+    pub max_slice_drift: Duration,
+    /// Parte del desfase ocurrida dentro del modelo de trabajo, por ejemplo
+    /// porque el sistema operativo despertó tarde al hilo
+    pub work_overrun: Duration,
+    /// Parte del desfase inyectada a propósito por la imprecisión del
+    /// temporizador de la estación (ver [`TimerJitter`])
+    pub injected_drift: Duration,
+    /// Veces que la estación se bloqueó en su punto de espera sin productos
+    /// listos. Como la espera la interrumpen los mensajes y no un sondeo
+    /// periódico, crece con los mensajes recibidos y no con el tiempo
+    /// ocioso (salvo mientras toda la cola espera dependencias o la
+    /// estación está en pausa, casos que se revisan cada pocos milisegundos)
+    pub wakeups: usize,
+    /// Productos que desistieron de unirse a la cola por superar el umbral
+    pub balked: usize,
+    /// Productos que abandonaron la cola tras agotar su paciencia
+    pub reneged: usize,
+    /// Veces que la estación se calentó tras un período ocioso
+    pub warm_ups: usize,
+    /// Tiempo total dedicado a calentamientos (incluido en `busy_time`)
+    pub warm_up_time: Duration,
+    /// Inspecciones aprobadas (solo en estaciones de inspección)
+    pub inspections_passed: usize,
+    /// Inspecciones no aprobadas, incluidos los productos rechazados
+    pub inspections_failed: usize,
+    /// Productos que no aprobaron la inspección y salieron de la línea
+    pub rejected: usize,
+    /// Productos enviados por cada salida (solo en divisores)
+    pub routed: Vec<usize>,
+    /// Productos de la cola que se cambiaron a la de una estación hermana
+    /// (ver [`crate::jockey`])
+    pub jockeyed: usize,
+    /// Cambios de cola entre unidades de recursos mientras la estación
+    /// esperaba una unidad para sus productos
+    pub unit_jockeys: usize,
+    /// Atenciones de cada estación de origen, en orden de la línea (solo en
+    /// uniones)
+    pub merge_sources: Vec<MergeSourceReport>,
+}
+
+/// Atenciones que una unión dio a una de sus estaciones de origen.
+/// 
+/// Un origen se considera omitido cada vez que tenía productos listos en la
+/// cola de la unión y la unión atendió a otro origen; una racha larga de
+/// omisiones indica que el origen está siendo postergado.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MergeSourceReport {
+    /// Estación de origen, o `None` para los productos que llegan desde la
+    /// entrada de la línea
+    pub source: Option<usize>,
+    /// Productos del origen que la unión comenzó a procesar
+    pub merged: usize,
+    /// Tiempo total que esos productos esperaron antes de ser atendidos
+    pub total_wait: Duration,
+    /// Mayor espera de un producto del origen antes de ser atendido
+    pub max_wait: Duration,
+    /// Veces que el origen tenía productos listos y se atendió a otro
+    pub bypassed: usize,
+    /// Mayor cantidad de omisiones seguidas del origen
+    pub max_consecutive_bypassed: usize,
+    /// Omisiones seguidas en curso
+    consecutive_bypassed: usize,
+}
+
+impl MergeSourceReport {
+    /// Espera promedio de los productos del origen.
+    pub fn average_wait(&self) -> Duration {
+        if self.merged == 0 {
+            return Duration::ZERO;
+        }
+        self.total_wait / self.merged as u32
+    }
+}
+
+impl StationReport {
+    /// Calcula la utilización de la estación.
+    /// 
+    /// # Returns
+    /// 
+    /// Fracción del tiempo de vida de la estación que estuvo ocupada (0.0 a 1.0)
+    pub fn utilization(&self) -> f64 {
+        let total = self.busy_time + self.idle_time + self.blocked_time;
+        if total.is_zero() {
+            0.0
+        } else {
+            self.busy_time.as_secs_f64() / total.as_secs_f64()
+        }
+    }
+
+    /// Calcula la parte del desfase atribuible al propio motor de
+    /// simulación (registro de métricas, sincronización, mensajes) y no al
+    /// modelo de trabajo ni al retraso inyectado.
+    pub fn engine_overhead(&self) -> Duration {
+        self.slice_drift.saturating_sub(self.work_overrun + self.injected_drift)
+    }
+
+    /// Calcula la duración medida total de las porciones: la solicitada
+    /// más el desfase.
+    pub fn measured_slice_time(&self) -> Duration {
+        self.slice_time + self.slice_drift
+    }
+
+    /// Calcula cuántas veces más duraron las porciones respecto de lo
+    /// solicitado.
+    /// 
+    /// # Returns
+    /// 
+    /// Duración medida dividida por la solicitada (1.0 si la estación no
+    /// ejecutó porciones)
+    pub fn slice_stretch(&self) -> f64 {
+        if self.slice_time.is_zero() {
+            1.0
+        } else {
+            self.measured_slice_time().as_secs_f64() / self.slice_time.as_secs_f64()
+        }
+    }
+
+    /// Calcula la fracción del tiempo ocupado que corresponde a desfase.
+    /// 
+    /// # Returns
+    /// 
+    /// Desfase dividido por el tiempo ocupado (0.0 si la estación no
+    /// estuvo ocupada)
+    pub fn drift_ratio(&self) -> f64 {
+        if self.busy_time.is_zero() {
+            0.0
+        } else {
+            self.slice_drift.as_secs_f64() / self.busy_time.as_secs_f64()
+        }
+    }
 }
 
 /// Mensajes que se intercambian entre estaciones a través de canales.
@@ -86,24 +546,945 @@ pub enum Message {
     Shutdown,
 }
 
-impl Station {
-    /// Crea una nueva instancia de estación.
+/// Reacción de las estaciones ante la señal de apagado.
+/// 
+/// La señal de apagado llega cuando el generador liberó todas sus
+/// llegadas. Por defecto cada estación termina los productos que le quedan
+/// antes de reenviarla; los demás modos abandonan la cola, de modo que los
+/// productos sin terminar se reportan en `incomplete_products` con las
+/// estaciones que alcanzaron a completar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShutdownMode {
+    /// Procesa todos los productos en cola antes de reenviar la señal
+    #[default]
+    DrainQueues,
+    /// Termina la porción en curso, abandona la cola y reenvía la señal
+    Immediate,
+    /// Sigue procesando hasta que pasa el plazo desde que la estación
+    /// recibió la señal; luego abandona la cola como `Immediate`
+    AfterDeadline(Duration),
+}
+
+impl ShutdownMode {
+    /// Calcula el momento a partir del cual la estación abandona su cola.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `shutdown_at` - Momento en que la estación recibió la señal de apagado
+    /// 
+    /// # Returns
+    /// 
+    /// `None` si la estación drena su cola o aún no recibió la señal
+    pub fn deadline(&self, shutdown_at: Option<Instant>) -> Option<Instant> {
+        let shutdown_at = shutdown_at?;
+        match self {
+            Self::DrainQueues => None,
+            Self::Immediate => Some(shutdown_at),
+            Self::AfterDeadline(deadline) => Some(shutdown_at + *deadline),
+        }
+    }
+}
+
+impl fmt::Display for ShutdownMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DrainQueues => write!(f, "drenar colas"),
+            Self::Immediate => write!(f, "inmediato"),
+            Self::AfterDeadline(deadline) => write!(f, "drenar por {}", format_duration(*deadline)),
+        }
+    }
+}
+
+/// Curva de aprendizaje de una estación.
+/// 
+/// Los primeros productos que procesa la estación tardan más que su tiempo
+/// nominal, por ejemplo mientras el operario se familiariza con la tarea: el
+/// primero toma `initial_multiplier` veces su tiempo y el multiplicador
+/// decrece linealmente hasta 1.0, que se alcanza a partir del producto
+/// número `products + 1`. El tiempo agregado forma parte del servicio del
+/// producto, por lo que no se cuenta como espera y sí se refleja en el
+/// slowdown.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::RampUp;
+/// 
+/// let ramp_up = RampUp::new(4, 2.0);
+/// assert_eq!(ramp_up.multiplier(0), 2.0);
+/// assert_eq!(ramp_up.multiplier(2), 1.5);
+/// assert_eq!(ramp_up.multiplier(4), 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RampUp {
+    /// Cantidad de productos afectados por la curva
+    pub products: usize,
+    /// Multiplicador del tiempo nominal del primer producto
+    pub initial_multiplier: f64,
+}
+
+impl RampUp {
+    /// Crea una curva de aprendizaje.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `products` - Cantidad de productos que tardan más que el nominal
+    /// * `initial_multiplier` - Multiplicador del primer producto
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `products` es cero o si el multiplicador no es un
+    /// número finito mayor o igual que 1.0
+    pub fn new(products: usize, initial_multiplier: f64) -> Self {
+        assert!(products > 0, "La curva de aprendizaje debe afectar al menos a un producto");
+        assert!(
+            initial_multiplier.is_finite() && initial_multiplier >= 1.0,
+            "El multiplicador inicial debe ser mayor o igual que 1.0"
+        );
+        Self {
+            products,
+            initial_multiplier,
+        }
+    }
+
+    /// Calcula el multiplicador del tiempo nominal de un producto.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `position` - Posición del producto entre los que inició la
+    ///   estación (desde 0)
+    pub fn multiplier(&self, position: usize) -> f64 {
+        if position >= self.products {
+            return 1.0;
+        }
+        let pending = (self.products - position) as f64 / self.products as f64;
+        1.0 + (self.initial_multiplier - 1.0) * pending
+    }
+
+    /// Calcula el tiempo que la curva agrega al tiempo nominal de un producto.
+    pub(crate) fn extra_time(&self, position: usize, nominal: Duration) -> Duration {
+        nominal.mul_f64(self.multiplier(position) - 1.0)
+    }
+}
+
+impl fmt::Display for RampUp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "x{:.2} decreciente hasta x1.00 en {} productos",
+            self.initial_multiplier,
+            self.products
+        )
+    }
+}
+
+/// Calentamiento de una estación tras un período ocioso.
+/// 
+/// Algunas estaciones, como un horno de soldadura, se enfrían si pasan
+/// demasiado tiempo sin trabajo: cuando la estación estuvo ociosa más de
+/// `idle_threshold`, antes de atender al siguiente producto dedica
+/// `duration` a calentarse. Durante el calentamiento la estación está
+/// ocupada (consume tiempo y energía como al procesar) y el producto sigue
+/// en la cola, por lo que la demora cuenta como espera.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::WarmUp;
+/// 
+/// let warm_up = WarmUp::new(Duration::from_secs(60), Duration::from_secs(5));
+/// assert!(!warm_up.needed_after(Duration::from_secs(60)));
+/// assert!(warm_up.needed_after(Duration::from_secs(61)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WarmUp {
+    /// Tiempo ocioso a partir del cual la estación se enfría
+    pub idle_threshold: Duration,
+    /// Duración del calentamiento
+    pub duration: Duration,
+}
+
+impl WarmUp {
+    /// Crea un calentamiento.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `idle_threshold` - Tiempo ocioso que la estación tolera sin enfriarse
+    /// * `duration` - Tiempo que tarda en calentarse
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la duración del calentamiento es cero
+    pub fn new(idle_threshold: Duration, duration: Duration) -> Self {
+        assert!(!duration.is_zero(), "La duración del calentamiento debe ser mayor que cero");
+        Self {
+            idle_threshold,
+            duration,
+        }
+    }
+
+    /// Indica si la estación debe calentarse tras estar ociosa `idle`.
+    pub fn needed_after(&self, idle: Duration) -> bool {
+        idle > self.idle_threshold
+    }
+}
+
+impl fmt::Display for WarmUp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} tras más de {} ociosa",
+            format_duration(self.duration),
+            format_duration(self.idle_threshold)
+        )
+    }
+}
+
+/// Imprecisión del temporizador de una estación.
+/// 
+/// Los temporizadores reales no despiertan justo al vencer: con esta
+/// opción cada porción de procesamiento de la estación se alarga un retraso
+/// aleatorio, uniforme entre cero y `max_overshoot`, como si el hilo
+/// despertara tarde. Sirve para estudiar cuán robusta es la planificación
+/// por quantum ante ese ruido. El retraso ocupa a la estación y forma parte
+/// de la porción, pero se reporta aparte de la espera
+/// ([`StationReport::injected_drift`] y
+/// [`ProductMetrics::timer_drift`](crate::ProductMetrics::timer_drift)).
+/// 
+/// Con la misma semilla los retrasos se repiten en cada ejecución; cada
+/// estación combina la semilla con su índice, de modo que dos estaciones
+/// con la misma imprecisión no se retrasan a la vez.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::TimerJitter;
+/// 
+/// let jitter = TimerJitter::uniform(Duration::from_millis(5)).with_seed(7);
+/// assert_eq!(jitter.to_string(), "hasta 0.005s de retraso por porción (semilla 7)");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimerJitter {
+    /// Mayor retraso que puede sufrir una porción
+    pub max_overshoot: Duration,
+    /// Semilla de la secuencia de retrasos
+    pub seed: u64,
+}
+
+impl TimerJitter {
+    /// Crea una imprecisión con retrasos uniformes entre cero y
+    /// `max_overshoot`, con semilla 0.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el retraso máximo es cero
+    pub fn uniform(max_overshoot: Duration) -> Self {
+        assert!(!max_overshoot.is_zero(), "El retraso máximo del temporizador debe ser mayor que cero");
+        Self { max_overshoot, seed: 0 }
+    }
+
+    /// Asigna la semilla de la secuencia de retrasos.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Genera el retraso de una porción.
+    pub(crate) fn sample(&self, rng: &mut SimRng) -> Duration {
+        self.max_overshoot.mul_f64(rng.next_f64())
+    }
+}
+
+impl fmt::Display for TimerJitter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hasta {} de retraso por porción (semilla {})",
+            format_duration(self.max_overshoot),
+            self.seed
+        )
+    }
+}
+
+impl Station {
+    /// Crea una nueva instancia de estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `index` - Índice único de la estación en la línea de ensamblaje
+    /// * `config` - Configuración estática de la estación
+    /// * `algorithm` - Algoritmo de planificación a utilizar
+    /// 
+    /// # Returns
+    /// 
+    /// Una nueva instancia de `Station`
+    pub fn new(index: usize, config: StationConfig, algorithm: SchedulingAlgorithm) -> Self {
+        Self {
+            index,
+            config,
+            algorithm,
+            stop_signal: None,
+            work_model: Arc::new(SleepWork),
+            clock: Clock::Real,
+            start_time: OnceLock::new(),
+            shutdown_mode: ShutdownMode::DrainQueues,
+            observers: Vec::new(),
+            pending_returns: None,
+            loopback: None,
+            branches: Vec::new(),
+            resources: Vec::new(),
+            probe: None,
+            commands: None,
+            overrides: Mutex::default(),
+            scheduled: Mutex::default(),
+            discarded: None,
+            line_returns: None,
+            discipline: QueueDiscipline::Fifo,
+            queue_rng: Mutex::new(SimRng::new(0)),
+            merge_state: Mutex::default(),
+            balking_threshold: None,
+            patience: None,
+            ramp_up: None,
+            ramp_started: AtomicUsize::new(0),
+            warm_up: None,
+            timer_jitter: None,
+            jitter_rng: Mutex::new(SimRng::new(0)),
+            replacements: None,
+            visit_log: None,
+            jockeying: None,
+        }
+    }
+
+    /// Asocia una señal de parada anticipada a la estación.
+    /// 
+    /// Cuando la señal se activa, la estación termina la porción en curso y
+    /// finaliza sin procesar los productos que queden en su cola.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `signal` - Señal compartida de parada
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con la señal configurada
+    pub fn with_stop_signal(mut self, signal: StopSignal) -> Self {
+        self.stop_signal = Some(signal);
+        self
+    }
+
+    /// Asigna el modelo de trabajo de la estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `work_model` - Modelo que ejecuta cada porción de procesamiento
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con el modelo configurado
+    pub fn with_work_model(mut self, work_model: Arc<dyn WorkModel>) -> Self {
+        self.work_model = work_model;
+        self
+    }
+
+    /// Asigna la fuente de tiempo de la estación.
+    /// 
+    /// Un reloj virtual solo tiene sentido cuando un ejecutor lo hace
+    /// avanzar (ver `Simulation::run_virtual`); el ciclo con hilos de
+    /// [`Station::run`] usa siempre el tiempo real.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `clock` - Reloj a utilizar
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con el reloj configurado
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Asigna el inicio de la simulación.
+    /// 
+    /// Los momentos de entrada, salida y cada porción que la estación
+    /// registra en los productos se miden desde este instante.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `start_time` - Instante de inicio de la simulación, medido con el
+    ///   mismo reloj que la estación
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con el inicio configurado
+    pub fn with_start_time(self, start_time: Instant) -> Self {
+        let _ = self.start_time.set(start_time);
+        self
+    }
+
+    /// Asigna la reacción de la estación ante la señal de apagado.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - Modo de apagado
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con el modo configurado
+    pub fn with_shutdown_mode(mut self, mode: ShutdownMode) -> Self {
+        self.shutdown_mode = mode;
+        self
+    }
+
+    /// Asigna la disciplina con la que la estación elige entre los productos
+    /// en espera.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `discipline` - Disciplina de cola
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con la disciplina configurada
+    pub fn with_discipline(mut self, discipline: QueueDiscipline) -> Self {
+        if let QueueDiscipline::Random { seed } = discipline {
+            self.queue_rng = Mutex::new(SimRng::new(seed));
+        }
+        self.discipline = discipline;
+        self
+    }
+
+    /// Configura la impaciencia de los productos en la estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `balking_threshold` - Longitud de cola a partir de la cual los
+    ///   productos que llegan desisten de unirse (`None` = sin límite)
+    /// * `patience` - Espera máxima en cola de los productos sin paciencia
+    ///   propia (`None` = esperan indefinidamente)
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con la impaciencia configurada
+    pub fn with_impatience(mut self, balking_threshold: Option<usize>, patience: Option<Duration>) -> Self {
+        self.balking_threshold = balking_threshold;
+        self.patience = patience;
+        self
+    }
+
+    /// Asigna la curva de aprendizaje de la estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `ramp_up` - Curva de aprendizaje (`None` = tiempo nominal desde el
+    ///   primer producto)
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con la curva configurada
+    pub fn with_ramp_up(mut self, ramp_up: Option<RampUp>) -> Self {
+        self.ramp_up = ramp_up;
+        self
+    }
+
+    /// Asigna el calentamiento que la estación necesita tras estar ociosa.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `warm_up` - Calentamiento (`None` = la estación nunca se enfría)
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con el calentamiento configurado
+    pub fn with_warm_up(mut self, warm_up: Option<WarmUp>) -> Self {
+        self.warm_up = warm_up;
+        self
+    }
+
+    /// Asigna la imprecisión del temporizador de la estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `jitter` - Imprecisión (`None` = las porciones duran lo nominal)
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con la imprecisión configurada
+    pub fn with_timer_jitter(mut self, jitter: Option<TimerJitter>) -> Self {
+        if let Some(jitter) = jitter {
+            self.jitter_rng = Mutex::new(SimRng::new(jitter.seed.wrapping_add(self.index as u64)));
+        }
+        self.timer_jitter = jitter;
+        self
+    }
+
+    /// Genera el retraso del temporizador para la próxima porción.
+    /// 
+    /// # Returns
+    /// 
+    /// El tiempo que la porción debe durar además del nominal (cero si la
+    /// estación no tiene imprecisión configurada)
+    pub(crate) fn timer_overshoot(&self) -> Duration {
+        self.timer_jitter
+            .map(|jitter| jitter.sample(&mut lock_recover(&self.jitter_rng)))
+            .unwrap_or_default()
+    }
+
+    /// Inicia el calentamiento de la estación si estuvo ociosa más del
+    /// umbral configurado.
+    /// 
+    /// La estación pasa a `Busy` y registra el calentamiento en su reporte;
+    /// quien la llama debe dejar transcurrir la duración retornada antes
+    /// de atender al producto.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product` - Producto que la estación está por atender
+    /// * `status` - Máquina de estados de la estación
+    /// * `report` - Estadísticas de la estación
+    /// 
+    /// # Returns
+    /// 
+    /// La duración del calentamiento, o `None` si no hace falta
+    pub(crate) fn start_warm_up(
+        &self,
+        product: &Product,
+        status: &mut StatusMachine,
+        report: &mut StationReport,
+    ) -> Option<Duration> {
+        let warm_up = self.warm_up?;
+        let now = self.clock.now();
+        let idle = status.idle_for(now)?;
+        if !warm_up.needed_after(idle) {
+            return None;
+        }
+        println!(
+            "[{}] Calentando por {} tras {} ociosa (producto {:02} en espera)",
+            self.config.name,
+            format_duration(warm_up.duration),
+            format_duration(idle),
+            product.id
+        );
+        status.transition(StationStatus::Busy, now, report);
+        report.warm_ups += 1;
+        report.warm_up_time += warm_up.duration;
+        Some(warm_up.duration)
+    }
+
+    /// Indica si venció el plazo de apagado y la estación debe finalizar
+    /// aunque le queden productos en cola o retornos pendientes.
+    pub(crate) fn shutdown_expired(&self, shutdown_at: Option<Instant>) -> bool {
+        self.shutdown_mode
+            .deadline(shutdown_at)
+            .is_some_and(|deadline| self.clock.now() >= deadline)
+    }
+
+    /// Convierte un instante del reloj de la estación en el tiempo
+    /// transcurrido desde el inicio de la simulación.
+    pub(crate) fn since_start(&self, instant: Instant) -> Duration {
+        instant.saturating_duration_since(*self.start_time.get_or_init(|| instant))
+    }
+
+    /// Agrega un observador de los cambios de estado de la estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `observer` - Observador a notificar
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con el observador agregado
+    pub fn with_observer(mut self, observer: Arc<dyn StationObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Asigna el contador de retornos pendientes de la línea.
+    /// 
+    /// Solo corresponde a la primera estación: cada producto liberado suma
+    /// sus retornos (ver [`Product::reentries`]) y la estación descuenta uno
+    /// por cada producto recirculado que recibe. Mientras queden retornos
+    /// pendientes la estación no finaliza, aunque haya recibido la señal de
+    /// apagado.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pending_returns` - Contador compartido con los generadores
+    /// 
+    /// # Returns
+    /// 
+    /// La estación con el contador configurado
+    pub fn with_pending_returns(mut self, pending_returns: Arc<AtomicUsize>) -> Self {
+        self.pending_returns = Some(pending_returns);
+        self
+    }
+
+    /// Asigna el canal de recirculación de la última estación.
+    pub(crate) fn with_loopback(mut self, loopback: StationSender) -> Self {
+        self.loopback = Some(loopback);
+        self
+    }
+
+    /// Agrega un canal directo hacia una estación posterior de la línea.
+    /// 
+    /// Los productos cuya siguiente visita es esa estación se le envían
+    /// directamente, sin pasar por las estaciones intermedias, de modo que
+    /// una rama paralela no espera a las estaciones de otra rama.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `station` - Índice de la estación de destino
+    /// * `sender` - Canal de entrada de esa estación
+    pub fn with_branch(mut self, station: usize, sender: StationSender) -> Self {
+        self.branches.push((station, sender));
+        self
+    }
+
+    /// Asigna el tablero de cambios de cola, si la estación tiene hermanas.
+    pub(crate) fn with_jockeying(mut self, board: Option<Arc<JockeyBoard>>) -> Self {
+        self.jockeying = board.filter(|board| board.participates(self.index));
+        self
+    }
+
+    /// Asigna los recursos compartidos que usa la estación.
+    pub(crate) fn with_resources(mut self, resources: Vec<StationResource>) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// Asigna la sonda de estado en vivo de la estación, que también se
+    /// registra como observador de sus cambios de estado.
+    pub(crate) fn with_probe(mut self, probe: Arc<StationProbe>) -> Self {
+        self.observers.push(Arc::clone(&probe) as Arc<dyn StationObserver>);
+        self.probe = Some(probe);
+        self
+    }
+
+    /// Asigna el canal de comandos de la estación y los contadores con los
+    /// que descuenta los productos que descarta.
+    pub(crate) fn with_commands(
+        mut self,
+        commands: CommandReceiver,
+        discarded: Arc<AtomicUsize>,
+        line_returns: Option<Arc<AtomicUsize>>,
+    ) -> Self {
+        self.commands = Some(Mutex::new(commands));
+        self.discarded = Some(discarded);
+        self.line_returns = line_returns;
+        self
+    }
+
+    /// Asigna las órdenes de reemplazo en las que la estación publica los
+    /// productos que desecha y descuenta los que salen de la línea.
+    pub(crate) fn with_replacements(mut self, replacements: Option<Arc<ReplacementOrders>>) -> Self {
+        self.replacements = replacements;
+        self
+    }
+
+    /// Asigna el registro compacto en el que la estación agrega los
+    /// intervalos de espera y procesamiento (ver [`StorageMode::Compact`]).
+    /// 
+    /// [`StorageMode::Compact`]: crate::storage::StorageMode::Compact
+    #[cfg(feature = "async")]
+    pub(crate) fn with_visit_log(mut self, visit_log: Option<Arc<Mutex<VisitLog>>>) -> Self {
+        self.visit_log = visit_log;
+        self
+    }
+
+    /// Programa comandos para momentos de la simulación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `commands` - Pares (momento relativo al inicio, comando), en orden
+    pub(crate) fn with_scheduled_commands(self, commands: Vec<(Duration, StationCommand)>) -> Self {
+        lock_recover(&self.scheduled).extend(commands);
+        self
+    }
+
+    /// Aplica los comandos programados cuyo momento ya llegó y los
+    /// recibidos desde la última revisión.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `queue` - Cola de la estación, que `FlushQueue` vacía
+    pub(crate) fn apply_commands(&self, queue: &mut VecDeque<Arc<Product>>) {
+        let due: Vec<StationCommand> = {
+            let mut scheduled = lock_recover(&self.scheduled);
+            let now = match scheduled.front() {
+                Some(_) => self.since_start(self.clock.now()),
+                None => Duration::ZERO,
+            };
+            let count = scheduled.iter().take_while(|(at, _)| *at <= now).count();
+            scheduled.drain(..count).map(|(_, command)| command).collect()
+        };
+        for command in due {
+            println!("[{}] Reconfiguración aplicada: {}", self.config.name, command);
+            self.apply_command(command, queue);
+        }
+
+        if let Some(commands) = &self.commands {
+            let commands = lock_recover(commands);
+            while let Some(command) = commands.try_recv() {
+                println!("[{}] Comando recibido: {}", self.config.name, command);
+                self.apply_command(command, queue);
+            }
+        }
+    }
+
+    /// Aplica un comando a la estación.
+    fn apply_command(&self, command: StationCommand, queue: &mut VecDeque<Arc<Product>>) {
+        let mut overrides = lock_recover(&self.overrides);
+        match command {
+            StationCommand::SetQuantum(quantum) => overrides.quantum = Some(quantum),
+            StationCommand::SetProcessingTime(processing_time) => {
+                overrides.processing_time = Some(processing_time)
+            }
+            StationCommand::Pause => overrides.paused = true,
+            StationCommand::Resume => overrides.paused = false,
+            StationCommand::FlushQueue => {
+                println!("[{}] {} productos descartados de la cola", self.config.name, queue.len());
+                for product in queue.drain(..) {
+                    self.discard(&product);
+                }
+                self.publish(|probe| probe.set_queue(queue));
+            }
+        }
+    }
+
+    /// Indica si la estación está en pausa.
+    pub(crate) fn is_paused(&self) -> bool {
+        lock_recover(&self.overrides).paused
+    }
+
+    /// Descuenta de la línea un producto descartado, junto con los retornos
+    /// que le quedaban a la primera estación.
+    fn discard(&self, product: &Product) {
+        if let Some(discarded) = &self.discarded {
+            discarded.fetch_add(1, Ordering::SeqCst);
+        }
+        if let Some(replacements) = &self.replacements {
+            replacements.settle();
+        }
+        if let Some(returns) = &self.line_returns {
+            returns.fetch_sub(product.pending_reentries(), Ordering::SeqCst);
+        }
+    }
+
+    /// Publica un producto desechado para que el generador lo reponga, si
+    /// la simulación reemplaza los productos desechados.
+    fn scrap(&self, product: &Arc<Product>) {
+        if let Some(replacements) = &self.replacements {
+            replacements.scrap(product);
+        }
+    }
+
+    /// Encola un producto que llegó a la estación, salvo que desista.
+    /// 
+    /// El producto desiste si, al recibirlo, la cola ya alcanzó el umbral de
+    /// la estación; en ese caso sale de la línea y se reporta como perdido
+    /// en el momento en que llegó a la estación.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` si el producto se encoló
+    pub(crate) fn admit(
+        &self,
+        product: Arc<Product>,
+        queue: &mut VecDeque<Arc<Product>>,
+        report: &mut StationReport,
+    ) -> bool {
+        if self.balking_threshold.is_some_and(|threshold| queue.len() >= threshold) {
+            let at = lock_recover(product.current_state())
+                .queue_entry
+                .take()
+                .unwrap_or_else(|| self.since_start(self.clock.now()));
+            product.mark_lost(ProductLoss { station: self.index, reason: LossReason::Balked, at });
+            self.scrap(&product);
+            self.discard(&product);
+            report.balked += 1;
+            println!(
+                "[{}] Producto {:02} desistió: la cola tiene {} productos",
+                self.config.name,
+                product.id,
+                queue.len()
+            );
+            return false;
+        }
+        self.register_arrival(&product);
+        enqueue(queue, product);
+        report.max_queue_length = report.max_queue_length.max(queue.len());
+        true
+    }
+
+    /// Retira de la cola los productos que agotaron su paciencia.
     /// 
-    /// # Arguments
+    /// Solo abandonan la cola los productos que aún no comenzaron a
+    /// procesarse en esta visita; la pérdida se registra en el momento en que
+    /// se agotó la paciencia, aunque la estación lo detecte después (por
+    /// ejemplo, al terminar la porción en curso).
     /// 
-    /// * `index` - Índice único de la estación en la línea de ensamblaje
-    /// * `config` - Configuración estática de la estación
-    /// * `algorithm` - Algoritmo de planificación a utilizar
+    /// # Returns
+    /// 
+    /// `true` si algún producto abandonó la cola
+    pub(crate) fn renege(&self, queue: &mut VecDeque<Arc<Product>>, report: &mut StationReport) -> bool {
+        if self.patience.is_none() && queue.iter().all(|product| product.patience.is_none()) {
+            return false;
+        }
+        let reneged = report.reneged;
+        let now = self.since_start(self.clock.now());
+        queue.retain(|product| {
+            let Some(patience) = product.patience.or(self.patience) else {
+                return true;
+            };
+            let mut station_state = lock_recover(product.current_state());
+            let expired = match (station_state.first_entry, station_state.queue_entry) {
+                (None, Some(queue_entry)) if now.saturating_sub(queue_entry) > patience => queue_entry + patience,
+                _ => return true,
+            };
+            let queue_entry = station_state.queue_entry.take().unwrap_or(expired);
+            station_state.total_wait += expired.saturating_sub(queue_entry);
+            match &self.visit_log {
+                Some(visit_log) => lock_recover(visit_log).record_abandoned(product, (queue_entry, expired)),
+                None => station_state.queued.push((queue_entry, expired)),
+            }
+            drop(station_state);
+
+            product.mark_lost(ProductLoss { station: self.index, reason: LossReason::Reneged, at: expired });
+            self.scrap(product);
+            self.discard(product);
+            report.reneged += 1;
+            println!(
+                "[{}] Producto {:02} abandonó la cola tras esperar {}",
+                self.config.name,
+                product.id,
+                format_duration(patience)
+            );
+            false
+        });
+        report.reneged > reneged
+    }
+
+    /// Cambia productos de la cola a la de una estación hermana según la
+    /// política de cambios de cola (ver [`crate::jockey`]).
+    /// 
+    /// Primero encola los productos que las hermanas cambiaron a esta
+    /// estación; luego, mientras la política lo permita, cambia el último
+    /// producto de la cola que aún no comenzó a procesarse en esta visita.
     /// 
     /// # Returns
     /// 
-    /// Una nueva instancia de `Station`
-    pub fn new(index: usize, config: StationConfig, algorithm: SchedulingAlgorithm) -> Self {
-        Self {
-            index,
-            config,
-            algorithm,
+    /// `true` si la cola cambió
+    fn jockey(&self, queue: &mut VecDeque<Arc<Product>>, report: &mut StationReport) -> bool {
+        let Some(board) = &self.jockeying else {
+            return false;
+        };
+        let arrivals = board.take_arrivals(self.index);
+        let mut changed = !arrivals.is_empty();
+        self.receive_jockeys(arrivals, queue, report);
+
+        while let Some(position) = queue
+            .iter()
+            .rposition(|product| lock_recover(product.current_state()).first_entry.is_none())
+        {
+            let Some(sibling) = board.best_sibling(self.index, queue.len(), &queue[position]) else {
+                break;
+            };
+            let product = queue.remove(position).expect("La posición está en la cola");
+            let product_id = product.id;
+            match board.try_move(queue.len() + 1, product, sibling) {
+                Ok(()) => {
+                    changed = true;
+                    report.jockeyed += 1;
+                    println!(
+                        "[{}] Producto {:02} se cambia a la cola de '{}'",
+                        self.config.name,
+                        product_id,
+                        board.name(sibling.station)
+                    );
+                }
+                Err(product) => {
+                    queue.insert(position, product);
+                    break;
+                }
+            }
         }
+        board.set_load(self.index, queue.len());
+        changed
+    }
+
+    /// Encola los productos que una estación hermana cambió a esta.
+    /// 
+    /// Conservan su entrada a la cola, por lo que su espera incluye la de
+    /// la cola que dejaron.
+    fn receive_jockeys(&self, arrivals: Vec<Arc<Product>>, queue: &mut VecDeque<Arc<Product>>, report: &mut StationReport) {
+        for product in arrivals {
+            self.register_arrival(&product);
+            enqueue(queue, product);
+            report.max_queue_length = report.max_queue_length.max(queue.len());
+        }
+    }
+
+    /// Registra la entrada del producto a la cola de su siguiente visita en
+    /// el momento en que se envía, de modo que la espera incluya el tiempo
+    /// en el canal aunque el destino lo reciba más tarde.
+    pub(crate) fn stamp_queue_entry(&self, product: &Product) {
+        if product.is_completed() {
+            return;
+        }
+        let mut next_state = lock_recover(product.current_state());
+        if next_state.queue_entry.is_none() && next_state.first_entry.is_none() {
+            next_state.queue_entry = Some(self.since_start(self.clock.now()));
+        }
+    }
+
+    /// Calcula la duración de la siguiente porción, respetando el quantum
+    /// asignado por comando si lo hay.
+    fn calculate_quantum(&self, remaining: Duration) -> Duration {
+        match lock_recover(&self.overrides).quantum {
+            Some(quantum) => remaining.min(quantum),
+            None => self.algorithm.calculate_quantum(remaining),
+        }
+    }
+
+    /// Publica un cambio en la sonda de estado, si la estación tiene una.
+    fn publish(&self, update: impl FnOnce(&StationProbe)) {
+        if let Some(probe) = &self.probe {
+            update(probe);
+        }
+    }
+
+    /// Indica si la estación debe seguir esperando productos recirculados.
+    pub(crate) fn awaiting_returns(&self) -> bool {
+        self.pending_returns
+            .as_ref()
+            .is_some_and(|pending| pending.load(Ordering::SeqCst) > 0)
+    }
+
+    /// Descuenta un retorno pendiente si el producto recibido fue recirculado.
+    /// 
+    /// Los productos nuevos llegan sin visitas completadas, por lo que un
+    /// producto con visitas completadas en la primera estación solo puede
+    /// venir del canal de recirculación.
+    pub(crate) fn register_return(&self, product: &Product) {
+        if let Some(pending) = &self.pending_returns {
+            if product.completed_visits() > 0 {
+                pending.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Crea la máquina de estados de una ejecución de la estación.
+    pub(crate) fn status_machine(&self, started_at: Instant) -> StatusMachine {
+        StatusMachine::new(self.index, self.config.name.clone(), started_at, self.observers.clone())
+    }
+
+    /// Indica si se solicitó detener la simulación de forma anticipada.
+    fn stop_requested(&self) -> bool {
+        self.stop_signal
+            .as_ref()
+            .is_some_and(StopSignal::is_stop_requested)
     }
 
     /// Ejecuta el bucle principal de procesamiento de la estación.
@@ -112,38 +1493,93 @@ impl Station {
     /// 1. Recibe productos desde la estación anterior (o generador)
     /// 2. Los encola internamente según el algoritmo de planificación
     /// 3. Los procesa aplicando el quantum correspondiente
-    /// 4. Los envía a la siguiente estación o al colector final
+    /// 4. Los envía a la siguiente estación (o directamente a la estación de
+    ///    una rama, ver [`Station::with_branch`]), los recircula a la primera
+    ///    estación (rutas reentrantes) o los envía al colector final
     /// 5. Maneja las señales de apagado de forma ordenada
     /// 
+    /// Durante el ciclo la estación pasa por los estados `Idle`, `Busy` y
+    /// `Blocked` y termina en `Down`; cada cambio se publica a sus
+    /// observadores y el tiempo en cada estado se acumula en el reporte.
+    /// 
     /// # Arguments
     /// 
     /// * `receiver` - Canal para recibir productos y señales de la estación anterior
     /// * `next_sender` - Canal opcional para enviar productos a la siguiente estación
     /// * `collector` - Canal opcional para enviar productos completados al colector final
     /// 
+    /// # Returns
+    /// 
+    /// `StationReport` con las estadísticas de operación de la estación
+    /// 
+    /// Si el canal de recepción se cierra sin haber recibido la señal de
+    /// apagado (por una falla aguas arriba), la estación finaliza de inmediato
+    /// y cierra sus canales de salida para propagar el apagado.
+    /// 
     /// # Panics
     /// 
-    /// La función puede hacer panic si:
-    /// - El canal de recepción se cierra inesperadamente
-    /// - No se puede enviar un producto o señal a través de los canales de salida
+    /// La función puede hacer panic si no se puede enviar un producto o señal
+    /// a través de los canales de salida
     pub fn run(
         &self,
-        receiver: mpsc::Receiver<Message>,
-        next_sender: Option<mpsc::Sender<Message>>,
+        receiver: StationReceiver,
+        next_sender: Option<StationSender>,
         collector: Option<mpsc::Sender<Arc<Product>>>,
-    ) {
-        let mut queue: VecDeque<Arc<Product>> = VecDeque::new();
-        let mut shutdown_received = false;
+    ) -> StationReport {
+        let outputs = Outputs { next_sender, collector };
+        let started_at = self.clock.now();
+        self.start_time.get_or_init(|| started_at);
+        let mut state = RunState {
+            queue: VecDeque::new(),
+            shutdown_received: false,
+            shutdown_at: None,
+            disconnected: false,
+            status: self.status_machine(started_at),
+            report: self.new_report(),
+            #[cfg(feature = "crossbeam")]
+            commands: self.commands.as_ref().and_then(|commands| lock_recover(commands).selectable()),
+        };
 
         println!("[INFO] Estación '{}' iniciada", self.config.name);
 
         loop {
-            // Si la cola está vacía, esperamos por mensajes
-            if queue.is_empty() {
-                if shutdown_received {
+            // Si se solicitó una parada anticipada, abandonamos la cola
+            if self.stop_requested() {
+                println!(
+                    "[INFO] Estación '{}' detenida ({} productos en cola)",
+                    self.config.name,
+                    state.queue.len()
+                );
+                break;
+            }
+
+            // Con un modo de apagado que no drena, vencido el plazo se
+            // abandonan la cola y los retornos pendientes
+            if (!state.queue.is_empty() || self.awaiting_returns()) && self.shutdown_expired(state.shutdown_at) {
+                if let Some(sender) = &outputs.next_sender {
+                    sender
+                        .send(Message::Shutdown)
+                        .expect("No se pudo reenviar señal de apagado");
+                }
+                println!(
+                    "[INFO] Estación '{}' finalizando sin procesar {} productos en cola",
+                    self.config.name,
+                    state.queue.len()
+                );
+                break;
+            }
+
+            if state.queue.is_empty() {
+                if state.shutdown_received && !self.awaiting_returns() {
+                    // Antes de terminar se atienden los productos que una
+                    // estación hermana haya cambiado a esta
+                    if let Some(Err(arrivals)) = self.jockeying.as_ref().map(|board| board.close(self.index)) {
+                        self.receive_jockeys(arrivals, &mut state.queue, &mut state.report);
+                        continue;
+                    }
                     // Si ya recibimos la señal de apagado y no hay productos en cola,
                     // reenviamos la señal y terminamos
-                    if let Some(sender) = &next_sender {
+                    if let Some(sender) = &outputs.next_sender {
                         sender
                             .send(Message::Shutdown)
                             .expect("No se pudo reenviar señal de apagado");
@@ -152,36 +1588,358 @@ impl Station {
                     break;
                 }
 
-                // Esperamos por el próximo mensaje (bloqueo)
-                match receiver.recv().expect("Canal de recepción cerrado inesperadamente") {
-                    Message::Product(product) => {
-                        self.register_arrival(&product);
-                        queue.push_back(product);
-                    }
-                    Message::Shutdown => {
-                        shutdown_received = true;
+                if state.disconnected {
+                    // El emisor anterior terminó sin enviar la señal de apagado,
+                    // lo que indica una parada anticipada o una falla aguas arriba
+                    if self.stop_requested() {
+                        println!("[INFO] Estación '{}' detenida", self.config.name);
+                    } else {
+                        println!(
+                            "[ERROR] Estación '{}' perdió su canal de entrada, finalizando",
+                            self.config.name
+                        );
                     }
+                    break;
                 }
+            }
+
+            // Único punto de recepción: sin productos en cola esperamos sin
+            // límite; con productos listos solo tomamos los mensajes ya
+            // disponibles; si todos están bloqueados por dependencias esperamos
+            // brevemente a que lleguen mensajes o se liberen las dependencias.
+            // En pausa la espera también es breve, para revisar los comandos,
+            // y sin productos también si una hermana puede cambiarle alguno.
+            // Si hay un plazo de apagado, la espera no lo sobrepasa
+            let timeout = if state.queue.is_empty() {
+                let deadline = self.shutdown_mode
+                    .deadline(state.shutdown_at)
+                    .map(|deadline| deadline.saturating_duration_since(self.clock.now()));
+                match (&self.jockeying, deadline) {
+                    (Some(_), Some(timeout)) => Some(timeout.min(JOCKEY_POLL_INTERVAL)),
+                    (Some(_), None) => Some(JOCKEY_POLL_INTERVAL),
+                    (None, timeout) => timeout,
+                }
+            } else if self.is_paused() {
+                Some(PAUSE_POLL_INTERVAL)
+            } else if state.queue.iter().any(|product| self.is_ready(product)) {
+                Some(Duration::ZERO)
+            } else {
+                Some(DEPENDENCY_POLL_INTERVAL)
+            };
+            if timeout != Some(Duration::ZERO) {
+                state.status.transition(StationStatus::Idle, self.clock.now(), &mut state.report);
+                state.report.wakeups += 1;
+            }
+            self.receive_messages(&receiver, timeout, &mut state, &outputs);
+            self.apply_commands(&mut state.queue);
+            if self.renege(&mut state.queue, &mut state.report) {
+                self.publish(|probe| probe.set_queue(&state.queue));
+            }
+            if self.jockey(&mut state.queue, &mut state.report) {
+                self.publish(|probe| probe.set_queue(&state.queue));
+            }
+
+            // Procesamos el próximo producto listo de la cola
+            if self.is_paused() {
                 continue;
             }
+            if let Some(product) = self.take_next_ready(&mut state.queue, &mut state.report) {
+                self.process_product(product, &mut state, &outputs);
+            }
+        }
 
-            // Procesamos mensajes adicionales sin bloquear
-            while let Ok(message) = receiver.try_recv() {
-                match message {
-                    Message::Product(product) => {
-                        self.register_arrival(&product);
-                        queue.push_back(product);
-                    }
-                    Message::Shutdown => {
-                        shutdown_received = true;
-                    }
+        if let Some(board) = &self.jockeying {
+            board.abandon(self.index);
+        }
+        state.status.transition(StationStatus::Down, self.clock.now(), &mut state.report);
+        state.report
+    }
+
+    /// Recibe los mensajes disponibles en el canal de entrada.
+    /// 
+    /// Espera el primer mensaje como máximo `timeout` (`None` = sin límite)
+    /// y luego procesa sin bloquear los que ya estén en el canal. Si el
+    /// canal se desconectó lo registra en el estado de la estación.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `receiver` - Canal de entrada de la estación
+    /// * `timeout` - Tiempo máximo de espera del primer mensaje
+    /// * `state` - Estado de ejecución de la estación
+    /// * `outputs` - Canales de salida de la estación
+    fn receive_messages(
+        &self,
+        receiver: &StationReceiver,
+        timeout: Option<Duration>,
+        state: &mut RunState,
+        outputs: &Outputs,
+    ) {
+        if state.disconnected {
+            // Sin emisores no llegarán mensajes: solo esperamos dependencias
+            if let Some(timeout) = timeout {
+                thread::sleep(timeout);
+            }
+            return;
+        }
+
+        match self.wait_message(receiver, timeout, state) {
+            Ok(message) => {
+                // Los comandos enviados mientras la estación esperaba se
+                // aplican antes del mensaje que la despertó
+                self.apply_commands(&mut state.queue);
+                self.handle_message(message, state, outputs)
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => return,
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                state.disconnected = true;
+                return;
+            }
+        }
+
+        while let Ok(message) = receiver.try_recv() {
+            self.handle_message(message, state, outputs);
+        }
+    }
+
+    /// Espera el primer mensaje del canal de entrada como máximo `timeout`
+    /// (`None` = sin límite).
+    /// 
+    /// Con canales de crossbeam la espera incluye el canal de comandos: un
+    /// comando se aplica en cuanto llega y la espera termina como si hubiera
+    /// vencido, para que la estación vuelva a revisar su cola y su pausa.
+    #[cfg_attr(not(feature = "crossbeam"), allow(unused_variables))]
+    fn wait_message(
+        &self,
+        receiver: &StationReceiver,
+        timeout: Option<Duration>,
+        state: &mut RunState,
+    ) -> Result<Message, mpsc::RecvTimeoutError> {
+        #[cfg(feature = "crossbeam")]
+        if let Some(commands) = &state.commands {
+            match receiver.select(commands, timeout) {
+                Some(Ok(Received::Message(message))) => return Ok(message),
+                Some(Ok(Received::Command(command))) => {
+                    println!("[{}] Comando recibido: {}", self.config.name, command);
+                    self.apply_command(command, &mut state.queue);
+                    return Err(mpsc::RecvTimeoutError::Timeout);
+                }
+                Some(Ok(Received::CommandsClosed)) => {
+                    state.commands = None;
+                    return Err(mpsc::RecvTimeoutError::Timeout);
+                }
+                Some(Err(error)) => return Err(error),
+                None => {}
+            }
+        }
+        match timeout {
+            None => receiver.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            Some(timeout) if timeout.is_zero() => receiver.try_recv().map_err(|error| match error {
+                mpsc::TryRecvError::Empty => mpsc::RecvTimeoutError::Timeout,
+                mpsc::TryRecvError::Disconnected => mpsc::RecvTimeoutError::Disconnected,
+            }),
+            Some(timeout) => receiver.recv_timeout(timeout),
+        }
+    }
+
+    /// Procesa un mensaje recibido por la estación.
+    /// 
+    /// Los productos cuya visita en curso no corresponde a esta estación se
+    /// reenvían de inmediato sin encolarse ni registrar métricas.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `message` - Mensaje recibido
+    /// * `state` - Estado de ejecución de la estación
+    /// * `outputs` - Canales de salida de la estación
+    fn handle_message(&self, message: Message, state: &mut RunState, outputs: &Outputs) {
+        match message {
+            Message::Product(product) => {
+                self.register_return(&product);
+                if product.current_station() != Some(self.index) {
+                    self.forward(product, outputs, &mut state.status, &mut state.report);
+                    return;
+                }
+                if self.admit(product, &mut state.queue, &mut state.report) {
+                    self.publish(|probe| probe.set_queue(&state.queue));
+                }
+            }
+            Message::Shutdown => {
+                state.shutdown_received = true;
+                state.shutdown_at.get_or_insert(self.clock.now());
+            }
+        }
+    }
+
+    /// Extrae de la cola el siguiente producto listo para procesarse.
+    /// 
+    /// En la primera visita de la ruta de cada producto se omiten los
+    /// productos cuyas dependencias de precedencia aún no fueron satisfechas. Con FCFS y Round Robin se
+    /// toma el primer producto listo de la cola; con EDD y CR se elige, entre
+    /// los productos listos de mayor prioridad, el de fecha de entrega más
+    /// próxima o el de menor razón crítica respectivamente. Con una
+    /// disciplina LIFO o aleatoria, la elección entre los candidatos
+    /// equivalentes (ver [`QueueDiscipline`]) la hace la disciplina. En una
+    /// unión la elección se limita a los productos del origen que elige su
+    /// política, y se registra en las atenciones por origen del reporte.
+    /// 
+    /// # Returns
+    /// 
+    /// `Some(producto)` si hay un producto listo, `None` si la cola está
+    /// vacía o todos sus productos están bloqueados
+    pub(crate) fn take_next_ready(
+        &self,
+        queue: &mut VecDeque<Arc<Product>>,
+        report: &mut StationReport,
+    ) -> Option<Arc<Product>> {
+        let source = match &self.config.kind {
+            StationKind::Merger(merger) => Some(self.choose_source(merger, queue, report)?),
+            _ => None,
+        };
+        let is_ready = |product: &Arc<Product>| {
+            self.is_ready(product) && source.is_none_or(|source| product.previous_station() == source)
+        };
+
+        let position = if self.discipline != QueueDiscipline::Fifo {
+            let candidates = self.candidates(queue, is_ready);
+            match self.discipline {
+                QueueDiscipline::Random { .. } if !candidates.is_empty() => {
+                    let pick = lock_recover(&self.queue_rng).next_u64() % candidates.len() as u64;
+                    candidates[pick as usize]
                 }
+                _ => *candidates.last()?,
+            }
+        } else if self.algorithm.is_due_date_rule() {
+            let now = self.since_start(self.clock.now());
+            queue
+                .iter()
+                .enumerate()
+                .filter(|(_, product)| is_ready(product))
+                .min_by(|(_, a), (_, b)| {
+                    b.priority.cmp(&a.priority).then_with(|| {
+                        self.dispatch_key(a, now).total_cmp(&self.dispatch_key(b, now))
+                    })
+                })
+                .map(|(position, _)| position)?
+        } else {
+            queue.iter().position(is_ready)?
+        };
+
+        let product = queue.remove(position)?;
+        if let Some(source) = source {
+            self.record_merge(&product, source, report);
+        }
+        Some(product)
+    }
+
+    /// Elige con la política de la unión el origen del siguiente producto y
+    /// cuenta una omisión para cada otro origen con productos listos.
+    /// 
+    /// # Returns
+    /// 
+    /// El origen elegido, o `None` si no hay productos listos
+    fn choose_source(
+        &self,
+        merger: &Merger,
+        queue: &VecDeque<Arc<Product>>,
+        report: &mut StationReport,
+    ) -> Option<Option<usize>> {
+        let mut waiting: Vec<Option<usize>> = queue
+            .iter()
+            .filter(|product| self.is_ready(product))
+            .map(|product| product.previous_station())
+            .collect();
+        waiting.sort_unstable();
+        waiting.dedup();
+        let chosen = merger.choose(&waiting, &mut lock_recover(&self.merge_state))?;
+
+        for &source in &waiting {
+            let entry = merge_source(report, source);
+            if source == chosen {
+                entry.consecutive_bypassed = 0;
+            } else {
+                entry.bypassed += 1;
+                entry.consecutive_bypassed += 1;
+                entry.max_consecutive_bypassed = entry.max_consecutive_bypassed.max(entry.consecutive_bypassed);
             }
+        }
+        Some(chosen)
+    }
+
+    /// Registra la atención de un producto en su origen. Los productos
+    /// interrumpidos que vuelven a atenderse no se cuentan de nuevo.
+    fn record_merge(&self, product: &Product, source: Option<usize>, report: &mut StationReport) {
+        let state = lock_recover(product.current_state());
+        if state.first_entry.is_some() {
+            return;
+        }
+        let now = self.since_start(self.clock.now());
+        let wait = state.queue_entry.map_or(Duration::ZERO, |entry| now.saturating_sub(entry));
+        drop(state);
+
+        let entry = merge_source(report, source);
+        entry.merged += 1;
+        entry.total_wait += wait;
+        entry.max_wait = entry.max_wait.max(wait);
+    }
+
+    /// Posiciones en la cola de los productos listos entre los que elige la
+    /// disciplina, en orden de llegada: los de mayor prioridad y, con EDD y
+    /// CR, además los de clave de despacho mínima.
+    fn candidates(&self, queue: &VecDeque<Arc<Product>>, is_ready: impl Fn(&Arc<Product>) -> bool) -> Vec<usize> {
+        let ready: Vec<(usize, &Arc<Product>)> = queue
+            .iter()
+            .enumerate()
+            .filter(|(_, product)| is_ready(product))
+            .collect();
+        // La cola está ordenada por prioridad descendente
+        let Some(priority) = ready.first().map(|(_, product)| product.priority) else {
+            return Vec::new();
+        };
+        let top = ready.into_iter().take_while(|(_, product)| product.priority == priority);
+        if !self.algorithm.is_due_date_rule() {
+            return top.map(|(position, _)| position).collect();
+        }
+
+        let now = self.since_start(self.clock.now());
+        let keyed: Vec<(usize, f64)> = top
+            .map(|(position, product)| (position, self.dispatch_key(product, now)))
+            .collect();
+        let best = keyed.iter().map(|(_, key)| *key).fold(f64::INFINITY, f64::min);
+        keyed
+            .into_iter()
+            .filter(|(_, key)| *key == best)
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Indica si un producto en cola puede procesarse.
+    /// 
+    /// Un producto está bloqueado solo en la primera visita de su ruta y
+    /// mientras sus dependencias de precedencia no estén satisfechas.
+    fn is_ready(&self, product: &Product) -> bool {
+        product.completed_visits() > 0 || product.dependencies_satisfied()
+    }
+
+    /// Calcula la clave de despacho de un producto para las reglas EDD y CR.
+    /// 
+    /// Un valor menor indica mayor urgencia. Los productos sin fecha de
+    /// entrega reciben `f64::INFINITY` para ser atendidos al final.
+    fn dispatch_key(&self, product: &Product, now: Duration) -> f64 {
+        let Some(due) = product.due_date else {
+            return f64::INFINITY;
+        };
 
-            // Procesamos el próximo producto en la cola
-            if let Some(product) = queue.pop_front() {
-                self.process_product(product, &mut queue, &next_sender, &collector);
+        // Tiempo hasta la fecha de entrega (negativo si ya está atrasado)
+        let slack = due.as_secs_f64() - now.as_secs_f64();
+
+        match self.algorithm {
+            SchedulingAlgorithm::CriticalRatio => {
+                let remaining = lock_recover(product.current_state())
+                    .remaining
+                    .max(Duration::from_millis(1));
+                slack / remaining.as_secs_f64()
             }
+            _ => slack,
         }
     }
 
@@ -189,21 +1947,28 @@ impl Station {
     /// 
     /// Actualiza las métricas del producto para reflejar su entrada a la cola
     /// de esta estación. Inicializa el tiempo restante de procesamiento si
-    /// es la primera vez que el producto llega a esta estación.
+    /// es la primera vez que el producto llega a esta visita de su ruta, o con
+    /// el tiempo asignado por [`StationCommand::SetProcessingTime`].
     /// 
     /// # Arguments
     /// 
     /// * `product` - Referencia al producto que llega a la estación
-    fn register_arrival(&self, product: &Arc<Product>) {
-        let now = Instant::now();
-        let mut station_state = product.station_state(self.index).lock()
-            .expect("No se pudo obtener el lock del estado de la estación");
+    pub(crate) fn register_arrival(&self, product: &Arc<Product>) {
+        let now = self.since_start(self.clock.now());
+        let mut station_state = lock_recover(product.current_state());
         
-        station_state.queue_entry = Some(now);
+        // Respetar la entrada registrada por quien envió el producto
+        if station_state.queue_entry.is_none() {
+            station_state.queue_entry = Some(now);
+        }
         
-        // Inicializar tiempo restante si es la primera vez que llega
-        if station_state.remaining.is_zero() {
-            station_state.remaining = self.config.processing_time;
+        // Inicializar tiempo restante si es la primera vez que llega; un
+        // tiempo asignado por comando reemplaza al inicializado por el
+        // generador, escalado por el tamaño del producto
+        if let Some(processing_time) = lock_recover(&self.overrides).processing_time {
+            station_state.remaining = processing_time.mul_f64(self.config.size_factor(product.size));
+        } else if station_state.remaining.is_zero() {
+            station_state.remaining = product.processing_time(self.index);
         }
 
         println!(
@@ -219,51 +1984,195 @@ impl Station {
     /// Esta función implementa la lógica central del procesamiento:
     /// 1. Calcula el tiempo de espera acumulado
     /// 2. Determina el quantum de procesamiento según el algoritmo
-    /// 3. Simula el procesamiento mediante `thread::sleep`
+    /// 3. Simula el procesamiento mediante el modelo de trabajo de la estación
     /// 4. Actualiza las métricas del producto
     /// 5. Decide si enviar el producto a la siguiente estación o reencolarlo
     /// 
     /// # Arguments
     /// 
     /// * `product` - Producto a procesar
-    /// * `queue` - Cola de productos de la estación (para reencolar si es necesario)
-    /// * `next_sender` - Canal opcional para enviar a la siguiente estación
-    /// * `collector` - Canal opcional para enviar al colector final
-    fn process_product(
+    /// * `state` - Estado de ejecución de la estación (cola y estadísticas)
+    /// * `outputs` - Canales de salida de la estación
+    fn process_product(&self, product: Arc<Product>, state: &mut RunState, outputs: &Outputs) {
+        if let Some(board) = &self.jockeying {
+            board.set_load(self.index, state.queue.len() + 1);
+        }
+        self.publish(|probe| {
+            probe.set_queue(&state.queue);
+            probe.set_in_service(Some(product.id));
+        });
+        if let Some(warm_up) = self.start_warm_up(&product, &mut state.status, &mut state.report) {
+            self.work_model.perform(warm_up);
+        }
+        let (resources, resource_wait) = self.acquire_resources(&product, &mut state.status, &mut state.report);
+        let (started_at, remaining, slice) = self.start_slice(&product, &resources, resource_wait);
+        state.status.transition(StationStatus::Busy, started_at, &mut state.report);
+
+        // Simular el procesamiento junto con el retraso del temporizador,
+        // que el modelo de trabajo realiza como parte de la porción (sin
+        // costo real con `NoWork`), midiendo cuánto se excede el modelo
+        let overshoot = self.timer_overshoot();
+        let work_started = self.clock.now();
+        self.work_model.perform(slice + overshoot);
+        state.report.work_overrun += self.clock.now()
+            .saturating_duration_since(work_started)
+            .saturating_sub(slice + overshoot);
+
+        let completed = self.finish_slice(&product, started_at, remaining, slice, overshoot, &mut state.report);
+        if !resources.is_empty() {
+            drop(resources);
+            self.publish(StationProbe::release_all);
+        }
+        if completed && self.reject(&product, &mut state.report) {
+            self.publish(|probe| probe.set_in_service(None));
+        } else if completed {
+            self.forward(product, outputs, &mut state.status, &mut state.report);
+        } else {
+            enqueue(&mut state.queue, product);
+            state.report.max_queue_length = state.report.max_queue_length.max(state.queue.len());
+            self.publish(|probe| {
+                probe.set_in_service(None);
+                probe.set_queue(&state.queue);
+            });
+        }
+    }
+
+    /// Adquiere los recursos compartidos que la estación necesita para el producto.
+    /// 
+    /// Si algún recurso no tiene unidades libres, la estación pasa al estado
+    /// `Blocked` hasta que otra estación libere una.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product` - Producto a procesar
+    /// * `status` - Máquina de estados de la estación
+    /// * `report` - Estadísticas de la estación
+    /// 
+    /// # Returns
+    /// 
+    /// Las unidades adquiridas, que se liberan al descartarse, y el tiempo
+    /// que tomó obtenerlas
+    fn acquire_resources(
         &self,
-        product: Arc<Product>,
-        queue: &mut VecDeque<Arc<Product>>,
-        next_sender: &Option<mpsc::Sender<Message>>,
-        collector: &Option<mpsc::Sender<Arc<Product>>>,
-    ) {
-        let now = Instant::now();
+        product: &Product,
+        status: &mut StatusMachine,
+        report: &mut StationReport,
+    ) -> (Vec<ResourceGuard<'_>>, Duration) {
+        let started_at = self.clock.now();
+        let resources = self.resources
+            .iter()
+            .filter(|resource| resource.applies_to(product))
+            .map(|resource| {
+                let guard = resource.pool.try_acquire().unwrap_or_else(|| {
+                    status.transition(StationStatus::Blocked, self.clock.now(), report);
+                    println!(
+                        "[{}] Producto {:02} espera el recurso '{}'",
+                        self.config.name,
+                        product.id,
+                        resource.pool.name
+                    );
+                    self.publish(|probe| probe.set_waiting_for(Some(&resource.pool.name)));
+                    let (guard, moves) = resource.pool.acquire();
+                    self.publish(|probe| probe.set_waiting_for(None));
+                    if moves > 0 {
+                        product.add_jockeys(moves);
+                        report.unit_jockeys += moves;
+                    }
+                    guard
+                });
+                self.publish(|probe| probe.hold(&resource.pool.name));
+                guard
+            })
+            .collect();
+        (resources, self.clock.now().saturating_duration_since(started_at))
+    }
+
+    /// Inicia una porción de procesamiento de un producto.
+    /// 
+    /// Acumula el tiempo de espera en cola (separando el bloqueo por
+    /// dependencias y la espera de recursos compartidos), registra la
+    /// primera entrada y calcula el quantum.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product` - Producto a procesar
+    /// * `resources` - Unidades de recursos adquiridas para esta porción
+    /// * `resource_wait` - Tiempo que la estación esperó recursos para esta porción
+    /// 
+    /// # Returns
+    /// 
+    /// Tupla con el inicio de la porción, el tiempo restante antes de ella
+    /// y la duración de la porción
+    pub(crate) fn start_slice(
+        &self,
+        product: &Product,
+        resources: &[ResourceGuard<'_>],
+        resource_wait: Duration,
+    ) -> (Instant, Duration, Duration) {
+        let now = self.clock.now();
         
+        // Momento en que se liberaron las dependencias de precedencia
+        let dependencies_ready_at = if product.completed_visits() == 0 {
+            product.dependencies_ready_at()
+        } else {
+            None
+        };
+
         // Obtener y actualizar el estado del producto en esta estación
         let remaining = {
-            let mut station_state = product.station_state(self.index).lock()
-                .expect("No se pudo obtener el lock del estado de la estación");
+            let mut station_state = lock_recover(product.current_state());
             
             let queue_entry = station_state
                 .queue_entry
                 .take()
                 .expect("Se esperaba tiempo de entrada a la cola");
+            let entered_service = self.since_start(now);
             
+            // Separar el bloqueo por dependencias de la espera en cola
+            let blocked = if station_state.first_entry.is_none() {
+                dependencies_ready_at
+                    .map(|ready_at| ready_at.saturating_sub(queue_entry))
+                    .unwrap_or_default()
+            } else {
+                Duration::ZERO
+            };
+            station_state.dependency_wait += blocked;
+            station_state.resource_wait += resource_wait;
+
             // Acumular tiempo de espera
-            station_state.total_wait += now - queue_entry;
+            station_state.total_wait += entered_service.saturating_sub(queue_entry).saturating_sub(blocked + resource_wait);
+            let units = resources.iter().map(ResourceGuard::assignment).collect();
+            match &self.visit_log {
+                Some(visit_log) => lock_recover(visit_log).record_served(product, (queue_entry, entered_service), units),
+                None => {
+                    station_state.queued.push((queue_entry, entered_service));
+                    station_state.slice_units.push(units);
+                }
+            }
             
-            // Registrar primera entrada si es necesario
+            // Registrar primera entrada y recursos usados si es necesario;
+            // la curva de aprendizaje alarga a los primeros productos que
+            // inician
             if station_state.first_entry.is_none() {
-                station_state.first_entry = Some(now);
+                station_state.first_entry = Some(entered_service);
+                station_state.resources = self.resources
+                    .iter()
+                    .filter(|resource| resource.applies_to(product))
+                    .map(|resource| resource.pool.name.clone())
+                    .collect();
+                if let Some(ramp_up) = &self.ramp_up {
+                    let position = self.ramp_started.fetch_add(1, Ordering::SeqCst);
+                    let extra = ramp_up.extra_time(position, station_state.remaining);
+                    station_state.remaining += extra;
+                    station_state.ramp_up += extra;
+                }
             }
             
             station_state.remaining
         };
 
         // Determinar quantum de procesamiento según el algoritmo
-        let slice = match &self.algorithm {
-            SchedulingAlgorithm::Fcfs => remaining,
-            SchedulingAlgorithm::RoundRobin { quantum } => remaining.min(*quantum),
-        };
+        let slice = self.calculate_quantum(remaining);
 
         println!(
             "[{}] Producto {:02} inicia procesamiento por {} (restante: {})",
@@ -273,40 +2182,81 @@ impl Station {
             format_duration(remaining)
         );
 
-        // Simular el procesamiento
-        thread::sleep(slice);
-        let completed_at = Instant::now();
+        (now, remaining, slice)
+    }
+
+    /// Finaliza una porción de procesamiento y actualiza el estado del producto.
+    /// 
+    /// El tiempo ocupado de la estación lo acumula su máquina de estados.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `product` - Producto procesado
+    /// * `started_at` - Inicio de la porción
+    /// * `remaining` - Tiempo restante antes de la porción
+    /// * `slice` - Duración de la porción
+    /// * `overshoot` - Retraso del temporizador inyectado en la porción
+    /// * `report` - Estadísticas de la estación
+    /// 
+    /// # Returns
+    /// 
+    /// `true` si el producto completó la estación, `false` si fue
+    /// interrumpido y debe volver a la cola
+    pub(crate) fn finish_slice(
+        &self,
+        product: &Product,
+        started_at: Instant,
+        remaining: Duration,
+        slice: Duration,
+        overshoot: Duration,
+        report: &mut StationReport,
+    ) -> bool {
+        let completed_at = self.clock.now();
+        report.slices_executed += 1;
+        report.slice_time += slice;
+        let drift = completed_at.saturating_duration_since(started_at).saturating_sub(slice);
+        report.slice_drift += drift;
+        report.max_slice_drift = report.max_slice_drift.max(drift);
+        report.injected_drift += overshoot;
 
         // Actualizar estado después del procesamiento
-        let mut station_state = product.station_state(self.index).lock()
-            .expect("No se pudo obtener el lock del estado de la estación");
+        let mut station_state = lock_recover(product.current_state());
+        let exit = self.since_start(completed_at);
+        let interval = (self.since_start(started_at), exit);
+        match &self.visit_log {
+            Some(visit_log) => lock_recover(visit_log).record_slice(product, interval),
+            None => station_state.slices.push(interval),
+        }
+        station_state.timer_drift += overshoot;
 
         if slice >= remaining {
             // Producto completado en esta estación
             station_state.remaining = Duration::ZERO;
-            station_state.final_exit = Some(completed_at);
+            station_state.final_exit = Some(exit);
+            let inspection = station_state.inspection;
+            let output = station_state.output;
             drop(station_state);
+            product.mark_station_completed();
+            report.products_processed += 1;
+            match inspection {
+                Some(true) => report.inspections_passed += 1,
+                Some(false) => report.inspections_failed += 1,
+                None => {}
+            }
+            if let Some(count) = output.and_then(|output| report.routed.get_mut(output)) {
+                *count += 1;
+            }
 
             println!(
                 "[{}] Producto {:02} completado en la estación",
                 self.config.name,
                 product.id
             );
-
-            // Enviar a la siguiente estación o al colector
-            if let Some(sender) = next_sender {
-                sender
-                    .send(Message::Product(product))
-                    .expect("No se pudo enviar producto a la siguiente estación");
-            } else if let Some(collector) = collector {
-                collector
-                    .send(product)
-                    .expect("No se pudo enviar producto al colector");
-            }
+            true
         } else {
             // Producto interrumpido, vuelve a la cola
             station_state.remaining = remaining - slice;
-            station_state.queue_entry = Some(completed_at);
+            station_state.queue_entry = Some(exit);
             let remaining_after = station_state.remaining;
             drop(station_state);
 
@@ -316,12 +2266,173 @@ impl Station {
                 product.id,
                 format_duration(remaining_after)
             );
+            report.preemptions += 1;
+            false
+        }
+    }
+
+    /// Saca de la línea un producto que terminó su ruta al no aprobar la
+    /// inspección de esta estación.
+    /// 
+    /// El producto se reporta como perdido en el momento en que terminó la
+    /// inspección y, con una política de reemplazo, se repone.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` si el producto fue rechazado y no debe enviarse
+    pub(crate) fn reject(&self, product: &Arc<Product>, report: &mut StationReport) -> bool {
+        if !product.rejected || !product.is_completed() {
+            return false;
+        }
+        let at = lock_recover(product.visit_state(product.stations.len() - 1))
+            .final_exit
+            .unwrap_or_else(|| self.since_start(self.clock.now()));
+        product.mark_lost(ProductLoss { station: self.index, reason: LossReason::Rejected, at });
+        self.scrap(product);
+        self.discard(product);
+        report.rejected += 1;
+        println!("[{}] Producto {:02} rechazado en la inspección", self.config.name, product.id);
+        true
+    }
+
+    /// Crea el reporte vacío de la estación, con un contador por salida si
+    /// la estación es un divisor.
+    pub(crate) fn new_report(&self) -> StationReport {
+        let outputs = match &self.config.kind {
+            StationKind::Splitter(splitter) => splitter.outputs.len(),
+            _ => 0,
+        };
+        StationReport {
+            index: self.index,
+            name: self.config.name.clone(),
+            description: self.config.description.clone(),
+            color: self.config.color.clone(),
+            routed: vec![0; outputs],
+            ..StationReport::default()
+        }
+    }
+
+    /// Envía un producto a la siguiente estación o al colector.
+    /// 
+    /// Los productos que saltan a una estación con canal directo (ver
+    /// [`Station::with_branch`]) se envían por ese canal, y la última
+    /// estación recircula a la primera los productos que aún
+    /// tienen visitas pendientes (rutas reentrantes). Antes de enviarlo
+    /// registra su entrada a la cola de la siguiente visita de su ruta, de
+    /// modo que el tiempo en el buffer del canal (y en la recirculación)
+    /// cuenta como espera. Con canales acotados el envío puede bloquearse;
+    /// en ese caso la estación pasa al estado `Blocked` hasta su siguiente
+    /// cambio de estado.
+    /// 
+    /// Durante una parada anticipada, o con un modo de apagado que abandona
+    /// las colas, el destino puede haber finalizado, por lo que en ese caso
+    /// los errores de envío se ignoran y el producto queda sin terminar.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el envío falla sin que se haya solicitado una parada
+    fn forward(
+        &self,
+        product: Arc<Product>,
+        outputs: &Outputs,
+        status: &mut StatusMachine,
+        report: &mut StationReport,
+    ) {
+        let pending_visit = !product.is_completed();
+        let product_id = product.id;
+        self.publish(|probe| probe.set_in_service(Some(product_id)));
+        self.stamp_queue_entry(&product);
 
-            queue.push_back(product);
+        let next_station = product.current_station();
+        let branch = self.branches
+            .iter()
+            .find(|(station, _)| Some(*station) == next_station)
+            .map(|(_, sender)| sender);
+        let sender = match (branch, &outputs.next_sender) {
+            (Some(sender), _) | (None, Some(sender)) => Some(sender),
+            (None, None) if pending_visit => self.loopback.as_ref(),
+            (None, None) => None,
+        };
+        if let Some(sender) = sender {
+            let result = match sender.try_send(Message::Product(product)) {
+                Err(mpsc::TrySendError::Full(message)) => {
+                    status.transition(StationStatus::Blocked, self.clock.now(), report);
+                    sender.send(message).map_err(|_| ())
+                }
+                result => result.map_err(|_| ()),
+            };
+            if result.is_err() && !self.stop_requested() && self.shutdown_mode == ShutdownMode::DrainQueues {
+                panic!("No se pudo enviar producto a la siguiente estación");
+            }
+        } else if let Some(collector) = &outputs.collector {
+            if collector.send(product).is_err() && !self.stop_requested() {
+                panic!("No se pudo enviar producto al colector");
+            }
         }
+        self.publish(|probe| probe.set_in_service(None));
     }
 }
 
+/// Estado mutable de una estación durante su ejecución.
+struct RunState {
+    /// Productos en espera de ser procesados
+    queue: VecDeque<Arc<Product>>,
+    /// Indica si se recibió la señal de apagado
+    shutdown_received: bool,
+    /// Momento en que se recibió la señal de apagado
+    shutdown_at: Option<Instant>,
+    /// Indica si el canal de entrada se desconectó sin señal de apagado
+    disconnected: bool,
+    /// Máquina de estados de la estación
+    status: StatusMachine,
+    /// Estadísticas acumuladas de la estación
+    report: StationReport,
+    /// Canal de comandos que la estación espera junto con su canal de
+    /// entrada, mientras siga abierto
+    #[cfg(feature = "crossbeam")]
+    commands: Option<crossbeam_channel::Receiver<StationCommand>>,
+}
+
+/// Canales de salida de una estación.
+struct Outputs {
+    /// Canal hacia la siguiente estación, si no es la última
+    next_sender: Option<StationSender>,
+    /// Canal hacia el colector final, si es la última estación
+    collector: Option<mpsc::Sender<Arc<Product>>>,
+}
+
+/// Obtiene las atenciones de un origen en el reporte de una unión,
+/// agregándolo en orden de la línea si aún no aparece.
+fn merge_source(report: &mut StationReport, source: Option<usize>) -> &mut MergeSourceReport {
+    let position = match report.merge_sources.binary_search_by_key(&source, |entry| entry.source) {
+        Ok(position) => position,
+        Err(position) => {
+            report.merge_sources.insert(position, MergeSourceReport { source, ..MergeSourceReport::default() });
+            position
+        }
+    };
+    &mut report.merge_sources[position]
+}
+
+/// Inserta un producto en la cola respetando su prioridad.
+/// 
+/// Los productos con mayor prioridad se ubican delante de los de menor
+/// prioridad; entre productos de igual prioridad se conserva el orden
+/// de llegada, por lo que sin prioridades la cola se comporta como FIFO.
+/// 
+/// # Arguments
+/// 
+/// * `queue` - Cola de productos de la estación
+/// * `product` - Producto a encolar
+pub(crate) fn enqueue(queue: &mut VecDeque<Arc<Product>>, product: Arc<Product>) {
+    // Se busca desde el final: con prioridades iguales la inserción es O(1)
+    let position = queue
+        .iter()
+        .rposition(|queued| queued.priority >= product.priority)
+        .map_or(0, |index| index + 1);
+    queue.insert(position, product);
+}
+
 /// Formatea una duración para mostrar en formato legible.
 /// 
 /// Convierte una `Duration` a una representación de string en formato
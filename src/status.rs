@@ -0,0 +1,257 @@
+//! # Módulo de Estados de Estación
+//! 
+//! Este módulo define la máquina de estados de una estación de trabajo. En
+//! cada momento una estación está ociosa, ocupada, bloqueada o fuera de
+//! servicio; cada cambio de estado se publica a los observadores
+//! registrados y el tiempo pasado en cada estado se acumula en el reporte
+//! de la estación.
+//! 
+//! Los estados son `Idle`, `Busy`, `Blocked` y `Down`. Una estación puede
+//! pasar libremente entre los tres primeros; `Down` es el estado final al
+//! que llega cuando termina, se detiene o pierde su canal de entrada.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::clock::Instant;
+use crate::product::lock_recover;
+use crate::station::StationReport;
+
+/// Estado de operación de una estación.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StationStatus {
+    /// Sin productos listos para procesar
+    Idle,
+    /// Procesando una porción de un producto
+    Busy,
+    /// Esperando espacio en el buffer de la siguiente estación o una unidad
+    /// de un recurso compartido
+    Blocked,
+    /// Fuera de servicio: la estación finalizó o fue detenida (estado final)
+    Down,
+}
+
+impl StationStatus {
+    /// Indica si la máquina de estados admite pasar de `self` a `next`.
+    /// 
+    /// Todos los cambios están permitidos salvo salir de `Down`, que es el
+    /// estado final de la estación.
+    pub fn can_transition_to(self, next: StationStatus) -> bool {
+        self != StationStatus::Down && self != next
+    }
+}
+
+impl fmt::Display for StationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Idle => write!(f, "ociosa"),
+            Self::Busy => write!(f, "ocupada"),
+            Self::Blocked => write!(f, "bloqueada"),
+            Self::Down => write!(f, "fuera de servicio"),
+        }
+    }
+}
+
+/// Cambio de estado de una estación.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusTransition {
+    /// Índice de la estación en la línea
+    pub station_index: usize,
+    /// Nombre de la estación
+    pub station_name: String,
+    /// Estado anterior
+    pub from: StationStatus,
+    /// Estado nuevo
+    pub to: StationStatus,
+    /// Momento del cambio
+    pub at: Instant,
+    /// Tiempo que la estación pasó en el estado anterior
+    pub time_in_previous: Duration,
+}
+
+/// Observador de los cambios de estado de las estaciones.
+/// 
+/// Se invoca desde el hilo (o tarea) de cada estación en el momento del
+/// cambio, por lo que debe retornar rápido para no alterar la simulación.
+/// Cualquier closure `Fn(&StatusTransition)` que sea `Send + Sync` es un
+/// observador.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig, StationStatus};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// let busy_starts = Arc::new(AtomicUsize::new(0));
+/// let counter = Arc::clone(&busy_starts);
+/// simulation.add_station_observer(move |transition: &assembly_line_simulator::StatusTransition| {
+///     if transition.to == StationStatus::Busy {
+///         counter.fetch_add(1, Ordering::Relaxed);
+///     }
+/// });
+/// simulation.run().expect("La simulación falló");
+/// assert!(busy_starts.load(Ordering::Relaxed) >= 1);
+/// ```
+pub trait StationObserver: Send + Sync {
+    /// Recibe un cambio de estado de una estación.
+    fn on_transition(&self, transition: &StatusTransition);
+}
+
+impl<F> StationObserver for F
+where
+    F: Fn(&StatusTransition) + Send + Sync,
+{
+    fn on_transition(&self, transition: &StatusTransition) {
+        self(transition)
+    }
+}
+
+/// Observador que conserva el estado actual de cada estación.
+/// 
+/// Es útil para paneles en vivo: se registra en la simulación y se consulta
+/// desde otro hilo mientras la simulación avanza. Las copias comparten el
+/// mismo tablero.
+#[derive(Clone, Debug, Default)]
+pub struct StatusBoard {
+    statuses: Arc<Mutex<Vec<Option<BoardEntry>>>>,
+}
+
+/// Nombre y último estado publicado de una estación
+type BoardEntry = (String, StationStatus);
+
+impl StatusBoard {
+    /// Crea un tablero vacío.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Obtiene el último estado publicado por cada estación.
+    /// 
+    /// # Returns
+    /// 
+    /// Pares (nombre, estado) ordenados por índice de estación; las
+    /// estaciones que aún no cambiaron de estado no aparecen
+    pub fn snapshot(&self) -> Vec<(String, StationStatus)> {
+        lock_recover(&self.statuses).iter().flatten().cloned().collect()
+    }
+}
+
+impl StationObserver for StatusBoard {
+    fn on_transition(&self, transition: &StatusTransition) {
+        let mut statuses = lock_recover(&self.statuses);
+        if statuses.len() <= transition.station_index {
+            statuses.resize(transition.station_index + 1, None);
+        }
+        statuses[transition.station_index] = Some((transition.station_name.clone(), transition.to));
+    }
+}
+
+/// Máquina de estados de una estación durante su ejecución.
+/// 
+/// Acumula el tiempo pasado en cada estado en el reporte de la estación y
+/// publica cada cambio a los observadores.
+pub(crate) struct StatusMachine {
+    station_index: usize,
+    station_name: String,
+    status: StationStatus,
+    since: Instant,
+    observers: Vec<Arc<dyn StationObserver>>,
+}
+
+impl StatusMachine {
+    /// Crea la máquina en estado `Idle` a partir de `started_at`.
+    pub(crate) fn new(
+        station_index: usize,
+        station_name: String,
+        started_at: Instant,
+        observers: Vec<Arc<dyn StationObserver>>,
+    ) -> Self {
+        Self {
+            station_index,
+            station_name,
+            status: StationStatus::Idle,
+            since: started_at,
+            observers,
+        }
+    }
+
+    /// Cambia al estado `next` en el instante `now`.
+    /// 
+    /// Si la estación ya está en ese estado no hace nada.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si se intenta salir del estado `Down`
+    pub(crate) fn transition(&mut self, next: StationStatus, now: Instant, report: &mut StationReport) {
+        if self.status == next {
+            return;
+        }
+        assert!(
+            self.status.can_transition_to(next),
+            "Transición inválida en la estación '{}': {:?} -> {:?}",
+            self.station_name,
+            self.status,
+            next
+        );
+
+        let elapsed = now.saturating_duration_since(self.since);
+        match self.status {
+            StationStatus::Idle => report.idle_time += elapsed,
+            StationStatus::Busy => report.busy_time += elapsed,
+            StationStatus::Blocked => report.blocked_time += elapsed,
+            StationStatus::Down => {}
+        }
+
+        let transition = StatusTransition {
+            station_index: self.station_index,
+            station_name: self.station_name.clone(),
+            from: self.status,
+            to: next,
+            at: now,
+            time_in_previous: elapsed,
+        };
+        self.status = next;
+        self.since = now;
+        for observer in &self.observers {
+            observer.on_transition(&transition);
+        }
+    }
+
+    /// Tiempo que la estación lleva ociosa en el instante `now`, o `None`
+    /// si no está en estado `Idle`.
+    pub(crate) fn idle_for(&self, now: Instant) -> Option<Duration> {
+        (self.status == StationStatus::Idle).then(|| now.saturating_duration_since(self.since))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_machine_accounts_time_per_state() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let board = StatusBoard::new();
+        let mut report = StationReport::default();
+        let mut machine = StatusMachine::new(1, "Corte".to_string(), start, vec![Arc::new(board.clone())]);
+
+        machine.transition(StationStatus::Busy, at(10), &mut report);
+        machine.transition(StationStatus::Busy, at(20), &mut report);
+        machine.transition(StationStatus::Blocked, at(40), &mut report);
+        assert_eq!(board.snapshot(), vec![("Corte".to_string(), StationStatus::Blocked)]);
+        machine.transition(StationStatus::Idle, at(45), &mut report);
+        machine.transition(StationStatus::Down, at(60), &mut report);
+
+        assert_eq!(report.idle_time, Duration::from_millis(25));
+        assert_eq!(report.busy_time, Duration::from_millis(30));
+        assert_eq!(report.blocked_time, Duration::from_millis(5));
+        assert_eq!(board.snapshot(), vec![("Corte".to_string(), StationStatus::Down)]);
+        assert!(!StationStatus::Down.can_transition_to(StationStatus::Idle));
+    }
+}
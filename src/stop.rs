@@ -0,0 +1,149 @@
+//! # Módulo de Condiciones de Parada
+//! 
+//! Este módulo define las condiciones que permiten finalizar una simulación
+//! antes de que todos los productos completen la línea: por duración, por
+//! número de productos completados o mediante un predicado sobre las
+//! métricas en vivo.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Métricas parciales disponibles mientras la simulación está en curso.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LiveMetrics {
+    /// Tiempo transcurrido desde el inicio de la simulación
+    pub elapsed: Duration,
+    /// Productos que completaron toda la línea
+    pub completed: usize,
+    /// Productos que ingresaron a la línea (completados o en proceso)
+    pub admitted: usize,
+    /// Productos en proceso dentro de la línea
+    pub in_flight: usize,
+    /// Turnaround promedio de los productos completados
+    pub average_turnaround: Duration,
+}
+
+/// Predicado evaluado sobre las métricas en vivo.
+pub type StopPredicate = Arc<dyn Fn(&LiveMetrics) -> bool + Send + Sync>;
+
+/// Condición que determina cuándo finaliza la simulación.
+#[derive(Clone, Default)]
+pub enum StopCondition {
+    /// La simulación termina cuando todos los productos completan la línea.
+    #[default]
+    AllCompleted,
+    /// La simulación termina al alcanzar la duración indicada.
+    Duration(Duration),
+    /// La simulación termina cuando se completan los productos indicados.
+    Completions(usize),
+    /// La simulación termina cuando el predicado retorna `true`.
+    Predicate(StopPredicate),
+}
+
+impl StopCondition {
+    /// Crea una condición a partir de un predicado sobre las métricas en vivo.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::StopCondition;
+    /// 
+    /// let condition = StopCondition::predicate(|live| live.in_flight > 5);
+    /// ```
+    pub fn predicate<F>(predicate: F) -> Self
+    where
+        F: Fn(&LiveMetrics) -> bool + Send + Sync + 'static,
+    {
+        Self::Predicate(Arc::new(predicate))
+    }
+
+    /// Determina si la condición requiere vigilar la simulación en vivo.
+    pub fn is_monitored(&self) -> bool {
+        !matches!(self, Self::AllCompleted)
+    }
+
+    /// Evalúa la condición sobre las métricas en vivo.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` si la simulación debe detenerse
+    pub fn is_met(&self, live: &LiveMetrics) -> bool {
+        match self {
+            Self::AllCompleted => false,
+            Self::Duration(limit) => live.elapsed >= *limit,
+            Self::Completions(count) => live.completed >= *count,
+            Self::Predicate(predicate) => predicate(live),
+        }
+    }
+}
+
+impl fmt::Debug for StopCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AllCompleted => write!(f, "AllCompleted"),
+            Self::Duration(limit) => f.debug_tuple("Duration").field(limit).finish(),
+            Self::Completions(count) => f.debug_tuple("Completions").field(count).finish(),
+            Self::Predicate(_) => write!(f, "Predicate(..)"),
+        }
+    }
+}
+
+/// Señal compartida para detener la simulación de forma anticipada.
+/// 
+/// El colector la activa cuando se cumple la condición de parada; los
+/// generadores y las estaciones la consultan para finalizar sin esperar
+/// a que se vacíe la línea.
+#[derive(Clone, Debug, Default)]
+pub struct StopSignal(Arc<AtomicBool>);
+
+impl StopSignal {
+    /// Crea una nueva señal sin activar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Activa la señal de parada.
+    pub fn request_stop(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Indica si se solicitó detener la simulación.
+    pub fn is_stop_requested(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_conditions() {
+        let live = LiveMetrics {
+            elapsed: Duration::from_millis(500),
+            completed: 3,
+            admitted: 5,
+            in_flight: 2,
+            average_turnaround: Duration::from_millis(200),
+        };
+
+        assert!(!StopCondition::AllCompleted.is_met(&live));
+        assert!(StopCondition::Duration(Duration::from_millis(500)).is_met(&live));
+        assert!(!StopCondition::Duration(Duration::from_secs(1)).is_met(&live));
+        assert!(StopCondition::Completions(3).is_met(&live));
+        assert!(!StopCondition::Completions(4).is_met(&live));
+        assert!(StopCondition::predicate(|live| live.in_flight >= 2).is_met(&live));
+        assert!(!StopCondition::AllCompleted.is_monitored());
+    }
+
+    #[test]
+    fn test_stop_signal() {
+        let signal = StopSignal::new();
+        let shared = signal.clone();
+        assert!(!shared.is_stop_requested());
+        signal.request_stop();
+        assert!(shared.is_stop_requested());
+    }
+}
@@ -0,0 +1,321 @@
+//! # Módulo de Almacenamiento de Visitas
+//! 
+//! Por defecto cada visita de un producto guarda sus intervalos de espera y
+//! de procesamiento en vectores propios dentro de su [`StationState`]. En
+//! corridas de cientos de miles o millones de productos esos vectores
+//! pequeños (cada uno con su propia reserva de memoria) dominan el consumo.
+//! 
+//! En el modo compacto ([`StorageMode::Compact`]) cada estación agrega sus
+//! intervalos a un registro propio organizado como estructura de arreglos
+//! (una columna por campo, con tiempos en nanosegundos), y al terminar la
+//! ejecución los registros se fusionan en los estados de los productos
+//! reservando exactamente la capacidad necesaria. Las métricas finales son
+//! las mismas que en el modo completo.
+//! 
+//! [`StationState`]: crate::station::StationState
+
+use std::fmt;
+#[cfg(feature = "async")]
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "async")]
+use crate::product::lock_recover;
+use crate::product::Product;
+#[cfg(feature = "async")]
+use crate::station::StationState;
+
+/// Forma en que se guardan los intervalos de las visitas durante la ejecución.
+/// 
+/// Solo lo aplican los backends asíncronos ([`Simulation::run_async`],
+/// [`Simulation::run_virtual`] y [`Simulation::run_pooled`]); el backend con
+/// hilos guarda siempre los intervalos completos.
+/// 
+/// Con [`StorageMode::Compact`] los intervalos de cada visita solo se
+/// incorporan a los productos al terminar la ejecución, por lo que las
+/// métricas que reciben los [`Sink`](crate::Sink) mientras la simulación
+/// avanza no incluyen porciones, unidades de recursos ni línea de tiempo.
+/// Por el mismo motivo, en este modo el colector no finaliza las métricas
+/// de los productos al salir de la línea: se calculan todas al final, a
+/// partir de los intervalos ya incorporados.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig, StorageMode};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_secs(1))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// simulation.set_storage_mode(StorageMode::Compact);
+/// 
+/// # #[cfg(feature = "async")]
+/// # {
+/// let metrics = simulation.run_virtual().unwrap();
+/// assert_eq!(metrics.products[2].slices, vec![(0, Duration::from_secs(2), Duration::from_secs(3))]);
+/// # }
+/// ```
+/// 
+/// [`Simulation::run_async`]: crate::Simulation::run_async
+/// [`Simulation::run_virtual`]: crate::Simulation::run_virtual
+/// [`Simulation::run_pooled`]: crate::Simulation::run_pooled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StorageMode {
+    /// Cada visita guarda sus intervalos en vectores propios
+    #[default]
+    Full,
+    /// Las estaciones agregan los intervalos a registros por estación que
+    /// se fusionan al terminar
+    Compact,
+}
+
+impl fmt::Display for StorageMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full => write!(f, "completo"),
+            Self::Compact => write!(f, "compacto (registros por estación)"),
+        }
+    }
+}
+
+/// Tipo de un intervalo registrado.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RecordKind {
+    /// Espera en cola terminada al iniciar una porción (con sus unidades de recursos)
+    Served,
+    /// Espera en cola terminada porque el producto la abandonó
+    Abandoned,
+    /// Porción de procesamiento
+    Slice,
+}
+
+/// Cantidad de valores de cada bloque de una columna.
+const BLOCK_LEN: usize = 4096;
+
+/// Columna de un registro, guardada en bloques de [`BLOCK_LEN`] valores.
+/// 
+/// El primer bloque crece como un `Vec` (las estaciones con pocos registros
+/// no reservan un bloque completo); los siguientes se reservan de una vez,
+/// por lo que crecer no copia los valores ya registrados ni reserva el
+/// doble de lo necesario: a lo sumo sobra un bloque.
+#[derive(Debug)]
+struct Column<T> {
+    blocks: Vec<Vec<T>>,
+}
+
+impl<T> Default for Column<T> {
+    fn default() -> Self {
+        Self { blocks: Vec::new() }
+    }
+}
+
+impl<T: Copy> Column<T> {
+    fn push(&mut self, value: T) {
+        match self.blocks.last_mut() {
+            Some(block) if block.len() < BLOCK_LEN => block.push(value),
+            Some(_) => {
+                let mut block = Vec::with_capacity(BLOCK_LEN);
+                block.push(value);
+                self.blocks.push(block);
+            }
+            None => self.blocks.push(vec![value]),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.blocks.last().map_or(0, |last| (self.blocks.len() - 1) * BLOCK_LEN + last.len())
+    }
+
+    #[cfg(feature = "async")]
+    fn get(&self, index: usize) -> T {
+        self.blocks[index / BLOCK_LEN][index % BLOCK_LEN]
+    }
+
+    #[cfg(feature = "async")]
+    fn heap_bytes(&self) -> usize {
+        self.blocks.iter().map(Vec::capacity).sum::<usize>() * std::mem::size_of::<T>()
+    }
+}
+
+/// Registro de intervalos de una estación, organizado como estructura de arreglos.
+/// 
+/// Cada posición de las columnas es un registro; las unidades de recursos
+/// de los registros [`RecordKind::Served`] se guardan en columnas aparte,
+/// a partir de `unit_offsets`.
+#[derive(Debug, Default)]
+pub(crate) struct VisitLog {
+    /// Identificador del producto de cada registro
+    products: Column<u32>,
+    /// Posición en la ruta del producto de la visita de cada registro
+    visits: Column<u32>,
+    kinds: Column<RecordKind>,
+    /// Inicio de cada intervalo, en nanosegundos desde el inicio de la simulación
+    starts: Column<u64>,
+    /// Fin de cada intervalo, en nanosegundos desde el inicio de la simulación
+    ends: Column<u64>,
+    /// Posición en `unit_resources` y `unit_indices` de la primera unidad de cada registro
+    unit_offsets: Column<u32>,
+    /// Recurso de cada unidad, como posición en `resource_names`
+    unit_resources: Column<u16>,
+    /// Índice de cada unidad dentro de su recurso
+    unit_indices: Column<u32>,
+    /// Nombres de los recursos usados por la estación
+    resource_names: Vec<String>,
+}
+
+impl VisitLog {
+    /// Registra la espera en cola de la visita en curso de un producto que
+    /// inicia una porción atendida por las unidades indicadas.
+    pub(crate) fn record_served(&mut self, product: &Product, interval: (Duration, Duration), units: Vec<(String, usize)>) {
+        self.push(product, RecordKind::Served, interval);
+        for (resource, unit) in units {
+            let position = match self.resource_names.iter().position(|name| *name == resource) {
+                Some(position) => position,
+                None => {
+                    self.resource_names.push(resource);
+                    self.resource_names.len() - 1
+                }
+            };
+            self.unit_resources.push(u16::try_from(position).expect("Demasiados recursos en una estación"));
+            self.unit_indices.push(u32::try_from(unit).expect("Índice de unidad fuera de rango"));
+        }
+    }
+
+    /// Registra la espera en cola de un producto que la abandonó.
+    pub(crate) fn record_abandoned(&mut self, product: &Product, interval: (Duration, Duration)) {
+        self.push(product, RecordKind::Abandoned, interval);
+    }
+
+    /// Registra una porción de procesamiento de la visita en curso.
+    pub(crate) fn record_slice(&mut self, product: &Product, interval: (Duration, Duration)) {
+        self.push(product, RecordKind::Slice, interval);
+    }
+
+    fn push(&mut self, product: &Product, kind: RecordKind, (start, end): (Duration, Duration)) {
+        self.products.push(u32::try_from(product.id).expect("Identificador de producto fuera de rango"));
+        self.visits.push(u32::try_from(product.completed_visits()).expect("Ruta demasiado larga"));
+        self.kinds.push(kind);
+        self.starts.push(nanos(start));
+        self.ends.push(nanos(end));
+        self.unit_offsets.push(u32::try_from(self.unit_resources.len()).expect("Demasiadas unidades registradas"));
+    }
+}
+
+// La fusión solo la usan los backends asíncronos
+#[cfg(feature = "async")]
+impl VisitLog {
+    /// Cantidad de registros.
+    pub(crate) fn len(&self) -> usize {
+        self.kinds.len()
+    }
+
+    /// Bytes reservados por las columnas del registro.
+    pub(crate) fn heap_bytes(&self) -> usize {
+        self.products.heap_bytes()
+            + self.visits.heap_bytes()
+            + self.kinds.heap_bytes()
+            + self.starts.heap_bytes()
+            + self.ends.heap_bytes()
+            + self.unit_offsets.heap_bytes()
+            + self.unit_resources.heap_bytes()
+            + self.unit_indices.heap_bytes()
+    }
+
+    /// Visita (producto y posición en su ruta) de un registro.
+    fn visit(&self, record: u32) -> (u32, u32) {
+        (self.products.get(record as usize), self.visits.get(record as usize))
+    }
+
+    /// Unidades de recursos de un registro.
+    fn units(&self, record: usize) -> Vec<(String, usize)> {
+        let start = self.unit_offsets.get(record) as usize;
+        let end = if record + 1 < self.len() {
+            self.unit_offsets.get(record + 1) as usize
+        } else {
+            self.unit_resources.len()
+        };
+        (start..end)
+            .map(|unit| {
+                let name = self.resource_names[self.unit_resources.get(unit) as usize].clone();
+                (name, self.unit_indices.get(unit) as usize)
+            })
+            .collect()
+    }
+
+    /// Incorpora los intervalos registrados a los estados de los productos.
+    /// 
+    /// Los registros de cada visita se agregan en el orden en que se
+    /// produjeron, reservando exactamente la capacidad que necesitan.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `products` - Productos de la ejecución indexados por identificador
+    ///   (posición `id - 1`)
+    pub(crate) fn merge_into(self, products: &[Option<&Arc<Product>>]) {
+        // Agrupar los registros por visita conservando su orden
+        let mut order: Vec<u32> = (0..self.len() as u32).collect();
+        order.sort_by_key(|&record| self.visit(record));
+
+        for group in order.chunk_by(|&a, &b| self.visit(a) == self.visit(b)) {
+            let (id, visit) = self.visit(group[0]);
+            let Some(product) = products.get(id as usize - 1).copied().flatten() else {
+                continue;
+            };
+            let mut state = lock_recover(product.visit_state(visit as usize));
+            self.merge_visit(group, &mut state);
+        }
+    }
+
+    /// Incorpora los registros de una visita a su estado.
+    fn merge_visit(&self, group: &[u32], state: &mut StationState) {
+        let count = |kinds: &[RecordKind]| {
+            group.iter().filter(|&&record| kinds.contains(&self.kinds.get(record as usize))).count()
+        };
+        let served = count(&[RecordKind::Served]);
+        state.queued.reserve_exact(count(&[RecordKind::Served, RecordKind::Abandoned]));
+        state.slices.reserve_exact(count(&[RecordKind::Slice]));
+        state.slice_units.reserve_exact(served);
+
+        for &record in group {
+            let record = record as usize;
+            let interval = (
+                Duration::from_nanos(self.starts.get(record)),
+                Duration::from_nanos(self.ends.get(record)),
+            );
+            match self.kinds.get(record) {
+                RecordKind::Served => {
+                    state.queued.push(interval);
+                    state.slice_units.push(self.units(record));
+                }
+                RecordKind::Abandoned => state.queued.push(interval),
+                RecordKind::Slice => state.slices.push(interval),
+            }
+        }
+    }
+}
+
+/// Convierte una duración a nanosegundos.
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).expect("Tiempo de simulación fuera de rango")
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_spans_several_blocks() {
+        let mut column = Column::default();
+        for value in 0..(2 * BLOCK_LEN + 3) as u32 {
+            column.push(value);
+        }
+
+        assert_eq!(column.len(), 2 * BLOCK_LEN + 3);
+        assert_eq!(column.blocks.len(), 3);
+        assert_eq!(column.get(BLOCK_LEN - 1), BLOCK_LEN as u32 - 1);
+        assert_eq!(column.get(BLOCK_LEN), BLOCK_LEN as u32);
+        assert_eq!(column.get(2 * BLOCK_LEN + 2), 2 * BLOCK_LEN as u32 + 2);
+        // Solo el último bloque tiene capacidad sin usar
+        assert_eq!(column.heap_bytes(), 3 * BLOCK_LEN * 4);
+    }
+}
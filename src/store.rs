@@ -0,0 +1,665 @@
+//! # Módulo de Almacenamiento de Resultados
+//! 
+//! Este módulo (feature `store`) guarda los resultados de las simulaciones
+//! en una base de datos SQLite, de modo que los experimentos repetidos se
+//! acumulen en un solo lugar consultable. Cada ejecución recibe un
+//! identificador propio y se asocia al hash de su configuración, lo que
+//! permite agrupar las corridas de una misma configuración.
+//! 
+//! Se enlaza directamente con la biblioteca del sistema `libsqlite3`, sin
+//! dependencias adicionales. El esquema es:
+//! 
+//! ```sql
+//! runs(run_id, config_hash, created_at, algorithm, stations, completed,
+//!      incomplete, average_wait, average_turnaround, throughput, total_time)
+//! products(run_id, product_id, completed, arrival, wait, turnaround, service)
+//! events(run_id, product_id, station, event, start, end)
+//! ```
+//! 
+//! Los tiempos se guardan en segundos; `created_at` en segundos Unix.
+//! `events` contiene la línea de tiempo de cada producto (ver
+//! [`Product::timeline`](crate::Product::timeline)).
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::fmt;
+use std::path::Path;
+use std::ptr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::metrics::SimulationMetrics;
+use crate::simulation::Simulation;
+
+/// Esquema de la base de datos; se crea si no existe.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    run_id TEXT PRIMARY KEY,
+    config_hash TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    algorithm TEXT NOT NULL,
+    stations INTEGER NOT NULL,
+    completed INTEGER NOT NULL,
+    incomplete INTEGER NOT NULL,
+    average_wait REAL NOT NULL,
+    average_turnaround REAL NOT NULL,
+    throughput REAL NOT NULL,
+    total_time REAL NOT NULL
+);
+CREATE INDEX IF NOT EXISTS runs_by_config ON runs(config_hash);
+CREATE TABLE IF NOT EXISTS products (
+    run_id TEXT NOT NULL REFERENCES runs(run_id),
+    product_id INTEGER NOT NULL,
+    completed INTEGER NOT NULL,
+    arrival REAL NOT NULL,
+    wait REAL NOT NULL,
+    turnaround REAL NOT NULL,
+    service REAL NOT NULL,
+    PRIMARY KEY (run_id, product_id)
+);
+CREATE TABLE IF NOT EXISTS events (
+    run_id TEXT NOT NULL REFERENCES runs(run_id),
+    product_id INTEGER NOT NULL,
+    station INTEGER NOT NULL,
+    event TEXT NOT NULL,
+    start REAL NOT NULL,
+    end REAL NOT NULL
+);
+";
+
+/// Error al acceder a la base de datos de resultados.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoreError {
+    /// Código de resultado de SQLite
+    pub code: i32,
+    /// Mensaje de SQLite
+    pub message: String,
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error de SQLite ({}): {}", self.code, self.message)
+    }
+}
+
+impl Error for StoreError {}
+
+/// Resumen de una ejecución guardada.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredRun {
+    /// Identificador de la ejecución
+    pub run_id: String,
+    /// Hash de la configuración de la simulación
+    pub config_hash: String,
+    /// Momento en que se guardó (segundos Unix)
+    pub created_at: i64,
+    /// Algoritmo de planificación general
+    pub algorithm: String,
+    /// Productos completados
+    pub completed: usize,
+    /// Tiempo promedio de espera
+    pub average_wait: Duration,
+    /// Turnaround promedio
+    pub average_turnaround: Duration,
+    /// Throughput en productos por segundo
+    pub throughput: f64,
+}
+
+/// Calcula el hash de la configuración de una simulación.
+/// 
+/// Considera todo lo que determina el resultado de una ejecución: los
+/// algoritmos, las estaciones (nombre, tiempo, sensibilidad al tamaño,
+/// tipo, algoritmo y disciplina de cola de cada una), las llegadas de la
+/// fuente principal y las fuentes adicionales, el catálogo y los tipos,
+/// tamaños y metadatos de los productos, las fechas de entrega, las
+/// dependencias, los recursos compartidos, la paciencia, los cambios de
+/// cola, las reconfiguraciones y las condiciones de parada. Los
+/// observadores, los destinos y el modelo de trabajo no forman parte del
+/// hash. Usa FNV-1a de 64 bits, por lo que el valor es estable entre
+/// ejecuciones y versiones del compilador.
+/// 
+/// # Returns
+/// 
+/// El hash como 16 dígitos hexadecimales
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{store, Simulation, SchedulingAlgorithm};
+/// 
+/// let fcfs = Simulation::new(SchedulingAlgorithm::fcfs());
+/// let rr = Simulation::new(SchedulingAlgorithm::round_robin(Duration::from_millis(300)));
+/// assert_eq!(store::config_hash(&fcfs), store::config_hash(&Simulation::new(SchedulingAlgorithm::fcfs())));
+/// assert_ne!(store::config_hash(&fcfs), store::config_hash(&rr));
+/// ```
+pub fn config_hash(simulation: &Simulation) -> String {
+    let mut signature = String::new();
+    let mut field = |name: &str, value: &dyn fmt::Debug| signature.push_str(&format!("|{}={:?}", name, value));
+
+    field("algorithm", &simulation.algorithm);
+    for (index, station) in simulation.station_configs.iter().enumerate() {
+        field("station", &(
+            &station.name,
+            station.processing_time,
+            station.size_sensitivity,
+            &station.kind,
+            simulation.station_algorithm(index),
+            simulation.queue_discipline(index),
+        ));
+    }
+    field("arrivals", &simulation.arrival_times);
+    field("arrival_order", &simulation.arrival_order);
+    field("sources", &simulation.sources);
+    field("due_dates", &simulation.due_dates);
+    field("catalog", &simulation.catalog);
+    field("product_types", &sorted(&simulation.product_types));
+    field("product_sizes", &sorted(&simulation.product_sizes));
+    let metadata: BTreeMap<_, _> = simulation
+        .product_metadata
+        .iter()
+        .map(|(product, entries)| (product, entries.iter().collect::<BTreeMap<_, _>>()))
+        .collect();
+    field("product_metadata", &metadata);
+    field("dependencies", &simulation.dependencies);
+    field("resources", &simulation.resources);
+    field("balking", &sorted(&simulation.balking_thresholds));
+    field("ramp_ups", &sorted(&simulation.ramp_ups));
+    field("warm_ups", &sorted(&simulation.warm_ups));
+    field("timer_jitters", &sorted(&simulation.timer_jitters));
+    field("patience", &simulation.patience);
+    field("scrap_policy", &simulation.scrap_policy);
+    field("jockeying", &simulation.jockeying);
+    field("reconfigurations", &simulation.reconfigurations);
+    field("stop_condition", &simulation.stop_condition);
+    field("timeouts", &(simulation.completion_timeout, simulation.watchdog_timeout, simulation.deadlock_timeout));
+    field("channel_backend", &simulation.channel_backend);
+    field("shutdown_mode", &simulation.shutdown_mode);
+    field("tick_length", &simulation.tick_length());
+
+    let hash = signature.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Ordena un mapa por estación o producto para que el hash no dependa del
+/// orden de iteración de `HashMap`.
+fn sorted<V>(map: &HashMap<usize, V>) -> BTreeMap<&usize, &V> {
+    map.iter().collect()
+}
+
+/// Base de datos SQLite de resultados de simulaciones.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{store::ResultStore, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+/// let metrics = simulation.run().unwrap();
+/// 
+/// let mut store = ResultStore::in_memory().unwrap();
+/// let run_id = store.record_run(&simulation, &metrics).unwrap();
+/// assert_eq!(store.runs().unwrap()[0].run_id, run_id);
+/// ```
+pub struct ResultStore {
+    db: *mut ffi::Sqlite3,
+}
+
+impl ResultStore {
+    /// Abre (o crea) una base de datos de resultados.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - Ruta del archivo SQLite
+    /// 
+    /// # Returns
+    /// 
+    /// La base de datos con el esquema creado, o `StoreError` si no se pudo
+    /// abrir
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let path = path.as_ref().to_string_lossy();
+        Self::open_raw(&path)
+    }
+
+    /// Crea una base de datos de resultados en memoria.
+    /// 
+    /// Útil para pruebas o para consultar resultados sin dejar archivos.
+    pub fn in_memory() -> Result<Self, StoreError> {
+        Self::open_raw(":memory:")
+    }
+
+    fn open_raw(path: &str) -> Result<Self, StoreError> {
+        let path = CString::new(path).map_err(|_| StoreError {
+            code: ffi::SQLITE_MISUSE,
+            message: "la ruta contiene un byte NUL".to_string(),
+        })?;
+        let mut db = ptr::null_mut();
+        // SAFETY: `path` es un string terminado en NUL y `db` recibe el handle
+        let code = unsafe {
+            ffi::sqlite3_open_v2(
+                path.as_ptr(),
+                &mut db,
+                ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE,
+                ptr::null(),
+            )
+        };
+        // SQLite asigna un handle incluso cuando falla; se libera en `drop`
+        let store = Self { db };
+        if code != ffi::SQLITE_OK {
+            return Err(store.error(code));
+        }
+        store.execute_batch(SCHEMA)?;
+        Ok(store)
+    }
+
+    /// Guarda una ejecución con sus métricas por producto y su línea de tiempo.
+    /// 
+    /// Todo se escribe en una sola transacción: si algo falla, la base queda
+    /// como estaba.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `simulation` - Simulación ejecutada (para el hash de configuración)
+    /// * `metrics` - Métricas resultantes
+    /// 
+    /// # Returns
+    /// 
+    /// El identificador asignado a la ejecución
+    pub fn record_run(&mut self, simulation: &Simulation, metrics: &SimulationMetrics) -> Result<String, StoreError> {
+        static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+        let config_hash = config_hash(simulation);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let run_id = format!(
+            "{}-{:x}-{}",
+            config_hash,
+            now.as_micros(),
+            SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        );
+
+        self.execute_batch("BEGIN")?;
+        let result = self.insert_run(&run_id, &config_hash, now, simulation, metrics);
+        match result {
+            Ok(()) => self.execute_batch("COMMIT")?,
+            Err(error) => {
+                let _ = self.execute_batch("ROLLBACK");
+                return Err(error);
+            }
+        }
+        Ok(run_id)
+    }
+
+    fn insert_run(
+        &self,
+        run_id: &str,
+        config_hash: &str,
+        now: Duration,
+        simulation: &Simulation,
+        metrics: &SimulationMetrics,
+    ) -> Result<(), StoreError> {
+        let mut run = self.prepare("INSERT INTO runs VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")?;
+        run.execute(&[
+            Value::Text(run_id),
+            Value::Text(config_hash),
+            Value::Int(now.as_secs() as i64),
+            Value::Text(&simulation.algorithm.to_string()),
+            Value::Int(simulation.station_configs.len() as i64),
+            Value::Int(metrics.products.len() as i64),
+            Value::Int(metrics.incomplete_products.len() as i64),
+            Value::Real(metrics.average_wait_time.as_secs_f64()),
+            Value::Real(metrics.average_turnaround_time.as_secs_f64()),
+            Value::Real(metrics.throughput),
+            Value::Real(metrics.total_simulation_time.as_secs_f64()),
+        ])?;
+
+        let mut product = self.prepare("INSERT INTO products VALUES (?, ?, ?, ?, ?, ?, ?)")?;
+        let mut event = self.prepare("INSERT INTO events VALUES (?, ?, ?, ?, ?, ?)")?;
+        let products = metrics.products.iter().map(|p| (p, true))
+            .chain(metrics.incomplete_products.iter().map(|p| (p, false)))
+            .chain(metrics.lost_products.iter().map(|p| (p, false)));
+        for (product_metrics, completed) in products {
+            let product_id = product_metrics.product_id;
+            product.execute(&[
+                Value::Text(run_id),
+                Value::Int(product_id as i64),
+                Value::Int(i64::from(completed)),
+                Value::Real(product_metrics.arrival_time.as_secs_f64()),
+                Value::Real(product_metrics.total_wait_time.as_secs_f64()),
+                Value::Real(product_metrics.turnaround_time.as_secs_f64()),
+                Value::Real(product_metrics.service_time.as_secs_f64()),
+            ])?;
+            for span in &product_metrics.timeline {
+                event.execute(&[
+                    Value::Text(run_id),
+                    Value::Int(product_id as i64),
+                    Value::Int(span.station as i64),
+                    Value::Text(span.event.key()),
+                    Value::Real(span.start.as_secs_f64()),
+                    Value::Real(span.end.as_secs_f64()),
+                ])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Lista las ejecuciones guardadas, de la más antigua a la más reciente.
+    pub fn runs(&self) -> Result<Vec<StoredRun>, StoreError> {
+        self.query_runs("SELECT run_id, config_hash, created_at, algorithm, completed, average_wait, \
+             average_turnaround, throughput FROM runs ORDER BY rowid", &[])
+    }
+
+    /// Lista las ejecuciones guardadas de una configuración.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config_hash` - Hash obtenido con [`config_hash`]
+    pub fn runs_with_config(&self, config_hash: &str) -> Result<Vec<StoredRun>, StoreError> {
+        self.query_runs("SELECT run_id, config_hash, created_at, algorithm, completed, average_wait, \
+             average_turnaround, throughput FROM runs WHERE config_hash = ? ORDER BY rowid",
+            &[Value::Text(config_hash)])
+    }
+
+    /// Cuenta los eventos de línea de tiempo guardados de una ejecución.
+    pub fn event_count(&self, run_id: &str) -> Result<usize, StoreError> {
+        let mut statement = self.prepare("SELECT COUNT(*) FROM events WHERE run_id = ?")?;
+        statement.bind(&[Value::Text(run_id)])?;
+        statement.step()?;
+        Ok(statement.column_int(0) as usize)
+    }
+
+    fn query_runs(&self, sql: &str, params: &[Value<'_>]) -> Result<Vec<StoredRun>, StoreError> {
+        let mut statement = self.prepare(sql)?;
+        statement.bind(params)?;
+        let mut runs = Vec::new();
+        while statement.step()? {
+            runs.push(StoredRun {
+                run_id: statement.column_text(0),
+                config_hash: statement.column_text(1),
+                created_at: statement.column_int(2),
+                algorithm: statement.column_text(3),
+                completed: statement.column_int(4) as usize,
+                average_wait: Duration::from_secs_f64(statement.column_real(5)),
+                average_turnaround: Duration::from_secs_f64(statement.column_real(6)),
+                throughput: statement.column_real(7),
+            });
+        }
+        Ok(runs)
+    }
+
+    /// Ejecuta una o más sentencias sin parámetros.
+    fn execute_batch(&self, sql: &str) -> Result<(), StoreError> {
+        let sql = CString::new(sql).expect("El SQL no debe contener bytes NUL");
+        // SAFETY: `db` es un handle abierto y `sql` termina en NUL
+        let code = unsafe { ffi::sqlite3_exec(self.db, sql.as_ptr(), None, ptr::null_mut(), ptr::null_mut()) };
+        if code != ffi::SQLITE_OK {
+            return Err(self.error(code));
+        }
+        Ok(())
+    }
+
+    fn prepare(&self, sql: &str) -> Result<Statement<'_>, StoreError> {
+        let sql = CString::new(sql).expect("El SQL no debe contener bytes NUL");
+        let mut stmt = ptr::null_mut();
+        // SAFETY: `db` es un handle abierto y `sql` termina en NUL
+        let code = unsafe { ffi::sqlite3_prepare_v2(self.db, sql.as_ptr(), -1, &mut stmt, ptr::null_mut()) };
+        if code != ffi::SQLITE_OK {
+            return Err(self.error(code));
+        }
+        Ok(Statement { store: self, stmt })
+    }
+
+    fn error(&self, code: c_int) -> StoreError {
+        let message = if self.db.is_null() {
+            "no se pudo reservar memoria para la conexión".to_string()
+        } else {
+            // SAFETY: `db` es un handle válido; SQLite administra el mensaje
+            unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(self.db)) }.to_string_lossy().into_owned()
+        };
+        StoreError { code, message }
+    }
+}
+
+impl Drop for ResultStore {
+    fn drop(&mut self) {
+        // SAFETY: las sentencias viven menos que la conexión, por lo que ya
+        // fueron finalizadas; cerrar un handle nulo no hace nada
+        unsafe {
+            ffi::sqlite3_close(self.db);
+        }
+    }
+}
+
+/// Valor de un parámetro de una sentencia.
+enum Value<'a> {
+    Text(&'a str),
+    Int(i64),
+    Real(f64),
+}
+
+/// Sentencia preparada; se finaliza al salir de alcance.
+struct Statement<'a> {
+    store: &'a ResultStore,
+    stmt: *mut ffi::Stmt,
+}
+
+impl Statement<'_> {
+    /// Reinicia la sentencia y asigna sus parámetros en orden.
+    fn bind(&mut self, params: &[Value<'_>]) -> Result<(), StoreError> {
+        // SAFETY: `stmt` es una sentencia preparada válida; los textos se
+        // copian (SQLITE_TRANSIENT), por lo que pueden liberarse al retornar
+        unsafe {
+            ffi::sqlite3_reset(self.stmt);
+            for (index, param) in params.iter().enumerate() {
+                let index = index as c_int + 1;
+                let code = match param {
+                    Value::Text(text) => ffi::sqlite3_bind_text(
+                        self.stmt,
+                        index,
+                        text.as_ptr().cast(),
+                        text.len() as c_int,
+                        ffi::SQLITE_TRANSIENT,
+                    ),
+                    Value::Int(value) => ffi::sqlite3_bind_int64(self.stmt, index, *value),
+                    Value::Real(value) => ffi::sqlite3_bind_double(self.stmt, index, *value),
+                };
+                if code != ffi::SQLITE_OK {
+                    return Err(self.store.error(code));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Avanza la sentencia.
+    /// 
+    /// # Returns
+    /// 
+    /// `true` si hay una fila disponible, `false` si la sentencia terminó
+    fn step(&mut self) -> Result<bool, StoreError> {
+        // SAFETY: `stmt` es una sentencia preparada válida
+        match unsafe { ffi::sqlite3_step(self.stmt) } {
+            ffi::SQLITE_ROW => Ok(true),
+            ffi::SQLITE_DONE => Ok(false),
+            code => Err(self.store.error(code)),
+        }
+    }
+
+    /// Ejecuta una sentencia sin resultados con los parámetros indicados.
+    fn execute(&mut self, params: &[Value<'_>]) -> Result<(), StoreError> {
+        self.bind(params)?;
+        self.step().map(|_| ())
+    }
+
+    fn column_text(&self, column: c_int) -> String {
+        // SAFETY: hay una fila disponible; SQLite administra el texto hasta
+        // el siguiente paso, y se copia antes
+        unsafe {
+            let text = ffi::sqlite3_column_text(self.stmt, column);
+            if text.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(text.cast()).to_string_lossy().into_owned()
+            }
+        }
+    }
+
+    fn column_int(&self, column: c_int) -> i64 {
+        // SAFETY: hay una fila disponible
+        unsafe { ffi::sqlite3_column_int64(self.stmt, column) }
+    }
+
+    fn column_real(&self, column: c_int) -> f64 {
+        // SAFETY: hay una fila disponible
+        unsafe { ffi::sqlite3_column_double(self.stmt, column) }
+    }
+}
+
+impl Drop for Statement<'_> {
+    fn drop(&mut self) {
+        // SAFETY: `stmt` proviene de `sqlite3_prepare_v2` y se finaliza una vez
+        unsafe {
+            ffi::sqlite3_finalize(self.stmt);
+        }
+    }
+}
+
+/// Declaraciones mínimas de la API C de SQLite.
+mod ffi {
+    use super::{c_char, c_int, c_void};
+
+    /// Conexión opaca de SQLite
+    #[repr(C)]
+    pub struct Sqlite3 {
+        _private: [u8; 0],
+    }
+
+    /// Sentencia preparada opaca de SQLite
+    #[repr(C)]
+    pub struct Stmt {
+        _private: [u8; 0],
+    }
+
+    pub const SQLITE_OK: c_int = 0;
+    pub const SQLITE_MISUSE: c_int = 21;
+    pub const SQLITE_ROW: c_int = 100;
+    pub const SQLITE_DONE: c_int = 101;
+    pub const SQLITE_OPEN_READWRITE: c_int = 0x0000_0002;
+    pub const SQLITE_OPEN_CREATE: c_int = 0x0000_0004;
+    /// Indica a SQLite que copie el texto enlazado
+    pub const SQLITE_TRANSIENT: isize = -1;
+
+    #[link(name = "sqlite3")]
+    extern "C" {
+        pub fn sqlite3_open_v2(filename: *const c_char, db: *mut *mut Sqlite3, flags: c_int, vfs: *const c_char) -> c_int;
+        pub fn sqlite3_close(db: *mut Sqlite3) -> c_int;
+        pub fn sqlite3_errmsg(db: *mut Sqlite3) -> *const c_char;
+        pub fn sqlite3_exec(
+            db: *mut Sqlite3,
+            sql: *const c_char,
+            callback: Option<extern "C" fn(*mut c_void, c_int, *mut *mut c_char, *mut *mut c_char) -> c_int>,
+            argument: *mut c_void,
+            errmsg: *mut *mut c_char,
+        ) -> c_int;
+        pub fn sqlite3_prepare_v2(
+            db: *mut Sqlite3,
+            sql: *const c_char,
+            bytes: c_int,
+            stmt: *mut *mut Stmt,
+            tail: *mut *const c_char,
+        ) -> c_int;
+        pub fn sqlite3_bind_text(stmt: *mut Stmt, index: c_int, text: *const c_char, bytes: c_int, destructor: isize) -> c_int;
+        pub fn sqlite3_bind_int64(stmt: *mut Stmt, index: c_int, value: i64) -> c_int;
+        pub fn sqlite3_bind_double(stmt: *mut Stmt, index: c_int, value: f64) -> c_int;
+        pub fn sqlite3_step(stmt: *mut Stmt) -> c_int;
+        pub fn sqlite3_reset(stmt: *mut Stmt) -> c_int;
+        pub fn sqlite3_finalize(stmt: *mut Stmt) -> c_int;
+        pub fn sqlite3_column_text(stmt: *mut Stmt, column: c_int) -> *const u8;
+        pub fn sqlite3_column_int64(stmt: *mut Stmt, column: c_int) -> i64;
+        pub fn sqlite3_column_double(stmt: *mut Stmt, column: c_int) -> f64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::arrival::{ArrivalProcess, ArrivalSource};
+    use crate::jockey::JockeyPolicy;
+    use crate::product::{ProductDependency, ProductSpec};
+    use crate::resource::SharedResource;
+    use crate::scheduler::{QueueDiscipline, SchedulingAlgorithm};
+    use crate::station::StationConfig;
+    use crate::stop::StopCondition;
+
+    #[test]
+    fn test_config_hash_tracks_configuration() {
+        let stations = || vec![StationConfig::new("Corte", Duration::from_millis(10))];
+        let base = Simulation::with_config(stations(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO]);
+        let same = Simulation::with_config(stations(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO]);
+        let later = Simulation::with_config(stations(), SchedulingAlgorithm::fcfs(), vec![Duration::from_millis(1)]);
+
+        assert_eq!(config_hash(&base), config_hash(&same));
+        assert_ne!(config_hash(&base), config_hash(&later));
+        assert_eq!(config_hash(&base).len(), 16);
+    }
+
+    #[test]
+    fn test_config_hash_covers_the_full_configuration() {
+        let stations = vec![StationConfig::new("Corte", Duration::from_millis(10)); 2];
+        let base = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+        let variants = [
+            base.clone_with(|simulation| {
+                simulation.add_arrival_source(ArrivalSource::new(
+                    "extra",
+                    ArrivalProcess::Fixed(vec![Duration::ZERO]),
+                    ProductSpec::default(),
+                ));
+            }),
+            base.clone_with(|simulation| {
+                simulation.set_due_dates(vec![Duration::from_millis(50); 2]);
+            }),
+            base.clone_with(|simulation| {
+                simulation.add_dependency(ProductDependency { product: 1, depends_on: 2, station: 1 });
+            }),
+            base.clone_with(|simulation| {
+                simulation.add_shared_resource(SharedResource::new("Grúa", 1).used_by(0));
+            }),
+            base.clone_with(|simulation| {
+                simulation.set_queue_discipline(1, QueueDiscipline::lifo());
+            }),
+            base.clone_with(|simulation| {
+                simulation.set_patience(Duration::from_millis(20));
+            }),
+            base.clone_with(|simulation| {
+                simulation.set_jockeying(JockeyPolicy::shorter_queue(1));
+            }),
+            base.clone_with(|simulation| {
+                simulation.set_product_size(1, 2.0);
+            }),
+            base.clone_with(|simulation| {
+                simulation.set_stop_condition(StopCondition::Completions(1));
+            }),
+            base.clone_with(|simulation| {
+                simulation.set_tick_length(Duration::from_micros(10));
+            }),
+        ];
+
+        let hashes: HashSet<String> = variants.iter().map(config_hash).collect();
+        assert_eq!(hashes.len(), variants.len());
+        assert!(!hashes.contains(&config_hash(&base)));
+        assert_eq!(config_hash(&variants[3]), config_hash(&variants[3].clone()));
+    }
+
+    #[test]
+    fn test_open_reports_sqlite_errors() {
+        let error = ResultStore::open("/nonexistent-dir/results.db").err().expect("La ruta no debe abrirse");
+        assert_ne!(error.code, ffi::SQLITE_OK);
+        assert!(!error.message.is_empty());
+    }
+}
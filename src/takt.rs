@@ -0,0 +1,190 @@
+//! # Módulo de Takt Time
+//! 
+//! Este módulo compara el ritmo de salida de la línea con un takt time
+//! objetivo: el intervalo entre productos terminados que exige la demanda.
+//! A partir de la serie de intervalos entre salidas consecutivas se
+//! calcula el tiempo de ciclo real de cada período del reporte y se marcan
+//! los intervalos en los que la línea se atrasó respecto del takt.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::arrival::ArrivalPeriod;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+
+/// Intervalo entre dos salidas consecutivas que superó el takt time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LateInterval {
+    /// Salida anterior, relativa al inicio de la simulación
+    pub start: Duration,
+    /// Salida que llegó tarde, relativa al inicio de la simulación
+    pub end: Duration,
+    /// Exceso del intervalo sobre el takt time
+    pub excess: Duration,
+}
+
+/// Comparación del tiempo de ciclo de un período con el takt time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaktPeriod {
+    /// Nombre del período
+    pub name: String,
+    /// Inicio del período
+    pub start: Duration,
+    /// Fin del período (exclusivo)
+    pub end: Duration,
+    /// Productos que terminaron la línea durante el período
+    pub departures: usize,
+    /// Promedio de los intervalos entre salidas que terminaron en el
+    /// período, o `None` si no terminó ninguno
+    pub average_cycle_time: Option<Duration>,
+    /// Indica si el tiempo de ciclo superó el takt time (o si no hubo salidas)
+    pub behind: bool,
+}
+
+/// Resultado de comparar la línea con un takt time objetivo.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// // La línea entrega un producto cada 50ms pero la demanda exige uno cada 40ms
+/// let stations = vec![StationConfig::new("Soldadura", Duration::from_millis(50))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+/// simulation.set_takt_time(Duration::from_millis(40));
+/// 
+/// let metrics = simulation.run().unwrap();
+/// let takt = metrics.takt.unwrap();
+/// assert!(!takt.on_pace());
+/// assert_eq!(takt.late_intervals.len(), 3);
+/// assert!(takt.periods[0].behind);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaktReport {
+    /// Takt time objetivo
+    pub takt_time: Duration,
+    /// Promedio de los intervalos entre salidas consecutivas de toda la
+    /// simulación, o `None` si terminaron menos de dos productos
+    pub average_cycle_time: Option<Duration>,
+    /// Intervalos entre salidas que superaron el takt time, en orden
+    pub late_intervals: Vec<LateInterval>,
+    /// Comparación por período del reporte; sin períodos, uno solo que
+    /// abarca toda la simulación
+    pub periods: Vec<TaktPeriod>,
+}
+
+impl TaktReport {
+    /// Compara la serie de salidas de una simulación con el takt time.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// * `takt_time` - Intervalo objetivo entre productos terminados
+    /// * `periods` - Períodos del reporte (vacío para evaluar la simulación
+    ///   completa)
+    /// 
+    /// # Returns
+    /// 
+    /// La comparación con los intervalos atrasados y el detalle por período
+    pub fn analyze(metrics: &SimulationMetrics, takt_time: Duration, periods: &[ArrivalPeriod]) -> Self {
+        let mut departures: Vec<Duration> = metrics.products
+            .iter()
+            .map(|product| product.arrival_time + product.turnaround_time)
+            .collect();
+        departures.sort();
+        let intervals: Vec<(Duration, Duration)> = departures.windows(2).map(|pair| (pair[0], pair[1])).collect();
+
+        let late_intervals = intervals
+            .iter()
+            .filter_map(|&(start, end)| {
+                let excess = (end - start).checked_sub(takt_time).filter(|excess| !excess.is_zero())?;
+                Some(LateInterval { start, end, excess })
+            })
+            .collect();
+
+        let whole = [ArrivalPeriod {
+            name: "Total".to_string(),
+            start: Duration::ZERO,
+            end: metrics.total_simulation_time + Duration::from_nanos(1),
+            mean_interarrival: Duration::ZERO,
+        }];
+        let periods = if periods.is_empty() { &whole[..] } else { periods };
+        let periods = periods
+            .iter()
+            .map(|period| {
+                let within = |time: Duration| time >= period.start && time < period.end;
+                let cycle_times: Vec<Duration> = intervals
+                    .iter()
+                    .filter(|(_, end)| within(*end))
+                    .map(|(start, end)| *end - *start)
+                    .collect();
+                let average_cycle_time = Self::average(&cycle_times);
+                TaktPeriod {
+                    name: period.name.clone(),
+                    start: period.start,
+                    end: period.end,
+                    departures: departures.iter().filter(|&&time| within(time)).count(),
+                    average_cycle_time,
+                    behind: average_cycle_time.is_none_or(|cycle_time| cycle_time > takt_time),
+                }
+            })
+            .collect();
+
+        let all_cycle_times: Vec<Duration> = intervals.iter().map(|(start, end)| *end - *start).collect();
+        Self {
+            takt_time,
+            average_cycle_time: Self::average(&all_cycle_times),
+            late_intervals,
+            periods,
+        }
+    }
+
+    /// Indica si el tiempo de ciclo promedio de la simulación no superó el
+    /// takt time.
+    pub fn on_pace(&self) -> bool {
+        self.average_cycle_time.is_some_and(|cycle_time| cycle_time <= self.takt_time)
+    }
+
+    /// Promedio de una serie de duraciones, o `None` si está vacía.
+    fn average(durations: &[Duration]) -> Option<Duration> {
+        (!durations.is_empty()).then(|| durations.iter().sum::<Duration>() / durations.len() as u32)
+    }
+}
+
+impl fmt::Display for TaktReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = MetricsCalculator::format_duration;
+        let cycle = |cycle_time: Option<Duration>| cycle_time.map_or_else(|| "-".to_string(), time);
+        writeln!(
+            f,
+            "Takt time: {}, tiempo de ciclo promedio: {} ({})",
+            time(self.takt_time),
+            cycle(self.average_cycle_time),
+            if self.on_pace() { "al ritmo" } else { "atrasada" }
+        )?;
+        for period in &self.periods {
+            writeln!(
+                f,
+                "{} [{} - {}): {} salidas, ciclo {}{}",
+                period.name,
+                time(period.start),
+                time(period.end),
+                period.departures,
+                cycle(period.average_cycle_time),
+                if period.behind { " ATRASADA" } else { "" }
+            )?;
+        }
+        write!(f, "Intervalos sobre el takt: {}", self.late_intervals.len())?;
+        for interval in &self.late_intervals {
+            write!(
+                f,
+                "\n  {} - {} (+{})",
+                time(interval.start),
+                time(interval.end),
+                time(interval.excess)
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+//! # Módulo de Plantillas de Escenarios
+//! 
+//! Este módulo incluye en la biblioteca escenarios de ejemplo listos para
+//! ejecutar, que ilustran situaciones clásicas de planificación:
+//! 
+//! - `convoy`: una estación lenta al inicio retiene a los productos que
+//!   llegan juntos (efecto convoy con FCFS)
+//! - `burst`: llegadas en ráfagas atendidas con Round Robin
+//! - `balanced`: línea balanceada con llegadas al ritmo de la línea
+//! 
+//! Las plantillas están escritas en YAML (en `templates/`) y pueden
+//! exportarse en cualquiera de los formatos de escenario para editarlas
+//! (ver [`ScenarioTemplate::render`]). Desde la línea de comandos se usan
+//! con `--template <nombre>` y `--emit <archivo>`.
+
+use crate::json::Json;
+use crate::ron;
+use crate::scenario::{Scenario, ScenarioFormat};
+use crate::toml;
+use crate::yaml;
+
+/// Escenario de ejemplo incluido en la biblioteca.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScenarioTemplate {
+    /// Nombre con el que se elige la plantilla
+    pub name: &'static str,
+    /// Descripción de una línea
+    pub description: &'static str,
+    /// Documento YAML de la plantilla, con comentarios
+    pub source: &'static str,
+}
+
+/// Plantillas disponibles, en el orden en que se listan
+pub const TEMPLATES: [ScenarioTemplate; 3] = [
+    ScenarioTemplate {
+        name: "convoy",
+        description: "Estación lenta al inicio con llegadas simultáneas (efecto convoy con FCFS)",
+        source: include_str!("../templates/convoy.yaml"),
+    },
+    ScenarioTemplate {
+        name: "burst",
+        description: "Llegadas en ráfagas de 4 productos atendidas con Round Robin",
+        source: include_str!("../templates/burst.yaml"),
+    },
+    ScenarioTemplate {
+        name: "balanced",
+        description: "Línea balanceada con llegadas al ritmo de la línea",
+        source: include_str!("../templates/balanced.yaml"),
+    },
+];
+
+impl ScenarioTemplate {
+    /// Busca una plantilla por su nombre.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::ScenarioTemplate;
+    /// 
+    /// let template = ScenarioTemplate::find("convoy").unwrap();
+    /// let metrics = template.scenario().to_simulation().run().unwrap();
+    /// assert_eq!(metrics.products.len(), 6);
+    /// assert!(ScenarioTemplate::find("otra").is_none());
+    /// ```
+    pub fn find(name: &str) -> Option<&'static ScenarioTemplate> {
+        TEMPLATES.iter().find(|template| template.name == name)
+    }
+
+    /// Nombres de las plantillas disponibles, separados por comas.
+    pub fn names() -> String {
+        TEMPLATES.iter().map(|template| template.name).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Construye el escenario de la plantilla.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la plantilla no es un escenario válido, lo que indica
+    /// un error en la plantilla incluida
+    pub fn scenario(&self) -> Scenario {
+        Scenario::from_yaml(self.source)
+            .unwrap_or_else(|error| panic!("La plantilla '{}' no es válida: {}", self.name, error))
+    }
+
+    /// Escribe la plantilla en el formato indicado, para guardarla y
+    /// editarla.
+    /// 
+    /// En YAML se conserva el documento original con sus comentarios; en
+    /// JSON, RON y TOML se convierte el mismo documento, por lo que se mantienen
+    /// sus claves (por ejemplo, `burst` en lugar de la lista de llegadas).
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{Scenario, ScenarioFormat, ScenarioTemplate};
+    /// 
+    /// let template = ScenarioTemplate::find("burst").unwrap();
+    /// let json = template.render(ScenarioFormat::Json);
+    /// assert!(json.contains("\"burst\": {"));
+    /// let scenario = Scenario::from_json(&json).unwrap();
+    /// assert_eq!(scenario.arrivals, template.scenario().arrivals);
+    /// ```
+    pub fn render(&self, format: ScenarioFormat) -> String {
+        let document = || -> Json {
+            yaml::parse(self.source)
+                .unwrap_or_else(|error| panic!("La plantilla '{}' no es válida: {}", self.name, error))
+        };
+        match format {
+            ScenarioFormat::Yaml => self.source.to_string(),
+            ScenarioFormat::Json => format!("{}\n", document().to_pretty_string()),
+            ScenarioFormat::Ron => format!("{}\n", ron::to_string(&document())),
+            ScenarioFormat::Toml => toml::to_string(&document()),
+        }
+    }
+}
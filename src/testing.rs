@@ -0,0 +1,584 @@
+//! # Módulo de Pruebas
+//! 
+//! Este módulo ofrece herramientas para probar decisiones de planificación
+//! sin hilos ni esperas reales, de modo que las pruebas (las de esta
+//! biblioteca y las de quien la use) sean exactas y no dependan de la carga
+//! de la máquina:
+//! 
+//! - [`FakeClock`]: un reloj que solo avanza cuando la prueba lo indica
+//! - [`StationHarness`]: conduce una estación a través de una secuencia de
+//!   llegadas escrita de antemano y registra cada porción que atiende
+//!   ([`SchedulingDecision`]), para comparar el orden y los momentos de
+//!   atención con los esperados
+//! - `assert_golden_trace` (feature `async`): compara la traza de eventos
+//!   de una simulación en tiempo virtual con un archivo de referencia
+//! 
+//! El arnés usa la misma lógica de planificación y contabilidad que las
+//! estaciones de una simulación (algoritmo, disciplina de cola, desistimiento
+//! y abandono, curva de aprendizaje, calentamiento e inspecciones), pero no
+//! ejecuta el modelo de trabajo: el reloj salta directamente al final de
+//! cada porción. Como en el backend en tiempo virtual, los productos que
+//! llegan durante una porción se encolan detrás del producto interrumpido.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "async")]
+use std::path::Path;
+
+use crate::clock::{Clock, Instant, VirtualClock};
+use crate::metrics::MetricsCalculator;
+use crate::product::{lock_recover, Product, ProductSpec};
+use crate::scheduler::SchedulingAlgorithm;
+use crate::station::{enqueue, Station, StationConfig, StationReport};
+use crate::status::{StationStatus, StatusMachine};
+#[cfg(feature = "async")]
+use crate::simulation::Simulation;
+
+/// Variable de entorno que, definida, hace que [`assert_golden_trace`]
+/// reescriba los archivos de referencia en lugar de compararlos.
+#[cfg(feature = "async")]
+pub const UPDATE_GOLDEN_ENV: &str = "SIMULATOR_UPDATE_GOLDEN";
+
+/// Reloj controlable para pruebas.
+/// 
+/// Comienza en el tiempo cero y solo avanza con [`FakeClock::advance`] o
+/// [`FakeClock::set`]; las copias del reloj comparten el mismo tiempo. Con
+/// [`FakeClock::clock`] puede asignarse a una estación o a cualquier
+/// componente que reciba un [`Clock`].
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::testing::FakeClock;
+/// 
+/// let clock = FakeClock::new();
+/// let start = clock.now();
+/// clock.advance(Duration::from_millis(250));
+/// clock.set(Duration::from_secs(1));
+/// assert_eq!(clock.elapsed(), Duration::from_secs(1));
+/// assert_eq!(clock.clock().now() - start, Duration::from_secs(1));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FakeClock {
+    clock: VirtualClock,
+}
+
+impl FakeClock {
+    /// Crea un reloj detenido en el tiempo cero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retorna el instante actual del reloj.
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Retorna el tiempo transcurrido desde el tiempo cero.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed()
+    }
+
+    /// Adelanta el reloj la duración indicada.
+    pub fn advance(&self, duration: Duration) {
+        self.set(self.elapsed() + duration);
+    }
+
+    /// Lleva el reloj al momento indicado, medido desde el tiempo cero.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el momento es anterior al tiempo actual del reloj
+    pub fn set(&self, elapsed: Duration) {
+        assert!(
+            elapsed >= self.elapsed(),
+            "El reloj de prueba no puede retroceder de {} a {}",
+            MetricsCalculator::format_duration(self.elapsed()),
+            MetricsCalculator::format_duration(elapsed)
+        );
+        self.clock.advance_to(self.clock.now() - self.clock.elapsed() + elapsed);
+    }
+
+    /// Retorna el reloj como fuente de tiempo de una estación.
+    pub fn clock(&self) -> Clock {
+        Clock::Virtual(self.clock.clone())
+    }
+}
+
+impl From<FakeClock> for Clock {
+    fn from(clock: FakeClock) -> Self {
+        clock.clock()
+    }
+}
+
+/// Porción de procesamiento que una estación decidió atender.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SchedulingDecision {
+    /// ID del producto atendido
+    pub product: usize,
+    /// Inicio de la porción, relativo al inicio de la prueba
+    pub start: Duration,
+    /// Fin de la porción, relativo al inicio de la prueba
+    pub end: Duration,
+    /// Indica si el producto completó la estación con esta porción
+    /// (`false` si fue interrumpido por el quantum y volvió a la cola)
+    pub completed: bool,
+}
+
+impl fmt::Display for SchedulingDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{} - {}] producto {:02} {}",
+            MetricsCalculator::format_duration(self.start),
+            MetricsCalculator::format_duration(self.end),
+            self.product,
+            if self.completed { "completado" } else { "interrumpido" }
+        )
+    }
+}
+
+/// Arnés que conduce una estación a través de llegadas programadas.
+/// 
+/// Cada llegada crea un producto (con IDs desde 1, en el orden en que se
+/// programan) que solo visita la estación, con el tiempo de procesamiento
+/// indicado escalado por su tamaño. [`StationHarness::step`] atiende la
+/// siguiente porción y [`StationHarness::run`] atiende todas; entre
+/// porciones el reloj salta a la siguiente llegada si la cola está vacía.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{SchedulingAlgorithm, StationConfig};
+/// use assembly_line_simulator::testing::StationHarness;
+/// 
+/// let millis = Duration::from_millis;
+/// let config = StationConfig::new("Corte", millis(10));
+/// let mut harness = StationHarness::new(config, SchedulingAlgorithm::round_robin(millis(20)));
+/// harness.arrive(millis(0), millis(50));
+/// harness.arrive(millis(10), millis(20));
+/// harness.run();
+/// 
+/// // El producto 2 llega durante la primera porción y se encola detrás del
+/// // producto 1, que vuelve a la cola al agotar su quantum
+/// harness.assert_service_order(&[1, 1, 2, 1]);
+/// harness.assert_completion_order(&[2, 1]);
+/// assert_eq!(harness.decisions()[2].start, millis(40));
+/// assert_eq!(harness.product(1).total_wait_time(), millis(20));
+/// assert_eq!(harness.report().preemptions, 2);
+/// ```
+pub struct StationHarness {
+    /// Estación conducida por el arnés (índice 0 de una línea de una estación)
+    station: Station,
+    /// Reloj de la prueba
+    clock: FakeClock,
+    /// Productos programados que aún no llegan, ordenados por llegada
+    arrivals: VecDeque<Arc<Product>>,
+    /// Todos los productos programados, por ID
+    products: Vec<Arc<Product>>,
+    /// Cola de la estación
+    queue: VecDeque<Arc<Product>>,
+    /// Estado de la estación y su contabilidad de tiempos
+    status: StatusMachine,
+    /// Estadísticas de la estación
+    report: StationReport,
+    /// Porciones atendidas, en orden
+    decisions: Vec<SchedulingDecision>,
+}
+
+impl StationHarness {
+    /// Crea un arnés para una estación con la configuración y el algoritmo
+    /// indicados.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `config` - Configuración de la estación
+    /// * `algorithm` - Algoritmo de planificación de la estación
+    pub fn new(config: StationConfig, algorithm: SchedulingAlgorithm) -> Self {
+        let clock = FakeClock::new();
+        let station = Station::new(0, config, algorithm)
+            .with_clock(clock.clock())
+            .with_start_time(clock.now());
+        Self {
+            status: station.status_machine(clock.now()),
+            report: station.new_report(),
+            station,
+            clock,
+            arrivals: VecDeque::new(),
+            products: Vec::new(),
+            queue: VecDeque::new(),
+            decisions: Vec::new(),
+        }
+    }
+
+    /// Modifica la estación con sus constructores (disciplina de cola,
+    /// impaciencia, curva de aprendizaje, calentamiento, observadores).
+    /// 
+    /// El reloj y el inicio de la estación los fija el arnés, por lo que
+    /// no deben reemplazarse.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la estación ya atendió alguna porción
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{QueueDiscipline, SchedulingAlgorithm, StationConfig};
+    /// use assembly_line_simulator::testing::StationHarness;
+    /// 
+    /// let millis = Duration::from_millis;
+    /// let mut harness = StationHarness::new(StationConfig::new("Corte", millis(10)), SchedulingAlgorithm::fcfs())
+    ///     .configure(|station| station.with_discipline(QueueDiscipline::lifo()));
+    /// for _ in 0..3 {
+    ///     harness.arrive(Duration::ZERO, millis(10));
+    /// }
+    /// harness.run();
+    /// harness.assert_service_order(&[3, 2, 1]);
+    /// ```
+    pub fn configure(mut self, configure: impl FnOnce(Station) -> Station) -> Self {
+        assert!(self.decisions.is_empty(), "La estación debe configurarse antes de atender productos");
+        self.station = configure(self.station);
+        self.status = self.station.status_machine(self.clock.now());
+        self.report = self.station.new_report();
+        self
+    }
+
+    /// Programa la llegada de un producto.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `at` - Momento de llegada, relativo al inicio de la prueba
+    /// * `processing_time` - Tiempo de procesamiento del producto
+    /// 
+    /// # Returns
+    /// 
+    /// El ID del producto
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el momento de llegada ya pasó
+    pub fn arrive(&mut self, at: Duration, processing_time: Duration) -> usize {
+        self.arrive_with(at, processing_time, ProductSpec::default())
+    }
+
+    /// Programa la llegada de un producto con atributos (prioridad, fecha de
+    /// entrega, metadatos, paciencia o tamaño).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `at` - Momento de llegada, relativo al inicio de la prueba
+    /// * `processing_time` - Tiempo de procesamiento del producto antes de
+    ///   escalarlo por su tamaño
+    /// * `spec` - Atributos del producto
+    /// 
+    /// # Returns
+    /// 
+    /// El ID del producto
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el momento de llegada ya pasó
+    pub fn arrive_with(&mut self, at: Duration, processing_time: Duration, spec: ProductSpec) -> usize {
+        assert!(
+            at >= self.clock.elapsed(),
+            "La llegada en {} ya pasó (el reloj de prueba está en {})",
+            MetricsCalculator::format_duration(at),
+            MetricsCalculator::format_duration(self.clock.elapsed())
+        );
+        let config = StationConfig {
+            processing_time,
+            ..self.station.config.clone()
+        };
+        let product = Product::from_spec(self.products.len() + 1, at, &spec, &[config]);
+        let position = self.arrivals.partition_point(|pending| pending.arrival_offset <= at);
+        self.arrivals.insert(position, Arc::clone(&product));
+        self.products.push(product);
+        self.products.len()
+    }
+
+    /// Atiende la siguiente porción de procesamiento.
+    /// 
+    /// Encola los productos que ya llegaron, retira los que agotaron su
+    /// paciencia y atiende al producto que elige la estación; si la cola
+    /// está vacía, adelanta el reloj a la siguiente llegada. Al terminar la
+    /// porción encola los productos que llegaron durante ella.
+    /// 
+    /// # Returns
+    /// 
+    /// La porción atendida, o `None` si no quedan productos por atender
+    pub fn step(&mut self) -> Option<SchedulingDecision> {
+        let product = loop {
+            self.admit_arrivals();
+            self.station.renege(&mut self.queue, &mut self.report);
+            if let Some(product) = self.station.take_next_ready(&mut self.queue, &mut self.report) {
+                break product;
+            }
+            self.status.transition(StationStatus::Idle, self.clock.now(), &mut self.report);
+            let next = self.arrivals.front()?.arrival_offset;
+            self.report.wakeups += 1;
+            self.clock.set(next);
+        };
+
+        if let Some(warm_up) = self.station.start_warm_up(&product, &mut self.status, &mut self.report) {
+            self.clock.advance(warm_up);
+        }
+        let (started_at, remaining, slice) = self.station.start_slice(&product, &[], Duration::ZERO);
+        self.status.transition(StationStatus::Busy, started_at, &mut self.report);
+        let overshoot = self.station.timer_overshoot();
+        self.clock.advance(slice + overshoot);
+        let completed = self.station.finish_slice(&product, started_at, remaining, slice, overshoot, &mut self.report);
+        if completed {
+            self.station.reject(&product, &mut self.report);
+        } else {
+            enqueue(&mut self.queue, Arc::clone(&product));
+            self.report.max_queue_length = self.report.max_queue_length.max(self.queue.len());
+        }
+        // Los productos que llegaron durante la porción se encolan detrás del
+        // producto interrumpido
+        self.admit_arrivals();
+
+        let decision = SchedulingDecision {
+            product: product.id,
+            start: self.station.since_start(started_at),
+            end: self.clock.elapsed(),
+            completed,
+        };
+        self.decisions.push(decision);
+        Some(decision)
+    }
+
+    /// Atiende porciones hasta que no queden productos por atender.
+    /// 
+    /// # Returns
+    /// 
+    /// Todas las porciones atendidas desde el inicio de la prueba
+    pub fn run(&mut self) -> &[SchedulingDecision] {
+        while self.step().is_some() {}
+        &self.decisions
+    }
+
+    /// Porciones atendidas desde el inicio de la prueba, en orden.
+    pub fn decisions(&self) -> &[SchedulingDecision] {
+        &self.decisions
+    }
+
+    /// IDs de los productos atendidos en cada porción, en orden.
+    pub fn service_order(&self) -> Vec<usize> {
+        self.decisions.iter().map(|decision| decision.product).collect()
+    }
+
+    /// IDs de los productos en el orden en que completaron la estación.
+    pub fn completion_order(&self) -> Vec<usize> {
+        self.decisions
+            .iter()
+            .filter(|decision| decision.completed)
+            .map(|decision| decision.product)
+            .collect()
+    }
+
+    /// IDs de los productos en la cola de la estación, en orden.
+    pub fn queued(&self) -> Vec<usize> {
+        self.queue.iter().map(|product| product.id).collect()
+    }
+
+    /// Comprueba el orden en que la estación atendió las porciones.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic, mostrando todas las porciones atendidas, si el orden no
+    /// es el esperado
+    pub fn assert_service_order(&self, expected: &[usize]) {
+        assert_eq!(self.service_order(), expected, "Orden de atención inesperado:\n{}", self.schedule());
+    }
+
+    /// Comprueba el orden en que los productos completaron la estación.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic, mostrando todas las porciones atendidas, si el orden no
+    /// es el esperado
+    pub fn assert_completion_order(&self, expected: &[usize]) {
+        assert_eq!(self.completion_order(), expected, "Orden de finalización inesperado:\n{}", self.schedule());
+    }
+
+    /// Retorna un producto programado.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si no se programó un producto con ese ID
+    pub fn product(&self, id: usize) -> &Arc<Product> {
+        id.checked_sub(1)
+            .and_then(|index| self.products.get(index))
+            .unwrap_or_else(|| panic!("No se programó el producto {}", id))
+    }
+
+    /// Estadísticas de la estación hasta la última porción atendida.
+    pub fn report(&self) -> &StationReport {
+        &self.report
+    }
+
+    /// Reloj de la prueba.
+    pub fn clock(&self) -> &FakeClock {
+        &self.clock
+    }
+
+    /// Encola los productos cuya llegada ya ocurrió, como lo hace el
+    /// generador de una simulación.
+    fn admit_arrivals(&mut self) {
+        while self.arrivals.front().is_some_and(|product| product.arrival_offset <= self.clock.elapsed()) {
+            let product = self.arrivals.pop_front().expect("Llegada pendiente");
+            product.set_arrival_time(product.arrival_offset);
+            lock_recover(product.visit_state(0)).queue_entry.get_or_insert(product.arrival_offset);
+            self.station.admit(product, &mut self.queue, &mut self.report);
+        }
+    }
+
+    /// Lista las porciones atendidas, una por línea.
+    fn schedule(&self) -> String {
+        self.decisions.iter().map(|decision| format!("  {}\n", decision)).collect()
+    }
+}
+
+/// Comprueba que la traza de eventos de una simulación en tiempo virtual
+/// coincida con un archivo de referencia.
+/// 
+/// Si el archivo no existe, o si está definida la variable de entorno
+/// `SIMULATOR_UPDATE_GOLDEN` (ver [`UPDATE_GOLDEN_ENV`]), guarda la traza
+/// como nueva referencia en lugar de compararla.
+/// 
+/// # Panics
+/// 
+/// Hace panic con el reporte de la divergencia si las trazas difieren, o si
+/// la simulación falla o el archivo no puede leerse o escribirse
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{SchedulingAlgorithm, Simulation, StationConfig};
+/// use assembly_line_simulator::testing::assert_golden_trace;
+/// 
+/// let path = std::env::temp_dir().join(format!("traza-prueba-{}.trace", std::process::id()));
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(30))];
+/// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// assert_golden_trace(&simulation, &path); // crea la referencia
+/// assert_golden_trace(&simulation, &path); // la compara
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[cfg(feature = "async")]
+pub fn assert_golden_trace(simulation: &Simulation, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    if !path.exists() || std::env::var_os(UPDATE_GOLDEN_ENV).is_some() {
+        if let Err(error) = simulation.save_golden_trace(path) {
+            panic!("No se pudo guardar la traza de referencia: {}", error);
+        }
+        return;
+    }
+    match simulation.check_golden_trace(path) {
+        Ok(None) => {}
+        Ok(Some(divergence)) => panic!(
+            "La traza no coincide con '{}' (defina {} para actualizarla)\n{}",
+            path.display(),
+            UPDATE_GOLDEN_ENV,
+            divergence
+        ),
+        Err(error) => panic!("No se pudo comparar la traza de referencia: {}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    #[test]
+    fn test_round_robin_slices_are_exact() {
+        let mut harness = StationHarness::new(
+            StationConfig::new("Corte", millis(10)),
+            SchedulingAlgorithm::round_robin(millis(30)),
+        );
+        harness.arrive(millis(0), millis(90));
+        harness.arrive(millis(5), millis(30));
+        harness.arrive(millis(200), millis(10));
+
+        let decisions = harness.run().to_vec();
+        let slices: Vec<_> = decisions.iter().map(|decision| (decision.product, decision.start, decision.end)).collect();
+        assert_eq!(
+            slices,
+            vec![
+                (1, millis(0), millis(30)),
+                (1, millis(30), millis(60)),
+                (2, millis(60), millis(90)),
+                (1, millis(90), millis(120)),
+                (3, millis(200), millis(210)),
+            ]
+        );
+        assert_eq!(harness.completion_order(), vec![2, 1, 3]);
+        assert_eq!(harness.product(2).total_wait_time(), millis(55));
+        assert_eq!(harness.product(3).total_wait_time(), Duration::ZERO);
+        assert_eq!(harness.clock().elapsed(), millis(210));
+
+        let report = harness.report();
+        assert_eq!(report.busy_time, millis(130));
+        assert_eq!(report.idle_time, millis(80));
+        assert_eq!(report.slices_executed, 5);
+        assert_eq!(report.slice_drift, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_poisoned_product_state_is_recovered() {
+        let mut harness = StationHarness::new(
+            StationConfig::new("Corte", millis(10)),
+            SchedulingAlgorithm::round_robin(millis(5)),
+        );
+        harness.arrive(millis(0), millis(10));
+
+        // Otra estación hizo panic mientras tenía el estado del producto
+        let product = Arc::clone(harness.product(1));
+        let _ = std::thread::spawn(move || {
+            let _state = product.visit_state(0).lock();
+            panic!("Falla simulada de otra estación");
+        })
+        .join();
+        assert!(harness.product(1).visit_state(0).is_poisoned());
+
+        harness.run();
+        assert_eq!(harness.completion_order(), vec![1]);
+        assert_eq!(harness.report().slices_executed, 2);
+    }
+
+    #[test]
+    fn test_due_dates_reorder_waiting_products() {
+        let mut harness = StationHarness::new(
+            StationConfig::new("Corte", millis(50)),
+            SchedulingAlgorithm::earliest_due_date(),
+        );
+        harness.arrive_with(millis(0), millis(50), ProductSpec::default().due_at(millis(1000)));
+        harness.arrive_with(millis(10), millis(50), ProductSpec::default().due_at(millis(1000)));
+        harness.arrive_with(millis(10), millis(50), ProductSpec::default().due_at(millis(60)));
+
+        assert_eq!(harness.step().map(|decision| decision.product), Some(1));
+        assert_eq!(harness.queued(), vec![2, 3]);
+        harness.run();
+        harness.assert_service_order(&[1, 3, 2]);
+        assert_eq!(harness.product(3).completion_time(), Some(millis(100)));
+    }
+
+    #[test]
+    #[should_panic(expected = "no puede retroceder")]
+    fn test_fake_clock_never_goes_back() {
+        let clock = FakeClock::new();
+        clock.set(millis(20));
+        clock.set(millis(10));
+    }
+}
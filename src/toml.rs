@@ -0,0 +1,678 @@
+//! # Módulo de TOML
+//! 
+//! Analizador TOML mínimo para leer escenarios escritos en ese formato.
+//! Como los analizadores YAML y RON, produce el mismo árbol [`Json`] que el
+//! analizador JSON, por lo que el esquema de los escenarios es el mismo en
+//! todos los formatos.
+//! 
+//! Se admite lo que usan los archivos de configuración: pares `clave =
+//! valor` (con claves simples, entre comillas o con puntos), tablas
+//! (`[burst]`), arreglos de tablas (`[[stations]]`), arreglos de varias
+//! líneas, tablas en línea (`{ size = 4, gap_ms = 1500 }`), strings básicos
+//! y literales, enteros (incluidos `0x`, `0o` y `0b`), decimales, booleanos
+//! y comentarios con `#`. No se admiten strings multilínea ni fechas. TOML
+//! no tiene nulo, por lo que una clave ausente equivale a `null`.
+//! 
+//! ```toml
+//! algorithm = "rr"
+//! quantum_ms = 100
+//! arrivals_ms = [0, 120, 260]
+//! 
+//! [[stations]]
+//! name = "Corte"
+//! processing_time_ms = 400
+//! ```
+
+use crate::json::{too_deep, Json, Locations, MAX_DEPTH};
+
+/// Analiza un documento TOML.
+/// 
+/// # Arguments
+/// 
+/// * `text` - Texto del documento
+/// 
+/// # Returns
+/// 
+/// El valor del documento (siempre un objeto), o `Err` con la descripción
+/// y la posición del primer error
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::json::Json;
+/// use assembly_line_simulator::toml;
+/// 
+/// let value = toml::parse("quantum_ms = 100\n\n[[stations]]\nname = 'Corte'\n").unwrap();
+/// assert_eq!(value, Json::parse(r#"{"quantum_ms": 100, "stations": [{"name": "Corte"}]}"#).unwrap());
+/// ```
+pub fn parse(text: &str) -> Result<Json, String> {
+    parse_located(text).map(|(value, _)| value)
+}
+
+/// Analiza un documento TOML y registra la línea de cada valor (ver
+/// [`Locations`]).
+pub fn parse_located(text: &str) -> Result<(Json, Locations), String> {
+    let mut parser = Parser { text, position: 0, depth: 0, path: String::new(), locations: Locations::default() };
+    let mut root = Vec::new();
+    // Tabla abierta por el último encabezado y tablas ya definidas con uno
+    let mut table: Vec<String> = Vec::new();
+    let mut defined: Vec<String> = Vec::new();
+
+    parser.skip_blank()?;
+    parser.locations.record("", parser.line());
+    while parser.peek().is_some() {
+        let start = parser.position;
+        let line = parser.line();
+        if parser.rest().starts_with('[') {
+            let array = parser.rest().starts_with("[[");
+            parser.position += if array { 2 } else { 1 };
+            let keys = parser.parse_keys()?;
+            parser.skip_spaces();
+            parser.expect_str(if array { "]]" } else { "]" })?;
+            if keys.len() >= MAX_DEPTH {
+                return Err(parser.error(&too_deep()));
+            }
+            let (parent, path, _) = navigate(&mut root, &keys[..keys.len() - 1], String::new(), 0)
+                .map_err(|message| parser.error_at(start, &message))?;
+            let last = &keys[keys.len() - 1];
+            let path = Locations::field(&path, last);
+            let existing = parent.iter_mut().find(|(key, _)| key == last).map(|(_, value)| value);
+            let path = match (array, existing) {
+                (true, None) => {
+                    parent.push((last.clone(), Json::Array(vec![Json::Object(Vec::new())])));
+                    parser.locations.record(&path, line);
+                    Locations::index(&path, 0)
+                }
+                (true, Some(Json::Array(items))) if items.iter().all(|item| matches!(item, Json::Object(_))) => {
+                    items.push(Json::Object(Vec::new()));
+                    Locations::index(&path, items.len() - 1)
+                }
+                (false, None) => {
+                    parent.push((last.clone(), Json::Object(Vec::new())));
+                    path
+                }
+                (false, Some(Json::Object(_))) if !defined.contains(&path) => path,
+                _ => return Err(parser.error_at(start, &format!("la tabla '{}' ya está definida", path))),
+            };
+            parser.locations.record(&path, line);
+            defined.push(path);
+            table = keys;
+        } else {
+            let keys = parser.parse_keys()?;
+            parser.skip_spaces();
+            parser.expect_str("=")?;
+            let (entries, path, depth) = navigate(&mut root, &table, String::new(), 1)
+                .map_err(|message| parser.error_at(start, &message))?;
+            parser.depth = depth;
+            parser.parse_pair_value(entries, &keys, path, line, start)?;
+        }
+        parser.end_line()?;
+        parser.skip_blank()?;
+    }
+    Ok((Json::Object(root), parser.locations))
+}
+
+/// Escribe un objeto como documento TOML.
+/// 
+/// Los valores simples de cada tabla se escriben primero como `clave =
+/// valor`; los objetos se escriben después como tablas (`[clave]`) y los
+/// arreglos de objetos como arreglos de tablas (`[[clave]]`). Los objetos
+/// dentro de otros arreglos se escriben como tablas en línea. TOML no tiene
+/// nulo, por lo que las claves con valor nulo se omiten.
+/// 
+/// # Panics
+/// 
+/// Hace panic si `value` no es un objeto, porque un documento TOML es
+/// siempre una tabla
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::json::Json;
+/// use assembly_line_simulator::toml;
+/// 
+/// let value = Json::parse(r#"{"algorithm": "fcfs", "stations": [{"name": "Corte"}], "burst": {"size": 4}}"#).unwrap();
+/// let text = toml::to_string(&value);
+/// assert_eq!(text, "algorithm = \"fcfs\"\n\n[[stations]]\nname = \"Corte\"\n\n[burst]\nsize = 4\n");
+/// assert_eq!(toml::parse(&text).unwrap(), value);
+/// ```
+pub fn to_string(value: &Json) -> String {
+    let Json::Object(entries) = value else {
+        panic!("Un documento TOML debe ser un objeto, no un {}", value.type_name());
+    };
+    let mut output = String::new();
+    write_table(entries, "", &mut output);
+    output
+}
+
+fn write_table(entries: &[(String, Json)], path: &str, output: &mut String) {
+    for (key, value) in entries {
+        if !matches!(value, Json::Null) && !is_table(value) && !is_table_array(value) {
+            output.push_str(&format!("{} = ", write_key(key)));
+            write_inline(value, output);
+            output.push('\n');
+        }
+    }
+    for (key, value) in entries {
+        let child = if path.is_empty() { write_key(key) } else { format!("{}.{}", path, write_key(key)) };
+        match value {
+            Json::Object(inner) if is_table(value) => {
+                output.push_str(&format!("\n[{}]\n", child));
+                write_table(inner, &child, output);
+            }
+            Json::Array(items) if is_table_array(value) => {
+                for item in items {
+                    if let Json::Object(inner) = item {
+                        output.push_str(&format!("\n[[{}]]\n", child));
+                        write_table(inner, &child, output);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn write_inline(value: &Json, output: &mut String) {
+    match value {
+        Json::Array(items) => {
+            output.push('[');
+            for (index, item) in items.iter().filter(|item| !matches!(item, Json::Null)).enumerate() {
+                if index > 0 {
+                    output.push_str(", ");
+                }
+                write_inline(item, output);
+            }
+            output.push(']');
+        }
+        Json::Object(entries) => {
+            output.push('{');
+            for (index, (key, value)) in entries.iter().filter(|(_, value)| !matches!(value, Json::Null)).enumerate() {
+                output.push_str(if index > 0 { ", " } else { " " });
+                output.push_str(&format!("{} = ", write_key(key)));
+                write_inline(value, output);
+            }
+            output.push_str(if entries.is_empty() { "}" } else { " }" });
+        }
+        // Los strings básicos de TOML admiten los escapes de JSON usados aquí
+        scalar => output.push_str(&scalar.to_string()),
+    }
+}
+
+/// Indica si el valor se escribe como tabla con su propio encabezado.
+fn is_table(value: &Json) -> bool {
+    matches!(value, Json::Object(_))
+}
+
+/// Indica si el valor se escribe como arreglo de tablas.
+fn is_table_array(value: &Json) -> bool {
+    matches!(value, Json::Array(items) if !items.is_empty() && items.iter().all(is_table))
+}
+
+/// Escribe una clave simple tal cual y las demás entre comillas.
+fn write_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(is_bare_key) {
+        key.to_string()
+    } else {
+        Json::String(key.to_string()).to_string()
+    }
+}
+
+/// Indica si el carácter puede formar parte de una clave sin comillas.
+fn is_bare_key(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Pares clave-valor de una tabla.
+type Entries = Vec<(String, Json)>;
+
+/// Recorre (creando las que falten) las tablas indicadas por `keys` a partir
+/// de `entries`. En un arreglo de tablas se continúa por el último elemento.
+/// 
+/// # Returns
+/// 
+/// Las entradas de la tabla encontrada, su ruta y su profundidad (`depth`
+/// más los niveles recorridos), o `Err` si alguna clave ya tiene un valor
+/// que no es una tabla
+fn navigate<'a>(
+    mut entries: &'a mut Entries,
+    keys: &[String],
+    mut path: String,
+    mut depth: usize,
+) -> Result<(&'a mut Entries, String, usize), String> {
+    for key in keys {
+        let index = match entries.iter().position(|(existing, _)| existing == key) {
+            Some(index) => index,
+            None => {
+                entries.push((key.clone(), Json::Object(Vec::new())));
+                entries.len() - 1
+            }
+        };
+        path = Locations::field(&path, key);
+        depth += 1;
+        entries = match &mut entries[index].1 {
+            Json::Object(inner) => inner,
+            Json::Array(items) => {
+                let last = items.len().wrapping_sub(1);
+                match items.last_mut() {
+                    Some(Json::Object(inner)) => {
+                        path = Locations::index(&path, last);
+                        depth += 1;
+                        inner
+                    }
+                    _ => return Err(format!("la clave '{}' ya tiene un valor que no es una tabla", path)),
+                }
+            }
+            _ => return Err(format!("la clave '{}' ya tiene un valor que no es una tabla", path)),
+        };
+    }
+    Ok((entries, path, depth))
+}
+
+/// Analizador recursivo descendente.
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+    /// Tablas y arreglos abiertos en la posición actual
+    depth: usize,
+    /// Ruta del valor que se está leyendo
+    path: String,
+    locations: Locations,
+}
+
+impl Parser<'_> {
+    /// Línea (desde 1) de la posición actual.
+    fn line(&self) -> usize {
+        self.text[..self.position].matches('\n').count() + 1
+    }
+
+    /// Construye un error con la línea y columna de la posición actual.
+    fn error(&self, message: &str) -> String {
+        self.error_at(self.position, message)
+    }
+
+    /// Construye un error con la línea y columna de `position`.
+    fn error_at(&self, position: usize, message: &str) -> String {
+        let consumed = &self.text[..position];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.rsplit('\n').next().map_or(0, |last| last.chars().count()) + 1;
+        format!("TOML inválido en línea {}, columna {}: {}", line, column, message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn rest(&self) -> &str {
+        &self.text[self.position..]
+    }
+
+    /// Salta espacios y tabulaciones dentro de la línea.
+    fn skip_spaces(&mut self) {
+        while let Some(c) = self.peek().filter(|&c| c == ' ' || c == '\t') {
+            self.position += c.len_utf8();
+        }
+    }
+
+    /// Salta espacios, saltos de línea y comentarios.
+    fn skip_blank(&mut self) -> Result<(), String> {
+        loop {
+            while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+                self.position += c.len_utf8();
+            }
+            if self.peek() == Some('#') {
+                self.position += self.rest().find('\n').unwrap_or(self.rest().len());
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Verifica que la línea termina (admite un comentario final).
+    fn end_line(&mut self) -> Result<(), String> {
+        self.skip_spaces();
+        if self.peek() == Some('#') {
+            self.position += self.rest().find('\n').unwrap_or(self.rest().len());
+        }
+        if self.rest().starts_with("\r\n") || self.rest().starts_with('\n') || self.rest().is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("se esperaba un salto de línea"))
+        }
+    }
+
+    fn expect_str(&mut self, expected: &str) -> Result<(), String> {
+        if self.rest().starts_with(expected) {
+            self.position += expected.len();
+            Ok(())
+        } else {
+            Err(self.error(&format!("se esperaba '{}'", expected)))
+        }
+    }
+
+    /// Lee una clave, posiblemente con puntos (`burst.gap_ms`).
+    fn parse_keys(&mut self) -> Result<Vec<String>, String> {
+        let mut keys = Vec::new();
+        loop {
+            self.skip_spaces();
+            let key = match self.peek() {
+                Some('"') => self.parse_basic_string()?,
+                Some('\'') => self.parse_literal_string()?,
+                _ => {
+                    let start = self.position;
+                    while let Some(c) = self.peek().filter(|&c| is_bare_key(c)) {
+                        self.position += c.len_utf8();
+                    }
+                    if start == self.position {
+                        return Err(self.error("se esperaba una clave"));
+                    }
+                    self.text[start..self.position].to_string()
+                }
+            };
+            keys.push(key);
+            self.skip_spaces();
+            if self.peek() != Some('.') {
+                return Ok(keys);
+            }
+            self.position += 1;
+        }
+    }
+
+    /// Lee el valor de un par `claves = valor` y lo agrega a `entries`,
+    /// cuya ruta es `path`. `start` es la posición de la clave, para los
+    /// errores.
+    fn parse_pair_value(
+        &mut self,
+        entries: &mut Entries,
+        keys: &[String],
+        path: String,
+        line: usize,
+        start: usize,
+    ) -> Result<(), String> {
+        let (last, parents) = keys.split_last().expect("Una clave tiene al menos un segmento");
+        // Se verifica antes de crear las tablas intermedias de una clave con
+        // puntos, que de otro modo podrían ser miles
+        if self.depth + parents.len() >= MAX_DEPTH {
+            return Err(self.error_at(start, &too_deep()));
+        }
+        let (table, path, depth) = navigate(entries, parents, path, self.depth)
+            .map_err(|message| self.error_at(start, &message))?;
+        if depth >= MAX_DEPTH {
+            return Err(self.error_at(start, &too_deep()));
+        }
+        if table.iter().any(|(key, _)| key == last) {
+            return Err(self.error_at(start, &format!("clave repetida '{}'", Locations::field(&path, last))));
+        }
+        let depth = std::mem::replace(&mut self.depth, depth);
+        self.skip_spaces();
+        let value = self.parse_value_at(Locations::field(&path, last), line);
+        self.depth = depth;
+        table.push((last.clone(), value?));
+        Ok(())
+    }
+
+    /// Lee el valor de la ruta `path`, que empieza en la línea `line`.
+    fn parse_value_at(&mut self, path: String, line: usize) -> Result<Json, String> {
+        self.locations.record(&path, line);
+        let parent = std::mem::replace(&mut self.path, path);
+        let value = self.parse_value();
+        self.path = parent;
+        value
+    }
+
+    fn parse_value(&mut self) -> Result<Json, String> {
+        match self.peek() {
+            Some('[') => self.parse_nested(Self::parse_array),
+            Some('{') => self.parse_nested(Self::parse_inline_table),
+            Some('"') if self.rest().starts_with("\"\"\"") => Err(self.error("no se admiten strings multilínea")),
+            Some('\'') if self.rest().starts_with("'''") => Err(self.error("no se admiten strings multilínea")),
+            Some('"') => self.parse_basic_string().map(Json::String),
+            Some('\'') => self.parse_literal_string().map(Json::String),
+            Some(c) if c == '-' || c == '+' || c.is_ascii_alphanumeric() => self.parse_scalar(),
+            Some('\n') | Some('\r') | None => Err(self.error("falta el valor")),
+            Some(_) => Err(self.error("valor inesperado")),
+        }
+    }
+
+    /// Lee un valor anidado, rechazando los que superan [`MAX_DEPTH`].
+    fn parse_nested(&mut self, parse: fn(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error(&too_deep()));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    /// Lee un arreglo, que puede ocupar varias líneas y tener coma final.
+    fn parse_array(&mut self) -> Result<Json, String> {
+        self.expect_str("[")?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank()?;
+            if self.peek() == Some(']') {
+                self.position += 1;
+                return Ok(Json::Array(items));
+            }
+            let path = Locations::index(&self.path, items.len());
+            let line = self.line();
+            items.push(self.parse_value_at(path, line)?);
+            self.skip_blank()?;
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {}
+                _ => return Err(self.error("se esperaba ',' o ']'")),
+            }
+        }
+    }
+
+    /// Lee una tabla en línea (`{ clave = valor, ... }`), en una sola línea.
+    fn parse_inline_table(&mut self) -> Result<Json, String> {
+        self.expect_str("{")?;
+        let mut entries = Vec::new();
+        self.skip_spaces();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Json::Object(entries));
+        }
+        loop {
+            let start = self.position;
+            let line = self.line();
+            let keys = self.parse_keys()?;
+            self.expect_str("=")?;
+            let path = self.path.clone();
+            self.parse_pair_value(&mut entries, &keys, path, line, start)?;
+            self.skip_spaces();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(Json::Object(entries));
+                }
+                _ => return Err(self.error("se esperaba ',' o '}'")),
+            }
+        }
+    }
+
+    /// Lee un booleano o un número.
+    fn parse_scalar(&mut self) -> Result<Json, String> {
+        let start = self.position;
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_alphanumeric() || "+-._".contains(*c)) {
+            self.position += c.len_utf8();
+        }
+        let token = &self.text[start..self.position];
+        match token {
+            "true" => return Ok(Json::Bool(true)),
+            "false" => return Ok(Json::Bool(false)),
+            _ => {}
+        }
+        let digits = token.replace('_', "");
+        let (sign, unsigned) = match digits.strip_prefix('-') {
+            Some(unsigned) => (-1.0, unsigned),
+            None => (1.0, digits.strip_prefix('+').unwrap_or(&digits)),
+        };
+        let radix = match unsigned.get(..2) {
+            Some("0x") => Some(16),
+            Some("0o") => Some(8),
+            Some("0b") => Some(2),
+            _ => None,
+        };
+        let number = match radix {
+            Some(radix) => i64::from_str_radix(&unsigned[2..], radix).ok().map(|number| sign * number as f64),
+            None => unsigned.parse::<f64>().ok().map(|number| sign * number),
+        };
+        number
+            .filter(|number| number.is_finite() && !token.starts_with('.') && !token.ends_with('.'))
+            .map(Json::Number)
+            .ok_or_else(|| {
+                self.position = start;
+                self.error("valor inválido (se esperaba un número, un booleano o un string)")
+            })
+    }
+
+    /// Lee un string entre comillas dobles, con escapes.
+    fn parse_basic_string(&mut self) -> Result<String, String> {
+        self.expect_str("\"")?;
+        let mut result = String::new();
+        loop {
+            let c = self.peek().filter(|&c| c != '\n').ok_or_else(|| self.error("string sin cerrar"))?;
+            self.position += c.len_utf8();
+            match c {
+                '"' => return Ok(result),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("string sin cerrar"))?;
+                    self.position += escaped.len_utf8();
+                    result.push(match escaped {
+                        'b' => '\u{8}',
+                        't' => '\t',
+                        'n' => '\n',
+                        'f' => '\u{c}',
+                        'r' => '\r',
+                        '"' | '\\' => escaped,
+                        'u' => self.parse_unicode(4)?,
+                        'U' => self.parse_unicode(8)?,
+                        _ => return Err(self.error("escape inválido")),
+                    });
+                }
+                c => result.push(c),
+            }
+        }
+    }
+
+    /// Lee los `digits` dígitos hexadecimales de un escape `\u` o `\U`.
+    fn parse_unicode(&mut self, digits: usize) -> Result<char, String> {
+        let code = self
+            .rest()
+            .get(..digits)
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("escape unicode inválido"))?;
+        self.position += digits;
+        Ok(code)
+    }
+
+    /// Lee un string literal entre comillas simples, sin escapes.
+    fn parse_literal_string(&mut self) -> Result<String, String> {
+        self.expect_str("'")?;
+        let length = self
+            .rest()
+            .find(['\'', '\n'])
+            .filter(|&end| self.rest()[end..].starts_with('\''))
+            .ok_or_else(|| self.error("string sin cerrar"))?;
+        let result = self.rest()[..length].to_string();
+        self.position += length + 1;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tables_arrays_and_inline_values() {
+        let text = r#"
+            # escenario
+            algorithm = "rr"   # comentario final
+            quantum_ms = 1_000
+            "clave con espacios" = 'C:\literal'
+            tiempos = [
+                1, 2.5, -3e2,  # comentario
+                0x10,
+            ]
+            burst = { size = 4, gap.ms = 1500 }
+            activo = true
+
+            [[stations]]
+            name = "Corte\tA"
+
+            [[stations]]
+            name = "Empaque"
+            extra.color = "rojo"
+
+            [reloj]
+            formato = "ticks"
+        "#;
+        let expected = Json::parse(
+            r#"{"algorithm": "rr", "quantum_ms": 1000, "clave con espacios": "C:\\literal",
+                "tiempos": [1, 2.5, -300, 16], "burst": {"size": 4, "gap": {"ms": 1500}}, "activo": true,
+                "stations": [{"name": "Corte\tA"}, {"name": "Empaque", "extra": {"color": "rojo"}}],
+                "reloj": {"formato": "ticks"}}"#,
+        )
+        .unwrap();
+        assert_eq!(parse(text).unwrap(), expected);
+
+        let (_, locations) = parse_located(text).unwrap();
+        assert_eq!(locations.line(""), Some(3));
+        assert_eq!(locations.line("tiempos[3]"), Some(8));
+        assert_eq!(locations.line("burst.size"), Some(10));
+        assert_eq!(locations.line("stations[1]"), Some(16));
+        assert_eq!(locations.line("stations[1].name"), Some(17));
+        assert_eq!(locations.line("reloj.formato"), Some(21));
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        let error = parse("a = 1\nb = [1, 2\nc = 3").unwrap_err();
+        assert!(error.contains("línea 3"), "{}", error);
+        assert!(parse("a = 1\na = 2").unwrap_err().contains("clave repetida 'a'"));
+        assert!(parse("[t]\nx = 1\n[t]\ny = 2").unwrap_err().contains("ya está definida"));
+        assert!(parse("a = 1\n[a]").unwrap_err().contains("ya está definida"));
+        assert!(parse("a = 1\n[a.b]").unwrap_err().contains("no es una tabla"));
+        assert!(parse("a = 1 2").unwrap_err().contains("salto de línea"));
+        assert!(parse("a = \"abierto").unwrap_err().contains("sin cerrar"));
+        assert!(parse("a = nan").is_err());
+        assert!(parse("a = 1979-05-27").is_err());
+        assert!(parse("a = \"\"\"\nvarias\n\"\"\"").unwrap_err().contains("multilínea"));
+        assert!(parse("a =").unwrap_err().contains("falta el valor"));
+    }
+
+    #[test]
+    fn test_to_string_round_trips() {
+        let value = Json::parse(
+            r#"{"name": "línea \"A\"", "times": [0, 1.5], "nested": [[1], [{"a": 1}]], "skip": null,
+                "stations": [{"name": "Corte", "kind": {"type": "split"}}], "burst": {"size": 4, "extra key": {}}}"#,
+        )
+        .unwrap();
+        let text = to_string(&value);
+        let Json::Object(mut entries) = value else { unreachable!() };
+        entries.retain(|(key, _)| key != "skip");
+        assert_eq!(parse(&text).unwrap(), Json::Object(entries));
+    }
+
+    #[test]
+    fn test_deep_nesting_is_rejected() {
+        let nested = |depth: usize| format!("a = {}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH - 1)).is_ok());
+        // Sin el límite, estos documentos desbordarían la pila al validarlos
+        for text in [
+            nested(200_000),
+            format!("a = {}", "{ b = ".repeat(200_000)),
+            format!("[{}]", vec!["t"; 200_000].join(".")),
+            format!("{} = 1", vec!["k"; 200_000].join(".")),
+        ] {
+            assert!(parse(&text).unwrap_err().contains("niveles de anidamiento"));
+        }
+    }
+}
@@ -0,0 +1,621 @@
+//! # Módulo de Topología de la Línea
+//! 
+//! Este módulo construye el grafo de la línea a partir de la configuración
+//! de sus estaciones: por dónde entran los productos, a qué estaciones
+//! puede enviarlos cada una (según sean inspecciones, divisores o uniones)
+//! y por dónde salen. El grafo se obtiene recorriendo las rutas base de los
+//! productos (la ruta completa y la de cada tipo del catálogo) con todas
+//! las decisiones posibles de sus estaciones, del mismo modo en que se
+//! resuelve la ruta de cada producto al crearlo.
+//! 
+//! [`Simulation::validate`] usa el grafo para rechazar, antes de lanzar los
+//! hilos, las topologías que no pueden funcionar:
+//! 
+//! - Estaciones que ningún producto puede alcanzar
+//! - Ciclos de los que los productos no pueden salir: un retrabajo solo es
+//!   válido si alguna de sus salidas lleva al final de la ruta
+//! - Salidas de divisores que ningún producto puede tomar y uniones que
+//!   esperan productos de un origen que nunca les envía
+//! - Rutas y productos que referencian estaciones o tipos inexistentes
+//! 
+//! El grafo también puede exportarse en formato DOT de Graphviz (ver
+//! [`Simulation::export_topology_dot`]), con los tiempos, algoritmos,
+//! buffers y recursos de cada estación y, tras una ejecución, su
+//! utilización.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::error::ConfigError;
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::routing::{self, MergePolicy, RouteTarget, RoutingPolicy, StationKind};
+use crate::simulation::Simulation;
+
+/// Nodo del grafo de la línea.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TopologyNode {
+    /// Entrada de la línea, desde donde llegan los productos
+    Entry,
+    /// Estación de la línea (índice desde 0)
+    Station(usize),
+    /// Salida de la línea, por donde salen los productos terminados
+    Exit,
+    /// Rechazo, por donde salen los productos que no aprueban una inspección
+    Rejected,
+}
+
+impl fmt::Display for TopologyNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Entry => write!(f, "entrada"),
+            Self::Station(index) => write!(f, "estación {}", index + 1),
+            Self::Exit => write!(f, "salida"),
+            Self::Rejected => write!(f, "rechazo"),
+        }
+    }
+}
+
+/// Conexión posible entre dos nodos del grafo: algún producto puede pasar
+/// de `from` a `to`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopologyEdge {
+    /// Nodo de origen
+    pub from: TopologyNode,
+    /// Nodo de destino
+    pub to: TopologyNode,
+    /// Salida de la estación de origen que lleva al destino (por ejemplo,
+    /// `aprobados` o `salida 2`), si la estación tiene varias
+    pub label: Option<String>,
+}
+
+/// Grafo de la línea: las conexiones que pueden recorrer los productos.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{
+///     Inspection, RouteTarget, Simulation, SchedulingAlgorithm, StationConfig, TopologyNode,
+/// };
+/// 
+/// let millis = Duration::from_millis;
+/// let stations = vec![
+///     StationConfig::new("Ensamblaje", millis(10)),
+///     StationConfig::inspection("Control", Inspection::pass_rate(0.9, 1).with_fail_target(RouteTarget::Station(0))),
+///     StationConfig::new("Empaque", millis(5)),
+/// ];
+/// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// 
+/// let topology = simulation.topology();
+/// assert!(topology.reachable.iter().all(|&reachable| reachable));
+/// assert!(topology.closed_loops.is_empty());
+/// let rework = topology.edges_from(TopologyNode::Station(1));
+/// assert_eq!(rework.len(), 2);
+/// assert_eq!(rework[1].to, TopologyNode::Station(0));
+/// assert_eq!(rework[1].label.as_deref(), Some("no aprobados"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Topology {
+    /// Nombres de las estaciones, en orden de la línea
+    pub stations: Vec<String>,
+    /// Conexiones posibles, sin repetir, en el orden en que se encuentran
+    pub edges: Vec<TopologyEdge>,
+    /// Indica, por estación, si algún producto puede visitarla
+    pub reachable: Vec<bool>,
+    /// Estaciones de cada ciclo del que los productos no pueden salir, en
+    /// orden de la línea
+    pub closed_loops: Vec<Vec<usize>>,
+    /// Salidas de divisores (estación, posición de la salida) que ningún
+    /// producto puede tomar
+    pub unused_outputs: Vec<(usize, usize)>,
+}
+
+/// Siguiente paso de un producto desde una visita.
+#[derive(Clone, Copy)]
+enum Step {
+    /// Otra visita de la ruta (índice del estado)
+    Visit(usize),
+    /// Fin de la ruta
+    Exit,
+    /// Rechazo en una inspección
+    Rejected,
+}
+
+/// Visita posible de una ruta base: (ruta, posición en la ruta, estación).
+type Visit = (usize, usize, usize);
+
+impl Topology {
+    /// Construye el grafo de una simulación.
+    /// 
+    /// Las rutas de tipos que referencian estaciones inexistentes se
+    /// omiten; [`Simulation::validate`] las reporta.
+    pub fn of(simulation: &Simulation) -> Self {
+        let configs = &simulation.station_configs;
+        let station_count = configs.len();
+        // Rutas base: la completa (productos sin tipo) y la de cada tipo
+        let full_route: Vec<usize> = (0..station_count).collect();
+        let routes: Vec<(Vec<usize>, Option<&str>)> = std::iter::once((full_route.clone(), None))
+            .chain(simulation.catalog.types().into_iter().map(|product_type| {
+                let route = product_type.route.clone().unwrap_or_else(|| full_route.clone());
+                (route, Some(product_type.name.as_str()))
+            }))
+            .filter(|(route, _)| !route.is_empty() && route.iter().all(|&station| station < station_count))
+            .collect();
+
+        let mut topology = Self {
+            stations: configs.iter().map(|config| config.name.clone()).collect(),
+            reachable: vec![false; station_count],
+            ..Self::default()
+        };
+        let mut ids: HashMap<Visit, usize> = HashMap::new();
+        let mut visits: Vec<Visit> = Vec::new();
+        let mut steps: Vec<Vec<Step>> = Vec::new();
+        let mut pending = VecDeque::new();
+        let mut used_outputs: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut intern = |visit: Visit, visits: &mut Vec<Visit>, pending: &mut VecDeque<usize>| {
+            *ids.entry(visit).or_insert_with(|| {
+                visits.push(visit);
+                pending.push_back(visits.len() - 1);
+                visits.len() - 1
+            })
+        };
+
+        for (route, (base, _)) in routes.iter().enumerate() {
+            intern((route, 0, base[0]), &mut visits, &mut pending);
+            topology.add_edge(TopologyNode::Entry, TopologyNode::Station(base[0]), None);
+        }
+        while let Some(id) = pending.pop_front() {
+            let (route, position, station) = visits[id];
+            let (base, product_type) = &routes[route];
+            topology.reachable[station] = true;
+            let choices: Vec<(RouteTarget, Option<String>)> = match &configs[station].kind {
+                StationKind::Processing | StationKind::Merger(_) => vec![(RouteTarget::Next, None)],
+                StationKind::Inspection(inspection) => inspection
+                    .outcomes()
+                    .into_iter()
+                    .map(|passed| match passed {
+                        true => (inspection.on_pass, Some("aprobados".to_string())),
+                        false => (inspection.on_fail, Some("no aprobados".to_string())),
+                    })
+                    .collect(),
+                StationKind::Splitter(splitter) => {
+                    let outputs = splitter.reachable_outputs(*product_type);
+                    used_outputs.entry(station).or_default().extend(&outputs);
+                    outputs
+                        .into_iter()
+                        .map(|output| (splitter.outputs[output], Some(format!("salida {}", output + 1))))
+                        .collect()
+                }
+            };
+
+            let mut next_steps = Vec::new();
+            for (target, label) in choices {
+                let (step, node) = match routing::next_visit(base, position, target) {
+                    Some((position, next)) => (
+                        Step::Visit(intern((route, position, next), &mut visits, &mut pending)),
+                        TopologyNode::Station(next),
+                    ),
+                    None if target == RouteTarget::Reject => (Step::Rejected, TopologyNode::Rejected),
+                    None => (Step::Exit, TopologyNode::Exit),
+                };
+                next_steps.push(step);
+                topology.add_edge(TopologyNode::Station(station), node, label);
+            }
+            steps.push(next_steps);
+        }
+
+        topology.closed_loops = closed_loops(&visits, &steps);
+        for (station, config) in configs.iter().enumerate() {
+            if let (StationKind::Splitter(splitter), Some(used)) = (&config.kind, used_outputs.get(&station)) {
+                let unused = (0..splitter.outputs.len()).filter(|output| !used.contains(output));
+                topology.unused_outputs.extend(unused.map(|output| (station, output)));
+            }
+        }
+        topology
+    }
+
+    /// Agrega una conexión si aún no está en el grafo.
+    fn add_edge(&mut self, from: TopologyNode, to: TopologyNode, label: Option<String>) {
+        let edge = TopologyEdge { from, to, label };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Conexiones que salen de un nodo.
+    pub fn edges_from(&self, node: TopologyNode) -> Vec<&TopologyEdge> {
+        self.edges.iter().filter(|edge| edge.from == node).collect()
+    }
+
+    /// Orígenes desde los que llegan productos a una estación: las
+    /// estaciones de las que puede venir (`Some`) o la entrada de la línea
+    /// (`None`), en orden de la línea y sin repetir.
+    pub fn sources_of(&self, station: usize) -> Vec<Option<usize>> {
+        let mut sources: Vec<Option<usize>> = self.edges
+            .iter()
+            .filter(|edge| edge.to == TopologyNode::Station(station))
+            .filter_map(|edge| match edge.from {
+                TopologyNode::Entry => Some(None),
+                TopologyNode::Station(index) => Some(Some(index)),
+                _ => None,
+            })
+            .collect();
+        sources.sort_unstable();
+        sources.dedup();
+        sources
+    }
+}
+
+/// Genera la descripción DOT de Graphviz de la línea de una simulación.
+/// 
+/// Cada estación es un nodo con su tiempo de servicio, su algoritmo, su
+/// tipo (inspección, divisor o unión) y la capacidad de su buffer de
+/// entrada si los canales son acotados; la forma del nodo indica el tipo.
+/// Los recursos compartidos son nodos unidos con líneas punteadas a las
+/// estaciones que los usan, y las conexiones hacia estaciones anteriores
+/// (retrabajos y recirculaciones) se dibujan punteadas. Con métricas, cada
+/// estación indica además su utilización y se colorea de verde (ociosa) a
+/// rojo (saturada).
+/// 
+/// # Arguments
+/// 
+/// * `simulation` - Simulación cuya línea se describe
+/// * `metrics` - Métricas de una ejecución de la simulación, si se anotan
+pub(crate) fn to_dot(simulation: &Simulation, metrics: Option<&SimulationMetrics>) -> String {
+    let topology = Topology::of(simulation);
+    let buffer = simulation.channel_backend.capacity();
+    let node_id = |node: TopologyNode| match node {
+        TopologyNode::Entry => "entrada".to_string(),
+        TopologyNode::Station(index) => format!("estacion_{}", index),
+        TopologyNode::Exit => "salida".to_string(),
+        TopologyNode::Rejected => "rechazo".to_string(),
+    };
+
+    let mut dot = String::from("digraph linea {\n");
+    dot.push_str("    rankdir=LR;\n");
+    dot.push_str("    node [fontname=\"Helvetica\", fontsize=11];\n");
+    dot.push_str("    edge [fontname=\"Helvetica\", fontsize=9];\n");
+    dot.push_str("    entrada [label=\"Entrada\", shape=circle];\n");
+    dot.push_str("    salida [label=\"Salida\", shape=doublecircle];\n");
+    if topology.edges.iter().any(|edge| edge.to == TopologyNode::Rejected) {
+        dot.push_str("    rechazo [label=\"Rechazo\", shape=octagon];\n");
+    }
+
+    for (index, config) in simulation.station_configs.iter().enumerate() {
+        let (shape, kind) = match &config.kind {
+            StationKind::Processing => ("box", None),
+            StationKind::Inspection(inspection) => ("diamond", Some(format!("inspección: {}", inspection.rule))),
+            StationKind::Splitter(splitter) => ("trapezium", Some(format!("divisor: {}", splitter.policy))),
+            StationKind::Merger(merger) => ("invtrapezium", Some(format!("unión: {}", merger.policy))),
+        };
+        let mut lines = vec![
+            config.name.clone(),
+            format!(
+                "{} · {}",
+                MetricsCalculator::format_duration(config.processing_time),
+                simulation.station_algorithm(index)
+            ),
+        ];
+        lines.extend(kind);
+        lines.extend(buffer.map(|capacity| format!("buffer: {}", capacity)));
+        let mut attributes = format!("shape={}", shape);
+        if let Some(report) = metrics.and_then(|metrics| metrics.station_reports.get(index)) {
+            let utilization = report.utilization();
+            lines.push(format!("utilización: {:.1}%", utilization * 100.0));
+            // Tono de verde (0.33) a rojo (0.0) según la utilización
+            attributes.push_str(&format!(
+                ", style=filled, fillcolor=\"{:.3} 0.35 1.000\"",
+                (1.0 - utilization.clamp(0.0, 1.0)) * 0.33
+            ));
+        }
+        dot.push_str(&format!(
+            "    {} [label={}, {}];\n",
+            node_id(TopologyNode::Station(index)),
+            dot_string(&lines.join("\n")),
+            attributes
+        ));
+    }
+
+    for (index, resource) in simulation.resources.iter().enumerate() {
+        dot.push_str(&format!(
+            "    recurso_{} [label={}, shape=component];\n",
+            index,
+            dot_string(&format!("{}\n{} unidades", resource.name, resource.capacity))
+        ));
+        for user in &resource.users {
+            let label = user.product_type
+                .as_ref()
+                .map(|product_type| format!(", label={}", dot_string(product_type)))
+                .unwrap_or_default();
+            dot.push_str(&format!(
+                "    recurso_{} -> {} [style=dotted, arrowhead=none{}];\n",
+                index,
+                node_id(TopologyNode::Station(user.station)),
+                label
+            ));
+        }
+    }
+
+    for edge in &topology.edges {
+        let mut attributes = Vec::new();
+        if let Some(label) = &edge.label {
+            attributes.push(format!("label={}", dot_string(label)));
+        }
+        if let (TopologyNode::Station(from), TopologyNode::Station(to)) = (edge.from, edge.to) {
+            if to <= from {
+                attributes.push("style=dashed".to_string());
+                attributes.push("constraint=false".to_string());
+            }
+        }
+        let attributes = if attributes.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attributes.join(", "))
+        };
+        dot.push_str(&format!("    {} -> {}{};\n", node_id(edge.from), node_id(edge.to), attributes));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Convierte un texto en un string de DOT entre comillas dobles; los saltos
+/// de línea se escriben como `\n`, que Graphviz centra en la etiqueta.
+fn dot_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// Busca las visitas que forman ciclos desde los que no se llega al final
+/// de la ruta, y retorna las estaciones de cada ciclo sin repetir.
+fn closed_loops(visits: &[Visit], steps: &[Vec<Step>]) -> Vec<Vec<usize>> {
+    // Visitas desde las que algún producto puede terminar su ruta
+    let mut exits = vec![false; visits.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (id, next_steps) in steps.iter().enumerate() {
+            let exit = next_steps.iter().any(|step| match step {
+                Step::Exit => true,
+                Step::Visit(next) => exits[*next],
+                Step::Rejected => false,
+            });
+            if exit && !exits[id] {
+                exits[id] = true;
+                changed = true;
+            }
+        }
+    }
+
+    let successors = |id: usize| {
+        let mut seen = vec![false; visits.len()];
+        let mut pending: Vec<usize> = vec![id];
+        while let Some(current) = pending.pop() {
+            for step in &steps[current] {
+                if let Step::Visit(next) = *step {
+                    if !seen[next] {
+                        seen[next] = true;
+                        pending.push(next);
+                    }
+                }
+            }
+        }
+        seen
+    };
+    let reach: Vec<Vec<bool>> = (0..visits.len()).map(successors).collect();
+
+    let mut loops: Vec<Vec<usize>> = Vec::new();
+    for id in (0..visits.len()).filter(|&id| !exits[id] && reach[id][id]) {
+        // Componente del ciclo: las visitas alcanzables que vuelven a esta
+        let mut stations: Vec<usize> = (0..visits.len())
+            .filter(|&other| reach[id][other] && reach[other][id])
+            .map(|other| visits[other].2)
+            .collect();
+        stations.sort_unstable();
+        stations.dedup();
+        if !loops.contains(&stations) {
+            loops.push(stations);
+        }
+    }
+    loops
+}
+
+/// Verifica la topología de una simulación.
+/// 
+/// # Returns
+/// 
+/// `Ok(())` si la topología es válida, o el primer `ConfigError` encontrado
+pub(crate) fn check(simulation: &Simulation) -> Result<(), ConfigError> {
+    let configs = &simulation.station_configs;
+    let station_count = configs.len();
+    let unknown_station = |route: String, station_index: usize| ConfigError::UnknownRouteStation {
+        route,
+        station_index,
+        station_count,
+    };
+
+    // Referencias a estaciones y tipos inexistentes
+    for product_type in simulation.catalog.types() {
+        if let Some(&station) = product_type.route.iter().flatten().find(|&&station| station >= station_count) {
+            return Err(unknown_station(format!("la ruta del tipo '{}'", product_type.name), station));
+        }
+    }
+    if let Some(dependency) = simulation.dependencies.iter().find(|dependency| dependency.station >= station_count) {
+        return Err(unknown_station(format!("la dependencia del producto {}", dependency.product), dependency.station));
+    }
+    let known = |name: &String| simulation.catalog.get(name).is_some();
+    let mut assigned: Vec<(&usize, &String)> = simulation.product_types.iter().collect();
+    assigned.sort_unstable();
+    if let Some((id, name)) = assigned.into_iter().find(|(_, name)| !known(name)) {
+        return Err(ConfigError::UnknownProductType { used_by: format!("el producto {}", id), type_name: name.clone() });
+    }
+    for source in &simulation.sources {
+        if let Some(name) = source.spec.product_type.as_ref().filter(|name| !known(name)) {
+            return Err(ConfigError::UnknownProductType {
+                used_by: format!("la fuente '{}'", source.name),
+                type_name: name.clone(),
+            });
+        }
+    }
+    for (station_index, config) in configs.iter().enumerate() {
+        if let StationKind::Splitter(splitter) = &config.kind {
+            if let RoutingPolicy::ByType(rules) = &splitter.policy {
+                if let Some((name, _)) = rules.iter().find(|(name, _)| !known(name)) {
+                    return Err(ConfigError::DanglingOutput {
+                        station_index,
+                        station: config.name.clone(),
+                        reason: format!("la regla del tipo '{}' nunca se aplica: el tipo no está registrado", name),
+                    });
+                }
+            }
+        }
+    }
+
+    let topology = Topology::of(simulation);
+    if let Some(station_index) = topology.reachable.iter().position(|&reachable| !reachable) {
+        return Err(ConfigError::UnreachableStation { station_index, station: configs[station_index].name.clone() });
+    }
+    if let Some(stations) = topology.closed_loops.first() {
+        return Err(ConfigError::ClosedLoop {
+            stations: stations.clone(),
+            names: stations.iter().map(|&station| configs[station].name.clone()).collect(),
+        });
+    }
+    if let Some(&(station_index, output)) = topology.unused_outputs.first() {
+        let StationKind::Splitter(splitter) = &configs[station_index].kind else {
+            unreachable!("Solo los divisores tienen salidas sin uso");
+        };
+        return Err(ConfigError::DanglingOutput {
+            station_index,
+            station: configs[station_index].name.clone(),
+            reason: format!(
+                "la salida {} (hacia la {}) no recibe productos: ninguna regla la elige",
+                output + 1,
+                splitter.outputs[output]
+            ),
+        });
+    }
+    for (station_index, config) in configs.iter().enumerate() {
+        let StationKind::Merger(merger) = &config.kind else {
+            continue;
+        };
+        let listed = match &merger.policy {
+            MergePolicy::Alternating => Vec::new(),
+            MergePolicy::Weighted(weights) => weights.iter().map(|(source, _)| *source).collect(),
+            MergePolicy::Priority(order) => order.clone(),
+        };
+        let sources = topology.sources_of(station_index);
+        if let Some(source) = listed.into_iter().find(|source| !sources.contains(source)) {
+            let origin = source.map_or(TopologyNode::Entry, TopologyNode::Station);
+            return Err(ConfigError::InvalidMerger {
+                station_index,
+                station: config.name.clone(),
+                reason: format!("la política incluye la {}, que nunca le envía productos", origin),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::catalog::ProductType;
+    use crate::routing::{Inspection, Merger, Splitter};
+    use crate::scheduler::SchedulingAlgorithm;
+    use crate::station::StationConfig;
+
+    fn line(stations: Vec<StationConfig>) -> Simulation {
+        Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2])
+    }
+
+    fn station(name: &str) -> StationConfig {
+        StationConfig::new(name, Duration::from_millis(10))
+    }
+
+    #[test]
+    fn test_branches_and_merge_are_connected() {
+        let branches = vec![RouteTarget::Station(1), RouteTarget::Station(2)];
+        let simulation = line(vec![
+            station("Corte").with_splitter(Splitter::round_robin(branches)),
+            station("Pintura A").with_output(RouteTarget::Station(3)),
+            station("Pintura B"),
+            station("Empaque").with_merger(Merger::priority([Some(1), Some(2)])),
+        ]);
+        let topology = simulation.topology();
+        assert_eq!(topology.reachable, vec![true; 4]);
+        assert_eq!(topology.sources_of(3), vec![Some(1), Some(2)]);
+        assert_eq!(topology.sources_of(0), vec![None]);
+        assert!(topology.edges_from(TopologyNode::Station(3)).iter().all(|edge| edge.to == TopologyNode::Exit));
+        assert!(check(&simulation).is_ok());
+    }
+
+    #[test]
+    fn test_dot_escapes_labels() {
+        assert_eq!(dot_string("Pintura \"A\"\n10 ms"), "\"Pintura \\\"A\\\"\\n10 ms\"");
+        let dot = to_dot(&line(vec![station("Corte \\ Pulido")]), None);
+        assert!(dot.contains("estacion_0 [label=\"Corte \\\\ Pulido\\n"), "{}", dot);
+        assert!(dot.contains("    entrada -> estacion_0;\n    estacion_0 -> salida;\n}"), "{}", dot);
+        assert!(!dot.contains("rechazo"));
+    }
+
+    #[test]
+    fn test_detects_unreachable_stations_and_closed_loops() {
+        // Nada llega a Pintura B: Corte solo envía a Pintura A, que salta a Empaque
+        let simulation = line(vec![
+            station("Corte").with_output(RouteTarget::Station(1)),
+            station("Pintura A").with_output(RouteTarget::Station(3)),
+            station("Pintura B"),
+            station("Empaque"),
+        ]);
+        assert!(matches!(check(&simulation), Err(ConfigError::UnreachableStation { station_index: 2, .. })));
+
+        // Los aprobados vuelven a Ensamblaje y los demás se rechazan: nada termina la ruta
+        let simulation = line(vec![
+            station("Corte"),
+            station("Ensamblaje"),
+            StationConfig::inspection("Control", Inspection::pass_rate(0.5, 1).with_pass_target(RouteTarget::Station(1))),
+        ]);
+        let error = check(&simulation).unwrap_err();
+        assert_eq!(error, ConfigError::ClosedLoop {
+            stations: vec![1, 2],
+            names: vec!["Ensamblaje".to_string(), "Control".to_string()],
+        });
+
+        // Un retrabajo con salida es válido
+        let simulation = line(vec![
+            station("Corte"),
+            station("Ensamblaje"),
+            StationConfig::inspection("Control", Inspection::pass_rate(0.5, 1).with_fail_target(RouteTarget::Station(1))),
+        ]);
+        assert!(check(&simulation).is_ok());
+    }
+
+    #[test]
+    fn test_detects_dangling_outputs_and_unknown_references() {
+        let outputs = vec![RouteTarget::Next, RouteTarget::Station(2)];
+        let mut simulation = line(vec![
+            station("Corte").with_splitter(Splitter::by_type(outputs, [("sedan", 1)])),
+            station("Pintura"),
+            station("Empaque"),
+        ]);
+        assert!(matches!(check(&simulation), Err(ConfigError::DanglingOutput { station_index: 0, .. })));
+
+        // Con el tipo registrado, la regla elige la salida 2; sin productos de
+        // ese tipo asignados la salida sigue siendo posible
+        simulation.register_product_type(ProductType::new("sedan"));
+        assert!(check(&simulation).is_ok());
+
+        simulation.set_product_type(2, "camion");
+        assert_eq!(
+            check(&simulation),
+            Err(ConfigError::UnknownProductType { used_by: "el producto 2".to_string(), type_name: "camion".to_string() })
+        );
+
+        let simulation = line(vec![
+            station("Corte"),
+            station("Empaque").with_merger(Merger::priority([None])),
+        ]);
+        assert!(matches!(check(&simulation), Err(ConfigError::InvalidMerger { station_index: 1, .. })));
+    }
+}
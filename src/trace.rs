@@ -0,0 +1,481 @@
+//! # Módulo de Trazas de Eventos
+//! 
+//! Este módulo reconstruye la traza de eventos de una ejecución a partir de
+//! sus métricas: llegadas, entradas a cola, inicio y fin de cada porción,
+//! pérdidas y salidas de la línea, ordenadas por momento. En tiempo virtual
+//! la traza es exactamente reproducible, por lo que puede guardarse como un
+//! archivo de referencia ("golden") y compararse con la de una nueva
+//! ejecución para detectar con precisión cualquier cambio en las decisiones
+//! del planificador.
+//! 
+//! El archivo es de texto, con un evento por línea:
+//! 
+//! ```text
+//! # tiempo_ms producto estacion evento
+//! 0 1 - arrival
+//! 0 1 0 queued
+//! 0 1 0 start
+//! 10 1 0 preempted
+//! ```
+//! 
+//! Los tiempos están en milisegundos exactos (con hasta seis decimales), las
+//! estaciones son índices desde 0 (`-` en los eventos de la línea) y las
+//! líneas que comienzan con `#` son comentarios.
+//! 
+//! Con la feature `async`, [`Simulation::save_golden_trace`] y
+//! [`Simulation::check_golden_trace`] ejecutan la simulación en tiempo
+//! virtual y guardan o comparan su traza; en las pruebas,
+//! [`assert_golden_trace`](crate::testing::assert_golden_trace) hace ambas
+//! cosas.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::metrics::{MetricsCalculator, SimulationMetrics};
+use crate::product::{LossReason, TimelineEvent};
+#[cfg(feature = "async")]
+use crate::simulation::Simulation;
+
+/// Cantidad de eventos en común que se muestran antes de una divergencia.
+const DIVERGENCE_CONTEXT: usize = 3;
+
+/// Tipo de evento de una traza.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceEventKind {
+    /// El producto llega a la línea
+    Arrival,
+    /// El producto entra a la cola de la estación
+    Queued,
+    /// La estación inicia una porción de procesamiento del producto
+    SliceStarted,
+    /// La porción termina por el quantum y el producto vuelve a la cola
+    Preempted,
+    /// El producto completa la estación
+    StationCompleted,
+    /// El producto sale de la línea sin completarla
+    Lost(LossReason),
+    /// El producto completa la línea
+    Exited,
+}
+
+impl TraceEventKind {
+    /// Identificador estable del evento en los archivos de traza.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Arrival => "arrival",
+            Self::Queued => "queued",
+            Self::SliceStarted => "start",
+            Self::Preempted => "preempted",
+            Self::StationCompleted => "completed",
+            Self::Lost(reason) => reason.key(),
+            Self::Exited => "exit",
+        }
+    }
+
+    /// Interpreta el identificador de un evento.
+    fn from_key(key: &str) -> Option<Self> {
+        let kind = match key {
+            "arrival" => Self::Arrival,
+            "queued" => Self::Queued,
+            "start" => Self::SliceStarted,
+            "preempted" => Self::Preempted,
+            "completed" => Self::StationCompleted,
+            "exit" => Self::Exited,
+            _ => [LossReason::Balked, LossReason::Reneged, LossReason::Rejected]
+                .into_iter()
+                .find(|reason| reason.key() == key)
+                .map(Self::Lost)?,
+        };
+        Some(kind)
+    }
+}
+
+impl fmt::Display for TraceEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Arrival => write!(f, "llega a la línea"),
+            Self::Queued => write!(f, "entra a la cola"),
+            Self::SliceStarted => write!(f, "inicia una porción"),
+            Self::Preempted => write!(f, "es interrumpido"),
+            Self::StationCompleted => write!(f, "completa la estación"),
+            Self::Lost(reason) => write!(f, "{}", reason),
+            Self::Exited => write!(f, "sale de la línea"),
+        }
+    }
+}
+
+/// Evento de una traza.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TraceEvent {
+    /// Momento del evento, relativo al inicio de la simulación
+    pub at: Duration,
+    /// ID del producto
+    pub product: usize,
+    /// Índice de la estación, o `None` en los eventos de la línea
+    pub station: Option<usize>,
+    /// Tipo de evento
+    pub kind: TraceEventKind,
+}
+
+impl TraceEvent {
+    /// Formatea el evento como una línea del archivo de traza.
+    pub fn to_line(&self) -> String {
+        let station = self.station.map_or_else(|| "-".to_string(), |station| station.to_string());
+        format!("{} {} {} {}", exact_millis(self.at), self.product, station, self.kind.key())
+    }
+
+    /// Interpreta una línea del archivo de traza.
+    fn parse_line(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [at, product, station, kind] = fields[..] else {
+            return Err(format!("se esperaban 4 campos, hay {}", fields.len()));
+        };
+        Ok(Self {
+            at: parse_millis(at).ok_or_else(|| format!("tiempo inválido '{}'", at))?,
+            product: product.parse().map_err(|_| format!("producto inválido '{}'", product))?,
+            station: match station {
+                "-" => None,
+                station => Some(station.parse().map_err(|_| format!("estación inválida '{}'", station))?),
+            },
+            kind: TraceEventKind::from_key(kind).ok_or_else(|| format!("evento desconocido '{}'", kind))?,
+        })
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "t={} producto {:02}", MetricsCalculator::format_duration(self.at), self.product)?;
+        if let Some(station) = self.station {
+            write!(f, " en la estación {}", station + 1)?;
+        }
+        write!(f, " {}", self.kind)
+    }
+}
+
+/// Traza de eventos de una ejecución, ordenada por momento.
+/// 
+/// Los eventos simultáneos se ordenan por ID de producto y, para un mismo
+/// producto, en el orden en que ocurrieron.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{EventTrace, SchedulingAlgorithm, Simulation, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+/// let algorithm = SchedulingAlgorithm::round_robin(Duration::from_millis(10));
+/// let mut simulation = Simulation::with_config(stations, algorithm, vec![Duration::ZERO]);
+/// let metrics = simulation.run().unwrap();
+/// 
+/// let trace = EventTrace::from_metrics(&metrics);
+/// let kinds: Vec<&str> = trace.events.iter().map(|event| event.kind.key()).collect();
+/// assert_eq!(kinds, ["arrival", "queued", "start", "preempted", "queued", "start", "completed", "exit"]);
+/// assert_eq!(EventTrace::parse(&trace.to_text()), Ok(trace));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventTrace {
+    /// Eventos de la ejecución
+    pub events: Vec<TraceEvent>,
+}
+
+impl EventTrace {
+    /// Reconstruye la traza a partir de las métricas de una ejecución.
+    /// 
+    /// Incluye a los productos completados, a los perdidos y a los que
+    /// quedaron en la línea al finalizar la simulación.
+    pub fn from_metrics(metrics: &SimulationMetrics) -> Self {
+        let mut products: Vec<_> = metrics.products
+            .iter()
+            .map(|product| (product, true))
+            .chain(metrics.lost_products.iter().map(|product| (product, false)))
+            .chain(metrics.incomplete_products.iter().map(|product| (product, false)))
+            .collect();
+        products.sort_by_key(|(product, _)| product.product_id);
+
+        let mut events = Vec::new();
+        for (product, completed) in products {
+            let event = |at, station, kind| TraceEvent { at, product: product.product_id, station, kind };
+            events.push(event(product.arrival_time, None, TraceEventKind::Arrival));
+            for span in &product.timeline {
+                let station = Some(span.station);
+                match span.event {
+                    TimelineEvent::Queued => events.push(event(span.start, station, TraceEventKind::Queued)),
+                    TimelineEvent::Processing | TimelineEvent::Preempted => {
+                        let end = match span.event {
+                            TimelineEvent::Processing => TraceEventKind::StationCompleted,
+                            _ => TraceEventKind::Preempted,
+                        };
+                        events.push(event(span.start, station, TraceEventKind::SliceStarted));
+                        events.push(event(span.end, station, end));
+                    }
+                    TimelineEvent::Transfer => {}
+                }
+            }
+            if let Some(loss) = product.loss {
+                events.push(event(loss.at, Some(loss.station), TraceEventKind::Lost(loss.reason)));
+            }
+            if completed {
+                events.push(event(product.arrival_time + product.turnaround_time, None, TraceEventKind::Exited));
+            }
+        }
+        // El ordenamiento estable conserva el orden por producto y de cada producto
+        events.sort_by_key(|event| event.at);
+        Self { events }
+    }
+
+    /// Exporta la traza en el formato de los archivos de traza.
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# tiempo_ms producto estacion evento\n");
+        for event in &self.events {
+            text.push_str(&event.to_line());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Lee una traza en el formato de los archivos de traza.
+    /// 
+    /// # Returns
+    /// 
+    /// La traza, o `Err` con el número de la primera línea inválida
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let events = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(index, line)| TraceEvent::parse_line(line).map_err(|error| format!("línea {}: {}", index + 1, error)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { events })
+    }
+
+    /// Guarda la traza en un archivo.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        fs::write(path, self.to_text()).map_err(|error| format!("No se pudo escribir '{}': {}", path.display(), error))
+    }
+
+    /// Lee una traza desde un archivo.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("No se pudo leer '{}': {}", path.display(), error))?;
+        Self::parse(&text).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    /// Compara esta traza (la de referencia) con la de otra ejecución.
+    /// 
+    /// # Returns
+    /// 
+    /// La primera divergencia entre ambas, o `None` si son idénticas
+    pub fn diff(&self, actual: &EventTrace) -> Option<TraceDivergence> {
+        let index = self.events
+            .iter()
+            .zip(&actual.events)
+            .position(|(expected, actual)| expected != actual)
+            .or_else(|| (self.events.len() != actual.events.len()).then(|| self.events.len().min(actual.events.len())))?;
+
+        let mut products: Vec<usize> = self.events
+            .iter()
+            .chain(&actual.events)
+            .map(|event| event.product)
+            .collect();
+        products.sort_unstable();
+        products.dedup();
+        products.retain(|&product| {
+            let of = |trace: &EventTrace| -> Vec<TraceEvent> {
+                trace.events.iter().filter(|event| event.product == product).copied().collect()
+            };
+            of(self) != of(actual)
+        });
+
+        Some(TraceDivergence {
+            index,
+            expected: self.events.get(index).copied(),
+            actual: actual.events.get(index).copied(),
+            context: self.events[index.saturating_sub(DIVERGENCE_CONTEXT)..index].to_vec(),
+            expected_events: self.events.len(),
+            actual_events: actual.events.len(),
+            products,
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl Simulation {
+    /// Ejecuta la simulación en tiempo virtual y guarda su traza de eventos
+    /// como archivo de referencia.
+    /// 
+    /// # Returns
+    /// 
+    /// La traza guardada, o `Err` si la simulación falla o el archivo no
+    /// puede escribirse
+    pub fn save_golden_trace(&self, path: impl AsRef<Path>) -> Result<EventTrace, String> {
+        let trace = self.virtual_trace()?;
+        trace.save(path)?;
+        Ok(trace)
+    }
+
+    /// Vuelve a ejecutar la simulación en tiempo virtual y compara su traza
+    /// de eventos con la de un archivo de referencia.
+    /// 
+    /// # Returns
+    /// 
+    /// La primera divergencia (`None` si las trazas son idénticas), o `Err`
+    /// si el archivo no puede leerse o la simulación falla
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use assembly_line_simulator::{SchedulingAlgorithm, Simulation, StationConfig};
+    /// 
+    /// let millis = Duration::from_millis;
+    /// let path = std::env::temp_dir().join(format!("traza-doc-{}.trace", std::process::id()));
+    /// let stations = vec![StationConfig::new("Corte", millis(30))];
+    /// let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![millis(0), millis(10)]);
+    /// simulation.save_golden_trace(&path).unwrap();
+    /// assert_eq!(simulation.check_golden_trace(&path), Ok(None));
+    /// 
+    /// // Con Round Robin el producto 1 ya no completa la estación de una vez
+    /// let changed = simulation.clone_with(|simulation| {
+    ///     simulation.set_algorithm(SchedulingAlgorithm::round_robin(millis(20)));
+    /// });
+    /// let divergence = changed.check_golden_trace(&path).unwrap().unwrap();
+    /// assert_eq!(divergence.products, vec![1, 2]);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn check_golden_trace(&self, path: impl AsRef<Path>) -> Result<Option<TraceDivergence>, String> {
+        let golden = EventTrace::load(path)?;
+        Ok(golden.diff(&self.virtual_trace()?))
+    }
+
+    /// Ejecuta la simulación en tiempo virtual y reconstruye su traza.
+    fn virtual_trace(&self) -> Result<EventTrace, String> {
+        let metrics = self.run_virtual().map_err(|error| error.to_string())?;
+        Ok(EventTrace::from_metrics(&metrics))
+    }
+}
+
+/// Primera diferencia entre una traza de referencia y la de una nueva
+/// ejecución.
+/// 
+/// Se muestra como un reporte legible con los últimos eventos en común, el
+/// evento esperado y el obtenido, y los productos cuyo recorrido cambió.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceDivergence {
+    /// Posición del primer evento distinto (desde 0)
+    pub index: usize,
+    /// Evento de la traza de referencia, o `None` si la nueva tiene eventos
+    /// adicionales
+    pub expected: Option<TraceEvent>,
+    /// Evento de la nueva traza, o `None` si termina antes que la de referencia
+    pub actual: Option<TraceEvent>,
+    /// Últimos eventos en común antes de la divergencia
+    pub context: Vec<TraceEvent>,
+    /// Eventos de la traza de referencia
+    pub expected_events: usize,
+    /// Eventos de la nueva traza
+    pub actual_events: usize,
+    /// IDs de los productos cuyos eventos difieren entre ambas trazas
+    pub products: Vec<usize>,
+}
+
+impl fmt::Display for TraceDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "La traza diverge en el evento {}:", self.index + 1)?;
+        for event in &self.context {
+            writeln!(f, "    {}", event)?;
+        }
+        match self.expected {
+            Some(event) => writeln!(f, "  - esperado: {}", event)?,
+            None => writeln!(f, "  - esperado: fin de la traza")?,
+        }
+        match self.actual {
+            Some(event) => writeln!(f, "  + obtenido: {}", event)?,
+            None => writeln!(f, "  + obtenido: fin de la traza")?,
+        }
+        writeln!(f, "Eventos: {} esperados, {} obtenidos", self.expected_events, self.actual_events)?;
+        let products: Vec<String> = self.products.iter().map(usize::to_string).collect();
+        write!(f, "Productos con eventos distintos: {}", products.join(", "))
+    }
+}
+
+/// Formatea una duración en milisegundos exactos, sin ceros decimales de más.
+fn exact_millis(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    let (millis, fraction) = (nanos / 1_000_000, nanos % 1_000_000);
+    if fraction == 0 {
+        millis.to_string()
+    } else {
+        format!("{}.{:06}", millis, fraction).trim_end_matches('0').to_string()
+    }
+}
+
+/// Interpreta milisegundos con hasta seis decimales.
+fn parse_millis(text: &str) -> Option<Duration> {
+    let (millis, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if fraction.len() > 6 || !fraction.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let millis: u64 = millis.parse().ok()?;
+    let nanos: u64 = format!("{:0<6}", fraction).parse().ok()?;
+    Some(Duration::from_millis(millis) + Duration::from_nanos(nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(at_millis: u64, product: usize, station: Option<usize>, kind: TraceEventKind) -> TraceEvent {
+        TraceEvent { at: Duration::from_millis(at_millis), product, station, kind }
+    }
+
+    #[test]
+    fn test_times_round_trip_exactly() {
+        for nanos in [0, 1, 1_500_000, 12_000_000, 7_000_250] {
+            let duration = Duration::from_nanos(nanos);
+            assert_eq!(parse_millis(&exact_millis(duration)), Some(duration));
+        }
+        assert_eq!(exact_millis(Duration::from_micros(12_500)), "12.5");
+        assert_eq!(parse_millis("1.2345678"), None);
+        assert_eq!(
+            EventTrace::parse("# comentario\n0 1 - arrival\n5 1 0 bogus\n"),
+            Err("línea 3: evento desconocido 'bogus'".to_string())
+        );
+        let lost = event(5, 2, Some(1), TraceEventKind::Lost(LossReason::Reneged));
+        assert_eq!(TraceEvent::parse_line(&lost.to_line()), Ok(lost));
+    }
+
+    #[test]
+    fn test_diff_reports_first_divergence_and_changed_products() {
+        let expected = EventTrace {
+            events: vec![
+                event(0, 1, None, TraceEventKind::Arrival),
+                event(0, 2, None, TraceEventKind::Arrival),
+                event(0, 1, Some(0), TraceEventKind::SliceStarted),
+                event(10, 1, Some(0), TraceEventKind::StationCompleted),
+                event(10, 2, Some(0), TraceEventKind::SliceStarted),
+            ],
+        };
+        assert_eq!(expected.diff(&expected.clone()), None);
+
+        let mut actual = expected.clone();
+        actual.events[2].product = 2;
+        actual.events[4].product = 1;
+        let divergence = expected.diff(&actual).unwrap();
+        assert_eq!(divergence.index, 2);
+        assert_eq!(divergence.products, vec![1, 2]);
+        let report = divergence.to_string();
+        assert!(report.starts_with("La traza diverge en el evento 3:\n    t=0.000s producto 01 llega a la línea\n"), "{}", report);
+        assert!(report.contains("  - esperado: t=0.000s producto 01 en la estación 1 inicia una porción\n"), "{}", report);
+        assert!(report.contains("  + obtenido: t=0.000s producto 02 en la estación 1 inicia una porción\n"), "{}", report);
+
+        let truncated = EventTrace { events: expected.events[..3].to_vec() };
+        let divergence = expected.diff(&truncated).unwrap();
+        assert_eq!((divergence.index, divergence.actual), (3, None));
+        assert!(divergence.to_string().contains("  + obtenido: fin de la traza\nEventos: 5 esperados, 3 obtenidos"));
+    }
+}
@@ -0,0 +1,144 @@
+//! # Módulo de Trayectorias
+//! 
+//! Este módulo muestrea periódicamente la posición de cada producto en
+//! curso: en qué estación está, si espera o se procesa y cuánto tiempo lleva
+//! en el sistema. El resultado es un conjunto de trayectorias ("spaghetti")
+//! que permite visualizar cómo Round Robin intercala los productos entre
+//! porciones mientras FCFS los atiende en lotes. Las muestras se reconstruyen
+//! a partir de la línea de tiempo de cada producto, por lo que no dependen
+//! del backend con el que se ejecutó la simulación.
+
+use std::time::Duration;
+
+use crate::clock::ClockFormat;
+use crate::metrics::SimulationMetrics;
+use crate::product::TimelineEvent;
+
+/// Posición de un producto en curso en un instante de muestreo.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrajectorySample {
+    /// Instante de la muestra, relativo al inicio de la simulación
+    pub time: Duration,
+    /// ID del producto
+    pub product_id: usize,
+    /// Tiempo que el producto lleva en el sistema
+    pub time_in_system: Duration,
+    /// Índice de la estación en la que se encuentra (0-indexado); en un
+    /// traslado, la estación de destino
+    pub station: usize,
+    /// Qué hace el producto en ese instante
+    pub state: TimelineEvent,
+}
+
+/// Muestras periódicas de las trayectorias de los productos en la línea.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{Simulation, SchedulingAlgorithm, StationConfig, Trajectories};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(30))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+/// let metrics = simulation.run().unwrap();
+/// 
+/// let trajectories = Trajectories::sample(&metrics, Duration::from_millis(10));
+/// // A los 10ms el primero se procesa y los otros dos esperan
+/// assert_eq!(trajectories.at(Duration::from_millis(10)).count(), 3);
+/// assert!(trajectories.product(3).count() > trajectories.product(1).count());
+/// assert!(trajectories.to_csv().starts_with("Time,ProductID,TimeInSystem,Station,State\n"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trajectories {
+    /// Intervalo entre muestras
+    pub interval: Duration,
+    /// Muestras ordenadas por instante y, dentro de cada instante, por producto
+    pub samples: Vec<TrajectorySample>,
+}
+
+impl Trajectories {
+    /// Muestrea la posición de los productos en curso cada `interval`.
+    /// 
+    /// Se incluyen los productos completados, los incompletos y los
+    /// perdidos mientras estuvieron en la línea: desde su llegada hasta su
+    /// salida (excluida).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `metrics` - Métricas de la simulación
+    /// * `interval` - Intervalo entre muestras, desde el instante cero hasta
+    ///   el fin de la simulación
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si el intervalo es cero
+    pub fn sample(metrics: &SimulationMetrics, interval: Duration) -> Self {
+        assert!(!interval.is_zero(), "El intervalo de muestreo debe ser mayor que cero");
+        let mut products: Vec<_> = metrics.products
+            .iter()
+            .chain(&metrics.incomplete_products)
+            .chain(&metrics.lost_products)
+            .collect();
+        products.sort_by_key(|product| product.product_id);
+
+        let instants = std::iter::successors(Some(Duration::ZERO), |time| Some(*time + interval))
+            .take_while(|time| *time <= metrics.total_simulation_time);
+        let mut samples = Vec::new();
+        for time in instants {
+            for product in &products {
+                let departure = product.arrival_time + product.turnaround_time;
+                if time < product.arrival_time || time >= departure {
+                    continue;
+                }
+                // El intervalo que contiene la muestra indica dónde está; si
+                // no hay uno, el último que comenzó antes de ella
+                let span = product.timeline
+                    .iter()
+                    .find(|span| span.start <= time && time < span.end)
+                    .or_else(|| product.timeline.iter().rev().find(|span| span.start <= time));
+                let Some(span) = span else {
+                    continue;
+                };
+                samples.push(TrajectorySample {
+                    time,
+                    product_id: product.product_id,
+                    time_in_system: time - product.arrival_time,
+                    station: span.station,
+                    state: span.event,
+                });
+            }
+        }
+        Self { interval, samples }
+    }
+
+    /// Muestras de un producto, en orden de tiempo.
+    pub fn product(&self, product_id: usize) -> impl Iterator<Item = &TrajectorySample> {
+        self.samples.iter().filter(move |sample| sample.product_id == product_id)
+    }
+
+    /// Muestras tomadas en un instante, en orden de producto.
+    pub fn at(&self, time: Duration) -> impl Iterator<Item = &TrajectorySample> {
+        self.samples.iter().filter(move |sample| sample.time == time)
+    }
+
+    /// Exporta las muestras en formato CSV, una fila por muestra.
+    /// 
+    /// Los tiempos usan el formato de reloj vigente
+    /// ([`ClockFormat::current`]) sin unidad; las estaciones se numeran
+    /// desde 1 como en el reporte CSV de métricas.
+    pub fn to_csv(&self) -> String {
+        let time = |duration: Duration| ClockFormat::current().format_value(duration);
+        let mut csv = String::from("Time,ProductID,TimeInSystem,Station,State\n");
+        for sample in &self.samples {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                time(sample.time),
+                sample.product_id,
+                time(sample.time_in_system),
+                sample.station + 1,
+                sample.state.key()
+            ));
+        }
+        csv
+    }
+}
@@ -0,0 +1,241 @@
+//! # Módulo de Ajuste del Quantum
+//! 
+//! Este módulo busca el quantum de Round Robin que minimiza la espera o el
+//! turnaround promedio de un escenario. Cada candidato se evalúa con una
+//! ejecución en tiempo virtual ([`Simulation::run_virtual`]), por lo que la
+//! búsqueda es rápida y reproducible: dos búsquedas sobre el mismo
+//! escenario recomiendan el mismo quantum.
+//! 
+//! Hay dos estrategias: una grilla que evalúa quanta equiespaciados y
+//! devuelve la curva completa, y la búsqueda por sección áurea, que
+//! requiere menos ejecuciones pero supone que la métrica tiene un único
+//! mínimo en el intervalo.
+
+use std::time::Duration;
+
+use crate::error::SimulationError;
+use crate::metrics::SimulationMetrics;
+use crate::scheduler::SchedulingAlgorithm;
+use crate::simulation::Simulation;
+
+/// Métrica que el optimizador minimiza.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuantumObjective {
+    /// Tiempo promedio de espera
+    #[default]
+    AverageWait,
+    /// Tiempo promedio de turnaround
+    AverageTurnaround,
+}
+
+impl QuantumObjective {
+    /// Obtiene el valor de la métrica en los resultados de una simulación.
+    pub fn value(&self, metrics: &SimulationMetrics) -> Duration {
+        match self {
+            Self::AverageWait => metrics.average_wait_time,
+            Self::AverageTurnaround => metrics.average_turnaround_time,
+        }
+    }
+}
+
+/// Estrategia de búsqueda del quantum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuantumSearch {
+    /// Evalúa todos los quanta desde `min` hasta `max` con paso `step`
+    Grid {
+        /// Quantum mínimo evaluado
+        min: Duration,
+        /// Quantum máximo evaluado
+        max: Duration,
+        /// Separación entre quanta consecutivos
+        step: Duration,
+    },
+    /// Reduce el intervalo `[min, max]` por sección áurea hasta que mide
+    /// menos que `tolerance`
+    GoldenSection {
+        /// Extremo inferior del intervalo
+        min: Duration,
+        /// Extremo superior del intervalo
+        max: Duration,
+        /// Ancho del intervalo en el que se detiene la búsqueda
+        tolerance: Duration,
+    },
+}
+
+/// Quantum recomendado y la curva de la métrica evaluada.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantumRecommendation {
+    /// Quantum con el menor valor de la métrica
+    pub quantum: Duration,
+    /// Valor de la métrica con el quantum recomendado
+    pub value: Duration,
+    /// Pares (quantum, métrica) evaluados, ordenados por quantum
+    pub curve: Vec<(Duration, Duration)>,
+}
+
+/// Optimizador del quantum de Round Robin.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::{QuantumObjective, QuantumOptimizer, Simulation, SchedulingAlgorithm, StationConfig};
+/// 
+/// let stations = vec![StationConfig::new("Corte", Duration::from_millis(40))];
+/// let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+/// let recommendation = QuantumOptimizer::grid(Duration::from_millis(10), Duration::from_millis(40), Duration::from_millis(10))
+///     .minimizing(QuantumObjective::AverageTurnaround)
+///     .optimize(&mut simulation)
+///     .expect("La búsqueda falló");
+/// // Con trabajos iguales, un quantum que los cubre completos equivale a FCFS
+/// assert_eq!(recommendation.quantum, Duration::from_millis(40));
+/// assert_eq!(recommendation.curve.len(), 4);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuantumOptimizer {
+    /// Estrategia de búsqueda
+    pub search: QuantumSearch,
+    /// Métrica a minimizar
+    pub objective: QuantumObjective,
+}
+
+impl QuantumOptimizer {
+    /// Crea un optimizador que evalúa una grilla de quanta.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `min` o `step` son cero, o si `max` es menor que `min`
+    pub fn grid(min: Duration, max: Duration, step: Duration) -> Self {
+        assert!(!min.is_zero() && !step.is_zero(), "El quantum mínimo y el paso deben ser mayores que cero");
+        assert!(min <= max, "El quantum máximo no puede ser menor que el mínimo");
+        Self {
+            search: QuantumSearch::Grid { min, max, step },
+            objective: QuantumObjective::default(),
+        }
+    }
+
+    /// Crea un optimizador por sección áurea sobre `[min, max]`.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si `min` o `tolerance` son cero, o si `max` es menor que `min`
+    pub fn golden_section(min: Duration, max: Duration, tolerance: Duration) -> Self {
+        assert!(!min.is_zero() && !tolerance.is_zero(), "El quantum mínimo y la tolerancia deben ser mayores que cero");
+        assert!(min <= max, "El quantum máximo no puede ser menor que el mínimo");
+        Self {
+            search: QuantumSearch::GoldenSection { min, max, tolerance },
+            objective: QuantumObjective::default(),
+        }
+    }
+
+    /// Indica la métrica a minimizar (por defecto, la espera promedio).
+    pub fn minimizing(mut self, objective: QuantumObjective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Busca el quantum recomendado para el escenario de la simulación.
+    /// 
+    /// Cada candidato ejecuta la simulación en tiempo virtual con Round
+    /// Robin como algoritmo general; los algoritmos propios de cada
+    /// estación se conservan. Al terminar, la simulación recupera su
+    /// algoritmo original. Entre quanta con el mismo valor se recomienda
+    /// el menor.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `simulation` - Escenario a optimizar
+    /// 
+    /// # Returns
+    /// 
+    /// La recomendación con la curva evaluada, o el error de la primera
+    /// ejecución que falló
+    pub fn optimize(&self, simulation: &mut Simulation) -> Result<QuantumRecommendation, SimulationError> {
+        let original = simulation.algorithm.clone();
+        let mut curve = Vec::new();
+        let result = self.search(simulation, &mut curve);
+        simulation.algorithm = original;
+        result?;
+
+        curve.sort();
+        let &(quantum, value) = curve
+            .iter()
+            .min_by_key(|&&(_, value)| value)
+            .expect("La búsqueda evalúa al menos un quantum");
+        Ok(QuantumRecommendation { quantum, value, curve })
+    }
+
+    /// Recorre los candidatos según la estrategia y registra cada evaluación.
+    fn search(&self, simulation: &mut Simulation, curve: &mut Vec<(Duration, Duration)>) -> Result<(), SimulationError> {
+        match self.search {
+            QuantumSearch::Grid { min, max, step } => {
+                let mut quantum = min;
+                while quantum <= max {
+                    self.evaluate(simulation, quantum, curve)?;
+                    quantum += step;
+                }
+            }
+            QuantumSearch::GoldenSection { min, max, tolerance } => {
+                let ratio = (5f64.sqrt() - 1.0) / 2.0;
+                let (mut low, mut high) = (min.as_secs_f64(), max.as_secs_f64());
+                let point = |low: f64, high: f64, fraction: f64| Duration::from_secs_f64(low + (high - low) * fraction);
+                while high - low > tolerance.as_secs_f64() {
+                    let left = point(low, high, 1.0 - ratio);
+                    let right = point(low, high, ratio);
+                    if self.evaluate(simulation, left, curve)? <= self.evaluate(simulation, right, curve)? {
+                        high = right.as_secs_f64();
+                    } else {
+                        low = left.as_secs_f64();
+                    }
+                }
+                self.evaluate(simulation, point(low, high, 0.5), curve)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Evalúa un quantum, reutilizando el resultado si ya se evaluó.
+    fn evaluate(
+        &self,
+        simulation: &mut Simulation,
+        quantum: Duration,
+        curve: &mut Vec<(Duration, Duration)>,
+    ) -> Result<Duration, SimulationError> {
+        if let Some(&(_, value)) = curve.iter().find(|(evaluated, _)| *evaluated == quantum) {
+            return Ok(value);
+        }
+        simulation.algorithm = SchedulingAlgorithm::round_robin(quantum);
+        let value = self.objective.value(&simulation.run_virtual()?);
+        curve.push((quantum, value));
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::station::StationConfig;
+
+    #[test]
+    fn test_golden_section_matches_grid_on_unimodal_curve() {
+        let millis = Duration::from_millis;
+        let stations = vec![StationConfig::new("Corte", millis(60))];
+        let arrivals = vec![millis(0), millis(0), millis(0)];
+        let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+
+        // Entre 30 y 55 ms cada producto necesita dos porciones: la espera
+        // promedio crece con el quantum, así que el mínimo está en 30 ms
+        let grid = QuantumOptimizer::grid(millis(30), millis(55), millis(1))
+            .optimize(&mut simulation)
+            .unwrap();
+        let golden = QuantumOptimizer::golden_section(millis(30), millis(55), millis(1))
+            .optimize(&mut simulation)
+            .unwrap();
+
+        assert_eq!(grid.quantum, millis(30));
+        assert!(golden.quantum <= millis(31), "Recomendado: {:?}", golden.quantum);
+        assert!(golden.value <= grid.value + millis(1));
+        assert!(golden.curve.len() < grid.curve.len());
+        assert_eq!(simulation.algorithm, SchedulingAlgorithm::fcfs());
+    }
+}
@@ -0,0 +1,290 @@
+//! # Módulo de Modelos de Trabajo
+//! 
+//! Este módulo define cómo una estación "realiza" el trabajo de una porción
+//! de procesamiento. Por defecto las estaciones duermen el hilo durante la
+//! porción, pero es posible consumir CPU real para observar efectos de
+//! planificación del sistema operativo, o no hacer nada para ejecutar
+//! simulaciones casi instantáneas en pruebas.
+
+use std::fmt::{self, Debug};
+use std::hint::black_box;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Duración mínima de cada ronda de calibración de [`CpuBurn`].
+const CALIBRATION_ROUND: Duration = Duration::from_millis(10);
+
+/// Rondas de calibración de [`CpuBurn`]; se conserva la más rápida.
+const CALIBRATION_ROUNDS: usize = 3;
+
+/// Forma en que una estación ejecuta una porción de procesamiento.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::WorkModel;
+/// 
+/// /// Modelo que trabaja la mitad del tiempo nominal
+/// #[derive(Debug)]
+/// struct HalfSleep;
+/// 
+/// impl WorkModel for HalfSleep {
+///     fn perform(&self, slice: Duration) {
+///         std::thread::sleep(slice / 2);
+///     }
+/// }
+/// ```
+pub trait WorkModel: Debug + Send + Sync {
+    /// Ejecuta el trabajo correspondiente a una porción de procesamiento.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `slice` - Duración nominal de la porción
+    fn perform(&self, slice: Duration);
+}
+
+/// Modelo por defecto: duerme el hilo durante la porción.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SleepWork;
+
+impl WorkModel for SleepWork {
+    fn perform(&self, slice: Duration) {
+        thread::sleep(slice);
+    }
+}
+
+/// Modelo que consume CPU en espera activa durante la porción.
+/// 
+/// Útil para demostrar efectos reales de planificación cuando hay más
+/// estaciones que núcleos disponibles.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BusyWait;
+
+impl WorkModel for BusyWait {
+    fn perform(&self, slice: Duration) {
+        let start = Instant::now();
+        let mut accumulator = 0u64;
+        while start.elapsed() < slice {
+            accumulator = black_box(accumulator.wrapping_mul(31).wrapping_add(7));
+        }
+        black_box(accumulator);
+    }
+}
+
+/// Modelo que quema CPU: cada porción ejecuta una cantidad fija de
+/// iteraciones sobre un contador volátil, proporcional a su duración.
+/// 
+/// A diferencia de [`BusyWait`], que gira hasta que pasa el tiempo de reloj
+/// y por lo tanto termina a tiempo aunque el hilo casi no haya corrido, el
+/// trabajo de cada porción es fijo: si hay más hilos de estación ocupados
+/// que núcleos, el sistema operativo los reparte y las porciones tardan más
+/// de lo solicitado. El perfil de ejecución del reporte muestra esa
+/// interferencia como duración solicitada frente a medida.
+/// 
+/// La tasa (iteraciones por milisegundo) se calibra girando el mismo
+/// contador; conviene calibrar con la máquina ociosa, antes de la
+/// simulación, o fijarla con [`CpuBurn::with_rate`] para comparar máquinas.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use std::time::Duration;
+/// use assembly_line_simulator::CpuBurn;
+/// 
+/// let burn = CpuBurn::with_rate(50_000);
+/// assert_eq!(burn.iterations_for(Duration::from_millis(20)), 1_000_000);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CpuBurn {
+    /// Iteraciones que equivalen a un milisegundo de CPU
+    iterations_per_ms: u64,
+}
+
+impl CpuBurn {
+    /// Crea el modelo con una tasa fija.
+    /// 
+    /// # Panics
+    /// 
+    /// Hace panic si la tasa es cero
+    pub fn with_rate(iterations_per_ms: u64) -> Self {
+        assert!(iterations_per_ms > 0, "La tasa de iteraciones debe ser mayor que cero");
+        Self { iterations_per_ms }
+    }
+
+    /// Crea el modelo con la tasa medida en esta máquina.
+    /// 
+    /// La calibración se hace una sola vez por proceso (toma unas decenas
+    /// de milisegundos) y las llamadas siguientes reutilizan la tasa.
+    pub fn calibrated() -> Self {
+        static RATE: OnceLock<u64> = OnceLock::new();
+        Self::with_rate(*RATE.get_or_init(calibrate))
+    }
+
+    /// Iteraciones que equivalen a un milisegundo de CPU.
+    pub fn iterations_per_ms(&self) -> u64 {
+        self.iterations_per_ms
+    }
+
+    /// Iteraciones que ejecuta una porción de la duración indicada.
+    pub fn iterations_for(&self, slice: Duration) -> u64 {
+        (slice.as_secs_f64() * 1000.0 * self.iterations_per_ms as f64).round() as u64
+    }
+}
+
+impl WorkModel for CpuBurn {
+    fn perform(&self, slice: Duration) {
+        spin(self.iterations_for(slice));
+    }
+}
+
+/// Incrementa un contador volátil la cantidad de veces indicada.
+fn spin(iterations: u64) {
+    let mut counter = 0u64;
+    for _ in 0..iterations {
+        // SAFETY: el puntero apunta a una variable local viva y alineada
+        unsafe {
+            std::ptr::write_volatile(&mut counter, std::ptr::read_volatile(&counter).wrapping_add(1));
+        }
+    }
+}
+
+/// Mide cuántas iteraciones de [`spin`] caben en un milisegundo.
+fn calibrate() -> u64 {
+    (0..CALIBRATION_ROUNDS)
+        .map(|_| {
+            let mut iterations = 10_000u64;
+            loop {
+                let start = Instant::now();
+                spin(iterations);
+                let elapsed = start.elapsed();
+                if elapsed >= CALIBRATION_ROUND {
+                    return (iterations as f64 / (elapsed.as_secs_f64() * 1000.0)) as u64;
+                }
+                iterations *= 2;
+            }
+        })
+        .max()
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Modelo que no realiza trabajo: las porciones terminan de inmediato.
+/// 
+/// Los tiempos de llegada se siguen respetando en tiempo real, pero el
+/// procesamiento no consume tiempo, por lo que las métricas de las
+/// estaciones reflejan solo el costo del propio motor de simulación.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoWork;
+
+impl WorkModel for NoWork {
+    fn perform(&self, _slice: Duration) {}
+}
+
+/// Modelo de trabajo elegido por nombre, por ejemplo desde la línea de
+/// comandos.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkMode {
+    /// Duerme el hilo ([`SleepWork`])
+    Sleep,
+    /// Espera activa hasta que pasa el tiempo de reloj ([`BusyWait`])
+    BusyWait,
+    /// Quema una cantidad fija de CPU por porción ([`CpuBurn`])
+    CpuBurn(CpuBurn),
+    /// No realiza trabajo ([`NoWork`])
+    NoWork,
+}
+
+impl WorkModel for WorkMode {
+    fn perform(&self, slice: Duration) {
+        match self {
+            Self::Sleep => SleepWork.perform(slice),
+            Self::BusyWait => BusyWait.perform(slice),
+            Self::CpuBurn(burn) => burn.perform(slice),
+            Self::NoWork => NoWork.perform(slice),
+        }
+    }
+}
+
+impl fmt::Display for WorkMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sleep => write!(f, "dormir el hilo"),
+            Self::BusyWait => write!(f, "espera activa por tiempo de reloj"),
+            Self::CpuBurn(burn) => write!(f, "quemar CPU ({} iteraciones/ms)", burn.iterations_per_ms()),
+            Self::NoWork => write!(f, "sin trabajo"),
+        }
+    }
+}
+
+impl FromStr for WorkMode {
+    type Err = String;
+
+    /// Interpreta `dormir`, `espera`, `cpu`, `cpu:<iteraciones por ms>` o
+    /// `nada`; `cpu` sin tasa calibra la máquina.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use assembly_line_simulator::{CpuBurn, WorkMode};
+    /// 
+    /// assert_eq!("cpu:1000".parse(), Ok(WorkMode::CpuBurn(CpuBurn::with_rate(1000))));
+    /// assert_eq!("dormir".parse(), Ok(WorkMode::Sleep));
+    /// assert!("cpu:0".parse::<WorkMode>().is_err());
+    /// ```
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some(rate) = value.strip_prefix("cpu:") {
+            return match rate.parse::<u64>() {
+                Ok(rate) if rate > 0 => Ok(Self::CpuBurn(CpuBurn::with_rate(rate))),
+                _ => Err(format!("Tasa de iteraciones inválida: '{}'", rate)),
+            };
+        }
+        match value {
+            "dormir" => Ok(Self::Sleep),
+            "espera" => Ok(Self::BusyWait),
+            "cpu" => Ok(Self::CpuBurn(CpuBurn::calibrated())),
+            "nada" => Ok(Self::NoWork),
+            _ => Err(format!(
+                "Modo de trabajo desconocido: '{}' (use dormir, espera, cpu, cpu:<n> o nada)",
+                value
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_work_models_duration() {
+        let slice = Duration::from_millis(20);
+
+        let start = Instant::now();
+        BusyWait.perform(slice);
+        assert!(start.elapsed() >= slice);
+
+        let start = Instant::now();
+        SleepWork.perform(slice);
+        assert!(start.elapsed() >= slice);
+
+        let start = Instant::now();
+        NoWork.perform(slice);
+        assert!(start.elapsed() < slice);
+    }
+
+    #[test]
+    fn test_cpu_burn_does_a_fixed_amount_of_work() {
+        let burn = CpuBurn::calibrated();
+        assert_eq!(CpuBurn::calibrated(), burn);
+        assert_eq!(burn.iterations_for(Duration::from_millis(10)), burn.iterations_per_ms() * 10);
+
+        // Sin competencia, la porción dura aproximadamente lo solicitado
+        let slice = Duration::from_millis(20);
+        let start = Instant::now();
+        burn.perform(slice);
+        assert!(start.elapsed() >= slice / 2, "{:?}", start.elapsed());
+    }
+}
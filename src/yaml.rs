@@ -0,0 +1,518 @@
+//! # Módulo de YAML
+//! 
+//! Analizador YAML mínimo para leer escenarios escritos en ese formato. El
+//! resultado es el mismo árbol [`Json`] que produce el analizador JSON, de
+//! modo que ambos formatos comparten el esquema de los escenarios.
+//! 
+//! Se admite el subconjunto que usan los archivos de configuración:
+//! mapeos y secuencias por indentación (incluidos los elementos `- clave:
+//! valor`), colecciones en línea (`[0, 120]`, `{size: 5, gap_ms: 2000}`),
+//! strings con o sin comillas, números, `true`/`false`, `null`/`~`,
+//! comentarios con `#` y el separador de documento `---`. No se admiten
+//! anclas, etiquetas ni bloques de texto multilínea (`|`, `>`).
+//! 
+//! ```yaml
+//! algorithm: rr
+//! quantum_ms: 100
+//! stations:
+//!   - name: Corte
+//!     processing_time_ms: 400
+//! arrivals_ms: [0, 120, 260]
+//! ```
+
+use crate::json::{too_deep, Json, Locations, MAX_DEPTH};
+
+/// Analiza un documento YAML.
+/// 
+/// # Arguments
+/// 
+/// * `text` - Texto del documento
+/// 
+/// # Returns
+/// 
+/// El valor del documento, o `Err` con la descripción y la línea del
+/// primer error
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use assembly_line_simulator::json::Json;
+/// use assembly_line_simulator::yaml;
+/// 
+/// let value = yaml::parse("quantum_ms: 100\nstations:\n  - Corte\n  - 'Empaque'\n").unwrap();
+/// assert_eq!(value, Json::parse(r#"{"quantum_ms": 100, "stations": ["Corte", "Empaque"]}"#).unwrap());
+/// ```
+pub fn parse(text: &str) -> Result<Json, String> {
+    parse_located(text).map(|(value, _)| value)
+}
+
+/// Analiza un documento YAML y registra la línea de cada valor (ver
+/// [`Locations`]).
+pub fn parse_located(text: &str) -> Result<(Json, Locations), String> {
+    let mut lines = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let number = index + 1;
+        let content = strip_comment(raw);
+        let trimmed = content.trim();
+        if trimmed.is_empty() || trimmed == "---" || trimmed == "..." {
+            continue;
+        }
+        let indent = content.len() - content.trim_start().len();
+        if content[..indent].contains('\t') {
+            return Err(error(number, "la indentación debe usar espacios, no tabulaciones"));
+        }
+        lines.push(Line { number, indent, text: trimmed.to_string() });
+    }
+
+    if lines.is_empty() {
+        return Ok((Json::Null, Locations::default()));
+    }
+    let mut parser = Parser { lines, position: 0, depth: 0, path: String::new(), locations: Locations::default() };
+    let indent = parser.lines[0].indent;
+    let value = parser.parse_block(indent)?;
+    if let Some(line) = parser.lines.get(parser.position) {
+        return Err(error(line.number, "indentación inesperada"));
+    }
+    Ok((value, parser.locations))
+}
+
+/// Línea significativa del documento, sin comentarios ni indentación.
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String,
+}
+
+/// Analizador de bloques por indentación.
+struct Parser {
+    lines: Vec<Line>,
+    position: usize,
+    /// Mapeos y secuencias abiertos en la línea actual
+    depth: usize,
+    /// Ruta del valor que se está leyendo
+    path: String,
+    locations: Locations,
+}
+
+impl Parser {
+    /// Ejecuta `parse` para el valor de la ruta `path`, que empieza en la
+    /// línea `line`.
+    fn at(&mut self, path: String, line: usize, parse: impl FnOnce(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        self.locations.record(&path, line);
+        let parent = std::mem::replace(&mut self.path, path);
+        let value = parse(self);
+        self.path = parent;
+        value
+    }
+
+    /// Lee un valor escrito en una sola línea.
+    fn inline(&mut self, text: &str, line: usize) -> Result<Json, String> {
+        parse_inline(text, line, self.depth, &self.path, &mut self.locations)
+    }
+
+    /// Lee el bloque (mapeo, secuencia o escalar) que empieza en la línea
+    /// actual con la indentación indicada.
+    fn parse_block(&mut self, indent: usize) -> Result<Json, String> {
+        let line = &self.lines[self.position];
+        self.locations.record(&self.path, line.number);
+        let parse: fn(&mut Self, usize) -> Result<Json, String> = if is_sequence_item(&line.text) {
+            Self::parse_sequence
+        } else if split_key(&line.text).is_some() {
+            Self::parse_mapping
+        } else {
+            let (number, text) = (line.number, line.text.clone());
+            self.position += 1;
+            return self.inline(&text, number);
+        };
+        if self.depth == MAX_DEPTH {
+            return Err(error(line.number, &too_deep()));
+        }
+        self.depth += 1;
+        let value = parse(self, indent);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_sequence(&mut self, indent: usize) -> Result<Json, String> {
+        let mut items = Vec::new();
+        while let Some(line) = self.lines.get(self.position) {
+            if line.indent != indent || !is_sequence_item(&line.text) {
+                break;
+            }
+            let rest = line.text[1..].trim_start().to_string();
+            let number = line.number;
+            let path = Locations::index(&self.path, items.len());
+            let item = if rest.is_empty() {
+                self.position += 1;
+                self.at(path, number, |parser| parser.parse_nested(indent, false))?
+            } else if split_key(&rest).is_some() || is_sequence_item(&rest) {
+                // El contenido del elemento empieza en la misma línea: se
+                // reemplaza la línea por ese contenido con su indentación real
+                let offset = line.text.len() - rest.len();
+                self.lines[self.position] = Line { number, indent: indent + offset, text: rest };
+                self.at(path, number, |parser| parser.parse_block(indent + offset))?
+            } else {
+                self.position += 1;
+                self.at(path, number, |parser| parser.inline(&rest, number))?
+            };
+            items.push(item);
+        }
+        Ok(Json::Array(items))
+    }
+
+    fn parse_mapping(&mut self, indent: usize) -> Result<Json, String> {
+        let mut entries: Vec<(String, Json)> = Vec::new();
+        while let Some(line) = self.lines.get(self.position) {
+            if line.indent != indent {
+                if line.indent > indent {
+                    return Err(error(line.number, "indentación inesperada"));
+                }
+                break;
+            }
+            let number = line.number;
+            let Some((key, value)) = split_key(&line.text) else {
+                if is_sequence_item(&line.text) {
+                    break;
+                }
+                return Err(error(number, "se esperaba 'clave: valor'"));
+            };
+            let key = parse_key(key, number)?;
+            let value = value.to_string();
+            if entries.iter().any(|(existing, _)| *existing == key) {
+                return Err(error(number, &format!("clave repetida '{}'", key)));
+            }
+            self.position += 1;
+            let path = Locations::field(&self.path, &key);
+            let value = if value.is_empty() {
+                self.at(path, number, |parser| parser.parse_nested(indent, true))?
+            } else {
+                self.at(path, number, |parser| parser.inline(&value, number))?
+            };
+            entries.push((key, value));
+        }
+        Ok(Json::Object(entries))
+    }
+
+    /// Lee el valor de una clave o elemento que continúa en las líneas
+    /// siguientes: un bloque más indentado o, si `same_level_sequence`,
+    /// una secuencia con la misma indentación (el valor de una clave).
+    fn parse_nested(&mut self, indent: usize, same_level_sequence: bool) -> Result<Json, String> {
+        match self.lines.get(self.position) {
+            Some(next) if next.indent > indent => {
+                let indent = next.indent;
+                self.parse_block(indent)
+            }
+            Some(next) if same_level_sequence && next.indent == indent && is_sequence_item(&next.text) => {
+                self.parse_sequence(indent)
+            }
+            _ => Ok(Json::Null),
+        }
+    }
+}
+
+/// Construye un error con la línea del documento.
+fn error(line: usize, message: &str) -> String {
+    format!("YAML inválido en línea {}: {}", line, message)
+}
+
+/// Indica si el texto es un elemento de secuencia (`- valor` o `-`).
+fn is_sequence_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Elimina el comentario de una línea, respetando las comillas.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => return &line[..index],
+            None => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+/// Separa `clave: valor` en el primer `:` seguido de espacio o fin de
+/// línea que no esté entre comillas ni dentro de una colección en línea.
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with('[') || text.starts_with('{') {
+        return None;
+    }
+    let mut quote = None;
+    for (index, c) in text.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == ':' => {
+                let rest = &text[index + 1..];
+                if rest.is_empty() || rest.starts_with(' ') {
+                    return Some((text[..index].trim(), rest.trim()));
+                }
+            }
+            None => {}
+        }
+    }
+    None
+}
+
+/// Lee una clave de mapeo, con o sin comillas.
+fn parse_key(key: &str, line: usize) -> Result<String, String> {
+    match parse_inline(key, line, 0, "", &mut Locations::default())? {
+        Json::String(key) => Ok(key),
+        _ if key.is_empty() => Err(error(line, "clave vacía")),
+        _ => Ok(key.to_string()),
+    }
+}
+
+/// Lee un valor escrito en una sola línea: escalar o colección en línea.
+/// 
+/// `depth` es la cantidad de bloques que contienen al valor.
+fn parse_inline(text: &str, line: usize, depth: usize, path: &str, locations: &mut Locations) -> Result<Json, String> {
+    let mut flow = Flow { text, position: 0, line, depth, path: path.to_string(), locations };
+    let value = flow.parse_value(false)?;
+    flow.skip_whitespace();
+    if flow.position < text.len() {
+        return Err(error(line, "contenido adicional después del valor"));
+    }
+    Ok(value)
+}
+
+/// Analizador de valores en línea (escalares, `[...]` y `{...}`).
+struct Flow<'a, 'b> {
+    text: &'a str,
+    position: usize,
+    line: usize,
+    /// Bloques y colecciones abiertos en la posición actual
+    depth: usize,
+    /// Ruta del valor que se está leyendo
+    path: String,
+    locations: &'b mut Locations,
+}
+
+impl Flow<'_, '_> {
+    /// Lee el valor de la ruta `path`, registrando su línea.
+    fn parse_value_at(&mut self, path: String) -> Result<Json, String> {
+        self.locations.record(&path, self.line);
+        let parent = std::mem::replace(&mut self.path, path);
+        let value = self.parse_value(true);
+        self.path = parent;
+        value
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    /// Lee un valor; dentro de una colección los escalares sin comillas
+    /// terminan en `,`, `]`, `}` o `:`.
+    fn parse_value(&mut self, nested: bool) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => self.parse_nested(Self::parse_sequence),
+            Some('{') => self.parse_nested(Self::parse_mapping),
+            Some(quote @ ('"' | '\'')) => self.parse_quoted(quote).map(Json::String),
+            Some(_) => Ok(self.parse_plain(nested)),
+            None => Ok(Json::Null),
+        }
+    }
+
+    /// Lee una colección en línea, rechazando las que superan [`MAX_DEPTH`].
+    fn parse_nested(&mut self, parse: fn(&mut Self) -> Result<Json, String>) -> Result<Json, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(error(self.line, &too_deep()));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_sequence(&mut self) -> Result<Json, String> {
+        self.position += 1;
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(']') {
+                self.position += 1;
+                return Ok(Json::Array(items));
+            }
+            let path = Locations::index(&self.path, items.len());
+            items.push(self.parse_value_at(path)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {}
+                _ => return Err(error(self.line, "se esperaba ',' o ']'")),
+            }
+        }
+    }
+
+    fn parse_mapping(&mut self) -> Result<Json, String> {
+        self.position += 1;
+        let mut entries = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.position += 1;
+                return Ok(Json::Object(entries));
+            }
+            let key = match self.parse_value(true)? {
+                Json::String(key) => key,
+                other => other.to_string(),
+            };
+            self.skip_whitespace();
+            if self.peek() != Some(':') {
+                return Err(error(self.line, "se esperaba ':'"));
+            }
+            self.position += 1;
+            let value = self.parse_value_at(Locations::field(&self.path, &key))?;
+            entries.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {}
+                _ => return Err(error(self.line, "se esperaba ',' o '}'")),
+            }
+        }
+    }
+
+    /// Lee un string entre comillas simples (`''` escapa la comilla) o
+    /// dobles (con los escapes de JSON).
+    fn parse_quoted(&mut self, quote: char) -> Result<String, String> {
+        self.position += 1;
+        let mut result = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(error(self.line, "string sin cerrar"));
+            };
+            self.position += c.len_utf8();
+            match c {
+                '\'' if quote == '\'' => {
+                    if self.peek() == Some('\'') {
+                        self.position += 1;
+                        result.push('\'');
+                    } else {
+                        return Ok(result);
+                    }
+                }
+                '"' if quote == '"' => return Ok(result),
+                '\\' if quote == '"' => {
+                    let escaped = self.peek().ok_or_else(|| error(self.line, "string sin cerrar"))?;
+                    self.position += escaped.len_utf8();
+                    result.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '0' => '\0',
+                        '"' | '\\' | '/' => escaped,
+                        _ => return Err(error(self.line, "escape inválido")),
+                    });
+                }
+                c => result.push(c),
+            }
+        }
+    }
+
+    /// Lee un escalar sin comillas y lo interpreta como número, booleano,
+    /// nulo o string.
+    fn parse_plain(&mut self, nested: bool) -> Json {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if nested && (c == ',' || c == ']' || c == '}') {
+                break;
+            }
+            if nested && c == ':' && self.text[self.position + 1..].starts_with([' ', ',', ']', '}']) {
+                break;
+            }
+            self.position += c.len_utf8();
+        }
+        let plain = self.text[start..self.position].trim_end();
+        self.position = start + plain.len();
+        scalar(plain)
+    }
+}
+
+/// Interpreta un escalar sin comillas.
+fn scalar(plain: &str) -> Json {
+    match plain {
+        "" | "~" | "null" | "Null" | "NULL" => Json::Null,
+        "true" | "True" | "TRUE" => Json::Bool(true),
+        "false" | "False" | "FALSE" => Json::Bool(false),
+        _ => match plain.replace('_', "").parse::<f64>() {
+            Ok(number) if plain.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.') && number.is_finite() => {
+                Json::Number(number)
+            }
+            _ => Json::String(plain.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blocks_and_flow_collections() {
+        let text = "\
+---
+nombre: \"Línea # 1\"   # comentario
+vacio:
+lista:
+- 1
+- -2.5
+-
+- [a, 'b c', {x: 1}]
+anidado:
+  - clave: valor
+    otra: true
+  - - 3
+    - ~
+";
+        let value = parse(text).unwrap();
+        let expected = Json::parse(
+            r#"{"nombre": "Línea # 1", "vacio": null, "lista": [1, -2.5, null, ["a", "b c", {"x": 1}]],
+                "anidado": [{"clave": "valor", "otra": true}, [3, null]]}"#,
+        )
+        .unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(parse("").unwrap(), Json::Null);
+
+        let (_, locations) = parse_located(text).unwrap();
+        assert_eq!(locations.line("nombre"), Some(2));
+        assert_eq!(locations.line("lista[3][2].x"), Some(8));
+        assert_eq!(locations.line("anidado[0].otra"), Some(11));
+        assert_eq!(locations.line("anidado[1][1]"), Some(13));
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let error = parse("a: 1\n    b: 2\n").unwrap_err();
+        assert!(error.contains("línea 2"), "{}", error);
+        assert!(parse("a: 1\na: 2").unwrap_err().contains("clave repetida"));
+        assert!(parse("a: [1, 2").unwrap_err().contains("']'"));
+        assert!(parse("a: 'abc").unwrap_err().contains("sin cerrar"));
+        assert!(parse("a:\n\t- 1").unwrap_err().contains("tabulaciones"));
+    }
+
+    #[test]
+    fn test_deep_nesting_is_rejected() {
+        assert!(parse(&format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH))).is_ok());
+        // Sin el límite, estos documentos desbordarían la pila
+        let blocks = (0..200).map(|depth| format!("{}a:\n", " ".repeat(depth))).collect::<String>();
+        for text in ["[".repeat(200_000), "a: ".to_string() + &"{b: ".repeat(200_000), "- ".repeat(200_000) + "1", blocks] {
+            assert!(parse(&text).unwrap_err().contains("niveles de anidamiento"));
+        }
+    }
+}
@@ -1,14 +1,17 @@
 //! Tests de integración para el simulador de línea de ensamblaje
 
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use assembly_line_simulator::{
-    Simulation, SchedulingAlgorithm, StationConfig, config
+    ArrivalPeriod, ArrivalProcess, ArrivalSource, BatchRunner, ChannelBackend, ConfigError, DistributionMetric, LossReason, MetricsCalculator, NoWork, OutlierDetector, ProductDependency, Progress, ProductSpec, ProductType, QueueDiscipline, Reconfiguration, Simulation,
+    SharedResource, ShutdownMode, SimulationError, Sla, SchedulingAlgorithm, StallReason, StationCommand, StationConfig, StationStatus, StatusBoard, StatusTransition,
+    StopCondition, TimelineEvent, WorkModel, config
 };
 
 #[test]
 fn test_fcfs_simulation_completes() {
     let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
-    let metrics = simulation.run();
+    let metrics = simulation.run().expect("La simulación debe completarse");
     
     // Verificar que todos los productos fueron completados
     assert_eq!(metrics.products.len(), 10);
@@ -22,7 +25,7 @@ fn test_fcfs_simulation_completes() {
 fn test_round_robin_simulation_completes() {
     let algorithm = SchedulingAlgorithm::round_robin(Duration::from_millis(300));
     let mut simulation = Simulation::new(algorithm);
-    let metrics = simulation.run();
+    let metrics = simulation.run().expect("La simulación debe completarse");
     
     // Verificar que todos los productos fueron completados
     assert_eq!(metrics.products.len(), 10);
@@ -35,14 +38,8 @@ fn test_round_robin_simulation_completes() {
 #[test]
 fn test_custom_configuration() {
     let custom_stations = vec![
-        StationConfig {
-            name: "Test1",
-            processing_time: Duration::from_millis(100),
-        },
-        StationConfig {
-            name: "Test2", 
-            processing_time: Duration::from_millis(200),
-        },
+        StationConfig::new("Test1", Duration::from_millis(100)),
+        StationConfig::new("Test2", Duration::from_millis(200)),
     ];
     
     let custom_arrivals = vec![
@@ -53,7 +50,7 @@ fn test_custom_configuration() {
     
     let algorithm = SchedulingAlgorithm::fcfs();
     let mut simulation = Simulation::with_config(custom_stations, algorithm, custom_arrivals);
-    let metrics = simulation.run();
+    let metrics = simulation.run().expect("La simulación debe completarse");
     
     // Verificar configuración personalizada
     assert_eq!(metrics.products.len(), 3);
@@ -64,12 +61,12 @@ fn test_custom_configuration() {
 fn test_fcfs_vs_round_robin_performance() {
     // Simulación FCFS
     let mut fcfs_simulation = Simulation::new(SchedulingAlgorithm::fcfs());
-    let fcfs_metrics = fcfs_simulation.run();
+    let fcfs_metrics = fcfs_simulation.run().expect("La simulación debe completarse");
     
     // Simulación Round Robin
     let rr_algorithm = SchedulingAlgorithm::round_robin(Duration::from_millis(300));
     let mut rr_simulation = Simulation::new(rr_algorithm);
-    let rr_metrics = rr_simulation.run();
+    let rr_metrics = rr_simulation.run().expect("La simulación debe completarse");
     
     // En este caso específico, FCFS debería ser más eficiente
     // (quantum = 300ms vs tiempos de procesamiento de 400-600ms)
@@ -82,7 +79,7 @@ fn test_fcfs_vs_round_robin_performance() {
 #[test]
 fn test_metrics_consistency() {
     let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
-    let metrics = simulation.run();
+    let metrics = simulation.run().expect("La simulación debe completarse");
     
     // Verificar que las métricas son consistentes
     for product_metrics in &metrics.products {
@@ -108,20 +105,20 @@ fn test_algorithm_parameter_validation() {
     // Quantum muy pequeño
     let small_quantum = SchedulingAlgorithm::round_robin(Duration::from_millis(1));
     let mut simulation = Simulation::new(small_quantum);
-    let metrics = simulation.run();
+    let metrics = simulation.run().expect("La simulación debe completarse");
     assert_eq!(metrics.products.len(), 10);
     
     // Quantum muy grande (efectivamente como FCFS)
     let large_quantum = SchedulingAlgorithm::round_robin(Duration::from_secs(10));
     let mut simulation = Simulation::new(large_quantum);
-    let metrics = simulation.run();
+    let metrics = simulation.run().expect("La simulación debe completarse");
     assert_eq!(metrics.products.len(), 10);
 }
 
 #[test]
 fn test_report_generation() {
     let mut simulation = Simulation::new(SchedulingAlgorithm::fcfs());
-    let metrics = simulation.run();
+    let metrics = simulation.run().expect("La simulación debe completarse");
     
     // Generar reporte de texto
     let text_report = simulation.generate_report(&metrics);
@@ -134,7 +131,3056 @@ fn test_report_generation() {
     assert!(csv_report.contains("ArrivalTime"));
     assert!(csv_report.contains("WaitTime"));
     
-    // Verificar que el CSV tiene el número correcto de líneas
-    let lines: Vec<&str> = csv_report.lines().collect();
+    // Verificar que el CSV tiene el número correcto de líneas, tras los
+    // metadatos de la ejecución
+    assert!(csv_report.starts_with("# crate_version: "));
+    let lines: Vec<&str> = csv_report.lines().filter(|line| !line.starts_with('#')).collect();
     assert_eq!(lines.len(), 11); // 1 header + 10 products
-}
\ No newline at end of file
+}
+#[test]
+fn test_rush_order_injection() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(100))];
+    let arrivals = vec![Duration::ZERO; 4];
+    let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+
+    let handle = simulation.start();
+    std::thread::sleep(Duration::from_millis(30));
+    handle
+        .inject_product(ProductSpec::with_priority(10))
+        .expect("El generador debe aceptar productos inyectados");
+    let metrics = handle.wait().expect("La simulación debe completarse");
+
+    // El pedido urgente recibe el siguiente ID y adelanta a los productos en cola
+    assert_eq!(metrics.products.len(), 5);
+    assert_eq!(metrics.completion_order, vec![1, 5, 2, 3, 4]);
+}
+
+#[test]
+fn test_injection_after_close_returns_the_spec() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+    let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO]);
+
+    let mut handle = simulation.start();
+    handle.close_injection();
+    let rejected = handle
+        .inject_product(ProductSpec::with_priority(7))
+        .expect_err("La admisión cerrada debe rechazar el producto");
+    assert_eq!(rejected.0.priority, 7);
+    assert_eq!(handle.wait().expect("La simulación debe completarse").products.len(), 1);
+}
+
+#[test]
+fn test_product_dependency_blocks_first_station() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(50)),
+        StationConfig::new("Ensamblaje", Duration::from_millis(50)),
+    ];
+    let arrivals = vec![Duration::ZERO, Duration::ZERO];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation.add_dependency(ProductDependency { product: 1, depends_on: 2, station: 1 });
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    // El producto 1 espera a que el producto 2 termine la segunda estación
+    assert_eq!(metrics.completion_order, vec![2, 1]);
+
+    let blocked = metrics.products.iter().find(|p| p.product_id == 1).unwrap();
+    assert!(blocked.dependency_wait_time >= Duration::from_millis(90));
+    assert!(blocked.total_wait_time < Duration::from_millis(40));
+    assert!(metrics.average_dependency_wait_time > Duration::ZERO);
+}
+
+#[test]
+fn test_earliest_due_date_dispatch() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(50))];
+    let arrivals = vec![
+        Duration::ZERO,
+        Duration::from_millis(10),
+        Duration::from_millis(10),
+        Duration::from_millis(10),
+    ];
+    let due_dates = vec![
+        Duration::from_secs(1),
+        Duration::from_secs(1),
+        Duration::from_secs(1),
+        Duration::from_millis(60),
+    ];
+
+    let mut edd = Simulation::with_config(
+        stations.clone(),
+        SchedulingAlgorithm::earliest_due_date(),
+        arrivals.clone(),
+    );
+    edd.set_due_dates(due_dates.clone());
+    let edd_metrics = edd.run().expect("La simulación debe completarse");
+
+    // El producto 4 tiene la fecha más próxima y se adelanta a los demás en cola
+    assert_eq!(edd_metrics.completion_order, vec![1, 4, 2, 3]);
+    assert_eq!(edd_metrics.tardy_jobs, 1);
+    assert!(edd_metrics.max_lateness.unwrap() > 0.0);
+
+    let mut fcfs = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    fcfs.set_due_dates(due_dates);
+    let fcfs_metrics = fcfs.run().expect("La simulación debe completarse");
+
+    assert!(edd_metrics.total_tardiness < fcfs_metrics.total_tardiness);
+}
+
+#[test]
+fn test_station_reports() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(100)),
+        StationConfig::new("Empaque", Duration::from_millis(100)),
+    ];
+    let arrivals = vec![Duration::ZERO, Duration::ZERO];
+    let algorithm = SchedulingAlgorithm::round_robin(Duration::from_millis(50));
+    let mut simulation = Simulation::with_config(stations, algorithm, arrivals);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert_eq!(metrics.station_reports.len(), 2);
+    for (index, report) in metrics.station_reports.iter().enumerate() {
+        assert_eq!(report.index, index);
+        assert_eq!(report.products_processed, 2);
+        // Cada producto requiere dos porciones de 50ms y se interrumpe una vez
+        assert_eq!(report.slices_executed, 4);
+        assert_eq!(report.preemptions, 2);
+        assert!(report.busy_time >= Duration::from_millis(200));
+        assert!(report.utilization() > 0.0 && report.utilization() <= 1.0);
+    }
+
+    let text_report = simulation.generate_report(&metrics);
+    assert!(text_report.contains("ESTADÍSTICAS POR ESTACIÓN"));
+}
+
+#[test]
+fn test_station_names_built_at_runtime_with_display_metadata() {
+    let stations: Vec<StationConfig> = (1..=2)
+        .map(|cell| StationConfig::new(format!("Celda {}", cell), Duration::from_millis(10)))
+        .collect();
+    let stations = vec![
+        stations[0].clone().with_description("Soldadura por puntos").with_color("#1c7ed6"),
+        stations[1].clone(),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    let names: Vec<&str> = metrics.station_reports.iter().map(|report| report.name.as_str()).collect();
+    assert_eq!(names, ["Celda 1", "Celda 2"]);
+    assert!(simulation.generate_report(&metrics).contains("Celda 1: Soldadura por puntos"));
+    let json = MetricsCalculator::new().generate_json_report(&metrics);
+    assert!(json.contains("\"name\":\"Celda 1\",\"description\":\"Soldadura por puntos\",\"color\":\"#1c7ed6\""));
+    assert!(json.contains("\"name\":\"Celda 2\",\"description\":null,\"color\":null"));
+}
+
+#[test]
+fn test_reports_cover_every_station_of_the_line() {
+    for count in [2usize, 5, 10] {
+        let stations: Vec<StationConfig> = (1..=count)
+            .map(|index| StationConfig::new(format!("Celda {}", index), Duration::from_millis(5)))
+            .collect();
+        let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+        let metrics = simulation.run().expect("La simulación debe completarse");
+
+        let text_report = simulation.generate_report(&metrics);
+        let header = text_report.lines().find(|line| line.contains("Llegada")).unwrap();
+        for index in 1..=count {
+            assert!(header.contains(&format!("Celda {}", index)), "falta la columna {index} de {count}");
+        }
+        assert!(!text_report.contains("N/A"));
+
+        let csv_report = simulation.generate_csv_report(&metrics);
+        let mut lines = csv_report.lines().filter(|line| !line.starts_with('#'));
+        let csv_header = lines.next().unwrap();
+        assert!(csv_header.contains(&format!("Station{count}_Exit,Metadata")));
+        assert!(!csv_header.contains(&format!("Station{}_Entry", count + 1)));
+        let columns = csv_header.split(',').count();
+        assert_eq!(columns, 5 + 2 * count);
+        for line in lines {
+            assert_eq!(line.split(',').count(), columns);
+            assert!(!line.contains("N/A"));
+        }
+    }
+}
+
+#[test]
+fn test_wait_time_histogram_shows_the_queue_build_up() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    // Las esperas son ~0, ~20 y ~40 ms: una por intervalo de 15 ms
+    let histogram = MetricsCalculator::histogram(&metrics, DistributionMetric::WaitTime, Duration::from_millis(15));
+    assert_eq!(histogram.counts, vec![1, 1, 1]);
+    assert_eq!(histogram.bucket_start(2), Duration::from_millis(30));
+    assert_eq!(histogram.to_string().lines().count(), 4);
+
+    let report = simulation.generate_report(&metrics);
+    assert!(report.contains("=== DISTRIBUCIÓN DE TIEMPOS ==="));
+    assert!(report.contains("Turnaround (intervalos de"));
+}
+
+#[test]
+fn test_report_diagnoses_products_stuck_behind_a_slow_one() {
+    let stations = vec![StationConfig::new("Horno", Duration::from_millis(10))];
+    let arrivals = (0..20).map(|i| Duration::from_millis(30 * i)).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation
+        .register_product_type(ProductType::new("lento").with_service_time(0, Duration::from_millis(100)))
+        .set_product_type(10, "lento");
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    let analysis = OutlierDetector::new().detect(&metrics);
+    let suspects: Vec<usize> = analysis.suspects.iter().map(|outlier| outlier.product_id).collect();
+    assert!(suspects.starts_with(&[10, 11]), "{:?}", suspects);
+
+    let report = simulation.generate_report(&metrics);
+    assert!(report.contains("=== DIAGNÓSTICO: PRODUCTOS ATÍPICOS ==="));
+    assert!(report.contains("#11: espera"));
+    assert!(report.contains("espera por estación: Horno"));
+}
+
+#[test]
+fn test_sinks_receive_products_as_they_finish() {
+    use assembly_line_simulator::json::Json;
+    use assembly_line_simulator::{ChannelSink, NdjsonSink, VecSink};
+
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Empaque", Duration::from_millis(5)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    let collected = VecSink::new();
+    let ndjson = NdjsonSink::new(Vec::new());
+    let (channel, receiver) = ChannelSink::new();
+    simulation
+        .clear_sinks()
+        .add_sink(collected.clone())
+        .add_sink(ndjson.clone())
+        .add_sink(channel);
+
+    // El canal entrega los productos sin esperar a que la línea termine
+    let handle = simulation.start();
+    let first = receiver.recv_timeout(Duration::from_secs(5)).expect("Debe llegar un producto");
+    assert_eq!(first.product_id, 1);
+    let metrics = handle.wait().expect("La simulación debe completarse");
+
+    let ids: Vec<usize> = collected.products().iter().map(|product| product.product_id).collect();
+    assert_eq!(ids, metrics.completion_order);
+    assert_eq!(receiver.try_iter().count(), 3);
+
+    assert!(ndjson.take_error().is_none());
+    drop(simulation);
+    let output = String::from_utf8(ndjson.into_inner().expect("El destino ya no está registrado")).unwrap();
+    let lines: Vec<Json> = output.lines().map(|line| Json::parse(line).expect("Cada línea es JSON")).collect();
+    assert_eq!(lines.len(), 4);
+    for (line, product) in lines.iter().zip(&metrics.products) {
+        assert_eq!(line.get("id").and_then(Json::as_f64), Some(product.product_id as f64));
+        assert_eq!(line.get("timeline").and_then(Json::as_array).map(<[Json]>::len), Some(product.timeline.len()));
+    }
+    assert!(output.ends_with('\n'));
+}
+
+#[test]
+fn test_csv_writers_match_generated_report() {
+    use assembly_line_simulator::CsvSink;
+
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Empaque", Duration::from_millis(5)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    let sink = CsvSink::new(Vec::new(), 2);
+    simulation.clear_sinks().add_sink(sink.clone());
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    // El reporte escrito por filas es idéntico al generado en memoria
+    let generated = simulation.generate_csv_report(&metrics);
+    let mut written = Vec::new();
+    simulation.write_csv_report(&metrics, &mut written).expect("Escribir en memoria no falla");
+    assert_eq!(String::from_utf8(written).unwrap(), generated);
+
+    // El destino escribe las mismas filas, sin los metadatos de la ejecución
+    assert!(sink.take_error().is_none());
+    drop(simulation);
+    let streamed = String::from_utf8(sink.into_inner().expect("El destino ya no está registrado")).unwrap();
+    let expected: String = generated
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    assert_eq!(streamed, expected);
+    assert_eq!(streamed.lines().count(), 5);
+}
+
+#[test]
+fn test_metrics_stream_delivers_products_live() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(50))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    simulation.clear_sinks();
+
+    let mut handle = simulation.start();
+    let stream = handle.metrics_stream();
+    drop(handle.metrics_stream());
+
+    // El primer producto llega mientras los demás siguen en la línea
+    let first = stream.recv_timeout(Duration::from_secs(5)).expect("Debe llegar un producto");
+    assert_eq!(first.product_id, 1);
+    assert!(handle.progress().completed < 3);
+
+    handle.close_injection();
+    let rest: Vec<usize> = stream.iter().map(|product| product.product_id).collect();
+    assert_eq!(rest, vec![2, 3]);
+
+    // Una suscripción posterior al final del colector se cierra sin productos
+    assert!(handle.metrics_stream().recv().is_err());
+    let metrics = handle.wait().expect("La simulación debe completarse");
+    assert_eq!(metrics.completion_order, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_line_smooths_bursty_arrivals() {
+    let millis = Duration::from_millis;
+    let stations = vec![StationConfig::new("Corte", millis(20))];
+    let arrivals = [vec![millis(0); 4], vec![millis(200); 4]].concat();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    // Llegadas en dos ráfagas: intervalos 0, 0, 0, 200, 0, 0, 0 ms
+    assert_eq!(metrics.arrival_flow.intervals, 7);
+    assert!(metrics.arrival_flow.coefficient_of_variation > 2.0);
+
+    // La estación espacia las salidas cada ~20 ms dentro de cada ráfaga
+    assert_eq!(metrics.inter_departure_times.len(), 7);
+    assert!(metrics.inter_departure_times.iter().filter(|&&gap| gap >= millis(15)).count() >= 6);
+    assert!(metrics.departure_flow.coefficient_of_variation < metrics.arrival_flow.coefficient_of_variation);
+    assert!(metrics.flow_variability_ratio().unwrap() < 1.0);
+
+    let report = simulation.generate_report(&metrics);
+    assert!(report.contains("Intervalo entre salidas: promedio"));
+}
+
+#[test]
+fn test_invalid_configurations_are_rejected_before_running() {
+    let station = |name: &str, millis: u64| StationConfig::new(name, Duration::from_millis(millis));
+    let fcfs = SchedulingAlgorithm::fcfs();
+
+    let simulation = Simulation::with_config(Vec::new(), fcfs.clone(), vec![Duration::ZERO]);
+    assert_eq!(simulation.validate(), Err(ConfigError::NoStations));
+
+    let simulation = Simulation::with_config(vec![station("Corte", 10), station("Pintura", 0)], fcfs.clone(), vec![Duration::ZERO]);
+    assert_eq!(
+        simulation.validate(),
+        Err(ConfigError::ZeroProcessingTime { station_index: 1, station: "Pintura".to_string() })
+    );
+
+    let round_robin = SchedulingAlgorithm::round_robin(Duration::ZERO);
+    let simulation = Simulation::with_config(vec![station("Corte", 10)], round_robin.clone(), vec![Duration::ZERO]);
+    assert_eq!(simulation.validate(), Err(ConfigError::ZeroQuantum { station_index: None }));
+
+    let mut simulation = Simulation::with_config(vec![station("Corte", 10), station("Empaque", 10)], fcfs.clone(), vec![Duration::ZERO]);
+    simulation.set_station_algorithm(1, round_robin);
+    assert_eq!(simulation.validate(), Err(ConfigError::ZeroQuantum { station_index: Some(1) }));
+
+    let arrivals = vec![Duration::ZERO, Duration::from_millis(30), Duration::from_millis(20)];
+    let mut simulation = Simulation::with_config(vec![station("Corte", 10)], fcfs, arrivals);
+    let expected = ConfigError::UnsortedArrivals {
+        index: 2,
+        previous: Duration::from_millis(30),
+        current: Duration::from_millis(20),
+    };
+    assert_eq!(simulation.validate(), Err(expected.clone()));
+
+    // `run` no lanza hilos con una configuración inválida
+    match simulation.run() {
+        Err(SimulationError::InvalidConfig { error, metrics }) => {
+            assert_eq!(error, expected);
+            assert!(metrics.products.is_empty());
+        }
+        other => panic!("Se esperaba una configuración inválida, se obtuvo {:?}", other.map(|m| m.products.len())),
+    }
+    assert!(Simulation::new(SchedulingAlgorithm::fcfs()).validate().is_ok());
+}
+
+#[test]
+fn test_progress_callback_reports_completions_and_eta() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(40)),
+        StationConfig::new("Empaque", Duration::from_millis(5)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    let updates: Arc<Mutex<Vec<Progress>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&updates);
+    simulation.on_progress(move |progress| sink.lock().unwrap().push(*progress));
+    simulation.run().expect("La simulación debe completarse");
+
+    let updates = updates.lock().unwrap();
+    let completed: Vec<usize> = updates.iter().map(|progress| progress.completed).collect();
+    assert_eq!(completed, [1, 2, 3, 4]);
+    assert!(updates.iter().all(|progress| progress.total == Some(4) && progress.eta.is_some()));
+    // Al terminar el primer producto (t=45ms) el segundo está en Corte; la
+    // porción en curso cuenta completa hasta que termina
+    assert_eq!(updates[0].remaining_work, Duration::from_millis(135));
+    let last = updates.last().unwrap();
+    assert_eq!(last.remaining_work, Duration::ZERO);
+    assert_eq!(last.eta, Some(Duration::ZERO));
+    assert_eq!(last.fraction(), Some(1.0));
+    assert!(last.to_string().starts_with("[####################] 4/4"));
+}
+
+#[test]
+fn test_progress_updates_are_throttled_for_large_runs() {
+    use assembly_line_simulator::progress::MAX_UPDATES;
+
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(1))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2500]);
+    let updates: Arc<Mutex<Vec<usize>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&updates);
+    simulation
+        .set_work_model(NoWork)
+        .clear_sinks()
+        .on_progress(move |progress| sink.lock().unwrap().push(progress.completed));
+    simulation.run().expect("La simulación debe completarse");
+
+    // Un aviso cada tres finalizaciones, más el del último producto
+    let updates = updates.lock().unwrap();
+    assert!(updates.len() <= MAX_UPDATES, "{}", updates.len());
+    assert!(updates.iter().all(|completed| completed % 3 == 0 || *completed == 2500));
+    assert_eq!(updates.last(), Some(&2500));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_progress_eta_in_virtual_time() {
+    let stations = vec![StationConfig::new("Horno", Duration::from_millis(100))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    let updates: Arc<Mutex<Vec<Progress>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&updates);
+    simulation.on_progress(move |progress| sink.lock().unwrap().push(*progress));
+    simulation.run_virtual().expect("La simulación debe completarse");
+
+    let updates = updates.lock().unwrap();
+    let elapsed: Vec<Duration> = updates.iter().map(|progress| progress.elapsed).collect();
+    assert_eq!(elapsed, [100, 200, 300].map(Duration::from_millis));
+    // Tras 100 ms se completó un tercio del trabajo: faltan 200 ms
+    assert_eq!(updates[0].eta, Some(Duration::from_millis(200)));
+}
+
+#[test]
+fn test_cancel_returns_partial_metrics_with_in_flight_products() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(40)),
+        StationConfig::new("Empaque", Duration::from_millis(40)),
+    ];
+    let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 8]);
+    let mut handle = simulation.start();
+    handle.close_injection();
+    std::thread::sleep(Duration::from_millis(100));
+    handle.cancel();
+    let metrics = handle.wait().expect("La cancelación no es una falla");
+
+    assert!(!metrics.products.is_empty());
+    assert_eq!(metrics.products.len() + metrics.incomplete_products.len(), 8);
+    assert!(metrics.total_simulation_time < Duration::from_millis(400));
+    assert!(simulation.generate_report(&metrics).contains("PRODUCTOS INCOMPLETOS"));
+}
+
+#[cfg(feature = "store")]
+#[test]
+fn test_result_store_accumulates_runs_by_configuration() {
+    use assembly_line_simulator::store::{self, ResultStore};
+
+    let path = std::env::temp_dir().join(format!("simulador-store-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let stations = || vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Empaque", Duration::from_millis(10)),
+    ];
+    let mut fcfs = Simulation::with_config(stations(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    let algorithm = SchedulingAlgorithm::round_robin(Duration::from_millis(5));
+    let mut round_robin = Simulation::with_config(stations(), algorithm, vec![Duration::ZERO; 3]);
+
+    let first_run = {
+        let mut results = ResultStore::open(&path).expect("La base debe crearse");
+        let metrics = fcfs.run().expect("La simulación debe completarse");
+        let run_id = results.record_run(&fcfs, &metrics).unwrap();
+        let metrics = round_robin.run().expect("La simulación debe completarse");
+        results.record_run(&round_robin, &metrics).unwrap();
+        run_id
+    };
+
+    // Al reabrir la base, las ejecuciones anteriores siguen ahí
+    let mut results = ResultStore::open(&path).expect("La base debe abrirse");
+    let metrics = fcfs.run().expect("La simulación debe completarse");
+    let second_run = results.record_run(&fcfs, &metrics).unwrap();
+    assert_ne!(first_run, second_run);
+
+    assert_eq!(results.runs().unwrap().len(), 3);
+    let same_config = results.runs_with_config(&store::config_hash(&fcfs)).unwrap();
+    let ids: Vec<&str> = same_config.iter().map(|run| run.run_id.as_str()).collect();
+    assert_eq!(ids, [first_run.as_str(), second_run.as_str()]);
+    assert!(same_config.iter().all(|run| run.completed == 3 && run.algorithm == "FCFS"));
+
+    // Cada producto tiene al menos un traslado, una espera y una porción por estación
+    assert!(results.event_count(&second_run).unwrap() >= 3 * 2 * 2);
+    drop(results);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "plotters")]
+#[test]
+fn test_charts_render_from_metrics() {
+    use assembly_line_simulator::charts::{self, ChartData};
+
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(20)),
+        StationConfig::new("Empaque", Duration::from_millis(10)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    let ChartData::Bars(waits) = charts::wait_times(&metrics).data else { panic!("Se esperaban barras") };
+    assert_eq!(waits.len(), 4);
+    let ChartData::Bars(utilization) = charts::utilization(&metrics).data else { panic!("Se esperaban barras") };
+    assert_eq!(utilization.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(), ["Corte", "Empaque"]);
+    // Los cuatro productos entran juntos a la cola de Corte y luego se vacía
+    let ChartData::Steps(queue) = charts::queue_length(&metrics, 0).data else { panic!("Se esperaba una serie") };
+    assert_eq!(queue.iter().map(|(_, length)| *length).fold(0.0, f64::max), 4.0);
+    assert_eq!(queue.last().map(|(_, length)| *length), Some(0.0));
+
+    let directory = std::env::temp_dir().join(format!("simulador-charts-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let charts = [
+        ("espera.svg", charts::wait_times(&metrics)),
+        ("turnaround.svg", charts::turnaround_histogram(&metrics, 4)),
+        ("cola.svg", charts::queue_length(&metrics, 0)),
+        ("utilizacion.svg", charts::utilization(&metrics)),
+    ];
+    for (name, chart) in &charts {
+        chart.save(directory.join(name)).expect("El SVG debe guardarse");
+        let svg = std::fs::read_to_string(directory.join(name)).unwrap();
+        assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(&chart.title));
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_product_metrics_expose_round_robin_slices() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(90)),
+        StationConfig::new("Empaque", Duration::from_millis(30)),
+    ];
+    let algorithm = SchedulingAlgorithm::round_robin(Duration::from_millis(30));
+    let mut simulation = Simulation::with_config(stations, algorithm, vec![Duration::ZERO; 2]);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    for product in &metrics.products {
+        // 90 ms en porciones de 30 ms en Corte; una sola porción en Empaque
+        assert_eq!(product.slice_counts, vec![3, 1]);
+        assert_eq!(product.slices.len(), 4);
+        assert!(product.slices.windows(2).all(|pair| pair[0].2 <= pair[1].1));
+        let (_, first_start, _) = product.slices[0];
+        assert_eq!(first_start, product.visit_times[0].1);
+    }
+    let executed: usize = metrics.station_reports.iter().map(|report| report.slices_executed).sum();
+    let consumed: usize = metrics.products.iter().map(|product| product.slices.len()).sum();
+    assert_eq!(executed, consumed);
+
+    let json = MetricsCalculator::new().generate_json_report(&metrics);
+    assert!(json.contains("\"slice_counts\":[3,1]"));
+}
+
+#[test]
+fn test_generator_panic_returns_error_with_partial_metrics() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    // La llegada imposible desborda el instante objetivo y hace fallar al generador
+    let arrivals = vec![Duration::ZERO, Duration::MAX];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+
+    match simulation.run() {
+        Err(SimulationError::GeneratorPanicked { metrics, .. }) => {
+            assert_eq!(metrics.products.len(), 1);
+        }
+        other => panic!("Se esperaba GeneratorPanicked, se obtuvo {:?}", other.map(|m| m.products.len())),
+    }
+}
+
+#[test]
+fn test_dependency_cycle_reports_stall() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(20)),
+        StationConfig::new("Empaque", Duration::from_millis(20)),
+    ];
+    let arrivals = vec![Duration::ZERO, Duration::ZERO, Duration::ZERO];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    // Los productos 1 y 2 se esperan mutuamente: ninguno puede iniciar
+    simulation
+        .add_dependency(ProductDependency { product: 1, depends_on: 2, station: 0 })
+        .add_dependency(ProductDependency { product: 2, depends_on: 1, station: 0 })
+        .set_completion_timeout(Duration::from_millis(300));
+
+    match simulation.run() {
+        Err(SimulationError::Stalled { reason, in_flight, metrics }) => {
+            assert_eq!(reason, StallReason::NoProgress(Duration::from_millis(300)));
+            assert_eq!(metrics.completion_order, vec![3]);
+            let mut held: Vec<(usize, &str)> = in_flight
+                .iter()
+                .map(|product| (product.product_id, product.station.as_str()))
+                .collect();
+            held.sort();
+            assert_eq!(held, vec![(1, "Corte"), (2, "Corte")]);
+        }
+        other => panic!("Se esperaba Stalled, se obtuvo {:?}", other.map(|m| m.products.len())),
+    }
+}
+
+#[test]
+fn test_burst_arrivals_penalize_round_robin_turnaround() {
+    let burst = ArrivalProcess::Burst {
+        start: Duration::ZERO,
+        size: 4,
+        gap: Duration::from_millis(300),
+        bursts: 2,
+    };
+    let run = |algorithm: SchedulingAlgorithm| {
+        let stations = vec![StationConfig::new("Corte", Duration::from_millis(40))];
+        Simulation::with_config(stations, algorithm, burst.offsets())
+            .run()
+            .expect("La simulación debe completarse")
+    };
+    let fcfs = run(SchedulingAlgorithm::fcfs());
+    let rr = run(SchedulingAlgorithm::round_robin(Duration::from_millis(10)));
+
+    assert_eq!(fcfs.products.len(), 8);
+    assert_eq!(rr.products.len(), 8);
+    // Dentro de cada ráfaga Round Robin intercala los productos y todos
+    // terminan cerca del final, mientras FCFS los despacha de a uno
+    assert!(rr.average_turnaround_time > fcfs.average_turnaround_time);
+}
+
+#[test]
+fn test_multiple_arrival_sources() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    let arrivals = vec![Duration::ZERO, Duration::from_millis(10)];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation
+        .add_arrival_source(ArrivalSource::new(
+            "retrabajo",
+            ArrivalProcess::Fixed(vec![Duration::from_millis(150)]),
+            ProductSpec::default().with_class("retrabajo"),
+        ))
+        .add_arrival_source(ArrivalSource::new(
+            "manual",
+            ArrivalProcess::Periodic {
+                start: Duration::from_millis(5),
+                interval: Duration::from_millis(40),
+                count: 2,
+            },
+            ProductSpec::default().with_class("manual"),
+        ));
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    // La línea espera a que terminen todas las fuentes antes de apagarse
+    assert_eq!(metrics.products.len(), 5);
+    assert_eq!(metrics.completion_order.last(), Some(&3));
+
+    let class_of = |id: usize| {
+        metrics.products.iter().find(|p| p.product_id == id).unwrap().class.clone()
+    };
+    assert_eq!(class_of(1), None);
+    assert_eq!(class_of(3).as_deref(), Some("retrabajo"));
+    assert_eq!(class_of(4).as_deref(), Some("manual"));
+    assert_eq!(class_of(5).as_deref(), Some("manual"));
+}
+
+#[test]
+fn test_stop_after_completions_reports_incomplete_products() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(40)),
+        StationConfig::new("Empaque", Duration::from_millis(40)),
+    ];
+    let arrivals = vec![Duration::ZERO; 6];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation.set_stop_condition(StopCondition::Completions(2));
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert!(metrics.products.len() >= 2);
+    assert!(metrics.products.len() < 6);
+    assert_eq!(metrics.products.len() + metrics.incomplete_products.len(), 6);
+    for product in &metrics.incomplete_products {
+        assert!(product.station_times.last().unwrap().1.is_zero());
+    }
+
+    let text_report = simulation.generate_report(&metrics);
+    assert!(text_report.contains("PRODUCTOS INCOMPLETOS"));
+}
+
+#[test]
+fn test_final_metrics_match_products_delivered_to_sinks() {
+    use assembly_line_simulator::{ProductMetrics, VecSink};
+
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(30)),
+        StationConfig::new("Empaque", Duration::from_millis(20)),
+    ];
+    let arrivals = vec![Duration::ZERO; 6];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::round_robin(Duration::from_millis(15)),
+        arrivals,
+    );
+    let collected = VecSink::new();
+    simulation
+        .clear_sinks()
+        .add_sink(collected.clone())
+        .set_stop_condition(StopCondition::Completions(3))
+        .set_shutdown_mode(ShutdownMode::Immediate);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    // Las métricas finales de los completados son las que recibió el
+    // destino al salir de la línea, en el orden de los productos
+    let mut finished = collected.products();
+    finished.sort_by_key(|product| product.product_id);
+    let summary = |product: &ProductMetrics| {
+        (
+            product.product_id,
+            product.turnaround_time,
+            product.station_times.clone(),
+            product.slices.clone(),
+            product.timeline.clone(),
+        )
+    };
+    assert_eq!(
+        metrics.products.iter().map(summary).collect::<Vec<_>>(),
+        finished.iter().map(summary).collect::<Vec<_>>()
+    );
+    assert!(!metrics.incomplete_products.is_empty());
+    assert_eq!(metrics.products.len() + metrics.incomplete_products.len(), 6);
+}
+
+#[test]
+fn test_shutdown_mode_abandons_queued_products() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(50))];
+    let arrivals = vec![Duration::ZERO; 4];
+
+    let mut drain = Simulation::with_config(stations.clone(), SchedulingAlgorithm::fcfs(), arrivals.clone());
+    let metrics = drain.run().expect("La simulación debe completarse");
+    assert_eq!(metrics.products.len(), 4);
+    assert!(metrics.incomplete_products.is_empty());
+
+    let mut immediate = Simulation::with_config(stations.clone(), SchedulingAlgorithm::fcfs(), arrivals.clone());
+    immediate.set_shutdown_mode(ShutdownMode::Immediate);
+    let metrics = immediate.run().expect("La simulación debe completarse");
+    assert!(metrics.incomplete_products.len() >= 2, "{:?}", metrics.incomplete_products.len());
+    assert_eq!(metrics.products.len() + metrics.incomplete_products.len(), 4);
+
+    let mut deadline = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    deadline.set_shutdown_mode(ShutdownMode::AfterDeadline(Duration::from_millis(75)));
+    let metrics = deadline.run().expect("La simulación debe completarse");
+    assert!(!metrics.products.is_empty());
+    assert!(!metrics.incomplete_products.is_empty());
+    assert_eq!(metrics.products.len() + metrics.incomplete_products.len(), 4);
+}
+
+#[test]
+fn test_control_commands_change_station_at_runtime() {
+    let stations = vec![StationConfig::new("Ensamblaje", Duration::from_millis(40))];
+    let rr = SchedulingAlgorithm::round_robin(Duration::from_millis(10));
+    let arrivals = vec![Duration::from_millis(50), Duration::from_millis(60)];
+    let simulation = Simulation::with_config(stations, rr, arrivals);
+    let handle = simulation.start();
+
+    // La estación está ociosa: aplica los comandos al recibir el primer producto
+    let control = handle.control();
+    assert_eq!(control.station_index("Ensamblaje"), Some(0));
+    assert_eq!(control.station_index("Pintura"), None);
+    control.send(0, StationCommand::SetProcessingTime(Duration::from_millis(30))).unwrap();
+    control.send(0, StationCommand::SetQuantum(Duration::from_millis(30))).unwrap();
+
+    let metrics = handle.wait().expect("La simulación debe completarse");
+    assert_eq!(metrics.products.len(), 2);
+    for product in &metrics.products {
+        assert_eq!(product.slice_counts, vec![1]);
+        let (_, start, end) = product.slices[0];
+        assert!(end - start >= Duration::from_millis(30));
+        assert!(end - start < Duration::from_millis(40));
+    }
+}
+
+#[test]
+fn test_control_pause_and_flush_queue() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(5)),
+        StationConfig::new("Empaque", Duration::from_millis(20)),
+    ];
+    let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 6]);
+    let mut handle = simulation.start();
+    let control = handle.control();
+    control.send(1, StationCommand::Pause).unwrap();
+
+    // En pausa, los productos se acumulan en la cola del empaque
+    std::thread::sleep(Duration::from_millis(100));
+    let progress = handle.progress();
+    assert!(progress.completed <= 1, "{:?}", progress);
+    assert_eq!(progress.in_flight + progress.completed, 6);
+
+    control.send(1, StationCommand::FlushQueue).unwrap();
+    assert_eq!(control.broadcast(StationCommand::Resume), 2);
+    handle.close_injection();
+    let metrics = handle.wait().expect("La simulación debe completarse");
+
+    assert!(metrics.incomplete_products.len() >= 5);
+    assert_eq!(metrics.products.len() + metrics.incomplete_products.len(), 6);
+    for product in &metrics.incomplete_products {
+        assert!(product.station_times[1].1.is_zero());
+    }
+}
+
+#[test]
+fn test_reconfiguration_splits_metrics_into_epochs() {
+    let millis = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", millis(10)),
+        StationConfig::new("Empaque", millis(60)),
+    ];
+    let arrivals = (0..4).map(|i| millis(i * 100)).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+
+    // Una reconfiguración con otras estaciones no puede aplicarse
+    let invalid = simulation.clone_with(|simulation| {
+        let mismatched = vec![StationConfig::new("Corte", millis(10))];
+        simulation.add_reconfiguration(Reconfiguration::new(millis(150), mismatched));
+    });
+    assert!(matches!(invalid.validate(), Err(ConfigError::InvalidReconfiguration { .. })));
+
+    let improved = vec![
+        StationConfig::new("Corte", millis(10)),
+        StationConfig::new("Empaque", millis(20)),
+    ];
+    simulation.add_reconfiguration(Reconfiguration::new(millis(150), improved));
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    let epochs: Vec<usize> = metrics.products.iter().map(|product| product.config_epoch).collect();
+    assert_eq!(epochs, vec![0, 0, 1, 1]);
+    for product in &metrics.products[2..] {
+        let (entry, exit) = product.station_times[1];
+        assert!(exit - entry < millis(60), "{:?}", product.station_times);
+    }
+    assert_eq!(metrics.config_epochs.len(), 2);
+    assert_eq!(metrics.config_epochs[0].name, "Configuración 0");
+    assert_eq!(metrics.config_epochs[1].start, millis(150));
+    assert_eq!(metrics.config_epochs[0].arrivals + metrics.config_epochs[1].arrivals, 4);
+    assert!(metrics.config_epochs[1].average_turnaround_time < metrics.config_epochs[0].average_turnaround_time);
+}
+
+#[test]
+fn test_queue_discipline_per_station() {
+    let millis = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", millis(40)),
+        StationConfig::new("Empaque", millis(5)),
+    ];
+    let arrivals: Vec<Duration> = (0..5).map(|i| millis(i * 5)).collect();
+    let mut lifo = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    lifo.set_queue_discipline(0, QueueDiscipline::lifo());
+    let metrics = lifo.run().expect("La simulación debe completarse");
+
+    // Mientras el corte procesa el primero llegan los demás, que se atienden
+    // del último al primero
+    assert_eq!(metrics.completion_order, vec![1, 5, 4, 3, 2]);
+
+    let random = lifo.clone_with(|simulation| {
+        simulation.set_queue_discipline(0, QueueDiscipline::random(11));
+    });
+    let first = random.clone().run().expect("La simulación debe completarse");
+    let second = random.clone().run().expect("La simulación debe completarse");
+    assert_eq!(first.completion_order, second.completion_order);
+    let mut sorted = first.completion_order.clone();
+    sorted.sort();
+    assert_eq!(sorted, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_balking_products_are_reported_as_lost() {
+    let millis = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Caja", millis(100)),
+        StationConfig::new("Empaque", millis(5)),
+    ];
+    let arrivals = (0..5).map(|i| millis(i * 10)).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation.set_balking_threshold(0, 2);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    // Mientras la caja atiende al primero, dos esperan y los demás desisten
+    assert_eq!(metrics.completion_order, vec![1, 2, 3]);
+    assert!(metrics.incomplete_products.is_empty());
+    let lost: Vec<usize> = metrics.lost_products.iter().map(|product| product.product_id).collect();
+    assert_eq!(lost, vec![4, 5]);
+    for product in &metrics.lost_products {
+        let loss = product.loss.expect("Los productos perdidos registran su pérdida");
+        assert_eq!((loss.station, loss.reason), (0, LossReason::Balked));
+        assert!(product.turnaround_time < millis(5), "{:?}", product.turnaround_time);
+    }
+    assert_eq!(metrics.station_reports[0].balked, 2);
+    assert_eq!(metrics.station_reports[0].max_queue_length, 2);
+
+    let report = simulation.generate_report(&metrics);
+    assert!(report.contains("=== PRODUCTOS PERDIDOS ==="), "{}", report);
+}
+
+#[test]
+fn test_stop_after_duration() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(30))];
+    let arrivals: Vec<Duration> = (0..20).map(|i| Duration::from_millis(i * 100)).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation.set_stop_condition(StopCondition::Duration(Duration::from_millis(250)));
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    // Las llegadas posteriores a la parada nunca ingresan a la línea
+    assert!(metrics.total_simulation_time < Duration::from_secs(1));
+    assert!(!metrics.products.is_empty());
+    assert!(metrics.products.len() + metrics.incomplete_products.len() < 20);
+}
+
+#[test]
+fn test_piecewise_arrivals_segment_report_by_period() {
+    let millis = Duration::from_millis;
+    let stations = vec![StationConfig::new("Corte", millis(20))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), Vec::new());
+    simulation.add_arrival_source(ArrivalSource::new(
+        "turnos",
+        ArrivalProcess::Piecewise {
+            periods: vec![
+                ArrivalPeriod::new("normal", millis(0), millis(200), millis(50)),
+                ArrivalPeriod::new("pico", millis(200), millis(400), millis(10)),
+                ArrivalPeriod::new("recuperación", millis(400), millis(600), millis(50)),
+            ],
+            seed: None,
+        },
+        ProductSpec::default(),
+    ));
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    let arrivals: Vec<usize> = metrics.periods.iter().map(|period| period.arrivals).collect();
+    assert_eq!(arrivals, vec![4, 20, 4]);
+    let (normal, peak, recovery) = (&metrics.periods[0], &metrics.periods[1], &metrics.periods[2]);
+    // El pico satura la estación y la cola sigue afectando al período siguiente
+    assert!(peak.average_wait_time > normal.average_wait_time);
+    assert!(recovery.average_wait_time > normal.average_wait_time);
+    assert!(simulation.generate_report(&metrics).contains("ESTADÍSTICAS POR PERÍODO"));
+}
+
+#[test]
+fn test_slas_report_verdict_and_margin() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(50))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    simulation
+        .add_sla(Sla::TurnaroundWithin {
+            fraction: 0.5,
+            limit: Duration::from_secs(1),
+        })
+        .add_sla(Sla::MaxAverageWait(Duration::from_millis(10)));
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    let (turnaround, wait) = (&metrics.sla_results[0], &metrics.sla_results[1]);
+    assert!(turnaround.passed);
+    // El percentil 50 de cuatro productos en fila es el segundo (~100ms)
+    assert!(turnaround.actual >= 0.1 && turnaround.margin > 0.8);
+    // Los productos esperan en promedio ~75ms, muy por encima del objetivo
+    assert!(!wait.passed);
+    assert!(wait.margin < -0.05);
+    assert!(!metrics.slas_met());
+
+    assert!(simulation.generate_report(&metrics).contains("NO CUMPLE"));
+    assert!(MetricsCalculator::new().generate_json_report(&metrics).contains("\"passed\":false"));
+}
+
+#[test]
+fn test_open_loop_arrivals_with_warmup() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(10))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), Vec::new());
+    simulation
+        .add_arrival_source(ArrivalSource::new(
+            "poisson",
+            ArrivalProcess::Exponential {
+                mean_interarrival: Duration::from_millis(20),
+                seed: 7,
+                count: None,
+            },
+            ProductSpec::default(),
+        ))
+        .set_stop_condition(StopCondition::Completions(15))
+        .set_warmup(Duration::from_millis(100));
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert!(metrics.products.len() >= 15);
+    let steady = metrics.steady_state.as_ref().expect("Debe haber estadísticas de estado estable");
+    assert_eq!(steady.warmup, Duration::from_millis(100));
+    assert!(steady.products_considered < metrics.products.len());
+    assert!(steady.throughput > 0.0);
+
+    let text_report = simulation.generate_report(&metrics);
+    assert!(text_report.contains("ESTADO ESTABLE"));
+}
+
+#[test]
+#[should_panic(expected = "condición de parada")]
+fn test_open_loop_requires_stop_condition() {
+    let mut simulation = Simulation::with_config(
+        config::default_station_configs(),
+        SchedulingAlgorithm::fcfs(),
+        Vec::new(),
+    );
+    simulation.add_arrival_source(ArrivalSource::new(
+        "poisson",
+        ArrivalProcess::Exponential {
+            mean_interarrival: Duration::from_millis(20),
+            seed: 7,
+            count: None,
+        },
+        ProductSpec::default(),
+    ));
+    let _ = simulation.run();
+}
+
+#[test]
+fn test_product_metadata_flows_to_metrics_and_csv() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO, Duration::from_millis(10)],
+    );
+    simulation.set_product_metadata(1, "orden", "OC-1042");
+
+    let handle = simulation.start();
+    handle
+        .inject_product(ProductSpec::default().with_metadata("sku", "A-7"))
+        .unwrap();
+    let metrics = handle.wait().expect("La simulación debe completarse");
+
+    let find = |id: usize| metrics.products.iter().find(|p| p.product_id == id).unwrap();
+    assert_eq!(find(1).metadata.get("orden").map(String::as_str), Some("OC-1042"));
+    assert!(find(2).metadata.is_empty());
+    assert_eq!(find(3).metadata.get("sku").map(String::as_str), Some("A-7"));
+
+    let csv_report = simulation.generate_csv_report(&metrics);
+    assert!(csv_report.lines().find(|line| !line.starts_with('#')).unwrap().ends_with(",Metadata"));
+    assert!(csv_report.contains("orden=OC-1042"));
+    assert!(csv_report.contains("sku=A-7"));
+}
+
+#[test]
+fn test_mixed_model_product_types() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(30)),
+        StationConfig::new("Pintura", Duration::from_millis(30)),
+        StationConfig::new("Empaque", Duration::from_millis(30)),
+    ];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO, Duration::from_millis(10)],
+    );
+    simulation
+        .register_product_type(
+            ProductType::new("sin_pintura")
+                .with_route(vec![0, 2])
+                .with_service_time(2, Duration::from_millis(10)),
+        )
+        .set_product_type(2, "sin_pintura");
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert_eq!(metrics.products.len(), 2);
+    let unpainted = metrics.products.iter().find(|p| p.product_id == 2).unwrap();
+    assert_eq!(unpainted.class.as_deref(), Some("sin_pintura"));
+    assert_eq!(unpainted.station_times[1], (Duration::ZERO, Duration::ZERO));
+    let (entry, exit) = unpainted.station_times[2];
+    assert!(exit - entry >= Duration::from_millis(10));
+    assert!(exit - entry < Duration::from_millis(30));
+
+    assert_eq!(metrics.station_reports[1].products_processed, 1);
+    assert_eq!(metrics.station_reports[2].products_processed, 2);
+}
+
+#[test]
+fn test_reentrant_route_visits_station_twice() {
+    let stations = vec![
+        StationConfig::new("Litografía", Duration::from_millis(20)),
+        StationConfig::new("Grabado", Duration::from_millis(20)),
+        StationConfig::new("Inspección", Duration::from_millis(20)),
+    ];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO, Duration::from_millis(10), Duration::from_millis(20)],
+    );
+    simulation
+        .register_product_type(ProductType::new("oblea").with_route(vec![0, 1, 0, 2]))
+        .set_product_type(1, "oblea")
+        .set_product_type(3, "oblea");
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert_eq!(metrics.products.len(), 3);
+    assert!(metrics.incomplete_products.is_empty());
+    let wafer = metrics.products.iter().find(|p| p.product_id == 1).unwrap();
+    let visited: Vec<usize> = wafer.visit_times.iter().map(|&(station, _, _)| station).collect();
+    assert_eq!(visited, vec![0, 1, 0, 2]);
+    for pair in wafer.visit_times.windows(2) {
+        assert!(pair[1].1 >= pair[0].2, "Cada visita debe comenzar tras la anterior");
+    }
+    // La estación 0 resume la primera entrada y la última salida
+    assert_eq!(wafer.station_times[0], (wafer.visit_times[0].1, wafer.visit_times[2].2));
+    assert_eq!(wafer.service_time, Duration::from_millis(80));
+
+    assert_eq!(metrics.station_reports[0].products_processed, 5);
+    assert_eq!(metrics.station_reports[1].products_processed, 3);
+    assert_eq!(metrics.station_reports[2].products_processed, 3);
+}
+
+#[test]
+fn test_shared_resource_serializes_stations() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(50)),
+        StationConfig::new("Empaque", Duration::from_millis(50)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    simulation.add_shared_resource(SharedResource::new("grua", 1).used_by(0).used_by(1));
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert_eq!(metrics.products.len(), 3);
+    // Con una sola grúa las seis porciones se ejecutan una tras otra
+    assert!(metrics.total_simulation_time >= Duration::from_millis(300));
+    assert!(metrics.products.iter().any(|p| p.resource_wait_time >= Duration::from_millis(40)));
+    assert!(metrics.average_resource_wait_time > Duration::ZERO);
+    assert!(metrics.station_reports.iter().any(|r| !r.blocked_time.is_zero()));
+    for product in &metrics.products {
+        let (_, first_exit) = product.station_times[0];
+        let (second_entry, _) = product.station_times[1];
+        // El traslado por el canal entre estaciones no cuenta como espera
+        let transfer: Duration = product.timeline.iter()
+            .filter(|span| span.event == TimelineEvent::Transfer)
+            .map(|span| span.duration())
+            .sum();
+        assert!(product.total_wait_time + product.resource_wait_time + transfer >= second_entry - first_exit);
+    }
+    assert!(simulation
+        .generate_report(&metrics)
+        .contains("Tiempo promedio esperando recursos compartidos"));
+}
+
+#[test]
+fn test_no_work_model_runs_instantly() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_secs(5)),
+        StationConfig::new("Empaque", Duration::from_secs(5)),
+    ];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::round_robin(Duration::from_secs(1)),
+        vec![Duration::ZERO; 5],
+    );
+    simulation.set_work_model(NoWork);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert_eq!(metrics.products.len(), 5);
+    assert!(metrics.total_simulation_time < Duration::from_secs(1));
+    assert_eq!(metrics.station_reports[0].slices_executed, 25);
+}
+
+/// Modelo de trabajo que falla al procesar, para simular una estación rota
+/// Modelo de trabajo que duerme 5 ms más que la porción nominal.
+#[derive(Debug)]
+struct LateWork;
+
+impl WorkModel for LateWork {
+    fn perform(&self, slice: Duration) {
+        std::thread::sleep(slice + Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn test_slice_drift_is_measured_per_station() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Empaque", Duration::from_millis(10)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(Duration::from_millis(5)), vec![Duration::ZERO; 2]);
+    simulation.clear_sinks().set_work_model(LateWork);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    for station in &metrics.station_reports {
+        // Dos porciones de 5 ms por producto, cada una con 5 ms de retraso
+        assert_eq!(station.slices_executed, 4);
+        assert!(station.work_overrun >= Duration::from_millis(20), "{:?}", station);
+        assert!(station.slice_drift >= station.work_overrun);
+        assert!(station.max_slice_drift >= Duration::from_millis(5));
+        assert!(station.drift_ratio() > 0.3);
+    }
+    let report = simulation.generate_report(&metrics);
+    assert!(report.contains("=== PERFIL DE EJECUCIÓN ==="), "{}", report);
+}
+
+#[test]
+fn test_cpu_burn_reports_requested_and_measured_slice_time() {
+    use assembly_line_simulator::CpuBurn;
+
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Empaque", Duration::from_millis(10)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(Duration::from_millis(5)), vec![Duration::ZERO; 2]);
+    simulation.clear_sinks().set_work_model(CpuBurn::calibrated());
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    for station in &metrics.station_reports {
+        assert_eq!(station.slices_executed, 4);
+        assert_eq!(station.slice_time, Duration::from_millis(20));
+        assert_eq!(station.measured_slice_time(), station.slice_time + station.slice_drift);
+        assert!(station.slice_stretch() >= 1.0);
+    }
+    let report = simulation.generate_report(&metrics);
+    if metrics.station_reports.iter().any(|station| !station.slice_drift.is_zero()) {
+        assert!(report.contains("Solicitado"), "{}", report);
+    }
+}
+
+#[derive(Debug)]
+struct BrokenWork;
+
+impl WorkModel for BrokenWork {
+    fn perform(&self, _slice: Duration) {
+        panic!("Falla del equipo");
+    }
+}
+
+#[test]
+fn test_station_panic_returns_error_with_partial_metrics() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO, Duration::from_millis(10)],
+    );
+    simulation.set_work_model(BrokenWork);
+
+    match simulation.run() {
+        Err(SimulationError::StationPanicked { station, cause, metrics }) => {
+            assert_eq!(station, "Corte");
+            assert!(cause.contains("Falla del equipo"));
+            assert!(metrics.products.is_empty());
+        }
+        other => panic!("Se esperaba StationPanicked, se obtuvo {:?}", other.map(|m| m.products.len())),
+    }
+}
+
+#[test]
+fn test_batch_runs_scenarios_in_order() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    let mut scenarios: Vec<Simulation> = (1..=4)
+        .map(|products| {
+            Simulation::with_config(stations.clone(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; products])
+        })
+        .collect();
+    scenarios[2].set_work_model(BrokenWork);
+
+    let results = BatchRunner::new().with_max_parallelism(2).run(scenarios);
+
+    assert_eq!(results.len(), 4);
+    for (index, result) in results.iter().enumerate() {
+        match result {
+            Ok(metrics) => assert_eq!(metrics.products.len(), index + 1),
+            Err(SimulationError::StationPanicked { .. }) => assert_eq!(index, 2),
+            Err(other) => panic!("Error inesperado en el escenario {}: {}", index, other),
+        }
+    }
+    assert!(results[2].is_err());
+}
+
+#[test]
+fn test_batch_reports_invalid_configs_as_errors() {
+    let scenarios = vec![
+        Simulation::with_config(
+            vec![StationConfig::new("Corte", Duration::from_millis(5))],
+            SchedulingAlgorithm::fcfs(),
+            vec![Duration::ZERO; 2],
+        ),
+        Simulation::with_config(Vec::new(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO]),
+    ];
+
+    let results = BatchRunner::new().run(scenarios);
+
+    assert_eq!(results[0].as_ref().expect("El primer escenario es válido").products.len(), 2);
+    match &results[1] {
+        Err(SimulationError::InvalidConfig { error, .. }) => assert_eq!(*error, ConfigError::NoStations),
+        other => panic!("Se esperaba InvalidConfig, se obtuvo {:?}", other.as_ref().map(|m| m.products.len())),
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_async_backend_matches_threaded_backend() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(30)),
+        StationConfig::new("Empaque", Duration::from_millis(20)),
+    ];
+    let arrivals = vec![Duration::ZERO, Duration::from_millis(10), Duration::from_millis(20)];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+
+    let threaded = simulation.run().expect("La simulación debe completarse");
+    let asynchronous = simulation.run_async().expect("La simulación asíncrona debe completarse");
+
+    assert_eq!(asynchronous.products.len(), threaded.products.len());
+    assert_eq!(asynchronous.completion_order, threaded.completion_order);
+    assert_eq!(asynchronous.station_reports.len(), threaded.station_reports.len());
+    for (a, t) in asynchronous.station_reports.iter().zip(&threaded.station_reports) {
+        assert_eq!(a.products_processed, t.products_processed);
+        assert_eq!(a.slices_executed, t.slices_executed);
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_async_backend_many_stations() {
+    let stations: Vec<StationConfig> = (0..500)
+        .map(|_| StationConfig::new("Micro", Duration::from_micros(100)))
+        .collect();
+    let simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::round_robin(Duration::from_micros(50)),
+        vec![Duration::ZERO; 20],
+    );
+
+    let metrics = simulation.run_async().expect("La simulación asíncrona debe completarse");
+    assert_eq!(metrics.products.len(), 20);
+    assert_eq!(metrics.station_reports.len(), 500);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_pooled_backend_matches_threaded_backend() {
+    let stations: Vec<StationConfig> = (0..300)
+        .map(|index| StationConfig::new(format!("Estación {}", index), Duration::from_micros(200)))
+        .collect();
+    let arrivals: Vec<Duration> = (0..8).map(Duration::from_millis).collect();
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::round_robin(Duration::from_micros(100)),
+        arrivals,
+    );
+
+    let pooled = simulation.run_pooled(4).expect("La simulación con grupo de hilos debe completarse");
+    let threaded = simulation.run().expect("La simulación debe completarse");
+
+    assert_eq!(pooled.completion_order, threaded.completion_order);
+    assert_eq!(pooled.station_reports.len(), 300);
+    for (p, t) in pooled.station_reports.iter().zip(&threaded.station_reports) {
+        assert_eq!(p.name, t.name);
+        assert_eq!(p.products_processed, t.products_processed);
+        assert_eq!(p.slices_executed, t.slices_executed);
+    }
+    assert!(pooled.products.iter().all(|product| product.turnaround_time > Duration::ZERO));
+    assert_eq!(pooled.synchronization, "canales asíncronos en un grupo de 4 hilos");
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_virtual_time_is_exact_and_fast() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_secs(100)),
+        StationConfig::new("Empaque", Duration::from_secs(200)),
+    ];
+    let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+
+    let started = std::time::Instant::now();
+    let metrics = simulation.run_virtual().expect("La simulación virtual debe completarse");
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    // Empaque es el cuello de botella: 100 s + 3 × 200 s
+    assert_eq!(metrics.total_simulation_time, Duration::from_secs(700));
+    assert_eq!(metrics.completion_order, vec![1, 2, 3]);
+    let turnarounds: Vec<_> = metrics.products.iter().map(|p| p.turnaround_time).collect();
+    assert_eq!(turnarounds, [300, 500, 700].map(Duration::from_secs).to_vec());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_reconfiguration_in_virtual_time_is_exact() {
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Horno", secs(10))];
+    let arrivals = (0..4).map(|i| secs(i * 20)).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    let scenario = r#"{"algorithm": "rr", "quantum_ms": 2000, "stations": [{"name": "Horno", "processing_time_ms": 5000}]}"#;
+    simulation.add_reconfiguration(Reconfiguration::from_json(secs(30), scenario).unwrap());
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    let turnarounds: Vec<_> = metrics.products.iter().map(|p| p.turnaround_time).collect();
+    assert_eq!(turnarounds, [10, 10, 5, 5].map(secs).to_vec());
+    // Desde la reconfiguración el horno procesa en porciones de 2 s
+    assert_eq!(metrics.products[3].slice_counts, vec![3]);
+    assert_eq!(metrics.config_epochs[1].average_turnaround_time, secs(5));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_product_size_scales_service_per_station_in_virtual_time() {
+    let secs = Duration::from_secs;
+    let stations = vec![
+        StationConfig::new("Corte", secs(10)),
+        StationConfig::new("Empaque", secs(4)).with_size_sensitivity(0.0),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+    simulation.set_product_size(1, 2.0).set_product_size(2, 0.5);
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Corte: 1 de 0 a 20 s y 2 de 20 a 25 s; Empaque: 4 s para ambos
+    let big = &metrics.products[0];
+    assert_eq!((big.service_time, big.total_wait_time, big.turnaround_time), (secs(24), secs(0), secs(24)));
+    let small = &metrics.products[1];
+    assert_eq!((small.service_time, small.total_wait_time, small.turnaround_time), (secs(9), secs(20), secs(29)));
+    assert!((small.slowdown - 29.0 / 9.0).abs() < 1e-9);
+    assert_eq!(metrics.station_reports[0].busy_time, secs(25));
+    assert_eq!(metrics.station_reports[1].busy_time, secs(8));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_lifo_discipline_in_virtual_time() {
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Horno", secs(10))];
+    let arrivals = (0..4).map(secs).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation.set_queue_discipline(0, QueueDiscipline::lifo());
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    assert_eq!(metrics.completion_order, vec![1, 4, 3, 2]);
+    let turnarounds: Vec<_> = metrics.products.iter().map(|p| p.turnaround_time).collect();
+    assert_eq!(turnarounds, [10, 39, 28, 17].map(secs).to_vec());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_reneging_in_virtual_time() {
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Caja", secs(10))];
+    let arrivals = (0..3).map(secs).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation.set_patience(secs(15));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // El tercero llega a los 2 s y a los 17 s aún no fue atendido
+    assert_eq!(metrics.completion_order, vec![1, 2]);
+    let lost = &metrics.lost_products[0];
+    assert_eq!(lost.product_id, 3);
+    assert_eq!(lost.loss.map(|loss| (loss.reason, loss.at)), Some((LossReason::Reneged, secs(17))));
+    assert_eq!(lost.turnaround_time, secs(15));
+    assert_eq!(lost.total_wait_time, secs(15));
+    assert_eq!(metrics.station_reports[0].reneged, 1);
+    assert_eq!(metrics.total_simulation_time, secs(20));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_takt_time_flags_periods_behind_pace() {
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Ensamble", secs(10))];
+    let arrivals = vec![secs(0), secs(0), secs(30), secs(45)];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation
+        .set_report_periods(vec![
+            ArrivalPeriod::new("Mañana", secs(0), secs(30), secs(15)),
+            ArrivalPeriod::new("Tarde", secs(30), secs(60), secs(15)),
+        ])
+        .set_takt_time(secs(12));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Salidas a los 10, 20, 40 y 55 s
+    let takt = metrics.takt.as_ref().expect("Debe haber comparación con el takt");
+    assert_eq!(takt.average_cycle_time, Some(secs(15)));
+    assert!(!takt.on_pace());
+    let late: Vec<_> = takt.late_intervals.iter().map(|late| (late.start, late.end, late.excess)).collect();
+    assert_eq!(late, vec![(secs(20), secs(40), secs(8)), (secs(40), secs(55), secs(3))]);
+
+    let (morning, afternoon) = (&takt.periods[0], &takt.periods[1]);
+    assert_eq!((morning.departures, morning.average_cycle_time, morning.behind), (2, Some(secs(10)), false));
+    assert_eq!(
+        (afternoon.departures, afternoon.average_cycle_time, afternoon.behind),
+        (2, Some(Duration::from_millis(17_500)), true)
+    );
+    let report = simulation.generate_report(&metrics);
+    assert!(report.contains("=== TAKT TIME ==="));
+    assert!(report.contains("ATRASADA"));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_ramp_up_counts_as_service_not_wait() {
+    use assembly_line_simulator::RampUp;
+
+    let secs = Duration::from_secs;
+    let stations = vec![
+        StationConfig::new("Montaje", secs(10)),
+        StationConfig::new("Empaque", secs(5)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    simulation.set_ramp_up(0, RampUp::new(2, 3.0));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Montaje tarda 30 s, 20 s y 10 s; Empaque no tiene curva
+    let summary: Vec<_> = metrics.products
+        .iter()
+        .map(|product| (product.service_time, product.total_wait_time, product.turnaround_time))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(secs(35), secs(0), secs(35)), (secs(25), secs(30), secs(55)), (secs(15), secs(50), secs(65))]
+    );
+    assert_eq!(metrics.products[0].slowdown, 1.0);
+    assert_eq!(metrics.station_reports[0].busy_time, secs(60));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_warm_up_after_long_idle_gap() {
+    use assembly_line_simulator::WarmUp;
+
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Horno", secs(10))];
+    let arrivals = vec![secs(0), secs(20), secs(100)];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation.set_warm_up(0, WarmUp::new(secs(30), secs(5)));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Solo el tercero encuentra el horno frío (70 s ociosa) y espera el calentamiento
+    let waits: Vec<_> = metrics.products.iter().map(|product| product.total_wait_time).collect();
+    assert_eq!(waits, vec![secs(0), secs(0), secs(5)]);
+    let report = &metrics.station_reports[0];
+    assert_eq!(report.warm_ups, 1);
+    assert_eq!(report.warm_up_time, secs(5));
+    assert_eq!(report.busy_time, secs(35));
+    assert!(simulation.generate_report(&metrics).contains("=== CALENTAMIENTOS ==="));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_scrapped_products_are_replaced_up_to_limit() {
+    use assembly_line_simulator::ScrapPolicy;
+
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Horno", secs(10))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    simulation
+        .set_patience(secs(5))
+        .set_scrap_policy(ScrapPolicy::replace(1));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Los productos 2 y 3 abandonan la cola y se reponen al detectarse (t=10 s);
+    // el reemplazo 5 también abandona, pero su pedido ya agotó los reemplazos
+    let lost: Vec<_> = metrics.lost_products
+        .iter()
+        .map(|product| (product.product_id, product.replaced_by))
+        .collect();
+    assert_eq!(lost, vec![(2, Some(4)), (3, Some(5)), (5, None)]);
+    let completed: Vec<_> = metrics.products
+        .iter()
+        .map(|product| (product.product_id, product.replaces, product.arrival_time))
+        .collect();
+    assert_eq!(completed, vec![(1, None, secs(0)), (4, Some(2), secs(10))]);
+    assert_eq!(metrics.total_simulation_time, secs(20));
+    assert!(simulation.generate_report(&metrics).contains("reemplazado por #04"));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_compact_storage_matches_full_storage() {
+    use assembly_line_simulator::metrics::SimulationMetrics;
+    use assembly_line_simulator::{EventTrace, StorageMode};
+
+    let secs = Duration::from_secs;
+    let stations = vec![
+        StationConfig::new("Corte", secs(10)),
+        StationConfig::new("Ensamble", secs(6)),
+    ];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::round_robin(secs(4)),
+        vec![Duration::ZERO; 6],
+    );
+    simulation
+        .set_patience(secs(9))
+        .add_shared_resource(SharedResource::new("operario", 1).used_by(1));
+    let full = simulation.run_virtual().expect("La simulación debe completarse");
+    simulation.set_storage_mode(StorageMode::Compact);
+    let compact = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Hay porciones interrumpidas, unidades de recursos y abandonos de cola
+    assert!(!full.lost_products.is_empty());
+    assert!(full.products.iter().any(|product| product.slices.len() > 2));
+    assert_eq!(EventTrace::from_metrics(&compact), EventTrace::from_metrics(&full));
+    let products = |metrics: &SimulationMetrics| {
+        metrics.products
+            .iter()
+            .chain(&metrics.lost_products)
+            .map(|product| (product.slices.clone(), product.slice_units.clone(), product.timeline.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(products(&compact), products(&full));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_genealogy_links_replacements_components_and_operators() {
+    use assembly_line_simulator::{Genealogy, Relation, ScrapPolicy};
+
+    let secs = Duration::from_secs;
+    let stations = vec![
+        StationConfig::new("Corte", secs(10)),
+        StationConfig::new("Ensamble", secs(10)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    simulation
+        .set_patience(secs(15))
+        .set_scrap_policy(ScrapPolicy::replace(1))
+        .add_dependency(ProductDependency { product: 2, depends_on: 1, station: 0 })
+        .add_shared_resource(SharedResource::new("operario", 1).used_by(1));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    let genealogy = Genealogy::build(&metrics);
+    let finished: Vec<usize> = genealogy.trees.iter().map(|tree| tree.product_id).collect();
+    assert_eq!(finished, metrics.products.iter().map(|product| product.product_id).collect::<Vec<_>>());
+
+    // El producto 2 se ensambla con el 1 como componente y usa al operario
+    let tree = genealogy.tree(2).expect("El producto 2 debe completarse");
+    assert_eq!(tree.related[0].product_id, 1);
+    assert_eq!(tree.related[0].relation, Some(Relation::Component { station: 0 }));
+    assert_eq!(tree.visits[1].station_name, "Ensamble");
+    assert_eq!(tree.visits[1].resources, vec!["operario".to_string()]);
+    assert!(tree.visits[0].resources.is_empty());
+
+    // El producto 3 abandona la cola y su reemplazo lo referencia
+    let replacement = metrics.lost_products[0].replaced_by.expect("El producto perdido debe reponerse");
+    let tree = genealogy.tree(replacement).expect("El reemplazo debe completarse");
+    assert_eq!(tree.related_ids(), vec![replacement, 3]);
+    assert_eq!(tree.related[0].relation, Some(Relation::Replaces));
+    assert!(tree.related[0].loss.is_some() && tree.related[0].departure.is_none());
+    assert!(genealogy.to_json().contains("\"resources\":[\"operario\"]"));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_resource_units_record_slice_assignments() {
+    let secs = Duration::from_secs;
+    let stations = vec![
+        StationConfig::new("Corte", secs(10)),
+        StationConfig::new("Empaque", secs(10)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    simulation
+        .add_shared_resource(SharedResource::new("operario", 2).used_by(0).used_by(1))
+        .add_shared_resource(SharedResource::new("grua", 2).used_by(1));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Cada porción registra una unidad de cada recurso que usó su estación
+    for product in &metrics.products {
+        assert_eq!(product.slice_units.len(), product.slices.len());
+        assert_eq!(product.slice_units[0].len(), 1);
+        assert_eq!(product.slice_units[1].len(), 2);
+    }
+    let units: Vec<_> = metrics.resource_units
+        .iter()
+        .map(|unit| (unit.resource.as_str(), unit.unit, unit.slices))
+        .collect();
+    // Las dos estaciones trabajan en paralelo y ocupan ambos operarios; la
+    // grúa solo se usa en Empaque, por lo que la segunda unidad queda ociosa
+    assert_eq!(&units[..2], &[("operario", 0, 4), ("operario", 1, 2)]);
+    assert_eq!(&units[2..], &[("grua", 0, 3), ("grua", 1, 0)]);
+    assert_eq!(metrics.resource_units[2].busy_time, secs(30));
+    assert!((metrics.resource_units[2].utilization - 0.75).abs() < 1e-9);
+
+    let report = simulation.generate_report(&metrics);
+    assert!(report.contains("=== UNIDADES DE RECURSOS ==="));
+    assert!(report.contains("grua #2: 0 porciones"));
+    let json = MetricsCalculator::new().generate_json_report(&metrics);
+    assert!(json.contains("\"resource_units\":[{\"resource\":\"operario\",\"unit\":0,"));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_lookahead_release_order_reduces_makespan() {
+    use assembly_line_simulator::{ArrivalOrder, ProductType};
+
+    let secs = Duration::from_secs;
+    let stations = vec![
+        StationConfig::new("Corte", secs(5)),
+        StationConfig::new("Empaque", secs(5)),
+    ];
+    let mut local = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    local
+        .register_product_type(ProductType::new("A").with_service_time(0, secs(8)).with_service_time(1, secs(2)))
+        .register_product_type(ProductType::new("B").with_service_time(0, secs(3)).with_service_time(1, secs(6)))
+        .register_product_type(ProductType::new("C").with_service_time(0, secs(1)).with_service_time(1, secs(4)))
+        .register_product_type(ProductType::new("D").with_service_time(0, secs(6)).with_service_time(1, secs(5)));
+    for (id, name) in ["A", "B", "C", "D"].into_iter().enumerate() {
+        local.set_product_type(id + 1, name);
+    }
+    let global = local.clone_with(|simulation| {
+        simulation.set_arrival_order(ArrivalOrder::lookahead());
+    });
+    let baseline = local.run_virtual().expect("La simulación debe completarse");
+    let metrics = global.run_virtual().expect("La simulación debe completarse");
+
+    // Johnson: primero C y B (más cortos en Corte), luego D y A
+    let plan = metrics.release_plan.clone().expect("Debe haber un plan de liberación");
+    assert_eq!(plan.order, vec![3, 2, 4, 1]);
+    assert_eq!(plan.baseline_makespan, baseline.total_simulation_time);
+    assert_eq!(plan.planned_makespan, metrics.total_simulation_time);
+    assert_eq!(metrics.total_simulation_time, secs(20));
+    assert_eq!(baseline.total_simulation_time, secs(26));
+    assert!(baseline.release_plan.is_none());
+    assert!(global.generate_report(&metrics).contains("-23.1% frente a decisiones locales"));
+}
+
+#[test]
+fn test_johnson_sequence_beats_fcfs_on_three_stations() {
+    use assembly_line_simulator::{FlowShop, ProductType, SequencingComparison};
+
+    let ms = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", ms(10)),
+        StationConfig::new("Soldadura", ms(10)),
+        StationConfig::new("Empaque", ms(10)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+    simulation
+        .register_product_type(
+            ProductType::new("X").with_service_time(0, ms(60)).with_service_time(1, ms(10)).with_service_time(2, ms(10)),
+        )
+        .register_product_type(
+            ProductType::new("Y").with_service_time(0, ms(30)).with_service_time(1, ms(10)).with_service_time(2, ms(70)),
+        )
+        .set_product_type(1, "X")
+        .set_product_type(2, "Y");
+
+    // Soldadura nunca es el cuello de botella: la extensión de Johnson es óptima
+    let shop = FlowShop::from_simulation(&simulation);
+    assert_eq!(shop.stations(), 3);
+    assert!(shop.is_optimal());
+    assert_eq!(shop.johnson(), vec![1, 0]);
+
+    let comparison = SequencingComparison::run(&simulation).expect("Las simulaciones deben completarse");
+    assert_eq!(comparison.order, vec![2, 1]);
+    assert_eq!(comparison.predicted_fcfs_makespan, ms(170));
+    assert_eq!(comparison.predicted_johnson_makespan, ms(120));
+    assert!(comparison.fcfs_makespan >= ms(170));
+    assert!(comparison.johnson_makespan < comparison.fcfs_makespan);
+    assert!(comparison.improvement() > 0.0);
+    assert!(comparison.to_string().contains("SECUENCIACIÓN DE JOHNSON (óptima)"));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_metrics_are_segmented_by_class() {
+    use assembly_line_simulator::ProductType;
+
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Corte", secs(10))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    simulation
+        .register_product_type(ProductType::new("grande"))
+        .register_product_type(ProductType::new("chico").with_service_time(0, secs(5)))
+        .set_product_type(1, "grande")
+        .set_product_type(2, "chico")
+        .set_product_type(3, "grande");
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Grande: esperas 0 y 15 s, turnaround 10 y 25 s; chico: espera 10 s, turnaround 15 s
+    assert_eq!(metrics.by_class.len(), 2);
+    let large = &metrics.by_class["grande"];
+    assert_eq!(large.completed, 2);
+    assert_eq!(large.average_wait_time, Duration::from_millis(7500));
+    assert_eq!(large.average_turnaround_time, Duration::from_millis(17500));
+    assert!((large.max_slowdown - 2.5).abs() < 1e-9);
+    assert!((large.throughput - 2.0 / 25.0).abs() < 1e-9);
+    let small = &metrics.by_class["chico"];
+    assert_eq!(small.completed, 1);
+    assert_eq!(small.average_turnaround_time, secs(15));
+    assert!((small.average_slowdown - 3.0).abs() < 1e-9);
+
+    let calculator = MetricsCalculator::new();
+    assert!(simulation.generate_report(&metrics).contains("=== MÉTRICAS POR CLASE ==="));
+    let csv = calculator.generate_class_csv_report(&metrics);
+    let rows: Vec<&str> = csv.lines().filter(|line| !line.starts_with('#')).collect();
+    assert_eq!(rows.len(), 3);
+    assert!(rows[1].starts_with("chico,1,0,0,"));
+    assert!(rows[2].starts_with("grande,2,0,0,"));
+}
+
+#[test]
+fn test_replications_report_mean_and_confidence_interval() {
+    use assembly_line_simulator::Replications;
+
+    let ms = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", ms(4)),
+        StationConfig::new("Empaque", ms(2)),
+    ];
+    let arrivals = (0..5).map(|i| ms(i * 3)).collect();
+    let simulation = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(ms(3)), arrivals);
+
+    let replications = Replications::run(&simulation, 3..=6).unwrap();
+    assert_eq!(replications.seeds, vec![3, 4, 5, 6]);
+    assert!(replications.metrics.iter().all(|metrics| metrics.products.len() == 5));
+
+    let statistics = replications.statistics();
+    let makespan = statistics.iter().find(|statistic| statistic.name == "Makespan").unwrap();
+    let runs: Vec<f64> = replications.metrics.iter().map(|metrics| metrics.total_simulation_time.as_secs_f64()).collect();
+    assert!((makespan.mean - runs.iter().sum::<f64>() / 4.0).abs() < 1e-9);
+    assert!(makespan.half_width.is_finite() && makespan.half_width >= 0.0);
+
+    let report = replications.report();
+    assert!(report.starts_with("=== RÉPLICAS (n = 4, semillas 3-6, IC 95%) ==="));
+    assert_eq!(report.lines().filter(|line| line.contains(" ± ")).count(), statistics.len());
+
+    let narrower = replications.clone().with_confidence(0.80).statistics();
+    assert!(narrower[2].half_width <= makespan.half_width);
+
+    let markdown = replications.to_markdown();
+    assert!(markdown.starts_with("## RÉPLICAS (n = 4, semillas 3-6, IC 95%)"));
+    assert!(markdown.contains("| Makespan | "));
+    let html = replications.to_html();
+    assert!(html.contains("<th>Media ± IC 95%</th>"));
+    assert_eq!(html.matches("<tr><td>").count(), statistics.len());
+
+    // Con una sola réplica no hay intervalo
+    let single = Replications::from_runs(vec![(9, replications.metrics[0].clone())]);
+    assert!(single.report().contains("± ∞"));
+}
+
+#[test]
+fn test_metrics_diff_reports_configuration_changes() {
+    use assembly_line_simulator::{diff_metrics, ArrivalOrder, ScenarioChange, ScenarioDiff};
+
+    let ms = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", ms(6)),
+        StationConfig::new("Empaque", ms(3)),
+    ];
+    let mut base = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![ms(0), ms(2), ms(4)]);
+    let mut variant = base.clone_with(|simulation| {
+        simulation
+            .set_station_algorithm(1, SchedulingAlgorithm::round_robin(ms(1)))
+            .set_arrival_order(ArrivalOrder::shuffled(7));
+    });
+
+    let changes = ScenarioDiff::compare(&base, &variant);
+    assert_eq!(changes.changes.len(), 2);
+    assert_eq!(
+        changes.changes[0],
+        ScenarioChange::StationAlgorithm {
+            station: 1,
+            a: SchedulingAlgorithm::fcfs(),
+            b: SchedulingAlgorithm::round_robin(ms(1)),
+        }
+    );
+
+    let diff = diff_metrics(&base.run().unwrap(), &variant.run().unwrap()).with_scenario(changes);
+    let report = diff.to_string();
+    assert!(report.starts_with("=== CAMBIOS DE CONFIGURACIÓN (a -> b) ===\n"), "{}", report);
+    assert!(report.contains("Algoritmo de la estación 2: FCFS -> Round Robin (quantum 1 ms)"), "{}", report);
+    assert!(report.contains("Orden de llegada: orden dado -> aleatorio (semilla 7)"), "{}", report);
+    assert!(report.find("CAMBIOS").unwrap() < report.find("=== DIFERENCIAS").unwrap());
+}
+
+#[test]
+fn test_scenario_files_detect_format_by_extension() {
+    use assembly_line_simulator::Scenario;
+
+    let directory = std::env::temp_dir().join(format!("simulador-escenarios-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let files = [
+        ("linea.json", r#"{"algorithm": "fcfs", "stations": [{"name": "Corte", "processing_time_ms": 5}], "arrivals_ms": [0, 2]}"#),
+        ("linea.yml", "algorithm: fcfs\nstations:\n  - name: Corte\n    processing_time_ms: 5\narrivals_ms: [0, 2]\n"),
+        ("linea.ron", r#"(algorithm: "fcfs", stations: [(name: "Corte", processing_time_ms: 5)], arrivals_ms: [0, 2])"#),
+        ("linea.toml", "algorithm = \"fcfs\"\narrivals_ms = [0, 2]\n\n[[stations]]\nname = \"Corte\"\nprocessing_time_ms = 5\n"),
+    ];
+
+    for (name, text) in files {
+        let path = directory.join(name);
+        std::fs::write(&path, text).unwrap();
+        let scenario = Scenario::from_file(&path).unwrap_or_else(|error| panic!("{}: {}", name, error));
+        assert_eq!(scenario.stations[0].name, "Corte");
+        assert_eq!(scenario.arrivals, vec![Duration::ZERO, Duration::from_millis(2)]);
+        let metrics = scenario.to_simulation().run().unwrap();
+        assert_eq!(metrics.completion_order, vec![1, 2]);
+    }
+
+    // Los errores indican el archivo y la línea del problema
+    let broken = directory.join("roto.yaml");
+    std::fs::write(&broken, "algorithm: fcfs\nstations:\n  - name: [Corte\n").unwrap();
+    let error = Scenario::from_file(&broken).unwrap_err();
+    assert!(error.contains("roto.yaml") && error.contains("línea 3"), "{}", error);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_templates_render_to_every_format() {
+    use assembly_line_simulator::{Scenario, ScenarioFormat, TEMPLATES};
+
+    for template in &TEMPLATES {
+        let scenario = template.scenario();
+        assert!(!scenario.stations.is_empty(), "{} sin estaciones", template.name);
+        for format in [ScenarioFormat::Json, ScenarioFormat::Yaml, ScenarioFormat::Ron, ScenarioFormat::Toml] {
+            let rendered = template.render(format);
+            let parsed = match format {
+                ScenarioFormat::Json => Scenario::from_json(&rendered),
+                ScenarioFormat::Yaml => Scenario::from_yaml(&rendered),
+                ScenarioFormat::Ron => Scenario::from_ron(&rendered),
+                ScenarioFormat::Toml => Scenario::from_toml(&rendered),
+            }
+            .unwrap_or_else(|error| panic!("{} en {:?}: {}", template.name, format, error));
+            assert_eq!(parsed.algorithm, scenario.algorithm);
+            assert_eq!(parsed.arrivals, scenario.arrivals);
+            assert_eq!(parsed.stations.len(), scenario.stations.len());
+        }
+    }
+}
+
+#[test]
+fn test_parallel_simulations_keep_their_own_clock_format() {
+    use assembly_line_simulator::ClockFormat;
+    use std::thread;
+
+    let run = |format: ClockFormat| {
+        thread::spawn(move || {
+            let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+            let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+            simulation.set_clock_format(format);
+            let metrics = simulation.run().expect("La simulación falló");
+            (simulation.generate_report(&metrics), simulation.generate_csv_report(&metrics))
+        })
+    };
+    let (millis, clock) = (run(ClockFormat::Millis), run(ClockFormat::Clock));
+    let (millis_report, millis_csv) = millis.join().expect("La simulación en ms falló");
+    let (clock_report, clock_csv) = clock.join().expect("La simulación en hh:mm:ss falló");
+
+    assert!(millis_report.contains("ms"));
+    assert!(!millis_report.contains("00:00:00."));
+    assert!(clock_report.contains("00:00:00."));
+    let first_row = |csv: &str| csv.lines().find(|line| line.starts_with("1,")).map(str::to_string);
+    assert!(!first_row(&millis_csv).expect("Falta la fila del producto 1").contains(':'));
+    assert!(first_row(&clock_csv).expect("Falta la fila del producto 1").contains("00:00:00."));
+    // El formato global, que usan las demás simulaciones, no cambia
+    assert_eq!(ClockFormat::global(), ClockFormat::default());
+}
+
+#[test]
+fn test_parallel_simulations_keep_their_own_tick_length() {
+    use assembly_line_simulator::{ClockFormat, SimTime};
+    use std::thread;
+
+    let run = |tick: Duration| {
+        thread::spawn(move || {
+            let service = SimTime::from_ticks(4).to_duration(tick);
+            let stations = vec![StationConfig::new("Corte", service)];
+            let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+            simulation.set_tick_length(tick).set_clock_format(ClockFormat::Ticks);
+            let metrics = simulation.run().expect("La simulación falló");
+            let csv = simulation.generate_csv_report(&metrics);
+            (metrics, csv)
+        })
+    };
+    let (fine, coarse) = (run(Duration::from_millis(5)), run(Duration::from_millis(10)));
+    let (fine, fine_csv) = fine.join().expect("La simulación con ticks de 5 ms falló");
+    let (coarse, coarse_csv) = coarse.join().expect("La simulación con ticks de 10 ms falló");
+
+    assert_eq!(fine.tick_length, Duration::from_millis(5));
+    assert_eq!(coarse.tick_length, Duration::from_millis(10));
+    for metrics in [&fine, &coarse] {
+        assert_eq!(metrics.to_ticks(metrics.products[0].service_time), SimTime::from_ticks(4));
+        assert_eq!(metrics.from_ticks(SimTime::from_ticks(4)), metrics.products[0].service_time);
+    }
+    // El CSV de cada simulación cuenta los ticks con su propia longitud
+    let service_ticks = |csv: &str| -> u64 {
+        let row = csv.lines().find(|line| line.starts_with("1,")).expect("Falta la fila del producto 1");
+        let fields: Vec<&str> = row.split(',').collect();
+        fields[5].parse::<u64>().unwrap() - fields[4].parse::<u64>().unwrap()
+    };
+    assert!((3..=5).contains(&service_ticks(&fine_csv)));
+    assert!((3..=5).contains(&service_ticks(&coarse_csv)));
+}
+
+#[test]
+fn test_dry_run_bounds_hold_for_the_real_run() {
+    use assembly_line_simulator::ProductType;
+
+    let millis = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", millis(10)),
+        StationConfig::new("Pintura", millis(30)),
+        StationConfig::new("Empaque", millis(5)),
+    ];
+    let arrivals = (0..6).map(|i| millis(i * 5)).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(millis(10)), arrivals);
+    simulation.register_product_type(ProductType::new("panel").with_route(vec![0, 2]));
+    simulation.set_product_type(2, "panel");
+
+    let bounds = simulation.analyze().unwrap();
+    assert_eq!(bounds.products.len(), 6);
+    assert_eq!(bounds.products[1].min_turnaround, millis(15));
+    assert_eq!(bounds.stations[1].products, 5);
+    assert_eq!(bounds.stations[1].load, millis(150));
+    assert_eq!(bounds.total_service_time, millis(60 + 150 + 30));
+    assert_eq!(bounds.bottleneck, Some(1));
+
+    let metrics = simulation.run().unwrap();
+    assert!(metrics.total_simulation_time >= bounds.makespan);
+    for product in &metrics.products {
+        let bound = bounds.products.iter().find(|bound| bound.id == product.product_id).unwrap();
+        assert!(product.turnaround_time >= bound.min_turnaround);
+    }
+
+    let invalid = Simulation::with_config(Vec::new(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO]);
+    assert!(invalid.analyze().is_err());
+}
+
+#[test]
+fn test_run_metadata_is_exported_with_every_report() {
+    use assembly_line_simulator::json::Json;
+
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 2]);
+    let metrics = simulation.run().unwrap();
+    let run = &metrics.run_metadata;
+    assert_eq!(run.crate_version, env!("CARGO_PKG_VERSION"));
+    assert!(!run.hostname.is_empty());
+
+    let report = simulation.generate_report(&metrics);
+    assert!(report.contains("=== DATOS DE LA EJECUCIÓN ==="));
+    assert!(report.contains(&format!("Fecha (UTC): {}", run.timestamp_utc())));
+
+    let csv = simulation.generate_csv_report(&metrics);
+    assert!(csv.starts_with(&run.csv_preamble()));
+    assert!(csv.contains(&format!("# hostname: {}\n", run.hostname)));
+
+    let json = Json::parse(&MetricsCalculator::new().generate_json_report(&metrics)).unwrap();
+    let exported = json.get("run").unwrap();
+    assert_eq!(exported.get("crate_version").and_then(Json::as_str), Some(run.crate_version.as_str()));
+    assert_eq!(exported.get("timestamp").and_then(Json::as_str), Some(run.timestamp_utc().as_str()));
+    assert_eq!(exported.get("git_commit").and_then(Json::as_str), run.git_commit.as_deref());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_inspection_rejects_and_reworks_products_in_virtual_time() {
+    use assembly_line_simulator::{Inspection, RouteTarget, ScrapPolicy, INSPECTION_TIME};
+
+    let secs = Duration::from_secs;
+    let stations = vec![
+        StationConfig::new("Ensamblaje", secs(10)),
+        StationConfig::inspection("Control", Inspection::metadata("calidad", "ok")),
+        StationConfig::new("Empaque", secs(4)),
+    ];
+    let mut simulation = Simulation::with_config(stations.clone(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 3]);
+    simulation
+        .set_product_metadata(1, "calidad", "ok")
+        .set_product_metadata(3, "calidad", "ok");
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // El producto 2 no aprueba el control al terminar Ensamblaje (20 s) y sale de la línea
+    assert_eq!(metrics.completion_order, vec![1, 3]);
+    let lost = &metrics.lost_products[0];
+    assert_eq!(lost.product_id, 2);
+    assert_eq!(
+        lost.loss.map(|loss| (loss.station, loss.reason, loss.at)),
+        Some((1, LossReason::Rejected, secs(20) + INSPECTION_TIME))
+    );
+    assert_eq!(metrics.products[1].turnaround_time, secs(34) + INSPECTION_TIME);
+    let control = &metrics.station_reports[1];
+    assert_eq!((control.inspections_passed, control.inspections_failed, control.rejected), (2, 1, 1));
+    assert_eq!(metrics.station_reports[2].products_processed, 2);
+    assert!(MetricsCalculator::new().generate_report(&metrics, &stations).contains("Control: 3 inspecciones, 2 aprobadas"));
+
+    // Con reemplazo, el producto rechazado se repone con uno nuevo que vuelve a inspeccionarse
+    simulation.set_scrap_policy(ScrapPolicy::replace(1));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+    assert_eq!(metrics.lost_products[0].replaced_by, Some(4));
+    assert_eq!(metrics.lost_products[1].product_id, 4);
+
+    // Los productos que no aprueban vuelven a Ensamblaje; los sorteos dependen
+    // solo de la semilla y del producto, por lo que las rutas se repiten
+    let mut stations = stations;
+    stations[1] = StationConfig::inspection("Control", Inspection::pass_rate(0.5, 3).with_fail_target(RouteTarget::Station(0)));
+    let rework = || {
+        let simulation = Simulation::with_config(stations.clone(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 6]);
+        simulation.run_virtual().expect("La simulación debe completarse")
+    };
+    let (first, second) = (rework(), rework());
+    assert_eq!(first.products.len(), 6);
+    assert!(first.lost_products.is_empty());
+    let failed = first.station_reports[1].inspections_failed;
+    assert!(failed > 0);
+    assert_eq!(first.station_reports[0].products_processed, 6 + failed);
+    assert_eq!(first.station_reports[1].inspections_passed, 6);
+    assert_eq!(first.completion_order, second.completion_order);
+    assert_eq!(first.total_simulation_time, second.total_simulation_time);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_splitter_runs_parallel_branches_in_virtual_time() {
+    use assembly_line_simulator::{ConfigError, RouteTarget, Splitter};
+
+    let secs = Duration::from_secs;
+    let cabinas = vec![RouteTarget::Station(1), RouteTarget::Station(2)];
+    let stations = vec![
+        StationConfig::new("Corte", secs(2)).with_splitter(Splitter::round_robin(cabinas.clone())),
+        StationConfig::new("Pintura A", secs(10)).with_output(RouteTarget::Station(3)),
+        StationConfig::new("Pintura B", secs(10)),
+        StationConfig::new("Empaque", secs(1)),
+    ];
+    let simulation = Simulation::with_config(stations.clone(), SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Pintura B recibe el producto 2 mientras Pintura A procesa el 1, y ambas
+    // cabinas envían sus productos directamente a Empaque
+    let turnarounds: Vec<Duration> = metrics.products.iter().map(|product| product.turnaround_time).collect();
+    assert_eq!(turnarounds, vec![secs(13), secs(15), secs(23), secs(25)]);
+    assert_eq!(metrics.station_reports[0].routed, vec![2, 2]);
+    assert_eq!(metrics.station_reports[1].products_processed, 2);
+    assert_eq!(metrics.station_reports[2].products_processed, 2);
+    assert_eq!(metrics.station_reports[3].products_processed, 4);
+    assert!(MetricsCalculator::new()
+        .generate_report(&metrics, &stations)
+        .contains("Corte: 4 productos repartidos (salida 1: 2, salida 2: 2)"));
+
+    // Un divisor por metadato que devuelve productos a su propia estación nunca los dejaría salir
+    let mut invalid = stations;
+    invalid[0] = StationConfig::new("Corte", secs(2)).with_splitter(Splitter::by_metadata(
+        vec![RouteTarget::Station(0), RouteTarget::Station(2)],
+        "color",
+        [("rojo", 1)],
+    ));
+    let simulation = Simulation::with_config(invalid, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    assert!(matches!(simulation.validate(), Err(ConfigError::InvalidSplitter { station_index: 0, .. })));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_merger_interleaves_branches_and_reports_starvation() {
+    use assembly_line_simulator::{Merger, RouteTarget, Splitter};
+
+    let secs = Duration::from_secs;
+    let line = |merger: Merger| {
+        let cabinas = vec![RouteTarget::Station(1), RouteTarget::Station(2)];
+        vec![
+            StationConfig::new("Corte", secs(1)).with_splitter(Splitter::round_robin(cabinas)),
+            StationConfig::new("Pintura A", secs(1)).with_output(RouteTarget::Station(3)),
+            StationConfig::new("Pintura B", secs(1)),
+            StationConfig::new("Empaque", secs(5)).with_merger(merger),
+        ]
+    };
+    let run = |stations: Vec<StationConfig>| {
+        let simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 6]);
+        simulation.run_virtual().expect("La simulación debe completarse")
+    };
+
+    // Con prioridad para Pintura A, los productos de Pintura B esperan a que A se vacíe
+    let stations = line(Merger::priority([Some(1)]));
+    let metrics = run(stations.clone());
+    assert_eq!(metrics.completion_order, vec![1, 3, 5, 2, 4, 6]);
+    let sources = &metrics.station_reports[3].merge_sources;
+    assert_eq!(sources.iter().map(|source| (source.source, source.merged)).collect::<Vec<_>>(), vec![(Some(1), 3), (Some(2), 3)]);
+    assert_eq!((sources[0].bypassed, sources[0].max_wait), (0, secs(6)));
+    assert_eq!((sources[1].bypassed, sources[1].max_consecutive_bypassed), (2, 2));
+    assert_eq!(sources[1].max_wait, secs(20));
+    let report = MetricsCalculator::new().generate_report(&metrics, &stations);
+    assert!(report.contains("desde Pintura B: 3 productos"));
+    assert!(report.contains("omitido 2 veces (máx. 2 seguidas)"));
+
+    // Alternando, ningún origen se omite dos veces seguidas
+    let metrics = run(line(Merger::alternating()));
+    assert_eq!(metrics.completion_order, vec![1, 2, 3, 4, 5, 6]);
+    let sources = &metrics.station_reports[3].merge_sources;
+    assert!(sources.iter().all(|source| source.merged == 3 && source.max_consecutive_bypassed == 1));
+}
+
+#[test]
+fn test_topology_errors_are_reported_before_launching_threads() {
+    use assembly_line_simulator::{Inspection, ProductType, RouteTarget, TopologyNode};
+
+    let millis = Duration::from_millis;
+    let fcfs = SchedulingAlgorithm::fcfs();
+    // Pintura A salta directamente a Empaque y nada llega a Pintura B
+    let stations = vec![
+        StationConfig::new("Corte", millis(10)),
+        StationConfig::new("Pintura A", millis(10)).with_output(RouteTarget::Station(3)),
+        StationConfig::new("Pintura B", millis(10)),
+        StationConfig::new("Empaque", millis(10)),
+    ];
+    let mut simulation = Simulation::with_config(stations, fcfs.clone(), vec![Duration::ZERO; 2]);
+    let expected = ConfigError::UnreachableStation { station_index: 2, station: "Pintura B".to_string() };
+    match simulation.run() {
+        Err(SimulationError::InvalidConfig { error, metrics }) => {
+            assert_eq!(error, expected);
+            assert!(metrics.products.is_empty());
+        }
+        other => panic!("Se esperaba una configuración inválida, se obtuvo {:?}", other.map(|m| m.products.len())),
+    }
+    assert!(expected.to_string().contains("ningún producto puede llegar a la estación 3 ('Pintura B')"));
+
+    // Los aprobados vuelven a Pintura y los demás se rechazan: ningún producto termina
+    let stations = vec![
+        StationConfig::new("Corte", millis(10)),
+        StationConfig::new("Pintura", millis(10)),
+        StationConfig::inspection("Control", Inspection::pass_rate(0.5, 9).with_pass_target(RouteTarget::Station(1))),
+    ];
+    let mut simulation = Simulation::with_config(stations, fcfs, vec![Duration::ZERO; 2]);
+    assert!(matches!(simulation.validate(), Err(ConfigError::ClosedLoop { ref stations, .. }) if *stations == vec![1, 2]));
+    let topology = simulation.topology();
+    assert!(topology.edges_from(TopologyNode::Station(2)).iter().any(|edge| edge.to == TopologyNode::Rejected));
+
+    // Los tipos asignados a los productos deben estar registrados
+    simulation.register_product_type(ProductType::new("corto").with_route(vec![0]));
+    simulation.set_product_type(1, "corto").set_product_type(2, "largo");
+    let error = simulation.validate().unwrap_err();
+    assert_eq!(error.to_string(), "el producto 2 usa el tipo de producto 'largo', que no está registrado");
+}
+
+#[test]
+fn test_topology_dot_describes_the_line_and_its_utilization() {
+    use assembly_line_simulator::{ChannelBackend, Inspection, RouteTarget, SharedResource};
+
+    let millis = Duration::from_millis;
+    // Los productos que no aprueban el control vuelven a Ensamblaje
+    let stations = vec![
+        StationConfig::new("Ensamblaje", millis(10)),
+        StationConfig::inspection("Control", Inspection::pass_rate(0.5, 7).with_fail_target(RouteTarget::Station(0))),
+        StationConfig::new("Empaque", millis(5)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+    simulation
+        .set_station_algorithm(2, SchedulingAlgorithm::round_robin(millis(2)))
+        .set_channel_backend(ChannelBackend::Bounded { capacity: 3 })
+        .add_shared_resource(SharedResource::new("grua", 1).used_by(0).used_by_type(2, "pesado"));
+
+    let dot = simulation.export_topology_dot();
+    assert!(dot.contains("estacion_1 [label=\"Control\\n"), "{}", dot);
+    assert!(dot.contains("inspección: aprueba el 50.0% (semilla 7)\\nbuffer: 3\", shape=diamond];"), "{}", dot);
+    assert!(dot.contains("Empaque\\n0.005s · Round Robin (quantum 2 ms)"), "{}", dot);
+    assert!(dot.contains("recurso_0 [label=\"grua\\n1 unidades\", shape=component];"), "{}", dot);
+    assert!(dot.contains("recurso_0 -> estacion_2 [style=dotted, arrowhead=none, label=\"pesado\"];"), "{}", dot);
+    assert!(dot.contains("estacion_1 -> estacion_0 [label=\"no aprobados\", style=dashed, constraint=false];"), "{}", dot);
+    assert!(!dot.contains("rechazo"));
+    assert!(!dot.contains("utilización"));
+
+    let metrics = simulation.run().unwrap();
+    let dot = simulation.export_topology_dot_with_utilization(&metrics);
+    let utilization = format!("utilización: {:.1}%", metrics.station_reports[0].utilization() * 100.0);
+    assert!(dot.contains(&utilization), "{}", dot);
+    assert_eq!(dot.matches("style=filled").count(), 3);
+}
+
+#[test]
+fn test_station_harness_scripts_arrivals_without_sleeping() {
+    use assembly_line_simulator::testing::StationHarness;
+    use assembly_line_simulator::{LossReason, ProductSpec};
+
+    let millis = Duration::from_millis;
+    let mut harness = StationHarness::new(StationConfig::new("Corte", millis(40)), SchedulingAlgorithm::fcfs())
+        .configure(|station| station.with_impatience(Some(2), Some(millis(60))));
+    harness.arrive(millis(0), millis(40));
+    harness.arrive(millis(10), millis(40));
+    let rush = harness.arrive_with(millis(20), millis(40), ProductSpec::with_priority(5));
+    harness.arrive(millis(30), millis(40));
+
+    // El pedido urgente se adelanta al producto 2, que agota su paciencia
+    // mientras espera; el producto 4 encuentra la cola llena y desiste
+    let first = harness.step().unwrap();
+    assert_eq!((first.product, first.start, first.end), (1, millis(0), millis(40)));
+    assert_eq!(harness.queued(), vec![rush, 2]);
+    harness.run();
+    harness.assert_service_order(&[1, rush]);
+    assert_eq!(harness.decisions()[1].start, millis(40));
+    assert_eq!(harness.product(2).loss().map(|loss| (loss.reason, loss.at)), Some((LossReason::Reneged, millis(70))));
+    assert_eq!(harness.product(4).loss().map(|loss| loss.reason), Some(LossReason::Balked));
+    assert_eq!((harness.report().balked, harness.report().reneged), (1, 1));
+    assert_eq!(harness.clock().elapsed(), millis(80));
+}
+
+#[test]
+fn test_metrics_satisfy_invariants_across_algorithms() {
+    use assembly_line_simulator::{verify_invariants, Inspection, ProductDependency, RouteTarget, SharedResource};
+
+    let millis = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", millis(12)),
+        StationConfig::inspection("Control", Inspection::pass_rate(0.7, 3).with_fail_target(RouteTarget::Station(0))),
+        StationConfig::new("Empaque", millis(6)),
+    ];
+    let arrivals: Vec<Duration> = (0..6).map(|i| millis(i * 4)).collect();
+    for algorithm in [
+        SchedulingAlgorithm::fcfs(),
+        SchedulingAlgorithm::round_robin(millis(5)),
+        SchedulingAlgorithm::earliest_due_date(),
+    ] {
+        let mut simulation = Simulation::with_config(stations.clone(), algorithm, arrivals.clone());
+        simulation
+            .add_shared_resource(SharedResource::new("operario", 1).used_by(0).used_by(2))
+            .add_dependency(ProductDependency { product: 4, depends_on: 5, station: 0 });
+        let metrics = simulation.run().expect("La simulación debe completarse");
+        assert_eq!(verify_invariants(&metrics), Vec::new());
+        #[cfg(feature = "async")]
+        assert_eq!(verify_invariants(&simulation.run_virtual().unwrap()), Vec::new());
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_golden_trace_detects_scheduling_changes() {
+    use assembly_line_simulator::testing::assert_golden_trace;
+    use assembly_line_simulator::{EventTrace, TraceEventKind};
+
+    let millis = Duration::from_millis;
+    let dir = std::env::temp_dir().join(format!("simulador-traza-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("linea.trace");
+    let stations = vec![
+        StationConfig::new("Corte", millis(20)),
+        StationConfig::new("Empaque", millis(10)),
+    ];
+    let arrivals: Vec<Duration> = (0..4).map(|i| millis(i * 5)).collect();
+    let simulation = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(millis(15)), arrivals);
+
+    let golden = simulation.save_golden_trace(&path).expect("La traza debe guardarse");
+    assert_eq!(EventTrace::load(&path), Ok(golden.clone()));
+    assert_eq!(simulation.check_golden_trace(&path), Ok(None));
+    assert_golden_trace(&simulation, &path);
+    let exits = golden.events.iter().filter(|event| event.kind == TraceEventKind::Exited).count();
+    assert_eq!(exits, 4);
+
+    let changed = simulation.clone_with(|simulation| {
+        simulation.set_algorithm(SchedulingAlgorithm::round_robin(millis(10)));
+    });
+    let divergence = changed
+        .check_golden_trace(&path)
+        .unwrap()
+        .expect("Cambiar el quantum debe cambiar la traza");
+    assert_eq!(divergence.expected, golden.events.get(divergence.index).copied());
+    assert_eq!(divergence.context.last(), divergence.index.checked_sub(1).map(|i| &golden.events[i]));
+    let report = divergence.to_string();
+    assert!(report.starts_with(&format!("La traza diverge en el evento {}:", divergence.index + 1)));
+    assert!(report.contains("  - esperado: "));
+    assert!(report.contains("  + obtenido: "));
+    assert!(!divergence.products.is_empty());
+
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| assert_golden_trace(&changed, &path))).unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.contains("SIMULATOR_UPDATE_GOLDEN"));
+    assert!(message.contains(&report));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_timer_jitter_is_reported_apart_from_wait() {
+    use assembly_line_simulator::{verify_invariants, EventTrace, TimerJitter};
+
+    let millis = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", millis(20)),
+        StationConfig::new("Empaque", millis(10)),
+    ];
+    let arrivals: Vec<Duration> = (0..5).map(|i| millis(i * 6)).collect();
+    let base = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(millis(8)), arrivals);
+    let jittered = base.clone_with(|simulation| {
+        simulation.set_timer_jitter(0, TimerJitter::uniform(millis(5)).with_seed(3));
+    });
+
+    let nominal = base.run_virtual().unwrap();
+    let metrics = jittered.run_virtual().unwrap();
+    assert_eq!(verify_invariants(&metrics), Vec::new());
+    assert_eq!(EventTrace::from_metrics(&jittered.run_virtual().unwrap()), EventTrace::from_metrics(&metrics));
+
+    // En tiempo virtual todo el desfase es el inyectado, y solo en la estación configurada
+    let corte = &metrics.station_reports[0];
+    assert!(!corte.injected_drift.is_zero());
+    assert_eq!(corte.slice_drift, corte.injected_drift);
+    assert!(corte.max_slice_drift <= millis(5));
+    assert_eq!(corte.engine_overhead(), Duration::ZERO);
+    assert_eq!(metrics.station_reports[1].injected_drift, Duration::ZERO);
+    assert_eq!(nominal.station_reports[0].injected_drift, Duration::ZERO);
+
+    // El retraso alarga las porciones pero no se cuenta como espera
+    let timer_drift: Duration = metrics.products.iter().map(|product| product.timer_drift).sum();
+    assert_eq!(timer_drift, corte.injected_drift);
+    for product in &metrics.products {
+        assert_eq!(product.service_time, millis(30));
+        assert_eq!(product.turnaround_time, product.total_wait_time + product.service_time + product.timer_drift);
+    }
+    assert!(metrics.average_turnaround_time > nominal.average_turnaround_time);
+
+    let other_seed = base.clone_with(|simulation| {
+        simulation.set_timer_jitter(0, TimerJitter::uniform(millis(5)).with_seed(4));
+    });
+    assert_ne!(other_seed.run_virtual().unwrap().station_reports[0].injected_drift, corte.injected_drift);
+
+    let report = jittered.generate_report(&metrics);
+    assert!(report.contains("=== IMPRECISIÓN DE TEMPORIZADORES ==="), "{}", report);
+    assert!(!base.generate_report(&nominal).contains("IMPRECISIÓN DE TEMPORIZADORES"));
+}
+
+#[test]
+fn test_timer_jitter_is_performed_by_the_work_model() {
+    use assembly_line_simulator::{NoWork, TimerJitter};
+
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(20))];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::round_robin(Duration::from_millis(5)),
+        vec![Duration::ZERO; 3],
+    );
+    simulation
+        .set_work_model(NoWork)
+        .set_timer_jitter(0, TimerJitter::uniform(Duration::from_secs(1)).with_seed(2));
+
+    // Sin trabajo real el retraso inyectado no duerme al hilo de la estación
+    let started = std::time::Instant::now();
+    let metrics = simulation.run().expect("La simulación debe completarse");
+    assert!(started.elapsed() < Duration::from_secs(2), "{:?}", started.elapsed());
+    assert!(metrics.station_reports[0].injected_drift > Duration::from_secs(2));
+}
+
+#[cfg(all(feature = "affinity", target_os = "linux"))]
+#[test]
+fn test_pinned_stations_report_os_scheduling() {
+    use assembly_line_simulator::affinity::ThreadPlacement;
+
+    let millis = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", millis(10)),
+        StationConfig::new("Empaque", millis(10)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(millis(5)), vec![Duration::ZERO; 3]);
+    simulation.clear_sinks();
+    let placement = ThreadPlacement::pinned(vec![0]).with_nice(1, 5);
+    let (metrics, report) = simulation.run_with_placement(placement).expect("La simulación debe completarse");
+    assert_eq!(metrics.products.len(), 3);
+
+    assert_eq!(report.stations.len(), 2);
+    for (station, station_report) in report.stations.iter().zip(&metrics.station_reports) {
+        assert_eq!(station.errors, Vec::<String>::new());
+        assert_eq!(station.core, Some(0));
+        assert_eq!(station.cpus, vec![0]);
+        assert_eq!(station.migrations, 0);
+        assert_eq!(station.slices, 6);
+        assert_eq!(station.slices, station_report.slices_executed);
+        assert!(station.voluntary_switches > 0);
+        assert!(station.measured >= station.nominal);
+        assert!(station.nominal >= millis(29) && station.nominal <= millis(31), "{:?}", station);
+        assert!(station.stretch() >= 1.0);
+    }
+    assert_eq!(report.stations[0].nice, None);
+    assert_eq!(report.stations[1].nice, Some(5));
+    assert!(report.to_string().contains("=== PLANIFICACIÓN DEL SISTEMA OPERATIVO ==="));
+
+    // El observador de la ubicación no queda registrado para otras ejecuciones
+    let (_, unpinned) = simulation.run_with_placement(ThreadPlacement::default()).unwrap();
+    assert!(unpinned.stations.iter().all(|station| station.core.is_none() && station.errors.is_empty()));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_shuffled_arrival_order_keeps_offsets() {
+    use assembly_line_simulator::ArrivalOrder;
+
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Corte", secs(1))];
+    let arrivals: Vec<Duration> = (0..6).map(|i| secs(i * 10)).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals.clone());
+    simulation
+        .set_due_dates((1..=6).map(|i| secs(i * 100)).collect())
+        .set_arrival_order(ArrivalOrder::shuffled(11));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // Cada producto conserva su fecha de entrega y ocupa el tiempo que le asigna la permutación
+    let order = ArrivalOrder::shuffled(11).permutation(6);
+    assert_ne!(order, vec![0, 1, 2, 3, 4, 5]);
+    for product in &metrics.products {
+        let slot = order.iter().position(|&idx| idx == product.product_id - 1).unwrap();
+        assert_eq!(product.arrival_time, arrivals[slot]);
+        assert_eq!(product.due_date, Some(secs(product.product_id as u64 * 100)));
+    }
+    let first_come: Vec<usize> = order.iter().map(|idx| idx + 1).collect();
+    assert_eq!(metrics.completion_order, first_come);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_trajectories_show_round_robin_interleaving() {
+    use assembly_line_simulator::{TimelineEvent::*, Trajectories};
+
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Horno", secs(10))];
+    let states = |algorithm: SchedulingAlgorithm| {
+        let simulation = Simulation::with_config(stations.clone(), algorithm, vec![Duration::ZERO; 2]);
+        let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+        let trajectories = Trajectories::sample(&metrics, secs(5));
+        trajectories.samples
+            .iter()
+            .map(|sample| (sample.time.as_secs(), sample.product_id, sample.state))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        states(SchedulingAlgorithm::fcfs()),
+        vec![
+            (0, 1, Processing), (0, 2, Queued),
+            (5, 1, Processing), (5, 2, Queued),
+            (10, 2, Processing),
+            (15, 2, Processing),
+        ]
+    );
+    assert_eq!(
+        states(SchedulingAlgorithm::round_robin(secs(5))),
+        vec![
+            (0, 1, Preempted), (0, 2, Queued),
+            (5, 1, Queued), (5, 2, Preempted),
+            (10, 1, Processing), (10, 2, Queued),
+            (15, 2, Processing),
+        ]
+    );
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_poisson_line_matches_md1_prediction() {
+    use assembly_line_simulator::{AnalyticalBaseline, QueueModel};
+
+    let secs = Duration::from_secs;
+    let stations = vec![StationConfig::new("Prensa", secs(5))];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), Vec::new());
+    simulation.add_arrival_source(ArrivalSource::new(
+        "poisson",
+        ArrivalProcess::Exponential { mean_interarrival: secs(10), seed: 5, count: Some(3000) },
+        ProductSpec::default(),
+    ));
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    // ρ ≈ 0.5: M/D/1 predice Wq = 5 s y Lq = 0.25
+    let baseline = AnalyticalBaseline::compare(&simulation, &metrics, QueueModel::MD1);
+    let station = &baseline.stations[0];
+    assert!((baseline.arrival_rate - 0.1).abs() < 0.01, "{}", baseline.arrival_rate);
+    assert!((station.simulated_utilization - station.utilization).abs() < 0.05);
+    let (expected, simulated) = (station.expected_wait.unwrap().as_secs_f64(), station.simulated_wait.as_secs_f64());
+    assert!((simulated - expected).abs() / expected < 0.2, "Wq teórica {expected}, simulada {simulated}");
+    let expected_length = station.expected_queue_length.unwrap();
+    assert!((station.simulated_queue_length - expected_length).abs() / expected_length < 0.2);
+
+    // M/M/1 duplica la espera de M/D/1 y la simulación determinista queda por debajo
+    let exponential = AnalyticalBaseline::compare(&simulation, &metrics, QueueModel::MM1);
+    assert!(exponential.stations[0].expected_wait.unwrap() > station.simulated_wait);
+    assert!(baseline.to_string().contains("M/D/1"));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_reentrant_route_in_virtual_time() {
+    let stations = vec![
+        StationConfig::new("Litografía", Duration::from_secs(1)),
+        StationConfig::new("Grabado", Duration::from_secs(2)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO]);
+    simulation
+        .register_product_type(ProductType::new("oblea").with_route(vec![0, 1, 0, 1]))
+        .set_product_type(1, "oblea");
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    let wafer = &metrics.products[0];
+    let secs = Duration::from_secs;
+    assert_eq!(
+        wafer.visit_times,
+        vec![(0, secs(0), secs(1)), (1, secs(1), secs(3)), (0, secs(3), secs(4)), (1, secs(4), secs(6))]
+    );
+    assert_eq!(wafer.turnaround_time, secs(6));
+    assert_eq!(wafer.total_wait_time, Duration::ZERO);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_timeline_shows_round_robin_slices_and_transfers() {
+    let millis = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", millis(20)),
+        StationConfig::new("Empaque", millis(10)),
+    ];
+    let simulation = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(millis(10)), vec![Duration::ZERO; 2]);
+    let metrics = simulation.run_virtual().expect("La simulación debe completarse");
+
+    let spans: Vec<_> = metrics.products[0]
+        .timeline
+        .iter()
+        .map(|span| (span.station, span.event, span.start, span.end))
+        .collect();
+    // El producto 2 toma la estación entre las dos porciones del producto 1
+    assert_eq!(
+        spans,
+        vec![
+            (0, TimelineEvent::Queued, millis(0), millis(0)),
+            (0, TimelineEvent::Preempted, millis(0), millis(10)),
+            (0, TimelineEvent::Queued, millis(10), millis(20)),
+            (0, TimelineEvent::Processing, millis(20), millis(30)),
+            (1, TimelineEvent::Transfer, millis(30), millis(30)),
+            (1, TimelineEvent::Queued, millis(30), millis(30)),
+            (1, TimelineEvent::Processing, millis(30), millis(40)),
+        ]
+    );
+    let waited: Duration = metrics.products[0]
+        .timeline
+        .iter()
+        .filter(|span| span.event == TimelineEvent::Queued)
+        .map(|span| span.duration())
+        .sum();
+    assert_eq!(waited, metrics.products[0].total_wait_time);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_quantum_optimizer_recommends_minimum_of_curve() {
+    use assembly_line_simulator::{QuantumObjective, QuantumOptimizer};
+
+    let millis = Duration::from_millis;
+    let stations = vec![
+        StationConfig::new("Corte", millis(50)),
+        StationConfig::new("Empaque", millis(30)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 4]);
+
+    let recommendation = QuantumOptimizer::grid(millis(10), millis(60), millis(10))
+        .minimizing(QuantumObjective::AverageTurnaround)
+        .optimize(&mut simulation)
+        .expect("La búsqueda debe completarse");
+
+    assert_eq!(recommendation.curve.len(), 6);
+    assert!(recommendation.curve.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    let best = recommendation.curve.iter().map(|&(_, value)| value).min().unwrap();
+    assert_eq!(recommendation.value, best);
+    assert!(recommendation.curve.contains(&(recommendation.quantum, recommendation.value)));
+
+    // La curva se obtiene en tiempo virtual: repetir la búsqueda da el mismo resultado
+    let again = QuantumOptimizer::grid(millis(10), millis(60), millis(10))
+        .minimizing(QuantumObjective::AverageTurnaround)
+        .optimize(&mut simulation)
+        .unwrap();
+    assert_eq!(again, recommendation);
+}
+
+#[test]
+fn test_bounded_channels_block_upstream_station() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Pintura", Duration::from_millis(60)),
+    ];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO; 6],
+    );
+    simulation.set_channel_backend(ChannelBackend::Bounded { capacity: 1 });
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert_eq!(metrics.products.len(), 6);
+    assert_eq!(metrics.completion_order, vec![1, 2, 3, 4, 5, 6]);
+    // La estación rápida debe esperar a que la lenta libere espacio en su buffer
+    assert!(metrics.station_reports[0].blocked_time >= Duration::from_millis(60));
+    assert!(metrics.station_reports[1].blocked_time.is_zero());
+}
+
+#[test]
+fn test_semaphore_channels_match_bounded_channels() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Pintura", Duration::from_millis(60)),
+    ];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO; 6],
+    );
+    simulation.set_channel_backend(ChannelBackend::Semaphore { capacity: 1 });
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert_eq!(metrics.completion_order, vec![1, 2, 3, 4, 5, 6]);
+    assert!(metrics.station_reports[0].blocked_time >= Duration::from_millis(60));
+    assert_eq!(metrics.synchronization, "buffer con semáforos contadores (capacidad 1)");
+    assert!(simulation
+        .generate_report(&metrics)
+        .contains("Sincronización entre estaciones: buffer con semáforos contadores (capacidad 1)"));
+}
+
+#[cfg(feature = "crossbeam")]
+#[test]
+fn test_crossbeam_channels_block_and_wake_idle_stations_with_commands() {
+    use assembly_line_simulator::StationCommand;
+
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Pintura", Duration::from_millis(60)),
+    ];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO; 6],
+    );
+    simulation.set_channel_backend(ChannelBackend::Crossbeam { capacity: 1 });
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    assert_eq!(metrics.completion_order, vec![1, 2, 3, 4, 5, 6]);
+    assert!(metrics.station_reports[0].blocked_time >= Duration::from_millis(60));
+    assert_eq!(metrics.synchronization, "canales crossbeam acotados (capacidad 1)");
+
+    // La estación ociosa recibe el comando mientras espera productos y el
+    // segundo producto ya se procesa con el nuevo tiempo
+    let stations = vec![StationConfig::new("Horno", Duration::from_millis(20))];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO, Duration::from_millis(150)],
+    );
+    simulation.set_channel_backend(ChannelBackend::Crossbeam { capacity: 1 });
+    let handle = simulation.start();
+    std::thread::sleep(Duration::from_millis(60));
+    handle.control().send(0, StationCommand::SetProcessingTime(Duration::from_millis(5))).unwrap();
+    let metrics = handle.wait().expect("La simulación debe completarse");
+
+    let (_, start, end) = metrics.products[1].slices[0];
+    assert!(end - start >= Duration::from_millis(5));
+    assert!(end - start < Duration::from_millis(20));
+}
+
+#[test]
+fn test_deadlock_is_reported_instead_of_hanging() {
+    // La última estación recircula hacia la primera: con buffers de un solo
+    // lugar ambas terminan esperando espacio en el buffer de la otra
+    let stations = vec![
+        StationConfig::new("Litografía", Duration::from_millis(10)),
+        StationConfig::new("Grabado", Duration::from_millis(30)),
+    ];
+    let arrivals = (0..20).map(|i| Duration::from_millis(4 * i)).collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+    simulation.register_product_type(ProductType::new("oblea").with_route(vec![0, 1, 0, 1]));
+    for id in 1..=20 {
+        simulation.set_product_type(id, "oblea");
+    }
+    simulation
+        .set_channel_backend(ChannelBackend::Semaphore { capacity: 1 })
+        .set_deadlock_timeout(Duration::from_millis(200));
+
+    match simulation.run() {
+        Err(SimulationError::Deadlocked { report, metrics }) => {
+            assert!(metrics.products.is_empty());
+            assert_eq!(report.blocked_stations().len(), 2);
+            // Cada estación retiene el producto que intenta enviar
+            assert!(report.stations.iter().all(|station| station.in_service.is_some()));
+            assert!(!report.in_flight.is_empty());
+            assert!(report.to_string().contains("[Grabado] bloqueada esperando espacio en el buffer siguiente"));
+        }
+        other => panic!("Se esperaba un interbloqueo, se obtuvo {:?}", other.map(|m| m.products.len())),
+    }
+}
+
+#[test]
+fn test_station_status_transitions_are_published() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Pintura", Duration::from_millis(40)),
+    ];
+    let mut simulation = Simulation::with_config(
+        stations,
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO; 4],
+    );
+    let transitions = Arc::new(Mutex::new(Vec::new()));
+    let recorder = Arc::clone(&transitions);
+    let board = StatusBoard::new();
+    simulation
+        .set_channel_backend(ChannelBackend::Bounded { capacity: 1 })
+        .add_station_observer(move |transition: &StatusTransition| {
+            recorder.lock().unwrap().push(transition.clone());
+        })
+        .add_station_observer(board.clone());
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    let transitions = transitions.lock().unwrap();
+    for report in &metrics.station_reports {
+        let own: Vec<&StatusTransition> = transitions
+            .iter()
+            .filter(|transition| transition.station_index == report.index)
+            .collect();
+        assert_eq!(own.last().map(|transition| transition.to), Some(StationStatus::Down));
+
+        // El tiempo en cada estado suma la vida completa de la estación
+        let time_in = |status| -> Duration {
+            own.iter()
+                .filter(|transition| transition.from == status)
+                .map(|transition| transition.time_in_previous)
+                .sum()
+        };
+        assert_eq!(time_in(StationStatus::Busy), report.busy_time);
+        assert_eq!(time_in(StationStatus::Idle), report.idle_time);
+        assert_eq!(time_in(StationStatus::Blocked), report.blocked_time);
+    }
+    // La estación rápida se bloquea esperando espacio en el buffer de la lenta
+    assert!(transitions
+        .iter()
+        .any(|transition| transition.station_index == 0 && transition.to == StationStatus::Blocked));
+    assert_eq!(
+        board.snapshot(),
+        vec![("Corte".to_string(), StationStatus::Down), ("Pintura".to_string(), StationStatus::Down)]
+    );
+}
+
+#[test]
+fn test_product_timestamps_are_relative_to_start() {
+    let stations = vec![
+        StationConfig::new("Corte", Duration::from_millis(10)),
+        StationConfig::new("Pintura", Duration::from_millis(10)),
+    ];
+    let arrivals = vec![Duration::ZERO, Duration::from_millis(30)];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::round_robin(Duration::from_millis(5)), arrivals);
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    for product in &metrics.products {
+        // Todos los momentos se miden desde el mismo origen
+        let (_, entry, _) = product.visit_times[0];
+        let (_, _, exit) = *product.visit_times.last().unwrap();
+        assert!(entry >= product.arrival_time);
+        assert_eq!(product.arrival_time + product.turnaround_time, exit);
+        assert_eq!(product.timeline.last().map(|span| span.end), Some(exit));
+        assert!(product.slices.iter().all(|&(_, start, end)| entry <= start && start <= end && end <= exit));
+    }
+    assert!(metrics.products[1].arrival_time >= Duration::from_millis(30));
+}
+
+/// Tiempo de CPU consumido por el hilo actual, en ticks del reloj del sistema.
+#[cfg(target_os = "linux")]
+fn thread_cpu_ticks() -> u64 {
+    let stat = std::fs::read_to_string("/proc/thread-self/stat").expect("No se pudo leer el estado del hilo");
+    let fields: Vec<&str> = stat[stat.rfind(')').unwrap() + 2..].split_whitespace().collect();
+    fields[11].parse::<u64>().unwrap() + fields[12].parse::<u64>().unwrap()
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_idle_station_uses_no_cpu() {
+    let stations = vec![StationConfig::new("Corte", Duration::from_millis(5))];
+    let arrivals = vec![Duration::ZERO, Duration::from_millis(400)];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), arrivals);
+
+    // Los observadores corren en el hilo de la estación: medimos su CPU
+    // desde que queda ociosa hasta que sale de ese estado
+    let idle_since = Arc::new(Mutex::new(None));
+    let idle_cpu = Arc::new(Mutex::new(0));
+    let (since, cpu) = (Arc::clone(&idle_since), Arc::clone(&idle_cpu));
+    simulation.add_station_observer(move |transition: &StatusTransition| {
+        let now = thread_cpu_ticks();
+        if let Some(start) = since.lock().unwrap().take() {
+            *cpu.lock().unwrap() += now - start;
+        }
+        if transition.to == StationStatus::Idle {
+            *since.lock().unwrap() = Some(now);
+        }
+    });
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    let report = &metrics.station_reports[0];
+    assert!(report.idle_time >= Duration::from_millis(350), "{:?}", report.idle_time);
+    // Menos de un tick del reloj del sistema (normalmente 10 ms) por espera
+    assert!(*idle_cpu.lock().unwrap() <= 1, "CPU ociosa: {} ticks", idle_cpu.lock().unwrap());
+    // Una espera por cada mensaje, no por cada milisegundo ocioso
+    assert!(report.wakeups <= 4, "{} esperas", report.wakeups);
+}
+
+#[test]
+fn test_jockeying_moves_waiting_products_to_a_shorter_branch() {
+    use assembly_line_simulator::{JockeyPolicy, RouteTarget, Splitter};
+
+    let millis = Duration::from_millis;
+    let cabinas = vec![RouteTarget::Station(1), RouteTarget::Station(2)];
+    let stations = vec![
+        StationConfig::new("Corte", millis(5)).with_splitter(Splitter::round_robin(cabinas)),
+        StationConfig::new("Pintura A", millis(60)).with_output(RouteTarget::Station(3)),
+        StationConfig::new("Pintura B", millis(10)),
+        StationConfig::new("Empaque", millis(5)),
+    ];
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 8]);
+    let baseline = simulation.run().expect("La simulación debe completarse");
+    let metrics = simulation
+        .set_jockeying(JockeyPolicy::shorter_queue(0))
+        .run()
+        .expect("La simulación debe completarse");
+
+    assert!(baseline.jockeying.is_none());
+    assert!(baseline.products.iter().all(|product| product.jockeys == 0));
+
+    // Los productos que esperan la cabina lenta se cambian a la rápida y
+    // siguen hacia Empaque como cualquier otro
+    let jockeying = metrics.jockeying.as_ref().expect("Debe haber un resumen de cambios de cola");
+    assert_eq!(metrics.products.len(), 8);
+    assert!(jockeying.branch_moves > 0);
+    assert_eq!(jockeying.unit_moves, 0);
+    assert_eq!(
+        jockeying.branch_moves,
+        metrics.station_reports[1].jockeyed + metrics.station_reports[2].jockeyed
+    );
+    assert_eq!(jockeying.moves, metrics.products.iter().map(|product| product.jockeys).sum::<usize>());
+    assert!(metrics.station_reports[2].products_processed > 4);
+    assert_eq!(
+        metrics.station_reports[1].products_processed + metrics.station_reports[2].products_processed,
+        8
+    );
+    for product in &metrics.products {
+        let stations: Vec<usize> = product.visit_times.iter().map(|(station, _, _)| *station).collect();
+        assert_eq!(stations.len(), 3);
+        assert_eq!((stations[0], stations[2]), (0, 3));
+    }
+
+    // La espera se reparte entre las cabinas, por lo que se dispersa menos
+    let change = jockeying.variance_change(&baseline).expect("La referencia tiene dispersión");
+    assert!(change < 0.0, "La varianza de la espera debe bajar: {:+.2}", change);
+    assert!(jockeying.average_wait < baseline.average_wait_time + baseline.average_resource_wait_time);
+
+    assert!(simulation.generate_report(&metrics).contains("=== CAMBIOS DE COLA ==="));
+    let json = MetricsCalculator::new().generate_json_report(&metrics);
+    assert!(json.contains("\"jockeying\":{\"policy\":\"a la cola más corta\""));
+    assert!(MetricsCalculator::new().generate_json_report(&baseline).contains("\"jockeying\":null"));
+}
+
+#[test]
+fn test_jockeying_moves_waiting_stations_between_resource_units() {
+    use assembly_line_simulator::JockeyPolicy;
+
+    let stations: Vec<StationConfig> = ["Corte", "Soldadura", "Pintura", "Ensamblaje", "Empaque"]
+        .into_iter()
+        .map(|name| StationConfig::new(name, Duration::from_millis(5)))
+        .collect();
+    let mut simulation = Simulation::with_config(stations, SchedulingAlgorithm::fcfs(), vec![Duration::ZERO; 12]);
+    simulation
+        .add_shared_resource(SharedResource::new("operario", 2).used_by(0).used_by(1).used_by(2).used_by(3).used_by(4))
+        .set_jockeying(JockeyPolicy::shorter_queue(0));
+    let metrics = simulation.run().expect("La simulación debe completarse");
+
+    // Cinco estaciones compiten por dos operarios, cada uno con su cola
+    let jockeying = metrics.jockeying.as_ref().expect("Debe haber un resumen de cambios de cola");
+    assert_eq!(metrics.products.len(), 12);
+    assert_eq!(jockeying.branch_moves, 0);
+    assert!(jockeying.unit_moves > 0);
+    assert_eq!(
+        jockeying.unit_moves,
+        metrics.station_reports.iter().map(|report| report.unit_jockeys).sum::<usize>()
+    );
+    assert_eq!(jockeying.moves, metrics.products.iter().map(|product| product.jockeys).sum::<usize>());
+    assert_eq!(
+        jockeying.products_moved,
+        metrics.products.iter().filter(|product| product.jockeys > 0).count()
+    );
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_async_backend_rejects_unsupported_options() {
+    use assembly_line_simulator::{ChannelBackend, ConfigError, NoWork, SimulationError};
+
+    type Configure = fn(&mut Simulation);
+
+    let configurations: Vec<(&str, Configure)> = vec![
+        ("fuentes de llegada infinitas", |simulation| {
+            simulation
+                .add_arrival_source(ArrivalSource::new(
+                    "poisson",
+                    ArrivalProcess::Exponential {
+                        mean_interarrival: Duration::from_millis(20),
+                        seed: 7,
+                        count: None,
+                    },
+                    ProductSpec::default(),
+                ))
+                .set_stop_condition(StopCondition::Completions(5));
+        }),
+        ("condiciones de parada", |simulation| {
+            simulation.set_stop_condition(StopCondition::Completions(1));
+        }),
+        ("completion_timeout", |simulation| {
+            simulation.set_completion_timeout(Duration::from_secs(1));
+        }),
+        ("watchdog_timeout", |simulation| {
+            simulation.set_watchdog_timeout(Duration::from_secs(1));
+        }),
+        ("deadlock_timeout", |simulation| {
+            simulation.set_deadlock_timeout(Duration::from_secs(1));
+        }),
+        ("channel_backend", |simulation| {
+            simulation.set_channel_backend(ChannelBackend::Bounded { capacity: 1 });
+        }),
+        ("work_model", |simulation| {
+            simulation.set_work_model(NoWork);
+        }),
+    ];
+    for (expected, configure) in configurations {
+        let mut simulation = Simulation::with_config(
+            vec![StationConfig::new("Corte", Duration::from_millis(5))],
+            SchedulingAlgorithm::fcfs(),
+            vec![Duration::ZERO; 2],
+        );
+        configure(&mut simulation);
+        for result in [simulation.run_virtual(), simulation.run_async(), simulation.run_pooled(2)] {
+            match result {
+                Err(SimulationError::InvalidConfig { error: ConfigError::UnsupportedByAsyncBackend { option }, .. }) => {
+                    assert!(option.contains(expected), "'{}' no menciona '{}'", option, expected);
+                }
+                other => panic!("'{}' debía rechazarse, no {:?}", expected, other.map(|metrics| metrics.products.len())),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_pooled_backend_rejects_an_empty_pool() {
+    use assembly_line_simulator::{ConfigError, SimulationError};
+
+    let simulation = Simulation::with_config(
+        vec![StationConfig::new("Corte", Duration::from_millis(5))],
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO; 2],
+    );
+    match simulation.run_pooled(0) {
+        Err(SimulationError::InvalidConfig { error, metrics }) => {
+            assert_eq!(error, ConfigError::NoWorkers);
+            assert!(metrics.products.is_empty());
+        }
+        other => panic!("un grupo sin hilos debía rechazarse, no {:?}", other.map(|metrics| metrics.products.len())),
+    }
+    assert_eq!(simulation.run_pooled(1).expect("Un hilo basta").products.len(), 2);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_async_backend_rejects_jockeying() {
+    use assembly_line_simulator::{ConfigError, JockeyPolicy, SimulationError};
+
+    let mut simulation = Simulation::with_config(
+        vec![StationConfig::new("Corte", Duration::from_millis(5))],
+        SchedulingAlgorithm::fcfs(),
+        vec![Duration::ZERO; 2],
+    );
+    simulation.set_jockeying(JockeyPolicy::shorter_queue(0));
+    for result in [simulation.run_virtual(), simulation.run_async(), simulation.run_pooled(2)] {
+        match result {
+            Err(SimulationError::InvalidConfig { error: ConfigError::UnsupportedByAsyncBackend { option }, .. }) => {
+                assert!(option.contains("jockeying"));
+            }
+            other => panic!("se esperaba un error de configuración, no {:?}", other.map(|metrics| metrics.products.len())),
+        }
+    }
+}